@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Differential execution across versions of the execution layer.
+//!
+//! Every version of the execution layer that a network might run must produce exactly the same
+//! effects for the same transaction on the same state, otherwise validators running different
+//! binaries across an upgrade would fork. [`Simulacrum::execute_transaction_differential`]
+//! executes a transaction with the current execution layer and with a baseline version on
+//! identical state, and fails with a [`Divergence`] if their effects differ.
+//!
+//! [`Simulacrum::execute_transaction_differential`]: crate::Simulacrum::execute_transaction_differential
+
+use std::fmt;
+
+use anyhow::Result;
+use sui_types::{
+    base_types::TransactionDigest, digests::TransactionEffectsDigest, effects::TransactionEffects,
+    message_envelope::Message, signature::VerifyParams, transaction::Transaction,
+};
+
+use crate::Simulacrum;
+
+/// Effects of the same transaction, executed on the same state by two versions of the execution
+/// layer, that do not match.
+#[derive(Debug)]
+pub struct Divergence {
+    pub transaction: TransactionDigest,
+    pub candidate_version: u64,
+    pub candidate: Box<TransactionEffects>,
+    pub baseline_version: u64,
+    pub baseline: Box<TransactionEffects>,
+}
+
+impl<R> Simulacrum<R> {
+    /// Executes the provided Transaction like [`Simulacrum::execute_transaction`], and also with
+    /// the `baseline_version` of the execution layer, against the same state.
+    ///
+    /// If the effects from the two versions are identical, the transaction is committed and its
+    /// effects returned, otherwise a [`Divergence`] is returned and the chain is left untouched.
+    pub fn execute_transaction_differential(
+        &mut self,
+        transaction: Transaction,
+        baseline_version: u64,
+    ) -> Result<TransactionEffects> {
+        // This only supports traditional authenticators and not zklogin
+        let transaction = transaction.verify(&VerifyParams::default())?;
+
+        let (inner_temporary_store, effects, _execution_error_opt) = self
+            .epoch_state
+            .execute_transaction(&self.store, &self.deny_config, &transaction)?;
+
+        let (baseline_config, baseline_executor) =
+            self.epoch_state.execution_layer(baseline_version)?;
+        let (_, baseline_effects, _) = self.epoch_state.execute_transaction_with(
+            &baseline_config,
+            baseline_executor.as_ref(),
+            &self.store,
+            &self.deny_config,
+            &transaction,
+        )?;
+
+        if effects != baseline_effects {
+            return Err(Divergence {
+                transaction: *transaction.digest(),
+                candidate_version: self.epoch_state.protocol_config().execution_version(),
+                candidate: Box::new(effects),
+                baseline_version,
+                baseline: Box::new(baseline_effects),
+            }
+            .into());
+        }
+
+        self.commit_transaction(transaction, inner_temporary_store, effects.clone());
+        Ok(effects)
+    }
+}
+
+impl Divergence {
+    pub fn candidate_digest(&self) -> TransactionEffectsDigest {
+        self.candidate.digest()
+    }
+
+    pub fn baseline_digest(&self) -> TransactionEffectsDigest {
+        self.baseline.digest()
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Execution versions {} and {} diverge on transaction {}: effects {} != {}",
+            self.candidate_version,
+            self.baseline_version,
+            self.transaction,
+            self.candidate_digest(),
+            self.baseline_digest(),
+        )?;
+        writeln!(
+            f,
+            "version {} effects: {:#?}",
+            self.candidate_version, self.candidate
+        )?;
+        write!(
+            f,
+            "version {} effects: {:#?}",
+            self.baseline_version, self.baseline
+        )
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+#[cfg(test)]
+mod tests {
+    use fastcrypto::traits::KeyPair;
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+    use shared_crypto::intent::Intent;
+    use sui_types::{
+        base_types::SuiAddress,
+        crypto::AccountKeyPair,
+        effects::TransactionEffectsAPI,
+        object::Object,
+        programmable_transaction_builder::ProgrammableTransactionBuilder,
+        transaction::{GasData, TransactionData, TransactionKind},
+    };
+
+    use super::*;
+
+    /// Number of transactions to generate, which CI raises to run a longer workload.
+    const TRANSACTIONS_ENV: &str = "SIMULACRUM_DIFFERENTIAL_TRANSACTIONS";
+    const DEFAULT_TRANSACTIONS: usize = 100;
+
+    const GAS_BUDGET: u64 = 50_000_000;
+
+    fn gas_coins(sim: &Simulacrum<StdRng>, owner: SuiAddress) -> Vec<Object> {
+        let mut coins: Vec<_> = sim
+            .store()
+            .owned_objects(owner)
+            .filter(|object| object.is_gas_coin())
+            .cloned()
+            .collect();
+        // Largest coin first, so that it can be used to pay for gas.
+        coins.sort_by_key(|coin| std::cmp::Reverse(coin.get_coin_value_unsafe()));
+        coins
+    }
+
+    /// A random transaction from `sender`, exercising transfers, splits and merges of coins, as
+    /// well as failed executions.
+    fn generate_transaction(
+        sim: &mut Simulacrum<StdRng>,
+        sender: SuiAddress,
+        key: &AccountKeyPair,
+        recipients: &[SuiAddress],
+    ) -> Transaction {
+        let coins = gas_coins(sim, sender);
+        let gas = coins[0].compute_object_reference();
+        let balance = coins[0].get_coin_value_unsafe() - GAS_BUDGET;
+        let rng = sim.rng();
+        let recipient = *recipients.choose(rng).unwrap();
+
+        let mut payment = vec![gas];
+        let mut builder = ProgrammableTransactionBuilder::new();
+        match rng.gen_range(0..5) {
+            0 => builder.transfer_sui(recipient, Some(rng.gen_range(1..=balance / 100))),
+            1 => {
+                let count = rng.gen_range(1..=3);
+                let recipients = recipients.choose_multiple(rng, count).cloned().collect();
+                let amounts = (0..count)
+                    .map(|_| rng.gen_range(1..=balance / 100))
+                    .collect();
+                builder.pay_sui(recipients, amounts).unwrap();
+            }
+            // Merge all of the sender's coins into the gas coin.
+            2 => {
+                payment.extend(
+                    coins[1..]
+                        .iter()
+                        .map(|coin| coin.compute_object_reference()),
+                );
+                builder.pay_all_sui(sender);
+            }
+            3 if coins.len() > 1 => {
+                let coin = coins[1..].choose(rng).unwrap().compute_object_reference();
+                builder.transfer_object(recipient, coin).unwrap();
+            }
+            // Fails during execution, for lack of funds.
+            _ => builder.transfer_sui(recipient, Some(balance + GAS_BUDGET + 1)),
+        }
+
+        let kind = TransactionKind::ProgrammableTransaction(builder.finish());
+        let gas_data = GasData {
+            payment,
+            owner: sender,
+            price: sim.reference_gas_price(),
+            budget: GAS_BUDGET,
+        };
+        let tx_data = TransactionData::new_with_gas_data(kind, sender, gas_data);
+        Transaction::from_data_and_signer(tx_data, Intent::sui_transaction(), vec![key])
+    }
+
+    /// Runs a generated workload against the latest execution layer and the previous cut, which
+    /// must agree on the effects of every transaction.
+    #[test]
+    fn latest_matches_previous_cut() {
+        let transactions = std::env::var(TRANSACTIONS_ENV)
+            .map(|count| count.parse().expect("Invalid transaction count"))
+            .unwrap_or(DEFAULT_TRANSACTIONS);
+
+        let mut sim = Simulacrum::new_with_rng(StdRng::seed_from_u64(0));
+        let latest = sim.epoch_state.protocol_config().execution_version();
+        let previous = latest
+            .checked_sub(1)
+            .expect("No previous execution layer cut");
+
+        let accounts: Vec<_> = sim
+            .keystore()
+            .accounts()
+            .map(|(address, key)| (*address, key.copy()))
+            .collect();
+        let mut recipients: Vec<_> = accounts.iter().map(|(address, _)| *address).collect();
+        recipients.extend((0..4).map(|_| SuiAddress::generate(sim.rng())));
+
+        let mut failures = 0;
+        for i in 0..transactions {
+            let (sender, key) = accounts.choose(sim.rng()).unwrap();
+            let tx = generate_transaction(&mut sim, *sender, key, &recipients);
+            let effects = sim
+                .execute_transaction_differential(tx, previous)
+                .unwrap_or_else(|e| panic!("{e}"));
+            if !effects.status().is_ok() {
+                failures += 1;
+            }
+
+            if i % 10 == 9 {
+                sim.create_checkpoint();
+            }
+            if i % 50 == 49 {
+                sim.advance_epoch();
+            }
+        }
+
+        assert!(failures > 0, "Workload should exercise failed executions");
+    }
+}
@@ -3,10 +3,11 @@
 
 use std::{collections::HashSet, sync::Arc};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_execution::Executor;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_transaction_checks::verifier_cache::VerifierCache;
 use sui_types::{
     committee::{Committee, EpochId},
     effects::TransactionEffects,
@@ -28,6 +29,7 @@ pub struct EpochState {
     protocol_config: ProtocolConfig,
     limits_metrics: Arc<LimitsMetrics>,
     bytecode_verifier_metrics: Arc<BytecodeVerifierMetrics>,
+    verifier_cache: VerifierCache,
     executor: Arc<dyn Executor + Send + Sync>,
     /// A counter that advances each time we advance the clock in order to ensure that each update
     /// txn has a unique digest. This is reset on epoch changes
@@ -51,6 +53,7 @@ impl EpochState {
             protocol_config,
             limits_metrics,
             bytecode_verifier_metrics,
+            verifier_cache: VerifierCache::default(),
             executor,
             next_consensus_round: 0,
         }
@@ -91,25 +94,66 @@ impl EpochState {
         InnerTemporaryStore,
         TransactionEffects,
         Result<(), sui_types::error::ExecutionError>,
+    )> {
+        self.execute_transaction_with(
+            &self.protocol_config,
+            self.executor.as_ref(),
+            store,
+            deny_config,
+            transaction,
+        )
+    }
+
+    /// The protocol config of this epoch with its execution version replaced by
+    /// `execution_version`, and an executor for that version of the execution layer.
+    pub fn execution_layer(
+        &self,
+        execution_version: u64,
+    ) -> Result<(ProtocolConfig, Arc<dyn Executor + Send + Sync>)> {
+        let max_version = ProtocolConfig::get_for_max_version_UNSAFE().execution_version();
+        if execution_version > max_version && execution_version != sui_execution::VM_REWORK {
+            bail!("Unsupported execution version {execution_version}");
+        }
+
+        let mut protocol_config = self.protocol_config.clone();
+        protocol_config.set_execution_version_for_testing(execution_version);
+        let executor = sui_execution::executor(&protocol_config, false, true)?;
+        Ok((protocol_config, executor))
+    }
+
+    /// Executes `transaction` against `store` using `executor`, configured by `protocol_config`,
+    /// rather than this epoch's own execution layer.
+    pub fn execute_transaction_with(
+        &self,
+        protocol_config: &ProtocolConfig,
+        executor: &(dyn Executor + Send + Sync),
+        store: &InMemoryStore,
+        deny_config: &TransactionDenyConfig,
+        transaction: &VerifiedTransaction,
+    ) -> Result<(
+        InnerTemporaryStore,
+        TransactionEffects,
+        Result<(), sui_types::error::ExecutionError>,
     )> {
         // Run the transaction input checks that would run when submitting the txn to a validator
         // for signing
         let (gas_status, input_objects) = sui_transaction_checks::check_transaction_input(
             store,
-            &self.protocol_config,
+            protocol_config,
             self.epoch_start_state.reference_gas_price(),
             self.epoch(),
             transaction.data().transaction_data(),
             deny_config,
             &self.bytecode_verifier_metrics,
+            &self.verifier_cache,
         )?;
 
         let tx_digest = *transaction.digest();
         let transaction_data = transaction.data().transaction_data();
         let (kind, signer, gas) = transaction_data.execution_parts();
-        Ok(self.executor.execute_transaction_to_effects(
+        Ok(executor.execute_transaction_to_effects(
             store,
-            &self.protocol_config,
+            protocol_config,
             self.limits_metrics.clone(),
             false,           // enable_expensive_checks
             &HashSet::new(), // certificate_deny_set
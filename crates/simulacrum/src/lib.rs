@@ -29,11 +29,13 @@ use sui_types::{
 };
 
 use self::checkpoint_builder::CheckpointBuilder;
+pub use self::differential::Divergence;
 use self::epoch_state::EpochState;
 pub use self::store::InMemoryStore;
 use self::store::KeyStore;
 
 mod checkpoint_builder;
+mod differential;
 mod epoch_state;
 mod store;
 
@@ -131,6 +133,18 @@ impl<R> Simulacrum<R> {
             .epoch_state
             .execute_transaction(&self.store, &self.deny_config, &transaction)?;
 
+        self.commit_transaction(transaction, inner_temporary_store, effects.clone());
+        Ok(effects)
+    }
+
+    /// Applies the outcome of executing `transaction` to the store, and enqueues it to be included
+    /// in the next checkpoint.
+    fn commit_transaction(
+        &mut self,
+        transaction: VerifiedTransaction,
+        inner_temporary_store: InnerTemporaryStore,
+        effects: TransactionEffects,
+    ) {
         let InnerTemporaryStore {
             written, events, ..
         } = inner_temporary_store;
@@ -144,9 +158,7 @@ impl<R> Simulacrum<R> {
 
         // Insert into checkpoint builder
         self.checkpoint_builder
-            .push_transaction(transaction, effects.clone());
-
-        Ok(effects)
+            .push_transaction(transaction, effects);
     }
 
     /// Creates the next Checkpoint using the Transactions enqueued since the last checkpoint was
@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests TransferObjects consulting the transfer hooks of objects' types
+
+//# init --addresses test=0x0 malformed=0x0 --accounts A B
+
+//# publish
+module test::bound {
+    use sui::object::{Self, UID};
+    use sui::tx_context::{Self, TxContext};
+
+    // Can only be transferred back to its creator
+    struct Bound has key, store {
+        id: UID,
+        creator: address,
+    }
+
+    public fun new(ctx: &mut TxContext): Bound {
+        Bound { id: object::new(ctx), creator: tx_context::sender(ctx) }
+    }
+
+    fun transfer_hook(bound: &mut Bound, recipient: address, _ctx: &TxContext): bool {
+        recipient == bound.creator
+    }
+}
+
+module test::art {
+    use sui::object::{Self, UID};
+    use sui::tx_context::TxContext;
+
+    // Can only be transferred once a royalty has been paid, for each transfer
+    struct Art<phantom T> has key, store {
+        id: UID,
+        paid: bool,
+        transfers: u64,
+    }
+
+    public fun new<T>(ctx: &mut TxContext): Art<T> {
+        Art { id: object::new(ctx), paid: false, transfers: 0 }
+    }
+
+    public fun pay_royalty<T>(art: &mut Art<T>) {
+        art.paid = true;
+    }
+
+    fun transfer_hook<T>(art: &mut Art<T>, _recipient: address, _ctx: &TxContext): bool {
+        if (!art.paid) return false;
+        art.paid = false;
+        art.transfers = art.transfers + 1;
+        true
+    }
+}
+
+module test::other {
+    use sui::object::{Self, UID};
+    use sui::tx_context::TxContext;
+
+    struct Obj has key, store {
+        id: UID,
+    }
+
+    struct Other has key, store {
+        id: UID,
+    }
+
+    public fun other(ctx: &mut TxContext): Other {
+        Other { id: object::new(ctx) }
+    }
+
+    fun transfer_hook(_obj: &mut Obj, _recipient: address, _ctx: &TxContext): bool {
+        true
+    }
+}
+
+// rejected by the hook
+//# programmable --sender A --inputs @B
+//> 0: test::bound::new();
+//> TransferObjects([Result(0)], Input(0));
+
+// allowed by the hook
+//# programmable --sender A --inputs @A
+//> 0: test::bound::new();
+//> TransferObjects([Result(0)], Input(0));
+
+// rejected until the royalty is paid, including in a vector
+//# programmable --sender A --inputs @B
+//> 0: test::art::new<u64>();
+//> 1: MakeMoveVec([Result(0)]);
+//> TransferObjects([Result(1)], Input(0));
+
+// changes made by the hook are kept
+//# programmable --sender A --inputs @B
+//> 0: test::art::new<u64>();
+//> test::art::pay_royalty<u64>(Result(0));
+//> TransferObjects([Result(0)], Input(0));
+
+//# view-object 5,0
+
+// the hook is for another type
+//# programmable --sender A --inputs @B
+//> 0: test::other::other();
+//> TransferObjects([Result(0)], Input(0));
+
+// malformed hooks are rejected at publish
+//# publish
+module malformed::m {
+    use sui::object::UID;
+
+    struct Obj has key, store {
+        id: UID,
+    }
+
+    fun transfer_hook(_obj: &mut Obj, _recipient: address): bool {
+        true
+    }
+}
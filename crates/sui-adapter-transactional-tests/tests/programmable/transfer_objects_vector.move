@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests TransferObjects with vectors of objects
+
+//# init --addresses test=0x0 --accounts A B
+
+//# publish
+module test::m1 {
+    use sui::object::{Self, UID};
+    use sui::tx_context::TxContext;
+    use std::vector;
+
+    struct Pub has key, store {
+        id: UID,
+        value: u64,
+    }
+
+    struct Cap {}
+
+    struct Cup<T> has key, store {
+        id: UID,
+        value: T,
+    }
+
+    public fun new(ctx: &mut TxContext): Pub {
+        Pub { id: object::new(ctx), value: 112 }
+    }
+
+    public fun cup<T>(value: T, ctx: &mut TxContext): Cup<T> {
+        Cup { id: object::new(ctx), value }
+    }
+
+    public fun cap(): Cap {
+        Cap {}
+    }
+
+    public fun pubs(v: vector<Pub>) {
+        while (!vector::is_empty(&v)) {
+            let Pub { id, value: _ } = vector::pop_back(&mut v);
+            object::delete(id);
+        };
+        vector::destroy_empty(v);
+    }
+}
+
+// vector of objects
+//# programmable --sender A --inputs @B
+//> 0: test::m1::new();
+//> 1: test::m1::new();
+//> 2: test::m1::new();
+//> 3: MakeMoveVec([Result(0), Result(1), Result(2)]);
+//> TransferObjects([Result(3)], Input(0));
+
+//# view-object 2,0
+
+// objects and vectors, including an empty one
+//# programmable --sender A --inputs @B
+//> 0: test::m1::new();
+//> 1: test::m1::new();
+//> 2: MakeMoveVec<test::m1::Pub>([Result(1)]);
+//> 3: MakeMoveVec<test::m1::Pub>([]);
+//> TransferObjects([Result(0), Result(2), Result(3)], Input(0));
+
+// not a vector of objects
+//# programmable --sender A --inputs @B
+//> 0: test::m1::cap();
+//> 1: MakeMoveVec<test::m1::Cap>([Result(0)]);
+//> TransferObjects([Result(1)], Input(0));
+
+// not a vector of objects (but sneaky)
+//# programmable --sender A --inputs @B
+//> 0: test::m1::cap();
+// Cup<Cap> is not an object since Cap does not have store
+//> 1: test::m1::cup<test::m1::Cap>(Result(0));
+//> 2: MakeMoveVec<test::m1::Cup<test::m1::Cap>>([Result(1)]);
+//> TransferObjects([Result(2)], Input(0));
+
+// vector of objects, transferred by the framework
+//# programmable --sender A --inputs @B
+//> 0: test::m1::new();
+//> 1: test::m1::new();
+//> 2: MakeMoveVec([Result(0), Result(1)]);
+//> sui::transfer::public_transfer_vec<test::m1::Pub>(Result(2), Input(0));
@@ -0,0 +1,20 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests results that no later command uses, checked as soon as they are produced
+
+//# init --early-unused-result-check true
+
+// unused result with drop
+//# programmable --inputs 0u256
+//> sui::address::from_u256(Input(0))
+
+// unused result without drop
+//# programmable --inputs 0u256
+//> 0: sui::object::new();
+//> sui::address::from_u256(Input(0))
+
+// result used by a later command
+//# programmable
+//> 0: sui::object::new();
+//> sui::object::delete(Result(0))
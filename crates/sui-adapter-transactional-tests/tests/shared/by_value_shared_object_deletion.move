@@ -0,0 +1,62 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// tests that, with shared object deletion enabled, shared objects taken by value can be deleted or
+// re-shared, but not transferred, frozen or wrapped
+
+//# init --addresses t2=0x0 --accounts A --shared-object-deletion true
+
+//# publish
+
+module t2::o2 {
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::TxContext;
+
+    struct Obj2 has key, store {
+        id: UID,
+    }
+
+    public entry fun create(ctx: &mut TxContext) {
+        let o = Obj2 { id: object::new(ctx) };
+        transfer::public_share_object(o)
+    }
+
+    public entry fun consume_o2(o2: Obj2) {
+        let Obj2 { id } = o2;
+        object::delete(id);
+    }
+}
+
+//# run t2::o2::create
+
+//# run t2::o2::create
+
+//# run t2::o2::create
+
+//# run t2::o2::create
+
+//# run t2::o2::create
+
+// delete
+//# run t2::o2::consume_o2 --args object(2,0)
+
+// transfer
+//# programmable --inputs object(3,0) @A
+//> sui::transfer::public_transfer<t2::o2::Obj2>(Input(0), Input(1))
+
+// freeze
+//# programmable --inputs object(4,0)
+//> sui::transfer::public_freeze_object<t2::o2::Obj2>(Input(0))
+
+// wrap
+//# programmable --inputs object(5,0) 0u64 @A
+//> 0: sui::bag::new();
+//> sui::bag::add<u64, t2::o2::Obj2>(Result(0), Input(1), Input(0));
+//> sui::transfer::public_transfer<sui::bag::Bag>(Result(0), Input(2))
+
+// re-share
+//# programmable --inputs object(6,0)
+//> sui::transfer::public_share_object<t2::o2::Obj2>(Input(0))
+
+//# view-object 6,0
@@ -653,6 +653,7 @@ fn get_owner_type(object: &Object) -> OwnerType {
         Owner::ObjectOwner(_) => OwnerType::ObjectOwner,
         Owner::Shared { .. } => OwnerType::Shared,
         Owner::Immutable => OwnerType::Immutable,
+        Owner::ConsensusV2 { .. } => OwnerType::ConsensusV2,
     }
 }
 
@@ -662,6 +663,9 @@ fn get_owner_address(object: &Object) -> Option<String> {
         Owner::ObjectOwner(address) => Some(address.to_string()),
         Owner::Shared { .. } => None,
         Owner::Immutable => None,
+        Owner::ConsensusV2 { authenticator, .. } => {
+            Some(authenticator.as_single_owner().to_string())
+        }
     }
 }
 
@@ -130,6 +130,7 @@ pub enum OwnerType {
     ObjectOwner,
     Shared,
     Immutable,
+    ConsensusV2,
 }
 
 // Object information.
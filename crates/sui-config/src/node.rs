@@ -20,6 +20,7 @@ use std::time::Duration;
 use std::usize;
 use sui_keys::keypair_file::{read_authority_keypair_from_file, read_keypair_from_file};
 use sui_protocol_config::SupportedProtocolVersions;
+use sui_storage::group_commit::GroupCommitConfig;
 use sui_storage::object_store::ObjectStoreConfig;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::crypto::AuthorityPublicKeyBytes;
@@ -27,6 +28,7 @@ use sui_types::crypto::KeypairTraits;
 use sui_types::crypto::NetworkKeyPair;
 use sui_types::crypto::SuiKeyPair;
 use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair};
+use sui_types::digests::TransactionDigest;
 use sui_types::multiaddr::Multiaddr;
 use tracing::info;
 
@@ -155,6 +157,20 @@ pub struct NodeConfig {
 
     #[serde(default = "default_jwk_fetch_interval_seconds")]
     pub jwk_fetch_interval_seconds: u64,
+
+    /// Capture the objects written by selected transactions, for debugging. Disabled if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_capture_config: Option<ExecutionCaptureConfig>,
+
+    /// Limits on the number of certificates executed concurrently, by class of transaction.
+    /// Each limit defaults to the number of cpus if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_concurrency_config: Option<ExecutionConcurrencyConfig>,
+
+    /// Write the outputs of transactions executed concurrently to the database together, as a
+    /// single write. Disabled if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_commit_config: Option<GroupCommitConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -328,6 +344,11 @@ pub struct ConsensusConfig {
     /// on consensus latency estimates.
     pub submit_delay_step_override_millis: Option<u64>,
 
+    /// How far, in milliseconds, the timestamp of a consensus commit can be from the local clock
+    /// before a warning is logged and the skew alert metric is incremented.
+    /// Default to 10 seconds.
+    pub commit_timestamp_skew_alert_threshold_ms: Option<u64>,
+
     pub narwhal_config: ConsensusParameters,
 }
 
@@ -349,6 +370,13 @@ impl ConsensusConfig {
             .map(Duration::from_millis)
     }
 
+    pub fn commit_timestamp_skew_alert_threshold(&self) -> Duration {
+        Duration::from_millis(
+            self.commit_timestamp_skew_alert_threshold_ms
+                .unwrap_or(10_000),
+        )
+    }
+
     pub fn narwhal_config(&self) -> &ConsensusParameters {
         &self.narwhal_config
     }
@@ -833,6 +861,48 @@ pub struct StateDebugDumpConfig {
     pub dump_file_directory: Option<PathBuf>,
 }
 
+/// Selects the transactions whose written objects are captured in full after their execution, to
+/// debug their state transitions after the fact. A transaction is captured if it matches any of
+/// the lists.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionCaptureConfig {
+    /// Transactions sent by these addresses.
+    #[serde(default)]
+    pub senders: Vec<SuiAddress>,
+    /// Transactions calling functions in these packages.
+    #[serde(default)]
+    pub packages: Vec<ObjectID>,
+    /// These transactions.
+    #[serde(default)]
+    pub digests: Vec<TransactionDigest>,
+    /// How long the objects of a captured transaction are kept, in seconds.
+    #[serde(default = "default_execution_capture_ttl_secs")]
+    pub ttl_secs: u64,
+}
+
+fn default_execution_capture_ttl_secs() -> u64 {
+    // One week
+    7 * 24 * 60 * 60
+}
+
+/// Limits on the number of certificates executed concurrently for each class of transaction, so
+/// that a class cannot starve the others of execution slots. Unset limits default to the number of
+/// cpus.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionConcurrencyConfig {
+    /// Transactions only taking owned objects as input, executed on the fast path.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owned_object_limit: Option<usize>,
+    /// Transactions taking shared objects as input, executed once sequenced by consensus.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_object_limit: Option<usize>,
+    /// System transactions, e.g. consensus commit prologues and end of epoch transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_limit: Option<usize>,
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
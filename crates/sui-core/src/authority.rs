@@ -60,7 +60,8 @@ use sui_archival::reader::ArchiveReaderBalancer;
 use sui_config::certificate_deny_config::CertificateDenyConfig;
 use sui_config::genesis::Genesis;
 use sui_config::node::{
-    AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
+    AuthorityStorePruningConfig, DBCheckpointConfig, ExecutionConcurrencyConfig,
+    ExpensiveSafetyCheckConfig,
 };
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_framework::{BuiltInFramework, SystemPackage};
@@ -75,6 +76,7 @@ use sui_storage::indexes::{CoinInfo, ObjectIndexChanges};
 use sui_storage::key_value_store::{TransactionKeyValueStore, TransactionKeyValueStoreTrait};
 use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
 use sui_storage::IndexStore;
+use sui_transaction_checks::verifier_cache::VerifierCache;
 use sui_types::authenticator_state::get_authenticator_state;
 use sui_types::committee::{EpochId, ProtocolVersion};
 use sui_types::crypto::{default_hash, AuthoritySignInfo, Signer};
@@ -129,6 +131,7 @@ use crate::authority::authority_store::{ExecutionLockReadGuard, InputKey, Object
 use crate::authority::authority_store_pruner::AuthorityStorePruner;
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
+use crate::authority::execution_capture::ExecutionCaptureStore;
 use crate::checkpoints::checkpoint_executor::CheckpointExecutor;
 use crate::checkpoints::CheckpointStore;
 use crate::epoch::committee_store::CommitteeStore;
@@ -169,6 +172,7 @@ pub mod authority_store_pruner;
 pub mod authority_store_tables;
 pub mod authority_store_types;
 pub mod epoch_start_configuration;
+pub mod execution_capture;
 pub mod test_authority_builder;
 
 pub(crate) mod authority_notify_read;
@@ -203,6 +207,8 @@ pub struct AuthorityMetrics {
     pub(crate) transaction_manager_num_enqueued_certificates: IntCounterVec,
     pub(crate) transaction_manager_num_missing_objects: IntGauge,
     pub(crate) transaction_manager_num_pending_certificates: IntGauge,
+    pub(crate) transaction_manager_recovered_certificates: IntGaugeVec,
+    pub(crate) transaction_manager_oldest_recovered_certificate_age_ms: IntGauge,
     pub(crate) transaction_manager_num_executing_certificates: IntGauge,
     pub(crate) transaction_manager_num_ready: IntGauge,
     pub(crate) transaction_manager_object_cache_size: IntGauge,
@@ -213,9 +219,14 @@ pub struct AuthorityMetrics {
     pub(crate) transaction_manager_package_cache_hits: IntCounter,
     pub(crate) transaction_manager_package_cache_misses: IntCounter,
     pub(crate) transaction_manager_package_cache_evictions: IntCounter,
+    pub(crate) transaction_manager_shared_object_wait_latency: Histogram,
+    pub(crate) transaction_manager_hot_object_queued_certificates: IntGaugeVec,
+    pub(crate) transaction_manager_hot_object_wait_ms: IntGaugeVec,
 
     pub(crate) execution_driver_executed_transactions: IntCounter,
     pub(crate) execution_driver_dispatch_queue: IntGauge,
+    pub(crate) execution_driver_class_pending: IntGaugeVec,
+    pub(crate) execution_driver_class_executing: IntGaugeVec,
 
     pub(crate) skipped_consensus_txns: IntCounter,
     pub(crate) skipped_consensus_txns_cache_hit: IntCounter,
@@ -236,6 +247,9 @@ pub struct AuthorityMetrics {
     pub consensus_handler_scores: IntGaugeVec,
     pub consensus_committed_subdags: IntCounterVec,
     pub consensus_committed_certificates: IntCounterVec,
+    pub consensus_commit_timestamp_skew_ms: IntGauge,
+    pub consensus_commit_timestamp_skew_alerts: IntCounterVec,
+    pub consensus_commit_timestamp_clamped: IntCounter,
 
     pub limits_metrics: Arc<LimitsMetrics>,
 
@@ -401,6 +415,19 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            transaction_manager_recovered_certificates: register_int_gauge_vec_with_registry!(
+                "transaction_manager_recovered_certificates",
+                "Number of certificates found in the pending execution table on startup, by how far they got through execution before the node stopped",
+                &["state"],
+                registry,
+            )
+            .unwrap(),
+            transaction_manager_oldest_recovered_certificate_age_ms: register_int_gauge_with_registry!(
+                "transaction_manager_oldest_recovered_certificate_age_ms",
+                "How long the oldest certificate found in the pending execution table on startup had been waiting to execute",
+                registry,
+            )
+            .unwrap(),
             transaction_manager_num_executing_certificates: register_int_gauge_with_registry!(
                 "transaction_manager_num_executing_certificates",
                 "Number of executing certificates, including queued and actually running certificates",
@@ -461,6 +488,27 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            transaction_manager_shared_object_wait_latency: register_histogram_with_registry!(
+                "transaction_manager_shared_object_wait_latency",
+                "Time certificates spend in TransactionManager waiting for a shared object to become available",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            transaction_manager_hot_object_queued_certificates: register_int_gauge_vec_with_registry!(
+                "transaction_manager_hot_object_queued_certificates",
+                "Number of certificates that queued for each of the most contended shared objects, over the recent window",
+                &["object_id"],
+                registry,
+            )
+            .unwrap(),
+            transaction_manager_hot_object_wait_ms: register_int_gauge_vec_with_registry!(
+                "transaction_manager_hot_object_wait_ms",
+                "Total time certificates waited for each of the most contended shared objects, over the recent window",
+                &["object_id"],
+                registry,
+            )
+            .unwrap(),
             execution_driver_executed_transactions: register_int_counter_with_registry!(
                 "execution_driver_executed_transactions",
                 "Cumulative number of transaction executed by execution driver",
@@ -473,6 +521,20 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            execution_driver_class_pending: register_int_gauge_vec_with_registry!(
+                "execution_driver_class_pending",
+                "Number of transactions waiting for an execution slot, by class of transaction",
+                &["class"],
+                registry,
+            )
+            .unwrap(),
+            execution_driver_class_executing: register_int_gauge_vec_with_registry!(
+                "execution_driver_class_executing",
+                "Number of transactions being executed, by class of transaction",
+                &["class"],
+                registry,
+            )
+            .unwrap(),
             skipped_consensus_txns: register_int_counter_with_registry!(
                 "skipped_consensus_txns",
                 "Total number of consensus transactions skipped",
@@ -553,6 +615,25 @@ impl AuthorityMetrics {
                 registry,
             )
                 .unwrap(),
+            consensus_commit_timestamp_skew_ms: register_int_gauge_with_registry!(
+                "consensus_commit_timestamp_skew_ms",
+                "Local time minus the timestamp of the latest consensus commit, in ms",
+                registry,
+            )
+                .unwrap(),
+            consensus_commit_timestamp_skew_alerts: register_int_counter_vec_with_registry!(
+                "consensus_commit_timestamp_skew_alerts",
+                "Number of consensus commits whose timestamp is further from local time than the alert threshold, sliced by leader",
+                &["authority"],
+                registry,
+            )
+                .unwrap(),
+            consensus_commit_timestamp_clamped: register_int_counter_with_registry!(
+                "consensus_commit_timestamp_clamped",
+                "Number of consensus commits whose timestamp was clamped before being given to the Clock",
+                registry,
+            )
+                .unwrap(),
             limits_metrics: Arc::new(LimitsMetrics::new(registry)),
             bytecode_verifier_metrics: Arc::new(BytecodeVerifierMetrics::new(registry)),
             authenticator_state_update_failed: register_int_counter_with_registry!(
@@ -611,10 +692,16 @@ pub struct AuthorityState {
 
     transaction_deny_config: TransactionDenyConfig,
 
+    /// Results of metering packages published by transactions, shared by signing and dry runs.
+    verifier_cache: VerifierCache,
+
     certificate_deny_config: CertificateDenyConfig,
 
     /// Config for state dumping on forks
     debug_dump_config: StateDebugDumpConfig,
+
+    /// Captures the objects written by selected transactions, if enabled.
+    execution_capture: Option<Arc<ExecutionCaptureStore>>,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -673,6 +760,7 @@ impl AuthorityState {
             &transaction.data().intent_message().value,
             &self.transaction_deny_config,
             &self.metrics.bytecode_verifier_metrics,
+            &self.verifier_cache,
         )?;
 
         let owned_objects = input_objects.filter_owned_objects();
@@ -1051,6 +1139,17 @@ impl AuthorityState {
 
         fail_point_async!("crash");
 
+        if let Some(execution_capture) = &self.execution_capture {
+            // Capturing is only for debugging, failing to capture must not fail execution.
+            if let Err(e) = execution_capture.capture(
+                certificate,
+                &inner_temporary_store.written,
+                Self::unixtime_now_ms(),
+            ) {
+                error!(?digest, "Failed to capture written objects: {e}");
+            }
+        }
+
         self.commit_cert_and_notify(
             certificate,
             inner_temporary_store,
@@ -1286,6 +1385,7 @@ impl AuthorityState {
                     &transaction,
                     gas_object,
                     &self.metrics.bytecode_verifier_metrics,
+                    &self.verifier_cache,
                 )?,
                 Some(gas_object_id),
             )
@@ -1299,6 +1399,7 @@ impl AuthorityState {
                     &transaction,
                     &self.transaction_deny_config,
                     &self.metrics.bytecode_verifier_metrics,
+                    &self.verifier_cache,
                 )?,
                 None,
             )
@@ -1485,6 +1586,7 @@ impl AuthorityState {
             effects,
             inner_temp_store.events.clone(),
             execution_result,
+            inner_temp_store.child_object_access,
             &module_cache,
         )
     }
@@ -1957,7 +2059,9 @@ impl AuthorityState {
         certificate_deny_config: CertificateDenyConfig,
         indirect_objects_threshold: usize,
         debug_dump_config: StateDebugDumpConfig,
+        execution_capture: Option<Arc<ExecutionCaptureStore>>,
         archive_readers: ArchiveReaderBalancer,
+        execution_concurrency_config: ExecutionConcurrencyConfig,
     ) -> Arc<Self> {
         Self::check_protocol_version(supported_protocol_versions, epoch_store.protocol_version());
 
@@ -2000,8 +2104,10 @@ impl AuthorityState {
             db_checkpoint_config: db_checkpoint_config.clone(),
             expensive_safety_check_config,
             transaction_deny_config,
+            verifier_cache: VerifierCache::default(),
             certificate_deny_config,
             debug_dump_config,
+            execution_capture,
         });
 
         // Start a task to execute ready certificates.
@@ -2009,7 +2115,8 @@ impl AuthorityState {
         spawn_monitored_task!(execution_process(
             authority_state,
             rx_ready_certificates,
-            rx_execution_shutdown
+            rx_execution_shutdown,
+            execution_concurrency_config,
         ));
 
         // TODO: This doesn't belong to the constructor of AuthorityState.
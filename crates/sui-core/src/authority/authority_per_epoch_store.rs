@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use fastcrypto_zkp::bn254::zk_login::{JwkId, OIDCProvider, JWK};
+use fastcrypto_zkp::bn254::zk_login::{JwkId, JWK};
 use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use futures::future::{join_all, select, Either};
 use futures::FutureExt;
@@ -16,6 +16,7 @@ use std::future::Future;
 use std::iter;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use sui_config::node::ExpensiveSafetyCheckConfig;
 use sui_types::accumulator::Accumulator;
 use sui_types::authenticator_state::{get_authenticator_state, ActiveJwk};
@@ -37,7 +38,7 @@ use typed_store::traits::{TableSummary, TypedStoreDebug};
 
 use super::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::{EpochFlag, EpochStartConfiguration};
-use crate::authority::{AuthorityStore, ResolverWrapper};
+use crate::authority::{AuthorityState, AuthorityStore, ResolverWrapper};
 use crate::checkpoints::{
     BuilderCheckpointSummary, CheckpointCommitHeight, CheckpointServiceNotify, EpochStats,
     PendingCheckpoint,
@@ -56,7 +57,6 @@ use mysten_common::sync::notify_once::NotifyOnce;
 use mysten_common::sync::notify_read::NotifyRead;
 use mysten_metrics::monitored_scope;
 use prometheus::IntCounter;
-use std::str::FromStr;
 use sui_execution::{self, Executor};
 use sui_macros::fail_point;
 use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
@@ -68,6 +68,7 @@ use sui_types::executable_transaction::{
 use sui_types::message_envelope::TrustedEnvelope;
 use sui_types::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, CheckpointSignatureMessage, CheckpointSummary,
+    CheckpointTimestamp,
 };
 use sui_types::messages_consensus::{
     check_total_jwk_size, AuthorityCapabilities, ConsensusTransaction, ConsensusTransactionKey,
@@ -79,6 +80,7 @@ use sui_types::storage::{
 use sui_types::sui_system_state::epoch_start_sui_system_state::{
     EpochStartSystemState, EpochStartSystemStateTrait,
 };
+use sui_types::zk_login_util::ZkLoginProviderRegistry;
 use tap::TapOptional;
 use tokio::time::Instant;
 use typed_store::{retry_transaction_forever, Map};
@@ -90,6 +92,7 @@ const LAST_CONSENSUS_INDEX_ADDR: u64 = 0;
 const RECONFIG_STATE_INDEX: u64 = 0;
 const FINAL_EPOCH_CHECKPOINT_INDEX: u64 = 0;
 const OVERRIDE_PROTOCOL_UPGRADE_BUFFER_STAKE_INDEX: u64 = 0;
+const LAST_CONSENSUS_COMMIT_TIMESTAMP_INDEX: u64 = 0;
 pub const EPOCH_DB_PREFIX: &str = "epoch_";
 
 // CertLockGuard and CertTxGuard are functionally identical right now, but we retain a distinction
@@ -234,6 +237,10 @@ pub struct AuthorityEpochTables {
     #[default_options_override_fn = "pending_execution_table_default_config"]
     pending_execution: DBMap<TransactionDigest, TrustedExecutableTransaction>,
 
+    /// Time at which each certificate in `pending_execution` was added to it, in milliseconds
+    /// since the unix epoch. Used to report how long certificates have been waiting to execute.
+    pending_execution_enqueued_at: DBMap<TransactionDigest, u64>,
+
     /// Track which transactions have been processed in handle_consensus_transaction. We must be
     /// sure to advance next_shared_object_versions exactly once for each transaction we receive from
     /// consensus. But, we may also be processing transactions from checkpoints, so we need to
@@ -331,6 +338,11 @@ pub struct AuthorityEpochTables {
     /// This would normally be stored as (JwkId, JWK) -> u64, but we need to be able to scan to
     /// find all Jwks for a given round
     active_jwks: DBMap<(u64, (JwkId, JWK)), ()>,
+
+    /// Contains a single key, LAST_CONSENSUS_COMMIT_TIMESTAMP_INDEX, mapping to the timestamp
+    /// given to the Clock by the prologue of the latest consensus commit processed in this epoch.
+    /// It is written together with the pending checkpoint of the commit.
+    last_consensus_commit_timestamp: DBMap<u64, CheckpointTimestamp>,
 }
 
 fn signed_transactions_table_default_config() -> DBOptions {
@@ -357,6 +369,60 @@ fn pending_checkpoints_table_default_config() -> DBOptions {
         .optimize_for_large_values_no_scan(1 << 10)
 }
 
+/// How far a certificate in the `pending_execution` table got through execution, as found after
+/// a restart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingExecutionState {
+    /// Execution has not started.
+    Pending,
+    /// Execution was interrupted after its signatures were recorded, but before its effects were
+    /// committed.
+    PartiallyExecuted,
+    /// Effects were committed, but the certificate was not removed from the table.
+    Executed,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingExecutionEntry {
+    pub digest: TransactionDigest,
+    pub state: PendingExecutionState,
+    /// Unknown for certificates enqueued by versions of the node that did not record it.
+    pub enqueued_at_ms: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub struct PendingExecutionAudit {
+    pub entries: Vec<PendingExecutionEntry>,
+}
+
+impl PendingExecutionState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PendingExecutionState::Pending => "pending",
+            PendingExecutionState::PartiallyExecuted => "partially_executed",
+            PendingExecutionState::Executed => "executed",
+        }
+    }
+}
+
+impl PendingExecutionAudit {
+    pub fn count(&self, state: PendingExecutionState) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.state == state)
+            .count()
+    }
+
+    /// How long the certificate that was enqueued first has been waiting, as of `now_ms`.
+    pub fn oldest_age(&self, now_ms: u64) -> Option<Duration> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.enqueued_at_ms)
+            .min()
+            .map(|enqueued_at_ms| Duration::from_millis(now_ms.saturating_sub(enqueued_at_ms)))
+    }
+}
+
 impl AuthorityEpochTables {
     pub fn open(epoch: EpochId, parent_path: &Path, db_options: Option<Options>) -> Self {
         Self::open_tables_transactional(
@@ -408,6 +474,67 @@ impl AuthorityEpochTables {
         Ok(())
     }
 
+    /// Classifies every certificate in the `pending_execution` table by how far it got through
+    /// execution, where `is_executed` tells whether a transaction's effects have been committed.
+    pub fn audit_pending_execution(
+        &self,
+        is_executed: impl Fn(&TransactionDigest) -> SuiResult<bool>,
+    ) -> SuiResult<PendingExecutionAudit> {
+        let mut entries = vec![];
+        for (digest, _) in self.pending_execution.unbounded_iter() {
+            // Signatures are recorded in the epoch store before effects are committed to the
+            // perpetual store, so a crash in between leaves only the signatures behind.
+            let state = if is_executed(&digest)? {
+                PendingExecutionState::Executed
+            } else if self.effects_signatures.contains_key(&digest)?
+                || self.transaction_cert_signatures.contains_key(&digest)?
+            {
+                PendingExecutionState::PartiallyExecuted
+            } else {
+                PendingExecutionState::Pending
+            };
+
+            entries.push(PendingExecutionEntry {
+                digest,
+                state,
+                enqueued_at_ms: self.pending_execution_enqueued_at.get(&digest)?,
+            });
+        }
+        Ok(PendingExecutionAudit { entries })
+    }
+
+    /// Deletes one pending certificate.
+    pub fn remove_pending_execution(&self, digest: &TransactionDigest) -> SuiResult {
+        let mut batch = self.pending_execution.batch();
+        batch.delete_batch(&self.pending_execution, [digest])?;
+        batch.delete_batch(&self.pending_execution_enqueued_at, [digest])?;
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Resets a pending certificate so that it is executed from scratch when the node restarts:
+    /// signatures left behind by an interrupted execution are removed, and it is treated as newly
+    /// enqueued.
+    ///
+    /// WARNING: This method is very subtle and can corrupt the database if used incorrectly.
+    /// It must only be used while the node is stopped, on certificates whose effects have not
+    /// been committed.
+    pub fn requeue_pending_execution_subtle(&self, digest: &TransactionDigest) -> SuiResult {
+        if !self.pending_execution.contains_key(digest)? {
+            return Err(SuiError::TransactionNotFound { digest: *digest });
+        }
+
+        let mut batch = self.pending_execution.batch();
+        batch.delete_batch(&self.effects_signatures, [digest])?;
+        batch.delete_batch(&self.transaction_cert_signatures, [digest])?;
+        batch.insert_batch(
+            &self.pending_execution_enqueued_at,
+            [(*digest, AuthorityState::unixtime_now_ms())],
+        )?;
+        batch.write()?;
+        Ok(())
+    }
+
     pub fn get_last_consensus_index(&self) -> SuiResult<Option<ExecutionIndicesWithHash>> {
         Ok(self.last_consensus_index.get(&LAST_CONSENSUS_INDEX_ADDR)?)
     }
@@ -481,10 +608,10 @@ impl AuthorityPerEpochStore {
             _ => ZkLoginEnv::Test,
         };
 
-        let supported_providers = protocol_config
-            .zklogin_supported_providers()
-            .iter()
-            .map(|s| OIDCProvider::from_str(s).expect("Invalid provider string"))
+        let supported_providers = ZkLoginProviderRegistry::from_protocol_config(&protocol_config)
+            .expect("Invalid provider string")
+            .providers()
+            .cloned()
             .collect::<Vec<_>>();
 
         let signature_verifier = SignatureVerifier::new(
@@ -774,6 +901,15 @@ impl AuthorityPerEpochStore {
             .map_err(SuiError::from)
     }
 
+    /// The timestamp given to the Clock by the prologue of the latest consensus commit processed
+    /// in this epoch, if any.
+    pub fn get_last_consensus_commit_timestamp(&self) -> SuiResult<Option<CheckpointTimestamp>> {
+        Ok(self
+            .tables
+            .last_consensus_commit_timestamp
+            .get(&LAST_CONSENSUS_COMMIT_TIMESTAMP_INDEX)?)
+    }
+
     pub fn get_accumulators_in_checkpoint_range(
         &self,
         from_checkpoint: CheckpointSequenceNumber,
@@ -829,10 +965,18 @@ impl AuthorityPerEpochStore {
             .collect())
     }
 
+    /// Classifies the pending certificates left behind by the previous run of the node. Used
+    /// during recovery.
+    pub fn audit_pending_execution(
+        &self,
+        is_executed: impl Fn(&TransactionDigest) -> SuiResult<bool>,
+    ) -> SuiResult<PendingExecutionAudit> {
+        self.tables.audit_pending_execution(is_executed)
+    }
+
     /// Deletes one pending certificate.
     pub fn remove_pending_execution(&self, digest: &TransactionDigest) -> SuiResult<()> {
-        self.tables.pending_execution.remove(digest)?;
-        Ok(())
+        self.tables.remove_pending_execution(digest)
     }
 
     pub fn get_all_pending_consensus_transactions(&self) -> Vec<ConsensusTransaction> {
@@ -1109,6 +1253,13 @@ impl AuthorityPerEpochStore {
                 .iter()
                 .map(|cert| (*cert.inner().digest(), cert.clone())),
         )?;
+        let enqueued_at = AuthorityState::unixtime_now_ms();
+        batch.insert_batch(
+            &self.tables.pending_execution_enqueued_at,
+            certs
+                .iter()
+                .map(|cert| (*cert.inner().digest(), enqueued_at)),
+        )?;
         batch.write()?;
         Ok(())
     }
@@ -1538,6 +1689,10 @@ impl AuthorityPerEpochStore {
             &self.tables.pending_execution,
             [(*certificate.digest(), certificate.clone().serializable())],
         )?;
+        batch.insert_batch(
+            &self.tables.pending_execution_enqueued_at,
+            [(*certificate.digest(), AuthorityState::unixtime_now_ms())],
+        )?;
         // User signatures are written in the same batch as consensus certificate processed flag,
         // which means we won't attempt to insert this twice for the same tx digest
         debug_assert!(!self
@@ -2166,6 +2321,13 @@ impl AuthorityPerEpochStore {
             &self.tables.pending_checkpoints,
             std::iter::once((checkpoint.height(), checkpoint)),
         )?;
+        batch.insert_batch(
+            &self.tables.last_consensus_commit_timestamp,
+            [(
+                LAST_CONSENSUS_COMMIT_TIMESTAMP_INDEX,
+                checkpoint.details.timestamp_ms,
+            )],
+        )?;
 
         Ok(())
     }
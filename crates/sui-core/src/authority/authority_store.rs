@@ -15,10 +15,12 @@ use move_core_types::resolver::ModuleResolver;
 use once_cell::sync::OnceCell;
 use serde::{Deserialize, Serialize};
 use sui_protocol_config::ProtocolConfig;
+use sui_storage::group_commit::{GroupCommitConfig, GroupCommitMetrics, GroupCommitter};
 use sui_storage::mutex_table::{MutexGuard, MutexTable, RwLockGuard, RwLockTable};
 use sui_types::accumulator::Accumulator;
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::error::UserInputError;
+use sui_types::execution::ObjectLineage;
 use sui_types::message_envelope::Message;
 use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
 use sui_types::object::Owner;
@@ -132,6 +134,10 @@ pub struct AuthorityStore {
 
     indirect_objects_threshold: usize,
 
+    /// Groups the writes of transactions committed concurrently into a single RocksDB write, if
+    /// enabled.
+    group_committer: Option<GroupCommitter>,
+
     /// Whether to enable expensive SUI conservation check at epoch boundaries.
     enable_epoch_sui_conservation_check: bool,
 
@@ -150,6 +156,7 @@ impl AuthorityStore {
         committee_store: &Arc<CommitteeStore>,
         indirect_objects_threshold: usize,
         enable_epoch_sui_conservation_check: bool,
+        group_commit_config: Option<GroupCommitConfig>,
         registry: &Registry,
     ) -> SuiResult<Arc<Self>> {
         let epoch_start_configuration = if perpetual_tables.database_is_empty()? {
@@ -183,6 +190,7 @@ impl AuthorityStore {
             &committee,
             indirect_objects_threshold,
             enable_epoch_sui_conservation_check,
+            group_commit_config,
             registry,
         )
         .await?;
@@ -220,6 +228,7 @@ impl AuthorityStore {
             committee,
             indirect_objects_threshold,
             true,
+            None,
             &Registry::new(),
         )
         .await
@@ -231,6 +240,7 @@ impl AuthorityStore {
         committee: &Committee,
         indirect_objects_threshold: usize,
         enable_epoch_sui_conservation_check: bool,
+        group_commit_config: Option<GroupCommitConfig>,
         registry: &Registry,
     ) -> SuiResult<Arc<Self>> {
         let epoch = committee.epoch;
@@ -245,6 +255,9 @@ impl AuthorityStore {
             execution_lock: RwLock::new(epoch),
             objects_lock_table: Arc::new(RwLockTable::new(NUM_SHARDS)),
             indirect_objects_threshold,
+            group_committer: group_commit_config.map(|config| {
+                GroupCommitter::new(config, Arc::new(GroupCommitMetrics::new(registry)))
+            }),
             enable_epoch_sui_conservation_check,
             metrics: AuthorityStoreMetrics::new(registry),
         });
@@ -350,6 +363,16 @@ impl AuthorityStore {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
+    /// The objects wrapped or unwrapped by the transaction, and the objects they were wrapped in or
+    /// unwrapped from, if it was executed with the `record_object_lineage` feature and wrapped or
+    /// unwrapped any objects.
+    pub fn get_object_lineage(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<BTreeMap<ObjectID, ObjectLineage>>> {
+        Ok(self.perpetual_tables.object_lineage.get(tx_digest)?)
+    }
+
     pub fn multi_get_effects<'a>(
         &self,
         effects_digests: impl Iterator<Item = &'a TransactionEffectsDigest>,
@@ -1006,6 +1029,9 @@ impl AuthorityStore {
             .await;
         // Extract the new state from the execution
         let mut write_batch = self.perpetual_tables.transactions.batch();
+        if self.group_committer.is_some() {
+            write_batch = write_batch.appendable();
+        }
 
         // Store the certificate indexed by transaction digest
         let transaction_digest = transaction.digest();
@@ -1039,8 +1065,11 @@ impl AuthorityStore {
         // test crashing before writing the batch
         fail_point_async!("crash");
 
-        // Commit.
-        write_batch.write()?;
+        // Commit, together with the outputs of transactions committed concurrently if enabled.
+        match &self.group_committer {
+            Some(group_committer) => group_committer.commit(write_batch).await?,
+            None => write_batch.write()?,
+        }
 
         // test crashing before notifying
         fail_point_async!("crash");
@@ -1096,6 +1125,8 @@ impl AuthorityStore {
             loaded_runtime_objects: _,
             no_extraneous_module_bytes: _,
             runtime_packages_loaded_from_db: _,
+            object_lineage,
+            child_object_access: _,
         } = inner_temporary_store;
         trace!(written =? written.iter().map(|(obj_id, obj)| (obj_id, obj.version())).collect::<Vec<_>>(),
                "batch_update_objects: temp store written");
@@ -1224,6 +1255,13 @@ impl AuthorityStore {
 
         write_batch.insert_batch(&self.perpetual_tables.events, events)?;
 
+        if !object_lineage.is_empty() {
+            write_batch.insert_batch(
+                &self.perpetual_tables.object_lineage,
+                iter::once((*transaction.digest(), object_lineage)),
+            )?;
+        }
+
         let new_locks_to_init: Vec<_> = written
             .values()
             .filter_map(|new_object| {
@@ -1557,6 +1595,7 @@ impl AuthorityStore {
                 &(*events_digest, usize::MAX),
             )?;
         }
+        write_batch.delete_batch(&self.perpetual_tables.object_lineage, iter::once(tx_digest))?;
 
         let tombstones = effects
             .all_removed_objects()
@@ -1634,6 +1673,27 @@ impl AuthorityStore {
             .find_object_lt_or_eq_version(object_id, version)
     }
 
+    /// Returns whether `version` of the object is missing from the objects table because it was
+    /// pruned: objects have been pruned, the object has a later version, and no version of it at
+    /// or before `version` is kept anymore.
+    pub fn is_object_version_pruned(
+        &self,
+        object_id: ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<bool> {
+        if self.perpetual_tables.get_highest_pruned_checkpoint()? == 0 {
+            return Ok(false);
+        }
+        let Some((_, latest_version, _)) = self.get_latest_object_ref_or_tombstone(object_id)?
+        else {
+            return Ok(false);
+        };
+        Ok(latest_version > version
+            && self
+                .find_object_lt_or_eq_version(object_id, version)
+                .is_none())
+    }
+
     /// Returns the latest object reference we have for this object_id in the objects table.
     ///
     /// The method may also return the reference to a deleted object with a digest of
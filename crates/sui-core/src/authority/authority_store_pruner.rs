@@ -42,6 +42,7 @@ static PERIODIC_PRUNING_TABLES: Lazy<HashSet<String>> = Lazy::new(|| {
         "effects",
         "transactions",
         "events",
+        "object_lineage",
         "executed_effects",
         "executed_transactions_to_checkpoint",
     ]
@@ -190,6 +191,7 @@ impl AuthorityStorePruner {
 
         perpetual_batch.delete_batch(&perpetual_db.transactions, transactions.iter())?;
         perpetual_batch.delete_batch(&perpetual_db.executed_effects, transactions.iter())?;
+        perpetual_batch.delete_batch(&perpetual_db.object_lineage, transactions.iter())?;
         perpetual_batch.delete_batch(
             &perpetual_db.executed_transactions_to_checkpoint,
             transactions,
@@ -5,11 +5,13 @@ use super::*;
 use crate::authority::authority_store::LockDetailsWrapper;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::SequenceNumber;
 use sui_types::digests::TransactionEventsDigest;
 use sui_types::effects::TransactionEffects;
+use sui_types::execution::ObjectLineage;
 use sui_types::storage::MarkerValue;
 use typed_store::metrics::SamplingInterval;
 use typed_store::rocks::util::{empty_compaction_filter, reference_count_merge_operator};
@@ -92,6 +94,11 @@ pub struct AuthorityPerpetualTables {
     #[default_options_override_fn = "events_table_default_config"]
     pub(crate) events: DBMap<(TransactionEventsDigest, usize), Event>,
 
+    /// The objects wrapped or unwrapped by a transaction, and the objects they were wrapped in or
+    /// unwrapped from, for transactions executed with the `record_object_lineage` feature that
+    /// wrapped or unwrapped any objects. Pruned along with the transactions.
+    pub(crate) object_lineage: DBMap<TransactionDigest, BTreeMap<ObjectID, ObjectLineage>>,
+
     /// DEPRECATED in favor of the table of the same name in authority_per_epoch_store.
     /// Please do not add new accessors/callsites.
     /// When transaction is executed via checkpoint executor, we store association here
@@ -313,6 +320,10 @@ impl AuthorityPerpetualTables {
         Ok(Some(transaction))
     }
 
+    pub fn is_tx_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
+        Ok(self.executed_effects.contains_key(digest)?)
+    }
+
     pub fn get_effects(&self, digest: &TransactionDigest) -> SuiResult<Option<TransactionEffects>> {
         let Some(effect_digest) = self.executed_effects.get(digest)? else {
             return Ok(None);
@@ -444,6 +455,7 @@ impl AuthorityPerpetualTables {
         self.owned_object_transaction_locks.unsafe_clear()?;
         self.executed_effects.unsafe_clear()?;
         self.events.unsafe_clear()?;
+        self.object_lineage.unsafe_clear()?;
         self.executed_transactions_to_checkpoint.unsafe_clear()?;
         self.root_state_hash_by_epoch.unsafe_clear()?;
         self.epoch_start_configuration.unsafe_clear()?;
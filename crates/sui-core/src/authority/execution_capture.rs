@@ -0,0 +1,195 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use mysten_metrics::spawn_monitored_task;
+use serde::{Deserialize, Serialize};
+use sui_config::node::ExecutionCaptureConfig;
+use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::error::SuiResult;
+use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::inner_temporary_store::WrittenObjects;
+use sui_types::object::Object;
+use sui_types::transaction::TransactionDataAPI;
+use tracing::{error, info};
+use typed_store::rocks::{DBMap, MetricConf};
+use typed_store::traits::{TableSummary, TypedStoreDebug};
+use typed_store::Map;
+use typed_store_derive::DBMapUtils;
+
+/// Expired captures are looked for at least this often.
+const MAX_PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The objects written by a captured transaction.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapturedTransaction {
+    pub captured_at_ms: u64,
+    pub written: Vec<Object>,
+}
+
+#[derive(DBMapUtils)]
+pub struct ExecutionCaptureTables {
+    captured_transactions: DBMap<TransactionDigest, CapturedTransaction>,
+}
+
+/// Keeps the full contents of the objects written by the transactions selected by an
+/// `ExecutionCaptureConfig`, for post-mortem debugging of their state transitions. Captures are
+/// deleted once they are older than the configured TTL.
+pub struct ExecutionCaptureStore {
+    tables: ExecutionCaptureTables,
+    senders: HashSet<SuiAddress>,
+    packages: HashSet<ObjectID>,
+    digests: HashSet<TransactionDigest>,
+    ttl: Duration,
+}
+
+impl ExecutionCaptureStore {
+    /// Opens the store at `path`, and starts deleting the expired captures in the background.
+    pub fn new(path: PathBuf, config: &ExecutionCaptureConfig) -> Arc<Self> {
+        let store = Arc::new(Self::new_without_pruning(path, config));
+        spawn_monitored_task!(prune_expired_captures(Arc::downgrade(&store)));
+        store
+    }
+
+    fn new_without_pruning(path: PathBuf, config: &ExecutionCaptureConfig) -> Self {
+        Self {
+            tables: ExecutionCaptureTables::open_tables_read_write(
+                path,
+                MetricConf::default(),
+                None,
+                None,
+            ),
+            senders: config.senders.iter().copied().collect(),
+            packages: config.packages.iter().copied().collect(),
+            digests: config.digests.iter().copied().collect(),
+            ttl: Duration::from_secs(config.ttl_secs),
+        }
+    }
+
+    pub fn should_capture(&self, certificate: &VerifiedExecutableTransaction) -> bool {
+        let tx_data = &certificate.data().intent_message().value;
+        self.digests.contains(certificate.digest())
+            || self.senders.contains(&tx_data.sender())
+            || tx_data
+                .move_calls()
+                .into_iter()
+                .any(|(package, _, _)| self.packages.contains(package))
+    }
+
+    /// Captures the objects written by `certificate`, if it is selected by the configuration.
+    pub fn capture(
+        &self,
+        certificate: &VerifiedExecutableTransaction,
+        written: &WrittenObjects,
+        now_ms: u64,
+    ) -> SuiResult {
+        if !self.should_capture(certificate) {
+            return Ok(());
+        }
+        let captured = CapturedTransaction {
+            captured_at_ms: now_ms,
+            written: written.values().cloned().collect(),
+        };
+        self.tables
+            .captured_transactions
+            .insert(certificate.digest(), &captured)?;
+        Ok(())
+    }
+
+    pub fn get(&self, digest: &TransactionDigest) -> SuiResult<Option<CapturedTransaction>> {
+        Ok(self.tables.captured_transactions.get(digest)?)
+    }
+
+    /// Deletes the captures older than the TTL, and returns how many were deleted.
+    pub fn prune_expired(&self, now_ms: u64) -> SuiResult<usize> {
+        let ttl_ms = u64::try_from(self.ttl.as_millis()).unwrap_or(u64::MAX);
+        let mut expired = vec![];
+        for entry in self.tables.captured_transactions.safe_iter() {
+            let (digest, captured) = entry?;
+            if captured.captured_at_ms.saturating_add(ttl_ms) <= now_ms {
+                expired.push(digest);
+            }
+        }
+        self.tables
+            .captured_transactions
+            .multi_remove(expired.iter())?;
+        Ok(expired.len())
+    }
+}
+
+async fn prune_expired_captures(store: Weak<ExecutionCaptureStore>) {
+    let Some(interval) = store
+        .upgrade()
+        .map(|store| store.ttl.min(MAX_PRUNING_INTERVAL))
+    else {
+        return;
+    };
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        // Stop once the store is dropped.
+        let Some(store) = store.upgrade() else {
+            return;
+        };
+        let now_ms = crate::authority::AuthorityState::unixtime_now_ms();
+        match store.prune_expired(now_ms) {
+            Ok(0) => (),
+            Ok(pruned) => info!("Deleted {pruned} expired execution captures"),
+            Err(e) => error!("Failed to delete expired execution captures: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{dbg_addr, random_object_ref};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair};
+    use sui_types::transaction::{TransactionData, VerifiedTransaction};
+    use sui_types::utils::to_sender_signed_transaction;
+
+    fn certificate(sender: SuiAddress, key: &AccountKeyPair) -> VerifiedExecutableTransaction {
+        let tx_data =
+            TransactionData::new_transfer_sui(dbg_addr(9), sender, None, random_object_ref(), 0, 0);
+        VerifiedExecutableTransaction::new_system(
+            VerifiedTransaction::new_unchecked(to_sender_signed_transaction(tx_data, key)),
+            0,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_capture_and_prune() {
+        let (captured_sender, captured_key): (_, AccountKeyPair) = get_key_pair();
+        let (ignored_sender, ignored_key): (_, AccountKeyPair) = get_key_pair();
+        let config = ExecutionCaptureConfig {
+            senders: vec![captured_sender],
+            packages: vec![],
+            digests: vec![],
+            ttl_secs: 60,
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let store = ExecutionCaptureStore::new_without_pruning(dir.path().to_path_buf(), &config);
+
+        let captured = certificate(captured_sender, &captured_key);
+        let ignored = certificate(ignored_sender, &ignored_key);
+        let object = Object::immutable_with_id_for_testing(ObjectID::random());
+        let written = WrittenObjects::from([(object.id(), object)]);
+        store.capture(&captured, &written, 1_000).unwrap();
+        store.capture(&ignored, &written, 1_000).unwrap();
+
+        assert_eq!(
+            store.get(captured.digest()).unwrap().unwrap().written.len(),
+            1
+        );
+        assert!(store.get(ignored.digest()).unwrap().is_none());
+
+        // Captures are kept for 60s.
+        assert_eq!(store.prune_expired(60_999).unwrap(), 0);
+        assert_eq!(store.prune_expired(61_000).unwrap(), 1);
+        assert!(store.get(captured.digest()).unwrap().is_none());
+    }
+}
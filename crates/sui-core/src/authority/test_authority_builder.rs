@@ -19,7 +19,8 @@ use sui_config::certificate_deny_config::CertificateDenyConfig;
 use sui_config::genesis::Genesis;
 use sui_config::node::StateDebugDumpConfig;
 use sui_config::node::{
-    AuthorityStorePruningConfig, DBCheckpointConfig, ExpensiveSafetyCheckConfig,
+    AuthorityStorePruningConfig, DBCheckpointConfig, ExecutionConcurrencyConfig,
+    ExpensiveSafetyCheckConfig,
 };
 use sui_config::transaction_deny_config::TransactionDenyConfig;
 use sui_macros::nondeterministic;
@@ -224,7 +225,9 @@ impl<'a> TestAuthorityBuilder<'a> {
             StateDebugDumpConfig {
                 dump_file_directory: Some(tempdir().unwrap().into_path()),
             },
+            None,
             ArchiveReaderBalancer::default(),
+            ExecutionConcurrencyConfig::default(),
         )
         .await;
         // For any type of local testing that does not actually spawn a node, the checkpoint executor
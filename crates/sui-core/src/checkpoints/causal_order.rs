@@ -130,7 +130,7 @@ impl RWLockDependencyBuilder {
                             .or_default()
                             .push(*effect.transaction_digest());
                     }
-                    InputSharedObjectKind::Mutate => {
+                    InputSharedObjectKind::Mutate | InputSharedObjectKind::MutateDeleted => {
                         // write transaction
                         overwrite_versions
                             .entry(*effect.transaction_digest())
@@ -250,6 +250,33 @@ mod tests {
         assert!(r.contains(&2));
     }
 
+    #[test]
+    pub fn test_causal_order_shared_object_deletion() {
+        let mut e1 = e(d(1), vec![]);
+        let mut e2 = e(d(2), vec![]);
+        let obj_ref = (
+            o(1),
+            SequenceNumber::from_u64(1),
+            ObjectDigest::new(Default::default()),
+        );
+        e1.unsafe_add_input_shared_object_for_testing(
+            obj_ref,
+            InputSharedObjectKind::MutateDeleted,
+        );
+        e2.unsafe_add_input_shared_object_for_testing(obj_ref, InputSharedObjectKind::ReadOnly);
+
+        let kinds: Vec<_> = e1
+            .input_shared_objects()
+            .into_iter()
+            .map(|(_, kind)| kind)
+            .collect();
+        assert!(matches!(kinds[..], [InputSharedObjectKind::MutateDeleted]));
+
+        // Deleting the object overwrites the version read by [2]
+        let r = extract(CausalOrder::causal_sort(vec![e1, e2]));
+        assert_eq!(r, vec![2, 1]);
+    }
+
     fn extract(e: Vec<TransactionEffects>) -> Vec<u8> {
         e.into_iter()
             .map(|e| e.transaction_digest().inner()[0])
@@ -5,7 +5,7 @@ use crate::authority::authority_per_epoch_store::{
     AuthorityPerEpochStore, ExecutionIndicesWithHash,
 };
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
-use crate::authority::AuthorityMetrics;
+use crate::authority::{AuthorityMetrics, AuthorityState};
 use crate::checkpoints::{CheckpointServiceNotify, PendingCheckpoint, PendingCheckpointInfo};
 use std::cmp::Ordering;
 
@@ -26,6 +26,7 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::Duration;
 use sui_protocol_config::ConsensusTransactionOrdering;
 use sui_types::authenticator_state::ActiveJwk;
 use sui_types::base_types::{AuthorityName, EpochId, TransactionDigest};
@@ -36,7 +37,7 @@ use sui_types::executable_transaction::VerifiedExecutableTransaction;
 use sui_types::messages_consensus::{
     ConsensusTransaction, ConsensusTransactionKey, ConsensusTransactionKind,
 };
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 
 pub struct ConsensusHandler<T, C> {
     /// A store created for each epoch. ConsensusHandler is recreated each epoch, with the
@@ -59,6 +60,12 @@ pub struct ConsensusHandler<T, C> {
     /// Lru cache to quickly discard transactions processed by consensus
     processed_cache: LruCache<SequencedConsensusTransactionKey, ()>,
     transaction_scheduler: AsyncTransactionScheduler,
+    /// The timestamp given to the Clock by the prologue of the last processed commit, or the
+    /// start of the epoch. It is recovered on restart, and the timestamps of later commits are
+    /// never clamped below it.
+    last_commit_timestamp: u64,
+    /// How far the timestamp of a commit can be from the local clock before it is reported.
+    commit_timestamp_skew_alert_threshold: Duration,
 }
 
 const PROCESSED_CACHE_CAP: usize = 1024 * 1024;
@@ -72,12 +79,18 @@ impl<T, C> ConsensusHandler<T, C> {
         low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
         committee: Committee,
         metrics: Arc<AuthorityMetrics>,
+        commit_timestamp_skew_alert_threshold: Duration,
     ) -> Self {
         // last_seen is zero at the beginning of epoch, including for hash.
         // It needs to be recovered on restart to ensure consistent consensus hash.
         let last_seen = epoch_store
             .get_last_consensus_index()
             .expect("Should be able to read last consensus index");
+        let epoch_start = epoch_store.epoch_start_config().epoch_start_timestamp_ms();
+        let last_commit_timestamp = epoch_store
+            .get_last_consensus_commit_timestamp()
+            .expect("Should be able to read last consensus commit timestamp")
+            .map_or(epoch_start, |timestamp| timestamp.max(epoch_start));
         let transaction_scheduler =
             AsyncTransactionScheduler::start(transaction_manager, epoch_store.clone());
         Self {
@@ -90,6 +103,8 @@ impl<T, C> ConsensusHandler<T, C> {
             metrics,
             processed_cache: LruCache::new(NonZeroUsize::new(PROCESSED_CACHE_CAP).unwrap()),
             transaction_scheduler,
+            last_commit_timestamp,
+            commit_timestamp_skew_alert_threshold,
         }
     }
 
@@ -109,6 +124,26 @@ impl<T, C> ConsensusHandler<T, C> {
     }
 }
 
+/// Clamps the timestamp of a commit before it is given to the Clock, following the policy
+/// documented on `ProtocolConfig::max_consensus_commit_timestamp_skew_ms`: it is at most
+/// `max_skew_ms` ahead of the median creation time of the commit's certificates, and never earlier
+/// than `last_timestamp`. The result only depends on the commit, so it is the same on all
+/// validators.
+fn clamp_commit_timestamp(
+    commit_timestamp: u64,
+    mut certificate_timestamps: Vec<u64>,
+    last_timestamp: u64,
+    max_skew_ms: u64,
+) -> u64 {
+    certificate_timestamps.sort_unstable();
+    let upper_bound = certificate_timestamps
+        .get(certificate_timestamps.len() / 2)
+        .map_or(commit_timestamp, |median| {
+            median.saturating_add(max_skew_ms)
+        });
+    commit_timestamp.min(upper_bound).max(last_timestamp)
+}
+
 fn update_hash(
     last_seen: &ExecutionIndicesWithHash,
     index: ExecutionIndices,
@@ -176,21 +211,56 @@ impl<T: ObjectStore + Send + Sync, C: CheckpointServiceNotify + Send + Sync> Exe
 
         /* (serialized, transaction, output_cert) */
         let mut transactions = vec![];
-        let timestamp = consensus_output.sub_dag.commit_timestamp();
+        let commit_timestamp = consensus_output.sub_dag.commit_timestamp();
         let leader_author = consensus_output.sub_dag.leader.header().author();
 
+        // The skew against the local clock is only reported: it differs between validators, so it
+        // cannot be used to decide the timestamp given to the Clock.
+        let skew_ms = AuthorityState::unixtime_now_ms() as i64 - commit_timestamp as i64;
+        self.metrics.consensus_commit_timestamp_skew_ms.set(skew_ms);
+        if u128::from(skew_ms.unsigned_abs())
+            > self.commit_timestamp_skew_alert_threshold.as_millis()
+        {
+            warn!(
+                "Commit timestamp {commit_timestamp} is {skew_ms}ms behind local time (negative if ahead), author {leader_author}, round {round}",
+            );
+            self.metrics
+                .consensus_commit_timestamp_skew_alerts
+                .with_label_values(&[&leader_author.to_string()])
+                .inc();
+        }
+
         let epoch_start = self
             .epoch_store
             .epoch_start_config()
             .epoch_start_timestamp_ms();
-        let timestamp = if timestamp < epoch_start {
+        if commit_timestamp < epoch_start {
             error!(
-                "Unexpected commit timestamp {timestamp} less then epoch start time {epoch_start}, author {leader_author}, round {round}",
+                "Unexpected commit timestamp {commit_timestamp} less then epoch start time {epoch_start}, author {leader_author}, round {round}",
             );
-            epoch_start
-        } else {
-            timestamp
+        }
+        let timestamp = match self
+            .epoch_store
+            .protocol_config()
+            .max_consensus_commit_timestamp_skew_ms_as_option()
+        {
+            Some(max_skew_ms) => clamp_commit_timestamp(
+                commit_timestamp,
+                consensus_output
+                    .sub_dag
+                    .certificates
+                    .iter()
+                    .map(|cert| *cert.header().created_at())
+                    .collect(),
+                self.last_commit_timestamp,
+                max_skew_ms,
+            ),
+            None => commit_timestamp.max(epoch_start),
         };
+        if timestamp != commit_timestamp {
+            self.metrics.consensus_commit_timestamp_clamped.inc();
+        }
+        self.last_commit_timestamp = timestamp;
 
         info!(
             "Received consensus output {:?} at leader round {}, subdag index {}, timestamp {} epoch {}",
@@ -724,6 +794,7 @@ mod tests {
             Arc::new(ArcSwap::default()),
             committee.clone(),
             Arc::new(AuthorityMetrics::new(&Registry::new())),
+            Duration::from_secs(10),
         );
 
         // AND
@@ -824,6 +895,27 @@ mod tests {
         assert!(update_hash(&last_seen, index2, tx).is_some());
     }
 
+    #[test]
+    fn test_clamp_commit_timestamp() {
+        // Within the allowed skew of the median certificate.
+        assert_eq!(
+            clamp_commit_timestamp(1_500, vec![1_000, 1_200, 1_400], 0, 500),
+            1_500
+        );
+        // Too far ahead of the median certificate.
+        assert_eq!(
+            clamp_commit_timestamp(10_000, vec![1_000, 1_200, 50_000], 0, 500),
+            1_700
+        );
+        // Never earlier than the previous commit.
+        assert_eq!(
+            clamp_commit_timestamp(10_000, vec![1_000, 1_200, 1_400], 2_000, 500),
+            2_000
+        );
+        // No certificates to compare with.
+        assert_eq!(clamp_commit_timestamp(1_500, vec![], 1_000, 500), 1_500);
+    }
+
     #[test]
     fn test_order_by_gas_price() {
         let mut v = vec![cap_txn(10), user_txn(42), user_txn(100), cap_txn(1)];
@@ -7,6 +7,7 @@ use std::{
 };
 
 use mysten_metrics::{monitored_scope, spawn_monitored_task};
+use sui_config::node::ExecutionConcurrencyConfig;
 use sui_types::digests::TransactionEffectsDigest;
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
 use tokio::{
@@ -26,6 +27,64 @@ mod execution_driver_tests;
 pub const EXECUTION_MAX_ATTEMPTS: u32 = 10;
 const EXECUTION_FAILURE_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
+/// The classes of transactions whose executions are limited separately, so that e.g. a burst of
+/// owned object transactions cannot delay the execution of consensus transactions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ExecutionClass {
+    OwnedObject,
+    SharedObject,
+    System,
+}
+
+impl ExecutionClass {
+    fn of(certificate: &VerifiedExecutableTransaction) -> Self {
+        if certificate.is_system_tx() {
+            Self::System
+        } else if certificate.contains_shared_object() {
+            Self::SharedObject
+        } else {
+            Self::OwnedObject
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::OwnedObject => "owned_object",
+            Self::SharedObject => "shared_object",
+            Self::System => "system",
+        }
+    }
+}
+
+/// One semaphore per execution class.
+struct ExecutionLimits {
+    owned_object: Arc<Semaphore>,
+    shared_object: Arc<Semaphore>,
+    system: Arc<Semaphore>,
+}
+
+impl ExecutionLimits {
+    fn new(config: &ExecutionConcurrencyConfig) -> Self {
+        // Rate limit concurrent executions to # of cpus by default.
+        let limit = |limit: Option<usize>| {
+            Arc::new(Semaphore::new(limit.unwrap_or_else(num_cpus::get)))
+        };
+        Self {
+            owned_object: limit(config.owned_object_limit),
+            shared_object: limit(config.shared_object_limit),
+            system: limit(config.system_limit),
+        }
+    }
+
+    fn get(&self, class: ExecutionClass) -> Arc<Semaphore> {
+        match class {
+            ExecutionClass::OwnedObject => self.owned_object.clone(),
+            ExecutionClass::SharedObject => self.shared_object.clone(),
+            ExecutionClass::System => self.system.clone(),
+        }
+    }
+}
+
 /// When a notification that a new pending transaction is received we activate
 /// processing the transaction in a loop.
 pub async fn execution_process(
@@ -35,11 +94,11 @@ pub async fn execution_process(
         Option<TransactionEffectsDigest>,
     )>,
     mut rx_execution_shutdown: oneshot::Receiver<()>,
+    concurrency_config: ExecutionConcurrencyConfig,
 ) {
-    info!("Starting pending certificates execution process.");
+    info!(?concurrency_config, "Starting pending certificates execution process.");
 
-    // Rate limit concurrent executions to # of cpus.
-    let limit = Arc::new(Semaphore::new(num_cpus::get()));
+    let limits = ExecutionLimits::new(&concurrency_config);
 
     // Loop whenever there is a signal that a new transactions is ready to process.
     loop {
@@ -81,15 +140,30 @@ pub async fn execution_process(
         let digest = *certificate.digest();
         trace!(?digest, "Pending certificate execution activated.");
 
-        let limit = limit.clone();
-        // hold semaphore permit until task completes. unwrap ok because we never close
-        // the semaphore in this context.
-        let permit = limit.acquire_owned().await.unwrap();
+        let class = ExecutionClass::of(&certificate);
+        let limit = limits.get(class);
+        let pending = authority
+            .metrics
+            .execution_driver_class_pending
+            .with_label_values(&[class.as_str()]);
+        let executing = authority
+            .metrics
+            .execution_driver_class_executing
+            .with_label_values(&[class.as_str()]);
+        pending.inc();
 
         // Certificate execution can take significant time, so run it in a separate task.
+        // The permit is acquired in the task, so that a class at its limit does not delay the
+        // dispatch of the other classes.
         spawn_monitored_task!(async move {
+            // hold semaphore permit until task completes. unwrap ok because we never close
+            // the semaphore in this context.
+            let permit = limit.acquire_owned().await.unwrap();
+            pending.dec();
+            executing.inc();
             let _scope = monitored_scope("ExecutionDriver::task");
             let _guard = permit;
+            let _executing_guard = scopeguard::guard(executing, |executing| executing.dec());
             if let Ok(true) = authority.is_tx_already_executed(&digest) {
                 return;
             }
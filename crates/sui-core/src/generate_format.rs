@@ -36,7 +36,7 @@ use sui_types::{
         AuthorityPublicKeyBytes, AuthoritySignature, KeypairTraits, Signature, SuiKeyPair,
     },
     multisig::{MultiSig, MultiSigPublicKey},
-    object::{Data, Owner},
+    object::{Authenticator, Data, Owner},
     signature::GenericSignature,
     storage::DeleteKind,
     transaction::{
@@ -135,6 +135,7 @@ fn get_registry() -> Result<Registry> {
 
     // 2. Trace the main entry point(s) + every enum separately.
     tracer.trace_type::<Owner>(&samples)?;
+    tracer.trace_type::<Authenticator>(&samples)?;
     tracer.trace_type::<ExecutionStatus>(&samples)?;
     tracer.trace_type::<ExecutionFailureStatus>(&samples)?;
     tracer.trace_type::<AbortLocation>(&samples)?;
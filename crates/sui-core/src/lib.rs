@@ -18,6 +18,7 @@ mod execution_driver;
 pub mod metrics;
 pub mod module_cache_metrics;
 pub mod narwhal_manager;
+pub mod object_contention;
 pub mod quorum_driver;
 pub mod safe_client;
 mod scoring_decision;
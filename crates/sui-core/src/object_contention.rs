@@ -0,0 +1,182 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks contention on shared objects in TransactionManager: how many certificates had to queue
+//! for each shared object, and how long they waited for it, over a sliding window. Used to report
+//! the objects that are hot spots during congestion.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sui_types::base_types::ObjectID;
+
+/// How far back contention is reported.
+pub const DEFAULT_CONTENTION_WINDOW: Duration = Duration::from_secs(60);
+
+/// Upper bound on the number of events kept, so that memory stays bounded under heavy load. When
+/// it is reached, the oldest events are dropped before they leave the window.
+const MAX_CONTENTION_EVENTS: usize = 1_000_000;
+
+/// Contention on a shared object over the window of an [`ObjectContentionTracker`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HotObject {
+    pub object_id: ObjectID,
+    /// Number of certificates that queued for the object.
+    pub queued_certificates: u64,
+    /// Total time that certificates which acquired the object waited for it.
+    pub total_wait: Duration,
+    /// Longest time that a certificate waited for the object.
+    pub max_wait: Duration,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ContentionEvent {
+    /// A certificate started waiting for the object.
+    Queued,
+    /// A certificate acquired the object after waiting for this long.
+    Acquired(Duration),
+}
+
+pub struct ObjectContentionTracker {
+    window: Duration,
+    events: VecDeque<(Instant, ObjectID, ContentionEvent)>,
+}
+
+impl ObjectContentionTracker {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Records that a certificate has to wait for the shared object `object_id`.
+    pub fn record_queued(&mut self, object_id: ObjectID, now: Instant) {
+        self.record(now, object_id, ContentionEvent::Queued);
+    }
+
+    /// Records that a certificate acquired the shared object `object_id` after waiting for `wait`.
+    pub fn record_acquired(&mut self, object_id: ObjectID, wait: Duration, now: Instant) {
+        self.record(now, object_id, ContentionEvent::Acquired(wait));
+    }
+
+    /// The `limit` shared objects with the most certificates queued for them in the window ending
+    /// at `now`, breaking ties by the total time certificates waited for them.
+    pub fn hot_objects(&self, now: Instant, limit: usize) -> Vec<HotObject> {
+        let mut objects: HashMap<ObjectID, HotObject> = HashMap::new();
+        for (_, object_id, event) in self
+            .events
+            .iter()
+            .filter(|(time, _, _)| now.saturating_duration_since(*time) <= self.window)
+        {
+            let object = objects.entry(*object_id).or_insert_with(|| HotObject {
+                object_id: *object_id,
+                queued_certificates: 0,
+                total_wait: Duration::ZERO,
+                max_wait: Duration::ZERO,
+            });
+            match event {
+                ContentionEvent::Queued => object.queued_certificates += 1,
+                ContentionEvent::Acquired(wait) => {
+                    object.total_wait += *wait;
+                    object.max_wait = object.max_wait.max(*wait);
+                }
+            }
+        }
+
+        let mut objects: Vec<_> = objects.into_values().collect();
+        objects.sort_by(|a, b| {
+            (b.queued_certificates, b.total_wait, a.object_id).cmp(&(
+                a.queued_certificates,
+                a.total_wait,
+                b.object_id,
+            ))
+        });
+        objects.truncate(limit);
+        objects
+    }
+
+    fn record(&mut self, now: Instant, object_id: ObjectID, event: ContentionEvent) {
+        while let Some((time, _, _)) = self.events.front() {
+            if now.saturating_duration_since(*time) <= self.window
+                && self.events.len() < MAX_CONTENTION_EVENTS
+            {
+                break;
+            }
+            self.events.pop_front();
+        }
+        self.events.push_back((now, object_id, event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_hot_objects() {
+        let start = Instant::now();
+        let mut tracker = ObjectContentionTracker::new(Duration::from_secs(10));
+        let (hot, warm, cold) = (ObjectID::random(), ObjectID::random(), ObjectID::random());
+
+        for _ in 0..3 {
+            tracker.record_queued(hot, start);
+        }
+        tracker.record_acquired(hot, ms(100), start + ms(100));
+        tracker.record_acquired(hot, ms(300), start + ms(300));
+        tracker.record_queued(warm, start);
+        tracker.record_queued(warm, start);
+        tracker.record_acquired(warm, ms(50), start + ms(50));
+        tracker.record_acquired(cold, ms(5), start + ms(5));
+
+        let now = start + ms(500);
+        assert_eq!(
+            tracker.hot_objects(now, 2),
+            vec![
+                HotObject {
+                    object_id: hot,
+                    queued_certificates: 3,
+                    total_wait: ms(400),
+                    max_wait: ms(300),
+                },
+                HotObject {
+                    object_id: warm,
+                    queued_certificates: 2,
+                    total_wait: ms(50),
+                    max_wait: ms(50),
+                },
+            ]
+        );
+        assert_eq!(tracker.hot_objects(now, 10).len(), 3);
+    }
+
+    #[test]
+    fn test_window() {
+        let start = Instant::now();
+        let mut tracker = ObjectContentionTracker::new(Duration::from_secs(10));
+        let (old, recent) = (ObjectID::random(), ObjectID::random());
+
+        tracker.record_queued(old, start);
+        tracker.record_queued(old, start);
+        tracker.record_queued(recent, start + Duration::from_secs(5));
+        assert_eq!(
+            tracker.hot_objects(start + Duration::from_secs(6), 1)[0].object_id,
+            old
+        );
+
+        // Events that have left the window are no longer reported, and are dropped once newer
+        // events are recorded.
+        let later = start + Duration::from_secs(12);
+        let hot = tracker.hot_objects(later, 10);
+        assert_eq!(hot.len(), 1);
+        assert_eq!(hot[0].object_id, recent);
+
+        tracker.record_queued(recent, later);
+        assert_eq!(tracker.events.len(), 2);
+    }
+}
@@ -12,7 +12,6 @@ use lru::LruCache;
 use mysten_metrics::monitored_scope;
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use prometheus::{register_int_counter_with_registry, IntCounter, Registry};
-use shared_crypto::intent::Intent;
 use std::hash::Hash;
 use std::sync::Arc;
 use sui_types::digests::SenderSignedDataDigest;
@@ -21,10 +20,10 @@ use sui_types::signature::GenericSignature;
 use sui_types::transaction::SenderSignedData;
 use sui_types::{
     committee::Committee,
-    crypto::{AuthoritySignInfoTrait, VerificationObligation},
+    crypto::VerificationObligation,
     digests::CertificateDigest,
     error::{SuiError, SuiResult},
-    message_envelope::{AuthenticatedMessage, Message},
+    message_envelope::{batch_verify_committee_sigs, AuthenticatedMessage, Message},
     messages_checkpoint::SignedCheckpointSummary,
     signature::VerifyParams,
     transaction::{CertifiedTransaction, VerifiedCertificate},
@@ -478,7 +477,7 @@ pub fn batch_verify_certificates(
 ) -> Vec<SuiResult> {
     // certs.data() is assumed to be verified already by the caller.
     let verify_params = VerifyParams::new(Default::default(), Vec::new(), Default::default());
-    match batch_verify(committee, certs, &[]) {
+    match batch_verify_committee_sigs(committee, certs) {
         Ok(_) => vec![Ok(()); certs.len()],
 
         // Verify one by one to find which certs were invalid.
@@ -501,15 +500,11 @@ fn batch_verify(
     let mut obligation = VerificationObligation::default();
 
     for cert in certs {
-        let idx = obligation.add_message(cert.data(), cert.epoch(), Intent::sui_app(cert.scope()));
-        cert.auth_sig()
-            .add_to_verification_obligation(committee, &mut obligation, idx)?;
+        cert.add_to_verification_obligation(committee, &mut obligation)?;
     }
 
     for ckpt in checkpoints {
-        let idx = obligation.add_message(ckpt.data(), ckpt.epoch(), Intent::sui_app(ckpt.scope()));
-        ckpt.auth_sig()
-            .add_to_verification_obligation(committee, &mut obligation, idx)?;
+        ckpt.add_to_verification_obligation(committee, &mut obligation)?;
     }
 
     obligation.verify_all()
@@ -1,29 +1,66 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::subscription_handler::{SubscriptionMetrics, EVENT_DISPATCH_BUFFER_SIZE};
+use crate::subscription_handler::SubscriptionMetrics;
 use futures::Stream;
 use mysten_metrics::metered_channel::Sender;
 use mysten_metrics::spawn_monitored_task;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use prometheus::Registry;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::{Arc, Weak};
+use std::task::{Context, Poll, Waker};
 use sui_json_rpc_types::Filter;
 use sui_types::base_types::ObjectID;
 use sui_types::error::SuiError;
-use tokio::sync::mpsc;
-use tokio_stream::wrappers::ReceiverStream;
-use tracing::{debug, warn};
+use tracing::debug;
 
-type Subscribers<T, F> = Arc<RwLock<BTreeMap<String, (tokio::sync::mpsc::Sender<T>, F)>>>;
+#[cfg(test)]
+#[path = "unit_tests/streamer_tests.rs"]
+mod streamer_tests;
 
-/// The Streamer splits a mpsc channel into multiple mpsc channels using the subscriber's `Filter<T>` object.
-/// Data will be sent to the subscribers in parallel and the subscription will be dropped if it received a send error.
+type Subscribers<S, F> = Arc<RwLock<BTreeMap<String, (Weak<SubscriberBuffer<S>>, F)>>>;
+
+/// An item received by a subscriber.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamItem<S> {
+    Data(S),
+    /// The subscriber fell behind, and this many of the oldest items in its buffer were dropped
+    /// to make room for newer ones. Delivered before any of the items that were kept.
+    Gap {
+        missed: u64,
+    },
+}
+
+/// Buffer between the streamer and a single subscriber. When it is full, the oldest item is
+/// dropped to make room for the newest one, so a slow subscriber never holds up others, and is
+/// never unsubscribed.
+struct SubscriberBuffer<S> {
+    capacity: usize,
+    state: Mutex<BufferState<S>>,
+}
+
+struct BufferState<S> {
+    items: VecDeque<S>,
+    missed: u64,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+/// Receiving end of a subscription. Dropping it unsubscribes.
+pub struct SubscriptionHandle<S> {
+    buffer: Arc<SubscriberBuffer<S>>,
+}
+
+/// The Streamer splits a mpsc channel into multiple subscriptions using the subscriber's `Filter<T>` object.
+/// Data is buffered for each subscriber separately, and subscribers that fall behind lose their oldest data
+/// rather than their subscription, so subscriptions last as long as the streamer itself, across epochs.
 pub struct Streamer<T, S, F: Filter<T>> {
     streamer_queue: Sender<T>,
     subscribers: Subscribers<S, F>,
+    buffer: usize,
 }
 
 impl<T, S, F> Streamer<T, S, F>
@@ -54,6 +91,7 @@ where
         let streamer = Self {
             streamer_queue: tx,
             subscribers: Default::default(),
+            buffer,
         };
         let mut rx = rx;
         let subscribers = streamer.subscribers.clone();
@@ -67,6 +105,14 @@ where
                 )
                 .await;
             }
+
+            // Only reached once the streamer is dropped. Let subscribers know that no more data is
+            // coming, instead of leaving them waiting.
+            for (subscriber, _) in subscribers.read().values() {
+                if let Some(subscriber) = subscriber.upgrade() {
+                    subscriber.close();
+                }
+            }
         });
         streamer
     }
@@ -80,43 +126,48 @@ where
         let success_counter = metrics
             .streaming_success
             .with_label_values(&[metrics_label]);
-        let failure_counter = metrics
-            .streaming_failure
-            .with_label_values(&[metrics_label]);
         let subscriber_count = metrics
             .streaming_active_subscriber_number
             .with_label_values(&[metrics_label]);
+        let dropped_counter = metrics
+            .streaming_dropped
+            .with_label_values(&[metrics_label]);
+        let max_lag = metrics
+            .streaming_max_subscriber_lag
+            .with_label_values(&[metrics_label]);
 
-        let to_remove = {
+        let (to_remove, lag) = {
             let mut to_remove = vec![];
+            let mut lag = 0;
             let subscribers_snapshot = subscribers.read();
             subscriber_count.set(subscribers_snapshot.len() as i64);
 
             for (id, (subscriber, filter)) in subscribers_snapshot.iter() {
+                let Some(subscriber) = subscriber.upgrade() else {
+                    // The subscriber's handle has been dropped.
+                    to_remove.push(id.clone());
+                    continue;
+                };
                 if !(filter.matches(&data)) {
                     continue;
                 }
-                let data = data.clone();
-                match subscriber.try_send(data.into()) {
-                    Ok(_) => {
-                        debug!(subscription_id = id, "Streaming data to subscriber.");
-                        success_counter.inc();
-                    }
-                    Err(e) => {
-                        warn!(
-                            subscription_id = id,
-                            "Error when streaming data, removing subscriber. Error: {e}"
-                        );
-                        // It does not matter what the error is - channel full or closed, we remove the subscriber.
-                        // In the case of a full channel, this nudges the subscriber to catch up separately and not
-                        // miss any data.
-                        to_remove.push(id.clone());
-                        failure_counter.inc();
-                    }
+
+                if subscriber.push(data.clone().into()) {
+                    debug!(
+                        subscription_id = id,
+                        "Subscriber is lagging, dropped its oldest data."
+                    );
+                    dropped_counter.inc();
+                } else {
+                    debug!(subscription_id = id, "Streaming data to subscriber.");
                 }
+                success_counter.inc();
+                lag = lag.max(subscriber.len());
             }
-            to_remove
+            (to_remove, lag)
         };
+
+        max_lag.set(lag as i64);
         if !to_remove.is_empty() {
             let mut subscribers = subscribers.write();
             for sub in to_remove {
@@ -126,12 +177,13 @@ where
     }
 
     /// Subscribe to the data stream filtered by the filter object.
-    pub fn subscribe(&self, filter: F) -> impl Stream<Item = S> {
-        let (tx, rx) = mpsc::channel::<S>(EVENT_DISPATCH_BUFFER_SIZE);
-        self.subscribers
-            .write()
-            .insert(ObjectID::random().to_string(), (tx, filter));
-        ReceiverStream::new(rx)
+    pub fn subscribe(&self, filter: F) -> SubscriptionHandle<S> {
+        let buffer = Arc::new(SubscriberBuffer::new(self.buffer));
+        self.subscribers.write().insert(
+            ObjectID::random().to_string(),
+            (Arc::downgrade(&buffer), filter),
+        );
+        SubscriptionHandle { buffer }
     }
 
     pub async fn send(&self, data: T) -> Result<(), SuiError> {
@@ -143,3 +195,66 @@ where
             })
     }
 }
+
+impl<S> SubscriberBuffer<S> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(BufferState {
+                items: VecDeque::with_capacity(capacity),
+                missed: 0,
+                closed: false,
+                waker: None,
+            }),
+        }
+    }
+
+    /// Add `item` to the buffer, returning whether the oldest item had to be dropped to fit it.
+    fn push(&self, item: S) -> bool {
+        let mut state = self.state.lock();
+        let dropped = state.items.len() >= self.capacity;
+        if dropped {
+            state.items.pop_front();
+            state.missed += 1;
+        }
+        state.items.push_back(item);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        dropped
+    }
+
+    /// No more items will be pushed. The subscriber's stream ends once it has received everything
+    /// already in the buffer.
+    fn close(&self) {
+        let mut state = self.state.lock();
+        state.closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.state.lock().items.len()
+    }
+}
+
+impl<S> Stream for SubscriptionHandle<S> {
+    type Item = StreamItem<S>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.buffer.state.lock();
+        if state.missed > 0 {
+            let missed = std::mem::take(&mut state.missed);
+            return Poll::Ready(Some(StreamItem::Gap { missed }));
+        }
+        if let Some(item) = state.items.pop_front() {
+            return Poll::Ready(Some(StreamItem::Data(item)));
+        }
+        if state.closed {
+            return Poll::Ready(None);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
@@ -7,10 +7,9 @@ use prometheus::{
     register_int_counter_vec_with_registry, register_int_gauge_vec_with_registry, IntCounterVec,
     IntGaugeVec, Registry,
 };
-use tokio_stream::Stream;
 use tracing::{error, instrument, trace};
 
-use crate::streamer::Streamer;
+use crate::streamer::{Streamer, SubscriptionHandle};
 use sui_json_rpc_types::{
     EffectsWithInput, EventFilter, SuiTransactionBlockEffects, SuiTransactionBlockEvents,
     TransactionFilter,
@@ -27,8 +26,9 @@ pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
 
 pub struct SubscriptionMetrics {
     pub streaming_success: IntCounterVec,
-    pub streaming_failure: IntCounterVec,
+    pub streaming_dropped: IntCounterVec,
     pub streaming_active_subscriber_number: IntGaugeVec,
+    pub streaming_max_subscriber_lag: IntGaugeVec,
 }
 
 impl SubscriptionMetrics {
@@ -41,9 +41,9 @@ impl SubscriptionMetrics {
                 registry,
             )
             .unwrap(),
-            streaming_failure: register_int_counter_vec_with_registry!(
-                "streaming_failure",
-                "Total number of items that fail to be streamed",
+            streaming_dropped: register_int_counter_vec_with_registry!(
+                "streaming_dropped",
+                "Total number of items dropped from the buffers of subscribers that fell behind",
                 &["type"],
                 registry,
             )
@@ -55,6 +55,13 @@ impl SubscriptionMetrics {
                 registry,
             )
             .unwrap(),
+            streaming_max_subscriber_lag: register_int_gauge_vec_with_registry!(
+                "streaming_max_subscriber_lag",
+                "Number of items waiting to be received by the subscriber that is furthest behind",
+                &["type"],
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -108,14 +115,18 @@ impl SubscriptionHandler {
         Ok(())
     }
 
-    pub fn subscribe_events(&self, filter: EventFilter) -> impl Stream<Item = SuiEvent> {
+    /// Subscribe to events matching `filter`. Subscribers that fall behind stay subscribed, and
+    /// receive a [`crate::streamer::StreamItem::Gap`] in place of the events they missed.
+    pub fn subscribe_events(&self, filter: EventFilter) -> SubscriptionHandle<SuiEvent> {
         self.event_streamer.subscribe(filter)
     }
 
+    /// Subscribe to the effects of transactions matching `filter`, with the same policy for
+    /// subscribers that fall behind as [`Self::subscribe_events`].
     pub fn subscribe_transactions(
         &self,
         filter: TransactionFilter,
-    ) -> impl Stream<Item = SuiTransactionBlockEffects> {
+    ) -> SubscriptionHandle<SuiTransactionBlockEffects> {
         self.transaction_streamer.subscribe(filter)
     }
 }
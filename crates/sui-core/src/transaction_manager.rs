@@ -5,6 +5,7 @@ use std::{
     cmp::max,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use lru::LruCache;
@@ -19,13 +20,14 @@ use sui_types::{
     transaction::{TransactionDataAPI, VerifiedCertificate},
 };
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::{error, trace, warn};
+use tracing::{error, info, trace, warn};
 
 use crate::authority::{
-    authority_per_epoch_store::AuthorityPerEpochStore,
+    authority_per_epoch_store::{AuthorityPerEpochStore, PendingExecutionState},
     authority_store::{InputKey, LockMode},
 };
-use crate::authority::{AuthorityMetrics, AuthorityStore};
+use crate::authority::{AuthorityMetrics, AuthorityState, AuthorityStore};
+use crate::object_contention::{HotObject, ObjectContentionTracker, DEFAULT_CONTENTION_WINDOW};
 use tap::TapOptional;
 
 #[cfg(test)]
@@ -35,6 +37,12 @@ mod transaction_manager_tests;
 /// Minimum capacity of HashMaps used in TransactionManager.
 const MIN_HASHMAP_CAPACITY: usize = 1000;
 
+/// Number of the most contended shared objects reported in metrics.
+const HOT_OBJECTS_METRICS_LIMIT: usize = 10;
+
+/// How often the metrics of the most contended shared objects are updated.
+const HOT_OBJECTS_METRICS_INTERVAL: Duration = Duration::from_secs(10);
+
 /// TransactionManager is responsible for managing object dependencies of pending transactions,
 /// and publishing a stream of certified transactions (certificates) ready to execute.
 /// It receives certificates from Narwhal, validator RPC handlers, and checkpoint executor.
@@ -66,6 +74,10 @@ struct PendingCertificate {
     acquiring_locks: BTreeMap<InputKey, LockMode>,
     // Input object locks that have been acquired.
     acquired_locks: BTreeMap<InputKey, LockMode>,
+    // Shared input objects of the transaction, whose contention is tracked.
+    shared_objects: BTreeSet<ObjectID>,
+    // When the certificate was enqueued, to measure how long it waits for its input objects.
+    enqueue_time: Instant,
 }
 
 /// LockQueue is a queue of transactions waiting or holding a lock on an object.
@@ -256,6 +268,13 @@ struct Inner {
     pending_certificates: HashMap<TransactionDigest, PendingCertificate>,
     // Maps executing transaction digests to their acquired input object locks.
     executing_certificates: HashMap<TransactionDigest, BTreeMap<InputKey, LockMode>>,
+
+    // Certificates queued for and waiting on shared objects, over a recent window.
+    object_contention: ObjectContentionTracker,
+    // When the metrics of the most contended shared objects were last updated.
+    hot_objects_metrics_updated: Instant,
+
+    metrics: Arc<AuthorityMetrics>,
 }
 
 impl Inner {
@@ -264,9 +283,12 @@ impl Inner {
             epoch,
             lock_waiters: HashMap::with_capacity(MIN_HASHMAP_CAPACITY),
             input_objects: HashMap::with_capacity(MIN_HASHMAP_CAPACITY),
-            available_objects_cache: AvailableObjectsCache::new(metrics),
+            available_objects_cache: AvailableObjectsCache::new(metrics.clone()),
             pending_certificates: HashMap::with_capacity(MIN_HASHMAP_CAPACITY),
             executing_certificates: HashMap::with_capacity(MIN_HASHMAP_CAPACITY),
+            object_contention: ObjectContentionTracker::new(DEFAULT_CONTENTION_WINDOW),
+            hot_objects_metrics_updated: Instant::now(),
+            metrics,
         }
     }
 
@@ -319,10 +341,19 @@ impl Inner {
             self.input_objects.remove(&input_key.id());
         }
 
+        let now = Instant::now();
         for digest in digests {
             // Pending certificate must exist.
             let pending_cert = self.pending_certificates.get_mut(&digest).unwrap();
             let lock_mode = pending_cert.acquiring_locks.remove(&input_key).unwrap();
+            if pending_cert.shared_objects.contains(&input_key.id()) {
+                let wait = now.saturating_duration_since(pending_cert.enqueue_time);
+                self.object_contention
+                    .record_acquired(input_key.id(), wait, now);
+                self.metrics
+                    .transaction_manager_shared_object_wait_latency
+                    .observe(wait.as_secs_f64());
+            }
             assert!(pending_cert
                 .acquired_locks
                 .insert(input_key, lock_mode)
@@ -377,10 +408,64 @@ impl TransactionManager {
             inner: RwLock::new(Inner::new(epoch_store.epoch(), metrics)),
             tx_ready_certificates,
         };
+        transaction_manager.recover(epoch_store);
         transaction_manager
-            .enqueue(epoch_store.all_pending_execution().unwrap(), epoch_store)
+    }
+
+    /// Checks how far each certificate that was pending execution when the node stopped got
+    /// through execution, reports it, and enqueues the ones that still need to execute.
+    fn recover(&self, epoch_store: &AuthorityPerEpochStore) {
+        let audit = epoch_store
+            .audit_pending_execution(|digest| self.authority_store.is_tx_already_executed(digest))
+            .expect("Failed to audit pending certificates");
+
+        for entry in &audit.entries {
+            if entry.state == PendingExecutionState::Executed {
+                // Ensure the transaction will not be retried after the next restart.
+                epoch_store
+                    .remove_pending_execution(&entry.digest)
+                    .expect("Failed to remove executed certificate");
+            }
+        }
+
+        let pending = audit.count(PendingExecutionState::Pending);
+        let partially_executed = audit.count(PendingExecutionState::PartiallyExecuted);
+        let executed = audit.count(PendingExecutionState::Executed);
+        let oldest_age = audit.oldest_age(AuthorityState::unixtime_now_ms());
+
+        for (state, count) in [
+            (PendingExecutionState::Pending, pending),
+            (PendingExecutionState::PartiallyExecuted, partially_executed),
+            (PendingExecutionState::Executed, executed),
+        ] {
+            self.metrics
+                .transaction_manager_recovered_certificates
+                .with_label_values(&[state.as_str()])
+                .set(count as i64);
+        }
+        self.metrics
+            .transaction_manager_oldest_recovered_certificate_age_ms
+            .set(oldest_age.map_or(0, |age| age.as_millis() as i64));
+
+        if partially_executed > 0 || executed > 0 {
+            warn!(
+                pending,
+                partially_executed,
+                executed,
+                ?oldest_age,
+                "Recovered certificates pending execution after an unclean shutdown. \
+                 Partially executed certificates are requeued, executed ones are removed"
+            );
+        } else {
+            info!(
+                pending,
+                ?oldest_age,
+                "Recovered certificates pending execution"
+            );
+        }
+
+        self.enqueue(epoch_store.all_pending_execution().unwrap(), epoch_store)
             .expect("Initialize TransactionManager with pending certificates failed.");
-        transaction_manager
     }
 
     /// Enqueues certificates / verified transactions into TransactionManager. Once all of the input objects are available
@@ -560,12 +645,23 @@ impl TransactionManager {
 
         let mut pending = Vec::new();
 
+        let enqueue_time = Instant::now();
         for (cert, expected_effects_digest, input_object_locks) in certs {
+            let shared_objects = cert
+                .data()
+                .intent_message()
+                .value
+                .shared_input_objects()
+                .into_iter()
+                .map(|object| object.id)
+                .collect();
             pending.push(PendingCertificate {
                 certificate: cert,
                 expected_effects_digest,
                 acquiring_locks: input_object_locks,
                 acquired_locks: BTreeMap::new(),
+                shared_objects,
+                enqueue_time,
             });
         }
 
@@ -656,6 +752,11 @@ impl TransactionManager {
                     }
                 }
                 if acquire {
+                    if pending_cert.shared_objects.contains(&key.id()) {
+                        inner
+                            .object_contention
+                            .record_queued(key.id(), enqueue_time);
+                    }
                     pending_cert.acquiring_locks.insert(key, lock_mode);
                     let input_count = inner.input_objects.entry(key.id()).or_default();
                     *input_count += 1;
@@ -697,6 +798,7 @@ impl TransactionManager {
             .transaction_manager_num_pending_certificates
             .set(inner.pending_certificates.len() as i64);
 
+        self.maybe_update_hot_objects_metrics(&mut inner);
         inner.maybe_reserve_capacity();
 
         Ok(())
@@ -856,6 +958,43 @@ impl TransactionManager {
             .collect()
     }
 
+    /// Returns the `limit` shared objects that the most certificates have queued for recently,
+    /// with how long certificates waited for them.
+    pub fn hot_objects(&self, limit: usize) -> Vec<HotObject> {
+        let inner = self.inner.read();
+        inner.object_contention.hot_objects(Instant::now(), limit)
+    }
+
+    fn maybe_update_hot_objects_metrics(&self, inner: &mut Inner) {
+        let now = Instant::now();
+        if now.saturating_duration_since(inner.hot_objects_metrics_updated)
+            < HOT_OBJECTS_METRICS_INTERVAL
+        {
+            return;
+        }
+        inner.hot_objects_metrics_updated = now;
+
+        // Only the objects that are currently hot are reported, to bound the number of series.
+        self.metrics
+            .transaction_manager_hot_object_queued_certificates
+            .reset();
+        self.metrics.transaction_manager_hot_object_wait_ms.reset();
+        for object in inner
+            .object_contention
+            .hot_objects(now, HOT_OBJECTS_METRICS_LIMIT)
+        {
+            let object_id = object.object_id.to_string();
+            self.metrics
+                .transaction_manager_hot_object_queued_certificates
+                .with_label_values(&[&object_id])
+                .set(object.queued_certificates as i64);
+            self.metrics
+                .transaction_manager_hot_object_wait_ms
+                .with_label_values(&[&object_id])
+                .set(object.total_wait.as_millis() as i64);
+        }
+    }
+
     // Returns the number of transactions pending or being executed right now.
     pub(crate) fn inflight_queue_len(&self) -> usize {
         let inner = self.inner.read();
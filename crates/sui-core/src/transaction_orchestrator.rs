@@ -28,9 +28,12 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
 use sui_types::base_types::TransactionDigest;
-use sui_types::effects::{TransactionEffectsAPI, VerifiedCertifiedTransactionEffects};
+use sui_types::effects::{
+    CertifiedTransactionEffects, TransactionEffectsAPI, VerifiedCertifiedTransactionEffects,
+};
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
+use sui_types::finality_proof::FinalityProof;
 use sui_types::object::Object;
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
@@ -476,6 +479,36 @@ where
         self.quorum_driver_handler.subscribe_to_effects()
     }
 
+    /// Packages the effects certificate of a finalized transaction with the certified checkpoint
+    /// that includes it, into a proof of finality that light clients can verify offline with
+    /// `sui_types::finality_proof::verify_finality_proof`. Returns `None` until this node has
+    /// synced the checkpoint, or if the transaction was executed in an earlier epoch.
+    pub fn finality_proof(
+        &self,
+        effects_cert: CertifiedTransactionEffects,
+    ) -> SuiResult<Option<FinalityProof>> {
+        let epoch_store = self.validator_state.load_epoch_store_one_call_per_task();
+        if effects_cert.epoch() != epoch_store.epoch() {
+            return Ok(None);
+        }
+
+        let Some(checkpoint) = self
+            .validator_state
+            .get_transaction_checkpoint(effects_cert.transaction_digest(), &epoch_store)?
+        else {
+            return Ok(None);
+        };
+        let contents = self
+            .validator_state
+            .get_checkpoint_contents(checkpoint.content_digest)?;
+
+        Ok(Some(FinalityProof::new(
+            effects_cert,
+            checkpoint.into(),
+            contents,
+        )))
+    }
+
     fn update_metrics(
         &'_ self,
         transaction: &VerifiedTransaction,
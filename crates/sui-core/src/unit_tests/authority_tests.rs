@@ -35,6 +35,7 @@ use sui_types::dynamic_field::DynamicFieldType;
 use sui_types::effects::TransactionEffects;
 use sui_types::epoch_data::EpochData;
 use sui_types::error::UserInputError;
+use sui_types::execution::ObjectLineage;
 use sui_types::execution_status::{ExecutionFailureStatus, ExecutionStatus};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages_consensus::ConsensusCommitPrologue;
@@ -108,6 +109,9 @@ impl TestCallArg {
                 initial_shared_version: *initial_shared_version,
                 mutable: true,
             },
+            Owner::ConsensusV2 { .. } => {
+                panic!("Consensus-owned object {object_id} cannot be used as an argument yet")
+            }
         }
     }
 }
@@ -585,7 +589,10 @@ async fn test_dev_inspect_dynamic_field() {
 
     // add a dynamic field to an object
     let DevInspectResults {
-        effects, results, ..
+        effects,
+        results,
+        child_object_access,
+        ..
     } = call_dev_inspect(
         &fullnode,
         &sender,
@@ -615,6 +622,11 @@ async fn test_dev_inspect_dynamic_field() {
     } = exec_results;
     assert_eq!(mutable_reference_outputs.len(), 1);
     assert!(return_values.is_empty());
+    // the field is looked up before being added, but does not exist yet
+    let child_object_access = child_object_access.unwrap();
+    assert!(child_object_access.num_accessed > 0);
+    assert_eq!(child_object_access.num_loaded, 0);
+    assert_eq!(child_object_access.loaded_bytes, 0);
 }
 
 #[tokio::test]
@@ -3451,6 +3463,103 @@ async fn test_store_revert_unwrap_move_call() {
     let gas = db.get_object(&gas_object_id).unwrap().unwrap();
     assert_eq!(gas.version(), wrap_effects.gas_object().0 .1);
 }
+
+#[tokio::test]
+async fn test_store_object_lineage() {
+    let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+        config.set_record_object_lineage_for_testing(true);
+        config
+    });
+
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, object_basics) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+    let db = &authority_state.database;
+
+    let create_effects = create_move_object(
+        &object_basics.0,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    assert!(create_effects.status().is_ok());
+    let object_id = create_effects.created()[0].0 .0;
+
+    // Nothing is recorded for transactions that do not wrap or unwrap objects
+    assert!(db
+        .get_object_lineage(create_effects.transaction_digest())
+        .unwrap()
+        .is_none());
+
+    let wrap_effects = wrap_object(
+        &object_basics.0,
+        &authority_state,
+        &object_id,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    assert!(wrap_effects.status().is_ok());
+    let wrapper_id = wrap_effects.created()[0].0 .0;
+
+    let lineage = db
+        .get_object_lineage(wrap_effects.transaction_digest())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        lineage,
+        BTreeMap::from([(
+            object_id,
+            ObjectLineage::Wrapped {
+                container: Some(wrapper_id)
+            }
+        )])
+    );
+
+    let unwrap_effects = call_move(
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+        &object_basics.0,
+        "object_basics",
+        "unwrap",
+        vec![],
+        vec![TestCallArg::Object(wrapper_id)],
+    )
+    .await
+    .unwrap();
+    assert!(unwrap_effects.status().is_ok());
+
+    let lineage = db
+        .get_object_lineage(unwrap_effects.transaction_digest())
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        lineage,
+        BTreeMap::from([(
+            object_id,
+            ObjectLineage::Unwrapped {
+                container: Some(wrapper_id)
+            }
+        )])
+    );
+
+    // Reverting the transaction removes its lineage
+    db.revert_state_update(unwrap_effects.transaction_digest())
+        .await
+        .unwrap();
+    assert!(db
+        .get_object_lineage(unwrap_effects.transaction_digest())
+        .unwrap()
+        .is_none());
+}
 #[tokio::test]
 async fn test_store_get_dynamic_object() {
     let (_, fields) = create_and_retrieve_df_info(ident_str!("add_ofield")).await;
@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::{FutureExt, StreamExt};
+
+use super::*;
+
+#[test]
+fn test_lagging_subscriber_drops_oldest() {
+    let buffer = Arc::new(SubscriberBuffer::new(3));
+    let mut handle = SubscriptionHandle {
+        buffer: buffer.clone(),
+    };
+
+    for i in 0..5 {
+        assert_eq!(buffer.push(i), i >= 3);
+    }
+
+    // The gap is reported before the items that were kept, which are the newest ones.
+    assert_eq!(
+        handle.next().now_or_never(),
+        Some(Some(StreamItem::Gap { missed: 2 }))
+    );
+    for i in 2..5 {
+        assert_eq!(
+            handle.next().now_or_never(),
+            Some(Some(StreamItem::Data(i)))
+        );
+    }
+    assert_eq!(handle.next().now_or_never(), None);
+
+    // Having caught up, the subscriber receives new items without a gap.
+    buffer.push(5);
+    assert_eq!(
+        handle.next().now_or_never(),
+        Some(Some(StreamItem::Data(5)))
+    );
+}
+
+#[test]
+fn test_closed_subscription_drains_buffer() {
+    let buffer = Arc::new(SubscriberBuffer::new(3));
+    let mut handle = SubscriptionHandle {
+        buffer: buffer.clone(),
+    };
+
+    buffer.push(0);
+    buffer.close();
+
+    assert_eq!(
+        handle.next().now_or_never(),
+        Some(Some(StreamItem::Data(0)))
+    );
+    assert_eq!(handle.next().now_or_never(), Some(None));
+}
@@ -3,13 +3,15 @@
 
 use std::{time::Duration, vec};
 
+use shared_crypto::intent::{Intent, IntentScope};
 use sui_test_transaction_builder::TestTransactionBuilder;
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
 use sui_types::transaction::VerifiedTransaction;
 use sui_types::{
     base_types::ObjectID,
-    crypto::deterministic_random_account_key,
+    crypto::{deterministic_random_account_key, AuthoritySignInfo},
     digests::TransactionEffectsDigest,
+    effects::TransactionEffects,
     object::Object,
     transaction::{CallArg, ObjectArg},
     SUI_FRAMEWORK_PACKAGE_ID,
@@ -166,6 +168,58 @@ async fn transaction_manager_basics() {
     transaction_manager.check_empty_for_testing();
 }
 
+#[tokio::test(flavor = "current_thread", start_paused = true)]
+async fn transaction_manager_recovery() {
+    // Initialize an authority state.
+    let (owner, _keypair) = deterministic_random_account_key();
+    let gas_objects: Vec<Object> = (0..2)
+        .map(|_| Object::with_id_owner_for_testing(ObjectID::random(), owner))
+        .collect();
+    let state = init_state_with_objects(gas_objects.clone()).await;
+    let epoch_store = state.epoch_store_for_testing();
+
+    // Leave behind one certificate that never started executing, and one whose execution was
+    // interrupted after its effects were signed.
+    let pending = make_transaction(gas_objects[0].clone(), vec![]);
+    let interrupted = make_transaction(gas_objects[1].clone(), vec![]);
+    epoch_store
+        .insert_pending_execution(&[
+            pending.clone().serializable(),
+            interrupted.clone().serializable(),
+        ])
+        .unwrap();
+    let effects_signature = AuthoritySignInfo::new(
+        epoch_store.epoch(),
+        &TransactionEffects::default(),
+        Intent::sui_app(IntentScope::TransactionEffects),
+        state.name,
+        &*state.secret,
+    );
+    epoch_store
+        .insert_tx_cert_and_effects_signature(interrupted.digest(), None, Some(&effects_signature))
+        .unwrap();
+
+    // A restarted TM audits and requeues both of them.
+    let (transaction_manager, mut rx_ready_certificates) = make_transaction_manager(&state);
+    let recovered = &state.metrics.transaction_manager_recovered_certificates;
+    assert_eq!(recovered.with_label_values(&["pending"]).get(), 1);
+    assert_eq!(
+        recovered.with_label_values(&["partially_executed"]).get(),
+        1
+    );
+    assert_eq!(recovered.with_label_values(&["executed"]).get(), 0);
+
+    let audit = epoch_store.audit_pending_execution(|_| Ok(false)).unwrap();
+    assert!(audit
+        .entries
+        .iter()
+        .all(|entry| entry.enqueued_at_ms.is_some()));
+
+    rx_ready_certificates.recv().await.unwrap();
+    rx_ready_certificates.recv().await.unwrap();
+    assert_eq!(transaction_manager.inflight_queue_len(), 2);
+}
+
 #[tokio::test(flavor = "current_thread", start_paused = true)]
 async fn transaction_manager_read_lock() {
     // Initialize an authority state, with gas objects and a shared object.
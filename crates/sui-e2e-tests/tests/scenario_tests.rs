@@ -0,0 +1,47 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_macros::sim_test;
+use sui_types::gas_coin::MIST_PER_SUI;
+use test_cluster::scenario::{created_objects, ScenarioBuilder};
+
+#[sim_test]
+async fn test_shared_counter_between_actors() {
+    let scenario = ScenarioBuilder::new()
+        .with_actor("alice", 10 * MIST_PER_SUI)
+        .with_actor("bob", 10 * MIST_PER_SUI)
+        .build()
+        .await;
+    assert_eq!(scenario.balance("bob").await, (10 * MIST_PER_SUI) as u128);
+
+    let package = scenario.publish_examples("alice", "basics").await;
+    let response = scenario
+        .call("alice", package, "counter", "create")
+        .execute()
+        .await;
+    let counter = created_objects(&response)[0];
+
+    scenario
+        .call("bob", package, "counter", "increment")
+        .object_arg(counter)
+        .execute()
+        .await;
+
+    // The counter survives reconfiguration.
+    scenario.advance_epoch().await;
+    scenario
+        .call("alice", package, "counter", "assert_value")
+        .object_arg(counter)
+        .pure_arg(1u64)
+        .execute()
+        .await;
+
+    // Only the creator of the counter can set its value.
+    let response = scenario
+        .call("bob", package, "counter", "set_value")
+        .object_arg(counter)
+        .pure_arg(10u64)
+        .execute_may_fail()
+        .await;
+    assert!(!response.status_ok().unwrap());
+}
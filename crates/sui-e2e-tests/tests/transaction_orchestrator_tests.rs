@@ -13,9 +13,11 @@ use sui_storage::key_value_store_metrics::KeyValueStoreMetrics;
 use sui_test_transaction_builder::{
     batch_make_transfer_transactions, make_transfer_sui_transaction,
 };
+use sui_types::effects::CertifiedTransactionEffects;
+use sui_types::finality_proof::verify_finality_proof;
 use sui_types::quorum_driver_types::{
-    ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
-    FinalizedEffects, QuorumDriverError,
+    EffectsFinalityInfo, ExecuteTransactionRequest, ExecuteTransactionRequestType,
+    ExecuteTransactionResponse, FinalizedEffects, QuorumDriverError,
 };
 use sui_types::transaction::Transaction;
 use test_cluster::TestClusterBuilder;
@@ -300,6 +302,50 @@ async fn test_tx_across_epoch_boundaries() {
     info!("test completed in {:?}", start.elapsed());
 }
 
+#[sim_test]
+async fn test_finality_proof() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+    let tx = make_transfer_sui_transaction(&test_cluster.wallet, None, None).await;
+    let digest = *tx.digest();
+
+    let (orchestrator, committee) = test_cluster.fullnode_handle.sui_node.with(|node| {
+        (
+            node.transaction_orchestrator().unwrap(),
+            node.state().clone_committee_for_testing(),
+        )
+    });
+
+    let ExecuteTransactionResponse::EffectsCert(result) = execute_with_orchestrator(
+        &orchestrator,
+        tx,
+        ExecuteTransactionRequestType::WaitForLocalExecution,
+    )
+    .await
+    .unwrap_or_else(|e| panic!("Failed to execute transaction {:?}: {:?}", digest, e));
+    let (finalized_effects, _, _) = *result;
+    let EffectsFinalityInfo::Certified(sig) = finalized_effects.finality_info else {
+        panic!("Expected certified effects for {:?}", digest);
+    };
+    let effects_cert =
+        CertifiedTransactionEffects::new_from_data_and_sig(finalized_effects.effects.clone(), sig);
+
+    // The proof is only available once the fullnode has synced the checkpoint that includes the
+    // transaction.
+    let proof = timeout(Duration::from_secs(30), async {
+        loop {
+            if let Some(proof) = orchestrator.finality_proof(effects_cert.clone()).unwrap() {
+                break proof;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    let effects = verify_finality_proof(&proof.to_bytes(), &committee).unwrap();
+    assert_eq!(effects, finalized_effects.effects);
+}
+
 async fn execute_with_orchestrator(
     orchestrator: &TransactiondOrchestrator<NetworkAuthorityClient>,
     txn: Transaction,
@@ -187,6 +187,7 @@ async fn test_conflicting_jwks() {
     use futures::StreamExt;
     use std::collections::HashSet;
     use std::sync::{Arc, Mutex};
+    use sui_core::streamer::StreamItem;
     use sui_json_rpc_types::SuiTransactionBlockEffectsAPI;
     use sui_json_rpc_types::TransactionFilter;
     use sui_types::base_types::ObjectID;
@@ -208,7 +209,10 @@ async fn test_conflicting_jwks() {
         let state = node.state();
 
         tokio::spawn(async move {
-            while let Some(tx) = txns.next().await {
+            while let Some(item) = txns.next().await {
+                let StreamItem::Data(tx) = item else {
+                    panic!("Subscription fell behind, and missed transactions");
+                };
                 let digest = *tx.transaction_digest();
                 let tx = state
                     .database
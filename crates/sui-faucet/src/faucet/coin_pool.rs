@@ -0,0 +1,104 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use prometheus::IntGauge;
+use sui_types::base_types::ObjectID;
+use tokio::sync::Mutex;
+
+use crate::metrics::FaucetMetrics;
+use crate::FaucetConfig;
+
+/// The most coins that are smashed together into one in a single merge transaction, kept well
+/// below the protocol's limit on the number of gas payment objects.
+const MAX_DUST_COINS_PER_MERGE: usize = 200;
+
+/// Book-keeping for the gas coins backing the faucet's (non-batch) coin queue.
+///
+/// The pool counts every coin it manages, whether it is waiting in the queue or in flight, and
+/// separately tracks "dust" -- coins whose balance fell too low to serve a request. When
+/// rebalancing is enabled, dust is periodically merged back into usable coins, and whenever the
+/// pool holds fewer than `target_size` coins, a large coin is split into coins of `coin_balance`
+/// each, so that the faucet does not run out of coins under load.
+pub(crate) struct CoinPool {
+    /// Whether the faucet should periodically split and merge coins to maintain the pool.
+    pub rebalance_enabled: bool,
+    /// Number of coins the pool tries to maintain.
+    pub target_size: usize,
+    /// Balance of each coin created when splitting.
+    pub coin_balance: u64,
+    /// Smallest balance (excluding gas) a coin needs to serve a request.
+    pub min_balance: u64,
+    size: AtomicUsize,
+    dust: Mutex<Vec<ObjectID>>,
+    size_gauge: IntGauge,
+    dust_gauge: IntGauge,
+}
+
+impl CoinPool {
+    pub fn new(config: &FaucetConfig, initial_size: usize, metrics: &FaucetMetrics) -> Self {
+        metrics.current_pool_size.set(initial_size as i64);
+        metrics.pool_target_size.set(config.pool_target_size as i64);
+        Self {
+            rebalance_enabled: config.pool_rebalance_enabled,
+            target_size: config.pool_target_size,
+            coin_balance: config.pool_coin_balance,
+            min_balance: config.amount * config.num_coins as u64,
+            size: AtomicUsize::new(initial_size),
+            dust: Mutex::new(vec![]),
+            size_gauge: metrics.current_pool_size.clone(),
+            dust_gauge: metrics.current_dust_coins.clone(),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size.load(Ordering::Relaxed)
+    }
+
+    /// Number of coins missing from the pool to reach its target size.
+    pub fn deficit(&self) -> usize {
+        self.target_size.saturating_sub(self.size())
+    }
+
+    /// Record `n` new coins joining the pool.
+    pub fn add(&self, n: usize) {
+        let prev = self.size.fetch_add(n, Ordering::Relaxed);
+        self.size_gauge.set((prev + n) as i64);
+    }
+
+    /// Record `n` coins leaving the pool, either discarded or set aside as dust.
+    pub fn remove(&self, n: usize) {
+        let prev = self
+            .size
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |size| {
+                Some(size.saturating_sub(n))
+            })
+            .unwrap();
+        self.size_gauge.set(prev.saturating_sub(n) as i64);
+    }
+
+    /// Set aside a coin whose balance is too low to serve requests, to be merged later.
+    pub async fn push_dust(&self, coin_id: ObjectID) {
+        self.remove(1);
+        let mut dust = self.dust.lock().await;
+        dust.push(coin_id);
+        self.dust_gauge.set(dust.len() as i64);
+    }
+
+    /// Take up to a merge transaction's worth of dust coins out of the pool.
+    pub async fn take_dust(&self) -> Vec<ObjectID> {
+        let mut dust = self.dust.lock().await;
+        let split_at = dust.len().saturating_sub(MAX_DUST_COINS_PER_MERGE);
+        let taken = dust.split_off(split_at);
+        self.dust_gauge.set(dust.len() as i64);
+        taken
+    }
+
+    /// Return dust coins that could not be merged yet.
+    pub async fn return_dust(&self, coins: Vec<ObjectID>) {
+        let mut dust = self.dust.lock().await;
+        dust.extend(coins);
+        self.dust_gauge.set(dust.len() as i64);
+    }
+}
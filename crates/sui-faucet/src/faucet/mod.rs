@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
 use uuid::Uuid;
 
+mod coin_pool;
 mod simple_faucet;
 mod write_ahead_log;
 pub use self::simple_faucet::SimpleFaucet;
@@ -68,6 +69,8 @@ pub trait Faucet {
 
 pub const DEFAULT_AMOUNT: u64 = 1_000_000_000;
 pub const DEFAULT_NUM_OF_COINS: usize = 1;
+pub const DEFAULT_POOL_TARGET_SIZE: usize = 100;
+pub const DEFAULT_POOL_COIN_BALANCE: u64 = 100 * DEFAULT_AMOUNT;
 
 #[derive(Parser, Clone)]
 #[clap(
@@ -114,6 +117,19 @@ pub struct FaucetConfig {
 
     #[clap(long, action = clap::ArgAction::Set, default_value_t = false)]
     pub batch_enabled: bool,
+
+    /// Periodically split and merge gas coins to keep `pool_target_size` coins available.
+    #[clap(long, action = clap::ArgAction::Set, default_value_t = false)]
+    pub pool_rebalance_enabled: bool,
+
+    #[clap(long, default_value_t = DEFAULT_POOL_TARGET_SIZE)]
+    pub pool_target_size: usize,
+
+    #[clap(long, default_value_t = DEFAULT_POOL_COIN_BALANCE)]
+    pub pool_coin_balance: u64,
+
+    #[clap(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    pub pool_rebalance_interval: u64,
 }
 
 impl Default for FaucetConfig {
@@ -132,6 +148,10 @@ impl Default for FaucetConfig {
             batch_request_size: 500,
             ttl_expiration: 300,
             batch_enabled: false,
+            pool_rebalance_enabled: false,
+            pool_target_size: DEFAULT_POOL_TARGET_SIZE,
+            pool_coin_balance: DEFAULT_POOL_COIN_BALANCE,
+            pool_rebalance_interval: 60,
         }
     }
 }
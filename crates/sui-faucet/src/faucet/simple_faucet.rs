@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::faucet::coin_pool::CoinPool;
 use crate::faucet::write_ahead_log;
 use crate::metrics::FaucetMetrics;
 use async_recursion::async_recursion;
@@ -61,6 +62,8 @@ pub struct SimpleFaucet {
     task_id_cache: Mutex<TtlCache<Uuid, BatchSendStatus>>,
     ttl_expiration: u64,
     coin_amount: u64,
+    /// Tracks the coins backing the non-batch queue, and how to rebalance them.
+    pool: CoinPool,
     /// Shuts down the batch transfer task. Used only in testing.
     #[allow(unused)]
     batch_transfer_shutdown: parking_lot::Mutex<Option<oneshot::Sender<()>>>,
@@ -77,6 +80,7 @@ impl fmt::Debug for SimpleFaucet {
             .field("batch_request_size", &self.batch_request_size)
             .field("ttl_expiration", &self.ttl_expiration)
             .field("coin_amount", &self.coin_amount)
+            .field("pool_size", &self.pool.size())
             .finish()
     }
 }
@@ -94,6 +98,7 @@ const DEFAULT_GAS_COMPUTATION_BUCKET: u64 = 10_000_000;
 const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
 const RECV_TIMEOUT: Duration = Duration::from_secs(5);
 const BATCH_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_SPLIT_COINS_PER_TX: usize = 500;
 
 impl SimpleFaucet {
     pub async fn new(
@@ -121,7 +126,8 @@ impl SimpleFaucet {
         let wal = WriteAheadLog::open(wal_path);
         let mut pending = vec![];
 
-        let (producer, consumer) = mpsc::channel(coins.len());
+        // Leave room in the queue for the coins created when rebalancing the pool.
+        let (producer, consumer) = mpsc::channel(coins.len() + config.pool_target_size);
         let (batch_producer, batch_consumer) = mpsc::channel(coins.len());
 
         let (sender, mut receiver) =
@@ -133,6 +139,7 @@ impl SimpleFaucet {
         } else {
             coins.len()
         };
+        let mut pool_size = 0;
         // Put half of the coins in the old faucet impl queue, and put half in the other queue for batch coins.
         // In the test cases we create an account with 5 coins so we just let this run with a minimum of 5 coins
         for (coins_processed, coin) in coins.iter().enumerate() {
@@ -148,7 +155,10 @@ impl SimpleFaucet {
                 let uuid = Uuid::from_bytes(uuid);
                 info!(?uuid, ?recipient, ?coin_id, "Retrying txn from WAL.");
                 pending.push((uuid, recipient, coin_id, tx));
+                // Pending transactions are retried for the non-batch queue, see below.
+                pool_size += 1;
             } else if coins_processed < split_point {
+                pool_size += 1;
                 producer
                     .send(coin_id)
                     .await
@@ -170,6 +180,7 @@ impl SimpleFaucet {
                     .unwrap();
             }
         }
+        let pool = CoinPool::new(&config, pool_size, &metrics);
         let (batch_transfer_shutdown, mut rx_batch_transfer_shutdown) = oneshot::channel();
 
         let faucet = Self {
@@ -188,6 +199,7 @@ impl SimpleFaucet {
             task_id_cache: TtlCache::new(config.max_request_per_second as usize * 60 * 10).into(),
             ttl_expiration: config.ttl_expiration,
             coin_amount: config.amount,
+            pool,
             batch_transfer_shutdown: parking_lot::Mutex::new(Some(batch_transfer_shutdown)),
         };
 
@@ -528,8 +540,19 @@ impl SimpleFaucet {
             }
 
             GasCoinResponse::GasCoinWithInsufficientBalance(coin_id) => {
-                warn!(?uuid, ?coin_id, "Insufficient balance, removing from pool");
-                self.metrics.total_discarded_coins.inc();
+                if self.pool.rebalance_enabled {
+                    // Keep hold of the coin so its balance can be merged into a usable coin.
+                    warn!(
+                        ?uuid,
+                        ?coin_id,
+                        "Insufficient balance, setting aside for merging"
+                    );
+                    self.pool.push_dust(coin_id).await;
+                } else {
+                    warn!(?uuid, ?coin_id, "Insufficient balance, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                    self.pool.remove(1);
+                }
                 self.transfer_gases(amounts, recipient, uuid).await
             }
 
@@ -537,6 +560,7 @@ impl SimpleFaucet {
                 // The coin does not exist, or does not belong to the current active address.
                 warn!(?uuid, ?coin_id, "Invalid, removing from pool");
                 self.metrics.total_discarded_coins.inc();
+                self.pool.remove(1);
                 self.transfer_gases(amounts, recipient, uuid).await
             }
 
@@ -544,6 +568,173 @@ impl SimpleFaucet {
         }
     }
 
+    /// Rebalance the gas coin pool: merge coins whose balance is too low to serve requests, and
+    /// split a large coin if the pool holds fewer coins than its target size.
+    pub async fn rebalance_pool(&self) -> Result<(), FaucetError> {
+        let gas_cost = self.get_gas_cost().await?;
+        self.merge_dust_coins(gas_cost).await?;
+
+        let deficit = self.pool.deficit();
+        if deficit > 0 {
+            info!(
+                pool_size = self.pool.size(),
+                target_size = self.pool.target_size,
+                "Gas coin pool below target size, splitting coins"
+            );
+            self.split_pool_coin(deficit, gas_cost).await?;
+        }
+        Ok(())
+    }
+
+    /// Smash the dust coins set aside by the pool into a single coin and, if it is large enough to
+    /// serve requests again, put it back in the queue.
+    async fn merge_dust_coins(&self, gas_cost: u64) -> Result<(), FaucetError> {
+        let dust = self.pool.take_dust().await;
+        if dust.len() < 2 {
+            self.pool.return_dust(dust).await;
+            return Ok(());
+        }
+
+        let mut coins = Vec::with_capacity(dust.len());
+        let mut total_balance = 0;
+        for coin_id in dust {
+            match self.get_gas_coin_and_check_faucet_owner(coin_id).await {
+                Ok(Some(coin)) => {
+                    total_balance += coin.value();
+                    coins.push(coin_id);
+                }
+                Ok(None) => {
+                    warn!(?coin_id, "Invalid dust coin, removing from pool");
+                    self.metrics.total_discarded_coins.inc();
+                }
+                Err(e) => {
+                    error!(?coin_id, "Fullnode read error: {e:?}");
+                    coins.push(coin_id);
+                }
+            }
+        }
+
+        // Not worth merging yet: the merged coin would still be unable to serve a request.
+        if coins.len() < 2 || total_balance < self.pool.min_balance + 2 * gas_cost {
+            self.pool.return_dust(coins).await;
+            return Ok(());
+        }
+
+        let coin_id = coins[0];
+        let tx_data = match self.build_merge_coins_txn(&coins, gas_cost).await {
+            Ok(tx_data) => tx_data,
+            Err(e) => {
+                self.pool.return_dust(coins).await;
+                return Err(FaucetError::internal(e));
+            }
+        };
+
+        let uuid = Uuid::new_v4();
+        {
+            let mut wal = self.wal.lock().await;
+            wal.reserve(uuid, coin_id, self.active_address, tx_data.clone())
+                .map_err(FaucetError::internal)?;
+        }
+
+        // The merged coin is recycled into the queue once the transaction succeeds, so count it
+        // as part of the pool up front.
+        self.pool.add(1);
+        info!(?uuid, ?coin_id, "Merging {} dust coins", coins.len());
+        if let Err(e) = self
+            .sign_and_execute_txn(uuid, self.active_address, coin_id, tx_data, false)
+            .await
+        {
+            self.pool.remove(1);
+            return Err(e);
+        }
+        self.metrics
+            .total_pool_coins_merged
+            .inc_by(coins.len() as u64);
+        Ok(())
+    }
+
+    /// Take a coin from the queue and split up to `count` coins of the pool's coin balance off of
+    /// it, adding them to the queue.
+    async fn split_pool_coin(&self, count: usize, gas_cost: u64) -> Result<(), FaucetError> {
+        let uuid = Uuid::new_v4();
+        let Some(coin_id) = self.pop_gas_coin(uuid).await else {
+            warn!(?uuid, "No gas coin available to split");
+            return Ok(());
+        };
+
+        let balance = match self.get_gas_coin_and_check_faucet_owner(coin_id).await {
+            Ok(Some(coin)) => coin.value(),
+            Ok(None) => {
+                warn!(?uuid, ?coin_id, "Invalid, removing from pool");
+                self.metrics.total_discarded_coins.inc();
+                self.pool.remove(1);
+                return Ok(());
+            }
+            Err(e) => {
+                self.recycle_gas_coin(coin_id, uuid).await;
+                return Err(FaucetError::FullnodeReadingError(format!(
+                    "unknown gas coin {coin_id:?}: {e:?}"
+                )));
+            }
+        };
+
+        // The coin being split keeps enough balance to be a pool coin itself.
+        let spare_balance = balance.saturating_sub(self.pool.coin_balance + gas_cost);
+        let count = count
+            .min((spare_balance / self.pool.coin_balance) as usize)
+            .min(MAX_SPLIT_COINS_PER_TX);
+        if count == 0 {
+            info!(?uuid, ?coin_id, balance, "Coin is too small to split");
+            self.recycle_gas_coin(coin_id, uuid).await;
+            return Ok(());
+        }
+
+        let amounts = vec![self.pool.coin_balance; count];
+        let tx_data = match self
+            .build_pay_sui_txn(
+                coin_id,
+                self.active_address,
+                self.active_address,
+                &amounts,
+                gas_cost,
+            )
+            .await
+        {
+            Ok(tx_data) => tx_data,
+            Err(e) => {
+                self.recycle_gas_coin(coin_id, uuid).await;
+                return Err(FaucetError::internal(e));
+            }
+        };
+
+        {
+            let mut wal = self.wal.lock().await;
+            wal.reserve(uuid, coin_id, self.active_address, tx_data.clone())
+                .map_err(FaucetError::internal)?;
+        }
+
+        info!(
+            ?uuid,
+            ?coin_id,
+            "Splitting {count} coins for the gas coin pool"
+        );
+        let response = self
+            .sign_and_execute_txn(uuid, self.active_address, coin_id, tx_data, false)
+            .await?;
+        let (_, created) = self
+            .check_and_map_transfer_gas_result(response, count, self.active_address)
+            .await?;
+
+        self.pool.add(created.len());
+        self.metrics
+            .total_pool_coins_split
+            .inc_by(created.len() as u64);
+        for coin_id in created {
+            self.recycle_gas_coin(coin_id, uuid).await;
+        }
+        Ok(())
+    }
+
     async fn recycle_gas_coin(&self, coin_id: ObjectID, uuid: Uuid) {
         // Once transactions are done, in despite of success or failure,
         // we put back the coins. The producer should never wait indefinitely,
@@ -675,6 +866,35 @@ impl SimpleFaucet {
             })
     }
 
+    /// Build a transaction that smashes `coins` together by using them all as gas payment, and
+    /// sends the resulting coin back to the faucet.
+    async fn build_merge_coins_txn(
+        &self,
+        coins: &[ObjectID],
+        budget: u64,
+    ) -> Result<TransactionData, anyhow::Error> {
+        let gas_payment = futures::future::try_join_all(
+            coins
+                .iter()
+                .map(|coin_id| self.wallet.get_object_ref(*coin_id)),
+        )
+        .await?;
+        let gas_price = self.wallet.get_reference_gas_price().await?;
+        let pt = {
+            let mut builder = ProgrammableTransactionBuilder::new();
+            builder.pay_all_sui(self.active_address);
+            builder.finish()
+        };
+
+        Ok(TransactionData::new_programmable(
+            self.active_address,
+            gas_payment,
+            pt,
+            budget,
+            gas_price,
+        ))
+    }
+
     async fn check_and_map_transfer_gas_result(
         &self,
         res: SuiTransactionBlockResponse,
@@ -1170,6 +1390,52 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_rebalance_pool_splits_coins() {
+        let test_cluster = TestClusterBuilder::new().build().await;
+        let address = test_cluster.get_address_0();
+        let mut context = test_cluster.wallet;
+        let gases = get_current_gases(address, &mut context).await;
+        let target_size = gases.len() * 2;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let prom_registry = Registry::new();
+        let config = FaucetConfig {
+            pool_rebalance_enabled: true,
+            pool_target_size: target_size,
+            ..Default::default()
+        };
+        let faucet = SimpleFaucet::new(
+            context,
+            &prom_registry,
+            &tmp.path().join("faucet.wal"),
+            config,
+        )
+        .await
+        .unwrap();
+        faucet.shutdown_batch_send_task();
+        assert_eq!(faucet.pool.size(), gases.len());
+
+        faucet.rebalance_pool().await.unwrap();
+
+        // The pool is back at its target size, and a second rebalance is a no-op.
+        assert_eq!(faucet.pool.size(), target_size);
+        assert_eq!(
+            faucet.metrics.total_pool_coins_split.get() as usize,
+            target_size - gases.len()
+        );
+        faucet.rebalance_pool().await.unwrap();
+        assert_eq!(faucet.pool.size(), target_size);
+
+        let available = faucet.metrics.total_available_coins.get();
+        let faucet_unwrapped = &mut Arc::try_unwrap(faucet).unwrap();
+        let candidates = faucet_unwrapped.drain_gas_queue(target_size).await;
+        assert_eq!(available as usize, candidates.len());
+        for gas in gases {
+            assert!(candidates.contains(gas.id()));
+        }
+    }
+
     #[tokio::test]
     async fn test_transfer_state() {
         let test_cluster = TestClusterBuilder::new().build().await;
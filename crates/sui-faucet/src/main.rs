@@ -27,7 +27,7 @@ use sui_faucet::{
 use sui_sdk::wallet_context::WalletContext;
 use tower::{limit::RateLimitLayer, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
-use tracing::{info, warn};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 const CONCURRENCY_LIMIT: usize = 30;
@@ -61,6 +61,8 @@ async fn main() -> Result<(), anyhow::Error> {
         wallet_client_timeout_secs,
         ref write_ahead_log,
         wal_retry_interval,
+        pool_rebalance_enabled,
+        pool_rebalance_interval,
         ..
     } = config;
 
@@ -109,6 +111,19 @@ async fn main() -> Result<(), anyhow::Error> {
                 .into_inner(),
         );
 
+    if pool_rebalance_enabled {
+        let app_state = app_state.clone();
+        spawn_monitored_task!(async move {
+            info!("Starting task to rebalance the gas coin pool.");
+            loop {
+                tokio::time::sleep(Duration::from_secs(pool_rebalance_interval)).await;
+                if let Err(e) = app_state.faucet.rebalance_pool().await {
+                    error!("Failed to rebalance the gas coin pool: {e:?}");
+                }
+            }
+        });
+    }
+
     spawn_monitored_task!(async move {
         info!("Starting task to clear WAL.");
         loop {
@@ -28,6 +28,11 @@ pub struct FaucetMetrics {
     pub(crate) total_available_coins: IntGauge,
     pub(crate) total_discarded_coins: IntGauge,
     pub(crate) total_coin_requests_succeeded: IntGauge,
+    pub(crate) current_pool_size: IntGauge,
+    pub(crate) pool_target_size: IntGauge,
+    pub(crate) current_dust_coins: IntGauge,
+    pub(crate) total_pool_coins_split: IntCounter,
+    pub(crate) total_pool_coins_merged: IntCounter,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -112,6 +117,36 @@ impl FaucetMetrics {
                 registry,
             )
             .unwrap(),
+            current_pool_size: register_int_gauge_with_registry!(
+                "current_pool_size",
+                "Current number of coins managed by the gas coin pool, queued or in flight",
+                registry,
+            )
+            .unwrap(),
+            pool_target_size: register_int_gauge_with_registry!(
+                "pool_target_size",
+                "Number of coins the gas coin pool tries to maintain",
+                registry,
+            )
+            .unwrap(),
+            current_dust_coins: register_int_gauge_with_registry!(
+                "current_dust_coins",
+                "Current number of coins set aside for having too little balance, waiting to be merged",
+                registry,
+            )
+            .unwrap(),
+            total_pool_coins_split: register_int_counter_with_registry!(
+                "total_pool_coins_split",
+                "Total number of coins created by splitting when rebalancing the gas coin pool",
+                registry,
+            )
+            .unwrap(),
+            total_pool_coins_merged: register_int_counter_with_registry!(
+                "total_pool_coins_merged",
+                "Total number of dust coins merged when rebalancing the gas coin pool",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
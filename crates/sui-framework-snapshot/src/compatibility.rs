@@ -0,0 +1,213 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compatibility report between the system packages of the current framework and those of a
+//! previous bytecode snapshot, listing the modules, functions and structs that were added, removed
+//! or changed, and whether each change breaks the code depending on the framework.
+
+use std::collections::BTreeMap;
+
+use move_binary_format::file_format::Visibility;
+use move_binary_format::normalized;
+use serde::{Deserialize, Serialize};
+use sui_framework::SystemPackage;
+use sui_types::base_types::ObjectID;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    /// Protocol version of the snapshot the framework is compared against.
+    pub previous_version: u64,
+    /// Protocol version of the framework.
+    pub version: u64,
+    /// Changes of each system package, for the packages that changed.
+    pub packages: BTreeMap<ObjectID, Vec<Change>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub module: String,
+    /// The function or struct that changed, `None` if the whole module was added or removed.
+    pub member: Option<String>,
+    pub kind: ChangeKind,
+    /// Whether the change can break modules depending on the framework.
+    pub breaking: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    ModuleAdded,
+    ModuleRemoved,
+    FunctionAdded,
+    FunctionRemoved,
+    /// The type parameters, parameters or return types of the function changed.
+    FunctionSignatureChanged,
+    FunctionVisibilityChanged,
+    /// Only the code of the function changed.
+    FunctionCodeChanged,
+    StructAdded,
+    StructRemoved,
+    /// The names or types of the fields of the struct changed.
+    StructLayoutChanged,
+    StructAbilitiesChanged,
+    StructTypeParametersChanged,
+}
+
+impl CompatibilityReport {
+    /// Compare the `current` system packages against the `previous` ones. Packages that are new in
+    /// `current` have all of their modules reported as added.
+    pub fn new<'a>(
+        previous_version: u64,
+        previous: &[SystemPackage],
+        version: u64,
+        current: impl IntoIterator<Item = &'a SystemPackage>,
+    ) -> Self {
+        let previous: BTreeMap<_, _> = previous.iter().map(|p| (*p.id(), p)).collect();
+        let mut packages = BTreeMap::new();
+        for package in current {
+            let old_modules = previous
+                .get(package.id())
+                .map(|p| normalized_modules(p))
+                .unwrap_or_default();
+            let changes = diff_modules(&old_modules, &normalized_modules(package));
+            if !changes.is_empty() {
+                packages.insert(*package.id(), changes);
+            }
+        }
+        Self {
+            previous_version,
+            version,
+            packages,
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.breaking_changes().next().is_some()
+    }
+
+    pub fn breaking_changes(&self) -> impl Iterator<Item = (&ObjectID, &Change)> {
+        self.packages
+            .iter()
+            .flat_map(|(id, changes)| changes.iter().map(move |c| (id, c)))
+            .filter(|(_, change)| change.breaking)
+    }
+
+    /// Fail if the framework has breaking changes, but was not given a new protocol version.
+    pub fn check_version_bump(&self) -> anyhow::Result<()> {
+        if self.version > self.previous_version || !self.is_breaking() {
+            return Ok(());
+        }
+        let changes: Vec<_> = self
+            .breaking_changes()
+            .map(|(id, c)| match &c.member {
+                Some(member) => format!("{id}::{}::{member}: {:?}", c.module, c.kind),
+                None => format!("{id}::{}: {:?}", c.module, c.kind),
+            })
+            .collect();
+        anyhow::bail!(
+            "The framework has breaking changes, but its protocol version {} is the same as the \
+             one of the latest snapshot. Breaking changes:\n{}",
+            self.version,
+            changes.join("\n")
+        )
+    }
+}
+
+fn normalized_modules(package: &SystemPackage) -> BTreeMap<String, normalized::Module> {
+    package
+        .modules()
+        .iter()
+        .map(|m| {
+            let module = normalized::Module::new(m);
+            (module.name.to_string(), module)
+        })
+        .collect()
+}
+
+fn diff_modules(
+    old: &BTreeMap<String, normalized::Module>,
+    new: &BTreeMap<String, normalized::Module>,
+) -> Vec<Change> {
+    let mut changes = vec![];
+    for (name, old_module) in old {
+        match new.get(name) {
+            None => changes.push(Change {
+                module: name.clone(),
+                member: None,
+                kind: ChangeKind::ModuleRemoved,
+                breaking: true,
+            }),
+            Some(new_module) => diff_module(&mut changes, name, old_module, new_module),
+        }
+    }
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.push(Change {
+            module: name.clone(),
+            member: None,
+            kind: ChangeKind::ModuleAdded,
+            breaking: false,
+        });
+    }
+    changes
+}
+
+fn diff_module(
+    changes: &mut Vec<Change>,
+    module: &str,
+    old: &normalized::Module,
+    new: &normalized::Module,
+) {
+    let mut change = |member: &str, kind, breaking| {
+        changes.push(Change {
+            module: module.to_string(),
+            member: Some(member.to_string()),
+            kind,
+            breaking,
+        })
+    };
+
+    for (ident, old_struct) in &old.structs {
+        let name = ident.as_str();
+        let Some(new_struct) = new.structs.get(ident) else {
+            change(name, ChangeKind::StructRemoved, true);
+            continue;
+        };
+        if old_struct.abilities != new_struct.abilities {
+            change(name, ChangeKind::StructAbilitiesChanged, true);
+        }
+        if old_struct.type_parameters != new_struct.type_parameters {
+            change(name, ChangeKind::StructTypeParametersChanged, true);
+        }
+        if old_struct.fields != new_struct.fields {
+            change(name, ChangeKind::StructLayoutChanged, true);
+        }
+    }
+    for name in new.structs.keys().filter(|n| !old.structs.contains_key(*n)) {
+        change(name.as_str(), ChangeKind::StructAdded, false);
+    }
+
+    for (ident, old_fun) in &old.functions {
+        let name = ident.as_str();
+        // Only public functions can be called from other packages
+        let public = old_fun.visibility == Visibility::Public;
+        let Some(new_fun) = new.functions.get(ident) else {
+            change(name, ChangeKind::FunctionRemoved, public);
+            continue;
+        };
+        if old_fun.visibility != new_fun.visibility {
+            let breaking = public && new_fun.visibility != Visibility::Public;
+            change(name, ChangeKind::FunctionVisibilityChanged, breaking);
+        }
+        if old_fun.type_parameters != new_fun.type_parameters
+            || old_fun.parameters != new_fun.parameters
+            || old_fun.return_ != new_fun.return_
+        {
+            change(name, ChangeKind::FunctionSignatureChanged, public);
+        } else if old_fun.code != new_fun.code {
+            change(name, ChangeKind::FunctionCodeChanged, false);
+        }
+    }
+    for name in new.functions.keys().filter(|n| !old.functions.contains_key(*n)) {
+        change(name.as_str(), ChangeKind::FunctionAdded, false);
+    }
+}
@@ -1,15 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use compatibility::CompatibilityReport;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::{fs, io::Read, path::PathBuf};
-use sui_framework::SystemPackage;
+use sui_framework::{BuiltInFramework, SystemPackage};
 use sui_types::base_types::ObjectID;
 use sui_types::{
     DEEPBOOK_PACKAGE_ID, MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID,
 };
 
+pub mod compatibility;
+
 pub type SnapshotManifest = BTreeMap<u64, SingleSnapshot>;
 
 #[derive(Serialize, Deserialize)]
@@ -77,6 +80,31 @@ pub fn load_bytecode_snapshot(protocol_version: u64) -> anyhow::Result<Vec<Syste
     Ok(snapshot_objects)
 }
 
+/// Compare the current framework against the latest snapshot, or return `None` if there is no
+/// snapshot yet.
+pub fn compatibility_report_with_latest_snapshot(
+    version: u64,
+) -> anyhow::Result<Option<CompatibilityReport>> {
+    let Some(previous_version) = load_bytecode_snapshot_manifest().keys().max().copied() else {
+        return Ok(None);
+    };
+    let previous = load_bytecode_snapshot(previous_version)?;
+    Ok(Some(CompatibilityReport::new(
+        previous_version,
+        &previous,
+        version,
+        BuiltInFramework::iter_system_packages(),
+    )))
+}
+
+pub fn write_compatibility_report(report: &CompatibilityReport) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("compatibility_reports");
+    fs::create_dir_all(&dir).expect("Unable to create compatibility report directory");
+    let json = serde_json::to_string_pretty(report).expect("Could not serialize report");
+    fs::write(dir.join(format!("{}.json", report.version)), json)
+        .expect("Could not write compatibility report");
+}
+
 fn manifest_path() -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("manifest.json")
 }
@@ -5,7 +5,10 @@ use std::env;
 use std::fs;
 use std::path::PathBuf;
 use sui_framework::{BuiltInFramework, SystemPackage};
-use sui_framework_snapshot::update_bytecode_snapshot_manifest;
+use sui_framework_snapshot::{
+    compatibility_report_with_latest_snapshot, update_bytecode_snapshot_manifest,
+    write_compatibility_report,
+};
 use sui_protocol_config::ProtocolVersion;
 
 const GIT_REVISION: &str = {
@@ -27,6 +30,15 @@ const GIT_REVISION: &str = {
 fn main() {
     // Always generate snapshot for the latest version.
     let version = ProtocolVersion::MAX.as_u64();
+    // Report the changes since the latest snapshot before overwriting it.
+    let report = compatibility_report_with_latest_snapshot(version)
+        .expect("Unable to load the latest snapshot");
+    if let Some(report) = report {
+        write_compatibility_report(&report);
+        if let Err(e) = report.check_version_bump() {
+            panic!("{e}");
+        }
+    }
     let mut files = vec![];
     for package in BuiltInFramework::iter_system_packages() {
         write_package_to_file(version, package);
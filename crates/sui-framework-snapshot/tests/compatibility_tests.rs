@@ -4,7 +4,10 @@
 mod compatibility_tests {
     use std::collections::BTreeMap;
     use sui_framework::{compare_system_package, BuiltInFramework};
-    use sui_framework_snapshot::{load_bytecode_snapshot, load_bytecode_snapshot_manifest};
+    use sui_framework_snapshot::{
+        compatibility_report_with_latest_snapshot, load_bytecode_snapshot,
+        load_bytecode_snapshot_manifest,
+    };
     use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
 
     #[tokio::test]
@@ -67,4 +70,14 @@ mod compatibility_tests {
                 "The current framework differs the latest bytecode snapshot. Did you forget to upgrade protocol version?"
             );
     }
+
+    #[test]
+    fn check_breaking_changes_with_protocol_upgrade() {
+        // This test checks that breaking changes to the framework since the latest bytecode
+        // snapshot come with a new protocol version.
+        let report = compatibility_report_with_latest_snapshot(ProtocolVersion::MAX.as_u64())
+            .unwrap()
+            .unwrap();
+        report.check_version_bump().unwrap();
+    }
 }
@@ -0,0 +1,13 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module gas_thresholds::gas {
+    #[test]
+    fun cheap() {}
+
+    #[test]
+    fun expensive() {
+        let i = 0;
+        while (i < 1000) i = i + 1;
+    }
+}
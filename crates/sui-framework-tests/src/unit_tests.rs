@@ -1,10 +1,12 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use clap::ValueEnum;
 use move_cli::base::test::UnitTestResult;
 use move_unit_test::UnitTestingConfig;
 use std::{fs, io, path::PathBuf};
-use sui_move::unit_test::run_move_unit_tests;
+use sui_move::new::Template;
+use sui_move::unit_test::{run_move_unit_tests, run_move_unit_tests_with_gas_report};
 use sui_move_build::BuildConfig;
 
 #[test]
@@ -77,6 +79,37 @@ fn run_docs_examples_move_unit_tests() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+#[cfg_attr(msim, ignore)]
+fn run_sui_move_new_templates_move_unit_tests() {
+    for template in Template::value_variants() {
+        let name = template.to_possible_value().unwrap().get_name().to_owned();
+        let path = {
+            let mut buf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+            buf.extend(["..", "sui-move", "templates", &name]);
+            buf
+        };
+
+        // The files written by `sui move new --template` are all the Move files of the package
+        let mut files: Vec<_> = template
+            .files()
+            .iter()
+            .map(|(file, _)| path.join(file))
+            .collect();
+        let mut move_files = vec![];
+        for dir in ["sources", "tests"] {
+            for entry in fs::read_dir(path.join(dir)).unwrap() {
+                move_files.push(entry.unwrap().path());
+            }
+        }
+        files.sort();
+        move_files.sort();
+        assert_eq!(files, move_files, "Files of template {name}");
+
+        check_move_unit_tests(path);
+    }
+}
+
 #[test]
 #[cfg_attr(msim, ignore)]
 fn run_book_examples_move_unit_tests() {
@@ -87,6 +120,35 @@ fn run_book_examples_move_unit_tests() {
     });
 }
 
+#[test]
+#[cfg_attr(msim, ignore)]
+fn gas_report_fails_on_regressions() {
+    let path = {
+        let mut buf = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        buf.extend(["data", "gas_thresholds"]);
+        buf
+    };
+    let mut config = BuildConfig::new_for_testing();
+    config.config.dev_mode = true;
+    config.config.test_mode = true;
+
+    // All tests pass, but `gas::expensive` uses more gas than its threshold.
+    assert_eq!(
+        run_move_unit_tests(path.clone(), config.config.clone(), None, false).unwrap(),
+        UnitTestResult::Success
+    );
+    assert_eq!(
+        run_move_unit_tests_with_gas_report(
+            path,
+            config.config,
+            UnitTestingConfig::default_with_bound(None),
+            false,
+        )
+        .unwrap(),
+        UnitTestResult::Failure
+    );
+}
+
 fn check_move_unit_tests(path: PathBuf) {
     let mut config = BuildConfig::new_for_testing();
     // Make sure to verify tests
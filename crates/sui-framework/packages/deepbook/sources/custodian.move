@@ -160,8 +160,6 @@ module deepbook::custodian {
         table::borrow(&custodian.account_balances, user)
     }
 
-    #[test_only]
-    friend deepbook::clob_test;
     #[test_only]
     use sui::test_scenario::{Self, Scenario, take_shared, take_from_sender, ctx};
     #[test_only]
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+#[allow(unused_friend)]
 /// Sui object identifiers
 module sui::object {
     use std::bcs;
@@ -13,9 +14,6 @@ module sui::object {
     friend sui::transfer;
     friend sui::authenticator_state;
 
-    #[test_only]
-    friend sui::test_scenario;
-
     /// The hardcoded ID for the singleton Sui System State Object.
     const SUI_SYSTEM_STATE_OBJECT_ID: address = @0x5;
 
@@ -3,6 +3,7 @@
 
 module sui::transfer {
 
+    use std::vector;
     use sui::object::{Self, ID, UID};
     use sui::prover;
 
@@ -46,6 +47,28 @@ module sui::transfer {
         transfer_impl(obj, recipient)
     }
 
+    /// Transfer ownership of every object in `objs` to `recipient`, as `transfer` does for a
+    /// single object.
+    /// This function has custom rules performed by the Sui Move bytecode verifier that ensures
+    /// that `T` is an object defined in the module where `transfer_vec` is invoked. Use
+    /// `public_transfer_vec` to transfer objects with `store` outside of their module.
+    public fun transfer_vec<T: key>(objs: vector<T>, recipient: address) {
+        while (!vector::is_empty(&objs)) {
+            transfer_impl(vector::pop_back(&mut objs), recipient)
+        };
+        vector::destroy_empty(objs)
+    }
+
+    /// Transfer ownership of every object in `objs` to `recipient`, as `public_transfer` does for
+    /// a single object.
+    /// The objects must have `store` to be transferred outside of their module.
+    public fun public_transfer_vec<T: key + store>(objs: vector<T>, recipient: address) {
+        while (!vector::is_empty(&objs)) {
+            transfer_impl(vector::pop_back(&mut objs), recipient)
+        };
+        vector::destroy_empty(objs)
+    }
+
     /// Freeze `obj`. After freezing `obj` becomes immutable and can no longer be transferred or
     /// mutated.
     /// This function has custom rules performed by the Sui Move bytecode verifier that ensures
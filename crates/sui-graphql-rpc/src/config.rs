@@ -1,15 +1,22 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use async_graphql::*;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 
 use crate::functional_group::FunctionalGroup;
 
 const MAX_QUERY_DEPTH: u32 = 10;
 const MAX_QUERY_NODES: u32 = 100;
+const MAX_MULTI_GET_SIZE: u32 = 50;
+const MAX_CHECKPOINT_LAG_MS: u64 = 5 * 60 * 1000;
+const WATERMARK_POLL_INTERVAL_MS: u64 = 1000;
+const READ_REQUESTS_PER_SECOND: u32 = 50;
+const READ_BURST: u32 = 100;
+const EXECUTE_REQUESTS_PER_SECOND: u32 = 5;
+const EXECUTE_BURST: u32 = 10;
 
 /// Configuration on connections for the RPC, passed in as command-line arguments.
 pub struct ConnectionConfig {
@@ -30,6 +37,12 @@ pub struct ServiceConfig {
 
     #[serde(default)]
     pub(crate) experiments: Experiments,
+
+    #[serde(default)]
+    pub(crate) health: Health,
+
+    #[serde(default)]
+    pub(crate) rate_limits: RateLimits,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
@@ -39,6 +52,61 @@ pub struct Limits {
     pub(crate) max_query_depth: u32,
     #[serde(default)]
     pub(crate) max_query_nodes: u32,
+    /// Maximum number of keys that can be looked up by a single multi-get field.
+    #[serde(default = "Limits::default_max_multi_get_size")]
+    pub(crate) max_multi_get_size: u32,
+}
+
+/// Thresholds used by the `/health` and `/ready` endpoints.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Health {
+    /// The service reports itself as not ready if the latest checkpoint in its data source is
+    /// older than this.
+    #[serde(default = "Health::default_max_checkpoint_lag_ms")]
+    pub(crate) max_checkpoint_lag_ms: u64,
+    /// How often the latest checkpoint is fetched from the data source. Must not be zero.
+    #[serde(
+        default = "Health::default_watermark_poll_interval_ms",
+        deserialize_with = "Health::deserialize_watermark_poll_interval_ms"
+    )]
+    pub(crate) watermark_poll_interval_ms: u64,
+}
+
+/// Per-client rate limits, enforced with a token bucket for each client and kind of request.
+/// Clients are identified by their API key if it is one of `api_keys`, and by their IP address
+/// otherwise.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct RateLimits {
+    /// Rate limiting is off unless enabled, for deployments behind a gateway that already does it.
+    #[serde(default)]
+    pub(crate) enabled: bool,
+    /// Quotas for clients without a known API key.
+    #[serde(flatten)]
+    pub(crate) quotas: Quotas,
+    /// Quotas for clients that identify themselves with one of these API keys.
+    #[serde(default)]
+    pub(crate) api_keys: BTreeMap<String, Quotas>,
+}
+
+/// Separate budgets for requests that only read data, and requests that execute transactions.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Quotas {
+    #[serde(default = "Quota::default_read")]
+    pub(crate) read: Quota,
+    #[serde(default = "Quota::default_execute")]
+    pub(crate) execute: Quota,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Quota {
+    /// Rate at which a client's budget is replenished.
+    pub(crate) requests_per_second: u32,
+    /// Size of a client's budget: how many requests it can make at once after being idle.
+    pub(crate) burst: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, Default)]
@@ -92,6 +160,12 @@ impl ServiceConfig {
     async fn max_query_nodes(&self) -> Result<u32> {
         Ok(self.limits.max_query_nodes)
     }
+
+    /// The maximum number of keys that can be looked up in a single multi-get, such as `objects`
+    /// or `transactionBlocksByDigests`.
+    async fn max_multi_get_size(&self) -> Result<u32> {
+        Ok(self.limits.max_multi_get_size)
+    }
 }
 
 impl Default for ConnectionConfig {
@@ -109,6 +183,69 @@ impl Default for Limits {
         Self {
             max_query_depth: MAX_QUERY_DEPTH,
             max_query_nodes: MAX_QUERY_NODES,
+            max_multi_get_size: MAX_MULTI_GET_SIZE,
+        }
+    }
+}
+
+impl Limits {
+    fn default_max_multi_get_size() -> u32 {
+        MAX_MULTI_GET_SIZE
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self {
+            max_checkpoint_lag_ms: MAX_CHECKPOINT_LAG_MS,
+            watermark_poll_interval_ms: WATERMARK_POLL_INTERVAL_MS,
+        }
+    }
+}
+
+impl Health {
+    fn default_max_checkpoint_lag_ms() -> u64 {
+        MAX_CHECKPOINT_LAG_MS
+    }
+
+    fn default_watermark_poll_interval_ms() -> u64 {
+        WATERMARK_POLL_INTERVAL_MS
+    }
+
+    fn deserialize_watermark_poll_interval_ms<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<u64, D::Error> {
+        let interval_ms = u64::deserialize(deserializer)?;
+        if interval_ms == 0 {
+            return Err(D::Error::custom(
+                "watermark-poll-interval-ms must be greater than 0",
+            ));
+        }
+        Ok(interval_ms)
+    }
+}
+
+impl Default for Quotas {
+    fn default() -> Self {
+        Self {
+            read: Quota::default_read(),
+            execute: Quota::default_execute(),
+        }
+    }
+}
+
+impl Quota {
+    fn default_read() -> Self {
+        Self {
+            requests_per_second: READ_REQUESTS_PER_SECOND,
+            burst: READ_BURST,
+        }
+    }
+
+    fn default_execute() -> Self {
+        Self {
+            requests_per_second: EXECUTE_REQUESTS_PER_SECOND,
+            burst: EXECUTE_BURST,
         }
     }
 }
@@ -138,6 +275,7 @@ mod tests {
             limits: Limits {
                 max_query_depth: 100,
                 max_query_nodes: 300,
+                max_multi_get_size: MAX_MULTI_GET_SIZE,
             },
             ..Default::default()
         };
@@ -161,6 +299,8 @@ mod tests {
             limits: Limits::default(),
             disabled_features: BTreeSet::from([G::Coins, G::NameService]),
             experiments: Experiments::default(),
+            health: Health::default(),
+            rate_limits: RateLimits::default(),
         };
 
         assert_eq!(actual, expect)
@@ -183,6 +323,105 @@ mod tests {
         assert_eq!(actual, expect)
     }
 
+    #[test]
+    fn test_read_health_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [health]
+                max-checkpoint-lag-ms = 30000
+                watermark-poll-interval-ms = 500
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            health: Health {
+                max_checkpoint_lag_ms: 30000,
+                watermark_poll_interval_ms: 500,
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_zero_watermark_poll_interval_in_service_config() {
+        let err = ServiceConfig::read(
+            r#" [health]
+                watermark-poll-interval-ms = 0
+            "#,
+        )
+        .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("watermark-poll-interval-ms must be greater than 0"));
+    }
+
+    #[test]
+    fn test_read_partial_health_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [health]
+                max-checkpoint-lag-ms = 30000
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            health: Health {
+                max_checkpoint_lag_ms: 30000,
+                ..Health::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
+    #[test]
+    fn test_read_rate_limits_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [rate-limits]
+                enabled = true
+                read = { requests-per-second = 10, burst = 20 }
+
+                [rate-limits.api-keys.partner]
+                read = { requests-per-second = 100, burst = 200 }
+                execute = { requests-per-second = 20, burst = 40 }
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            rate_limits: RateLimits {
+                enabled: true,
+                quotas: Quotas {
+                    read: Quota {
+                        requests_per_second: 10,
+                        burst: 20,
+                    },
+                    execute: Quota::default_execute(),
+                },
+                api_keys: BTreeMap::from([(
+                    "partner".to_string(),
+                    Quotas {
+                        read: Quota {
+                            requests_per_second: 100,
+                            burst: 200,
+                        },
+                        execute: Quota {
+                            requests_per_second: 20,
+                            burst: 40,
+                        },
+                    },
+                )]),
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect)
+    }
+
     #[test]
     fn test_read_everything_in_service_config() {
         let actual = ServiceConfig::read(
@@ -191,9 +430,18 @@ mod tests {
                 [limits]
                 max-query-depth = 42
                 max-query-nodes = 320
+                max-multi-get-size = 10
 
                 [experiments]
                 test-flag = true
+
+                [health]
+                max-checkpoint-lag-ms = 60000
+                watermark-poll-interval-ms = 2000
+
+                [rate-limits]
+                enabled = true
+                execute = { requests-per-second = 1, burst = 2 }
             "#,
         )
         .unwrap();
@@ -202,9 +450,25 @@ mod tests {
             limits: Limits {
                 max_query_depth: 42,
                 max_query_nodes: 320,
+                max_multi_get_size: 10,
             },
             disabled_features: BTreeSet::from([FunctionalGroup::Analytics]),
             experiments: Experiments { test_flag: true },
+            health: Health {
+                max_checkpoint_lag_ms: 60000,
+                watermark_poll_interval_ms: 2000,
+            },
+            rate_limits: RateLimits {
+                enabled: true,
+                quotas: Quotas {
+                    read: Quota::default_read(),
+                    execute: Quota {
+                        requests_per_second: 1,
+                        burst: 2,
+                    },
+                },
+                api_keys: BTreeMap::new(),
+            },
         };
 
         assert_eq!(actual, expect);
@@ -5,18 +5,36 @@ use crate::types::balance::Balance;
 use crate::types::checkpoint::Checkpoint;
 use crate::types::object::ObjectFilter;
 use crate::types::protocol_config::ProtocolConfigs;
+use crate::types::transaction_block::TransactionBlock;
 use crate::types::{object::Object, sui_address::SuiAddress};
 use async_graphql::connection::Connection;
 use async_graphql::*;
 use async_trait::async_trait;
-use sui_json_rpc_types::SuiObjectDataOptions;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponse, TransactionFilter};
+use sui_sdk::types::authenticator_state::ActiveJwk;
 use sui_sdk::types::base_types::ObjectID;
-use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use sui_sdk::types::sui_system_state::sui_system_state_summary::{
+    SuiSystemStateSummary, SuiValidatorSummary,
+};
+use sui_sdk::types::transaction::Transaction;
+
+/// The latest checkpoint known to a data source.
+pub(crate) struct CheckpointWatermark {
+    pub sequence_number: u64,
+    pub timestamp_ms: u64,
+    /// The lowest checkpoint the data source still has data for, as it may have pruned older
+    /// checkpoints.
+    pub lowest_available: u64,
+}
 
 #[async_trait]
 pub(crate) trait DataProvider: Send + Sync {
     async fn fetch_obj(&self, address: SuiAddress, version: Option<u64>) -> Result<Option<Object>>;
 
+    /// Latest versions of the objects at `addresses`, in the same order, with `None` for objects
+    /// that don't exist.
+    async fn multi_fetch_obj(&self, addresses: Vec<SuiAddress>) -> Result<Vec<Option<Object>>>;
+
     async fn fetch_owned_objs(
         &self,
         owner: &SuiAddress,
@@ -58,9 +76,33 @@ pub(crate) trait DataProvider: Send + Sync {
         before: Option<String>,
     ) -> Result<Connection<String, Checkpoint>>;
 
+    async fn fetch_transaction_block_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionFilter>,
+    ) -> Result<Connection<String, TransactionBlock>>;
+
     async fn fetch_chain_id(&self) -> Result<String>;
 
     async fn fetch_protocol_config(&self, version: Option<u64>) -> Result<ProtocolConfigs>;
 
     async fn get_latest_sui_system_state(&self) -> Result<SuiSystemStateSummary>;
+
+    /// The active validators of `epoch`, or `None` if the data source has no record of it.
+    async fn fetch_epoch_validators(&self, epoch: u64) -> Result<Option<Vec<SuiValidatorSummary>>>;
+
+    async fn fetch_latest_checkpoint_watermark(&self) -> Result<CheckpointWatermark>;
+
+    /// The JWKs of the OIDC providers that zkLogin signatures are currently verified against, or
+    /// none if the network has no authenticator state.
+    async fn fetch_active_jwks(&self) -> Result<Vec<ActiveJwk>>;
+
+    /// Submits a signed transaction for execution, and waits for its effects.
+    async fn execute_transaction_block(
+        &self,
+        transaction: Transaction,
+    ) -> Result<SuiTransactionBlockResponse>;
 }
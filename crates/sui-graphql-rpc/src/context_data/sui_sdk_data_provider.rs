@@ -34,26 +34,34 @@ use async_graphql::dataloader::*;
 use async_graphql::*;
 use async_trait::async_trait;
 use fastcrypto::traits::EncodeDecodeBase64;
+use move_core_types::language_storage::TypeTag;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 use std::str::FromStr;
 use std::time::Duration;
 use sui_json_rpc_types::{
-    SuiObjectDataOptions, SuiObjectResponseQuery, SuiPastObjectResponse, SuiRawData,
-    SuiTransactionBlockResponseOptions,
+    CheckpointId, SuiObjectDataOptions, SuiObjectResponseQuery, SuiPastObjectResponse, SuiRawData,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
 };
+use sui_sdk::types::authenticator_state::{ActiveJwk, AuthenticatorState, AuthenticatorStateInner};
 use sui_sdk::types::digests::TransactionDigest;
+use sui_sdk::types::dynamic_field::{derive_dynamic_field_id, Field};
+use sui_sdk::types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_sdk::types::sui_serde::BigInt as SerdeBigInt;
 use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
+use sui_sdk::types::transaction::Transaction;
 use sui_sdk::{
     types::{
         base_types::{ObjectID as NativeObjectID, SuiAddress as NativeSuiAddress},
         object::Owner as NativeOwner,
         sui_system_state::sui_system_state_summary::SuiValidatorSummary,
+        SUI_AUTHENTICATOR_STATE_OBJECT_ID,
     },
     SuiClient,
 };
 
-use super::data_provider::DataProvider;
+use super::data_provider::{CheckpointWatermark, DataProvider};
 
 const RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD: Duration = Duration::from_millis(10_000);
 const MAX_CONCURRENT_REQUESTS: usize = 1_000;
@@ -119,6 +127,26 @@ impl DataProvider for SuiClient {
         Ok(Some(convert_obj(&g)))
     }
 
+    async fn multi_fetch_obj(&self, addresses: Vec<SuiAddress>) -> Result<Vec<Option<Object>>> {
+        let oids = addresses
+            .iter()
+            .map(|address| address.into_array().as_slice().try_into())
+            .collect::<Result<Vec<NativeObjectID>, _>>()?;
+
+        let responses = self
+            .read_api()
+            .multi_get_object_with_options(oids, SuiObjectDataOptions::full_content())
+            .await?;
+
+        Ok(responses
+            .iter()
+            .map(|n| match (&n.error, &n.data) {
+                (None, Some(data)) => Some(convert_obj(data)),
+                _ => None,
+            })
+            .collect())
+    }
+
     async fn fetch_owned_objs(
         &self,
         owner: &SuiAddress,
@@ -311,6 +339,45 @@ impl DataProvider for SuiClient {
         Ok(connection)
     }
 
+    async fn fetch_transaction_block_connection(
+        &self,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionFilter>,
+    ) -> Result<Connection<String, TransactionBlock>> {
+        ensure_forward_pagination(&first, &after, &last, &before)?;
+
+        let count = first.map(|q| q as usize);
+        let cursor = after
+            .map(|x| TransactionDigest::from_str(&x))
+            .transpose()
+            .map_err(|_| {
+                Error::InvalidCursor(
+                    "Cannot convert after parameter into a transaction digest".to_string(),
+                )
+                .extend()
+            })?;
+
+        let query = SuiTransactionBlockResponseQuery::new(
+            filter,
+            Some(SuiTransactionBlockResponseOptions::full_content()),
+        );
+        let pg = self
+            .read_api()
+            .query_transaction_blocks(query, cursor, count, false)
+            .await?;
+
+        let mut connection = Connection::new(false, pg.has_next_page);
+        connection.edges.extend(pg.data.into_iter().map(|tx| {
+            let tx = TransactionBlock::from(tx);
+            Edge::new(tx.digest.to_string(), tx)
+        }));
+
+        Ok(connection)
+    }
+
     async fn fetch_chain_id(&self) -> Result<String> {
         Ok(self.read_api().get_chain_identifier().await?)
     }
@@ -350,6 +417,101 @@ impl DataProvider for SuiClient {
     async fn get_latest_sui_system_state(&self) -> Result<SuiSystemStateSummary> {
         Ok(self.governance_api().get_latest_sui_system_state().await?)
     }
+
+    async fn fetch_epoch_validators(
+        &self,
+        epoch: u64,
+    ) -> Result<Option<Vec<SuiValidatorSummary>>> {
+        // The cursor is exclusive, so the page starts right after the previous epoch.
+        let cursor = epoch.checked_sub(1).map(SerdeBigInt::from);
+        let pg = self.read_api().get_epochs(cursor, Some(1), false).await?;
+        Ok(pg
+            .data
+            .into_iter()
+            .find(|e| e.epoch == epoch)
+            .map(|e| e.validators))
+    }
+
+    async fn fetch_latest_checkpoint_watermark(&self) -> Result<CheckpointWatermark> {
+        let sequence_number = self
+            .read_api()
+            .get_latest_checkpoint_sequence_number()
+            .await?;
+        let checkpoint = self
+            .read_api()
+            .get_checkpoint(CheckpointId::SequenceNumber(sequence_number))
+            .await?;
+        // Checkpoints are paginated in ascending order, starting from the lowest one that has not
+        // been pruned.
+        let lowest_available = self
+            .read_api()
+            .get_checkpoints(None, Some(1), false)
+            .await?
+            .data
+            .first()
+            .map_or(sequence_number, |cp| cp.sequence_number);
+        Ok(CheckpointWatermark {
+            sequence_number,
+            timestamp_ms: checkpoint.timestamp_ms,
+            lowest_available,
+        })
+    }
+
+    async fn fetch_active_jwks(&self) -> Result<Vec<ActiveJwk>> {
+        let Some(state) =
+            fetch_move_object::<AuthenticatorState>(self, SUI_AUTHENTICATOR_STATE_OBJECT_ID).await?
+        else {
+            return Ok(vec![]);
+        };
+
+        // The JWKs are stored in a dynamic field of the state object, keyed by its version.
+        let version_bytes = bcs::to_bytes(&state.version)
+            .map_err(|e| Error::Internal(format!("Failed to serialize field name: {e}")).extend())?;
+        let inner_id = derive_dynamic_field_id(state.id.id.bytes, &TypeTag::U64, &version_bytes)
+            .map_err(|e| Error::Internal(format!("Failed to derive field ID: {e}")).extend())?;
+        let inner = fetch_move_object::<Field<u64, AuthenticatorStateInner>>(self, inner_id)
+            .await?
+            .ok_or_else(|| {
+                Error::Internal("Authenticator state has no inner object".to_string()).extend()
+            })?;
+        Ok(inner.value.active_jwks)
+    }
+
+    async fn execute_transaction_block(
+        &self,
+        transaction: Transaction,
+    ) -> Result<SuiTransactionBlockResponse> {
+        Ok(self
+            .quorum_driver_api()
+            .execute_transaction_block(
+                transaction,
+                SuiTransactionBlockResponseOptions::new()
+                    .with_effects()
+                    .with_object_changes(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await?)
+    }
+}
+
+/// The contents of the Move object `id`, deserialized from BCS, or `None` if it does not exist.
+async fn fetch_move_object<T: DeserializeOwned>(
+    client: &SuiClient,
+    id: NativeObjectID,
+) -> Result<Option<T>> {
+    let response = client
+        .read_api()
+        .get_object_with_options(id, SuiObjectDataOptions::new().with_bcs())
+        .await?;
+    let Some(data) = response.data else {
+        return Ok(None);
+    };
+    let Some(SuiRawData::MoveObject(object)) = data.bcs else {
+        return Err(Error::Internal(format!("{id} is not a Move object")).extend());
+    };
+    bcs::from_bytes(&object.bcs_bytes)
+        .map(Some)
+        .map_err(|e| Error::Internal(format!("Failed to deserialize {id}: {e}")).extend())
 }
 
 pub(crate) async fn sui_sdk_client_v0(rpc_url: impl AsRef<str>) -> SuiClient {
@@ -453,12 +615,16 @@ pub(crate) fn convert_obj(s: &sui_json_rpc_types::SuiObjectData) -> Object {
                 initial_shared_version: _,
             } => ObjectKind::Shared,
             NativeOwner::Immutable => ObjectKind::Immutable,
+            // Consensus-owned objects are owned by a single address, and only differ from other
+            // owned objects in how transactions using them are sequenced
+            NativeOwner::ConsensusV2 { .. } => ObjectKind::Owned,
         }),
     }
 }
 
 fn convert_bal(b: sui_json_rpc_types::Balance) -> Balance {
     Balance {
+        coin_type: b.coin_type,
         coin_object_count: b.coin_object_count as u64,
         total_balance: BigInt::from_str(&format!("{}", b.total_balance)).unwrap(),
     }
@@ -3,14 +3,28 @@
 
 use async_graphql::{ErrorExtensionValues, ErrorExtensions, Response, ServerError};
 use async_graphql_axum::GraphQLResponse;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::CallError;
 
-/// Error codes for the `extensions.code` field of a GraphQL error that originates from outside
-/// GraphQL.
+/// Error codes for the `extensions.code` field of a GraphQL error. Every error returned by the
+/// service carries one of these codes.
 /// `<https://www.apollographql.com/docs/apollo-server/data/errors/#built-in-error-codes>`
 pub(crate) mod code {
     pub const BAD_REQUEST: &str = "BAD_REQUEST";
     pub const BAD_USER_INPUT: &str = "BAD_USER_INPUT";
+    pub const DATA_PRUNED: &str = "DATA_PRUNED";
+    pub const GRAPHQL_PARSE_FAILED: &str = "GRAPHQL_PARSE_FAILED";
+    pub const GRAPHQL_VALIDATION_FAILED: &str = "GRAPHQL_VALIDATION_FAILED";
     pub const INTERNAL_SERVER_ERROR: &str = "INTERNAL_SERVER_ERROR";
+    pub const LIMIT_EXCEEDED: &str = "LIMIT_EXCEEDED";
+    pub const REQUEST_TIMEOUT: &str = "REQUEST_TIMEOUT";
+    pub const TOO_MANY_REQUESTS: &str = "TOO_MANY_REQUESTS";
+
+    /// Whether the same request may succeed if it is retried later, unchanged. Reported in the
+    /// `extensions.retryable` field of an error.
+    pub fn is_retryable(code: &str) -> bool {
+        matches!(code, REQUEST_TIMEOUT | TOO_MANY_REQUESTS)
+    }
 }
 
 /// Create a GraphQL Response containing an Error.
@@ -30,6 +44,7 @@ pub(crate) fn graphql_error_response(code: &str, message: impl Into<String>) ->
 pub(crate) fn graphql_error(code: &str, message: impl Into<String>) -> ServerError {
     let mut ext = ErrorExtensionValues::default();
     ext.set("code", code);
+    ext.set("retryable", code::is_retryable(code));
 
     ServerError {
         message: message.into(),
@@ -56,22 +71,65 @@ pub enum Error {
     MultiGet(String),
     #[error("Internal error occurred while processing request.")]
     Internal(String),
+    #[error("Request to the data source timed out.")]
+    DataSourceTimeout,
+    #[error("{0}")]
+    DataPruned(String),
+    #[error("Invalid package transaction: {0}")]
+    InvalidPackageTransaction(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Unsupported filter: {0}")]
+    UnsupportedFilter(String),
 }
 
-impl ErrorExtensions for Error {
-    fn extend(&self) -> async_graphql::Error {
-        async_graphql::Error::new(format!("{}", self)).extend_with(|_err, e| match self {
+impl Error {
+    fn code(&self) -> &'static str {
+        match self {
             Error::CursorNoBeforeAfter
             | Error::CursorNoFirstLast
             | Error::CursorNoReversePagination
             | Error::InvalidCursor(_)
             | Error::CursorConnectionFetchFailed(_)
-            | Error::MultiGet(_) => {
-                e.set("code", code::BAD_USER_INPUT);
-            }
-            Error::Internal(_) => {
-                e.set("code", code::INTERNAL_SERVER_ERROR);
+            | Error::MultiGet(_)
+            | Error::InvalidPackageTransaction(_)
+            | Error::InvalidSignature(_)
+            | Error::UnsupportedFilter(_) => code::BAD_USER_INPUT,
+            Error::Internal(_) => code::INTERNAL_SERVER_ERROR,
+            Error::DataSourceTimeout => code::REQUEST_TIMEOUT,
+            Error::DataPruned(_) => code::DATA_PRUNED,
+        }
+    }
+
+    /// Classifies an error from the data source, if it is one that clients can act on: requests
+    /// that timed out, or that asked for data that has been pruned.
+    pub(crate) fn from_data_source(error: &sui_sdk::error::Error) -> Option<Error> {
+        let sui_sdk::error::Error::RpcError(error) = error else {
+            return None;
+        };
+
+        match error {
+            RpcError::RequestTimeout => Some(Error::DataSourceTimeout),
+            // The indexer reports the range of data that is still available alongside requests
+            // for data that has been pruned.
+            RpcError::Call(CallError::Custom(object))
+                if object.data().is_some_and(|data| {
+                    serde_json::from_str::<serde_json::Value>(data.get())
+                        .is_ok_and(|data| data.get("availableRange").is_some())
+                }) =>
+            {
+                Some(Error::DataPruned(object.message().to_string()))
             }
+            _ => None,
+        }
+    }
+}
+
+impl ErrorExtensions for Error {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{}", self)).extend_with(|_err, e| {
+            e.set("code", self.code());
+            e.set("retryable", code::is_retryable(self.code()));
         })
     }
 }
@@ -0,0 +1,97 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextRequest},
+    value, Response,
+};
+use std::sync::Arc;
+
+use crate::server::health::HealthState;
+
+/// Adds the range of checkpoints that the service has data for to every response, so that clients
+/// can tell data that has been pruned (or not indexed yet) from data that does not exist.
+pub(crate) struct AvailableRange {
+    health: Arc<HealthState>,
+}
+
+struct AvailableRangeExtension {
+    health: Arc<HealthState>,
+}
+
+impl AvailableRange {
+    pub(crate) fn new(health: Arc<HealthState>) -> Self {
+        Self { health }
+    }
+}
+
+impl ExtensionFactory for AvailableRange {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(AvailableRangeExtension {
+            health: self.health.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for AvailableRangeExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let resp = next.run(ctx).await;
+        // Nothing to report until the range has been fetched from the data source.
+        let Some((first, last)) = self.health.available_range() else {
+            return resp;
+        };
+
+        resp.extension(
+            "availableRange",
+            value!({
+                "first": first,
+                "last": last,
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+
+    use crate::{
+        config::Health, context_data::data_provider::CheckpointWatermark, types::query::Query,
+    };
+
+    use super::*;
+
+    async fn execute(health: &Arc<HealthState>) -> Response {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(AvailableRange::new(health.clone()))
+            .finish()
+            .execute("{ __typename }")
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_no_range_before_first_watermark() {
+        let health = Arc::new(HealthState::new(Health::default()));
+        let resp = execute(&health).await;
+        assert!(resp.errors.is_empty());
+        assert!(!resp.extensions.contains_key("availableRange"));
+    }
+
+    #[tokio::test]
+    async fn test_range_from_latest_watermark() {
+        let health = Arc::new(HealthState::new(Health::default()));
+        health.record(Ok(CheckpointWatermark {
+            sequence_number: 42,
+            timestamp_ms: 0,
+            lowest_available: 10,
+        }));
+
+        let resp = execute(&health).await;
+        assert!(resp.errors.is_empty());
+        assert_eq!(
+            resp.extensions.get("availableRange"),
+            Some(&value!({ "first": 10, "last": 42 })),
+        );
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::{
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextParseQuery, NextRequest, NextValidation,
+    },
+    parser::types::ExecutableDocument,
+    ErrorExtensionValues, Response, ServerError, ServerResult, ValidationResult, Value, Variables,
+};
+use std::sync::Arc;
+
+use crate::{
+    error::{code, Error},
+    server::health::HealthState,
+};
+
+/// Messages of the validation errors for queries that exceed the service's limits.
+const LIMIT_EXCEEDED_MESSAGES: [&str; 2] = ["Query is nested too deep.", "Query is too complex."];
+
+/// Gives every error in a response the same set of extensions, so that clients can decide how to
+/// handle errors without inspecting their messages:
+///
+/// - `code`, one of the codes in [`code`]. Errors that do not have one already are classified by
+///   where they come from, defaulting to an internal error.
+/// - `retryable`, whether the same request may succeed if it is retried later.
+/// - `checkpoint`, the latest checkpoint known to the service when the request was evaluated, if
+///   any.
+pub(crate) struct ErrorInfo {
+    health: Arc<HealthState>,
+}
+
+struct ErrorInfoExtension {
+    health: Arc<HealthState>,
+}
+
+impl ErrorInfo {
+    pub(crate) fn new(health: Arc<HealthState>) -> Self {
+        Self { health }
+    }
+}
+
+impl ExtensionFactory for ErrorInfo {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(ErrorInfoExtension {
+            health: self.health.clone(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for ErrorInfoExtension {
+    async fn request(&self, ctx: &ExtensionContext<'_>, next: NextRequest<'_>) -> Response {
+        let checkpoint = self.health.available_range().map(|(_, last)| last);
+        let mut resp = next.run(ctx).await;
+        for error in &mut resp.errors {
+            let code = classify(error);
+            let extensions = error.extensions.get_or_insert_with(Default::default);
+            if extensions.get("retryable").is_none() {
+                extensions.set("retryable", code::is_retryable(&code));
+            }
+            if let Some(checkpoint) = checkpoint {
+                extensions.set("checkpoint", checkpoint);
+            }
+        }
+        resp
+    }
+
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        next.run(ctx, query, variables).await.map_err(|mut error| {
+            set_code(&mut error, code::GRAPHQL_PARSE_FAILED);
+            error
+        })
+    }
+
+    async fn validation(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        next: NextValidation<'_>,
+    ) -> Result<ValidationResult, Vec<ServerError>> {
+        next.run(ctx).await.map_err(|mut errors| {
+            for error in &mut errors {
+                let code = if LIMIT_EXCEEDED_MESSAGES.contains(&error.message.as_str()) {
+                    code::LIMIT_EXCEEDED
+                } else {
+                    code::GRAPHQL_VALIDATION_FAILED
+                };
+                set_code(error, code);
+            }
+            errors
+        })
+    }
+}
+
+/// Sets the code of `error` if it does not have one yet.
+fn set_code(error: &mut ServerError, code: &str) {
+    let extensions = error
+        .extensions
+        .get_or_insert_with(ErrorExtensionValues::default);
+    if extensions.get("code").is_none() {
+        extensions.set("code", code);
+    }
+}
+
+/// Returns the code of `error`, first assigning one based on its source if it does not have one.
+fn classify(error: &mut ServerError) -> String {
+    if let Some(Value::String(code)) = error.extensions.as_ref().and_then(|e| e.get("code")) {
+        return code.clone();
+    }
+
+    let code = match error
+        .source::<sui_sdk::error::Error>()
+        .and_then(Error::from_data_source)
+    {
+        Some(Error::DataSourceTimeout) => code::REQUEST_TIMEOUT,
+        Some(Error::DataPruned(_)) => code::DATA_PRUNED,
+        _ => code::INTERNAL_SERVER_ERROR,
+    };
+    set_code(error, code);
+    code.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    use crate::{config::Health, context_data::data_provider::CheckpointWatermark};
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn timeout(&self) -> async_graphql::Result<bool> {
+            Err(sui_sdk::error::Error::RpcError(jsonrpsee::core::Error::RequestTimeout).into())
+        }
+
+        async fn internal(&self) -> async_graphql::Result<bool> {
+            Err("Something went wrong".into())
+        }
+
+        async fn nested(&self) -> Query {
+            Query
+        }
+    }
+
+    async fn execute(health: &Arc<HealthState>, query: &str) -> Response {
+        Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(ErrorInfo::new(health.clone()))
+            .limit_depth(2)
+            .finish()
+            .execute(query)
+            .await
+    }
+
+    fn extension(resp: &Response, name: &str) -> Option<Value> {
+        assert_eq!(resp.errors.len(), 1, "{:?}", resp.errors);
+        resp.errors[0]
+            .extensions
+            .as_ref()
+            .and_then(|e| e.get(name))
+            .cloned()
+    }
+
+    #[tokio::test]
+    async fn test_error_codes() {
+        let health = Arc::new(HealthState::new(Health::default()));
+
+        let resp = execute(&health, "{ timeout }").await;
+        assert_eq!(
+            extension(&resp, "code"),
+            Some(Value::from(code::REQUEST_TIMEOUT))
+        );
+        assert_eq!(extension(&resp, "retryable"), Some(Value::from(true)));
+        // No watermark has been recorded yet.
+        assert_eq!(extension(&resp, "checkpoint"), None);
+
+        let resp = execute(&health, "{ internal }").await;
+        assert_eq!(
+            extension(&resp, "code"),
+            Some(Value::from(code::INTERNAL_SERVER_ERROR))
+        );
+        assert_eq!(extension(&resp, "retryable"), Some(Value::from(false)));
+
+        let resp = execute(&health, "{ nested { nested { internal } } }").await;
+        assert_eq!(
+            extension(&resp, "code"),
+            Some(Value::from(code::LIMIT_EXCEEDED))
+        );
+
+        let resp = execute(&health, "{ unknown }").await;
+        assert_eq!(
+            extension(&resp, "code"),
+            Some(Value::from(code::GRAPHQL_VALIDATION_FAILED))
+        );
+
+        let resp = execute(&health, "{ internal").await;
+        assert_eq!(
+            extension(&resp, "code"),
+            Some(Value::from(code::GRAPHQL_PARSE_FAILED))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_at_evaluation() {
+        let health = Arc::new(HealthState::new(Health::default()));
+        health.record(Ok(CheckpointWatermark {
+            sequence_number: 42,
+            timestamp_ms: 0,
+            lowest_available: 10,
+        }));
+
+        let resp = execute(&health, "{ internal }").await;
+        assert_eq!(extension(&resp, "checkpoint"), Some(Value::from(42)));
+    }
+}
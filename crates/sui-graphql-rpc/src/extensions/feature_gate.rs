@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use async_graphql::{
     extensions::{Extension, ExtensionContext, ExtensionFactory, NextResolve, ResolveInfo},
-    ServerError, ServerResult, Value,
+    ServerResult, Value,
 };
 use async_trait::async_trait;
 
@@ -57,16 +57,16 @@ impl Extension for FeatureGate {
                 return if *is_for_introspection {
                     Ok(None)
                 } else {
-                    Err(ServerError::new(
+                    // TODO: Fork `async-graphl` to add field position information to
+                    // `ResolveInfo`, so the error can take advantage of it.  Similarly for
+                    // utilising the `path_node` to set the error path.
+                    Err(graphql_error(
+                        code::GRAPHQL_VALIDATION_FAILED,
                         format!(
                             "Cannot query field \"{name}\" on type \"{parent_type}\". \
                              Feature {} is disabled.",
                             group.name(),
                         ),
-                        // TODO: Fork `async-graphl` to add field position information to
-                        // `ResolveInfo`, so the error can take advantage of it.  Similarly for
-                        // utilising the `path_node` to set the error path.
-                        None,
                     ))
                 };
             }
@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod available_range;
+pub(crate) mod error_info;
 pub(crate) mod feature_gate;
 pub(crate) mod limits_info;
 pub(crate) mod logger;
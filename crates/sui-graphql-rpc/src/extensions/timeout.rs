@@ -3,12 +3,14 @@
 
 use async_graphql::{
     extensions::{Extension, ExtensionContext, ExtensionFactory, NextRequest},
-    Response, ServerError,
+    Response,
 };
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::timeout;
 
+use crate::error::{code, graphql_error};
+
 // 10s
 const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_millis(10_000);
 
@@ -48,12 +50,12 @@ impl Extension for TimeoutExtension {
         timeout(self.config.request_timeout, next.run(ctx))
             .await
             .unwrap_or_else(|_| {
-                Response::from_errors(vec![ServerError::new(
+                Response::from_errors(vec![graphql_error(
+                    code::REQUEST_TIMEOUT,
                     format!(
                         "Request timed out. Limit: {}s",
                         self.config.request_timeout.as_secs_f32()
                     ),
-                    None,
                 )])
             })
     }
@@ -67,6 +67,7 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
         BTreeMap::from_iter([
             (("Address", "balance"), G::Coins),
             (("Address", "balanceConnection"), G::Coins),
+            (("Address", "balances"), G::Coins),
             (("Address", "coinConnection"), G::Coins),
             (("Address", "defaultNameServiceName"), G::NameService),
             (("Address", "nameServiceConnection"), G::NameService),
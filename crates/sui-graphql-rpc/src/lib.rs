@@ -3,6 +3,7 @@
 
 pub mod commands;
 pub mod config;
+pub mod pricing;
 pub mod server;
 
 pub(crate) mod functional_group;
@@ -15,10 +16,11 @@ mod types;
 use async_graphql::*;
 use types::owner::ObjectOwner;
 
+use crate::types::mutation::Mutation;
 use crate::types::query::Query;
 
 pub fn schema_sdl_export() -> String {
-    let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+    let schema = Schema::build(Query, Mutation, EmptySubscription)
         .register_output_type::<ObjectOwner>()
         .finish();
     schema.sdl()
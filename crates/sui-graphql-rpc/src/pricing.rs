@@ -0,0 +1,110 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Extension point for estimating the fiat value of balances.  Deployments that want to serve
+//! `Balance.usdValue` provide a [`PriceOracle`], which is queried for the prices of all the coin
+//! types whose values are requested together.  Without an oracle, values are not estimated.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::*;
+use async_trait::async_trait;
+
+/// Price of a coin, in US dollars.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CoinPrice {
+    /// Price of one whole coin.
+    pub usd: f64,
+    /// Number of decimal places of the coin: balances are denominated in `10^-decimals` coins.
+    pub decimals: u8,
+}
+
+#[async_trait]
+pub trait PriceOracle: Send + Sync {
+    /// Current prices of coins of the given types, keyed by their fully-qualified type (e.g.
+    /// `0x2::sui::SUI`). Coin types the oracle does not know a price for are left out.
+    async fn fetch_prices(&self, coin_types: &[String]) -> Result<HashMap<String, CoinPrice>>;
+}
+
+pub(crate) struct PriceLoader {
+    oracle: Arc<dyn PriceOracle>,
+}
+
+impl CoinPrice {
+    /// Estimated value in US dollars of `amount` of the coin's smallest unit.
+    pub(crate) fn usd_value(&self, amount: f64) -> f64 {
+        amount / 10f64.powi(self.decimals as i32) * self.usd
+    }
+}
+
+/// Batches the price look-ups issued while resolving a request, so that the oracle is called once
+/// for all the balances in a page.  Prices are not cached across requests, as they go stale.
+pub(crate) fn price_data_loader(oracle: Arc<dyn PriceOracle>) -> DataLoader<PriceLoader> {
+    DataLoader::new(PriceLoader { oracle }, tokio::spawn)
+}
+
+#[async_trait]
+impl Loader<String> for PriceLoader {
+    type Value = CoinPrice;
+    type Error = async_graphql::Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, CoinPrice>, Self::Error> {
+        self.oracle.fetch_prices(keys).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FixedPrices {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl PriceOracle for FixedPrices {
+        async fn fetch_prices(&self, coin_types: &[String]) -> Result<HashMap<String, CoinPrice>> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            Ok(coin_types
+                .iter()
+                .filter(|t| *t == "0x2::sui::SUI")
+                .map(|t| {
+                    let price = CoinPrice {
+                        usd: 0.5,
+                        decimals: 9,
+                    };
+                    (t.clone(), price)
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_usd_value() {
+        let price = CoinPrice {
+            usd: 0.5,
+            decimals: 9,
+        };
+        assert_eq!(price.usd_value(3_000_000_000.0), 1.5);
+        assert_eq!(price.usd_value(0.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_prices_are_batched() {
+        let oracle = Arc::new(FixedPrices {
+            calls: AtomicUsize::new(0),
+        });
+        let loader = price_data_loader(oracle.clone());
+
+        let sui = loader.load_one("0x2::sui::SUI".to_string());
+        let unknown = loader.load_one("0x42::coin::COIN".to_string());
+        let (sui, unknown) = tokio::join!(sui, unknown);
+
+        assert_eq!(sui.unwrap().map(|p| p.usd), Some(0.5));
+        assert_eq!(unknown.unwrap(), None);
+        assert_eq!(oracle.calls.load(Ordering::Relaxed), 1);
+    }
+}
@@ -2,21 +2,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    config::RateLimits,
+    error::{code, graphql_error_response},
     extensions::limits_info::ShowUsage,
+    pricing::{price_data_loader, PriceOracle},
+    server::health::{self, HealthState},
+    server::rate_limit::{Budget, RateLimiter},
     server::version::{check_version_middleware, set_version_middleware},
+    types::mutation::Mutation,
     types::query::{Query, SuiGraphQLSchema},
 };
+use async_graphql::EmptySubscription;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql::{EmptyMutation, EmptySubscription};
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::{connect_info::IntoMakeServiceWithConnectInfo, ConnectInfo};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
 use axum::{middleware, TypedHeader};
-use axum::{routing::IntoMakeService, Router};
 use hyper::server::conn::AddrIncoming as HyperAddrIncoming;
 use hyper::Server as HyperServer;
 use std::any::Any;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
 
 pub(crate) struct Server {
-    pub server: HyperServer<HyperAddrIncoming, IntoMakeService<Router>>,
+    pub server: HyperServer<HyperAddrIncoming, IntoMakeServiceWithConnectInfo<Router, SocketAddr>>,
 }
 
 impl Server {
@@ -29,7 +41,9 @@ pub(crate) struct ServerBuilder {
     port: u16,
     host: String,
 
-    schema: SchemaBuilder<Query, EmptyMutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    health: Option<Arc<HealthState>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl ServerBuilder {
@@ -37,7 +51,9 @@ impl ServerBuilder {
         Self {
             port,
             host,
-            schema: async_graphql::Schema::build(Query, EmptyMutation, EmptySubscription),
+            schema: async_graphql::Schema::build(Query, Mutation, EmptySubscription),
+            health: None,
+            rate_limiter: None,
         }
     }
 
@@ -65,36 +81,85 @@ impl ServerBuilder {
         self
     }
 
-    fn build_schema(self) -> Schema<Query, EmptyMutation, EmptySubscription> {
+    /// Estimate the fiat value of balances with prices from `oracle`.
+    pub fn price_oracle(self, oracle: Arc<dyn PriceOracle>) -> Self {
+        self.context_data(price_data_loader(oracle))
+    }
+
+    /// Serve `/health` and `/ready` endpoints reporting on `health`.
+    pub fn health(mut self, health: Arc<HealthState>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// Limit the rate of requests from each client, if `config` enables it.
+    pub fn rate_limits(mut self, config: RateLimits) -> Self {
+        self.rate_limiter = config.enabled.then(|| Arc::new(RateLimiter::new(config)));
+        self
+    }
+
+    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
         self.schema.finish()
     }
 
     pub fn build(self) -> Server {
         let address = self.address();
+        let health_state = self.health.clone();
+        let rate_limiter = self.rate_limiter.clone();
         let schema = self.build_schema();
 
-        let app = axum::Router::new()
+        let mut app = axum::Router::new()
             .route("/", axum::routing::get(graphiql).post(graphql_handler))
             .layer(axum::extract::Extension(schema))
+            .layer(axum::extract::Extension(rate_limiter))
             .layer(middleware::from_fn(check_version_middleware))
             .layer(middleware::from_fn(set_version_middleware));
+
+        // Health checks are added after the version middleware, as load balancers probing them
+        // don't send version headers.
+        if let Some(health_state) = health_state {
+            app = app
+                .route("/health", axum::routing::get(health::health))
+                .route("/ready", axum::routing::get(health::ready))
+                .layer(axum::extract::Extension(health_state));
+        }
         Server {
-            server: axum::Server::bind(&address.parse().unwrap()).serve(app.into_make_service()),
+            server: axum::Server::bind(&address.parse().unwrap())
+                .serve(app.into_make_service_with_connect_info::<SocketAddr>()),
         }
     }
 }
 
 async fn graphql_handler(
     schema: axum::Extension<SuiGraphQLSchema>,
+    rate_limiter: axum::Extension<Option<Arc<RateLimiter>>>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     usage: Option<TypedHeader<ShowUsage>>,
     req: GraphQLRequest,
-) -> GraphQLResponse {
+) -> Response {
     let mut req = req.into_inner();
     if let Some(TypedHeader(usage)) = usage {
         req.data.insert(usage)
     }
 
-    schema.execute(req).await.into()
+    let Some(rate_limiter) = rate_limiter.0 else {
+        return GraphQLResponse::from(schema.execute(req).await).into_response();
+    };
+
+    let client = rate_limiter.client(&headers, connect_info.map(|ConnectInfo(addr)| addr.ip()));
+    let status = rate_limiter.check(client, Budget::of(&req), Instant::now());
+    if !status.allowed {
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            status.headers(),
+            graphql_error_response(code::TOO_MANY_REQUESTS, "Rate limit exceeded."),
+        )
+            .into_response();
+    }
+
+    let response = GraphQLResponse::from(schema.execute(req).await);
+    (status.headers(), response).into_response()
 }
 
 async fn graphiql() -> impl axum::response::IntoResponse {
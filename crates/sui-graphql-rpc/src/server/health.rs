@@ -0,0 +1,286 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use axum::{extract::Extension, http::StatusCode, Json};
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::{
+    config::Health,
+    context_data::data_provider::{CheckpointWatermark, DataProvider},
+};
+
+/// The watermark task is considered dead if it has not polled the data source for this many poll
+/// intervals.
+const MISSED_POLLS_BEFORE_DEAD: u32 = 3;
+
+/// State shared between the background task that tracks the latest checkpoint in the data source
+/// (the watermark), and the `/health` and `/ready` endpoints that report on it.
+pub(crate) struct HealthState {
+    config: Health,
+    inner: RwLock<Inner>,
+}
+
+struct Inner {
+    /// Last time the watermark task polled the data source, successfully or not.
+    heartbeat: Instant,
+    /// Latest watermark fetched from the data source.
+    watermark: Option<CheckpointWatermark>,
+    /// Error from the last poll, if it failed.
+    error: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct HealthReport {
+    /// Whether the background task tracking the watermark is still running.
+    watermark_task_alive: bool,
+    /// Whether the last attempt to reach the data source succeeded.
+    data_source_connected: bool,
+    latest_checkpoint: Option<u64>,
+    /// How far behind the wall clock the latest checkpoint's timestamp is.
+    checkpoint_lag_ms: Option<u64>,
+    max_checkpoint_lag_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl HealthState {
+    pub(crate) fn new(config: Health) -> Self {
+        Self {
+            config,
+            inner: RwLock::new(Inner {
+                heartbeat: Instant::now(),
+                watermark: None,
+                error: None,
+            }),
+        }
+    }
+
+    /// Record the outcome of polling the data source for its latest checkpoint.
+    pub(crate) fn record(&self, result: Result<CheckpointWatermark, String>) {
+        let mut inner = self.inner.write().unwrap();
+        inner.heartbeat = Instant::now();
+        match result {
+            Ok(watermark) => {
+                inner.watermark = Some(watermark);
+                inner.error = None;
+            }
+            Err(e) => inner.error = Some(e),
+        }
+    }
+
+    /// The first and last checkpoints that the data source has data for, as of the latest
+    /// watermark, if there is one yet.
+    pub(crate) fn available_range(&self) -> Option<(u64, u64)> {
+        let inner = self.inner.read().unwrap();
+        inner
+            .watermark
+            .as_ref()
+            .map(|w| (w.lowest_available, w.sequence_number))
+    }
+
+    fn report(&self) -> HealthReport {
+        let inner = self.inner.read().unwrap();
+        let poll_interval = Duration::from_millis(self.config.watermark_poll_interval_ms);
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        HealthReport {
+            watermark_task_alive: inner.heartbeat.elapsed()
+                <= poll_interval * MISSED_POLLS_BEFORE_DEAD,
+            data_source_connected: inner.watermark.is_some() && inner.error.is_none(),
+            latest_checkpoint: inner.watermark.as_ref().map(|w| w.sequence_number),
+            checkpoint_lag_ms: inner
+                .watermark
+                .as_ref()
+                .map(|w| now_ms.saturating_sub(w.timestamp_ms)),
+            max_checkpoint_lag_ms: self.config.max_checkpoint_lag_ms,
+            error: inner.error.clone(),
+        }
+    }
+}
+
+impl HealthReport {
+    /// The service is alive as long as its background tasks are.
+    fn is_healthy(&self) -> bool {
+        self.watermark_task_alive
+    }
+
+    /// The service is ready to serve requests if it is alive, can reach its data source, and that
+    /// data source is not lagging too far behind the network.
+    fn is_ready(&self) -> bool {
+        self.is_healthy()
+            && self.data_source_connected
+            && self
+                .checkpoint_lag_ms
+                .is_some_and(|lag| lag <= self.max_checkpoint_lag_ms)
+    }
+}
+
+/// Spawn the task that periodically fetches the latest checkpoint from `data_provider`, and
+/// records it in `state`.
+pub(crate) fn spawn_watermark_task(
+    state: Arc<HealthState>,
+    data_provider: Box<dyn DataProvider>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_millis(
+            state.config.watermark_poll_interval_ms,
+        ));
+        loop {
+            interval.tick().await;
+            let result = data_provider
+                .fetch_latest_checkpoint_watermark()
+                .await
+                .map_err(|e| e.message);
+            if let Err(e) = &result {
+                warn!("Failed to fetch the latest checkpoint watermark: {e}");
+            }
+            state.record(result);
+        }
+    })
+}
+
+/// Liveness check: 200 while the service's background tasks are running, 503 otherwise.
+pub(crate) async fn health(
+    Extension(state): Extension<Arc<HealthState>>,
+) -> (StatusCode, Json<HealthReport>) {
+    let report = state.report();
+    let status = if report.is_healthy() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+/// Readiness check: 200 if the service can serve up-to-date data, 503 if it can't reach its data
+/// source, or the data source's latest checkpoint lags too far behind.
+pub(crate) async fn ready(
+    Extension(state): Extension<Arc<HealthState>>,
+) -> (StatusCode, Json<HealthReport>) {
+    let report = state.report();
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    fn service(state: Arc<HealthState>) -> Router {
+        Router::new()
+            .route("/health", get(health))
+            .route("/ready", get(ready))
+            .layer(Extension(state))
+    }
+
+    async fn status(state: &Arc<HealthState>, uri: &str) -> StatusCode {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        service(state.clone())
+            .oneshot(request)
+            .await
+            .unwrap()
+            .status()
+    }
+
+    fn now_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64
+    }
+
+    #[tokio::test]
+    async fn not_ready_before_first_watermark() {
+        let state = Arc::new(HealthState::new(Health::default()));
+        assert_eq!(status(&state, "/health").await, StatusCode::OK);
+        assert_eq!(
+            status(&state, "/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[tokio::test]
+    async fn ready_with_recent_watermark() {
+        let state = Arc::new(HealthState::new(Health::default()));
+        state.record(Ok(CheckpointWatermark {
+            sequence_number: 42,
+            timestamp_ms: now_ms(),
+            lowest_available: 0,
+        }));
+        assert_eq!(status(&state, "/health").await, StatusCode::OK);
+        assert_eq!(status(&state, "/ready").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn not_ready_when_lagging() {
+        let config = Health::default();
+        let lag = config.max_checkpoint_lag_ms + 1000;
+        let state = Arc::new(HealthState::new(config));
+        state.record(Ok(CheckpointWatermark {
+            sequence_number: 42,
+            timestamp_ms: now_ms() - lag,
+            lowest_available: 0,
+        }));
+        assert_eq!(status(&state, "/health").await, StatusCode::OK);
+        assert_eq!(
+            status(&state, "/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[tokio::test]
+    async fn not_ready_when_disconnected() {
+        let state = Arc::new(HealthState::new(Health::default()));
+        state.record(Ok(CheckpointWatermark {
+            sequence_number: 42,
+            timestamp_ms: now_ms(),
+            lowest_available: 0,
+        }));
+        state.record(Err("connection refused".to_string()));
+        assert_eq!(status(&state, "/health").await, StatusCode::OK);
+        assert_eq!(
+            status(&state, "/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[tokio::test]
+    async fn unhealthy_when_watermark_task_stalls() {
+        let state = Arc::new(HealthState::new(Health {
+            watermark_poll_interval_ms: 10,
+            ..Default::default()
+        }));
+        state.record(Ok(CheckpointWatermark {
+            sequence_number: 42,
+            timestamp_ms: now_ms(),
+            lowest_available: 0,
+        }));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            status(&state, "/health").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(&state, "/ready").await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
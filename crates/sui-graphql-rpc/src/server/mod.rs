@@ -4,4 +4,6 @@
 pub mod simple_server;
 
 mod builder;
+mod health;
+mod rate_limit;
 mod version;
@@ -0,0 +1,303 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr},
+    sync::Mutex,
+    time::Instant,
+};
+
+use async_graphql::parser::{parse_query, types::OperationType};
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::config::{Quota, Quotas, RateLimits};
+
+pub(crate) static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-api-key");
+static LIMIT_HEADER: HeaderName = HeaderName::from_static("ratelimit-limit");
+static REMAINING_HEADER: HeaderName = HeaderName::from_static("ratelimit-remaining");
+static RESET_HEADER: HeaderName = HeaderName::from_static("ratelimit-reset");
+static RETRY_AFTER_HEADER: HeaderName = HeaderName::from_static("retry-after");
+
+/// Buckets that have refilled completely carry no information, and are dropped once there are
+/// more than this many buckets.
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+/// Kinds of request that are budgeted separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Budget {
+    Read,
+    Execute,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Client {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+/// Outcome of charging a request to its client's budget, reported back in `RateLimit-*` headers.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct RateLimitStatus {
+    pub allowed: bool,
+    /// Size of the budget.
+    pub limit: u32,
+    /// Requests left in the budget.
+    pub remaining: u32,
+    /// Seconds until the budget is full again.
+    pub reset_secs: u64,
+    /// Seconds until the next request would be allowed, if this one was not.
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Token buckets for every client and kind of request that has been seen recently.
+pub(crate) struct RateLimiter {
+    config: RateLimits,
+    buckets: Mutex<HashMap<(Client, Budget), TokenBucket>>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl Budget {
+    /// Requests containing a mutation execute transactions, all others only read data.
+    pub(crate) fn of(request: &async_graphql::Request) -> Self {
+        let Ok(document) = parse_query(&request.query) else {
+            // The request will be rejected when it is executed, so it only reads.
+            return Budget::Read;
+        };
+
+        if document
+            .operations
+            .iter()
+            .any(|(_, op)| op.node.ty == OperationType::Mutation)
+        {
+            Budget::Execute
+        } else {
+            Budget::Read
+        }
+    }
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimits) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Clients that present a known API key are identified by it, all others by their IP
+    /// address.
+    pub(crate) fn client(&self, headers: &HeaderMap, ip: Option<IpAddr>) -> Client {
+        if let Some(key) = headers
+            .get(&API_KEY_HEADER)
+            .and_then(|key| key.to_str().ok())
+        {
+            if self.config.api_keys.contains_key(key) {
+                return Client::ApiKey(key.to_string());
+            }
+        }
+
+        Client::Ip(ip.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED)))
+    }
+
+    /// Charge a request to `client`'s `budget`, if there is anything left in it.
+    pub(crate) fn check(&self, client: Client, budget: Budget, now: Instant) -> RateLimitStatus {
+        let quota = self.quota(&client, budget);
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() > MAX_IDLE_BUCKETS {
+            buckets.retain(|(client, budget), bucket| {
+                let quota = self.quota(client, *budget);
+                bucket.refill(quota, now) < quota.burst as f64
+            });
+        }
+
+        let bucket = buckets
+            .entry((client, budget))
+            .or_insert_with(|| TokenBucket {
+                tokens: quota.burst as f64,
+                refilled_at: now,
+            });
+
+        let tokens = bucket.refill(quota, now);
+        let allowed = tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        RateLimitStatus {
+            allowed,
+            limit: quota.burst,
+            remaining: bucket.tokens as u32,
+            reset_secs: secs_to_refill(quota, quota.burst as f64 - bucket.tokens),
+            retry_after_secs: (!allowed).then(|| secs_to_refill(quota, 1.0 - bucket.tokens)),
+        }
+    }
+
+    fn quota(&self, client: &Client, budget: Budget) -> Quota {
+        let Quotas { read, execute } = match client {
+            Client::ApiKey(key) => self.config.api_keys.get(key).unwrap_or(&self.config.quotas),
+            Client::Ip(_) => &self.config.quotas,
+        };
+
+        match budget {
+            Budget::Read => *read,
+            Budget::Execute => *execute,
+        }
+    }
+}
+
+impl TokenBucket {
+    /// Top up the bucket for the time elapsed since it was last refilled, and return the tokens
+    /// in it.
+    fn refill(&mut self, quota: Quota, now: Instant) -> f64 {
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * quota.requests_per_second as f64).min(quota.burst as f64);
+        self.refilled_at = now;
+        self.tokens
+    }
+}
+
+impl RateLimitStatus {
+    pub(crate) fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(LIMIT_HEADER.clone(), HeaderValue::from(self.limit));
+        headers.insert(REMAINING_HEADER.clone(), HeaderValue::from(self.remaining));
+        headers.insert(RESET_HEADER.clone(), HeaderValue::from(self.reset_secs));
+        if let Some(retry_after) = self.retry_after_secs {
+            headers.insert(RETRY_AFTER_HEADER.clone(), HeaderValue::from(retry_after));
+        }
+        headers
+    }
+}
+
+/// Whole seconds it takes `quota` to replenish `tokens`.
+fn secs_to_refill(quota: Quota, tokens: f64) -> u64 {
+    if tokens <= 0.0 || quota.requests_per_second == 0 {
+        0
+    } else {
+        (tokens / quota.requests_per_second as f64).ceil() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn rate_limiter() -> RateLimiter {
+        let quota = |requests_per_second, burst| Quota {
+            requests_per_second,
+            burst,
+        };
+
+        RateLimiter::new(RateLimits {
+            enabled: true,
+            quotas: Quotas {
+                read: quota(1, 2),
+                execute: quota(1, 1),
+            },
+            api_keys: BTreeMap::from([(
+                "partner".to_string(),
+                Quotas {
+                    read: quota(10, 20),
+                    execute: quota(1, 1),
+                },
+            )]),
+        })
+    }
+
+    fn ip(last: u8) -> Client {
+        Client::Ip(IpAddr::V4(Ipv4Addr::new(10, 0, 0, last)))
+    }
+
+    #[test]
+    fn burst_then_refill() {
+        let limiter = rate_limiter();
+        let start = Instant::now();
+
+        let first = limiter.check(ip(1), Budget::Read, start);
+        assert_eq!(
+            first,
+            RateLimitStatus {
+                allowed: true,
+                limit: 2,
+                remaining: 1,
+                reset_secs: 1,
+                retry_after_secs: None,
+            }
+        );
+
+        assert!(limiter.check(ip(1), Budget::Read, start).allowed);
+
+        let denied = limiter.check(ip(1), Budget::Read, start);
+        assert!(!denied.allowed);
+        assert_eq!(denied.remaining, 0);
+        assert_eq!(denied.reset_secs, 2);
+        assert_eq!(denied.retry_after_secs, Some(1));
+
+        // A second later, one request's worth of budget has been replenished.
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check(ip(1), Budget::Read, later).allowed);
+        assert!(!limiter.check(ip(1), Budget::Read, later).allowed);
+    }
+
+    #[test]
+    fn budgets_and_clients_are_separate() {
+        let limiter = rate_limiter();
+        let now = Instant::now();
+
+        assert!(limiter.check(ip(1), Budget::Execute, now).allowed);
+        assert!(!limiter.check(ip(1), Budget::Execute, now).allowed);
+
+        // Exhausting the execute budget leaves the read budget, and other clients, untouched.
+        assert!(limiter.check(ip(1), Budget::Read, now).allowed);
+        assert!(limiter.check(ip(2), Budget::Execute, now).allowed);
+
+        let partner = Client::ApiKey("partner".to_string());
+        let status = limiter.check(partner, Budget::Read, now);
+        assert!(status.allowed);
+        assert_eq!(status.limit, 20);
+    }
+
+    #[test]
+    fn identify_clients() {
+        let limiter = rate_limiter();
+        let addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(limiter.client(&headers, Some(addr)), Client::Ip(addr));
+
+        // Unknown API keys don't get a budget of their own.
+        headers.insert(API_KEY_HEADER.clone(), HeaderValue::from_static("unknown"));
+        assert_eq!(limiter.client(&headers, Some(addr)), Client::Ip(addr));
+
+        headers.insert(API_KEY_HEADER.clone(), HeaderValue::from_static("partner"));
+        assert_eq!(
+            limiter.client(&headers, Some(addr)),
+            Client::ApiKey("partner".to_string())
+        );
+    }
+
+    #[test]
+    fn classify_requests() {
+        let read = async_graphql::Request::new("{ chainIdentifier }");
+        assert_eq!(Budget::of(&read), Budget::Read);
+
+        let execute =
+            async_graphql::Request::new("mutation { executeTransactionBlock(txBytes: \"\") }");
+        assert_eq!(Budget::of(&execute), Budget::Execute);
+
+        let invalid = async_graphql::Request::new("{ not valid");
+        assert_eq!(Budget::of(&invalid), Budget::Read);
+    }
+}
@@ -4,15 +4,30 @@
 use crate::config::{ConnectionConfig, ServiceConfig};
 use crate::context_data::data_provider::DataProvider;
 use crate::context_data::sui_sdk_data_provider::{lru_cache_data_loader, sui_sdk_client_v0};
+use crate::extensions::available_range::AvailableRange;
+use crate::extensions::error_info::ErrorInfo;
 use crate::extensions::feature_gate::FeatureGate;
 use crate::extensions::limits_info::LimitsInfo;
 use crate::extensions::logger::Logger;
 use crate::extensions::timeout::Timeout;
+use crate::pricing::PriceOracle;
 use crate::server::builder::ServerBuilder;
+use crate::server::health::{spawn_watermark_task, HealthState};
 
 use std::default::Default;
+use std::sync::Arc;
 
 pub async fn start_example_server(conn: ConnectionConfig, service_config: ServiceConfig) {
+    start_example_server_with_price_oracle(conn, service_config, None).await
+}
+
+/// Like `start_example_server`, but estimating the fiat value of balances with prices from
+/// `price_oracle`, if one is provided.
+pub async fn start_example_server_with_price_oracle(
+    conn: ConnectionConfig,
+    service_config: ServiceConfig,
+    price_oracle: Option<Arc<dyn PriceOracle>>,
+) {
     let _guard = telemetry_subscribers::TelemetryConfig::new()
         .with_env()
         .init();
@@ -21,15 +36,26 @@ pub async fn start_example_server(conn: ConnectionConfig, service_config: Servic
     let data_provider: Box<dyn DataProvider> = Box::new(sui_sdk_client_v0.clone());
     let data_loader = lru_cache_data_loader(&sui_sdk_client_v0).await;
 
-    let builder = ServerBuilder::new(conn.port, conn.host);
+    let health = Arc::new(HealthState::new(service_config.health.clone()));
+    spawn_watermark_task(health.clone(), Box::new(sui_sdk_client_v0.clone()));
+
+    let mut builder = ServerBuilder::new(conn.port, conn.host);
     println!("Launch GraphiQL IDE at: http://{}", builder.address());
 
+    if let Some(price_oracle) = price_oracle {
+        builder = builder.price_oracle(price_oracle);
+    }
+
     builder
         .max_query_depth(service_config.limits.max_query_depth)
         .max_query_nodes(service_config.limits.max_query_nodes)
+        .rate_limits(service_config.rate_limits.clone())
         .context_data(data_provider)
         .context_data(data_loader)
         .context_data(service_config)
+        .health(health.clone())
+        .extension(ErrorInfo::new(health.clone()))
+        .extension(AvailableRange::new(health))
         .extension(FeatureGate)
         .extension(LimitsInfo)
         .extension(Logger::default())
@@ -44,6 +44,18 @@ impl Address {
         unimplemented!()
     }
 
+    /// All the balances owned by this address, one per coin type, paginated forwards.
+    async fn balances(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+    ) -> Result<Connection<String, Balance>> {
+        ctx.data_provider()
+            .fetch_balance_connection(&self.address, first, after, None, None)
+            .await
+    }
+
     // =========== Owner interface methods =============
 
     pub async fn location(&self) -> SuiAddress {
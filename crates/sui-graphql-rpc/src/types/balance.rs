@@ -2,12 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::big_int::BigInt;
+use crate::error::Error;
+use crate::pricing::PriceLoader;
 use crate::types::owner::Owner;
+use async_graphql::dataloader::DataLoader;
 use async_graphql::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+#[graphql(complex)]
 pub(crate) struct Balance {
-    // pub(crate) coin_type: MoveType,
+    /// Fully-qualified type of the coin, e.g. `0x2::sui::SUI`.
+    pub(crate) coin_type: String,
     pub(crate) coin_object_count: u64,
     pub(crate) total_balance: BigInt,
 }
@@ -18,3 +23,19 @@ pub(crate) struct BalanceChange {
     pub(crate) amount: BigInt,
     // pub(crate) coin_type: MoveType,
 }
+
+#[ComplexObject]
+impl Balance {
+    /// Estimated value of the balance in US dollars, if the service is configured with a price
+    /// oracle that knows the price of this coin.
+    async fn usd_value(&self, ctx: &Context<'_>) -> Result<Option<f64>> {
+        let Some(loader) = ctx.data_opt::<DataLoader<PriceLoader>>() else {
+            return Ok(None);
+        };
+
+        let price = loader.load_one(self.coin_type.clone()).await.map_err(|e| {
+            Error::Internal(format!("Failed to fetch price: {}", e.message)).extend()
+        })?;
+        Ok(price.map(|p| p.usd_value(self.total_balance.to_f64())))
+    }
+}
@@ -29,6 +29,13 @@ impl ScalarType for BigInt {
 #[derive(Debug, PartialEq, Eq)]
 pub(crate) struct NotANumber;
 
+impl BigInt {
+    /// The number as a float, for estimates that can tolerate the loss of precision.
+    pub(crate) fn to_f64(&self) -> f64 {
+        self.0.parse().expect("BigInt is always a valid number")
+    }
+}
+
 impl FromStr for BigInt {
     type Err = NotANumber;
 
@@ -62,7 +69,7 @@ macro_rules! impl_From {
     }
 }
 
-impl_From!(u8, u16, u32, u64, u128, U256);
+impl_From!(u8, u16, u32, u64, u128, i128, U256);
 
 #[cfg(test)]
 mod tests {
@@ -24,10 +24,10 @@ impl std::str::FromStr for Digest {
     type Err = InputValueError<String>;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut result = [0u8; BASE58_DIGEST_LENGTH];
-        result.copy_from_slice(
-            &Base58::decode(s).map_err(|r| InputValueError::custom(format!("{r}")))?,
-        );
+        let buffer = Base58::decode(s).map_err(|r| InputValueError::custom(format!("{r}")))?;
+        let result: [u8; BASE58_DIGEST_LENGTH] = buffer.try_into().map_err(|_| {
+            InputValueError::custom(format!("Expected a digest of {BASE58_DIGEST_LENGTH} bytes"))
+        })?;
         Ok(Digest(result))
     }
 }
@@ -91,5 +91,7 @@ mod tests {
             Digest(digest)
         );
         assert!(Digest::from_str("ILoveBase58").is_err());
+        // Valid Base58, but too short to be a digest.
+        assert!(Digest::from_str("1111").is_err());
     }
 }
@@ -9,9 +9,12 @@ use super::stake_subsidy::StakeSubsidy;
 use super::storage_fund::StorageFund;
 use super::system_parameters::SystemParameters;
 use super::validator_set::ValidatorSet;
+use super::validator_set_changes::ValidatorSetChanges;
+use crate::context_data::context_ext::DataProviderContextExt;
 use async_graphql::*;
 
 #[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+#[graphql(complex)]
 pub(crate) struct Epoch {
     pub epoch_id: u64,
     pub system_state_version: Option<BigInt>,
@@ -25,3 +28,28 @@ pub(crate) struct Epoch {
     pub start_timestamp: Option<DateTime>,
     // pub end_timestamp: Option<DateTime>, //TODO decide if we want this data exposed or not
 }
+
+#[ComplexObject]
+impl Epoch {
+    /// Validators that joined or left the active validator set, and changes to the stake and
+    /// commission rate of the others, relative to the previous epoch. Null for the first epoch, or
+    /// if the validators of either epoch are not available.
+    async fn validator_set_changes(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Option<ValidatorSetChanges>> {
+        let Some(previous_epoch_id) = self.epoch_id.checked_sub(1) else {
+            return Ok(None);
+        };
+
+        let data_provider = ctx.data_provider();
+        let Some(previous) = data_provider.fetch_epoch_validators(previous_epoch_id).await? else {
+            return Ok(None);
+        };
+        let Some(current) = data_provider.fetch_epoch_validators(self.epoch_id).await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(ValidatorSetChanges::new(previous_epoch_id, &previous, &current)?))
+    }
+}
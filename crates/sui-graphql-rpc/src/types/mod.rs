@@ -15,18 +15,23 @@ pub(crate) mod end_of_epoch_data;
 pub(crate) mod epoch;
 pub(crate) mod gas;
 pub(crate) mod move_value;
+pub(crate) mod mutation;
 pub(crate) mod name_service;
 pub(crate) mod object;
 pub(crate) mod owner;
+pub(crate) mod package_publish;
 pub(crate) mod protocol_config;
 pub(crate) mod query;
 pub(crate) mod safe_mode;
+pub(crate) mod signature_verification;
 pub(crate) mod stake;
 pub(crate) mod stake_subsidy;
 pub(crate) mod storage_fund;
 pub(crate) mod sui_address;
 pub(crate) mod system_parameters;
 pub(crate) mod transaction_block;
+pub(crate) mod transaction_block_kind;
 pub(crate) mod validator;
 pub(crate) mod validator_credentials;
 pub(crate) mod validator_set;
+pub(crate) mod validator_set_changes;
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use fastcrypto::traits::ToFromBytes;
+use shared_crypto::intent::Intent;
+use sui_sdk::types::signature::GenericSignature;
+use sui_sdk::types::transaction::{Transaction, TransactionData};
+
+use super::{
+    base64::Base64,
+    package_publish::{PublishPackageInput, PublishedPackage, UpgradePackageInput},
+};
+use crate::{context_data::context_ext::DataProviderContextExt, error::Error};
+
+pub(crate) struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Publish a new package, built from compiled modules, and return its ID.
+    ///
+    /// `signatures` are a list of `flag || signature || pubkey` bytes, Base64-encoded, over the
+    /// transaction returned by `Query.publishPackageTransaction` for the same `input`.
+    async fn publish_package(
+        &self,
+        ctx: &Context<'_>,
+        input: PublishPackageInput,
+        signatures: Vec<Base64>,
+    ) -> Result<PublishedPackage> {
+        execute(ctx, input.transaction_data()?, signatures).await
+    }
+
+    /// Upgrade an existing package to a new version, built from compiled modules, and return the
+    /// ID of the new version.
+    ///
+    /// `signatures` are a list of `flag || signature || pubkey` bytes, Base64-encoded, over the
+    /// transaction returned by `Query.upgradePackageTransaction` for the same `input`.
+    async fn upgrade_package(
+        &self,
+        ctx: &Context<'_>,
+        input: UpgradePackageInput,
+        signatures: Vec<Base64>,
+    ) -> Result<PublishedPackage> {
+        execute(ctx, input.transaction_data()?, signatures).await
+    }
+}
+
+async fn execute(
+    ctx: &Context<'_>,
+    transaction_data: TransactionData,
+    signatures: Vec<Base64>,
+) -> Result<PublishedPackage> {
+    let signatures = signatures
+        .iter()
+        .map(|signature| GenericSignature::from_bytes(&signature.0))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| {
+            Error::InvalidPackageTransaction(format!("Invalid signature: {e}")).extend()
+        })?;
+    let transaction =
+        Transaction::from_generic_sig_data(transaction_data, Intent::sui_transaction(), signatures);
+    let response = ctx
+        .data_provider()
+        .execute_transaction_block(transaction)
+        .await?;
+    PublishedPackage::from_response(&response)
+}
@@ -0,0 +1,225 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use async_graphql::*;
+use sui_json_rpc_types::{
+    ObjectChange, SuiExecutionStatus, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_sdk::types::base_types::{
+    ObjectDigest as NativeObjectDigest, ObjectID as NativeObjectID, ObjectRef,
+    SequenceNumber as NativeSequenceNumber, SuiAddress as NativeSuiAddress,
+};
+use sui_sdk::types::move_package::{MovePackage, UpgradePolicy};
+use sui_sdk::types::object::Owner as NativeOwner;
+use sui_sdk::types::transaction::TransactionData;
+
+use super::{base64::Base64, digest::Digest, sui_address::SuiAddress};
+use crate::error::Error;
+
+/// A reference to a specific version of an object.
+#[derive(InputObject)]
+pub(crate) struct ObjectRefInput {
+    object_id: SuiAddress,
+    version: u64,
+    /// Base58-encoded digest of the object at this version.
+    digest: String,
+}
+
+/// Everything needed to build a transaction that publishes a new package. The transaction is
+/// fully determined by these inputs, so the bytes the sender signs are the same bytes that
+/// `Mutation.publishPackage` submits.
+#[derive(InputObject)]
+pub(crate) struct PublishPackageInput {
+    sender: SuiAddress,
+    /// Compiled bytecode of each module in the package, Base64-encoded.
+    modules: Vec<Base64>,
+    /// IDs of the packages the new package depends on, including transitive dependencies.
+    dependencies: Vec<SuiAddress>,
+    gas_payment: ObjectRefInput,
+    gas_price: u64,
+    gas_budget: u64,
+}
+
+/// Everything needed to build a transaction that upgrades an existing package, with a
+/// compatible upgrade policy. The upgrade capability must be owned by the sender.
+#[derive(InputObject)]
+pub(crate) struct UpgradePackageInput {
+    sender: SuiAddress,
+    /// ID of the package being upgraded.
+    package: SuiAddress,
+    upgrade_capability: ObjectRefInput,
+    /// Compiled bytecode of each module in the new version of the package, Base64-encoded.
+    modules: Vec<Base64>,
+    /// IDs of the packages the new version depends on, including transitive dependencies.
+    dependencies: Vec<SuiAddress>,
+    gas_payment: ObjectRefInput,
+    gas_price: u64,
+    gas_budget: u64,
+}
+
+/// A package created by publishing or upgrading.
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct PublishedPackage {
+    /// ID of the new package.
+    package_id: SuiAddress,
+    /// Digest of the transaction that created the package.
+    transaction_digest: String,
+}
+
+impl ObjectRefInput {
+    fn to_object_ref(&self) -> Result<ObjectRef> {
+        let digest = Digest::from_str(&self.digest).map_err(|_| {
+            Error::InvalidPackageTransaction(format!("Invalid object digest: {}", self.digest))
+                .extend()
+        })?;
+        Ok((
+            object_id(self.object_id),
+            NativeSequenceNumber::from_u64(self.version),
+            NativeObjectDigest::new(digest.into_array()),
+        ))
+    }
+}
+
+impl PublishPackageInput {
+    pub(crate) fn transaction_data(&self) -> Result<TransactionData> {
+        Ok(TransactionData::new_module(
+            NativeSuiAddress::from(self.sender),
+            self.gas_payment.to_object_ref()?,
+            modules(&self.modules),
+            self.dependencies.iter().copied().map(object_id).collect(),
+            self.gas_budget,
+            self.gas_price,
+        ))
+    }
+}
+
+impl UpgradePackageInput {
+    pub(crate) fn transaction_data(&self) -> Result<TransactionData> {
+        let modules = modules(&self.modules);
+        let dependencies: Vec<_> = self.dependencies.iter().copied().map(object_id).collect();
+        let digest = MovePackage::compute_digest_for_modules_and_deps(
+            &modules,
+            &dependencies,
+            /* hash_modules */ true,
+        );
+        let sender = NativeSuiAddress::from(self.sender);
+        TransactionData::new_upgrade(
+            sender,
+            self.gas_payment.to_object_ref()?,
+            object_id(self.package),
+            modules,
+            dependencies,
+            (
+                self.upgrade_capability.to_object_ref()?,
+                NativeOwner::AddressOwner(sender),
+            ),
+            UpgradePolicy::COMPATIBLE,
+            digest.to_vec(),
+            self.gas_budget,
+            self.gas_price,
+        )
+        .map_err(|e| Error::InvalidPackageTransaction(e.to_string()).extend())
+    }
+}
+
+impl PublishedPackage {
+    /// Finds the package created by an executed publish or upgrade transaction, failing if the
+    /// transaction did not succeed.
+    pub(crate) fn from_response(response: &SuiTransactionBlockResponse) -> Result<Self> {
+        let transaction_digest = response.digest.to_string();
+        if let Some(SuiExecutionStatus::Failure { error }) =
+            response.effects.as_ref().map(|effects| effects.status())
+        {
+            return Err(Error::InvalidPackageTransaction(format!(
+                "Transaction {transaction_digest} failed: {error}"
+            ))
+            .extend());
+        }
+
+        let package_id = response
+            .object_changes
+            .iter()
+            .flatten()
+            .find_map(|change| match change {
+                ObjectChange::Published { package_id, .. } => Some(*package_id),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                Error::Internal(format!(
+                    "Transaction {transaction_digest} did not publish a package"
+                ))
+                .extend()
+            })?;
+
+        Ok(Self {
+            package_id: SuiAddress::from_array(package_id.into_bytes()),
+            transaction_digest,
+        })
+    }
+}
+
+fn object_id(address: SuiAddress) -> NativeObjectID {
+    NativeObjectID::new(address.into_array())
+}
+
+fn modules(modules: &[Base64]) -> Vec<Vec<u8>> {
+    modules.iter().map(|module| module.0.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::transaction::{Command, TransactionDataAPI, TransactionKind};
+
+    fn object_ref_input(byte: u8) -> ObjectRefInput {
+        ObjectRefInput {
+            object_id: SuiAddress::from_array([byte; 32]),
+            version: 1,
+            digest: Digest::from_array([byte; 32]).to_string(),
+        }
+    }
+
+    fn publish_input() -> PublishPackageInput {
+        PublishPackageInput {
+            sender: SuiAddress::from_array([1; 32]),
+            modules: vec![Base64(vec![0xa1, 0x1c, 0xeb, 0x0b])],
+            dependencies: vec![SuiAddress::from_array([2; 32])],
+            gas_payment: object_ref_input(3),
+            gas_price: 1000,
+            gas_budget: 10_000_000,
+        }
+    }
+
+    #[test]
+    fn test_publish_transaction_data() {
+        let input = publish_input();
+        let tx_data = input.transaction_data().unwrap();
+        assert_eq!(tx_data.sender(), NativeSuiAddress::from(input.sender));
+        assert_eq!(tx_data.gas_price(), 1000);
+        assert_eq!(tx_data.gas_budget(), 10_000_000);
+
+        let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind() else {
+            panic!("Expected a programmable transaction");
+        };
+        let Command::Publish(modules, dependencies) = &pt.commands[0] else {
+            panic!("Expected a publish command");
+        };
+        assert_eq!(modules, &vec![vec![0xa1, 0x1c, 0xeb, 0x0b]]);
+        assert_eq!(dependencies, &vec![NativeObjectID::new([2; 32])]);
+
+        // Building the same input twice gives the same bytes to sign.
+        assert_eq!(
+            bcs::to_bytes(&tx_data).unwrap(),
+            bcs::to_bytes(&input.transaction_data().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_object_digest() {
+        let mut input = publish_input();
+        input.gas_payment.digest = "not a digest".to_string();
+        assert!(input.transaction_data().is_err());
+    }
+}
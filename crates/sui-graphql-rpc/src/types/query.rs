@@ -1,20 +1,38 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use async_graphql::{connection::Connection, *};
+use std::str::FromStr;
+
+use async_graphql::{
+    connection::Connection,
+    dataloader::{DataLoader, LruCache},
+    *,
+};
+
+use sui_sdk::types::transaction::TransactionData;
 
 use super::{
-    address::Address, checkpoint::Checkpoint, object::Object, owner::ObjectOwner,
-    protocol_config::ProtocolConfigs, sui_address::SuiAddress,
+    address::Address,
+    base64::Base64,
+    checkpoint::Checkpoint,
+    digest::Digest,
+    mutation::Mutation,
+    object::Object,
+    owner::ObjectOwner,
+    package_publish::{PublishPackageInput, UpgradePackageInput},
+    protocol_config::ProtocolConfigs,
+    signature_verification::{self, SignatureVerification},
+    sui_address::SuiAddress,
+    transaction_block::{TransactionBlock, TransactionBlockFilter},
 };
 use crate::{
-    config::ServiceConfig,
-    context_data::context_ext::DataProviderContextExt,
-    error::{code, graphql_error},
+    config::{Limits, ServiceConfig},
+    context_data::{context_ext::DataProviderContextExt, sui_sdk_data_provider::SuiClientLoader},
+    error::{code, graphql_error, Error},
 };
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, EmptyMutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
 
 #[allow(unreachable_code)]
 #[allow(unused_variables)]
@@ -55,6 +73,38 @@ impl Query {
         ctx.data_provider().fetch_obj(address, version).await
     }
 
+    /// Look up several objects at once. The result has an entry for each address, in the same
+    /// order, which is null if there is no object at that address.
+    async fn objects(
+        &self,
+        ctx: &Context<'_>,
+        addresses: Vec<SuiAddress>,
+    ) -> Result<Vec<Option<Object>>> {
+        check_multi_get_size(ctx, addresses.len())?;
+        ctx.data_provider().multi_fetch_obj(addresses).await
+    }
+
+    /// Look up several transaction blocks at once. The result has an entry for each digest, in
+    /// the same order, which is null if there is no transaction block with that digest.
+    async fn transaction_blocks_by_digests(
+        &self,
+        ctx: &Context<'_>,
+        digests: Vec<String>,
+    ) -> Result<Vec<Option<TransactionBlock>>> {
+        check_multi_get_size(ctx, digests.len())?;
+        let digests = digests
+            .iter()
+            .map(|d| {
+                Digest::from_str(d)
+                    .map_err(|_| Error::MultiGet(format!("Invalid digest: {d}")).extend())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let loader = ctx.data_unchecked::<DataLoader<SuiClientLoader, LruCache>>();
+        let txs = loader.load_many(digests.iter().copied()).await?;
+        Ok(digests.iter().map(|d| txs.get(d).cloned()).collect())
+    }
+
     async fn address(&self, address: SuiAddress) -> Option<Address> {
         Some(Address { address })
     }
@@ -72,6 +122,27 @@ impl Query {
             .await
     }
 
+    /// Transaction blocks, paginated forwards from the earliest. Filtering by kind separates
+    /// the transaction blocks created by the system from those submitted by users.
+    async fn transaction_block_connection(
+        &self,
+        ctx: &Context<'_>,
+        first: Option<u64>,
+        after: Option<String>,
+        last: Option<u64>,
+        before: Option<String>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<Connection<String, TransactionBlock>> {
+        let filter = filter
+            .map(|f| f.to_rpc_filter())
+            .transpose()
+            .map_err(|e| e.extend())?
+            .flatten();
+        ctx.data_provider()
+            .fetch_transaction_block_connection(first, after, last, before, filter)
+            .await
+    }
+
     async fn protocol_config(
         &self,
         ctx: &Context<'_>,
@@ -81,4 +152,56 @@ impl Query {
             .fetch_protocol_config(protocol_version)
             .await
     }
+
+    /// The BCS-encoded `TransactionData` that `Mutation.publishPackage` submits for `input`, for
+    /// the sender to sign.
+    async fn publish_package_transaction(&self, input: PublishPackageInput) -> Result<Base64> {
+        transaction_bytes(&input.transaction_data()?)
+    }
+
+    /// The BCS-encoded `TransactionData` that `Mutation.upgradePackage` submits for `input`, for
+    /// the sender to sign.
+    async fn upgrade_package_transaction(&self, input: UpgradePackageInput) -> Result<Base64> {
+        transaction_bytes(&input.transaction_data()?)
+    }
+
+    /// Verify that `signature` is a signature of `message` under the personal message intent, by
+    /// the owner of `address`, e.g. to check that a user controls an address when they sign in.
+    ///
+    /// `signature` is the `flag || signature || pubkey` bytes, Base64-encoded, of any scheme
+    /// supported by Sui, including multisig and zkLogin. zkLogin signatures are verified against
+    /// the JWKs and epoch of the network, as validators would verify them.
+    async fn verify_personal_message_signature(
+        &self,
+        ctx: &Context<'_>,
+        message: Base64,
+        signature: Base64,
+        address: SuiAddress,
+    ) -> Result<SignatureVerification> {
+        signature_verification::verify_personal_message_signature(ctx, message, signature, address)
+            .await
+    }
+}
+
+/// Multi-gets are limited in size by the service config, rather than by the complexity of the
+/// query, which does not grow with the number of keys.
+fn check_multi_get_size(ctx: &Context<'_>, size: usize) -> Result<()> {
+    let max = match ctx.data_opt::<ServiceConfig>() {
+        Some(config) => config.limits.max_multi_get_size,
+        None => Limits::default().max_multi_get_size,
+    };
+
+    if size > max as usize {
+        return Err(Error::MultiGet(format!(
+            "Requested {size} keys, but at most {max} can be fetched at once"
+        ))
+        .extend());
+    }
+    Ok(())
+}
+
+fn transaction_bytes(transaction_data: &TransactionData) -> Result<Base64> {
+    let bytes = bcs::to_bytes(transaction_data)
+        .map_err(|e| Error::Internal(format!("Failed to serialize transaction: {e}")).extend())?;
+    Ok(Base64(bytes))
 }
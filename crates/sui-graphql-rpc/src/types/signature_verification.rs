@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use fastcrypto::traits::ToFromBytes;
+use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
+use shared_crypto::intent::{Intent, IntentMessage, IntentScope, PersonalMessage};
+use sui_protocol_config::{Chain, ProtocolConfig, ProtocolVersion};
+use sui_sdk::types::base_types::SuiAddress as NativeSuiAddress;
+use sui_sdk::types::committee::EpochId;
+use sui_sdk::types::digests::{get_mainnet_chain_identifier, get_testnet_chain_identifier};
+use sui_sdk::types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
+use sui_sdk::types::zk_login_util::ZkLoginProviderRegistry;
+
+use super::{base64::Base64, sui_address::SuiAddress};
+use crate::{context_data::context_ext::DataProviderContextExt, error::Error};
+
+/// The outcome of verifying a signature.
+#[derive(SimpleObject, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct SignatureVerification {
+    /// Whether the signature is valid, and was produced by the owner of the address.
+    pub success: bool,
+    /// Why the signature is invalid, null if it is valid.
+    pub error: Option<String>,
+}
+
+/// Verify that `signature` is a signature of `message` under the personal message intent, by
+/// the owner of `address`. The signature is the `flag || signature || pubkey` bytes of any
+/// signature scheme supported by Sui, including multisig and zkLogin.
+pub(crate) async fn verify_personal_message_signature(
+    ctx: &Context<'_>,
+    message: Base64,
+    signature: Base64,
+    address: SuiAddress,
+) -> Result<SignatureVerification> {
+    let signature = GenericSignature::from_bytes(&signature.0)
+        .map_err(|e| Error::InvalidSignature(e.to_string()).extend())?;
+    let message = IntentMessage::new(
+        Intent::sui_app(IntentScope::PersonalMessage),
+        PersonalMessage { message: message.0 },
+    );
+
+    // Only zkLogin signatures, possibly as members of a multisig, depend on the state of the
+    // network: the JWKs of the OIDC providers, and the current epoch, to check that the
+    // ephemeral key has not expired.
+    let (epoch, verify_params) = if signature.is_zklogin() || signature.is_upgraded_multisig() {
+        let (epoch, verify_params) = zklogin_verify_params(ctx).await?;
+        (Some(epoch), verify_params)
+    } else {
+        (None, VerifyParams::default())
+    };
+
+    let author = NativeSuiAddress::from(address);
+    let result = signature.verify_authenticator(&message, author, epoch, &verify_params);
+    Ok(SignatureVerification {
+        success: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    })
+}
+
+/// The current epoch, and the parameters to verify zkLogin signatures with, as validators would
+/// verify them in that epoch.
+async fn zklogin_verify_params(ctx: &Context<'_>) -> Result<(EpochId, VerifyParams)> {
+    let system_state = ctx.data_provider().get_latest_sui_system_state().await?;
+
+    let chain_id = ctx.data_provider().fetch_chain_id().await?;
+    let chain = [get_mainnet_chain_identifier(), get_testnet_chain_identifier()]
+        .into_iter()
+        .find(|id| id.to_string() == chain_id)
+        .map_or(Chain::Unknown, |id| id.chain());
+
+    let protocol_version = ProtocolVersion::new(system_state.protocol_version);
+    let protocol_config = ProtocolConfig::get_for_version_if_supported(protocol_version, chain)
+        .ok_or_else(|| {
+            Error::Internal(format!(
+                "Protocol version {} is not supported by this service",
+                system_state.protocol_version
+            ))
+            .extend()
+        })?;
+    let supported_providers = ZkLoginProviderRegistry::from_protocol_config(&protocol_config)
+        .map_err(|e| Error::Internal(e.to_string()).extend())?;
+
+    let verify_params = VerifyParams {
+        oidc_provider_jwks: ctx
+            .data_provider()
+            .fetch_active_jwks()
+            .await?
+            .into_iter()
+            .map(|active| (active.jwk_id, active.jwk))
+            .collect(),
+        supported_providers,
+        zk_login_env: match chain {
+            Chain::Mainnet => ZkLoginEnv::Prod,
+            _ => ZkLoginEnv::Test,
+        },
+    };
+    Ok((system_state.epoch, verify_params))
+}
@@ -1,8 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::context_data::{
-    context_ext::DataProviderContextExt, sui_sdk_data_provider::convert_to_epoch,
+use crate::{
+    context_data::{context_ext::DataProviderContextExt, sui_sdk_data_provider::convert_to_epoch},
+    error::Error,
 };
 
 use super::{
@@ -12,12 +13,15 @@ use super::{
     epoch::Epoch,
     gas::{GasEffects, GasInput},
     sui_address::SuiAddress,
+    transaction_block_kind::TransactionBlockKind,
 };
 use async_graphql::*;
 use sui_json_rpc_types::{
     SuiExecutionStatus, SuiTransactionBlockDataAPI, SuiTransactionBlockEffects,
-    SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+    SuiTransactionBlockEffectsAPI, SuiTransactionBlockKind, SuiTransactionBlockResponse,
+    TransactionFilter,
 };
+use sui_sdk::types::transaction::{SenderSignedData, TransactionDataAPI};
 
 #[derive(SimpleObject, Clone, Eq, PartialEq)]
 #[graphql(complex)]
@@ -28,6 +32,10 @@ pub(crate) struct TransactionBlock {
     pub sender: Option<Address>,
     pub bcs: Option<Base64>,
     pub gas_input: Option<GasInput>,
+    pub kind: Option<TransactionBlockKind>,
+    /// Whether the transaction block was created by the system (e.g. to start a consensus commit
+    /// or change epochs), rather than signed and submitted by a user.
+    pub is_system: Option<bool>,
 }
 
 impl From<SuiTransactionBlockResponse> for TransactionBlock {
@@ -38,12 +46,27 @@ impl From<SuiTransactionBlockResponse> for TransactionBlock {
         });
         let gas_input = transaction.map(|tx| GasInput::from(tx.data.gas_data()));
 
+        // The raw transaction carries the BCS bytes of pure inputs, which are not available from
+        // its JSON-RPC representation.
+        let native = bcs::from_bytes::<SenderSignedData>(&tx_block.raw_transaction).ok();
+        let native_kind = native.as_ref().map(|tx| tx.transaction_data().kind());
+        let kind = transaction
+            .and_then(|tx| TransactionBlockKind::from_sui(tx.data.transaction(), native_kind));
+        let is_system = transaction.map(|tx| {
+            !matches!(
+                tx.data.transaction(),
+                SuiTransactionBlockKind::ProgrammableTransaction(_)
+            )
+        });
+
         Self {
             digest: Digest::from_array(tx_block.digest.into_inner()),
             effects: tx_block.effects.as_ref().map(TransactionBlockEffects::from),
             sender,
             bcs: Some(Base64::from(&tx_block.raw_transaction)),
             gas_input,
+            kind,
+            is_system,
         }
     }
 }
@@ -127,7 +150,13 @@ impl TransactionBlockEffects {
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
 pub(crate) enum TransactionBlockKindInput {
     ProgrammableTx,
+    /// Any transaction block created by the system, rather than by a user.
     SystemTx,
+    ConsensusCommitPrologueTx,
+    ChangeEpochTx,
+    GenesisTx,
+    AuthenticatorStateUpdateTx,
+    EndOfEpochTx,
 }
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
@@ -153,3 +182,119 @@ pub(crate) struct TransactionBlockFilter {
     input_object: Option<SuiAddress>,
     changed_object: Option<SuiAddress>,
 }
+
+impl TransactionBlockKindInput {
+    /// Names of the transaction kinds this input matches, as understood by the data source.
+    fn kind_names(&self) -> &'static [&'static str] {
+        use TransactionBlockKindInput as K;
+        match self {
+            K::ProgrammableTx => &["ProgrammableTransaction"],
+            K::SystemTx => &[
+                "ConsensusCommitPrologue",
+                "ChangeEpoch",
+                "Genesis",
+                "AuthenticatorStateUpdate",
+                "EndOfEpochTransaction",
+            ],
+            K::ConsensusCommitPrologueTx => &["ConsensusCommitPrologue"],
+            K::ChangeEpochTx => &["ChangeEpoch"],
+            K::GenesisTx => &["Genesis"],
+            K::AuthenticatorStateUpdateTx => &["AuthenticatorStateUpdate"],
+            K::EndOfEpochTx => &["EndOfEpochTransaction"],
+        }
+    }
+}
+
+impl TransactionBlockFilter {
+    /// The filter to query the data source with. Only filtering by kind is supported so far, so
+    /// filters that set any other field are rejected rather than silently ignored.
+    pub(crate) fn to_rpc_filter(&self) -> Result<Option<TransactionFilter>, Error> {
+        let Self {
+            package,
+            module,
+            function,
+            kind,
+            checkpoint,
+            sign_address,
+            sent_address,
+            recv_address,
+            paid_address,
+            input_object,
+            changed_object,
+        } = self;
+
+        if package.is_some()
+            || module.is_some()
+            || function.is_some()
+            || checkpoint.is_some()
+            || sign_address.is_some()
+            || sent_address.is_some()
+            || recv_address.is_some()
+            || paid_address.is_some()
+            || input_object.is_some()
+            || changed_object.is_some()
+        {
+            return Err(Error::UnsupportedFilter(
+                "transaction blocks can only be filtered by kind".to_string(),
+            ));
+        }
+
+        Ok(kind.map(|kind| {
+            TransactionFilter::TransactionKindIn(
+                kind.kind_names().iter().map(|k| k.to_string()).collect(),
+            )
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind_filter(kind: Option<TransactionBlockKindInput>) -> TransactionBlockFilter {
+        TransactionBlockFilter {
+            package: None,
+            module: None,
+            function: None,
+            kind,
+            checkpoint: None,
+            sign_address: None,
+            sent_address: None,
+            recv_address: None,
+            paid_address: None,
+            input_object: None,
+            changed_object: None,
+        }
+    }
+
+    #[test]
+    fn kind_filter_to_rpc() {
+        assert!(kind_filter(None).to_rpc_filter().unwrap().is_none());
+
+        let Some(TransactionFilter::TransactionKindIn(kinds)) =
+            kind_filter(Some(TransactionBlockKindInput::ConsensusCommitPrologueTx))
+                .to_rpc_filter()
+                .unwrap()
+        else {
+            panic!("Expected a transaction kind filter");
+        };
+        assert_eq!(kinds, vec!["ConsensusCommitPrologue".to_string()]);
+    }
+
+    #[test]
+    fn system_kinds_exclude_programmable() {
+        let system = TransactionBlockKindInput::SystemTx.kind_names();
+        let programmable = TransactionBlockKindInput::ProgrammableTx.kind_names();
+        assert!(!system.iter().any(|k| programmable.contains(k)));
+    }
+
+    #[test]
+    fn unsupported_filter() {
+        let mut filter = kind_filter(Some(TransactionBlockKindInput::SystemTx));
+        filter.checkpoint = Some(1);
+        assert!(matches!(
+            filter.to_rpc_filter(),
+            Err(Error::UnsupportedFilter(_))
+        ));
+    }
+}
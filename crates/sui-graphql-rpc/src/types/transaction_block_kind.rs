@@ -0,0 +1,417 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_graphql::*;
+use sui_json_rpc_types::{
+    SuiArgument, SuiCallArg, SuiCommand, SuiObjectArg, SuiProgrammableTransactionBlock,
+    SuiTransactionBlockKind,
+};
+use sui_sdk::types::{
+    base_types::ObjectID,
+    transaction::{
+        CallArg, ProgrammableTransaction as NativeProgrammableTransaction, TransactionKind,
+    },
+};
+
+use super::{base64::Base64, big_int::BigInt, date_time::DateTime, sui_address::SuiAddress};
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum TransactionBlockKind {
+    ConsensusCommitPrologue(ConsensusCommitPrologueTransaction),
+    Genesis(GenesisTransaction),
+    ChangeEpoch(ChangeEpochTransaction),
+    Programmable(ProgrammableTransactionBlock),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ConsensusCommitPrologueTransaction {
+    pub epoch_id: Option<u64>,
+    pub round: Option<u64>,
+    pub timestamp: Option<DateTime>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct GenesisTransaction {
+    pub objects: Option<Vec<SuiAddress>>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ChangeEpochTransaction {
+    pub epoch_id: Option<u64>,
+    pub timestamp: Option<DateTime>,
+    pub storage_charge: Option<BigInt>,
+    pub computation_charge: Option<BigInt>,
+    pub storage_rebate: Option<BigInt>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct ProgrammableTransactionBlock {
+    pub inputs: Option<Vec<TransactionInput>>,
+    pub transactions: Option<Vec<ProgrammableTransaction>>,
+}
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum TransactionInput {
+    OwnedOrImmutable(OwnedOrImmutable),
+    SharedInput(SharedInput),
+    Receiving(Receiving),
+    Pure(Pure),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct OwnedOrImmutable {
+    pub address: SuiAddress,
+    pub version: u64,
+    pub digest: String,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct SharedInput {
+    pub address: SuiAddress,
+    pub initial_shared_version: u64,
+    pub mutable: bool,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct Receiving {
+    pub address: SuiAddress,
+    pub version: u64,
+    pub digest: String,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct Pure {
+    /// BCS-encoded value, as it appears in the transaction.
+    pub bcs: Option<Base64>,
+    /// The value, rendered as JSON, if its type could be inferred from the way it is used.
+    pub json: Option<String>,
+    #[graphql(name = "type")]
+    pub type_: Option<String>,
+}
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum TransactionArgument {
+    GasCoin(GasCoin),
+    Input(Input),
+    Result(TxResult),
+}
+
+/// The gas coin, after all gas payment coins have been merged into it.
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct GasCoin {
+    #[graphql(name = "_")]
+    pub dummy: Option<bool>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct Input {
+    pub ix: u16,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct TxResult {
+    pub cmd: u16,
+    /// Index into the command's results, for commands that return multiple values.
+    pub ix: Option<u16>,
+}
+
+#[derive(Union, Clone, Eq, PartialEq)]
+pub(crate) enum ProgrammableTransaction {
+    MoveCall(MoveCallTransaction),
+    TransferObjects(TransferObjectsTransaction),
+    SplitCoins(SplitCoinsTransaction),
+    MergeCoins(MergeCoinsTransaction),
+    Publish(PublishTransaction),
+    Upgrade(UpgradeTransaction),
+    MakeMoveVec(MakeMoveVecTransaction),
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct MoveCallTransaction {
+    pub package: SuiAddress,
+    pub module: String,
+    #[graphql(name = "function")]
+    pub function_name: String,
+    pub type_arguments: Vec<String>,
+    pub arguments: Vec<TransactionArgument>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct TransferObjectsTransaction {
+    pub objects: Vec<TransactionArgument>,
+    pub address: TransactionArgument,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct SplitCoinsTransaction {
+    pub coin: TransactionArgument,
+    pub amounts: Vec<TransactionArgument>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct MergeCoinsTransaction {
+    pub coin: TransactionArgument,
+    pub coins: Vec<TransactionArgument>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct PublishTransaction {
+    pub dependencies: Vec<SuiAddress>,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct UpgradeTransaction {
+    pub dependencies: Vec<SuiAddress>,
+    pub current_package: SuiAddress,
+    pub upgrade_ticket: TransactionArgument,
+}
+
+#[derive(SimpleObject, Clone, Eq, PartialEq)]
+pub(crate) struct MakeMoveVecTransaction {
+    #[graphql(name = "type")]
+    pub type_: Option<String>,
+    pub elements: Vec<TransactionArgument>,
+}
+
+impl TransactionBlockKind {
+    /// Decompose a transaction's kind. `native` is the transaction's own kind (decoded from its
+    /// BCS), if available, and is used to recover the raw bytes of pure inputs, which the JSON-RPC
+    /// representation only offers as JSON.
+    ///
+    /// Returns `None` for kinds that are not exposed yet (authenticator state updates and end of
+    /// epoch transactions).
+    pub(crate) fn from_sui(
+        kind: &SuiTransactionBlockKind,
+        native: Option<&TransactionKind>,
+    ) -> Option<Self> {
+        use SuiTransactionBlockKind as K;
+        Some(match kind {
+            K::ConsensusCommitPrologue(p) => {
+                Self::ConsensusCommitPrologue(ConsensusCommitPrologueTransaction {
+                    epoch_id: Some(p.epoch),
+                    round: Some(p.round),
+                    timestamp: DateTime::from_ms(p.commit_timestamp_ms as i64),
+                })
+            }
+
+            K::Genesis(g) => Self::Genesis(GenesisTransaction {
+                objects: Some(g.objects.iter().map(address).collect()),
+            }),
+
+            K::ChangeEpoch(e) => Self::ChangeEpoch(ChangeEpochTransaction {
+                epoch_id: Some(e.epoch),
+                timestamp: DateTime::from_ms(e.epoch_start_timestamp_ms as i64),
+                storage_charge: Some(BigInt::from(e.storage_charge)),
+                computation_charge: Some(BigInt::from(e.computation_charge)),
+                storage_rebate: Some(BigInt::from(e.storage_rebate)),
+            }),
+
+            K::ProgrammableTransaction(pt) => {
+                let native = match native {
+                    Some(TransactionKind::ProgrammableTransaction(pt)) => Some(pt),
+                    _ => None,
+                };
+                Self::Programmable(ProgrammableTransactionBlock::from_sui(pt, native))
+            }
+
+            K::AuthenticatorStateUpdate(_) | K::EndOfEpochTransaction(_) => return None,
+        })
+    }
+}
+
+impl ProgrammableTransactionBlock {
+    fn from_sui(
+        pt: &SuiProgrammableTransactionBlock,
+        native: Option<&NativeProgrammableTransaction>,
+    ) -> Self {
+        let inputs = pt
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(ix, input)| {
+                let bcs = match native.and_then(|pt| pt.inputs.get(ix)) {
+                    Some(CallArg::Pure(bytes)) => Some(Base64::from(bytes)),
+                    _ => None,
+                };
+                TransactionInput::from_sui(input, bcs)
+            })
+            .collect();
+
+        Self {
+            inputs: Some(inputs),
+            transactions: Some(
+                pt.commands
+                    .iter()
+                    .map(ProgrammableTransaction::from)
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl TransactionInput {
+    fn from_sui(input: &SuiCallArg, bcs: Option<Base64>) -> Self {
+        match input {
+            SuiCallArg::Pure(p) => Self::Pure(Pure {
+                bcs,
+                json: Some(p.value().to_json_value().to_string()),
+                type_: p.value_type().map(|t| t.to_string()),
+            }),
+
+            SuiCallArg::Object(SuiObjectArg::ImmOrOwnedObject {
+                object_id,
+                version,
+                digest,
+            }) => Self::OwnedOrImmutable(OwnedOrImmutable {
+                address: address(object_id),
+                version: version.value(),
+                digest: digest.to_string(),
+            }),
+
+            SuiCallArg::Object(SuiObjectArg::SharedObject {
+                object_id,
+                initial_shared_version,
+                mutable,
+            }) => Self::SharedInput(SharedInput {
+                address: address(object_id),
+                initial_shared_version: initial_shared_version.value(),
+                mutable: *mutable,
+            }),
+
+            SuiCallArg::Object(SuiObjectArg::Receiving {
+                object_id,
+                version,
+                digest,
+            }) => Self::Receiving(Receiving {
+                address: address(object_id),
+                version: version.value(),
+                digest: digest.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<&SuiCommand> for ProgrammableTransaction {
+    fn from(command: &SuiCommand) -> Self {
+        use SuiCommand as C;
+        match command {
+            C::MoveCall(call) => Self::MoveCall(MoveCallTransaction {
+                package: address(&call.package),
+                module: call.module.clone(),
+                function_name: call.function.clone(),
+                type_arguments: call.type_arguments.clone(),
+                arguments: arguments(&call.arguments),
+            }),
+
+            C::TransferObjects(objects, addr) => {
+                Self::TransferObjects(TransferObjectsTransaction {
+                    objects: arguments(objects),
+                    address: TransactionArgument::from(*addr),
+                })
+            }
+
+            C::SplitCoins(coin, amounts) => Self::SplitCoins(SplitCoinsTransaction {
+                coin: TransactionArgument::from(*coin),
+                amounts: arguments(amounts),
+            }),
+
+            C::MergeCoins(coin, coins) => Self::MergeCoins(MergeCoinsTransaction {
+                coin: TransactionArgument::from(*coin),
+                coins: arguments(coins),
+            }),
+
+            C::Publish(deps) => Self::Publish(PublishTransaction {
+                dependencies: deps.iter().map(address).collect(),
+            }),
+
+            C::Upgrade(deps, current, ticket) => Self::Upgrade(UpgradeTransaction {
+                dependencies: deps.iter().map(address).collect(),
+                current_package: address(current),
+                upgrade_ticket: TransactionArgument::from(*ticket),
+            }),
+
+            C::MakeMoveVec(type_, elements) => Self::MakeMoveVec(MakeMoveVecTransaction {
+                type_: type_.clone(),
+                elements: arguments(elements),
+            }),
+        }
+    }
+}
+
+impl From<SuiArgument> for TransactionArgument {
+    fn from(argument: SuiArgument) -> Self {
+        match argument {
+            SuiArgument::GasCoin => Self::GasCoin(GasCoin { dummy: None }),
+            SuiArgument::Input(ix) => Self::Input(Input { ix }),
+            SuiArgument::Result(cmd) => Self::Result(TxResult { cmd, ix: None }),
+            SuiArgument::NestedResult(cmd, ix) => Self::Result(TxResult { cmd, ix: Some(ix) }),
+        }
+    }
+}
+
+fn arguments(args: &[SuiArgument]) -> Vec<TransactionArgument> {
+    args.iter()
+        .copied()
+        .map(TransactionArgument::from)
+        .collect()
+}
+
+fn address(id: &ObjectID) -> SuiAddress {
+    SuiAddress::from_array(id.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_json_rpc_types::SuiProgrammableMoveCall;
+
+    use super::*;
+
+    #[test]
+    fn move_call_arguments() {
+        let package = ObjectID::random();
+        let command = SuiCommand::MoveCall(Box::new(SuiProgrammableMoveCall {
+            package,
+            module: "coin".to_string(),
+            function: "join".to_string(),
+            type_arguments: vec!["0x2::sui::SUI".to_string()],
+            arguments: vec![
+                SuiArgument::GasCoin,
+                SuiArgument::Input(1),
+                SuiArgument::Result(2),
+                SuiArgument::NestedResult(3, 4),
+            ],
+        }));
+
+        let expect = ProgrammableTransaction::MoveCall(MoveCallTransaction {
+            package: address(&package),
+            module: "coin".to_string(),
+            function_name: "join".to_string(),
+            type_arguments: vec!["0x2::sui::SUI".to_string()],
+            arguments: vec![
+                TransactionArgument::GasCoin(GasCoin { dummy: None }),
+                TransactionArgument::Input(Input { ix: 1 }),
+                TransactionArgument::Result(TxResult { cmd: 2, ix: None }),
+                TransactionArgument::Result(TxResult {
+                    cmd: 3,
+                    ix: Some(4),
+                }),
+            ],
+        });
+
+        assert!(ProgrammableTransaction::from(&command) == expect);
+    }
+
+    #[test]
+    fn upgrade_ticket() {
+        let (dep, current) = (ObjectID::random(), ObjectID::random());
+        let command = SuiCommand::Upgrade(vec![dep], current, SuiArgument::Result(0));
+
+        let expect = ProgrammableTransaction::Upgrade(UpgradeTransaction {
+            dependencies: vec![address(&dep)],
+            current_package: address(&current),
+            upgrade_ticket: TransactionArgument::Result(TxResult { cmd: 0, ix: None }),
+        });
+
+        assert!(ProgrammableTransaction::from(&command) == expect);
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::address::Address;
+use super::big_int::BigInt;
+use super::sui_address::SuiAddress;
+use super::validator::Validator;
+use crate::context_data::sui_sdk_data_provider::convert_to_validators;
+use async_graphql::*;
+use sui_sdk::types::sui_system_state::sui_system_state_summary::SuiValidatorSummary;
+
+/// How the active validators of an epoch differ from those of the previous epoch.
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub(crate) struct ValidatorSetChanges {
+    /// The epoch the changes are relative to.
+    pub previous_epoch_id: u64,
+    /// Validators that are active in this epoch, but were not in the previous epoch.
+    pub joined: Vec<Validator>,
+    /// Validators that were active in the previous epoch, but are not in this epoch, with their
+    /// details as of the previous epoch.
+    pub left: Vec<Validator>,
+    /// Validators active in both epochs, whose stake changed.
+    pub stake_changes: Vec<ValidatorStakeChange>,
+    /// Validators active in both epochs, whose commission rate changed.
+    pub commission_changes: Vec<ValidatorCommissionChange>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub(crate) struct ValidatorStakeChange {
+    pub address: Address,
+    pub previous_stake: BigInt,
+    pub stake: BigInt,
+    /// The stake minus the previous stake, negative if the stake decreased.
+    pub delta: BigInt,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, SimpleObject)]
+pub(crate) struct ValidatorCommissionChange {
+    pub address: Address,
+    /// Commission rates are in basis points.
+    pub previous_commission_rate: u64,
+    pub commission_rate: u64,
+}
+
+impl ValidatorSetChanges {
+    /// Compares the active validators of two consecutive epochs. Validators are identified by
+    /// their address.
+    pub(crate) fn new(
+        previous_epoch_id: u64,
+        previous: &[SuiValidatorSummary],
+        current: &[SuiValidatorSummary],
+    ) -> Result<Self> {
+        let previous_by_address: BTreeMap<_, _> =
+            previous.iter().map(|v| (v.sui_address, v)).collect();
+        let current_addresses: BTreeSet<_> = current.iter().map(|v| v.sui_address).collect();
+
+        let mut joined = vec![];
+        let mut stake_changes = vec![];
+        let mut commission_changes = vec![];
+        for v in current {
+            let Some(prev) = previous_by_address.get(&v.sui_address) else {
+                joined.push(v.clone());
+                continue;
+            };
+            let address = Address::from(SuiAddress::from(v.sui_address));
+
+            if prev.staking_pool_sui_balance != v.staking_pool_sui_balance {
+                let delta =
+                    v.staking_pool_sui_balance as i128 - prev.staking_pool_sui_balance as i128;
+                stake_changes.push(ValidatorStakeChange {
+                    address,
+                    previous_stake: BigInt::from(prev.staking_pool_sui_balance),
+                    stake: BigInt::from(v.staking_pool_sui_balance),
+                    delta: BigInt::from(delta),
+                });
+            }
+
+            if prev.commission_rate != v.commission_rate {
+                commission_changes.push(ValidatorCommissionChange {
+                    address,
+                    previous_commission_rate: prev.commission_rate,
+                    commission_rate: v.commission_rate,
+                });
+            }
+        }
+
+        let left = previous
+            .iter()
+            .filter(|v| !current_addresses.contains(&v.sui_address))
+            .cloned()
+            .collect();
+
+        Ok(Self {
+            previous_epoch_id,
+            joined: convert_to_validators(joined)?,
+            left: convert_to_validators(left)?,
+            stake_changes,
+            commission_changes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_sdk::types::base_types::SuiAddress as NativeSuiAddress;
+
+    fn validator(address: u8, stake: u64, commission_rate: u64) -> SuiValidatorSummary {
+        SuiValidatorSummary {
+            sui_address: NativeSuiAddress::from_bytes([address; 32]).unwrap(),
+            staking_pool_activation_epoch: Some(0),
+            staking_pool_sui_balance: stake,
+            commission_rate,
+            ..Default::default()
+        }
+    }
+
+    fn address(address: u8) -> Address {
+        Address::from(SuiAddress::from_array([address; 32]))
+    }
+
+    #[test]
+    fn validator_set_changes() {
+        let previous = [validator(1, 100, 200), validator(2, 100, 200)];
+        let current = [validator(2, 80, 300), validator(3, 100, 200)];
+        let changes = ValidatorSetChanges::new(4, &previous, &current).unwrap();
+
+        assert_eq!(changes.previous_epoch_id, 4);
+        assert_eq!(changes.joined.len(), 1);
+        assert_eq!(changes.joined[0].address, address(3));
+        assert_eq!(changes.left.len(), 1);
+        assert_eq!(changes.left[0].address, address(1));
+        assert_eq!(
+            changes.stake_changes,
+            vec![ValidatorStakeChange {
+                address: address(2),
+                previous_stake: BigInt::from(100u64),
+                stake: BigInt::from(80u64),
+                delta: BigInt::from(-20i128),
+            }]
+        );
+        assert_eq!(
+            changes.commission_changes,
+            vec![ValidatorCommissionChange {
+                address: address(2),
+                previous_commission_rate: 200,
+                commission_rate: 300,
+            }]
+        );
+    }
+
+    #[test]
+    fn unchanged_validator_set() {
+        let validators = [validator(1, 100, 200), validator(2, 100, 200)];
+        let changes = ValidatorSetChanges::new(0, &validators, &validators).unwrap();
+        assert!(changes.joined.is_empty());
+        assert!(changes.left.is_empty());
+        assert!(changes.stake_changes.is_empty());
+        assert!(changes.commission_changes.is_empty());
+    }
+}
@@ -0,0 +1,7 @@
+-- Lowest sequence number of each pruned entity that is still available. Data below a watermark
+-- may have existed, but has been pruned and can no longer be served.
+CREATE TABLE watermarks
+(
+    entity              VARCHAR(255) PRIMARY KEY,
+    lowest_available    BIGINT       NOT NULL
+);
@@ -0,0 +1,5 @@
+-- StructTag of the object in Display format, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+-- Null for packages, and for objects indexed before this column was added.
+ALTER TABLE objects ADD COLUMN object_type text;
+
+CREATE INDEX objects_object_type ON objects (object_type, object_id) WHERE object_type IS NOT NULL;
@@ -0,0 +1,349 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+
+use axum::{
+    body::{Bytes, StreamBody},
+    extract::{Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use fastcrypto::encoding::{Base64, Encoding};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::parse_sui_struct_tag;
+use sui_types::sui_serde::to_sui_struct_tag_string;
+use tracing::{error, info};
+
+use crate::errors::IndexerError;
+use crate::indexer_reader::IndexerReader;
+use crate::models_v2::{events::StoredEvent, objects::StoredObject};
+
+// Bulk exports stream every matching row as CSV, for analytics consumers that would otherwise
+// page through the JSON-RPC APIs. Rows are read in chunks, each in its own transaction, and
+// every row carries a `cursor`: if an export is interrupted, passing the cursor of the last row
+// received resumes it from the next row.
+//
+// Example commands:
+//
+// Export all objects of a type:
+//
+//   $ curl -G 'http://127.0.0.1:9185/export/objects' \
+//       --data-urlencode 'type=0x2::coin::Coin<0x2::sui::SUI>'
+//
+// Export the events emitted by calls into package 0x42, in checkpoints 1000 to 1999, resuming
+// after the event at transaction 5678, event 1:
+//
+//   $ curl -G 'http://127.0.0.1:9185/export/events' -d package=0x42 \
+//       -d start_checkpoint=1000 -d end_checkpoint=2000 -d cursor=5678:1
+
+const EXPORT_OBJECTS_ROUTE: &str = "/export/objects";
+const EXPORT_EVENTS_ROUTE: &str = "/export/events";
+
+/// Number of rows read from the DB per query while exporting.
+const EXPORT_CHUNK_SIZE: usize = 1000;
+
+pub async fn run_admin_server(reader: IndexerReader, port: u16) {
+    let app = Router::new()
+        .route(EXPORT_OBJECTS_ROUTE, get(export_objects))
+        .route(EXPORT_EVENTS_ROUTE, get(export_events))
+        .with_state(reader);
+
+    let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
+    info!(address =% socket_address, "starting admin server");
+
+    axum::Server::bind(&socket_address)
+        .serve(app.into_make_service())
+        .await
+        .unwrap()
+}
+
+#[derive(Deserialize)]
+struct ExportObjects {
+    #[serde(rename = "type")]
+    type_: String,
+    cursor: Option<String>,
+}
+
+/// A row of `/export/objects`. The cursor is the ID of the object.
+#[derive(Serialize)]
+struct ObjectRow {
+    cursor: String,
+    object_id: String,
+    object_version: i64,
+    object_digest: String,
+    checkpoint_sequence_number: i64,
+    owner_type: i16,
+    owner_id: Option<String>,
+    object_type: Option<String>,
+    coin_balance: Option<i64>,
+    /// Base64 encoded BCS of the object.
+    serialized_object: String,
+}
+
+async fn export_objects(
+    State(reader): State<IndexerReader>,
+    Query(export): Query<ExportObjects>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    // Types are stored in a canonical form, which the requested type may not be in.
+    let object_type = parse_sui_struct_tag(&export.type_)
+        .ok()
+        .and_then(|tag| to_sui_struct_tag_string(&tag).ok())
+        .ok_or_else(|| bad_request(format!("Invalid object type: {}", export.type_)))?;
+    let after = export
+        .cursor
+        .map(|cursor| {
+            ObjectID::from_str(&cursor)
+                .map_err(|_| bad_request(format!("Invalid cursor: {cursor}")))
+        })
+        .transpose()?;
+
+    Ok(csv_response(export_chunks(
+        reader,
+        after,
+        move |reader, after| {
+            reader.get_objects_of_type(object_type.clone(), after, EXPORT_CHUNK_SIZE)
+        },
+        |object: &StoredObject| Ok(ObjectID::from_bytes(&object.object_id)?),
+        ObjectRow::try_from,
+    )))
+}
+
+#[derive(Deserialize)]
+struct ExportEvents {
+    package: String,
+    start_checkpoint: CheckpointSequenceNumber,
+    /// Exclusive.
+    end_checkpoint: CheckpointSequenceNumber,
+    cursor: Option<String>,
+}
+
+/// A row of `/export/events`. The cursor is `<tx_sequence_number>:<event_sequence_number>`.
+#[derive(Serialize)]
+struct EventRow {
+    cursor: String,
+    tx_sequence_number: i64,
+    event_sequence_number: i64,
+    transaction_digest: String,
+    checkpoint_sequence_number: i64,
+    package: String,
+    module: String,
+    event_type: String,
+    timestamp_ms: i64,
+    /// Base64 encoded BCS of the event contents.
+    bcs: String,
+}
+
+async fn export_events(
+    State(reader): State<IndexerReader>,
+    Query(export): Query<ExportEvents>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let package = ObjectID::from_str(&export.package)
+        .map_err(|_| bad_request(format!("Invalid package: {}", export.package)))?;
+    let checkpoints = export.start_checkpoint..export.end_checkpoint;
+    let after = export
+        .cursor
+        .map(|cursor| {
+            parse_event_cursor(&cursor)
+                .ok_or_else(|| bad_request(format!("Invalid cursor: {cursor}")))
+        })
+        .transpose()?;
+
+    Ok(csv_response(export_chunks(
+        reader,
+        after,
+        move |reader, after| {
+            reader.get_package_events(package, checkpoints.clone(), after, EXPORT_CHUNK_SIZE)
+        },
+        |event: &StoredEvent| Ok((event.tx_sequence_number, event.event_sequence_number)),
+        EventRow::try_from,
+    )))
+}
+
+fn parse_event_cursor(cursor: &str) -> Option<(i64, i64)> {
+    let (tx_sequence_number, event_sequence_number) = cursor.split_once(':')?;
+    Some((
+        tx_sequence_number.parse().ok()?,
+        event_sequence_number.parse().ok()?,
+    ))
+}
+
+/// Streams the rows returned by `fetch`, one chunk at a time, as CSV. `fetch` is given the
+/// cursor of the last row of the previous chunk, and the export ends with the first chunk that
+/// is not full.
+fn export_chunks<C, S, R, F>(
+    reader: IndexerReader,
+    after: Option<C>,
+    fetch: F,
+    cursor: fn(&S) -> Result<C, IndexerError>,
+    to_row: fn(S) -> Result<R, IndexerError>,
+) -> impl Stream<Item = Result<Bytes, IndexerError>>
+where
+    C: Send + 'static,
+    S: Send + 'static,
+    R: Serialize + Send + 'static,
+    F: Fn(&IndexerReader, Option<C>) -> Result<Vec<S>, IndexerError> + Clone + Send + 'static,
+{
+    // `None` once the last chunk has been sent.
+    let start = Some((after, /* first_chunk */ true));
+    futures::stream::try_unfold(start, move |state| {
+        let reader = reader.clone();
+        let fetch = fetch.clone();
+        async move {
+            let Some((after, first_chunk)) = state else {
+                return Ok(None);
+            };
+            let stored = reader
+                .spawn_blocking(move |this| fetch(&this, after))
+                .await?;
+            let next = if stored.len() < EXPORT_CHUNK_SIZE {
+                None
+            } else {
+                let last = stored.last().expect("Chunk is full");
+                Some((Some(cursor(last)?), false))
+            };
+            let rows = stored
+                .into_iter()
+                .map(to_row)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Some((encode_csv(&rows, first_chunk)?, next)))
+        }
+    })
+}
+
+fn encode_csv<R: Serialize>(rows: &[R], headers: bool) -> Result<Bytes, IndexerError> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(headers)
+        .from_writer(vec![]);
+    for row in rows {
+        writer
+            .serialize(row)
+            .map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+    Ok(Bytes::from(bytes))
+}
+
+fn csv_response(
+    chunks: impl Stream<Item = Result<Bytes, IndexerError>> + Send + 'static,
+) -> impl IntoResponse {
+    // Errors can only be reported by cutting the response short, the client then resumes from
+    // the cursor of the last row it received.
+    let chunks = futures::StreamExt::inspect(chunks, |chunk| {
+        if let Err(e) = chunk {
+            error!("Bulk export failed: {e}");
+        }
+    });
+    (
+        [(header::CONTENT_TYPE, "text/csv")],
+        StreamBody::new(chunks),
+    )
+}
+
+fn bad_request(message: String) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+impl TryFrom<StoredObject> for ObjectRow {
+    type Error = IndexerError;
+
+    fn try_from(object: StoredObject) -> Result<Self, Self::Error> {
+        let (object_id, version, digest) = object.get_object_ref()?;
+        let owner_id = object
+            .owner_id
+            .map(|owner_id| SuiAddress::from_bytes(owner_id).map(|o| o.to_string()))
+            .transpose()
+            .map_err(|e| {
+                IndexerError::PersistentStorageDataCorruptionError(format!(
+                    "Object {object_id} has an invalid owner: {e}"
+                ))
+            })?;
+        Ok(Self {
+            cursor: object_id.to_string(),
+            object_id: object_id.to_string(),
+            object_version: version.value() as i64,
+            object_digest: digest.to_string(),
+            checkpoint_sequence_number: object.checkpoint_sequence_number,
+            owner_type: object.owner_type,
+            owner_id,
+            object_type: object.object_type,
+            coin_balance: object.coin_balance,
+            serialized_object: Base64::encode(object.serialized_object),
+        })
+    }
+}
+
+impl TryFrom<StoredEvent> for EventRow {
+    type Error = IndexerError;
+
+    fn try_from(event: StoredEvent) -> Result<Self, Self::Error> {
+        let transaction_digest =
+            TransactionDigest::try_from(event.transaction_digest.as_slice())
+                .map_err(|e| IndexerError::InvalidTransactionDigestError(e.to_string()))?;
+        let package = ObjectID::from_bytes(&event.package)?;
+        Ok(Self {
+            cursor: format!(
+                "{}:{}",
+                event.tx_sequence_number, event.event_sequence_number
+            ),
+            tx_sequence_number: event.tx_sequence_number,
+            event_sequence_number: event.event_sequence_number,
+            transaction_digest: transaction_digest.to_string(),
+            checkpoint_sequence_number: event.checkpoint_sequence_number,
+            package: package.to_string(),
+            module: event.module,
+            event_type: event.event_type,
+            timestamp_ms: event.timestamp_ms,
+            bcs: Base64::encode(event.bcs),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event_cursor() {
+        assert_eq!(parse_event_cursor("5678:1"), Some((5678, 1)));
+        assert_eq!(parse_event_cursor("5678"), None);
+        assert_eq!(parse_event_cursor("5678:"), None);
+        assert_eq!(parse_event_cursor("a:1"), None);
+    }
+
+    #[test]
+    fn test_encode_csv_headers_only_in_first_chunk() {
+        #[derive(Serialize)]
+        struct Row {
+            cursor: String,
+            value: u64,
+        }
+        let rows = vec![
+            Row {
+                cursor: "1".to_string(),
+                value: 10,
+            },
+            Row {
+                cursor: "2".to_string(),
+                value: 20,
+            },
+        ];
+        assert_eq!(
+            encode_csv(&rows, true).unwrap(),
+            Bytes::from("cursor,value\n1,10\n2,20\n")
+        );
+        assert_eq!(
+            encode_csv(&rows, false).unwrap(),
+            Bytes::from("1,10\n2,20\n")
+        );
+    }
+}
@@ -41,6 +41,10 @@ impl<S: IndexerStore> ExtendedApi<S> {
             ..
         }) = cursor
         {
+            self.state
+                .get_available_range()
+                .await?
+                .check_object_history(cp)?;
             cp
         } else {
             self.state
@@ -298,6 +298,12 @@ where
             cp_guard.stop_and_record();
             return cp_resp;
         }
+        if let CheckpointId::SequenceNumber(seq) = id {
+            self.state
+                .get_available_range()
+                .await?
+                .check_checkpoint(seq)?;
+        }
         Ok(self.state.get_checkpoint(id).await?)
     }
 
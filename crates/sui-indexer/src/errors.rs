@@ -3,12 +3,16 @@
 
 use fastcrypto::error::FastCryptoError;
 use jsonrpsee::core::Error as RpcError;
-use jsonrpsee::types::error::CallError;
+use jsonrpsee::types::error::{CallError, INVALID_PARAMS_CODE};
+use jsonrpsee::types::ErrorObject;
+use serde_json::json;
 use thiserror::Error;
 
 use sui_types::base_types::ObjectIDParseError;
 use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
 
+use crate::types::AvailableRange;
+
 #[derive(Debug, Error)]
 pub struct DataDownloadError {
     pub error: IndexerError,
@@ -125,6 +129,9 @@ pub enum IndexerError {
 
     #[error("Indexer failed to send item to channel with error: `{0}`")]
     MpscChannelError(String),
+
+    #[error("Indexer no longer has the requested data: `{0}`")]
+    DataPrunedError(String, AvailableRange),
 }
 
 pub trait Context<T> {
@@ -139,7 +146,17 @@ impl<T> Context<T> for Result<T, IndexerError> {
 
 impl From<IndexerError> for RpcError {
     fn from(e: IndexerError) -> Self {
-        RpcError::Call(CallError::Failed(e.into()))
+        match e {
+            // Report the range that is still available, so that clients can tell that the data
+            // existed but has been pruned.
+            IndexerError::DataPrunedError(_, ref range) => {
+                let data = json!({ "availableRange": range });
+                let error_object =
+                    ErrorObject::owned(INVALID_PARAMS_CODE, e.to_string(), Some(data));
+                RpcError::Call(CallError::Custom(error_object))
+            }
+            e => RpcError::Call(CallError::Failed(e.into())),
+        }
     }
 }
 
@@ -0,0 +1,500 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::{anyhow, ensure, Context, Result};
+use object_store::path::Path;
+use object_store::DynObjectStore;
+use sui_config::genesis::Genesis;
+use sui_rest_api::CheckpointData;
+use sui_storage::object_store::ObjectStoreConfig;
+use sui_types::committee::Committee;
+use sui_types::digests::CheckpointDigest;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::message_envelope::Message;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
+use tracing::{info, warn};
+
+/// Extension of the files holding the BCS encoded `CheckpointData` of a single checkpoint, at the
+/// root of the archive bucket.
+pub const CHECKPOINT_FILE_SUFFIX: &str = "chk";
+
+pub fn checkpoint_file_path(sequence_number: CheckpointSequenceNumber) -> Path {
+    Path::from(format!("{sequence_number}.{CHECKPOINT_FILE_SUFFIX}"))
+}
+
+/// Opens the archive bucket described by the YAML `ObjectStoreConfig` at `store_config_path`, and
+/// reads the committee to trust from the genesis blob at `genesis_path`.
+pub fn open_archive(
+    store_config_path: &std::path::Path,
+    genesis_path: &std::path::Path,
+) -> Result<(Arc<DynObjectStore>, Committee)> {
+    let store_config: ObjectStoreConfig = serde_yaml::from_reader(
+        std::fs::File::open(store_config_path)
+            .with_context(|| format!("failed to open {}", store_config_path.display()))?,
+    )
+    .with_context(|| format!("failed to parse {}", store_config_path.display()))?;
+    let store = store_config.make()?;
+    let committee = Genesis::load(genesis_path)?.committee()?;
+    Ok((store, committee))
+}
+
+/// Fetches checkpoints from an archive bucket instead of a fullnode, so that an indexer can
+/// backfill history that fullnodes have already pruned.
+///
+/// The bucket is not trusted: every checkpoint is verified against the committee chain that
+/// starts at the genesis committee, following the end of epoch checkpoints to learn the committee
+/// of each epoch, and the transactions, effects, events and output objects it carries are checked
+/// against the digests the committee signed.
+pub struct ArchiveCheckpointFetcher {
+    store: Arc<DynObjectStore>,
+    /// Committee of the epoch of the next checkpoint to download.
+    committee: Committee,
+    last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
+    /// Digest of `last_downloaded_checkpoint`, once it has been verified.
+    last_checkpoint_digest: Option<CheckpointDigest>,
+    sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
+}
+
+impl ArchiveCheckpointFetcher {
+    const INTERVAL_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
+    const CHECKPOINT_DOWNLOAD_CONCURRENCY: usize = 100;
+
+    /// `genesis_committee` is the trusted committee of epoch 0, usually read from the genesis
+    /// blob of the network the archive belongs to.
+    pub fn new(
+        store: Arc<DynObjectStore>,
+        genesis_committee: Committee,
+        last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
+        sender: mysten_metrics::metered_channel::Sender<CheckpointData>,
+    ) -> Self {
+        Self {
+            store,
+            committee: genesis_committee,
+            last_downloaded_checkpoint,
+            last_checkpoint_digest: None,
+            sender,
+        }
+    }
+
+    pub async fn run(mut self) {
+        let mut interval = tokio::time::interval(Self::INTERVAL_PERIOD);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        info!("ArchiveCheckpointFetcher started");
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.sync_committee().await {
+                warn!("error syncing committee up to the last downloaded checkpoint: {e}");
+                continue;
+            }
+
+            if let Err(e) = self.download_checkpoints().await {
+                warn!("error downloading checkpoints from archive: {e}");
+                continue;
+            }
+        }
+    }
+
+    /// When resuming from a checkpoint past genesis, walks the end of epoch checkpoints up to
+    /// the epoch of the last downloaded checkpoint, so that the next checkpoint can be verified.
+    pub(crate) async fn sync_committee(&mut self) -> Result<()> {
+        let Some(last_downloaded) = self.last_downloaded_checkpoint else {
+            return Ok(());
+        };
+        if self.last_checkpoint_digest.is_some() {
+            return Ok(());
+        }
+
+        let last = self
+            .fetch_checkpoint(last_downloaded)
+            .await?
+            .ok_or_else(|| anyhow!("checkpoint {last_downloaded} is missing from the archive"))?;
+        let target_epoch = last.checkpoint_summary.epoch();
+
+        // Lowest checkpoint known to belong to the epoch of `self.committee`.
+        let mut low = 0;
+        while self.committee.epoch < target_epoch {
+            let first_of_next_epoch = self
+                .first_checkpoint_after_epoch(self.committee.epoch, low, last_downloaded)
+                .await?;
+            let end_of_epoch = self
+                .fetch_checkpoint(first_of_next_epoch - 1)
+                .await?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "checkpoint {} is missing from the archive",
+                        first_of_next_epoch - 1
+                    )
+                })?;
+            self.verify_summary(&end_of_epoch.checkpoint_summary)?;
+            ensure!(
+                end_of_epoch
+                    .checkpoint_summary
+                    .next_epoch_committee()
+                    .is_some(),
+                "checkpoint {} is the last of epoch {} but has no next epoch committee",
+                first_of_next_epoch - 1,
+                self.committee.epoch
+            );
+            self.advance_epoch(&end_of_epoch.checkpoint_summary);
+            info!(
+                epoch = self.committee.epoch,
+                checkpoint = first_of_next_epoch - 1,
+                "verified committee from end of epoch checkpoint"
+            );
+            low = first_of_next_epoch;
+        }
+
+        self.verify_summary(&last.checkpoint_summary)?;
+        self.last_checkpoint_digest = Some(*last.checkpoint_summary.digest());
+        self.advance_epoch(&last.checkpoint_summary);
+        Ok(())
+    }
+
+    /// Binary searches `[low, high]` for the first checkpoint past `epoch`. Epochs only grow
+    /// with sequence numbers, and `high` is known to be past `epoch`.
+    async fn first_checkpoint_after_epoch(
+        &self,
+        epoch: u64,
+        mut low: CheckpointSequenceNumber,
+        mut high: CheckpointSequenceNumber,
+    ) -> Result<CheckpointSequenceNumber> {
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let checkpoint = self
+                .fetch_checkpoint(mid)
+                .await?
+                .ok_or_else(|| anyhow!("checkpoint {mid} is missing from the archive"))?;
+            if checkpoint.checkpoint_summary.epoch() > epoch {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        ensure!(low > 0, "genesis checkpoint cannot start a new epoch");
+        Ok(low)
+    }
+
+    pub(crate) async fn download_checkpoints(&mut self) -> Result<()> {
+        use futures::StreamExt;
+        use tap::Pipe;
+
+        // The archive has no notion of its latest checkpoint, so keep downloading batches of
+        // checkpoints until one of them has not been uploaded yet.
+        loop {
+            let next = self
+                .last_downloaded_checkpoint
+                .map(|i| i.saturating_add(1))
+                .unwrap_or(0);
+            let checkpoint_range = next..next + Self::CHECKPOINT_DOWNLOAD_CONCURRENCY as u64;
+
+            let checkpoints: Vec<_> = checkpoint_range
+                .map(|seq| self.fetch_checkpoint(seq))
+                .pipe(futures::stream::iter)
+                .buffered(Self::CHECKPOINT_DOWNLOAD_CONCURRENCY)
+                .collect()
+                .await;
+
+            for maybe_checkpoint in checkpoints {
+                let Some(checkpoint) = maybe_checkpoint? else {
+                    return Ok(());
+                };
+                self.verify_checkpoint(&checkpoint)?;
+
+                let summary = &checkpoint.checkpoint_summary;
+                self.last_downloaded_checkpoint = Some(*summary.sequence_number());
+                self.last_checkpoint_digest = Some(*summary.digest());
+                self.advance_epoch(summary);
+
+                info!(
+                    checkpoint = summary.sequence_number(),
+                    "successfully downloaded and verified checkpoint from archive"
+                );
+
+                self.sender
+                    .send(checkpoint)
+                    .await
+                    .expect("channel shouldn't be closed");
+            }
+        }
+    }
+
+    /// Returns `None` if the checkpoint has not been uploaded to the archive yet.
+    async fn fetch_checkpoint(
+        &self,
+        sequence_number: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointData>> {
+        let bytes = match self.store.get(&checkpoint_file_path(sequence_number)).await {
+            Ok(result) => result.bytes().await?,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let checkpoint: CheckpointData = bcs::from_bytes(&bytes)
+            .with_context(|| format!("failed to deserialize checkpoint {sequence_number}"))?;
+        ensure!(
+            *checkpoint.checkpoint_summary.sequence_number() == sequence_number,
+            "archive file for checkpoint {sequence_number} holds checkpoint {}",
+            checkpoint.checkpoint_summary.sequence_number()
+        );
+        Ok(Some(checkpoint))
+    }
+
+    fn verify_summary(&self, summary: &CertifiedCheckpointSummary) -> Result<()> {
+        ensure!(
+            summary.epoch() == self.committee.epoch,
+            "checkpoint {} is from epoch {}, expected epoch {}",
+            summary.sequence_number(),
+            summary.epoch(),
+            self.committee.epoch
+        );
+        summary
+            .verify_authority_signatures(&self.committee)
+            .with_context(|| {
+                format!(
+                    "invalid signatures on checkpoint {}",
+                    summary.sequence_number()
+                )
+            })
+    }
+
+    /// Moves on to the committee of the next epoch if `summary` is the last checkpoint of its
+    /// epoch. `summary` must already be verified.
+    fn advance_epoch(&mut self, summary: &CertifiedCheckpointSummary) {
+        if let Some(next_committee) = summary.handoff_committee() {
+            self.committee = next_committee;
+        }
+    }
+
+    /// Checks that `checkpoint` follows the last downloaded checkpoint, is certified by the
+    /// current committee, and that everything it carries matches what the committee signed.
+    pub(crate) fn verify_checkpoint(&self, checkpoint: &CheckpointData) -> Result<()> {
+        let summary = &checkpoint.checkpoint_summary;
+        let seq = *summary.sequence_number();
+
+        ensure!(
+            summary.previous_digest == self.last_checkpoint_digest,
+            "checkpoint {seq} does not follow the last downloaded checkpoint: \
+            previous digest is {:?}, expected {:?}",
+            summary.previous_digest,
+            self.last_checkpoint_digest
+        );
+        self.verify_summary(summary)?;
+        summary
+            .verify_with_contents(&self.committee, Some(&checkpoint.checkpoint_contents))
+            .with_context(|| format!("invalid contents for checkpoint {seq}"))?;
+
+        ensure!(
+            checkpoint.checkpoint_contents.size() == checkpoint.transactions.len(),
+            "checkpoint {seq} lists {} transactions, archive has {}",
+            checkpoint.checkpoint_contents.size(),
+            checkpoint.transactions.len()
+        );
+
+        for (digests, tx) in checkpoint
+            .checkpoint_contents
+            .iter()
+            .zip(&checkpoint.transactions)
+        {
+            ensure!(
+                *tx.transaction.digest() == digests.transaction,
+                "transaction digest mismatch in checkpoint {seq}: expected {}, got {}",
+                digests.transaction,
+                tx.transaction.digest()
+            );
+            ensure!(
+                tx.effects.digest() == digests.effects,
+                "effects digest mismatch for transaction {} in checkpoint {seq}",
+                digests.transaction
+            );
+            ensure!(
+                tx.events.as_ref().map(|events| events.digest()).as_ref()
+                    == tx.effects.events_digest(),
+                "events digest mismatch for transaction {} in checkpoint {seq}",
+                digests.transaction
+            );
+
+            let written: HashSet<_> = tx
+                .effects
+                .all_changed_objects()
+                .into_iter()
+                .map(|(object_ref, _, _)| object_ref)
+                .collect();
+            for object in &tx.output_objects {
+                let object_ref = object.compute_object_reference();
+                ensure!(
+                    written.contains(&object_ref),
+                    "output object {:?} of transaction {} in checkpoint {seq} \
+                    is not written by its effects",
+                    object_ref,
+                    digests.transaction
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use object_store::memory::InMemory;
+    use object_store::ObjectStore;
+    use prometheus::IntGauge;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use sui_config::genesis::Genesis;
+    use sui_protocol_config::ProtocolVersion;
+    use sui_rest_api::CheckpointData;
+    use sui_storage::object_store::ObjectStoreConfig;
+    use sui_types::committee::Committee;
+    use sui_types::crypto::AuthorityKeyPair;
+    use sui_types::gas::GasCostSummary;
+    use sui_types::message_envelope::Message;
+    use sui_types::messages_checkpoint::{
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, EndOfEpochData,
+    };
+    use sui_types::utils::make_committee_key;
+
+    use super::{checkpoint_file_path, ArchiveCheckpointFetcher};
+
+    struct TestChain {
+        store: Arc<InMemory>,
+        genesis_committee: Committee,
+        checkpoints: Vec<CheckpointData>,
+    }
+
+    /// Builds a chain of `len` empty checkpoints, where checkpoint `end_of_epoch` is the last one
+    /// of epoch 0, and uploads them to an in memory archive.
+    async fn test_chain(len: u64, end_of_epoch: u64) -> TestChain {
+        let mut rng = StdRng::from_seed([0; 32]);
+        let (genesis_keys, genesis_committee) = make_committee_key(&mut rng);
+        let (next_keys, next_committee) = make_committee_key(&mut rng);
+        let next_committee = Committee::new(1, next_committee.voting_rights.into_iter().collect());
+
+        let store = Arc::new(InMemory::new());
+        let mut checkpoints: Vec<CheckpointData> = vec![];
+        for seq in 0..len {
+            let (keys, committee): (&[AuthorityKeyPair], _) = if seq <= end_of_epoch {
+                (&genesis_keys, &genesis_committee)
+            } else {
+                (&next_keys, &next_committee)
+            };
+            let end_of_epoch_data = (seq == end_of_epoch).then(|| EndOfEpochData {
+                next_epoch_committee: next_committee.voting_rights.clone(),
+                next_epoch_protocol_version: ProtocolVersion::MIN,
+                epoch_commitments: vec![],
+            });
+
+            let contents = CheckpointContents::new_with_causally_ordered_transactions([]);
+            let summary = CheckpointSummary::new(
+                committee.epoch,
+                seq,
+                0,
+                &contents,
+                checkpoints.last().map(|c| *c.checkpoint_summary.digest()),
+                GasCostSummary::default(),
+                end_of_epoch_data,
+                0,
+            );
+            let checkpoint = CheckpointData {
+                checkpoint_summary: CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+                    summary, keys, committee,
+                ),
+                checkpoint_contents: contents,
+                transactions: vec![],
+            };
+
+            store
+                .put(
+                    &checkpoint_file_path(seq),
+                    bcs::to_bytes(&checkpoint).unwrap().into(),
+                )
+                .await
+                .unwrap();
+            checkpoints.push(checkpoint);
+        }
+
+        TestChain {
+            store,
+            genesis_committee,
+            checkpoints,
+        }
+    }
+
+    fn fetcher(
+        chain: &TestChain,
+        last_downloaded_checkpoint: Option<u64>,
+    ) -> (
+        ArchiveCheckpointFetcher,
+        mysten_metrics::metered_channel::Receiver<CheckpointData>,
+    ) {
+        let gauge = IntGauge::new("test_archive_fetcher", "test").unwrap();
+        let (sender, receiver) = mysten_metrics::metered_channel::channel(100, &gauge);
+        let fetcher = ArchiveCheckpointFetcher::new(
+            chain.store.clone(),
+            chain.genesis_committee.clone(),
+            last_downloaded_checkpoint,
+            sender,
+        );
+        (fetcher, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_download_across_epochs() {
+        let chain = test_chain(5, 2).await;
+        let (mut fetcher, mut receiver) = fetcher(&chain, None);
+
+        fetcher.download_checkpoints().await.unwrap();
+        assert_eq!(fetcher.last_downloaded_checkpoint, Some(4));
+        assert_eq!(fetcher.committee.epoch, 1);
+        for seq in 0..5 {
+            let checkpoint = receiver.recv().await.unwrap();
+            assert_eq!(*checkpoint.checkpoint_summary.sequence_number(), seq);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_rebuilds_committee() {
+        let chain = test_chain(8, 2).await;
+        let (mut fetcher, _receiver) = fetcher(&chain, Some(5));
+
+        fetcher.sync_committee().await.unwrap();
+        assert_eq!(fetcher.committee.epoch, 1);
+        assert_eq!(
+            fetcher.last_checkpoint_digest,
+            Some(*chain.checkpoints[5].checkpoint_summary.digest())
+        );
+
+        fetcher.download_checkpoints().await.unwrap();
+        assert_eq!(fetcher.last_downloaded_checkpoint, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_reject_checkpoint_from_wrong_committee() {
+        let chain = test_chain(3, 2).await;
+        let (fetcher, _receiver) = fetcher(&chain, None);
+
+        // Signed by the genesis committee, but claims to follow a checkpoint that was never
+        // downloaded.
+        assert!(fetcher.verify_checkpoint(&chain.checkpoints[1]).is_err());
+
+        // Correctly chained, but signed by a committee that is not the genesis committee.
+        let mut rng = StdRng::from_seed([1; 32]);
+        let (keys, committee) = make_committee_key(&mut rng);
+        let mut forged = chain.checkpoints[0].clone();
+        forged.checkpoint_summary = CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+            forged.checkpoint_summary.into_data(),
+            &keys,
+            &committee,
+        );
+        assert!(fetcher.verify_checkpoint(&forged).is_err());
+        assert!(fetcher.verify_checkpoint(&chain.checkpoints[0]).is_ok());
+    }
+}
@@ -1,13 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
+use object_store::DynObjectStore;
+use sui_types::committee::Committee;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
+use super::archive_fetcher::ArchiveCheckpointFetcher;
 use super::fetcher::CheckpointFetcher;
-use super::Handler;
+use super::pipeline::PipelineHandler;
+use super::{Handler, Pipeline};
+use crate::PgConnectionPool;
 
 pub struct IndexerBuilder {
     rest_url: Option<String>,
+    archive: Option<(Arc<DynObjectStore>, Committee)>,
     handlers: Vec<Box<dyn Handler>>,
     last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
     checkpoint_buffer_size: usize,
@@ -20,6 +28,7 @@ impl IndexerBuilder {
     pub fn new() -> Self {
         Self {
             rest_url: None,
+            archive: None,
             handlers: Vec::new(),
             last_downloaded_checkpoint: None,
             checkpoint_buffer_size: Self::DEFAULT_CHECKPOINT_BUFFER_SIZE,
@@ -31,11 +40,23 @@ impl IndexerBuilder {
         self
     }
 
+    /// Ingest checkpoints from an archive bucket instead of the fullnode at `rest_url`, verifying
+    /// them against the committee chain starting at `genesis_committee`.
+    pub fn archive(mut self, store: Arc<DynObjectStore>, genesis_committee: Committee) -> Self {
+        self.archive = Some((store, genesis_committee));
+        self
+    }
+
     pub fn handler<T: Handler + 'static>(mut self, handler: T) -> Self {
         self.handlers.push(Box::new(handler));
         self
     }
 
+    /// Populate the tables of `pipeline` in the database of `pool` from the ingested checkpoints.
+    pub fn pipeline<P: Pipeline>(self, pipeline: P, pool: PgConnectionPool) -> Self {
+        self.handler(PipelineHandler::new(pipeline, pool))
+    }
+
     pub fn last_downloaded_checkpoint(
         mut self,
         last_downloaded_checkpoint: Option<CheckpointSequenceNumber>,
@@ -59,15 +80,25 @@ impl IndexerBuilder {
                     .with_label_values(&["checkpoint_tx_downloading"]),
             );
 
-        // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
-        // service
-        let rest_api_url = format!("{}/rest", self.rest_url.unwrap());
-        let fetcher = CheckpointFetcher::new(
-            sui_rest_api::Client::new(rest_api_url),
-            self.last_downloaded_checkpoint,
-            downloaded_checkpoint_data_sender,
-        );
-        mysten_metrics::spawn_monitored_task!(fetcher.run());
+        if let Some((store, genesis_committee)) = self.archive {
+            let fetcher = ArchiveCheckpointFetcher::new(
+                store,
+                genesis_committee,
+                self.last_downloaded_checkpoint,
+                downloaded_checkpoint_data_sender,
+            );
+            mysten_metrics::spawn_monitored_task!(fetcher.run());
+        } else {
+            // experimental rest api route is found at `/rest` on the same interface as the jsonrpc
+            // service
+            let rest_api_url = format!("{}/rest", self.rest_url.unwrap());
+            let fetcher = CheckpointFetcher::new(
+                sui_rest_api::Client::new(rest_api_url),
+                self.last_downloaded_checkpoint,
+                downloaded_checkpoint_data_sender,
+            );
+            mysten_metrics::spawn_monitored_task!(fetcher.run());
+        }
 
         assert!(!self.handlers.is_empty());
 
@@ -3,10 +3,13 @@
 
 mod builder;
 pub mod interface;
+pub mod pipeline;
 
 // TODO remove the pub(crater) once indexer_v2.rs is renamed to lib.rs
+pub(crate) mod archive_fetcher;
 pub(crate) mod fetcher;
 pub(crate) mod runner;
 
 pub use builder::IndexerBuilder;
 pub use interface::Handler;
+pub use pipeline::Pipeline;
@@ -0,0 +1,118 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use diesel::connection::SimpleConnection;
+use diesel::{Connection, PgConnection};
+use sui_rest_api::CheckpointData;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use tracing::info;
+
+use crate::models::watermarks::Watermark;
+use crate::{get_pg_pool_connection, PgConnectionPool};
+
+use super::interface::Handler;
+
+/// Tables of an application-specific index (of a game, a DEX, ...), populated from the
+/// checkpoints ingested by the indexer. Pipelines can be defined in other crates, and registered
+/// with `IndexerBuilder::pipeline`, which takes care of creating their tables, ingesting
+/// checkpoints for them, and pruning them under their own watermark.
+pub trait Pipeline: Send + Sync + 'static {
+    /// Name of the pipeline, unique among the pipelines of the indexer. It is also the entity of
+    /// the pipeline's watermark.
+    fn name(&self) -> &'static str;
+
+    /// SQL statements creating the tables of the pipeline if they do not exist yet, run before
+    /// the first checkpoint is committed.
+    fn schema(&self) -> &'static str;
+
+    /// Writes the rows for `checkpoint`, in the same transaction for the whole checkpoint.
+    /// Checkpoints are committed in order, but the last ones may be committed again after a
+    /// restart, so writes should be idempotent.
+    fn commit(&self, conn: &mut PgConnection, checkpoint: &CheckpointData) -> Result<()>;
+
+    /// Number of most recent checkpoints whose rows are kept, or `None` to keep them all.
+    fn retention(&self) -> Option<u64> {
+        None
+    }
+
+    /// Deletes the rows for the checkpoints in `[from, to)`, once they are out of the retention
+    /// window. The watermark of the pipeline is moved to `to` in the same transaction.
+    fn prune(
+        &self,
+        _conn: &mut PgConnection,
+        _from: CheckpointSequenceNumber,
+        _to: CheckpointSequenceNumber,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs a pipeline as a handler of the indexer's checkpoints.
+pub(crate) struct PipelineHandler<P> {
+    pipeline: Arc<P>,
+    pool: PgConnectionPool,
+    schema_created: bool,
+}
+
+impl<P: Pipeline> PipelineHandler<P> {
+    pub(crate) fn new(pipeline: P, pool: PgConnectionPool) -> Self {
+        Self {
+            pipeline: Arc::new(pipeline),
+            pool,
+            schema_created: false,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<P: Pipeline> Handler for PipelineHandler<P> {
+    fn name(&self) -> &str {
+        self.pipeline.name()
+    }
+
+    async fn process_checkpoint(&mut self, checkpoint_data: &CheckpointData) -> Result<()> {
+        let pipeline = self.pipeline.clone();
+        let pool = self.pool.clone();
+        let create_schema = !self.schema_created;
+        let checkpoint = checkpoint_data.clone();
+        tokio::task::spawn_blocking(move || {
+            let conn = &mut get_pg_pool_connection(&pool)?;
+            if create_schema {
+                info!("Creating tables of pipeline {}", pipeline.name());
+                conn.batch_execute(pipeline.schema())?;
+            }
+            commit_and_prune(pipeline.as_ref(), conn, &checkpoint)
+        })
+        .await
+        .map_err(|e| anyhow!("Pipeline {} panicked: {e}", self.pipeline.name()))??;
+        self.schema_created = true;
+        Ok(())
+    }
+}
+
+fn commit_and_prune<P: Pipeline>(
+    pipeline: &P,
+    conn: &mut PgConnection,
+    checkpoint: &CheckpointData,
+) -> Result<()> {
+    conn.transaction::<_, anyhow::Error, _>(|conn| pipeline.commit(conn, checkpoint))?;
+
+    let Some(retention) = pipeline.retention() else {
+        return Ok(());
+    };
+    let sequence_number = checkpoint.checkpoint_summary.sequence_number;
+    let Some(lowest_available) = (sequence_number + 1).checked_sub(retention) else {
+        return Ok(());
+    };
+    conn.transaction::<_, anyhow::Error, _>(|conn| {
+        let pruned_up_to = Watermark::lowest_available(conn, pipeline.name())?;
+        if lowest_available > pruned_up_to {
+            pipeline.prune(conn, pruned_up_to, lowest_available)?;
+            Watermark::update(conn, pipeline.name(), lowest_available)?;
+        }
+        Ok(())
+    })
+}
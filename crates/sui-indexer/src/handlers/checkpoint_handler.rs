@@ -8,7 +8,7 @@ use mysten_metrics::{get_metrics, spawn_monitored_task};
 use std::collections::HashMap;
 use sui_rest_api::{CheckpointData, CheckpointTransaction};
 use sui_types::committee::EpochId;
-use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::effects::{ObjectChangeKind, TransactionEffects, TransactionEffectsAPI};
 use sui_types::object::Owner;
 use sui_types::transaction::TransactionDataAPI;
 use tap::tap::TapFallible;
@@ -946,29 +946,24 @@ pub fn get_deleted_db_objects(
     epoch: EpochId,
     checkpoint: CheckpointSequenceNumber,
 ) -> Vec<DeletedObject> {
-    let deleted = effects
-        .deleted()
+    // Input objects are only needed to classify objects that still exist.
+    effects
+        .object_changes([])
         .into_iter()
-        .map(|o| (ObjectStatus::Deleted, o));
-    let wrapped = effects
-        .wrapped()
-        .into_iter()
-        .map(|o| (ObjectStatus::Wrapped, o));
-    let unwrapped_then_deleted = effects
-        .unwrapped_then_deleted()
-        .into_iter()
-        .map(|o| (ObjectStatus::UnwrappedThenDeleted, o));
-    deleted
-        .chain(wrapped)
-        .chain(unwrapped_then_deleted)
-        .map(|(status, oref)| {
-            DeletedObject::from(
+        .filter_map(|change| {
+            let status = match change.kind {
+                ObjectChangeKind::Deleted => ObjectStatus::Deleted,
+                ObjectChangeKind::Wrapped => ObjectStatus::Wrapped,
+                ObjectChangeKind::UnwrappedThenDeleted => ObjectStatus::UnwrappedThenDeleted,
+                _ => return None,
+            };
+            Some(DeletedObject::from(
                 epoch,
                 Some(checkpoint),
-                &sui_json_rpc_types::SuiObjectRef::from(oref.to_owned()),
+                &sui_json_rpc_types::SuiObjectRef::from(change.object_ref),
                 effects.transaction_digest(),
                 &status,
-            )
+            ))
         })
         .collect::<Vec<_>>()
 }
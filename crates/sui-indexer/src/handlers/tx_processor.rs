@@ -23,7 +23,7 @@ use sui_json_rpc::get_object_changes;
 use sui_json_rpc::ObjectProvider;
 use sui_types::base_types::SequenceNumber;
 use sui_types::digests::TransactionDigest;
-use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::effects::TransactionEffects;
 use sui_types::transaction::{TransactionData, TransactionDataAPI};
 use tracing::debug;
 
@@ -182,17 +182,11 @@ impl TxChangesProcessor {
             .metrics
             .indexing_tx_object_changes_latency
             .start_timer();
-        let object_change: Vec<_> = get_object_changes(
-            self,
-            tx.sender(),
-            effects.modified_at_versions(),
-            effects.all_changed_objects(),
-            effects.all_removed_objects(),
-        )
-        .await?
-        .into_iter()
-        .map(IndexedObjectChange::from)
-        .collect();
+        let object_change: Vec<_> = get_object_changes(self, tx.sender(), effects)
+            .await?
+            .into_iter()
+            .map(IndexedObjectChange::from)
+            .collect();
         let balance_change = get_balance_changes_from_effect(
             self,
             effects,
@@ -7,15 +7,19 @@ use std::{
 };
 
 use crate::{
-    errors::IndexerError, models_v2::packages::StoredPackage, schema_v2::packages,
+    errors::IndexerError,
+    models_v2::{events::StoredEvent, objects::StoredObject, packages::StoredPackage},
+    schema_v2::{events, objects, packages},
     PgConectionPoolConfig, PgConnectionConfig, PgPoolConnection,
 };
 use anyhow::{anyhow, Result};
 use diesel::{
-    r2d2::ConnectionManager, ExpressionMethods, OptionalExtension, PgConnection, QueryDsl,
-    RunQueryDsl,
+    r2d2::ConnectionManager, BoolExpressionMethods, ExpressionMethods, OptionalExtension,
+    PgConnection, QueryDsl, RunQueryDsl,
+};
+use sui_types::{
+    base_types::ObjectID, messages_checkpoint::CheckpointSequenceNumber, move_package::MovePackage,
 };
-use sui_types::{base_types::ObjectID, move_package::MovePackage};
 
 #[derive(Clone)]
 pub struct IndexerReader {
@@ -147,6 +151,67 @@ impl IndexerReader {
     }
 }
 
+// Impl for bulk exports, which page through a table in primary key order, so that an export can
+// be resumed after the last row it returned.
+impl IndexerReader {
+    /// Up to `limit` objects whose type is exactly `object_type`, in order of their IDs, starting
+    /// after the object with ID `after`.
+    pub fn get_objects_of_type(
+        &self,
+        object_type: String,
+        after: Option<ObjectID>,
+        limit: usize,
+    ) -> Result<Vec<StoredObject>, IndexerError> {
+        self.run_query(|conn| {
+            let mut query = objects::dsl::objects
+                .filter(objects::dsl::object_type.eq(object_type))
+                .into_boxed();
+            if let Some(after) = after {
+                query = query.filter(objects::dsl::object_id.gt(after.to_vec()));
+            }
+            query
+                .order(objects::dsl::object_id.asc())
+                .limit(limit as i64)
+                .load::<StoredObject>(conn)
+        })
+    }
+
+    /// Up to `limit` events emitted by calls into `package`, in checkpoints from `checkpoints`,
+    /// in the order they were emitted, starting after the event at `after`, given as its
+    /// `(tx_sequence_number, event_sequence_number)`.
+    pub fn get_package_events(
+        &self,
+        package: ObjectID,
+        checkpoints: std::ops::Range<CheckpointSequenceNumber>,
+        after: Option<(i64, i64)>,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, IndexerError> {
+        self.run_query(|conn| {
+            let mut query = events::dsl::events
+                .filter(events::dsl::package.eq(package.to_vec()))
+                .filter(events::dsl::checkpoint_sequence_number.ge(checkpoints.start as i64))
+                .filter(events::dsl::checkpoint_sequence_number.lt(checkpoints.end as i64))
+                .into_boxed();
+            if let Some((tx_sequence_number, event_sequence_number)) = after {
+                query = query.filter(
+                    events::dsl::tx_sequence_number.gt(tx_sequence_number).or(
+                        events::dsl::tx_sequence_number
+                            .eq(tx_sequence_number)
+                            .and(events::dsl::event_sequence_number.gt(event_sequence_number)),
+                    ),
+                );
+            }
+            query
+                .order((
+                    events::dsl::tx_sequence_number.asc(),
+                    events::dsl::event_sequence_number.asc(),
+                ))
+                .limit(limit as i64)
+                .load::<StoredEvent>(conn)
+        })
+    }
+}
+
 #[derive(Clone, Default)]
 struct PackageCache {
     inner: Arc<RwLock<BTreeMap<ObjectID, MovePackage>>>,
@@ -1,7 +1,9 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::admin::run_admin_server;
 use crate::errors::IndexerError;
+use crate::indexer_reader::IndexerReader;
 use crate::metrics::IndexerMetrics;
 use crate::IndexerConfig;
 use anyhow::Result;
@@ -14,6 +16,7 @@ use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle};
 use tokio::runtime::Handle;
 use tracing::info;
 
+use crate::framework::archive_fetcher::ArchiveCheckpointFetcher;
 use crate::framework::fetcher::CheckpointFetcher;
 use crate::handlers::checkpoint_handler_v2::new_handlers;
 use crate::store::IndexerStoreV2;
@@ -42,6 +45,10 @@ impl IndexerV2 {
             let handle = build_json_rpc_server(registry, store.clone(), config, None)
                 .await
                 .expect("Json rpc server should not run into errors upon start.");
+            if let Some(admin_port) = config.admin_port {
+                let reader = IndexerReader::new(config.get_db_url()?)?;
+                spawn_monitored_task!(run_admin_server(reader, admin_port));
+            }
             Some(tokio::spawn(async move { handle.stopped().await }))
         } else {
             None
@@ -69,14 +76,25 @@ impl IndexerV2 {
                     .with_label_values(&["checkpoint_tx_downloading"]),
             );
 
-        let rest_api_url = format!("{}/rest", config.rpc_client_url);
-        let rest_client = sui_rest_api::Client::new(&rest_api_url);
-        let fetcher = CheckpointFetcher::new(
-            rest_client.clone(),
-            last_seq_from_db,
-            downloaded_checkpoint_data_sender,
-        );
-        spawn_monitored_task!(fetcher.run());
+        if let Some((store, genesis_committee)) = config.archive()? {
+            info!("Ingesting checkpoints from archive");
+            let fetcher = ArchiveCheckpointFetcher::new(
+                store,
+                genesis_committee,
+                last_seq_from_db,
+                downloaded_checkpoint_data_sender,
+            );
+            spawn_monitored_task!(fetcher.run());
+        } else {
+            let rest_api_url = format!("{}/rest", config.rpc_client_url);
+            let rest_client = sui_rest_api::Client::new(&rest_api_url);
+            let fetcher = CheckpointFetcher::new(
+                rest_client.clone(),
+                last_seq_from_db,
+                downloaded_checkpoint_data_sender,
+            );
+            spawn_monitored_task!(fetcher.run());
+        }
 
         let checkpoint_handler = new_handlers(store, metrics, config).await?;
 
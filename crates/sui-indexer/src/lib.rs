@@ -4,6 +4,8 @@
 
 use std::env;
 use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::{collections::HashMap, time::Duration};
 
 use anyhow::{anyhow, Result};
@@ -25,15 +27,19 @@ use apis::{
 };
 use errors::IndexerError;
 use mysten_metrics::{spawn_monitored_task, RegistryService};
+use object_store::DynObjectStore;
 use processors::processor_orchestrator::ProcessorOrchestrator;
 use store::IndexerStore;
 use sui_json_rpc::{JsonRpcServerBuilder, ServerHandle, ServerType, CLIENT_SDK_TYPE_HEADER};
 use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::committee::Committee;
 
 use crate::apis::MoveUtilsApi;
+use crate::framework::archive_fetcher::open_archive;
 use crate::framework::IndexerBuilder;
 use crate::handlers::checkpoint_handler::new_handlers;
 
+pub mod admin;
 pub mod apis;
 pub mod errors;
 pub mod framework;
@@ -116,6 +122,20 @@ pub struct IndexerConfig {
 
     #[clap(long)]
     pub use_v2: bool,
+
+    /// Port of the admin server, which serves bulk exports of indexed data. The server is only
+    /// started alongside the reader, and only listens on localhost.
+    #[clap(long)]
+    pub admin_port: Option<u16>,
+
+    /// YAML object store config of an archive bucket to ingest checkpoints from, instead of the
+    /// fullnode at `rpc_client_url`. This allows backfilling checkpoints the fullnode has pruned.
+    #[clap(long, requires = "archive_genesis")]
+    pub archive_store_config: Option<PathBuf>,
+    /// Genesis blob of the network the archive belongs to. Checkpoints from the archive are
+    /// verified against the committee chain starting at its committee.
+    #[clap(long)]
+    pub archive_genesis: Option<PathBuf>,
 }
 
 impl IndexerConfig {
@@ -137,6 +157,18 @@ impl IndexerConfig {
         IMPLEMENTED_METHODS.iter().map(|&s| s.to_string()).collect()
     }
 
+    /// Opens the archive bucket and reads its trusted committee, if ingesting from an archive.
+    pub fn archive(&self) -> Result<Option<(Arc<DynObjectStore>, Committee)>, IndexerError> {
+        let (Some(store_config), Some(genesis)) =
+            (&self.archive_store_config, &self.archive_genesis)
+        else {
+            return Ok(None);
+        };
+        open_archive(store_config, genesis)
+            .map(Some)
+            .map_err(|e| IndexerError::GenericError(format!("Failed to open archive: {e}")))
+    }
+
     pub fn get_db_url(&self) -> Result<String, anyhow::Error> {
         match (&self.db_url, &self.db_user_name, &self.db_password, &self.db_host, &self.db_port, &self.db_name) {
             (Some(db_url), _, _, _, _, _) => Ok(db_url.clone()),
@@ -171,6 +203,9 @@ impl Default for IndexerConfig {
             rpc_server_worker: true,
             skip_db_commit: false,
             use_v2: false,
+            admin_port: None,
+            archive_store_config: None,
+            archive_genesis: None,
         }
     }
 }
@@ -215,13 +250,16 @@ impl Indexer {
 
             let (checkpoint_handler, object_handler) = new_handlers(store, metrics, config);
 
-            IndexerBuilder::new()
+            let mut builder = IndexerBuilder::new()
                 .last_downloaded_checkpoint(last_downloaded_checkpoint)
                 .rest_url(&config.rpc_client_url)
                 .handler(checkpoint_handler)
-                .handler(object_handler)
-                .run()
-                .await;
+                .handler(object_handler);
+            if let Some((store, genesis_committee)) = config.archive()? {
+                info!("Ingesting checkpoints from archive");
+                builder = builder.archive(store, genesis_committee);
+            }
+            builder.run().await;
         }
 
         Ok(())
@@ -13,3 +13,4 @@ pub mod packages;
 pub mod system_state;
 pub mod transaction_index;
 pub mod transactions;
+pub mod watermarks;
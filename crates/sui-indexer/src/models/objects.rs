@@ -377,6 +377,13 @@ pub fn owner_to_owner_info(owner: &Owner) -> (OwnerType, Option<String>, Option<
             Some(initial_shared_version.value() as i64),
         ),
         Owner::Immutable => (OwnerType::Immutable, None, None),
+        // Consensus-owned objects are indexed as owned by their address, until the schema has an
+        // owner type for them
+        Owner::ConsensusV2 { authenticator, .. } => (
+            OwnerType::AddressOwner,
+            Some(authenticator.as_single_owner().to_string()),
+            None,
+        ),
     }
 }
 
@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, VarChar};
+
+use crate::schema::watermarks;
+
+/// Lowest sequence number of a pruned entity that is still available.
+#[derive(Queryable, Insertable, Debug, Clone)]
+#[diesel(table_name = watermarks)]
+pub struct Watermark {
+    pub entity: String,
+    pub lowest_available: i64,
+}
+
+impl Watermark {
+    /// Lowest available sequence number of `entity`, 0 if it has never been pruned.
+    pub fn lowest_available(conn: &mut PgConnection, entity: &str) -> QueryResult<u64> {
+        let lowest_available = watermarks::table
+            .find(entity)
+            .select(watermarks::lowest_available)
+            .first::<i64>(conn)
+            .optional()?;
+        Ok(lowest_available.map_or(0, |l| l as u64))
+    }
+
+    /// Records that `entity` has been pruned below `lowest_available`. Watermarks only move
+    /// forward, so an update below the current watermark has no effect.
+    pub fn update(
+        conn: &mut PgConnection,
+        entity: &str,
+        lowest_available: u64,
+    ) -> QueryResult<usize> {
+        diesel::sql_query(
+            "INSERT INTO watermarks (entity, lowest_available) VALUES ($1, $2) \
+             ON CONFLICT (entity) DO UPDATE SET lowest_available = \
+             GREATEST(watermarks.lowest_available, EXCLUDED.lowest_available)",
+        )
+        .bind::<VarChar, _>(entity)
+        .bind::<BigInt, _>(lowest_available as i64)
+        .execute(conn)
+    }
+}
+
+/// Data that is pruned independently, each with its own watermark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrunedEntity {
+    /// Checkpoints, by checkpoint sequence number.
+    Checkpoints,
+    /// Transactions, by transaction sequence number across the network, as counted by
+    /// checkpoints' `network_total_transactions`.
+    Transactions,
+    /// Object history, by the checkpoint sequence number that objects can be queried at.
+    ObjectsHistory,
+}
+
+impl PrunedEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrunedEntity::Checkpoints => "checkpoints",
+            PrunedEntity::Transactions => "transactions",
+            PrunedEntity::ObjectsHistory => "objects_history",
+        }
+    }
+}
@@ -34,6 +34,7 @@ pub struct StoredObject {
     pub df_name: Option<Vec<u8>>,
     pub df_object_type: Option<String>,
     pub df_object_id: Option<Vec<u8>>,
+    pub object_type: Option<String>,
 }
 
 #[derive(Queryable, Insertable, Debug, Identifiable, Clone, QueryableByName)]
@@ -44,6 +45,7 @@ pub struct StoredDeletedObject {
 
 impl From<IndexedObject> for StoredObject {
     fn from(o: IndexedObject) -> Self {
+        let object_type = o.object.type_().map(|t| t.to_string());
         Self {
             object_id: o.object_id.to_vec(),
             object_version: o.object_version as i64,
@@ -61,6 +63,7 @@ impl From<IndexedObject> for StoredObject {
             df_name: o.df_info.as_ref().map(|n| bcs::to_bytes(&n.name).unwrap()),
             df_object_type: o.df_info.as_ref().map(|v| v.object_type.clone()),
             df_object_id: o.df_info.as_ref().map(|v| v.object_id.to_vec()),
+            object_type,
         }
     }
 }
@@ -373,6 +373,14 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    watermarks (entity) {
+        #[max_length = 255]
+        entity -> Varchar,
+        lowest_available -> Int8,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     active_addresses,
     address_stats,
@@ -392,4 +400,5 @@ diesel::allow_tables_to_appear_in_same_query!(
     system_states,
     transactions,
     validators,
+    watermarks,
 );
@@ -79,6 +79,7 @@ diesel::table! {
         df_name -> Nullable<Bytea>,
         df_object_type -> Nullable<Text>,
         df_object_id -> Nullable<Bytea>,
+        object_type -> Nullable<Text>,
     }
 }
 
@@ -32,7 +32,8 @@ use crate::models::packages::Package;
 use crate::models::system_state::{DBSystemStateSummary, DBValidatorSummary};
 use crate::models::transaction_index::{ChangedObject, InputObject, MoveCall, Recipient};
 use crate::models::transactions::Transaction;
-use crate::types::CheckpointTransactionBlockResponse;
+use crate::models::watermarks::PrunedEntity;
+use crate::types::{AvailableRange, CheckpointTransactionBlockResponse};
 
 #[async_trait]
 pub trait IndexerStore {
@@ -57,6 +58,16 @@ pub trait IndexerStore {
         digest: CheckpointDigest,
     ) -> Result<CheckpointSequenceNumber, IndexerError>;
 
+    /// Range of data that has been indexed and not pruned since.
+    async fn get_available_range(&self) -> Result<AvailableRange, IndexerError>;
+    /// Record that `entity` has been pruned below `lowest_available`. Watermarks only move
+    /// forward, so an update below the current watermark has no effect.
+    async fn update_watermark(
+        &self,
+        entity: PrunedEntity,
+        lowest_available: u64,
+    ) -> Result<(), IndexerError>;
+
     async fn get_event(&self, id: EventID) -> Result<Event, IndexerError>;
     async fn get_events(
         &self,
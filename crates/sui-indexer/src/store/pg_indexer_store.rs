@@ -57,16 +57,18 @@ use crate::models::packages::Package;
 use crate::models::system_state::DBValidatorSummary;
 use crate::models::transaction_index::{ChangedObject, InputObject, MoveCall, Recipient};
 use crate::models::transactions::Transaction;
+use crate::models::watermarks::{PrunedEntity, Watermark};
 use crate::schema::{
     active_addresses, address_stats, addresses, changed_objects, checkpoint_metrics, checkpoints,
     epochs, events, input_objects, move_calls, objects, objects_history, packages, recipients,
-    system_states, transactions, validators,
+    system_states, transactions, validators, watermarks,
 };
 use crate::store::diesel_macro::{read_only_blocking, transactional_blocking};
 use crate::store::module_resolver::IndexerModuleResolver;
 use crate::store::query::DBFilter;
 use crate::store::TransactionObjectChanges;
 use crate::store::{IndexerStore, TemporaryEpochStore};
+use crate::types::AvailableRange;
 use crate::PgConnectionPool;
 
 const MAX_EVENT_PAGE_SIZE: usize = 1000;
@@ -205,6 +207,43 @@ impl PgIndexerStore {
         .context("Failed reading latest checkpoint sequence number from PostgresDB")
     }
 
+    fn get_available_range(&self) -> Result<AvailableRange, IndexerError> {
+        let watermarks = read_only_blocking!(&self.blocking_cp, |conn| {
+            watermarks::dsl::watermarks.load::<Watermark>(conn)
+        })
+        .context("Failed reading watermarks from PostgresDB")?;
+        let lowest_available = |entity: PrunedEntity| {
+            watermarks
+                .iter()
+                .find(|w| w.entity == entity.as_str())
+                .map_or(0, |w| w.lowest_available as u64)
+        };
+
+        let last_checkpoint = self.get_latest_tx_checkpoint_sequence_number()?;
+        Ok(AvailableRange {
+            first_checkpoint: lowest_available(PrunedEntity::Checkpoints),
+            // -1 if no checkpoints have been indexed yet.
+            last_checkpoint: u64::try_from(last_checkpoint).ok(),
+            first_transaction: lowest_available(PrunedEntity::Transactions),
+            first_object_history_checkpoint: lowest_available(PrunedEntity::ObjectsHistory),
+        })
+    }
+
+    fn update_watermark(
+        &self,
+        entity: PrunedEntity,
+        lowest_available: u64,
+    ) -> Result<(), IndexerError> {
+        transactional_blocking!(&self.blocking_cp, |conn| {
+            Watermark::update(conn, entity.as_str(), lowest_available)
+        })
+        .context(&format!(
+            "Failed updating {} watermark in PostgresDB",
+            entity.as_str()
+        ))?;
+        Ok(())
+    }
+
     fn get_latest_object_checkpoint_sequence_number(&self) -> Result<i64, IndexerError> {
         read_only_blocking!(&self.blocking_cp, |conn| {
             objects::dsl::objects
@@ -1959,6 +1998,19 @@ impl IndexerStore for PgIndexerStore {
             .await
     }
 
+    async fn get_available_range(&self) -> Result<AvailableRange, IndexerError> {
+        self.spawn_blocking(|this| this.get_available_range()).await
+    }
+
+    async fn update_watermark(
+        &self,
+        entity: PrunedEntity,
+        lowest_available: u64,
+    ) -> Result<(), IndexerError> {
+        self.spawn_blocking(move |this| this.update_watermark(entity, lowest_available))
+            .await
+    }
+
     async fn get_event(&self, id: EventID) -> Result<Event, IndexerError> {
         self.spawn_blocking(move |this| this.get_event(id)).await
     }
@@ -182,6 +182,7 @@ impl PgIndexerStoreV2 {
                             objects::df_name.eq(excluded(objects::df_name)),
                             objects::df_object_type.eq(excluded(objects::df_object_type)),
                             objects::df_object_id.eq(excluded(objects::df_object_id)),
+                            objects::object_type.eq(excluded(objects::object_type)),
                         ))
                         .execute(conn)
                         .map_err(IndexerError::from)
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 
 use sui_json_rpc_types::{
     BalanceChange, ObjectChange, SuiCommand, SuiTransactionBlock, SuiTransactionBlockDataAPI,
@@ -347,3 +348,47 @@ impl From<SuiTransactionBlockResponseWithOptions> for SuiTransactionBlockRespons
         }
     }
 }
+
+/// Range of data that the indexer can serve, after older data has been pruned. Requests for
+/// pruned data fail with this range attached, so that clients can tell data that has been pruned
+/// apart from data that never existed.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableRange {
+    /// Lowest checkpoint that has not been pruned.
+    pub first_checkpoint: CheckpointSequenceNumber,
+    /// Latest checkpoint that has been indexed, if any.
+    pub last_checkpoint: Option<CheckpointSequenceNumber>,
+    /// Lowest transaction sequence number that has not been pruned.
+    pub first_transaction: u64,
+    /// Lowest checkpoint that objects can be queried at.
+    pub first_object_history_checkpoint: CheckpointSequenceNumber,
+}
+
+impl AvailableRange {
+    pub fn check_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        if checkpoint < self.first_checkpoint {
+            return Err(IndexerError::DataPrunedError(
+                format!("Checkpoint {checkpoint} has been pruned"),
+                self.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn check_object_history(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Result<(), IndexerError> {
+        if checkpoint < self.first_object_history_checkpoint {
+            return Err(IndexerError::DataPrunedError(
+                format!("Object history at checkpoint {checkpoint} has been pruned"),
+                self.clone(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -216,6 +216,11 @@ pub fn owner_to_owner_info(owner: &Owner) -> (OwnerType, Option<SuiAddress>) {
         Owner::ObjectOwner(address) => (OwnerType::Object, Some(*address)),
         Owner::Shared { .. } => (OwnerType::Shared, None),
         Owner::Immutable => (OwnerType::Immutable, None),
+        // Consensus-owned objects are indexed as owned by their address, until the schema has an
+        // owner type for them
+        Owner::ConsensusV2 { authenticator, .. } => {
+            (OwnerType::Address, Some(*authenticator.as_single_owner()))
+        }
     }
 }
 
@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::RpcModule;
+use prometheus::Registry;
+use std::env;
+use sui_config::local_ip_utils;
+use sui_json_rpc::deprecation::{DEPRECATION_HEADER, SUNSET_HEADER};
+use sui_json_rpc::{
+    JsonRpcServerBuilder, SuiRpcModule, CLIENT_SDK_VERSION_HEADER,
+    CLIENT_TARGET_API_VERSION_HEADER,
+};
+use sui_open_rpc::Module;
+use sui_open_rpc_macros::open_rpc;
+
+const SUNSET: &str = "Wed, 01 Jan 2025 00:00:00 GMT";
+
+#[tokio::test]
+async fn test_deprecation_headers() {
+    env::set_var("DEPRECATED_METHODS_SUNSET", SUNSET);
+    env::set_var("DEPRECATED_METHODS_MIGRATION_GUIDE", "https://docs.sui.io/graphql");
+
+    let registry = Registry::new();
+    let mut builder = JsonRpcServerBuilder::new("1.5", &registry);
+    builder.register_module(TestApiModule).unwrap();
+
+    let address = local_ip_utils::new_local_tcp_socket_for_testing();
+    let _handle = builder.start(address, None, None).await.unwrap();
+    let url = format!("http://0.0.0.0:{}", address.port());
+
+    let call = |method: &'static str, version: Option<&'static str>| {
+        let mut request = reqwest::Client::new()
+            .post(&url)
+            .header("content-type", "application/json")
+            .header(CLIENT_SDK_VERSION_HEADER, "0.42.0")
+            .body(format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":["string"]}}"#
+            ));
+        if let Some(version) = version {
+            request = request.header(CLIENT_TARGET_API_VERSION_HEADER, version);
+        }
+        request.send()
+    };
+
+    // Methods that are not deprecated have no deprecation headers
+    let response = call("test_foo", None).await.unwrap();
+    assert!(response.headers().get(DEPRECATION_HEADER).is_none());
+    assert!(response.headers().get(SUNSET_HEADER).is_none());
+
+    let response = call("test_baz", None).await.unwrap();
+    let headers = response.headers();
+    assert_eq!(headers.get(DEPRECATION_HEADER).unwrap(), "true");
+    assert_eq!(headers.get(SUNSET_HEADER).unwrap(), SUNSET);
+    assert_eq!(
+        headers.get("link").unwrap(),
+        "<https://docs.sui.io/graphql>; rel=\"deprecation\""
+    );
+    assert!(headers
+        .get("warning")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .starts_with("299 - \"Method test_baz is deprecated"));
+
+    // Calls routed to methods serving older versions of the API are deprecated too
+    let response = call("test_foo", Some("1.5")).await.unwrap();
+    assert_eq!(response.headers().get(DEPRECATION_HEADER).unwrap(), "true");
+
+    let calls = registry
+        .gather()
+        .into_iter()
+        .find(|family| family.get_name() == "deprecated_rpc_requests")
+        .unwrap();
+    let mut counted: Vec<_> = calls
+        .get_metric()
+        .iter()
+        .map(|metric| {
+            let labels = metric.get_label();
+            let method = labels.iter().find(|l| l.get_name() == "method").unwrap();
+            let version = labels
+                .iter()
+                .find(|l| l.get_name() == "client_version")
+                .unwrap();
+            (
+                method.get_value().to_string(),
+                version.get_value().to_string(),
+                metric.get_counter().get_value() as u64,
+            )
+        })
+        .collect();
+    counted.sort();
+    assert_eq!(
+        counted,
+        vec![
+            ("test_baz".to_string(), "0.42.0".to_string(), 1),
+            ("test_foo_1_5".to_string(), "0.42.0".to_string(), 1),
+        ]
+    );
+}
+
+#[open_rpc(namespace = "test")]
+#[rpc(server, client, namespace = "test")]
+trait TestApi {
+    #[method(name = "foo")]
+    async fn foo(&self, some_str: String) -> RpcResult<String>;
+
+    #[method(name = "foo", version <= "1.5")]
+    async fn bar(&self, some_str: String) -> RpcResult<String>;
+
+    #[method(name = "baz", deprecated)]
+    async fn baz(&self, some_str: String) -> RpcResult<String>;
+}
+
+struct TestApiModule;
+
+#[async_trait]
+impl TestApiServer for TestApiModule {
+    async fn foo(&self, _some_str: String) -> RpcResult<String> {
+        Ok("Some string".into())
+    }
+
+    async fn bar(&self, _some_str: String) -> RpcResult<String> {
+        Ok("Some string from old method".into())
+    }
+
+    async fn baz(&self, _some_str: String) -> RpcResult<String> {
+        Ok("Some string from deprecated method".into())
+    }
+}
+
+impl SuiRpcModule for TestApiModule {
+    fn rpc(self) -> RpcModule<Self> {
+        self.into_rpc()
+    }
+    fn rpc_doc_module() -> Module {
+        TestApiOpenRpc::module_doc()
+    }
+}
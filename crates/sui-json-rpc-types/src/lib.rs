@@ -14,6 +14,7 @@ pub use sui_governance::*;
 pub use sui_move::*;
 pub use sui_object::*;
 pub use sui_protocol::*;
+pub use sui_rpc_error::*;
 pub use sui_transaction::*;
 use sui_types::base_types::ObjectID;
 use sui_types::dynamic_field::DynamicFieldInfo;
@@ -32,6 +33,7 @@ mod sui_governance;
 mod sui_move;
 mod sui_object;
 mod sui_protocol;
+mod sui_rpc_error;
 mod sui_transaction;
 
 pub type DynamicFieldPage = Page<DynamicFieldInfo, ObjectID>;
@@ -0,0 +1,74 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::sui_serde::BigInt;
+use sui_types::sui_serde::SequenceNumber as AsSequenceNumber;
+
+use crate::SuiObjectRef;
+
+/// Machine-readable description of why a request failed, sent in the `data` field of JSON-RPC
+/// error responses. The `code` field identifies the kind of failure, and the remaining fields
+/// carry the details clients need to decide whether and how to retry.
+#[serde_as]
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "code", rename_all = "camelCase")]
+pub enum SuiRpcErrorData {
+    /// The object does not exist.
+    #[serde(rename_all = "camelCase")]
+    ObjectNotFound { object_id: ObjectID },
+    /// The object exists, but the requested version is not available on this node because it has
+    /// been pruned. Another node with more history may be able to serve it.
+    #[serde(rename_all = "camelCase")]
+    ObjectVersionPruned {
+        object_id: ObjectID,
+        #[schemars(with = "AsSequenceNumber")]
+        #[serde_as(as = "AsSequenceNumber")]
+        version: SequenceNumber,
+    },
+    /// The object has been deleted or wrapped.
+    #[serde(rename_all = "camelCase")]
+    ObjectDeleted { object_ref: SuiObjectRef },
+    /// The gas coins provided do not hold enough SUI to cover the gas budget.
+    #[serde(rename_all = "camelCase")]
+    InsufficientGas {
+        #[schemars(with = "BigInt<u128>")]
+        #[serde_as(as = "BigInt<u128>")]
+        gas_balance: u128,
+        #[schemars(with = "BigInt<u128>")]
+        #[serde_as(as = "BigInt<u128>")]
+        needed_gas_amount: u128,
+    },
+    /// The gas budget is lower than the minimum budget required by the network.
+    #[serde(rename_all = "camelCase")]
+    GasBudgetTooLow {
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        gas_budget: u64,
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        min_budget: u64,
+    },
+    /// Owned objects used by the transaction are locked by other transactions, keyed by the
+    /// digest of the transaction holding the lock. The transaction cannot succeed until the end
+    /// of the epoch, unless one of the conflicting transactions is executed.
+    #[serde(rename_all = "camelCase")]
+    ObjectsLocked {
+        locked_objects: BTreeMap<TransactionDigest, Vec<SuiObjectRef>>,
+    },
+    /// The transaction was denied by the node's deny list.
+    #[serde(rename_all = "camelCase")]
+    TransactionDenied { reason: String },
+    /// The network is overloaded, and the request should be retried after `retry_after_ms`.
+    #[serde(rename_all = "camelCase")]
+    Overloaded {
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        retry_after_ms: u64,
+    },
+}
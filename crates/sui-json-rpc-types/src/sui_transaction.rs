@@ -12,9 +12,10 @@ use move_binary_format::CompiledModule;
 use move_bytecode_utils::module_cache::GetModule;
 use move_core_types::identifier::IdentStr;
 use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
-use move_core_types::value::MoveTypeLayout;
+use move_core_types::value::{MoveTypeLayout, MoveValue};
 use mysten_metrics::monitored_scope;
 use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::fmt::{self, Display, Formatter, Write};
@@ -23,9 +24,11 @@ use sui_types::authenticator_state::ActiveJwk;
 use sui_types::base_types::{
     EpochId, ObjectID, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
 };
+use sui_types::dev_inspect::ReturnValueDecoder;
 use sui_types::digests::{ObjectDigest, TransactionEventsDigest};
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
 use sui_types::error::{ExecutionError, SuiError, SuiResult};
+use sui_types::execution::ChildObjectAccessStats;
 use sui_types::execution_status::ExecutionStatus;
 use sui_types::gas::GasCostSummary;
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
@@ -793,6 +796,38 @@ pub struct DevInspectResults {
     /// Execution error from executing the transactions
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Child objects (e.g. dynamic fields) that the transaction looked up in storage
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub child_object_access: Option<SuiChildObjectAccess>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename = "ChildObjectAccess", rename_all = "camelCase")]
+pub struct SuiChildObjectAccess {
+    /// Number of distinct child objects looked up, including ones that did not exist. This is the
+    /// count limited by the object runtime's cached objects limit.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub num_accessed: u64,
+    /// Number of child objects that existed and were loaded.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub num_loaded: u64,
+    /// Total size in bytes of the loaded child objects.
+    #[schemars(with = "BigInt<u64>")]
+    #[serde_as(as = "BigInt<u64>")]
+    pub loaded_bytes: u64,
+}
+
+impl From<ChildObjectAccessStats> for SuiChildObjectAccess {
+    fn from(stats: ChildObjectAccessStats) -> Self {
+        Self {
+            num_accessed: stats.num_accessed,
+            num_loaded: stats.num_loaded,
+            loaded_bytes: stats.loaded_bytes,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -807,6 +842,57 @@ pub struct SuiExecutionResult {
     pub return_values: Vec<(Vec<u8>, SuiTypeTag)>,
 }
 
+impl SuiExecutionResult {
+    /// Decodes the values returned by the Move call this is the result of, checking them against
+    /// the signature of the function it called.
+    pub fn decode_return_values(&self, decoder: &ReturnValueDecoder) -> SuiResult<Vec<MoveValue>> {
+        if self.return_values.len() != decoder.len() {
+            return Err(SuiError::TypeError {
+                error: format!(
+                    "Expected {} return values, got {}",
+                    decoder.len(),
+                    self.return_values.len()
+                ),
+            });
+        }
+        self.return_values
+            .iter()
+            .enumerate()
+            .map(|(index, (bytes, type_))| {
+                decoder.decode_value(index, bytes, &return_value_type(index, type_)?)
+            })
+            .collect()
+    }
+
+    /// Decodes the `index`-th value returned by the Move call this is the result of into a `T`,
+    /// checking it against the signature of the function it called.
+    pub fn decode_return_value<T: DeserializeOwned>(
+        &self,
+        decoder: &ReturnValueDecoder,
+        index: usize,
+    ) -> SuiResult<T> {
+        let (bytes, type_) = self
+            .return_values
+            .get(index)
+            .ok_or_else(|| SuiError::TypeError {
+                error: format!(
+                    "Call returned {} values, there is no return value {index}",
+                    self.return_values.len()
+                ),
+            })?;
+        decoder.decode(index, bytes, &return_value_type(index, type_)?)
+    }
+}
+
+fn return_value_type(index: usize, type_: &SuiTypeTag) -> SuiResult<TypeTag> {
+    type_
+        .clone()
+        .try_into()
+        .map_err(|e: anyhow::Error| SuiError::TypeError {
+            error: format!("Invalid type of return value {index}: {e}"),
+        })
+}
+
 type ExecutionResult = (
     /*  mutable_reference_outputs */ Vec<(Argument, Vec<u8>, TypeTag)>,
     /*  return_values */ Vec<(Vec<u8>, TypeTag)>,
@@ -817,6 +903,7 @@ impl DevInspectResults {
         effects: TransactionEffects,
         events: TransactionEvents,
         return_values: Result<Vec<ExecutionResult>, ExecutionError>,
+        child_object_access: ChildObjectAccessStats,
         resolver: &impl GetModule,
     ) -> SuiResult<Self> {
         let tx_digest = *effects.transaction_digest();
@@ -851,8 +938,20 @@ impl DevInspectResults {
             events: SuiTransactionBlockEvents::try_from(events, tx_digest, None, resolver)?,
             results,
             error,
+            child_object_access: Some(child_object_access.into()),
         })
     }
+
+    /// The result of the `command`-th command of the transaction, or the error it failed with.
+    pub fn execution_result(&self, command: usize) -> SuiResult<&SuiExecutionResult> {
+        if let Some(error) = &self.error {
+            return Err(SuiError::ExecutionError(error.clone()));
+        }
+        self.results
+            .as_ref()
+            .and_then(|results| results.get(command))
+            .ok_or_else(|| SuiError::ExecutionError(format!("No result for command {command}")))
+    }
 }
 
 #[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
@@ -1239,7 +1338,9 @@ impl SuiProgrammableTransactionBlock {
             match command {
                 Command::MoveCall(c) => {
                     let id = ModuleId::new(c.package.into(), c.module.clone());
-                    let Some(types) = get_signature_types(id, c.function.as_ident_str(), module_cache) else {
+                    let Some(types) =
+                        get_signature_types(id, c.function.as_ident_str(), module_cache)
+                    else {
                         return result_types;
                     };
                     for (arg, type_) in c.arguments.iter().zip(types) {
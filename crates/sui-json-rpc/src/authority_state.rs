@@ -337,9 +337,9 @@ impl StateRead for AuthorityState {
         TransactionEffects,
         Option<ObjectID>,
     )> {
-        Ok(self
-            .dry_exec_transaction(transaction, transaction_digest)
-            .await?)
+        self.dry_exec_transaction(transaction, transaction_digest)
+            .await
+            .map_err(|e| pruned_object_version_error(self, e))
     }
 
     async fn dev_inspect_transaction_block(
@@ -348,9 +348,9 @@ impl StateRead for AuthorityState {
         transaction_kind: TransactionKind,
         gas_price: Option<u64>,
     ) -> StateReadResult<DevInspectResults> {
-        Ok(self
-            .dev_inspect_transaction_block(sender, transaction_kind, gas_price)
-            .await?)
+        self.dev_inspect_transaction_block(sender, transaction_kind, gas_price)
+            .await
+            .map_err(|e| pruned_object_version_error(self, e))
     }
 
     fn get_subscription_handler(&self) -> Arc<SubscriptionHandler> {
@@ -581,6 +581,9 @@ pub enum StateReadClientError {
     SuiError(#[from] SuiError),
     #[error(transparent)]
     UserInputError(#[from] UserInputError),
+    /// An object was not found at the requested version because the node has pruned it.
+    #[error(transparent)]
+    PrunedObjectVersion(UserInputError),
 }
 
 /// `StateReadError` is the error type for callers to work with.
@@ -611,6 +614,29 @@ impl From<SuiError> for StateReadError {
     }
 }
 
+/// Converts `e`, telling apart an object that was not found at the requested version because the
+/// node has pruned that version, from one that never had it.
+fn pruned_object_version_error(state: &AuthorityState, e: SuiError) -> StateReadError {
+    if let SuiError::UserInputError {
+        error:
+            error @ UserInputError::ObjectNotFound {
+                object_id,
+                version: Some(version),
+            },
+    } = &e
+    {
+        if let Ok(true) = state
+            .database
+            .is_object_version_pruned(*object_id, *version)
+        {
+            return StateReadError::Client(StateReadClientError::PrunedObjectVersion(
+                error.clone(),
+            ));
+        }
+    }
+    e.into()
+}
+
 impl From<UserInputError> for StateReadError {
     fn from(e: UserInputError) -> Self {
         StateReadError::Client(e.into())
@@ -18,6 +18,7 @@ use jsonrpsee::types::{ErrorObject, Id, InvalidRequest, Params, Request};
 use jsonrpsee::{core::server::rpc_module::Methods, server::logger::Logger};
 use serde_json::value::RawValue;
 
+use crate::deprecation::Deprecations;
 use crate::routing_layer::RpcRouter;
 use crate::CLIENT_TARGET_API_VERSION_HEADER;
 
@@ -32,13 +33,20 @@ pub struct JsonRpcService<L> {
     /// Registered server methods.
     methods: Methods,
     rpc_router: RpcRouter,
+    deprecations: Deprecations,
 }
 
 impl<L> JsonRpcService<L> {
-    pub fn new(methods: Methods, rpc_router: RpcRouter, logger: L) -> Self {
+    pub fn new(
+        methods: Methods,
+        rpc_router: RpcRouter,
+        deprecations: Deprecations,
+        logger: L,
+    ) -> Self {
         Self {
             methods,
             rpc_router,
+            deprecations,
             logger,
             id_provider: Arc::new(RandomIntegerIdProvider),
         }
@@ -106,26 +114,37 @@ pub async fn json_rpc_handler<L: Logger>(
     let api_version = headers
         .get(CLIENT_TARGET_API_VERSION_HEADER)
         .and_then(|h| h.to_str().ok());
-    let response = process_raw_request(&service, api_version, raw_request.get()).await;
+    let (response, method) = process_raw_request(&service, api_version, raw_request.get()).await;
 
-    ok_response(response.result)
+    let mut http_response = ok_response(response.result);
+    if let Some(method) = method {
+        service
+            .deprecations
+            .on_call(&method, &headers, http_response.headers_mut());
+    }
+    http_response
 }
 
+/// Process the request, returning the response and the name of the method the request was routed
+/// to, if it is a valid request.
 async fn process_raw_request<L: Logger>(
     service: &JsonRpcService<L>,
     api_version: Option<&str>,
     raw_request: &str,
-) -> MethodResponse {
+) -> (MethodResponse, Option<String>) {
     if let Ok(request) = serde_json::from_str::<Request>(raw_request) {
-        process_request(request, api_version, service.call_data()).await
+        let method = service.rpc_router.route(&request.method, api_version).to_string();
+        let response = process_request(request, api_version, service.call_data()).await;
+        (response, Some(method))
     } else if let Ok(_batch) = serde_json::from_str::<Vec<&RawValue>>(raw_request) {
-        MethodResponse::error(
+        let response = MethodResponse::error(
             Id::Null,
             ErrorObject::borrowed(BATCHES_NOT_SUPPORTED_CODE, &BATCHES_NOT_SUPPORTED_MSG, None),
-        )
+        );
+        (response, None)
     } else {
         let (id, code) = prepare_error(raw_request);
-        MethodResponse::error(id, ErrorObject::from(code))
+        (MethodResponse::error(id, ErrorObject::from(code)), None)
     }
 }
 
@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use hyper::header::{HeaderName, HeaderValue, InvalidHeaderValue, LINK, WARNING};
+use hyper::HeaderMap;
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+
+use crate::{CLIENT_SDK_TYPE_HEADER, CLIENT_SDK_VERSION_HEADER};
+
+/// Set to `true` on the responses of deprecated methods.
+/// `<https://datatracker.ietf.org/doc/draft-ietf-httpapi-deprecation-header/>`
+pub const DEPRECATION_HEADER: &str = "deprecation";
+/// The date after which deprecated methods may stop being served, as an HTTP-date.
+/// `<https://www.rfc-editor.org/rfc/rfc8594>`
+pub const SUNSET_HEADER: &str = "sunset";
+
+/// The deprecated methods, and how calls to them are reported: with headers on the response, for
+/// clients, and with a counter of calls by method and client version, for operators.
+#[derive(Debug, Clone)]
+pub struct Deprecations {
+    methods: HashSet<String>,
+    sunset: Option<HeaderValue>,
+    /// The `Link` header pointing to the migration guide.
+    link: Option<HeaderValue>,
+    calls: IntCounterVec,
+}
+
+impl Deprecations {
+    /// `sunset` is the HTTP-date after which the deprecated methods may be removed, and `link`
+    /// the URL of the migration guide, attached to the responses of deprecated methods if set.
+    pub fn new(
+        methods: impl IntoIterator<Item = String>,
+        sunset: Option<&str>,
+        link: Option<&str>,
+        registry: &Registry,
+    ) -> Result<Self, InvalidHeaderValue> {
+        Ok(Self {
+            methods: methods.into_iter().collect(),
+            sunset: sunset.map(HeaderValue::from_str).transpose()?,
+            link: link
+                .map(|url| HeaderValue::from_str(&format!("<{url}>; rel=\"deprecation\"")))
+                .transpose()?,
+            calls: register_int_counter_vec_with_registry!(
+                "deprecated_rpc_requests",
+                "Number of requests to deprecated methods, by method and client version",
+                &["method", "client_type", "client_version"],
+                registry,
+            )
+            .unwrap(),
+        })
+    }
+
+    pub fn is_deprecated(&self, method: &str) -> bool {
+        self.methods.contains(method)
+    }
+
+    /// Record a call to `method`, made with the `request` headers, and add the deprecation
+    /// headers to the `response` headers if the method is deprecated.
+    pub fn on_call(&self, method: &str, request: &HeaderMap, response: &mut HeaderMap) {
+        if !self.is_deprecated(method) {
+            return;
+        }
+
+        let header = |name: &str| {
+            request
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("Unknown")
+        };
+        self.calls
+            .with_label_values(&[
+                method,
+                header(CLIENT_SDK_TYPE_HEADER),
+                header(CLIENT_SDK_VERSION_HEADER),
+            ])
+            .inc();
+
+        response.insert(
+            HeaderName::from_static(DEPRECATION_HEADER),
+            HeaderValue::from_static("true"),
+        );
+        let mut warning = format!("Method {method} is deprecated");
+        if let Some(sunset) = &self.sunset {
+            response.insert(HeaderName::from_static(SUNSET_HEADER), sunset.clone());
+            if let Ok(sunset) = sunset.to_str() {
+                warning.push_str(&format!(" and may be removed after {sunset}"));
+            }
+        }
+        if let Some(link) = &self.link {
+            response.insert(LINK, link.clone());
+        }
+        // 299 is the code of miscellaneous persistent warnings, see RFC 7234, section 5.5.
+        if let Ok(warning) = HeaderValue::from_str(&format!("299 - \"{warning}\"")) {
+            response.insert(WARNING, warning);
+        }
+    }
+}
@@ -5,19 +5,26 @@ use fastcrypto::error::FastCryptoError;
 use hyper::header::InvalidHeaderValue;
 use itertools::Itertools;
 use jsonrpsee::core::Error as RpcError;
-use jsonrpsee::types::error::{CallError, INTERNAL_ERROR_CODE};
+use jsonrpsee::types::error::{
+    CallError, CALL_EXECUTION_FAILED_CODE, INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE,
+};
 use jsonrpsee::types::ErrorObject;
 use std::collections::BTreeMap;
+use sui_json_rpc_types::{SuiObjectRef, SuiRpcErrorData};
 use sui_types::error::{SuiError, SuiObjectResponseError, UserInputError};
 use sui_types::quorum_driver_types::QuorumDriverError;
 use thiserror::Error;
 use tokio::task::JoinError;
 
-use crate::authority_state::StateReadError;
+use crate::authority_state::{StateReadClientError, StateReadError};
 
 pub const TRANSIENT_ERROR_CODE: i32 = -32050;
 pub const TRANSACTION_EXECUTION_CLIENT_ERROR_CODE: i32 = -32002;
 
+/// How long clients are asked to wait before retrying a request that was rejected because the
+/// network is overloaded.
+pub const OVERLOAD_RETRY_AFTER_MS: u64 = 1_000;
+
 pub type RpcInterimResult<T = ()> = Result<T, Error>;
 
 #[derive(Debug, Error)]
@@ -78,27 +85,45 @@ impl From<SuiError> for Error {
 impl From<Error> for RpcError {
     fn from(e: Error) -> RpcError {
         match e {
-            Error::UserInputError(_) => RpcError::Call(CallError::InvalidParams(e.into())),
-            Error::SuiObjectResponseError(err) => match err {
-                SuiObjectResponseError::NotExists { .. }
-                | SuiObjectResponseError::DynamicFieldNotFound { .. }
-                | SuiObjectResponseError::Deleted { .. }
-                | SuiObjectResponseError::DisplayError { .. } => {
-                    RpcError::Call(CallError::InvalidParams(err.into()))
+            Error::UserInputError(err) => {
+                let data = user_input_error_data(&err);
+                error_with_data(INVALID_PARAMS_CODE, err.to_string(), data)
+            }
+            Error::SuiObjectResponseError(err) => {
+                let data = object_response_error_data(&err);
+                match err {
+                    SuiObjectResponseError::NotExists { .. }
+                    | SuiObjectResponseError::DynamicFieldNotFound { .. }
+                    | SuiObjectResponseError::Deleted { .. }
+                    | SuiObjectResponseError::DisplayError { .. } => {
+                        error_with_data(INVALID_PARAMS_CODE, err.to_string(), data)
+                    }
+                    _ => error_with_data(CALL_EXECUTION_FAILED_CODE, err.to_string(), data),
                 }
-                _ => RpcError::Call(CallError::Failed(err.into())),
-            },
-            Error::SuiRpcInputError(err) => RpcError::Call(CallError::InvalidParams(err.into())),
-            Error::SuiError(sui_error) => match sui_error {
-                SuiError::TransactionNotFound { .. }
-                | SuiError::TransactionsNotFound { .. }
-                | SuiError::TransactionEventsNotFound { .. } => {
-                    RpcError::Call(CallError::InvalidParams(sui_error.into()))
+            }
+            Error::SuiRpcInputError(err) => err.into(),
+            Error::SuiError(sui_error) => {
+                let data = sui_error_data(&sui_error);
+                match sui_error {
+                    SuiError::TransactionNotFound { .. }
+                    | SuiError::TransactionsNotFound { .. }
+                    | SuiError::TransactionEventsNotFound { .. } => {
+                        error_with_data(INVALID_PARAMS_CODE, sui_error.to_string(), data)
+                    }
+                    _ => error_with_data(CALL_EXECUTION_FAILED_CODE, sui_error.to_string(), data),
                 }
-                _ => RpcError::Call(CallError::Failed(sui_error.into())),
-            },
+            }
             Error::StateReadError(err) => match err {
-                StateReadError::Client(_) => RpcError::Call(CallError::InvalidParams(err.into())),
+                StateReadError::Client(client_error) => {
+                    let data = match &client_error {
+                        StateReadClientError::SuiError(err) => sui_error_data(err),
+                        StateReadClientError::UserInputError(err) => user_input_error_data(err),
+                        StateReadClientError::PrunedObjectVersion(err) => {
+                            pruned_object_version_data(err)
+                        }
+                    };
+                    error_with_data(INVALID_PARAMS_CODE, client_error.to_string(), data)
+                }
                 _ => {
                     let error_object = ErrorObject::owned(
                         jsonrpsee::types::error::INTERNAL_ERROR_CODE,
@@ -151,29 +176,39 @@ impl From<Error> for RpcError {
                         retried_tx_success
                     );
 
-                        let new_map = conflicting_txes
+                        let locked_objects = conflicting_txes
                             .into_iter()
                             .map(|(digest, (pairs, _))| {
                                 (
                                     digest,
-                                    pairs.into_iter().map(|(_, obj_ref)| obj_ref).collect(),
+                                    pairs
+                                        .into_iter()
+                                        .map(|(_, obj_ref)| SuiObjectRef::from(obj_ref))
+                                        .collect(),
                                 )
                             })
                             .collect::<BTreeMap<_, Vec<_>>>();
 
-                        let error_object = ErrorObject::owned(
+                        error_with_data(
                             TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
                             error_message,
-                            Some(new_map),
-                        );
-                        RpcError::Call(CallError::Custom(error_object))
+                            Some(SuiRpcErrorData::ObjectsLocked { locked_objects }),
+                        )
                     }
                     QuorumDriverError::NonRecoverableTransactionError { errors } => {
-                        let new_errors: Vec<String> = errors
+                        let errors: Vec<SuiError> = errors
                             .into_iter()
                             // sort by total stake, descending, so users see the most prominent one first
                             .sorted_by(|(_, a, _), (_, b, _)| b.cmp(a))
-                            .filter_map(|(err, _, _)| {
+                            .map(|(err, _, _)| err)
+                            .collect();
+
+                        // Report the details of the most prominent error that has any.
+                        let data = errors.iter().find_map(sui_error_data);
+
+                        let new_errors: Vec<String> = errors
+                            .into_iter()
+                            .filter_map(|err| {
                                 match &err {
                                     // Special handling of UserInputError:
                                     // ObjectNotFound and DependentPackageNotFound are considered
@@ -205,12 +240,7 @@ impl From<Error> for RpcError {
                         let error_list = new_errors.join(", ");
                         let error_msg = format!("Transaction execution failed due to issues with transaction inputs, please review the errors and try again: {}.", error_list);
 
-                        let error_object = ErrorObject::owned(
-                            TRANSACTION_EXECUTION_CLIENT_ERROR_CODE,
-                            error_msg,
-                            None::<()>,
-                        );
-                        RpcError::Call(CallError::Custom(error_object))
+                        error_with_data(TRANSACTION_EXECUTION_CLIENT_ERROR_CODE, error_msg, data)
                     }
                     QuorumDriverError::QuorumDriverInternalError(_) => {
                         let error_object = ErrorObject::owned(
@@ -220,11 +250,13 @@ impl From<Error> for RpcError {
                         );
                         RpcError::Call(CallError::Custom(error_object))
                     }
-                    QuorumDriverError::SystemOverload { .. } => {
-                        let error_object =
-                            ErrorObject::owned(TRANSIENT_ERROR_CODE, err.to_string(), None::<()>);
-                        RpcError::Call(CallError::Custom(error_object))
-                    }
+                    QuorumDriverError::SystemOverload { .. } => error_with_data(
+                        TRANSIENT_ERROR_CODE,
+                        err.to_string(),
+                        Some(SuiRpcErrorData::Overloaded {
+                            retry_after_ms: OVERLOAD_RETRY_AFTER_MS,
+                        }),
+                    ),
                 }
             }
             _ => RpcError::Call(CallError::Failed(e.into())),
@@ -273,7 +305,98 @@ pub enum SuiRpcInputError {
 
 impl From<SuiRpcInputError> for RpcError {
     fn from(e: SuiRpcInputError) -> Self {
-        RpcError::Call(CallError::InvalidParams(e.into()))
+        let data = match &e {
+            SuiRpcInputError::UserInputError(err) => user_input_error_data(err),
+            _ => None,
+        };
+        error_with_data(INVALID_PARAMS_CODE, e.to_string(), data)
+    }
+}
+
+/// Build an error response with the given code and message, and `data` describing the error in a
+/// machine-readable form, if there is any.
+fn error_with_data(
+    code: i32,
+    message: impl Into<String>,
+    data: Option<SuiRpcErrorData>,
+) -> RpcError {
+    RpcError::Call(CallError::Custom(ErrorObject::owned(code, message, data)))
+}
+
+fn sui_error_data(error: &SuiError) -> Option<SuiRpcErrorData> {
+    match error {
+        SuiError::UserInputError { error } => user_input_error_data(error),
+        SuiError::SuiObjectResponseError { error } => object_response_error_data(error),
+        SuiError::ObjectLockConflict {
+            obj_ref,
+            pending_transaction,
+        } => Some(SuiRpcErrorData::ObjectsLocked {
+            locked_objects: BTreeMap::from([(*pending_transaction, vec![(*obj_ref).into()])]),
+        }),
+        error if error.is_overload() => Some(SuiRpcErrorData::Overloaded {
+            retry_after_ms: OVERLOAD_RETRY_AFTER_MS,
+        }),
+        _ => None,
+    }
+}
+
+fn user_input_error_data(error: &UserInputError) -> Option<SuiRpcErrorData> {
+    Some(match error {
+        // Whether a missing version was pruned is only known by reading the state, which reports
+        // it with `StateReadClientError::PrunedObjectVersion`.
+        UserInputError::ObjectNotFound { object_id, .. } => SuiRpcErrorData::ObjectNotFound {
+            object_id: *object_id,
+        },
+        UserInputError::ObjectDeleted { object_ref } => SuiRpcErrorData::ObjectDeleted {
+            object_ref: (*object_ref).into(),
+        },
+        UserInputError::GasBalanceTooLow {
+            gas_balance,
+            needed_gas_amount,
+        } => SuiRpcErrorData::InsufficientGas {
+            gas_balance: *gas_balance,
+            needed_gas_amount: *needed_gas_amount,
+        },
+        UserInputError::GasBudgetTooLow {
+            gas_budget,
+            min_budget,
+        } => SuiRpcErrorData::GasBudgetTooLow {
+            gas_budget: *gas_budget,
+            min_budget: *min_budget,
+        },
+        UserInputError::TransactionDenied { error } => SuiRpcErrorData::TransactionDenied {
+            reason: error.clone(),
+        },
+        _ => return None,
+    })
+}
+
+fn pruned_object_version_data(error: &UserInputError) -> Option<SuiRpcErrorData> {
+    match error {
+        UserInputError::ObjectNotFound {
+            object_id,
+            version: Some(version),
+        } => Some(SuiRpcErrorData::ObjectVersionPruned {
+            object_id: *object_id,
+            version: *version,
+        }),
+        _ => user_input_error_data(error),
+    }
+}
+
+fn object_response_error_data(error: &SuiObjectResponseError) -> Option<SuiRpcErrorData> {
+    match error {
+        SuiObjectResponseError::NotExists { object_id } => Some(SuiRpcErrorData::ObjectNotFound {
+            object_id: *object_id,
+        }),
+        SuiObjectResponseError::Deleted {
+            object_id,
+            version,
+            digest,
+        } => Some(SuiRpcErrorData::ObjectDeleted {
+            object_ref: (*object_id, *version, *digest).into(),
+        }),
+        _ => None,
     }
 }
 
@@ -379,7 +502,7 @@ mod tests {
             let expected_message = expect!["Failed to sign transaction by a quorum of validators because of locked objects. Retried a conflicting transaction Some(TransactionDigest(11111111111111111111111111111111)), success: Some(true)"];
             expected_message.assert_eq(error_object.message());
             let expected_data = expect![[
-                r#"{"11111111111111111111111111111111":[["0x0000000000000000000000000000000000000000000000000000000000000000",0,"11111111111111111111111111111111"]]}"#
+                r#"{"code":"objectsLocked","lockedObjects":{"11111111111111111111111111111111":[{"objectId":"0x0000000000000000000000000000000000000000000000000000000000000000","version":0,"digest":"11111111111111111111111111111111"}]}}"#
             ]];
             let actual_data = error_object.data().unwrap().to_string();
             expected_data.assert_eq(&actual_data);
@@ -420,6 +543,11 @@ mod tests {
             let expected_message =
                 expect!["Transaction execution failed due to issues with transaction inputs, please review the errors and try again: Balance of gas object 10 is lower than the needed amount: 100., Object (0x0000000000000000000000000000000000000000000000000000000000000000, SequenceNumber(0), o#11111111111111111111111111111111) is not available for consumption, its current version: SequenceNumber(10).."];
             expected_message.assert_eq(error_object.message());
+            let expected_data = expect![[
+                r#"{"code":"insufficientGas","gasBalance":"10","neededGasAmount":"100"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
         }
 
         #[test]
@@ -452,6 +580,11 @@ mod tests {
             let expected_message =
                 expect!["Transaction execution failed due to issues with transaction inputs, please review the errors and try again: Could not find the referenced object 0x0000000000000000000000000000000000000000000000000000000000000000 at version None.."];
             expected_message.assert_eq(error_object.message());
+            let expected_data = expect![[
+                r#"{"code":"objectNotFound","objectId":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
         }
 
         #[test]
@@ -482,6 +615,113 @@ mod tests {
             expected_code.assert_eq(&error_object.code().to_string());
             let expected_message = expect!["Transaction is not processed because 10 of validators by stake are overloaded with certificates pending execution."];
             expected_message.assert_eq(error_object.message());
+            let expected_data = expect![[r#"{"code":"overloaded","retryAfterMs":"1000"}"#]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
+    }
+
+    mod match_user_input_error_tests {
+        use super::*;
+
+        #[test]
+        fn test_object_not_found() {
+            let rpc_error: RpcError = Error::UserInputError(UserInputError::ObjectNotFound {
+                object_id: ObjectID::ZERO,
+                version: None,
+            })
+            .into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_code = expect!["-32602"];
+            expected_code.assert_eq(&error_object.code().to_string());
+            let expected_data = expect![[
+                r#"{"code":"objectNotFound","objectId":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
+
+        #[test]
+        fn test_object_version_not_found() {
+            let rpc_error: RpcError = Error::UserInputError(UserInputError::ObjectNotFound {
+                object_id: ObjectID::ZERO,
+                version: Some(SequenceNumber::from_u64(5)),
+            })
+            .into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_code = expect!["-32602"];
+            expected_code.assert_eq(&error_object.code().to_string());
+            let expected_data = expect![[
+                r#"{"code":"objectNotFound","objectId":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
+
+        #[test]
+        fn test_object_version_pruned() {
+            let rpc_error: RpcError = Error::StateReadError(StateReadError::Client(
+                StateReadClientError::PrunedObjectVersion(UserInputError::ObjectNotFound {
+                    object_id: ObjectID::ZERO,
+                    version: Some(SequenceNumber::from_u64(5)),
+                }),
+            ))
+            .into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_code = expect!["-32602"];
+            expected_code.assert_eq(&error_object.code().to_string());
+            let expected_data = expect![[
+                r#"{"code":"objectVersionPruned","objectId":"0x0000000000000000000000000000000000000000000000000000000000000000","version":"5"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
+
+        #[test]
+        fn test_transaction_denied() {
+            let rpc_error: RpcError = Error::UserInputError(UserInputError::TransactionDenied {
+                error: "Access to input object is temporarily disabled".to_string(),
+            })
+            .into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_data = expect![[
+                r#"{"code":"transactionDenied","reason":"Access to input object is temporarily disabled"}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
+
+        #[test]
+        fn test_object_lock_conflict() {
+            let rpc_error: RpcError = Error::from(SuiError::ObjectLockConflict {
+                obj_ref: test_object_ref(),
+                pending_transaction: TransactionDigest::default(),
+            })
+            .into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_code = expect!["-32000"];
+            expected_code.assert_eq(&error_object.code().to_string());
+            let expected_data = expect![[
+                r#"{"code":"objectsLocked","lockedObjects":{"11111111111111111111111111111111":[{"objectId":"0x0000000000000000000000000000000000000000000000000000000000000000","version":0,"digest":"11111111111111111111111111111111"}]}}"#
+            ]];
+            let actual_data = error_object.data().unwrap().to_string();
+            expected_data.assert_eq(&actual_data);
+        }
+
+        #[test]
+        fn test_errors_without_data() {
+            let rpc_error: RpcError =
+                Error::UserInputError(UserInputError::MissingGasPayment).into();
+
+            let error_object: ErrorObjectOwned = rpc_error.into();
+            let expected_code = expect!["-32602"];
+            expected_code.assert_eq(&error_object.code().to_string());
+            assert!(error_object.data().is_none());
         }
     }
 }
@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::bail;
+use std::fmt::Display;
 use std::str::FromStr;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
+use sui_core::streamer::StreamItem;
 
 use async_trait::async_trait;
-use futures::Stream;
+use futures::{Stream, StreamExt};
 use jsonrpsee::core::error::SubscriptionClosed;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::types::SubscriptionResult;
@@ -58,17 +60,18 @@ const NAME_SERVICE_DEFAULT_REGISTRY: &str =
 const NAME_SERVICE_DEFAULT_REVERSE_REGISTRY: &str =
     "0x2fd099e17a292d2bc541df474f9fafa595653848cbabb2d7a4656ec786a1969f";
 
-pub fn spawn_subscription<S, T>(
+pub fn spawn_subscription<S, T, E>(
     mut sink: SubscriptionSink,
     rx: S,
     permit: Option<OwnedSemaphorePermit>,
 ) where
-    S: Stream<Item = T> + Unpin + Send + 'static,
+    S: Stream<Item = Result<T, E>> + Unpin + Send + 'static,
     T: Serialize,
+    E: Display,
 {
     spawn_monitored_task!(async move {
         let _permit = permit;
-        match sink.pipe_from_stream(rx).await {
+        match sink.pipe_from_try_stream(rx).await {
             SubscriptionClosed::Success => {
                 debug!("Subscription completed.");
                 sink.close(SubscriptionClosed::Success);
@@ -84,6 +87,18 @@ pub fn spawn_subscription<S, T>(
         };
     });
 }
+
+/// JSON-RPC subscriptions have no way of reporting a gap in their data, so a subscriber that falls
+/// behind is unsubscribed with an error instead, and can resubscribe and query for what it missed.
+fn fail_on_gap<T>(item: StreamItem<T>) -> Result<T, String> {
+    match item {
+        StreamItem::Data(data) => Ok(data),
+        StreamItem::Gap { missed } => Err(format!(
+            "Subscription fell behind, and missed {missed} items"
+        )),
+    }
+}
+
 const DEFAULT_MAX_SUBSCRIPTIONS: usize = 100;
 
 pub struct IndexerApi<R> {
@@ -305,7 +320,8 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             sink,
             self.state
                 .get_subscription_handler()
-                .subscribe_events(filter),
+                .subscribe_events(filter)
+                .map(fail_on_gap),
             Some(permit),
         );
         Ok(())
@@ -321,7 +337,8 @@ impl<R: ReadApiServer> IndexerApiServer for IndexerApi<R> {
             sink,
             self.state
                 .get_subscription_handler()
-                .subscribe_transactions(filter),
+                .subscribe_transactions(filter)
+                .map(fail_on_gap),
             Some(permit),
         );
         Ok(())
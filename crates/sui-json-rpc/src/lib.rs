@@ -21,6 +21,7 @@ pub use balance_changes::*;
 pub use object_changes::*;
 use sui_open_rpc::{Module, Project};
 
+use crate::deprecation::Deprecations;
 use crate::error::Error;
 use crate::metrics::MetricsLogger;
 use crate::routing_layer::RpcRouter;
@@ -30,6 +31,7 @@ pub mod authority_state;
 pub mod axum_router;
 mod balance_changes;
 pub mod coin_api;
+pub mod deprecation;
 pub mod error;
 pub mod governance_api;
 pub mod indexer_api;
@@ -160,6 +162,21 @@ impl JsonRpcServerBuilder {
                 "enabled"
             }
         );
+        // Methods serving older versions of the API are deprecated along with the methods marked
+        // as such.
+        let deprecated_methods = self
+            .rpc_doc
+            .deprecated_methods()
+            .map(String::from)
+            .chain(routing.values().map(|route| route.route_to.clone()))
+            .collect::<Vec<_>>();
+        let deprecations = Deprecations::new(
+            deprecated_methods,
+            env::var("DEPRECATED_METHODS_SUNSET").ok().as_deref(),
+            env::var("DEPRECATED_METHODS_MIGRATION_GUIDE").ok().as_deref(),
+            &self.registry,
+        )?;
+
         let rpc_router = RpcRouter::new(routing, disable_routing);
 
         let rpc_docs = self.rpc_doc.clone();
@@ -173,8 +190,12 @@ impl JsonRpcServerBuilder {
             .layer(Self::trace_layer())
             .layer(Self::cors()?);
 
-        let service =
-            crate::axum_router::JsonRpcService::new(module.into(), rpc_router, metrics_logger);
+        let service = crate::axum_router::JsonRpcService::new(
+            module.into(),
+            rpc_router,
+            deprecations,
+            metrics_logger,
+        );
 
         let mut router = axum::Router::new();
 
@@ -1,91 +1,123 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 
 use sui_json_rpc_types::ObjectChange;
-use sui_types::base_types::{ObjectID, ObjectRef, SequenceNumber, SuiAddress};
-use sui_types::effects::ObjectRemoveKind;
-use sui_types::object::Owner;
-use sui_types::storage::WriteKind;
+use sui_types::base_types::SuiAddress;
+use sui_types::effects::{ObjectChangeKind, TransactionEffects, TransactionEffectsAPI};
 
 use crate::ObjectProvider;
 
 pub async fn get_object_changes<P: ObjectProvider<Error = E>, E>(
     object_provider: &P,
     sender: SuiAddress,
-    modified_at_versions: Vec<(ObjectID, SequenceNumber)>,
-    all_changed_objects: Vec<(ObjectRef, Owner, WriteKind)>,
-    all_removed_objects: Vec<(ObjectRef, ObjectRemoveKind)>,
+    effects: &TransactionEffects,
 ) -> Result<Vec<ObjectChange>, E> {
-    let mut object_changes = vec![];
-
-    let modify_at_version = modified_at_versions.into_iter().collect::<BTreeMap<_, _>>();
+    // Mutated objects as they were before the transaction, to tell which were transferred. Objects
+    // whose previous versions have been pruned are reported as mutated.
+    let mutated: BTreeSet<_> = effects.mutated().into_iter().map(|(r, _)| r.0).collect();
+    let mut input_objects = vec![];
+    for (id, version) in effects.modified_at_versions() {
+        if mutated.contains(&id) {
+            input_objects.extend(
+                object_provider
+                    .find_object_lt_or_eq_version(&id, &version)
+                    .await?,
+            );
+        }
+    }
 
-    for ((object_id, version, digest), owner, kind) in all_changed_objects {
-        let o = object_provider.get_object(&object_id, &version).await?;
-        if let Some(type_) = o.type_() {
-            let object_type = type_.clone().into();
+    let mut object_changes = vec![];
+    for change in effects.object_changes(&input_objects) {
+        let (object_id, version, digest) = change.object_ref;
+        let object_change = match change.kind {
+            ObjectChangeKind::Created { owner } => {
+                let o = object_provider.get_object(&object_id, &version).await?;
+                if let Some(type_) = o.type_() {
+                    ObjectChange::Created {
+                        sender,
+                        owner,
+                        object_type: type_.clone().into(),
+                        object_id,
+                        version,
+                        digest,
+                    }
+                } else if let Some(p) = o.data.try_as_package() {
+                    ObjectChange::Published {
+                        package_id: p.id(),
+                        version: p.version(),
+                        digest,
+                        modules: p.serialized_module_map().keys().cloned().collect(),
+                    }
+                } else {
+                    continue;
+                }
+            }
 
-            match kind {
-                WriteKind::Mutate => object_changes.push(ObjectChange::Mutated {
+            ObjectChangeKind::Mutated {
+                owner,
+                previous_version,
+            } => {
+                let o = object_provider.get_object(&object_id, &version).await?;
+                let Some(type_) = o.type_() else {
+                    continue;
+                };
+                ObjectChange::Mutated {
                     sender,
                     owner,
-                    object_type,
+                    object_type: type_.clone().into(),
                     object_id,
                     version,
-                    // modify_at_version should always be available for mutated object
-                    previous_version: modify_at_version
-                        .get(&object_id)
-                        .cloned()
-                        .unwrap_or_default(),
+                    previous_version,
                     digest,
-                }),
-                WriteKind::Create => object_changes.push(ObjectChange::Created {
+                }
+            }
+
+            ObjectChangeKind::Transferred { new_owner, .. } => {
+                let o = object_provider.get_object(&object_id, &version).await?;
+                let Some(type_) = o.type_() else {
+                    continue;
+                };
+                ObjectChange::Transferred {
                     sender,
-                    owner,
-                    object_type,
+                    recipient: new_owner,
+                    object_type: type_.clone().into(),
                     object_id,
                     version,
                     digest,
-                }),
-                _ => {}
-            }
-        } else if let Some(p) = o.data.try_as_package() {
-            if kind == WriteKind::Create {
-                object_changes.push(ObjectChange::Published {
-                    package_id: p.id(),
-                    version: p.version(),
-                    digest,
-                    modules: p.serialized_module_map().keys().cloned().collect(),
-                })
+                }
             }
-        };
-    }
 
-    for ((id, version, _), kind) in all_removed_objects {
-        let o = object_provider
-            .find_object_lt_or_eq_version(&id, &version)
-            .await?;
-        if let Some(o) = o {
-            if let Some(type_) = o.type_() {
+            ObjectChangeKind::Deleted | ObjectChangeKind::Wrapped => {
+                let o = object_provider
+                    .find_object_lt_or_eq_version(&object_id, &version)
+                    .await?;
+                let Some(type_) = o.as_ref().and_then(|o| o.type_()) else {
+                    continue;
+                };
                 let object_type = type_.clone().into();
-                match kind {
-                    ObjectRemoveKind::Delete => object_changes.push(ObjectChange::Deleted {
+                if change.kind == ObjectChangeKind::Deleted {
+                    ObjectChange::Deleted {
                         sender,
                         object_type,
-                        object_id: id,
+                        object_id,
                         version,
-                    }),
-                    ObjectRemoveKind::Wrap => object_changes.push(ObjectChange::Wrapped {
+                    }
+                } else {
+                    ObjectChange::Wrapped {
                         sender,
                         object_type,
-                        object_id: id,
+                        object_id,
                         version,
-                    }),
+                    }
                 }
             }
+
+            // There are no object changes for unwrapped objects.
+            ObjectChangeKind::Unwrapped { .. } | ObjectChangeKind::UnwrappedThenDeleted => continue,
         };
+        object_changes.push(object_change);
     }
 
     Ok(object_changes)
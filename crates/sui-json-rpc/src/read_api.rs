@@ -432,9 +432,7 @@ impl ReadApi {
                         .intent_message()
                         .value
                         .sender(),
-                    effects.modified_at_versions(),
-                    effects.all_changed_objects(),
-                    effects.all_removed_objects(),
+                    effects,
                 ));
             }
             let results = join_all(results).await;
@@ -808,14 +806,7 @@ impl ReadApiServer for ReadApi {
                     (&temp_response.effects, &temp_response.transaction)
                 {
                     let sender = input.data().intent_message().value.sender();
-                    let object_changes = get_object_changes(
-                        &object_cache,
-                        sender,
-                        effects.modified_at_versions(),
-                        effects.all_changed_objects(),
-                        effects.all_removed_objects(),
-                    )
-                    .await;
+                    let object_changes = get_object_changes(&object_cache, sender, effects).await;
 
                     if let Ok(object_changes) = object_changes {
                         temp_response.object_changes = Some(object_changes);
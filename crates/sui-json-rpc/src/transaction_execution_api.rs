@@ -23,7 +23,6 @@ use sui_open_rpc::Module;
 use sui_types::base_types::SuiAddress;
 use sui_types::crypto::default_hash;
 use sui_types::digests::TransactionDigest;
-use sui_types::effects::TransactionEffectsAPI;
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
 };
@@ -181,16 +180,7 @@ impl TransactionExecutionApi {
             None
         };
         let object_changes = if opts.show_object_changes && is_executed_locally {
-            Some(
-                get_object_changes(
-                    &object_cache,
-                    sender,
-                    effects.effects.modified_at_versions(),
-                    effects.effects.all_changed_objects(),
-                    effects.effects.all_removed_objects(),
-                )
-                .await?,
-            )
+            Some(get_object_changes(&object_cache, sender, &effects.effects).await?)
         } else {
             None
         };
@@ -247,14 +237,8 @@ impl TransactionExecutionApi {
             mock_gas,
         )
         .await?;
-        let object_changes = get_object_changes(
-            &object_cache,
-            sender,
-            transaction_effects.modified_at_versions(),
-            transaction_effects.all_changed_objects(),
-            transaction_effects.all_removed_objects(),
-        )
-        .await?;
+        let object_changes =
+            get_object_changes(&object_cache, sender, &transaction_effects).await?;
 
         Ok(DryRunTransactionBlockResponse {
             effects: resp.effects,
@@ -0,0 +1,147 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::anyhow;
+use fastcrypto::encoding::{Base64, Encoding};
+use hmac::Hmac;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::Path;
+use sui_types::crypto::{EncodeDecodeBase64, SuiKeyPair};
+
+/// Version of the archive format written by `KeystoreArchive::encrypt`.
+pub const KEYSTORE_ARCHIVE_VERSION: u32 = 1;
+
+/// Number of PBKDF2 iterations used to derive the encryption key from the passphrase.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 600_000;
+
+const KDF_PBKDF2_HMAC_SHA256: &str = "pbkdf2-hmac-sha256";
+const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+/// The keys of a keystore, encrypted with a key derived from a passphrase, for moving them to
+/// another machine. The key derivation and cipher are recorded along with their parameters, so
+/// that archives written with older schemes can still be read when the defaults change.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeystoreArchive {
+    version: u32,
+    kdf: String,
+    kdf_iterations: u32,
+    /// Base64 encoded salt of the key derivation.
+    salt: String,
+    cipher: String,
+    /// Base64 encoded nonce of the cipher.
+    nonce: String,
+    /// Base64 encoded encryption of the JSON list of Base64 encoded `flag || privkey`, as in
+    /// sui.keystore.
+    ciphertext: String,
+}
+
+impl KeystoreArchive {
+    pub fn encrypt<'a>(
+        keys: impl IntoIterator<Item = &'a SuiKeyPair>,
+        passphrase: &str,
+    ) -> Result<Self, anyhow::Error> {
+        Self::encrypt_with_iterations(keys, passphrase, DEFAULT_KDF_ITERATIONS)
+    }
+
+    /// Like `encrypt`, with a custom number of key derivation iterations. Fewer iterations make
+    /// the passphrase easier to brute force, so this is meant for tests.
+    pub fn encrypt_with_iterations<'a>(
+        keys: impl IntoIterator<Item = &'a SuiKeyPair>,
+        passphrase: &str,
+        kdf_iterations: u32,
+    ) -> Result<Self, anyhow::Error> {
+        let keys = keys
+            .into_iter()
+            .map(EncodeDecodeBase64::encode_base64)
+            .collect::<Vec<_>>();
+        let plaintext = serde_json::to_vec(&keys)?;
+
+        let salt: [u8; SALT_LENGTH] = rand::random();
+        let nonce: [u8; NONCE_LENGTH] = rand::random();
+        let cipher = cipher(passphrase, &salt, kdf_iterations);
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|e| anyhow!("Cannot encrypt keystore archive: {e}"))?;
+
+        Ok(Self {
+            version: KEYSTORE_ARCHIVE_VERSION,
+            kdf: KDF_PBKDF2_HMAC_SHA256.to_string(),
+            kdf_iterations,
+            salt: Base64::encode(salt),
+            cipher: CIPHER_AES_256_GCM.to_string(),
+            nonce: Base64::encode(nonce),
+            ciphertext: Base64::encode(ciphertext),
+        })
+    }
+
+    /// The keys in the archive. Fails if the passphrase is not the one the archive was encrypted
+    /// with, or if the archive was modified.
+    pub fn decrypt(&self, passphrase: &str) -> Result<Vec<SuiKeyPair>, anyhow::Error> {
+        if self.version != KEYSTORE_ARCHIVE_VERSION {
+            return Err(anyhow!(
+                "Unsupported keystore archive version {}, expected {}",
+                self.version,
+                KEYSTORE_ARCHIVE_VERSION
+            ));
+        }
+        if self.kdf != KDF_PBKDF2_HMAC_SHA256 || self.cipher != CIPHER_AES_256_GCM {
+            return Err(anyhow!(
+                "Unsupported keystore archive encryption {} with {}",
+                self.cipher,
+                self.kdf
+            ));
+        }
+
+        let salt = decode_base64_field("salt", &self.salt)?;
+        let nonce = decode_base64_field("nonce", &self.nonce)?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(anyhow!(
+                "Invalid keystore archive nonce length {}",
+                nonce.len()
+            ));
+        }
+        let ciphertext = decode_base64_field("ciphertext", &self.ciphertext)?;
+
+        let cipher = cipher(passphrase, &salt, self.kdf_iterations);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow!("Cannot decrypt keystore archive, is the passphrase correct?"))?;
+        let keys: Vec<String> = serde_json::from_slice(&plaintext)
+            .map_err(|e| anyhow!("Invalid keystore archive content: {e}"))?;
+        keys.iter()
+            .map(|key| {
+                SuiKeyPair::decode_base64(key)
+                    .map_err(|e| anyhow!("Invalid key in keystore archive: {e}"))
+            })
+            .collect()
+    }
+
+    pub fn read(path: &Path) -> Result<Self, anyhow::Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Cannot read keystore archive at {:?}: {e}", path))?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid keystore archive at {:?}: {e}", path))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .map_err(|e| anyhow!("Cannot write keystore archive to {:?}: {e}", path))
+    }
+}
+
+fn cipher(passphrase: &str, salt: &[u8], kdf_iterations: u32) -> Aes256Gcm {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(passphrase.as_bytes(), salt, kdf_iterations, &mut key);
+    Aes256Gcm::new(&key.into())
+}
+
+fn decode_base64_field(name: &str, value: &str) -> Result<Vec<u8>, anyhow::Error> {
+    Base64::decode(value).map_err(|e| anyhow!("Invalid keystore archive {name}: {e}"))
+}
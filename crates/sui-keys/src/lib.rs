@@ -4,3 +4,4 @@
 pub mod key_derive;
 pub mod keypair_file;
 pub mod keystore;
+pub mod keystore_archive;
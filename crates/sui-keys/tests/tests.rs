@@ -6,6 +6,7 @@ use fastcrypto::hash::HashFunction;
 use tempfile::TempDir;
 
 use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_keys::keystore_archive::KeystoreArchive;
 use sui_types::crypto::{DefaultHash, SignatureScheme, SuiSignatureInner};
 use sui_types::{
     base_types::{SuiAddress, SUI_ADDRESS_LENGTH},
@@ -69,3 +70,31 @@ fn keystore_display_test() -> Result<(), anyhow::Error> {
     assert!(!keystore.to_string().contains("keys:"));
     Ok(())
 }
+
+#[test]
+fn keystore_archive_test() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let keystore_path = temp_dir.path().join("sui.keystore");
+    let mut keystore = Keystore::from(FileBasedKeystore::new(&keystore_path).unwrap());
+    keystore.generate_and_add_new_key(SignatureScheme::ED25519, None, None)?;
+    keystore.generate_and_add_new_key(SignatureScheme::Secp256k1, None, None)?;
+
+    let keys = keystore
+        .addresses()
+        .iter()
+        .map(|address| keystore.get_key(address))
+        .collect::<Result<Vec<_>, _>>()?;
+    let archive = KeystoreArchive::encrypt_with_iterations(keys, "passphrase", 1000)?;
+    let archive_path = temp_dir.path().join("sui.keystore.archive");
+    archive.write(&archive_path)?;
+
+    let archive = KeystoreArchive::read(&archive_path)?;
+    assert!(archive.decrypt("wrong passphrase").is_err());
+    let addresses = archive
+        .decrypt("passphrase")?
+        .iter()
+        .map(|key| SuiAddress::from(&key.public()))
+        .collect::<Vec<_>>();
+    assert_eq!(addresses, keystore.addresses());
+    Ok(())
+}
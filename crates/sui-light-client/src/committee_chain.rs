@@ -0,0 +1,158 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::committee_chain;
+use sui_types::error::SuiError;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointSummary, VerifiedCheckpoint,
+};
+
+use crate::{LightClientError, LightClientResult};
+
+/// Everything a light client needs to remember between runs: the committees it has verified so
+/// far, and the latest checkpoint it has seen.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeChainState {
+    /// Verified committees. The committee of the first epoch is trusted, every following one was
+    /// handed off by an end of epoch checkpoint certified by its predecessor.
+    pub committees: committee_chain::CommitteeChain,
+    /// Latest checkpoint verified so far, if any.
+    pub latest_checkpoint: Option<CheckpointSummary>,
+}
+
+/// Chain of committee handoffs, starting from a trusted committee. Checkpoint summaries from any
+/// epoch whose committee is in the chain can be verified, and verifying the last checkpoint of
+/// the latest epoch extends the chain with the next committee.
+pub struct CommitteeChain {
+    state: CommitteeChainState,
+}
+
+impl CommitteeChain {
+    /// Starts the chain from the committee in the genesis of the network.
+    pub fn new_from_genesis(genesis_committee: Committee) -> Self {
+        Self::new_from_trusted_committee(genesis_committee)
+    }
+
+    /// Starts the chain from a committee of a recent epoch, trusted out of band, so that the
+    /// client does not need to follow every handoff since genesis.
+    pub fn new_from_trusted_committee(committee: Committee) -> Self {
+        Self {
+            state: CommitteeChainState {
+                committees: committee_chain::CommitteeChain::new(committee),
+                latest_checkpoint: None,
+            },
+        }
+    }
+
+    pub fn state(&self) -> &CommitteeChainState {
+        &self.state
+    }
+
+    /// Committee of the latest epoch in the chain.
+    pub fn latest_committee(&self) -> &Committee {
+        self.state.committees.latest_committee()
+    }
+
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        self.state.committees.committee(epoch)
+    }
+
+    pub fn latest_checkpoint(&self) -> Option<&CheckpointSummary> {
+        self.state.latest_checkpoint.as_ref()
+    }
+
+    /// Checks that `checkpoint` is certified by the committee of its epoch, without recording
+    /// it.
+    pub fn verify_checkpoint(
+        &self,
+        checkpoint: &CertifiedCheckpointSummary,
+    ) -> LightClientResult<()> {
+        self.state
+            .committees
+            .verify_checkpoint(checkpoint)
+            .map_err(|error| verification_error(checkpoint, error))
+    }
+
+    /// Verifies `checkpoint` and records it. If it is the last checkpoint of the latest epoch in
+    /// the chain, the committee it hands off to is added to the chain.
+    pub fn add_checkpoint(
+        &mut self,
+        checkpoint: CertifiedCheckpointSummary,
+    ) -> LightClientResult<VerifiedCheckpoint> {
+        self.state
+            .committees
+            .add_checkpoint(&checkpoint)
+            .map_err(|error| verification_error(&checkpoint, error))?;
+
+        let is_latest = self
+            .state
+            .latest_checkpoint
+            .as_ref()
+            .map_or(true, |latest| {
+                latest.sequence_number < *checkpoint.sequence_number()
+            });
+        if is_latest {
+            self.state.latest_checkpoint = Some(checkpoint.data().clone());
+        }
+
+        Ok(VerifiedCheckpoint::new_unchecked(checkpoint))
+    }
+
+    /// Follows the handoffs in `end_of_epoch_checkpoints`, the last checkpoints of consecutive
+    /// epochs starting with the latest epoch in the chain.
+    pub fn add_end_of_epoch_checkpoints(
+        &mut self,
+        end_of_epoch_checkpoints: impl IntoIterator<Item = CertifiedCheckpointSummary>,
+    ) -> LightClientResult<()> {
+        for checkpoint in end_of_epoch_checkpoints {
+            if checkpoint.epoch() != self.latest_committee().epoch {
+                return Err(LightClientError::UnknownEpoch(checkpoint.epoch()));
+            }
+            if checkpoint.next_epoch_committee().is_none() {
+                return Err(LightClientError::NotEndOfEpoch(
+                    *checkpoint.sequence_number(),
+                ));
+            }
+            self.add_checkpoint(checkpoint)?;
+        }
+        Ok(())
+    }
+
+    /// Persists the verified state to `path`, replacing it atomically.
+    pub fn save(&self, path: &Path) -> LightClientResult<()> {
+        let bytes = bcs::to_bytes(&self.state)?;
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, bytes)?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Restores a chain saved with [`CommitteeChain::save`].
+    pub fn load(path: &Path) -> LightClientResult<Self> {
+        let state: CommitteeChainState = bcs::from_bytes(&std::fs::read(path)?)?;
+        if state.committees.committees().next().is_none() {
+            return Err(LightClientError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Committee chain state has no committees",
+            )));
+        }
+        Ok(Self { state })
+    }
+}
+
+fn verification_error(
+    checkpoint: &CertifiedCheckpointSummary,
+    error: SuiError,
+) -> LightClientError {
+    match error {
+        SuiError::MissingCommitteeAtEpoch(epoch) => LightClientError::UnknownEpoch(epoch),
+        error => LightClientError::InvalidSignatures {
+            sequence_number: *checkpoint.sequence_number(),
+            error,
+        },
+    }
+}
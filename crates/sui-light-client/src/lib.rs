@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Building blocks for Sui light clients: following the chain of committee handoffs from a
+//! trusted starting point, verifying checkpoint summaries against it, and checking that objects
+//! are included in certified checkpoints, without trusting the full node that serves the data.
+
+use sui_types::committee::EpochId;
+use sui_types::error::SuiError;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+mod committee_chain;
+mod object_proof;
+
+pub use committee_chain::{CommitteeChain, CommitteeChainState};
+pub use object_proof::ObjectInclusionProof;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, thiserror::Error)]
+pub enum LightClientError {
+    #[error("No verified committee for epoch {0}")]
+    UnknownEpoch(EpochId),
+    #[error("Invalid signatures on checkpoint {sequence_number}: {error}")]
+    InvalidSignatures {
+        sequence_number: CheckpointSequenceNumber,
+        error: SuiError,
+    },
+    #[error("Checkpoint {0} is not the last checkpoint of its epoch")]
+    NotEndOfEpoch(CheckpointSequenceNumber),
+    #[error("Invalid inclusion proof: {0}")]
+    InvalidProof(String),
+    #[error("Failed to persist committee chain: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to (de)serialize committee chain: {0}")]
+    Serialization(#[from] bcs::Error),
+}
+
+pub type LightClientResult<T> = Result<T, LightClientError>;
@@ -0,0 +1,67 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use sui_types::base_types::ObjectRef;
+use sui_types::effects::{CompactTransactionEffects, TransactionEffectsAPI};
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use sui_types::object::Object;
+
+use crate::{CommitteeChain, LightClientError, LightClientResult};
+
+/// Proof that `object` was written by a transaction in a certified checkpoint: the checkpoint
+/// commits to its contents, the contents commit to the effects of the transaction, and the
+/// effects commit to the reference of every object the transaction wrote.
+///
+/// The effects are sent in their compact form, and converted back to the canonical form, whose
+/// digest the checkpoint contents commit to, during verification.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ObjectInclusionProof {
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    pub effects: CompactTransactionEffects,
+    pub object: Object,
+}
+
+impl ObjectInclusionProof {
+    /// Verifies the proof against the committees in `chain`, returning the reference of the
+    /// object it proves.
+    pub fn verify(&self, chain: &CommitteeChain) -> LightClientResult<ObjectRef> {
+        chain.verify_checkpoint(&self.checkpoint)?;
+
+        if *self.contents.digest() != self.checkpoint.content_digest {
+            return Err(LightClientError::InvalidProof(format!(
+                "Contents do not match checkpoint {}",
+                self.checkpoint.sequence_number()
+            )));
+        }
+
+        let effects = self
+            .effects
+            .to_effects()
+            .map_err(|e| LightClientError::InvalidProof(e.to_string()))?;
+        let digests = effects.execution_digests();
+        if !self.contents.iter().any(|d| *d == digests) {
+            return Err(LightClientError::InvalidProof(format!(
+                "Checkpoint {} does not include transaction {} with effects {}",
+                self.checkpoint.sequence_number(),
+                digests.transaction,
+                digests.effects
+            )));
+        }
+
+        let object_ref = self.object.compute_object_reference();
+        if !effects
+            .all_changed_objects()
+            .iter()
+            .any(|(written, _, _)| *written == object_ref)
+        {
+            return Err(LightClientError::InvalidProof(format!(
+                "Object {:?} is not written by transaction {}",
+                object_ref, digests.transaction
+            )));
+        }
+
+        Ok(object_ref)
+    }
+}
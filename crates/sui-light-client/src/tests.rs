@@ -0,0 +1,168 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_protocol_config::ProtocolVersion;
+use sui_types::base_types::{
+    random_object_ref, ExecutionDigests, ObjectID, SuiAddress, TransactionDigest,
+};
+use sui_types::committee::Committee;
+use sui_types::effects::{CompactTransactionEffects, TransactionEffects, TransactionEffectsAPI};
+use sui_types::execution_status::ExecutionStatus;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+use sui_types::object::{Object, Owner};
+use sui_types::utils::{
+    make_epoch_checkpoint, make_epoch_checkpoint_with_contents, make_test_epochs,
+};
+
+use crate::{CommitteeChain, LightClientError, ObjectInclusionProof};
+
+#[test]
+fn test_follow_committee_handoffs() {
+    let epochs = make_test_epochs(3);
+    let mut chain = CommitteeChain::new_from_genesis(epochs[0].committee.clone());
+
+    // Checkpoints of later epochs cannot be verified before the handoff.
+    let later = make_epoch_checkpoint(&epochs[1], 20, None);
+    assert!(matches!(
+        chain.verify_checkpoint(&later),
+        Err(LightClientError::UnknownEpoch(1))
+    ));
+
+    chain
+        .add_end_of_epoch_checkpoints([
+            make_epoch_checkpoint(&epochs[0], 10, Some(&epochs[1])),
+            make_epoch_checkpoint(&epochs[1], 30, Some(&epochs[2])),
+        ])
+        .unwrap();
+    assert_eq!(chain.latest_committee().epoch, 2);
+    assert_eq!(chain.latest_checkpoint().unwrap().sequence_number, 30);
+
+    chain.verify_checkpoint(&later).unwrap();
+    chain
+        .add_checkpoint(make_epoch_checkpoint(&epochs[2], 40, None))
+        .unwrap();
+    assert_eq!(chain.latest_checkpoint().unwrap().sequence_number, 40);
+}
+
+#[test]
+fn test_reject_invalid_checkpoints() {
+    let epochs = make_test_epochs(2);
+    let mut chain = CommitteeChain::new_from_genesis(epochs[0].committee.clone());
+
+    // Claims to be from epoch 0, but is signed by the committee of epoch 1.
+    let forged = CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+        make_epoch_checkpoint(&epochs[0], 10, Some(&epochs[1])).into_data(),
+        &epochs[1].keys,
+        &Committee::new(
+            0,
+            epochs[1].committee.voting_rights.iter().cloned().collect(),
+        ),
+    );
+    assert!(matches!(
+        chain.add_checkpoint(forged),
+        Err(LightClientError::InvalidSignatures {
+            sequence_number: 10,
+            ..
+        })
+    ));
+
+    assert!(matches!(
+        chain.add_end_of_epoch_checkpoints([make_epoch_checkpoint(&epochs[0], 5, None)]),
+        Err(LightClientError::NotEndOfEpoch(5))
+    ));
+    assert_eq!(chain.latest_committee().epoch, 0);
+    assert!(chain.latest_checkpoint().is_none());
+}
+
+#[test]
+fn test_persist_committee_chain() {
+    let epochs = make_test_epochs(2);
+    let mut chain = CommitteeChain::new_from_genesis(epochs[0].committee.clone());
+    chain
+        .add_checkpoint(make_epoch_checkpoint(&epochs[0], 10, Some(&epochs[1])))
+        .unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("committee_chain");
+    chain.save(&path).unwrap();
+
+    let restored = CommitteeChain::load(&path).unwrap();
+    assert_eq!(restored.state(), chain.state());
+    restored
+        .verify_checkpoint(&make_epoch_checkpoint(&epochs[1], 11, None))
+        .unwrap();
+}
+
+#[test]
+fn test_object_inclusion_proof() {
+    let epochs = make_test_epochs(1);
+    let chain = CommitteeChain::new_from_genesis(epochs[0].committee.clone());
+
+    let owner = SuiAddress::random_for_testing_only();
+    let object = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+    let effects = TransactionEffects::new_from_execution(
+        ProtocolVersion::MIN,
+        ExecutionStatus::Success,
+        /* executed_epoch */ 0,
+        GasCostSummary::default(),
+        /* modified_at_versions */ vec![],
+        /* shared_objects */ vec![],
+        TransactionDigest::random(),
+        /* created */
+        vec![(
+            object.compute_object_reference(),
+            Owner::AddressOwner(owner),
+        )],
+        /* mutated */ vec![],
+        /* unwrapped */ vec![],
+        /* deleted */ vec![],
+        /* unwrapped_then_deleted */ vec![],
+        /* wrapped */ vec![],
+        /* gas_object */ (random_object_ref(), Owner::AddressOwner(owner)),
+        /* events_digest */ None,
+        /* dependencies */ vec![],
+    );
+    let contents = CheckpointContents::new_with_causally_ordered_transactions([
+        ExecutionDigests::random(),
+        effects.execution_digests(),
+    ]);
+
+    let proof = ObjectInclusionProof {
+        checkpoint: make_epoch_checkpoint_with_contents(&epochs[0], 1, &contents, None),
+        contents,
+        effects: CompactTransactionEffects::new(&effects),
+        object: object.clone(),
+    };
+    assert_eq!(
+        proof.verify(&chain).unwrap(),
+        object.compute_object_reference()
+    );
+
+    // An object the transaction did not write.
+    let mut other_object = proof.clone();
+    other_object.object = Object::with_id_owner_for_testing(ObjectID::random(), owner);
+    assert!(matches!(
+        other_object.verify(&chain),
+        Err(LightClientError::InvalidProof(_))
+    ));
+
+    // Effects of a transaction that is not in the checkpoint.
+    let mut other_effects = proof.clone();
+    let mut modified = effects.clone();
+    *modified.transaction_digest_mut_for_testing() = TransactionDigest::random();
+    other_effects.effects = CompactTransactionEffects::new(&modified);
+    assert!(matches!(
+        other_effects.verify(&chain),
+        Err(LightClientError::InvalidProof(_))
+    ));
+
+    // Contents that the checkpoint does not commit to.
+    let mut other_contents = proof;
+    other_contents.contents =
+        CheckpointContents::new_with_causally_ordered_transactions([effects.execution_digests()]);
+    assert!(matches!(
+        other_contents.verify(&chain),
+        Err(LightClientError::InvalidProof(_))
+    ));
+}
@@ -1,25 +1,77 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use move_cli::base::new;
-use std::path::PathBuf;
+use move_package::source_package::layout::SourcePackageLayout;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 const SUI_PKG_NAME: &str = "Sui";
 
 // Use testnet by default. Probably want to add options to make this configurable later
 const SUI_PKG_PATH: &str = "{ git = \"https://github.com/MystenLabs/sui.git\", subdir = \"crates/sui-framework/packages/sui-framework\", rev = \"framework/testnet\" }";
 
+/// Named address of the template packages, replaced by the name of the new package.
+const TEMPLATE_ADDR_NAME: &str = "template";
+
+/// Where the CI workflow is written in new packages created from a template.
+const CI_WORKFLOW_PATH: &str = ".github/workflows/move.yml";
+
+const CI_WORKFLOW: &str = include_str!("../templates/ci.yml");
+
 #[derive(Parser)]
 #[group(id = "sui-move-new")]
 pub struct New {
     #[clap(flatten)]
     pub new: new::New,
+
+    /// Create the package from an example, with its tests and a CI workflow building and testing
+    /// it.
+    #[clap(long, value_enum)]
+    pub template: Option<Template>,
+}
+
+/// Example packages that new packages can be created from. They are in `crates/sui-move/templates`,
+/// where they are built and tested as packages of their own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Template {
+    /// An NFT that anyone can mint
+    Nft,
+    /// A fungible token, minted by the publisher of the package
+    Coin,
+    /// A pool of two coin types to swap against
+    Defi,
+    /// A king of the hill game, paid in SUI
+    Game,
+}
+
+impl Template {
+    /// The Move sources and tests of the template, by path in the package.
+    pub fn files(&self) -> &'static [(&'static str, &'static str)] {
+        macro_rules! files {
+            ($template:literal, [$($file:literal),* $(,)?]) => {
+                &[$(
+                    ($file, include_str!(concat!("../templates/", $template, "/", $file))),
+                )*]
+            };
+        }
+
+        match self {
+            Template::Nft => files!("nft", ["sources/nft.move", "tests/nft_tests.move"]),
+            Template::Coin => files!("coin", ["sources/my_coin.move", "tests/my_coin_tests.move"]),
+            Template::Defi => files!("defi", ["sources/pool.move", "tests/pool_tests.move"]),
+            Template::Game => files!("game", ["sources/hill.move", "tests/hill_tests.move"]),
+        }
+    }
 }
 
 impl New {
     pub fn execute(self, path: Option<PathBuf>) -> anyhow::Result<()> {
         let name = &self.new.name.to_lowercase();
+        let package_path = path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&self.new.name));
         self.new.execute(
             path,
             "0.0.1",
@@ -27,6 +79,25 @@ impl New {
             [(name, "0x0")],
             "",
         )?;
+        if let Some(template) = self.template {
+            write_template(&package_path, name, template)?;
+        }
         Ok(())
     }
 }
+
+fn write_template(path: &Path, name: &str, template: Template) -> anyhow::Result<()> {
+    fs::create_dir_all(path.join(SourcePackageLayout::Tests.path()))?;
+    let template_addr = format!("{TEMPLATE_ADDR_NAME}::");
+    let addr = format!("{name}::");
+    for (file, contents) in template.files() {
+        fs::write(path.join(file), contents.replace(&template_addr, &addr))?;
+    }
+
+    let ci_workflow_path = path.join(CI_WORKFLOW_PATH);
+    if let Some(parent) = ci_workflow_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(ci_workflow_path, CI_WORKFLOW)?;
+    Ok(())
+}
@@ -2,22 +2,36 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::build;
+use anyhow::{bail, Context};
 use clap::Parser;
+use colored::Colorize;
 use move_cli::base::{
     self,
     test::{self, UnitTestResult},
 };
-use move_package::BuildConfig;
+use move_package::{
+    source_package::{
+        layout::SourcePackageLayout,
+        manifest_parser::{parse_move_manifest_string, parse_source_manifest},
+        parsed_manifest::SourceManifest,
+    },
+    BuildConfig,
+};
 use move_unit_test::{extensions::set_extension_hook, UnitTestingConfig};
 use move_vm_runtime::native_extensions::NativeContextExtensions;
 use once_cell::sync::Lazy;
-use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use sui_move_natives::{object_runtime::ObjectRuntime, NativesCostTable};
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{
     base_types::{ObjectID, SequenceNumber},
     error::SuiResult,
-    gas_model::tables::initial_cost_schedule_for_unit_tests,
+    gas_model::tables::{cost_schedule_for_unit_tests, initial_cost_schedule_for_unit_tests},
     metrics::LimitsMetrics,
     object::Object,
     storage::ChildObjectResolver,
@@ -26,6 +40,9 @@ use sui_types::{
 // Move unit tests will halt after executing this many steps. This is a protection to avoid divergence
 const MAX_UNIT_TEST_INSTRUCTIONS: u64 = 1_000_000;
 
+/// Section of the package manifest holding the gas thresholds checked by `--gas-report`.
+pub const GAS_THRESHOLDS_SECTION: &str = "gas-thresholds";
+
 #[derive(Parser)]
 #[group(id = "sui-move-test")]
 pub struct Test {
@@ -34,6 +51,11 @@ pub struct Test {
     /// If `true`, enable linters
     #[clap(long, global = true)]
     pub lint: bool,
+    /// Meter tests with the gas schedule of the latest protocol version and report the gas used
+    /// by each test. Fails if a test uses more gas than its threshold in the `[gas-thresholds]`
+    /// section of the package manifest.
+    #[clap(long = "gas-report")]
+    pub gas_report: bool,
 }
 
 impl Test {
@@ -60,12 +82,21 @@ impl Test {
             generate_struct_layouts,
             self.lint,
         )?;
-        run_move_unit_tests(
-            rerooted_path,
-            build_config,
-            Some(unit_test_config),
-            self.test.compute_coverage,
-        )
+        if self.gas_report {
+            run_move_unit_tests_with_gas_report(
+                rerooted_path,
+                build_config,
+                unit_test_config,
+                self.test.compute_coverage,
+            )
+        } else {
+            run_move_unit_tests(
+                rerooted_path,
+                build_config,
+                Some(unit_test_config),
+                self.test.compute_coverage,
+            )
+        }
     }
 }
 
@@ -126,6 +157,145 @@ pub fn run_move_unit_tests(
     )
 }
 
+/// Runs the tests in the package at `path` metered with the gas schedule of the latest protocol
+/// version, and reports the gas used by each test against the thresholds in the
+/// `[gas-thresholds]` section of its manifest. Unless a gas limit is set in `config`, each test
+/// may use up to the computation budget of a transaction.
+pub fn run_move_unit_tests_with_gas_report(
+    path: PathBuf,
+    build_config: BuildConfig,
+    config: UnitTestingConfig,
+    compute_coverage: bool,
+) -> anyhow::Result<UnitTestResult> {
+    // bind the extension hook if it has not yet been done
+    Lazy::force(&SET_EXTENSION_HOOK);
+
+    let thresholds = read_gas_thresholds(&path)?;
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let (result, gas_used) = move_cli::base::test::run_move_unit_tests_with_gas_used(
+        &path,
+        build_config,
+        UnitTestingConfig {
+            gas_limit: config
+                .gas_limit
+                .or(Some(protocol_config.max_gas_computation_bucket())),
+            report_stacktrace_on_abort: true,
+            ignore_compile_warnings: true,
+            ..config
+        },
+        sui_move_natives::all_natives(/* silent */ false),
+        Some(cost_schedule_for_unit_tests(
+            protocol_config.gas_model_version(),
+        )),
+        compute_coverage,
+        &mut std::io::sink(),
+        &mut std::io::stdout(),
+    )?;
+
+    let within_thresholds = report_gas_used(&gas_used, &thresholds, &mut std::io::stdout())?;
+    Ok(if within_thresholds {
+        result
+    } else {
+        UnitTestResult::Failure
+    })
+}
+
+/// Reads the `[gas-thresholds]` section of the manifest of the package at `path`, mapping tests
+/// to the most gas they are expected to use. The manifest names tests as
+/// `<address>::<module_name>::<fn_name>`, with either a numerical address or a named address of
+/// the package, and they are returned fully qualified with their numerical address.
+fn read_gas_thresholds(path: &Path) -> anyhow::Result<BTreeMap<String, u64>> {
+    let manifest = std::fs::read_to_string(path.join(SourcePackageLayout::Manifest.path()))?;
+    let manifest = parse_move_manifest_string(manifest)?;
+    let Some(section) = manifest.get(GAS_THRESHOLDS_SECTION) else {
+        return Ok(BTreeMap::new());
+    };
+    let Some(section) = section.as_table().cloned() else {
+        bail!("'[{GAS_THRESHOLDS_SECTION}]' section of the manifest must be a table");
+    };
+    let manifest = parse_source_manifest(manifest)?;
+    section
+        .iter()
+        .map(|(test, threshold)| match threshold.as_integer() {
+            Some(threshold) if threshold >= 0 => {
+                Ok((qualified_test_name(test, &manifest)?, threshold as u64))
+            }
+            _ => bail!(
+                "Gas threshold for '{test}' in '[{GAS_THRESHOLDS_SECTION}]' must be a \
+                 non-negative integer"
+            ),
+        })
+        .collect()
+}
+
+/// Names `test` as the test runner does, i.e. `0x<address>::<module_name>::<fn_name>`, resolving
+/// its address with the dev and regular address assignments of `manifest`.
+fn qualified_test_name(test: &str, manifest: &SourceManifest) -> anyhow::Result<String> {
+    let mut parts = test.split("::");
+    let (Some(address), Some(module), Some(function), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        bail!(
+            "Gas threshold for '{test}' in '[{GAS_THRESHOLDS_SECTION}]' must name a test as \
+             '<address>::<module_name>::<fn_name>'"
+        );
+    };
+    let address = if address.starts_with("0x") {
+        ObjectID::from_hex_literal(address)
+            .with_context(|| format!("Invalid address in gas threshold for '{test}'"))?
+    } else {
+        let dev_address = manifest
+            .dev_address_assignments
+            .iter()
+            .flatten()
+            .find(|(name, _)| name.as_str() == address)
+            .map(|(_, address)| *address);
+        let declared_address = manifest
+            .addresses
+            .iter()
+            .flatten()
+            .find(|(name, _)| name.as_str() == address)
+            .and_then(|(_, address)| *address);
+        let Some(address) = dev_address.or(declared_address) else {
+            bail!("Named address '{address}' of gas threshold for '{test}' is not assigned");
+        };
+        ObjectID::from(address)
+    };
+    Ok(format!(
+        "0x{}::{module}::{function}",
+        address.short_str_lossless()
+    ))
+}
+
+/// Writes the gas used by each test, along with its threshold if it has one, and returns `false`
+/// if any test used more than its threshold.
+fn report_gas_used<W: Write>(
+    gas_used: &BTreeMap<String, u64>,
+    thresholds: &BTreeMap<String, u64>,
+    writer: &mut W,
+) -> anyhow::Result<bool> {
+    let mut within_thresholds = true;
+    writeln!(writer, "\nGas Report:\n")?;
+    for (test, gas) in gas_used {
+        match thresholds.get(test).copied() {
+            Some(threshold) if *gas > threshold => {
+                within_thresholds = false;
+                writeln!(
+                    writer,
+                    "[ {} ] {test}: {gas} (threshold: {threshold})",
+                    "REGRESSED".red()
+                )?;
+            }
+            Some(threshold) => writeln!(
+                writer,
+                "[ OK        ] {test}: {gas} (threshold: {threshold})"
+            )?,
+            None => writeln!(writer, "[ OK        ] {test}: {gas}")?,
+        }
+    }
+    Ok(within_thresholds)
+}
+
 fn new_testing_object_and_natives_cost_runtime(ext: &mut NativeContextExtensions) {
     // Use a throwaway metrics registry for testing.
     let registry = prometheus::Registry::new();
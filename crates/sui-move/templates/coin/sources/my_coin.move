@@ -0,0 +1,45 @@
+/// A fungible token, `Coin<MY_COIN>`. Publishing the package creates the currency, and sends the
+/// `TreasuryCap` that mints and burns it to the publisher.
+module template::my_coin {
+    use std::option;
+    use sui::coin::{Self, Coin, TreasuryCap};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// The type of the coin. By convention, it has the name of its module in upper case, and is
+    /// used as a one-time witness to create the currency.
+    struct MY_COIN has drop {}
+
+    fun init(witness: MY_COIN, ctx: &mut TxContext) {
+        let (treasury_cap, metadata) = coin::create_currency(
+            witness,
+            6,
+            b"MYC",
+            b"My Coin",
+            b"An example coin",
+            option::none(),
+            ctx,
+        );
+        transfer::public_freeze_object(metadata);
+        transfer::public_transfer(treasury_cap, tx_context::sender(ctx))
+    }
+
+    /// Mints `amount` coins and sends them to `recipient`.
+    public entry fun mint(
+        treasury_cap: &mut TreasuryCap<MY_COIN>,
+        amount: u64,
+        recipient: address,
+        ctx: &mut TxContext,
+    ) {
+        coin::mint_and_transfer(treasury_cap, amount, recipient, ctx)
+    }
+
+    public entry fun burn(treasury_cap: &mut TreasuryCap<MY_COIN>, coin: Coin<MY_COIN>) {
+        coin::burn(treasury_cap, coin);
+    }
+
+    #[test_only]
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init(MY_COIN {}, ctx)
+    }
+}
@@ -0,0 +1,35 @@
+#[test_only]
+module template::my_coin_tests {
+    use sui::coin::{Self, Coin, TreasuryCap};
+    use sui::test_scenario;
+    use template::my_coin::{Self, MY_COIN};
+
+    const PUBLISHER: address = @0xA;
+    const RECIPIENT: address = @0xB;
+
+    #[test]
+    fun mint_and_burn() {
+        let scenario = test_scenario::begin(PUBLISHER);
+        my_coin::init_for_testing(test_scenario::ctx(&mut scenario));
+
+        test_scenario::next_tx(&mut scenario, PUBLISHER);
+        {
+            let treasury_cap = test_scenario::take_from_sender<TreasuryCap<MY_COIN>>(&scenario);
+            my_coin::mint(&mut treasury_cap, 100, RECIPIENT, test_scenario::ctx(&mut scenario));
+            test_scenario::return_to_sender(&scenario, treasury_cap);
+        };
+
+        test_scenario::next_tx(&mut scenario, RECIPIENT);
+        let coin = test_scenario::take_from_sender<Coin<MY_COIN>>(&scenario);
+        assert!(coin::value(&coin) == 100, 0);
+
+        test_scenario::next_tx(&mut scenario, PUBLISHER);
+        {
+            let treasury_cap = test_scenario::take_from_sender<TreasuryCap<MY_COIN>>(&scenario);
+            my_coin::burn(&mut treasury_cap, coin);
+            assert!(coin::total_supply(&treasury_cap) == 0, 1);
+            test_scenario::return_to_sender(&scenario, treasury_cap);
+        };
+        test_scenario::end(scenario);
+    }
+}
@@ -0,0 +1,111 @@
+/// A pool of two coin types that anyone can swap against, priced by the constant product formula
+/// `reserve_a * reserve_b = k`, minus a fee that stays in the pool. The liquidity is provided by
+/// the creator of the pool, who can withdraw it with the pool's `OwnerCap`.
+module template::pool {
+    use sui::balance::{Self, Balance};
+    use sui::coin::{Self, Coin};
+    use sui::object::{Self, ID, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// The fee is more than 100%.
+    const EInvalidFee: u64 = 0;
+    /// A pool cannot be created, or swapped against, with no coins.
+    const EZeroAmount: u64 = 1;
+    /// The swap would return fewer coins than the minimum requested.
+    const ESlippage: u64 = 2;
+    /// The `OwnerCap` is not the one of the pool.
+    const ENotOwner: u64 = 3;
+
+    /// Basis points in 100%.
+    const FEE_SCALE: u64 = 10_000;
+
+    struct Pool<phantom A, phantom B> has key {
+        id: UID,
+        reserve_a: Balance<A>,
+        reserve_b: Balance<B>,
+        /// Fee on the coins swapped in, in basis points.
+        fee_bps: u64,
+    }
+
+    /// Allows withdrawing the liquidity of the pool with ID `pool_id`.
+    struct OwnerCap has key, store {
+        id: UID,
+        pool_id: ID,
+    }
+
+    /// Creates and shares a pool with `a` and `b` as liquidity, and sends its `OwnerCap` to the
+    /// sender of the transaction.
+    public entry fun create<A, B>(a: Coin<A>, b: Coin<B>, fee_bps: u64, ctx: &mut TxContext) {
+        transfer::public_transfer(new(a, b, fee_bps, ctx), tx_context::sender(ctx))
+    }
+
+    public fun new<A, B>(a: Coin<A>, b: Coin<B>, fee_bps: u64, ctx: &mut TxContext): OwnerCap {
+        assert!(fee_bps <= FEE_SCALE, EInvalidFee);
+        assert!(coin::value(&a) > 0 && coin::value(&b) > 0, EZeroAmount);
+        let pool = Pool {
+            id: object::new(ctx),
+            reserve_a: coin::into_balance(a),
+            reserve_b: coin::into_balance(b),
+            fee_bps,
+        };
+        let cap = OwnerCap { id: object::new(ctx), pool_id: object::id(&pool) };
+        transfer::share_object(pool);
+        cap
+    }
+
+    public fun reserves<A, B>(pool: &Pool<A, B>): (u64, u64) {
+        (balance::value(&pool.reserve_a), balance::value(&pool.reserve_b))
+    }
+
+    /// Swaps `a` for at least `min_out` coins of type `B`.
+    public fun swap_a_for_b<A, B>(
+        pool: &mut Pool<A, B>,
+        a: Coin<A>,
+        min_out: u64,
+        ctx: &mut TxContext,
+    ): Coin<B> {
+        let (reserve_a, reserve_b) = reserves(pool);
+        let out = amount_out(coin::value(&a), reserve_a, reserve_b, pool.fee_bps);
+        assert!(out >= min_out, ESlippage);
+        coin::put(&mut pool.reserve_a, a);
+        coin::take(&mut pool.reserve_b, out, ctx)
+    }
+
+    /// Swaps `b` for at least `min_out` coins of type `A`.
+    public fun swap_b_for_a<A, B>(
+        pool: &mut Pool<A, B>,
+        b: Coin<B>,
+        min_out: u64,
+        ctx: &mut TxContext,
+    ): Coin<A> {
+        let (reserve_a, reserve_b) = reserves(pool);
+        let out = amount_out(coin::value(&b), reserve_b, reserve_a, pool.fee_bps);
+        assert!(out >= min_out, ESlippage);
+        coin::put(&mut pool.reserve_b, b);
+        coin::take(&mut pool.reserve_a, out, ctx)
+    }
+
+    /// Withdraws all the coins of the pool.
+    public fun withdraw<A, B>(
+        cap: &OwnerCap,
+        pool: &mut Pool<A, B>,
+        ctx: &mut TxContext,
+    ): (Coin<A>, Coin<B>) {
+        assert!(cap.pool_id == object::id(pool), ENotOwner);
+        (
+            coin::from_balance(balance::withdraw_all(&mut pool.reserve_a), ctx),
+            coin::from_balance(balance::withdraw_all(&mut pool.reserve_b), ctx),
+        )
+    }
+
+    /// The number of coins out of a swap of `amount_in` coins, with the reserves of the coins
+    /// swapped in and out of the pool.
+    public fun amount_out(amount_in: u64, reserve_in: u64, reserve_out: u64, fee_bps: u64): u64 {
+        assert!(amount_in > 0, EZeroAmount);
+        let amount_in = (amount_in as u128) * ((FEE_SCALE - fee_bps) as u128);
+        let out = amount_in * (reserve_out as u128)
+            / ((reserve_in as u128) * (FEE_SCALE as u128) + amount_in);
+        (out as u64)
+    }
+}
@@ -0,0 +1,76 @@
+#[test_only]
+module template::pool_tests {
+    use sui::coin;
+    use sui::sui::SUI;
+    use sui::test_scenario::{Self, Scenario};
+    use template::pool::{Self, OwnerCap, Pool};
+
+    struct USDC has drop {}
+
+    const OWNER: address = @0xA;
+    const TRADER: address = @0xB;
+
+    fun create_pool(scenario: &mut Scenario) {
+        let ctx = test_scenario::ctx(scenario);
+        pool::create(
+            coin::mint_for_testing<SUI>(1_000_000, ctx),
+            coin::mint_for_testing<USDC>(1_000_000, ctx),
+            30,
+            ctx,
+        );
+    }
+
+    #[test]
+    fun swap_and_withdraw() {
+        let scenario = test_scenario::begin(OWNER);
+        create_pool(&mut scenario);
+
+        test_scenario::next_tx(&mut scenario, TRADER);
+        {
+            let pool = test_scenario::take_shared<Pool<SUI, USDC>>(&scenario);
+            let ctx = test_scenario::ctx(&mut scenario);
+            let usdc = pool::swap_a_for_b(&mut pool, coin::mint_for_testing(1_000, ctx), 990, ctx);
+            // 1_000 SUI, minus the 0.3% fee, for at most 1_000 USDC at the current price
+            assert!(coin::burn_for_testing(usdc) == 996, 0);
+            let (reserve_sui, reserve_usdc) = pool::reserves(&pool);
+            assert!(reserve_sui == 1_001_000 && reserve_usdc == 999_004, 1);
+            test_scenario::return_shared(pool);
+        };
+
+        test_scenario::next_tx(&mut scenario, OWNER);
+        {
+            let cap = test_scenario::take_from_sender<OwnerCap>(&scenario);
+            let pool = test_scenario::take_shared<Pool<SUI, USDC>>(&scenario);
+            let (sui, usdc) = pool::withdraw(&cap, &mut pool, test_scenario::ctx(&mut scenario));
+            assert!(coin::burn_for_testing(sui) == 1_001_000, 2);
+            assert!(coin::burn_for_testing(usdc) == 999_004, 3);
+            test_scenario::return_to_sender(&scenario, cap);
+            test_scenario::return_shared(pool);
+        };
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = pool::ESlippage)]
+    fun swap_below_min_out() {
+        let scenario = test_scenario::begin(OWNER);
+        create_pool(&mut scenario);
+
+        test_scenario::next_tx(&mut scenario, TRADER);
+        let pool = test_scenario::take_shared<Pool<SUI, USDC>>(&scenario);
+        let ctx = test_scenario::ctx(&mut scenario);
+        let usdc = pool::swap_a_for_b(&mut pool, coin::mint_for_testing(1_000, ctx), 1_000, ctx);
+
+        coin::burn_for_testing(usdc);
+        test_scenario::return_shared(pool);
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    fun amount_out() {
+        // no fee, at a price of 2 moved slightly by the swap itself, rounded down
+        assert!(pool::amount_out(10, 1_000_000_000, 2_000_000_000, 0) == 19, 0);
+        // 100% fee
+        assert!(pool::amount_out(10, 1_000, 1_000, 10_000) == 0, 1);
+    }
+}
@@ -0,0 +1,70 @@
+/// King of the hill: anyone can become the king by paying more than the previous king did, who
+/// gets the payment. The game is a shared object, created when the package is published.
+module template::hill {
+    use sui::coin::{Self, Coin};
+    use sui::event;
+    use sui::object::{Self, UID};
+    use sui::sui::SUI;
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// The payment is not more than the price of the hill.
+    const EPaymentTooLow: u64 = 0;
+
+    /// Price of the hill before its first king.
+    const INITIAL_PRICE: u64 = 1_000;
+
+    struct Hill has key {
+        id: UID,
+        king: address,
+        /// What the king paid for the hill, to be exceeded by the next king.
+        price: u64,
+        /// Number of kings the hill has had.
+        reigns: u64,
+    }
+
+    /// Emitted when the hill has a new king.
+    struct Crowned has copy, drop {
+        king: address,
+        price: u64,
+    }
+
+    fun init(ctx: &mut TxContext) {
+        transfer::share_object(Hill {
+            id: object::new(ctx),
+            king: tx_context::sender(ctx),
+            price: INITIAL_PRICE,
+            reigns: 0,
+        })
+    }
+
+    public fun king(hill: &Hill): address {
+        hill.king
+    }
+
+    public fun price(hill: &Hill): u64 {
+        hill.price
+    }
+
+    public fun reigns(hill: &Hill): u64 {
+        hill.reigns
+    }
+
+    /// Makes the sender the king of the hill, paying `payment` to the previous king.
+    public entry fun claim(hill: &mut Hill, payment: Coin<SUI>, ctx: &mut TxContext) {
+        let price = coin::value(&payment);
+        assert!(price > hill.price, EPaymentTooLow);
+
+        let king = tx_context::sender(ctx);
+        transfer::public_transfer(payment, hill.king);
+        hill.king = king;
+        hill.price = price;
+        hill.reigns = hill.reigns + 1;
+        event::emit(Crowned { king, price });
+    }
+
+    #[test_only]
+    public fun init_for_testing(ctx: &mut TxContext) {
+        init(ctx)
+    }
+}
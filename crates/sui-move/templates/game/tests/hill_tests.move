@@ -0,0 +1,54 @@
+#[test_only]
+module template::hill_tests {
+    use sui::coin::{Self, Coin};
+    use sui::sui::SUI;
+    use sui::test_scenario::{Self, Scenario};
+    use template::hill::{Self, Hill};
+
+    const PUBLISHER: address = @0xA;
+    const ALICE: address = @0xB;
+    const BOB: address = @0xC;
+
+    fun claim(scenario: &mut Scenario, player: address, amount: u64) {
+        test_scenario::next_tx(scenario, player);
+        let hill = test_scenario::take_shared<Hill>(scenario);
+        let ctx = test_scenario::ctx(scenario);
+        hill::claim(&mut hill, coin::mint_for_testing(amount, ctx), ctx);
+        test_scenario::return_shared(hill);
+    }
+
+    #[test]
+    fun claim_twice() {
+        let scenario = test_scenario::begin(PUBLISHER);
+        hill::init_for_testing(test_scenario::ctx(&mut scenario));
+
+        claim(&mut scenario, ALICE, 2_000);
+        claim(&mut scenario, BOB, 3_000);
+
+        test_scenario::next_tx(&mut scenario, ALICE);
+        {
+            // Alice got Bob's payment
+            let payment = test_scenario::take_from_sender<Coin<SUI>>(&scenario);
+            assert!(coin::value(&payment) == 3_000, 0);
+            test_scenario::return_to_sender(&scenario, payment);
+
+            let hill = test_scenario::take_shared<Hill>(&scenario);
+            assert!(hill::king(&hill) == BOB, 1);
+            assert!(hill::price(&hill) == 3_000, 2);
+            assert!(hill::reigns(&hill) == 2, 3);
+            test_scenario::return_shared(hill);
+        };
+        test_scenario::end(scenario);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = hill::EPaymentTooLow)]
+    fun claim_without_outbidding() {
+        let scenario = test_scenario::begin(PUBLISHER);
+        hill::init_for_testing(test_scenario::ctx(&mut scenario));
+
+        claim(&mut scenario, ALICE, 2_000);
+        claim(&mut scenario, BOB, 2_000);
+        test_scenario::end(scenario);
+    }
+}
@@ -0,0 +1,71 @@
+/// An NFT collection that anyone can mint into. Each NFT has a name, a description and the URL of
+/// its image, and can be transferred, updated by its owner, or burned.
+module template::nft {
+    use std::string::{Self, String};
+    use sui::event;
+    use sui::object::{Self, ID, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+    use sui::url::{Self, Url};
+
+    struct Nft has key, store {
+        id: UID,
+        name: String,
+        description: String,
+        url: Url,
+    }
+
+    /// Emitted when an NFT is minted.
+    struct NftMinted has copy, drop {
+        object_id: ID,
+        creator: address,
+        name: String,
+    }
+
+    public fun name(nft: &Nft): &String {
+        &nft.name
+    }
+
+    public fun description(nft: &Nft): &String {
+        &nft.description
+    }
+
+    public fun url(nft: &Nft): &Url {
+        &nft.url
+    }
+
+    /// Mints a new NFT and sends it to the sender of the transaction.
+    public entry fun mint(
+        name: vector<u8>,
+        description: vector<u8>,
+        url: vector<u8>,
+        ctx: &mut TxContext,
+    ) {
+        let sender = tx_context::sender(ctx);
+        let nft = Nft {
+            id: object::new(ctx),
+            name: string::utf8(name),
+            description: string::utf8(description),
+            url: url::new_unsafe_from_bytes(url),
+        };
+        event::emit(NftMinted {
+            object_id: object::id(&nft),
+            creator: sender,
+            name: nft.name,
+        });
+        transfer::public_transfer(nft, sender);
+    }
+
+    public entry fun transfer(nft: Nft, recipient: address) {
+        transfer::public_transfer(nft, recipient)
+    }
+
+    public entry fun update_description(nft: &mut Nft, description: vector<u8>) {
+        nft.description = string::utf8(description)
+    }
+
+    public entry fun burn(nft: Nft) {
+        let Nft { id, name: _, description: _, url: _ } = nft;
+        object::delete(id)
+    }
+}
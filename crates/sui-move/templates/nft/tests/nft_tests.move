@@ -0,0 +1,34 @@
+#[test_only]
+module template::nft_tests {
+    use std::string;
+    use sui::test_scenario;
+    use template::nft::{Self, Nft};
+
+    const CREATOR: address = @0xA;
+    const RECIPIENT: address = @0xB;
+
+    #[test]
+    fun mint_transfer_update_burn() {
+        let scenario = test_scenario::begin(CREATOR);
+        nft::mint(b"Nft", b"An NFT", b"https://example.com/nft.png", test_scenario::ctx(&mut scenario));
+
+        test_scenario::next_tx(&mut scenario, CREATOR);
+        {
+            let nft = test_scenario::take_from_sender<Nft>(&scenario);
+            assert!(*nft::name(&nft) == string::utf8(b"Nft"), 0);
+            nft::transfer(nft, RECIPIENT);
+        };
+
+        test_scenario::next_tx(&mut scenario, RECIPIENT);
+        {
+            let nft = test_scenario::take_from_sender<Nft>(&scenario);
+            nft::update_description(&mut nft, b"An updated NFT");
+            assert!(*nft::description(&nft) == string::utf8(b"An updated NFT"), 1);
+            nft::burn(nft);
+        };
+
+        test_scenario::next_tx(&mut scenario, RECIPIENT);
+        assert!(!test_scenario::has_most_recent_for_sender<Nft>(&scenario), 2);
+        test_scenario::end(scenario);
+    }
+}
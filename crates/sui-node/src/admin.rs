@@ -37,6 +37,10 @@ use tracing::info;
 // View the node config (private keys will be masked):
 //
 //   $ curl 'http://127.0.0.1:1337/node-config'
+//
+// View the 20 shared objects that the most certificates queued for over the last minute:
+//
+//   $ curl 'http://127.0.0.1:1337/hot-objects?limit=20'
 
 const LOGGING_ROUTE: &str = "/logging";
 const SET_BUFFER_STAKE_ROUTE: &str = "/set-override-buffer-stake";
@@ -44,6 +48,9 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const HOT_OBJECTS: &str = "/hot-objects";
+
+const DEFAULT_HOT_OBJECTS_LIMIT: usize = 10;
 
 struct AppState {
     node: Arc<SuiNode>,
@@ -62,6 +69,7 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, filter_handle: Filt
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(HOT_OBJECTS, get(hot_objects))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -126,6 +134,36 @@ async fn node_config(State(state): State<Arc<AppState>>) -> (StatusCode, String)
     (StatusCode::OK, format!("{:#?}\n", node_config))
 }
 
+#[derive(Deserialize)]
+struct HotObjects {
+    limit: Option<usize>,
+}
+
+async fn hot_objects(
+    State(state): State<Arc<AppState>>,
+    hot_objects: Query<HotObjects>,
+) -> (StatusCode, String) {
+    let Query(HotObjects { limit }) = hot_objects;
+    let hot_objects = state
+        .node
+        .state()
+        .transaction_manager()
+        .hot_objects(limit.unwrap_or(DEFAULT_HOT_OBJECTS_LIMIT));
+
+    let mut output = String::new();
+    for object in &hot_objects {
+        output.push_str(&format!(
+            "{} queued_certificates={} total_wait_ms={} max_wait_ms={}\n",
+            object.object_id,
+            object.queued_certificates,
+            object.total_wait.as_millis(),
+            object.max_wait.as_millis(),
+        ));
+    }
+
+    (StatusCode::OK, output)
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
@@ -4,7 +4,6 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
-use std::str::FromStr;
 #[cfg(msim)]
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -56,6 +55,7 @@ use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::authority::epoch_start_configuration::EpochStartConfigTrait;
 use sui_core::authority::epoch_start_configuration::EpochStartConfiguration;
+use sui_core::authority::execution_capture::ExecutionCaptureStore;
 use sui_core::authority_aggregator::AuthorityAggregator;
 use sui_core::authority_server::ValidatorService;
 use sui_core::checkpoints::checkpoint_executor;
@@ -117,6 +117,7 @@ use sui_types::quorum_driver_types::QuorumDriverEffectsQueueResult;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemState;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::SuiSystemStateTrait;
+use sui_types::zk_login_util::ZkLoginProviderRegistry;
 use typed_store::rocks::default_db_options;
 use typed_store::DBMetrics;
 
@@ -262,12 +263,12 @@ impl SuiNode {
         consensus_adapter: Arc<ConsensusAdapter>,
     ) {
         let epoch = epoch_store.epoch();
-        let supported_providers = epoch_store
-            .protocol_config()
-            .zklogin_supported_providers()
-            .iter()
-            .map(|s| OIDCProvider::from_str(s).expect("Invalid provider string"))
-            .collect::<Vec<_>>();
+        let supported_providers =
+            ZkLoginProviderRegistry::from_protocol_config(epoch_store.protocol_config())
+                .expect("Invalid provider string")
+                .providers()
+                .cloned()
+                .collect::<Vec<_>>();
 
         let fetch_interval = Duration::from_secs(config.jwk_fetch_interval_seconds);
 
@@ -405,6 +406,7 @@ impl SuiNode {
             config
                 .expensive_safety_check_config
                 .enable_epoch_sui_conservation_check(),
+            config.group_commit_config.clone(),
             &prometheus_registry,
         )
         .await?;
@@ -480,6 +482,16 @@ impl SuiNode {
             None
         };
 
+        let execution_capture = config
+            .execution_capture_config
+            .as_ref()
+            .map(|capture_config| {
+                ExecutionCaptureStore::new(
+                    config.db_path().join("execution_capture"),
+                    capture_config,
+                )
+            });
+
         let chain_identifier = ChainIdentifier::from(*genesis.checkpoint().digest());
         // It's ok if the value is already set due to data races.
         let _ = CHAIN_IDENTIFIER.set(chain_identifier);
@@ -547,7 +559,12 @@ impl SuiNode {
             config.certificate_deny_config.clone(),
             config.indirect_objects_threshold,
             config.state_debug_dump_config.clone(),
+            execution_capture,
             archive_readers,
+            config
+                .execution_concurrency_config
+                .clone()
+                .unwrap_or_default(),
         )
         .await;
         // ensure genesis txn was executed
@@ -1029,6 +1046,11 @@ impl SuiNode {
         let new_epoch_start_state = epoch_store.epoch_start_state();
         let committee = new_epoch_start_state.get_narwhal_committee();
 
+        let consensus_config = config
+            .consensus_config
+            .as_ref()
+            .ok_or_else(|| anyhow!("Validator is missing consensus config"))?;
+
         let consensus_handler_initializer = || {
             ConsensusHandler::new(
                 epoch_store.clone(),
@@ -1038,14 +1060,11 @@ impl SuiNode {
                 low_scoring_authorities.clone(),
                 committee.clone(),
                 state.metrics.clone(),
+                consensus_config.commit_timestamp_skew_alert_threshold(),
             )
         };
 
-        let transactions_addr = &config
-            .consensus_config
-            .as_ref()
-            .ok_or_else(|| anyhow!("Validator is missing consensus config"))?
-            .address;
+        let transactions_addr = &consensus_config.address;
         let worker_cache = new_epoch_start_state.get_narwhal_worker_cache(transactions_addr);
 
         narwhal_manager
@@ -18,7 +18,6 @@ use serde_json::json;
 
 use sui_json::SuiJsonValue;
 use sui_json_rpc::error::Error;
-use sui_json_rpc_types::DevInspectResults;
 use sui_json_rpc_types::EventFilter;
 use sui_json_rpc_types::ProtocolConfigResponse;
 use sui_json_rpc_types::SuiTransactionBlockEvents;
@@ -38,6 +37,7 @@ use sui_json_rpc_types::{
     SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlockBytes,
     TransactionBlocksPage, TransferObjectParams,
 };
+use sui_json_rpc_types::{DevInspectResults, SuiChildObjectAccess};
 use sui_json_rpc_types::{SuiTypeTag, ValidatorApy, ValidatorApys};
 use sui_open_rpc::ExamplePairing;
 use sui_protocol_config::Chain;
@@ -291,6 +291,11 @@ impl RpcExampleProvider {
             events: SuiTransactionBlockEvents { data: vec![] },
             results: None,
             error: None,
+            child_object_access: Some(SuiChildObjectAccess {
+                num_accessed: 2,
+                num_loaded: 1,
+                loaded_bytes: 412,
+            }),
         };
 
         Examples::new(
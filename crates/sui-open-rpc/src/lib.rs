@@ -77,6 +77,14 @@ impl Project {
         self.method_routing.extend(module.method_routing);
     }
 
+    /// Names of the methods marked as deprecated.
+    pub fn deprecated_methods(&self) -> impl Iterator<Item = &str> {
+        self.methods
+            .iter()
+            .filter(|method| method.deprecated)
+            .map(|method| method.name.as_str())
+    }
+
     pub fn add_examples(&mut self, mut example_provider: BTreeMap<String, Vec<ExamplePairing>>) {
         for method in &mut self.methods {
             if let Occupied(entry) = example_provider.entry(method.name.clone()) {
@@ -707,5 +707,8 @@ async fn get_object_arg(
         Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
             ObjectArg::ImmOrOwnedObject(obj_ref)
         }
+        Owner::ConsensusV2 { .. } => {
+            anyhow::bail!("Consensus-owned object {id} cannot be used as an argument yet")
+        }
     })
 }
@@ -11,7 +11,7 @@ use tracing::{info, warn};
 
 /// The minimum and maximum protocol versions supported by this build.
 const MIN_PROTOCOL_VERSION: u64 = 1;
-const MAX_PROTOCOL_VERSION: u64 = 26;
+const MAX_PROTOCOL_VERSION: u64 = 27;
 
 // Record history of protocol version allocations here:
 //
@@ -76,6 +76,8 @@ const MAX_PROTOCOL_VERSION: u64 = 26;
 // Version 25: Add sui::table_vec::swap and sui::table_vec::swap_remove to system packages.
 // Version 26: New gas model version.
 //             Add support for receiving objects off of other objects in devnet only.
+// Version 27: Add support for transferring vectors of objects, and for transfer hooks, in
+//             TransferObjects in devnet only.
 
 #[derive(Copy, Clone, Debug, Hash, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ProtocolVersion(u64);
@@ -291,6 +293,30 @@ struct FeatureFlags {
     // Enable receiving sent objects
     #[serde(skip_serializing_if = "is_false")]
     receive_objects: bool,
+
+    // If true, record which objects were wrapped or unwrapped by a transaction, and the objects
+    // they were wrapped in or unwrapped from.
+    #[serde(skip_serializing_if = "is_false")]
+    record_object_lineage: bool,
+
+    // If true, TransferObjects accepts vectors of objects, transferring each of their elements.
+    #[serde(skip_serializing_if = "is_false")]
+    transfer_object_vectors: bool,
+
+    // If true, TransferObjects calls the `transfer_hook` function of the module defining an
+    // object's type, if it has one, which can reject the transfer.
+    #[serde(skip_serializing_if = "is_false")]
+    transfer_hooks: bool,
+
+    // If true, shared objects can be taken by value in Move calls, as long as they are deleted or
+    // re-shared by the end of the transaction.
+    #[serde(skip_serializing_if = "is_false")]
+    shared_object_deletion: bool,
+
+    // If true, the results of a programmable transaction command that no later command uses are
+    // checked right after the command, failing the transaction early if they cannot be dropped.
+    #[serde(skip_serializing_if = "is_false")]
+    early_unused_result_check: bool,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -780,6 +806,26 @@ pub struct ProtocolConfig {
     // Applied at the end of an epoch as a delta from the new epoch value, so setting this to 1
     // will cause the new epoch to start with JWKs from the previous epoch still valid.
     max_age_of_jwk_in_epochs: Option<u64>,
+
+    // When set, the timestamp that the prologue of a consensus commit gives to the Clock is clamped
+    // deterministically, to limit the effect of leaders whose clocks are off:
+    //  - it is at most this many ms ahead of the median creation time of the certificates in the
+    //    commit, which bounds how far a minority of clocks running ahead can move it, and
+    //  - it is never earlier than the timestamp of the previous commit's prologue (or the start of
+    //    the epoch), so the Clock never goes backwards.
+    // When unset, the commit timestamp is only clamped to the start of the epoch.
+    max_consensus_commit_timestamp_skew_ms: Option<u64>,
+
+    // Consensus parameters that override the values in the validators' local configuration when
+    // set. They take effect when consensus starts for a new epoch.
+    // How long (in ms) a primary waits to include the leader's certificate before proposing a
+    // header without it. Maps to Narwhal's `max_header_delay`.
+    consensus_leader_timeout_ms: Option<u64>,
+    // The maximum number of batches in a header. Maps to Narwhal's `max_header_num_of_batches`.
+    consensus_max_block_num_of_batches: Option<u64>,
+    // How long (in ms) a worker waits before sealing a batch that is not full. Maps to Narwhal's
+    // `max_batch_delay`.
+    consensus_propagation_delay_ms: Option<u64>,
 }
 
 // feature flags
@@ -923,6 +969,26 @@ impl ProtocolConfig {
         self.feature_flags.loaded_child_object_format_type
     }
 
+    pub fn record_object_lineage(&self) -> bool {
+        self.feature_flags.record_object_lineage
+    }
+
+    pub fn transfer_object_vectors(&self) -> bool {
+        self.feature_flags.transfer_object_vectors
+    }
+
+    pub fn transfer_hooks(&self) -> bool {
+        self.feature_flags.transfer_hooks
+    }
+
+    pub fn shared_object_deletion(&self) -> bool {
+        self.feature_flags.shared_object_deletion
+    }
+
+    pub fn early_unused_result_check(&self) -> bool {
+        self.feature_flags.early_unused_result_check
+    }
+
     pub fn end_of_epoch_transaction_supported(&self) -> bool {
         let ret = self.feature_flags.end_of_epoch_transaction_supported;
         if !ret {
@@ -1306,6 +1372,12 @@ impl ProtocolConfig {
 
                 max_age_of_jwk_in_epochs: None,
 
+            max_consensus_commit_timestamp_skew_ms: None,
+
+            consensus_leader_timeout_ms: None,
+            consensus_max_block_num_of_batches: None,
+            consensus_propagation_delay_ms: None,
+
             // When adding a new constant, set it to None in the earliest version, like this:
             // new_constant: None,
         };
@@ -1491,6 +1563,13 @@ impl ProtocolConfig {
                         cfg.feature_flags.receive_objects = true;
                     }
                 }
+                27 => {
+                    // Only enable vectors in TransferObjects, and transfer hooks, in devnet
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        cfg.feature_flags.transfer_object_vectors = true;
+                        cfg.feature_flags.transfer_hooks = true;
+                    }
+                }
                 // Use this template when making changes:
                 //
                 //     // modify an existing constant.
@@ -1555,12 +1634,30 @@ impl ProtocolConfig {
     pub fn set_consensus_bad_nodes_stake_threshold(&mut self, val: u64) {
         self.consensus_bad_nodes_stake_threshold = Some(val);
     }
+    pub fn set_max_consensus_commit_timestamp_skew_ms_for_testing(&mut self, val: Option<u64>) {
+        self.max_consensus_commit_timestamp_skew_ms = val;
+    }
     pub fn set_zklogin_supported_providers(&mut self, list: BTreeSet<String>) {
         self.feature_flags.zklogin_supported_providers = list
     }
     pub fn set_receive_object_for_testing(&mut self, val: bool) {
         self.feature_flags.receive_objects = val
     }
+    pub fn set_record_object_lineage_for_testing(&mut self, val: bool) {
+        self.feature_flags.record_object_lineage = val
+    }
+    pub fn set_transfer_object_vectors_for_testing(&mut self, val: bool) {
+        self.feature_flags.transfer_object_vectors = val
+    }
+    pub fn set_transfer_hooks_for_testing(&mut self, val: bool) {
+        self.feature_flags.transfer_hooks = val
+    }
+    pub fn set_shared_object_deletion_for_testing(&mut self, val: bool) {
+        self.feature_flags.shared_object_deletion = val
+    }
+    pub fn set_early_unused_result_check_for_testing(&mut self, val: bool) {
+        self.feature_flags.early_unused_result_check = val
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
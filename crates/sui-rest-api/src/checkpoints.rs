@@ -9,21 +9,27 @@ use std::{
 use anyhow::Result;
 use axum::{
     extract::{Path, State},
-    Json, TypedHeader,
+    TypedHeader,
 };
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
 use serde::{Deserialize, Serialize};
 use sui_core::authority::AuthorityState;
 use sui_types::{
+    crypto::AuthorityStrongQuorumSignInfo,
     effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
     messages_checkpoint::{
-        CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber, CheckpointSummary,
     },
     object::Object,
     storage::ObjectKey,
     transaction::Transaction,
 };
 
-use crate::{headers::Accept, AppError, Bcs};
+use crate::{
+    content::{negotiate, ResponseContent, RestContent},
+    headers::{Accept, AcceptFormat},
+    proto, AppError,
+};
 
 pub const GET_LATEST_CHECKPOINT_PATH: &str = "/checkpoints";
 pub const GET_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint";
@@ -32,12 +38,10 @@ pub const GET_FULL_CHECKPOINT_PATH: &str = "/checkpoints/:checkpoint/full";
 pub async fn get_full_checkpoint(
     //TODO support digest as well as sequence number
     Path(checkpoint_id): Path<CheckpointSequenceNumber>,
-    TypedHeader(accept): TypedHeader<Accept>,
+    accept: Option<TypedHeader<Accept>>,
     State(state): State<Arc<AuthorityState>>,
-) -> Result<Bcs<CheckpointData>, AppError> {
-    if accept.as_str() != crate::APPLICATION_BCS {
-        return Err(AppError(anyhow::anyhow!("invalid accept type")));
-    }
+) -> Result<ResponseContent<CheckpointData>, AppError> {
+    let format = negotiate::<CheckpointData>(accept)?;
 
     let verified_summary = state.get_verified_checkpoint_by_sequence_number(checkpoint_id)?;
     let checkpoint_contents = state.get_checkpoint_contents(verified_summary.content_digest)?;
@@ -159,11 +163,14 @@ pub async fn get_full_checkpoint(
         full_transactions.push(full_transaction);
     }
 
-    Ok(Bcs(CheckpointData {
-        checkpoint_summary: verified_summary.into(),
-        checkpoint_contents,
-        transactions: full_transactions,
-    }))
+    Ok(ResponseContent::new(
+        format,
+        CheckpointData {
+            checkpoint_summary: verified_summary.into(),
+            checkpoint_contents,
+            transactions: full_transactions,
+        },
+    ))
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -211,20 +218,89 @@ pub struct CheckpointTransaction {
     pub output_objects: Vec<Object>,
 }
 
+impl RestContent for CheckpointData {
+    const NAME: &'static str = "CheckpointData";
+    // Full checkpoints are too large to be worth returning as JSON
+    const FORMATS: &'static [AcceptFormat] = &[AcceptFormat::Bcs, AcceptFormat::Protobuf];
+
+    type Json = Self;
+    type Proto = proto::CheckpointData;
+
+    fn into_json(self) -> Result<Self> {
+        Ok(self)
+    }
+
+    fn into_proto(self) -> Result<proto::CheckpointData> {
+        Ok(proto::CheckpointData {
+            sequence_number: *self.checkpoint_summary.sequence_number(),
+            bcs: bcs::to_bytes(&self)?,
+        })
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Option<Schema> {
+        None
+    }
+}
+
 pub async fn get_latest_checkpoint(
+    accept: Option<TypedHeader<Accept>>,
     State(state): State<Arc<AuthorityState>>,
-) -> Result<Json<CertifiedCheckpointSummary>, AppError> {
+) -> Result<ResponseContent<CertifiedCheckpointSummary>, AppError> {
+    let format = negotiate::<CertifiedCheckpointSummary>(accept)?;
     let latest_checkpoint_sequence_number = state.get_latest_checkpoint_sequence_number()?;
     let verified_summary =
         state.get_verified_checkpoint_by_sequence_number(latest_checkpoint_sequence_number)?;
-    Ok(Json(verified_summary.into()))
+    Ok(ResponseContent::new(format, verified_summary.into()))
 }
 
 pub async fn get_checkpoint(
     //TODO support digest as well as sequence number
     Path(checkpoint_id): Path<CheckpointSequenceNumber>,
+    accept: Option<TypedHeader<Accept>>,
     State(state): State<Arc<AuthorityState>>,
-) -> Result<Json<CertifiedCheckpointSummary>, AppError> {
+) -> Result<ResponseContent<CertifiedCheckpointSummary>, AppError> {
+    let format = negotiate::<CertifiedCheckpointSummary>(accept)?;
     let verified_summary = state.get_verified_checkpoint_by_sequence_number(checkpoint_id)?;
-    Ok(Json(verified_summary.into()))
+    Ok(ResponseContent::new(format, verified_summary.into()))
+}
+
+/// A certified checkpoint summary, as returned in JSON. This is the same as the JSON encoding of
+/// `CertifiedCheckpointSummary`, which does not have a schema of its own.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct CheckpointSummaryResponse {
+    pub data: CheckpointSummary,
+    pub auth_signature: AuthorityStrongQuorumSignInfo,
+}
+
+impl RestContent for CertifiedCheckpointSummary {
+    const NAME: &'static str = "CheckpointSummary";
+    const FORMATS: &'static [AcceptFormat] = &[
+        AcceptFormat::Json,
+        AcceptFormat::Bcs,
+        AcceptFormat::Protobuf,
+    ];
+
+    type Json = CheckpointSummaryResponse;
+    type Proto = proto::CheckpointSummary;
+
+    fn into_json(self) -> Result<CheckpointSummaryResponse> {
+        let (data, auth_signature) = self.into_data_and_sig();
+        Ok(CheckpointSummaryResponse {
+            data,
+            auth_signature,
+        })
+    }
+
+    fn into_proto(self) -> Result<proto::CheckpointSummary> {
+        Ok(proto::CheckpointSummary {
+            sequence_number: *self.sequence_number(),
+            epoch: self.epoch,
+            digest: self.digest().as_ref().to_vec(),
+            bcs: bcs::to_bytes(&self)?,
+        })
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Option<Schema> {
+        Some(gen.subschema_for::<CheckpointSummaryResponse>())
+    }
 }
@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use sui_types::base_types::{ObjectID, SequenceNumber};
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
 
 use crate::checkpoints::CheckpointData;
+use crate::transactions::ExecutedTransaction;
 
 #[derive(Clone)]
 pub struct Client {
@@ -89,4 +90,22 @@ impl Client {
 
         bcs::from_bytes(&bytes).map_err(Into::into)
     }
+
+    pub async fn get_transaction(
+        &self,
+        transaction_digest: TransactionDigest,
+    ) -> Result<ExecutedTransaction> {
+        let url = format!("{}/transactions/{transaction_digest}", self.base_url);
+
+        let bytes = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .send()
+            .await?
+            .bytes()
+            .await?;
+
+        bcs::from_bytes(&bytes).map_err(Into::into)
+    }
 }
@@ -0,0 +1,86 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json, TypedHeader,
+};
+use schemars::{gen::SchemaGenerator, schema::Schema};
+use serde::Serialize;
+
+use crate::{
+    headers::{Accept, AcceptFormat},
+    AppError, Bcs, Protobuf,
+};
+
+/// A resource returned by the REST API, in one of the formats it supports. The BCS format is the
+/// encoding of the resource itself, the JSON and protobuf formats are views of it.
+pub trait RestContent: Serialize + Sized {
+    /// Name of the resource, also the name of its protobuf message in `proto/rest.proto`.
+    const NAME: &'static str;
+    /// The formats the resource can be returned in, the first one being the default.
+    const FORMATS: &'static [AcceptFormat];
+
+    type Json: Serialize;
+    type Proto: prost::Message;
+
+    fn into_json(self) -> anyhow::Result<Self::Json>;
+
+    fn into_proto(self) -> anyhow::Result<Self::Proto>;
+
+    /// Schema of the JSON format, `None` if it is not supported.
+    fn json_schema(gen: &mut SchemaGenerator) -> Option<Schema>;
+}
+
+/// Picks the format of a response with resource `T` for the client's `Accept` header, failing
+/// with `406 Not Acceptable` if none of the formats it accepts is supported.
+pub fn negotiate<T: RestContent>(
+    accept: Option<TypedHeader<Accept>>,
+) -> Result<AcceptFormat, AppError> {
+    let accept = accept.as_ref().map(|TypedHeader(accept)| accept);
+    AcceptFormat::negotiate(accept, T::FORMATS).ok_or_else(|| {
+        AppError::new(
+            StatusCode::NOT_ACCEPTABLE,
+            anyhow::anyhow!(
+                "{} can only be returned as one of: {}",
+                T::NAME,
+                T::FORMATS
+                    .iter()
+                    .map(|format| format.content_type())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    })
+}
+
+/// A resource, encoded in the negotiated format.
+pub struct ResponseContent<T> {
+    format: AcceptFormat,
+    content: T,
+}
+
+impl<T: RestContent> ResponseContent<T> {
+    pub fn new(format: AcceptFormat, content: T) -> Self {
+        debug_assert!(T::FORMATS.contains(&format));
+        Self { format, content }
+    }
+}
+
+impl<T: RestContent> IntoResponse for ResponseContent<T> {
+    fn into_response(self) -> Response {
+        let response = match self.format {
+            AcceptFormat::Json => self
+                .content
+                .into_json()
+                .map(|json| Json(json).into_response()),
+            AcceptFormat::Bcs => Ok(Bcs(self.content).into_response()),
+            AcceptFormat::Protobuf => self
+                .content
+                .into_proto()
+                .map(|message| Protobuf(message).into_response()),
+        };
+        response.unwrap_or_else(|e| AppError::from(e).into_response())
+    }
+}
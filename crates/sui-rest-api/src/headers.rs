@@ -40,7 +40,84 @@ impl Accept {
         Self(crate::APPLICATION_BCS.to_owned())
     }
 
+    pub fn protobuf() -> Self {
+        Self(crate::APPLICATION_PROTOBUF.to_owned())
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
 }
+
+/// The formats a response can be encoded in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AcceptFormat {
+    Json,
+    Bcs,
+    Protobuf,
+}
+
+impl AcceptFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => crate::APPLICATION_JSON,
+            Self::Bcs => crate::APPLICATION_BCS,
+            Self::Protobuf => crate::APPLICATION_PROTOBUF,
+        }
+    }
+
+    /// Picks the first of the media types in `accept` that is one of the `supported` formats,
+    /// ignoring their parameters (including quality values). Wildcards, and a missing header,
+    /// select the first supported format.
+    pub fn negotiate(accept: Option<&Accept>, supported: &[AcceptFormat]) -> Option<Self> {
+        let Some(accept) = accept else {
+            return supported.first().copied();
+        };
+        accept.as_str().split(',').find_map(|media_range| {
+            let media_type = media_range.split(';').next().unwrap_or_default().trim();
+            match media_type {
+                "*/*" | "application/*" => supported.first().copied(),
+                _ => supported
+                    .iter()
+                    .find(|format| format.content_type() == media_type)
+                    .copied(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: &[AcceptFormat] = &[
+        AcceptFormat::Json,
+        AcceptFormat::Bcs,
+        AcceptFormat::Protobuf,
+    ];
+
+    fn negotiate(accept: &str, supported: &[AcceptFormat]) -> Option<AcceptFormat> {
+        AcceptFormat::negotiate(Some(&Accept(accept.to_owned())), supported)
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(AcceptFormat::negotiate(None, ALL), Some(AcceptFormat::Json));
+        assert_eq!(negotiate("application/bcs", ALL), Some(AcceptFormat::Bcs));
+        assert_eq!(
+            negotiate("application/x-protobuf;q=0.9, application/json", ALL),
+            Some(AcceptFormat::Protobuf)
+        );
+        assert_eq!(
+            negotiate("text/html, application/bcs", ALL),
+            Some(AcceptFormat::Bcs)
+        );
+        assert_eq!(negotiate("*/*", ALL), Some(AcceptFormat::Json));
+        assert_eq!(
+            negotiate("*/*", &[AcceptFormat::Bcs, AcceptFormat::Protobuf]),
+            Some(AcceptFormat::Bcs)
+        );
+        assert_eq!(negotiate("application/json", &[AcceptFormat::Bcs]), None);
+        assert_eq!(negotiate("text/html", ALL), None);
+    }
+}
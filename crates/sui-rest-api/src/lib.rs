@@ -5,11 +5,16 @@ use axum::{http::StatusCode, routing::get, Router};
 
 mod checkpoints;
 mod client;
+mod content;
 pub mod headers;
 mod objects;
+mod openapi;
+pub mod proto;
+mod transactions;
 
 pub use checkpoints::{CheckpointData, CheckpointTransaction};
 pub use client::Client;
+pub use transactions::ExecutedTransaction;
 
 async fn health_check() -> StatusCode {
     StatusCode::OK
@@ -20,6 +25,7 @@ pub struct Bcs<T>(pub T);
 pub const TEXT_PLAIN_UTF_8: &str = "text/plain; charset=utf-8";
 pub const APPLICATION_BCS: &str = "application/bcs";
 pub const APPLICATION_JSON: &str = "application/json";
+pub const APPLICATION_PROTOBUF: &str = "application/x-protobuf";
 
 impl<T> axum::response::IntoResponse for Bcs<T>
 where
@@ -48,6 +54,24 @@ where
     }
 }
 
+pub struct Protobuf<T>(pub T);
+
+impl<T> axum::response::IntoResponse for Protobuf<T>
+where
+    T: prost::Message,
+{
+    fn into_response(self) -> axum::response::Response {
+        (
+            [(
+                axum::http::header::CONTENT_TYPE,
+                axum::http::HeaderValue::from_static(APPLICATION_PROTOBUF),
+            )],
+            self.0.encode_to_vec(),
+        )
+            .into_response()
+    }
+}
+
 pub fn rest_router(state: std::sync::Arc<sui_core::authority::AuthorityState>) -> Router {
     Router::new()
         .route("/", get(health_check))
@@ -68,6 +92,11 @@ pub fn rest_router(state: std::sync::Arc<sui_core::authority::AuthorityState>) -
             objects::GET_OBJECT_WITH_VERSION_PATH,
             get(objects::get_object_with_version),
         )
+        .route(
+            transactions::GET_TRANSACTION_PATH,
+            get(transactions::get_transaction),
+        )
+        .route(openapi::OPENAPI_PATH, get(openapi::get_openapi))
         .with_state(state)
 }
 
@@ -83,17 +112,28 @@ pub async fn start_service(
         .unwrap();
 }
 
-// Make our own error that wraps `anyhow::Error`.
-pub struct AppError(anyhow::Error);
+// Make our own error that wraps `anyhow::Error`, along with the status of the response.
+pub struct AppError(StatusCode, anyhow::Error);
+
+impl AppError {
+    pub fn new(status: StatusCode, error: anyhow::Error) -> Self {
+        Self(status, error)
+    }
+
+    pub fn not_found(message: &'static str) -> Self {
+        Self(StatusCode::NOT_FOUND, anyhow::anyhow!(message))
+    }
+}
 
 // Tell axum how to convert `AppError` into a response.
 impl axum::response::IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Something went wrong: {}", self.0),
-        )
-            .into_response()
+        let message = if self.0 == StatusCode::INTERNAL_SERVER_ERROR {
+            format!("Something went wrong: {}", self.1)
+        } else {
+            self.1.to_string()
+        };
+        (self.0, message).into_response()
     }
 }
 
@@ -104,6 +144,6 @@ where
     E: Into<anyhow::Error>,
 {
     fn from(err: E) -> Self {
-        Self(err.into())
+        Self(StatusCode::INTERNAL_SERVER_ERROR, err.into())
     }
 }
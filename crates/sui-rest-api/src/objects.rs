@@ -3,40 +3,114 @@
 
 use std::sync::Arc;
 
-use axum::extract::{Path, State};
+use axum::{
+    extract::{Path, State},
+    TypedHeader,
+};
+use fastcrypto::encoding::Base64;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 use sui_core::authority::AuthorityState;
 use sui_types::{
-    base_types::{ObjectID, SequenceNumber},
-    object::Object,
+    base_types::{ObjectDigest, ObjectID, SequenceNumber, TransactionDigest},
+    object::{Object, Owner},
     storage::ObjectStore,
 };
 
-use crate::{AppError, Bcs};
+use crate::{
+    content::{negotiate, ResponseContent, RestContent},
+    headers::{Accept, AcceptFormat},
+    proto, AppError,
+};
 
 pub const GET_OBJECT_PATH: &str = "/objects/:object_id";
 
 pub async fn get_object(
     Path(object_id): Path<ObjectID>,
+    accept: Option<TypedHeader<Accept>>,
     State(state): State<Arc<AuthorityState>>,
-) -> Result<Bcs<Object>, AppError> {
+) -> Result<ResponseContent<Object>, AppError> {
+    let format = negotiate::<Object>(accept)?;
     let object = state
         .database
         .get_object(&object_id)?
-        .ok_or_else(|| anyhow::anyhow!("object not found"))?;
+        .ok_or_else(|| AppError::not_found("object not found"))?;
 
-    Ok(Bcs(object))
+    Ok(ResponseContent::new(format, object))
 }
 
 pub const GET_OBJECT_WITH_VERSION_PATH: &str = "/objects/:object_id/version/:version";
 
 pub async fn get_object_with_version(
     Path((object_id, version)): Path<(ObjectID, SequenceNumber)>,
+    accept: Option<TypedHeader<Accept>>,
     State(state): State<Arc<AuthorityState>>,
-) -> Result<Bcs<Object>, AppError> {
+) -> Result<ResponseContent<Object>, AppError> {
+    let format = negotiate::<Object>(accept)?;
     let object = state
         .database
         .get_object_by_key(&object_id, version)?
-        .ok_or_else(|| anyhow::anyhow!("object not found"))?;
+        .ok_or_else(|| AppError::not_found("object not found"))?;
+
+    Ok(ResponseContent::new(format, object))
+}
+
+/// An object, as returned in JSON.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectResponse {
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub owner: Owner,
+    /// The type of a Move object, absent for packages.
+    pub object_type: Option<String>,
+    pub previous_transaction: TransactionDigest,
+    pub storage_rebate: u64,
+    /// BCS encoded `Object`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub bcs: Vec<u8>,
+}
+
+impl RestContent for Object {
+    const NAME: &'static str = "Object";
+    const FORMATS: &'static [AcceptFormat] = &[
+        AcceptFormat::Json,
+        AcceptFormat::Bcs,
+        AcceptFormat::Protobuf,
+    ];
+
+    type Json = ObjectResponse;
+    type Proto = proto::Object;
+
+    fn into_json(self) -> anyhow::Result<ObjectResponse> {
+        let (object_id, version, digest) = self.compute_object_reference();
+        Ok(ObjectResponse {
+            object_id,
+            version,
+            digest,
+            owner: self.owner,
+            object_type: self.type_().map(|type_| type_.to_string()),
+            previous_transaction: self.previous_transaction,
+            storage_rebate: self.storage_rebate,
+            bcs: bcs::to_bytes(&self)?,
+        })
+    }
+
+    fn into_proto(self) -> anyhow::Result<proto::Object> {
+        let (object_id, version, digest) = self.compute_object_reference();
+        Ok(proto::Object {
+            object_id: object_id.to_vec(),
+            version: version.value(),
+            digest: digest.as_ref().to_vec(),
+            object_type: self.type_().map(|type_| type_.to_string()),
+            bcs: bcs::to_bytes(&self)?,
+        })
+    }
 
-    Ok(Bcs(object))
+    fn json_schema(gen: &mut SchemaGenerator) -> Option<Schema> {
+        Some(gen.subschema_for::<ObjectResponse>())
+    }
 }
@@ -0,0 +1,164 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use axum::Json;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use serde_json::{json, Map, Value};
+use sui_types::{messages_checkpoint::CertifiedCheckpointSummary, object::Object};
+
+use crate::{
+    content::RestContent, headers::AcceptFormat, transactions::ExecutedTransaction, CheckpointData,
+};
+
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+pub async fn get_openapi() -> Json<Value> {
+    Json(openapi())
+}
+
+/// The OpenAPI document describing the endpoints of the REST API, and the schemas of the JSON
+/// responses.
+pub fn openapi() -> Value {
+    let mut gen = SchemaSettings::openapi3().into_generator();
+    let checkpoint = ("checkpoint", "Sequence number of the checkpoint", "integer");
+    let object_id = ("object_id", "ID of the object", "string");
+    let version = ("version", "Version of the object", "integer");
+    let transaction = ("transaction", "Digest of the transaction", "string");
+
+    let paths = json!({
+        "/checkpoints": operation::<CertifiedCheckpointSummary>(
+            &mut gen,
+            "Get the latest checkpoint summary",
+            &[],
+        ),
+        "/checkpoints/{checkpoint}": operation::<CertifiedCheckpointSummary>(
+            &mut gen,
+            "Get a checkpoint summary",
+            &[checkpoint],
+        ),
+        "/checkpoints/{checkpoint}/full": operation::<CheckpointData>(
+            &mut gen,
+            "Get a checkpoint, with its transactions and the objects they read and wrote",
+            &[checkpoint],
+        ),
+        "/objects/{object_id}": operation::<Object>(
+            &mut gen,
+            "Get the latest version of an object",
+            &[object_id],
+        ),
+        "/objects/{object_id}/version/{version}": operation::<Object>(
+            &mut gen,
+            "Get a version of an object",
+            &[object_id, version],
+        ),
+        "/transactions/{transaction}": operation::<ExecutedTransaction>(
+            &mut gen,
+            "Get an executed transaction, with its effects and events",
+            &[transaction],
+        ),
+    });
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Sui REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": gen.take_definitions(),
+        },
+    })
+}
+
+/// A `GET` operation returning a `T`, with path parameters given as their name, description and
+/// type.
+fn operation<T: RestContent>(
+    gen: &mut SchemaGenerator,
+    summary: &str,
+    parameters: &[(&str, &str, &str)],
+) -> Value {
+    let parameters: Vec<Value> = parameters
+        .iter()
+        .map(|(name, description, type_)| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "description": description,
+                "schema": { "type": type_ },
+            })
+        })
+        .collect();
+
+    let mut content = Map::new();
+    for format in T::FORMATS {
+        let schema = match format {
+            AcceptFormat::Json => {
+                let schema = T::json_schema(gen).expect("JSON is a supported format");
+                serde_json::to_value(schema).unwrap()
+            }
+            AcceptFormat::Bcs => json!({
+                "type": "string",
+                "format": "binary",
+                "description": format!("BCS encoded {}", T::NAME),
+            }),
+            AcceptFormat::Protobuf => json!({
+                "type": "string",
+                "format": "binary",
+                "description": format!("`sui.rest.{}` message of proto/rest.proto", T::NAME),
+            }),
+        };
+        content.insert(
+            format.content_type().to_owned(),
+            json!({ "schema": schema }),
+        );
+    }
+
+    json!({
+        "get": {
+            "summary": summary,
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "description": T::NAME,
+                    "content": content,
+                },
+                "404": { "description": "Not found" },
+                "406": { "description": "None of the accepted formats is supported" },
+            },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_openapi_schemas() {
+        let openapi = openapi();
+        let schemas = openapi["components"]["schemas"].as_object().unwrap();
+        for name in [
+            "ObjectResponse",
+            "CheckpointSummaryResponse",
+            "TransactionResponse",
+        ] {
+            assert!(schemas.contains_key(name), "missing schema {name}");
+        }
+
+        let full_checkpoint = &openapi["paths"]["/checkpoints/{checkpoint}/full"]["get"];
+        let content = full_checkpoint["responses"]["200"]["content"]
+            .as_object()
+            .unwrap();
+        assert!(!content.contains_key(crate::APPLICATION_JSON));
+        assert!(content.contains_key(crate::APPLICATION_BCS));
+        assert!(content.contains_key(crate::APPLICATION_PROTOBUF));
+
+        let object = &openapi["paths"]["/objects/{object_id}"]["get"];
+        assert_eq!(
+            object["responses"]["200"]["content"][crate::APPLICATION_JSON]["schema"]["$ref"],
+            "#/components/schemas/ObjectResponse"
+        );
+    }
+}
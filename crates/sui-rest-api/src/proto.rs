@@ -0,0 +1,50 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Protobuf messages of the REST API, kept in sync with `proto/rest.proto`.
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Object {
+    #[prost(bytes = "vec", tag = "1")]
+    pub object_id: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub digest: Vec<u8>,
+    #[prost(string, optional, tag = "4")]
+    pub object_type: Option<String>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub bcs: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CheckpointSummary {
+    #[prost(uint64, tag = "1")]
+    pub sequence_number: u64,
+    #[prost(uint64, tag = "2")]
+    pub epoch: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub digest: Vec<u8>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub bcs: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CheckpointData {
+    #[prost(uint64, tag = "1")]
+    pub sequence_number: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub bcs: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Transaction {
+    #[prost(bytes = "vec", tag = "1")]
+    pub digest: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub transaction: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub effects: Vec<u8>,
+    #[prost(bytes = "vec", optional, tag = "4")]
+    pub events: Option<Vec<u8>>,
+}
@@ -0,0 +1,126 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::{
+    extract::{Path, State},
+    TypedHeader,
+};
+use fastcrypto::encoding::Base64;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sui_core::authority::AuthorityState;
+use sui_types::{
+    base_types::TransactionDigest,
+    effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents},
+    transaction::Transaction,
+};
+
+use crate::{
+    content::{negotiate, ResponseContent, RestContent},
+    headers::{Accept, AcceptFormat},
+    proto, AppError,
+};
+
+pub const GET_TRANSACTION_PATH: &str = "/transactions/:transaction";
+
+pub async fn get_transaction(
+    Path(transaction_digest): Path<TransactionDigest>,
+    accept: Option<TypedHeader<Accept>>,
+    State(state): State<Arc<AuthorityState>>,
+) -> Result<ResponseContent<ExecutedTransaction>, AppError> {
+    let format = negotiate::<ExecutedTransaction>(accept)?;
+
+    let transaction = state
+        .database
+        .get_transaction_block(&transaction_digest)?
+        .ok_or_else(|| AppError::not_found("transaction not found"))?;
+    let effects = state
+        .database
+        .get_executed_effects(&transaction_digest)?
+        .ok_or_else(|| AppError::not_found("transaction not executed"))?;
+    let events = effects
+        .events_digest()
+        .map(|event_digest| state.get_transaction_events(event_digest))
+        .transpose()?;
+
+    Ok(ResponseContent::new(
+        format,
+        ExecutedTransaction {
+            transaction: transaction.into(),
+            effects,
+            events,
+        },
+    ))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExecutedTransaction {
+    pub transaction: Transaction,
+    /// The effects produced by executing this transaction
+    pub effects: TransactionEffects,
+    /// The events, if any, emitted by this transaction during execution
+    pub events: Option<TransactionEvents>,
+}
+
+/// An executed transaction, as returned in JSON.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct TransactionResponse {
+    pub digest: TransactionDigest,
+    /// BCS encoded `Transaction`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub transaction: Vec<u8>,
+    /// BCS encoded `TransactionEffects`.
+    #[serde_as(as = "Base64")]
+    #[schemars(with = "Base64")]
+    pub effects: Vec<u8>,
+    /// BCS encoded `TransactionEvents`, absent if the transaction emitted no events.
+    #[serde_as(as = "Option<Base64>")]
+    #[schemars(with = "Option<Base64>")]
+    pub events: Option<Vec<u8>>,
+}
+
+impl RestContent for ExecutedTransaction {
+    const NAME: &'static str = "Transaction";
+    const FORMATS: &'static [AcceptFormat] = &[
+        AcceptFormat::Json,
+        AcceptFormat::Bcs,
+        AcceptFormat::Protobuf,
+    ];
+
+    type Json = TransactionResponse;
+    type Proto = proto::Transaction;
+
+    fn into_json(self) -> Result<TransactionResponse> {
+        Ok(TransactionResponse {
+            digest: *self.transaction.digest(),
+            transaction: bcs::to_bytes(&self.transaction)?,
+            effects: bcs::to_bytes(&self.effects)?,
+            events: self.events.as_ref().map(bcs::to_bytes).transpose()?,
+        })
+    }
+
+    fn into_proto(self) -> Result<proto::Transaction> {
+        let TransactionResponse {
+            digest,
+            transaction,
+            effects,
+            events,
+        } = self.into_json()?;
+        Ok(proto::Transaction {
+            digest: digest.into_inner().to_vec(),
+            transaction,
+            effects,
+            events,
+        })
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Option<Schema> {
+        Some(gen.subschema_for::<TransactionResponse>())
+    }
+}
@@ -16,17 +16,19 @@ use crate::error::{Error, SuiRpcResult};
 use crate::RpcClient;
 use sui_json_rpc::api::GovernanceReadApiClient;
 use sui_json_rpc::api::{
-    CoinReadApiClient, IndexerApiClient, MoveUtilsClient, ReadApiClient, WriteApiClient,
+    CoinReadApiClient, ExtendedApiClient, IndexerApiClient, MoveUtilsClient, ReadApiClient,
+    WriteApiClient,
 };
 use sui_json_rpc_types::{
     Balance, Checkpoint, CheckpointId, Coin, CoinPage, DelegatedStake, DevInspectResults,
     DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
     ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
     SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
-    SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    SuiPastObjectResponse, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
+    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
+    TransactionFilter,
 };
-use sui_json_rpc_types::{CheckpointPage, SuiLoadedChildObjectsResponse};
+use sui_json_rpc_types::{CheckpointPage, EpochPage, SuiLoadedChildObjectsResponse};
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::event::EventID;
@@ -514,6 +516,22 @@ impl ReadApi {
             .await?)
     }
 
+    /// Return a paginated list of epochs with their validators, or an error upon failure.
+    ///
+    /// This method is only served by RPC nodes backed by an indexer.
+    pub async fn get_epochs(
+        &self,
+        cursor: Option<BigInt<u64>>,
+        limit: Option<usize>,
+        descending_order: bool,
+    ) -> SuiRpcResult<EpochPage> {
+        Ok(self
+            .api
+            .http
+            .get_epochs(cursor, limit, Some(descending_order))
+            .await?)
+    }
+
     /// Return the sequence number of the latest checkpoint that has been executed, or an error upon failure.
     pub async fn get_latest_checkpoint_sequence_number(
         &self,
@@ -558,6 +576,26 @@ impl ReadApi {
         )
     }
 
+    /// Return a stream of the effects of transactions matching the given filter, or an error upon failure.
+    ///
+    /// Subscription is only possible via WebSockets.
+    /// For a list of possible transaction filters, see [TransactionFilter].
+    pub async fn subscribe_transaction(
+        &self,
+        filter: TransactionFilter,
+    ) -> SuiRpcResult<impl Stream<Item = SuiRpcResult<SuiTransactionBlockEffects>>> {
+        match &self.api.ws {
+            Some(c) => {
+                let subscription: Subscription<SuiTransactionBlockEffects> =
+                    c.subscribe_transaction(filter).await?;
+                Ok(subscription.map(|item| Ok(item?)))
+            }
+            _ => Err(Error::Subscription(
+                "Subscription only supported by WebSocket client.".to_string(),
+            )),
+        }
+    }
+
     /// Return a map consisting of the move package name and the normalized module, or an error upon failure.
     pub async fn get_normalized_move_modules_by_package(
         &self,
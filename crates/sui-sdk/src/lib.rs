@@ -102,6 +102,7 @@ use crate::error::{Error, SuiRpcResult};
 pub mod apis;
 pub mod error;
 pub mod json_rpc_error;
+pub mod object_watcher;
 pub mod sui_client_config;
 pub mod wallet_context;
 pub const SUI_COIN_TYPE: &str = "0x2::sui::SUI";
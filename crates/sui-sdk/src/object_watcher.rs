@@ -0,0 +1,228 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+
+use futures::stream;
+use futures::StreamExt;
+use sui_json_rpc_types::{
+    SuiGetPastObjectRequest, SuiObjectData, SuiObjectDataOptions, SuiPastObjectResponse,
+    SuiTransactionBlockEffects, SuiTransactionBlockEffectsAPI, TransactionFilter,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+
+use crate::error::{Error, SuiRpcResult};
+use crate::SuiClient;
+
+/// How many transactions are remembered to not report the same transaction twice, as it can be
+/// received from several subscriptions.
+const SEEN_TRANSACTIONS_CAPACITY: usize = 1024;
+
+/// The change of a watched object by a transaction.
+#[derive(Debug, Clone)]
+pub struct ObjectDelta {
+    pub object_id: ObjectID,
+    /// The transaction changing the object
+    pub transaction: TransactionDigest,
+    /// The contents of the object before the transaction, `None` if it was created or unwrapped by it
+    pub before: Option<SuiObjectData>,
+    /// The contents of the object after the transaction, `None` if it was deleted or wrapped by it
+    pub after: Option<SuiObjectData>,
+}
+
+type DeltaCallback = Box<dyn Fn(&ObjectDelta) + Send + Sync>;
+
+/// Watches a set of objects, and invokes callbacks with their contents before and after each
+/// transaction changing them.
+///
+/// Transactions are received from subscriptions, so the client must be built with a WebSocket URL.
+/// The contents of the objects are then fetched at the versions read and written by the transaction.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use std::str::FromStr;
+/// use sui_sdk::object_watcher::ObjectWatcher;
+/// use sui_sdk::SuiClientBuilder;
+/// use sui_types::base_types::ObjectID;
+/// #[tokio::main]
+/// async fn main() -> Result<(), anyhow::Error> {
+///     let sui = SuiClientBuilder::default()
+///         .ws_url("ws://127.0.0.1:9000")
+///         .build_localnet()
+///         .await?;
+///     let object_id = ObjectID::from_str("0x0000....0000")?;
+///     ObjectWatcher::new(sui)
+///         .watch(object_id)
+///         .on_change(|delta| println!("{:?} -> {:?}", delta.before, delta.after))
+///         .run()
+///         .await?;
+///     Ok(())
+/// }
+/// ```
+pub struct ObjectWatcher {
+    client: SuiClient,
+    objects: BTreeSet<ObjectID>,
+    options: SuiObjectDataOptions,
+    callbacks: Vec<DeltaCallback>,
+}
+
+impl ObjectWatcher {
+    /// Create a watcher with no objects, fetching the full contents of the objects by default.
+    pub fn new(client: SuiClient) -> Self {
+        Self {
+            client,
+            objects: BTreeSet::new(),
+            options: SuiObjectDataOptions::full_content(),
+            callbacks: vec![],
+        }
+    }
+
+    /// Watch the given object.
+    pub fn watch(mut self, object_id: ObjectID) -> Self {
+        self.objects.insert(object_id);
+        self
+    }
+
+    /// Watch all the given objects.
+    pub fn watch_all(mut self, object_ids: impl IntoIterator<Item = ObjectID>) -> Self {
+        self.objects.extend(object_ids);
+        self
+    }
+
+    /// Set the options used to fetch the contents of the objects.
+    pub fn with_options(mut self, options: SuiObjectDataOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Add a callback invoked with every change of a watched object.
+    pub fn on_change(mut self, callback: impl Fn(&ObjectDelta) + Send + Sync + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// Watch the objects until the subscriptions end, or an error upon failure.
+    ///
+    /// Every object is watched with two subscriptions: one for the transactions taking it as
+    /// input, which catches mutations and deletions, and one for the transactions changing it,
+    /// which also catches creations and unwraps.
+    pub async fn run(self) -> SuiRpcResult<()> {
+        let mut subscriptions = vec![];
+        for object_id in &self.objects {
+            for filter in [
+                TransactionFilter::InputObject(*object_id),
+                TransactionFilter::ChangedObject(*object_id),
+            ] {
+                let subscription = self.client.read_api().subscribe_transaction(filter).await?;
+                subscriptions.push(subscription.boxed());
+            }
+        }
+
+        let mut effects_stream = stream::select_all(subscriptions);
+        let mut seen = SeenTransactions::default();
+        while let Some(effects) = effects_stream.next().await {
+            let effects = effects?;
+            if !seen.insert(*effects.transaction_digest()) {
+                continue;
+            }
+            for delta in self.deltas(&effects).await? {
+                for callback in &self.callbacks {
+                    callback(&delta);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the changes of the watched objects by the transaction with the given effects, or an
+    /// error upon failure.
+    pub async fn deltas(
+        &self,
+        effects: &SuiTransactionBlockEffects,
+    ) -> SuiRpcResult<Vec<ObjectDelta>> {
+        // the versions of the objects before and after the transaction
+        let mut versions: BTreeMap<ObjectID, (Option<SequenceNumber>, Option<SequenceNumber>)> =
+            BTreeMap::new();
+        for (object_id, version) in effects.modified_at_versions() {
+            if self.objects.contains(&object_id) {
+                versions.entry(object_id).or_default().0 = Some(version);
+            }
+        }
+        for (object_ref, _) in effects.all_changed_objects() {
+            let object_id = object_ref.object_id();
+            if self.objects.contains(&object_id) {
+                versions.entry(object_id).or_default().1 = Some(object_ref.reference.version);
+            }
+        }
+
+        let requests = versions
+            .iter()
+            .flat_map(|(object_id, (before, after))| {
+                before
+                    .iter()
+                    .chain(after)
+                    .map(|version| SuiGetPastObjectRequest {
+                        object_id: *object_id,
+                        version: *version,
+                    })
+            })
+            .collect::<Vec<_>>();
+        if requests.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut contents = BTreeMap::new();
+        for response in self
+            .client
+            .read_api()
+            .try_multi_get_parsed_past_object(requests, self.options.clone())
+            .await?
+        {
+            match response {
+                SuiPastObjectResponse::VersionFound(data) => {
+                    contents.insert((data.object_id, data.version), data);
+                }
+                response => {
+                    return Err(Error::DataError(format!(
+                        "Failed to fetch the contents of a watched object: {response:?}"
+                    )))
+                }
+            }
+        }
+
+        let transaction = *effects.transaction_digest();
+        Ok(versions
+            .into_iter()
+            .map(|(object_id, (before, after))| ObjectDelta {
+                object_id,
+                transaction,
+                before: before.and_then(|version| contents.remove(&(object_id, version))),
+                after: after.and_then(|version| contents.remove(&(object_id, version))),
+            })
+            .collect())
+    }
+}
+
+/// The digests of the most recently received transactions.
+#[derive(Default)]
+struct SeenTransactions {
+    order: VecDeque<TransactionDigest>,
+    digests: HashSet<TransactionDigest>,
+}
+
+impl SeenTransactions {
+    /// Record the digest, returning false if it was already recorded.
+    fn insert(&mut self, digest: TransactionDigest) -> bool {
+        if !self.digests.insert(digest) {
+            return false;
+        }
+        self.order.push_back(digest);
+        if self.order.len() > SEEN_TRANSACTIONS_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.digests.remove(&oldest);
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Group commit of RocksDB write batches.
+//!
+//! Every RocksDB write has a fixed cost (appending to the WAL and going through the write queue),
+//! which dominates when many small batches are written concurrently, as when a validator commits
+//! the outputs of many transactions at high TPS. The [`GroupCommitter`] writes the batches that
+//! are committed concurrently as a single batch:
+//!
+//! - Writes are serialized. Batches committed while a write is in progress are queued, and the
+//!   first of them to get its turn writes all the queued batches at once, as one atomic batch.
+//! - Under load, i.e. when the previous group had more than one batch, that writer waits for more
+//!   batches to join its group, until the oldest batch in the group has waited for the latency
+//!   target or the group is full. Batches committed one at a time are written right away, so
+//!   grouping never delays writes on an idle store.
+//!
+//! Each batch is still written atomically, and `commit` only returns once its batch is durable as
+//! configured by the batch's write options, as a group is written with the strictest options of
+//! its batches. Batches must be [`appendable`](DBBatch::appendable). Once queued, a batch is
+//! written even if the future committing it is dropped.
+
+use mysten_metrics::histogram::Histogram;
+use parking_lot::Mutex;
+use prometheus::Registry;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Notify};
+use tokio::time::Instant;
+use typed_store::rocks::{DBBatch, TypedStoreError};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct GroupCommitConfig {
+    /// How long a batch may wait for other batches to join its group under load, in milliseconds.
+    #[serde(default = "default_latency_target_ms")]
+    pub latency_target_ms: u64,
+    /// The maximum number of batches written together.
+    #[serde(default = "default_max_group_size")]
+    pub max_group_size: usize,
+}
+
+fn default_latency_target_ms() -> u64 {
+    2
+}
+
+fn default_max_group_size() -> usize {
+    256
+}
+
+impl Default for GroupCommitConfig {
+    fn default() -> Self {
+        Self {
+            latency_target_ms: default_latency_target_ms(),
+            max_group_size: default_max_group_size(),
+        }
+    }
+}
+
+impl GroupCommitConfig {
+    pub fn latency_target(&self) -> Duration {
+        Duration::from_millis(self.latency_target_ms)
+    }
+}
+
+pub struct GroupCommitMetrics {
+    group_commit_batches: Histogram,
+    group_commit_bytes: Histogram,
+    group_commit_latency_us: Histogram,
+}
+
+impl GroupCommitMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            group_commit_batches: Histogram::new_in_registry(
+                "group_commit_batches",
+                "Number of batches written together by a group commit",
+                registry,
+            ),
+            group_commit_bytes: Histogram::new_in_registry(
+                "group_commit_bytes",
+                "Size in bytes of the batches written by a group commit",
+                registry,
+            ),
+            group_commit_latency_us: Histogram::new_in_registry(
+                "group_commit_latency_us",
+                "Time from a batch being committed until it is written, in microseconds",
+                registry,
+            ),
+        }
+    }
+}
+
+struct PendingCommit {
+    batch: DBBatch,
+    commit_time: Instant,
+    result: oneshot::Sender<Result<(), TypedStoreError>>,
+}
+
+pub struct GroupCommitter {
+    config: GroupCommitConfig,
+    pending: Mutex<Vec<PendingCommit>>,
+    /// Held while writing a group, so that batches committed meanwhile are queued.
+    write_lock: tokio::sync::Mutex<()>,
+    /// Notified when a batch is queued, to wake up a writer waiting for its group to fill up.
+    queued: Notify,
+    /// The number of batches in the last group written, used to detect load.
+    last_group_size: AtomicUsize,
+    metrics: Arc<GroupCommitMetrics>,
+}
+
+impl GroupCommitter {
+    pub fn new(config: GroupCommitConfig, metrics: Arc<GroupCommitMetrics>) -> Self {
+        Self {
+            config,
+            pending: Mutex::new(Vec::new()),
+            write_lock: tokio::sync::Mutex::new(()),
+            queued: Notify::new(),
+            last_group_size: AtomicUsize::new(0),
+            metrics,
+        }
+    }
+
+    /// Writes `batch`, possibly together with batches committed concurrently. Returns once it is
+    /// written. Batches committed by the same caller are written in order.
+    pub async fn commit(&self, batch: DBBatch) -> Result<(), TypedStoreError> {
+        let (sender, mut receiver) = oneshot::channel();
+        self.pending.lock().push(PendingCommit {
+            batch,
+            commit_time: Instant::now(),
+            result: sender,
+        });
+        self.queued.notify_one();
+
+        let _write_guard = self.write_lock.lock().await;
+        loop {
+            // The batch may have been written by another group while waiting for the lock.
+            if let Ok(result) = receiver.try_recv() {
+                return result;
+            }
+            self.wait_for_group().await;
+            let group: Vec<_> = {
+                let mut pending = self.pending.lock();
+                let size = pending.len().min(self.config.max_group_size);
+                pending.drain(..size).collect()
+            };
+            self.write_group(group);
+        }
+    }
+
+    async fn wait_for_group(&self) {
+        if self.last_group_size.load(Ordering::Relaxed) <= 1 {
+            return;
+        }
+        loop {
+            let notified = self.queued.notified();
+            let deadline = {
+                let pending = self.pending.lock();
+                match pending.first() {
+                    Some(oldest) if pending.len() < self.config.max_group_size => {
+                        oldest.commit_time + self.config.latency_target()
+                    }
+                    _ => return,
+                }
+            };
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    fn write_group(&self, group: Vec<PendingCommit>) {
+        self.last_group_size.store(group.len(), Ordering::Relaxed);
+        self.metrics.group_commit_batches.report(group.len() as u64);
+
+        let mut batches = Vec::with_capacity(group.len());
+        let mut committers = Vec::with_capacity(group.len());
+        for commit in group {
+            batches.push(commit.batch);
+            committers.push((commit.commit_time, commit.result));
+        }
+        let mut batches = batches.into_iter();
+        let Some(mut batch) = batches.next() else {
+            return;
+        };
+
+        let result = batch.append(batches).and_then(|()| {
+            self.metrics
+                .group_commit_bytes
+                .report(batch.size_in_bytes() as u64);
+            batch.write()
+        });
+        for (commit_time, sender) in committers {
+            self.metrics
+                .group_commit_latency_us
+                .report(commit_time.elapsed().as_micros() as u64);
+            // The committer may have gone away, which is fine as its batch is written.
+            let _ = sender.send(result.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use typed_store::rocks::{open_cf, DBMap, MetricConf, ReadWriteOptions};
+    use typed_store::Map;
+
+    fn open_table(path: &std::path::Path) -> DBMap<u64, u64> {
+        let rocks = open_cf(path, None, MetricConf::default(), &["table"]).unwrap();
+        DBMap::reopen(&rocks, Some("table"), &ReadWriteOptions::default()).unwrap()
+    }
+
+    fn batch(table: &DBMap<u64, u64>, key: u64) -> DBBatch {
+        let mut batch = table.batch().appendable();
+        batch.insert_batch(table, [(key, key)]).unwrap();
+        batch
+    }
+
+    /// Commits `count` batches while a write is in progress, and returns the sizes of the groups
+    /// they were written in.
+    async fn commit_concurrently(config: GroupCommitConfig, count: u64) -> usize {
+        let dir = tempfile::tempdir().unwrap();
+        let table = open_table(dir.path());
+        let committer = Arc::new(GroupCommitter::new(
+            config,
+            Arc::new(GroupCommitMetrics::new(&Registry::new())),
+        ));
+
+        let write_guard = committer.write_lock.lock().await;
+        let commits: Vec<_> = (0..count)
+            .map(|key| {
+                let committer = committer.clone();
+                let batch = batch(&table, key);
+                tokio::spawn(async move { committer.commit(batch).await })
+            })
+            .collect();
+        while committer.pending.lock().len() < count as usize {
+            tokio::task::yield_now().await;
+        }
+        drop(write_guard);
+
+        for commit in commits {
+            commit.await.unwrap().unwrap();
+        }
+        for key in 0..count {
+            assert_eq!(table.get(&key).unwrap(), Some(key));
+        }
+        committer.last_group_size.load(Ordering::Relaxed)
+    }
+
+    #[tokio::test]
+    async fn test_single_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = open_table(dir.path());
+        let committer = GroupCommitter::new(
+            GroupCommitConfig::default(),
+            Arc::new(GroupCommitMetrics::new(&Registry::new())),
+        );
+
+        committer.commit(batch(&table, 1)).await.unwrap();
+        committer.commit(batch(&table, 2)).await.unwrap();
+        assert_eq!(table.get(&1).unwrap(), Some(1));
+        assert_eq!(table.get(&2).unwrap(), Some(2));
+        assert_eq!(committer.last_group_size.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commits_are_grouped() {
+        assert_eq!(
+            commit_concurrently(GroupCommitConfig::default(), 100).await,
+            100
+        );
+    }
+
+    #[tokio::test]
+    async fn test_group_size_is_bounded() {
+        let config = GroupCommitConfig {
+            max_group_size: 4,
+            ..Default::default()
+        };
+        // 10 batches are written in groups of 4, 4 and 2.
+        assert_eq!(commit_concurrently(config, 10).await, 2);
+    }
+}
@@ -223,6 +223,16 @@ impl IndexStoreTables {
     pub fn coin_index(&self) -> &DBMap<CoinIndexKey, CoinInfo> {
         &self.coin_index
     }
+
+    pub fn transactions_to_addr(
+        &self,
+    ) -> &DBMap<(SuiAddress, TxSequenceNumber), TransactionDigest> {
+        &self.transactions_to_addr
+    }
+
+    pub fn transaction_order(&self) -> &DBMap<TxSequenceNumber, TransactionDigest> {
+        &self.transaction_order
+    }
 }
 
 pub struct IndexStore {
@@ -22,6 +22,7 @@ use sui_types::storage::{ReadStore, WriteStore};
 use tracing::debug;
 
 pub mod blob;
+pub mod group_commit;
 pub mod http_key_value_store;
 pub mod key_value_store;
 pub mod key_value_store_metrics;
@@ -236,7 +236,8 @@ impl SurferState {
                             .insert(obj_ref);
                     }
                 }
-                Owner::ObjectOwner(_) => (),
+                // Consensus-owned objects cannot be used as arguments yet
+                Owner::ObjectOwner(_) | Owner::ConsensusV2 { .. } => (),
                 Owner::Shared {
                     initial_shared_version,
                 } => {
@@ -85,7 +85,8 @@ impl SurferTask {
                                     }
                                 }
                             }
-                            Owner::ObjectOwner(_) => (),
+                            // Consensus-owned objects cannot be used as arguments yet
+                            Owner::ObjectOwner(_) | Owner::ConsensusV2 { .. } => (),
                         }
                     }
                 }
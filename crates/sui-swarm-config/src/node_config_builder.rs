@@ -89,6 +89,7 @@ impl ValidatorConfigBuilder {
             max_pending_transactions: None,
             max_submit_position: None,
             submit_delay_step_override_millis: None,
+            commit_timestamp_skew_alert_threshold_ms: None,
             narwhal_config: narwhal_config::Parameters {
                 network_admin_server: NetworkAdminServerParameters {
                     primary_network_admin_server_port: local_ip_utils::get_available_port(
@@ -168,6 +169,9 @@ impl ValidatorConfigBuilder {
                 .jwk_fetch_interval
                 .map(|i| i.as_secs())
                 .unwrap_or(3600),
+            execution_capture_config: None,
+            execution_concurrency_config: None,
+            group_commit_config: None,
         }
     }
 
@@ -403,6 +407,9 @@ impl FullnodeConfigBuilder {
             enable_experimental_rest_api: true,
             // note: not used by fullnodes.
             jwk_fetch_interval_seconds: 3600,
+            execution_capture_config: None,
+            execution_concurrency_config: None,
+            group_commit_config: None,
         }
     }
 }
@@ -304,6 +304,9 @@ impl std::fmt::Display for OwnerOutput {
             Owner::Shared { .. } => {
                 write!(f, "shared")
             }
+            Owner::ConsensusV2 { authenticator, .. } => {
+                write!(f, "consensus({})", authenticator.as_single_owner())
+            }
         }
     }
 }
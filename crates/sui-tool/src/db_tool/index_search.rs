@@ -13,7 +13,7 @@ use std::fmt::Debug;
 use sui_storage::IndexStoreTables;
 use sui_types::{
     base_types::{ObjectID, SuiAddress, TxSequenceNumber},
-    Identifier, TypeTag,
+    parse_sui_type_tag, Identifier,
 };
 
 #[derive(Clone, Debug)]
@@ -281,7 +281,7 @@ fn from_addr_str_oid(s: &str) -> Result<(SuiAddress, String, ObjectID), anyhow::
         return Err(anyhow!("Invalid addr, type tag object id triplet"));
     }
     let address = SuiAddress::from_str(tokens[0].trim())?;
-    let tag: TypeTag = TypeTag::from_str(tokens[1].trim())?;
+    let tag = parse_sui_type_tag(tokens[1])?;
     let oid: ObjectID = ObjectID::from_str(tokens[2].trim())?;
 
     Ok((address, tag.to_string(), oid))
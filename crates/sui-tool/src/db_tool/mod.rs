@@ -3,13 +3,16 @@
 
 use self::db_dump::{dump_table, duplicate_objects_summary, list_tables, table_summary, StoreName};
 use self::index_search::{search_index, SearchRange};
+use self::rebuild_indexes::rebuild_indexes;
 use crate::db_tool::db_dump::{compact, print_table_metadata, prune_checkpoints, prune_objects};
 use anyhow::{anyhow, bail};
 use clap::Parser;
 use narwhal_storage::NodeStorage;
 use std::path::{Path, PathBuf};
-use sui_core::authority::authority_per_epoch_store::AuthorityEpochTables;
+use std::time::Duration;
+use sui_core::authority::authority_per_epoch_store::{AuthorityEpochTables, PendingExecutionState};
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::AuthorityState;
 use sui_core::checkpoints::CheckpointStore;
 use sui_types::base_types::{EpochId, ObjectID, SequenceNumber};
 use sui_types::digests::{CheckpointContentsDigest, TransactionDigest};
@@ -20,6 +23,7 @@ use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 use typed_store::rocks::MetricConf;
 pub mod db_dump;
 mod index_search;
+mod rebuild_indexes;
 
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
@@ -38,11 +42,14 @@ pub enum DbToolCommand {
     PrintCheckpointContent(PrintCheckpointContentOptions),
     RemoveObjectLock(RemoveObjectLockOptions),
     RemoveTransaction(RemoveTransactionOptions),
+    PrintPendingExecution(PrintPendingExecutionOptions),
+    RequeuePendingExecution(RequeuePendingExecutionOptions),
     ResetDB,
     RewindCheckpointExecution(RewindCheckpointExecutionOptions),
     Compact,
     PruneObjects,
     PruneCheckpoints,
+    RebuildIndexes(RebuildIndexesOptions),
 }
 
 #[derive(Parser)]
@@ -137,6 +144,33 @@ pub struct RemoveTransactionOptions {
     epoch: Option<EpochId>,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct PrintPendingExecutionOptions {
+    /// The epoch to use when loading AuthorityEpochTables.
+    /// Defaults to the current epoch.
+    #[arg(long = "epoch", short = 'e')]
+    epoch: Option<EpochId>,
+}
+
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct RequeuePendingExecutionOptions {
+    #[arg(long, help = "The digests of the pending certificates to requeue")]
+    digest: Vec<TransactionDigest>,
+
+    #[arg(long, help = "Requeue all partially executed certificates")]
+    all_partially_executed: bool,
+
+    #[arg(long)]
+    confirm: bool,
+
+    /// The epoch to use when loading AuthorityEpochTables.
+    /// Defaults to the current epoch.
+    #[arg(long = "epoch", short = 'e')]
+    epoch: Option<EpochId>,
+}
+
 #[derive(Parser)]
 #[command(rename_all = "kebab-case")]
 pub struct RemoveObjectLockOptions {
@@ -160,6 +194,18 @@ pub struct RewindCheckpointExecutionOptions {
     checkpoint_sequence_number: u64,
 }
 
+#[derive(Parser)]
+#[command(rename_all = "kebab-case")]
+pub struct RebuildIndexesOptions {
+    /// The number of threads rebuilding the transaction-to-address index.
+    /// Defaults to the number of available cores.
+    #[arg(long)]
+    num_threads: Option<usize>,
+
+    #[arg(long)]
+    confirm: bool,
+}
+
 pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
@@ -186,12 +232,15 @@ pub async fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> an
         DbToolCommand::ResetDB => reset_db_to_genesis(&db_path),
         DbToolCommand::RemoveObjectLock(d) => remove_object_lock(&db_path, d),
         DbToolCommand::RemoveTransaction(d) => remove_transaction(&db_path, d),
+        DbToolCommand::PrintPendingExecution(d) => print_pending_execution(&db_path, d),
+        DbToolCommand::RequeuePendingExecution(d) => requeue_pending_execution(&db_path, d),
         DbToolCommand::RewindCheckpointExecution(d) => {
             rewind_checkpoint_execution(&db_path, d.epoch, d.checkpoint_sequence_number)
         }
         DbToolCommand::Compact => compact(db_path),
         DbToolCommand::PruneObjects => prune_objects(db_path).await,
         DbToolCommand::PruneCheckpoints => prune_checkpoints(db_path).await,
+        DbToolCommand::RebuildIndexes(d) => rebuild_secondary_indexes(&db_path, d),
         DbToolCommand::IndexSearchKeyRange(rg) => {
             let res = search_index(
                 db_path,
@@ -370,6 +419,102 @@ pub fn remove_transaction(path: &Path, opt: RemoveTransactionOptions) -> anyhow:
     Ok(())
 }
 
+/// Lists the certificates that are waiting to be executed, how far each got through execution
+/// and how long it has been waiting.
+/// Run with: cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live print-pending-execution
+pub fn print_pending_execution(
+    path: &Path,
+    opt: PrintPendingExecutionOptions,
+) -> anyhow::Result<()> {
+    let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None);
+    let epoch = if let Some(epoch) = opt.epoch {
+        epoch
+    } else {
+        get_sui_system_state(&perpetual_db)?.epoch()
+    };
+    let epoch_store = AuthorityEpochTables::open(epoch, &path.join("store"), None);
+    let audit =
+        epoch_store.audit_pending_execution(|digest| perpetual_db.is_tx_executed(digest))?;
+
+    let now_ms = AuthorityState::unixtime_now_ms();
+    for entry in &audit.entries {
+        let age = entry
+            .enqueued_at_ms
+            .map(|enqueued_at_ms| Duration::from_millis(now_ms.saturating_sub(enqueued_at_ms)));
+        println!(
+            "{:?}: {}, waiting for {:?}",
+            entry.digest,
+            entry.state.as_str(),
+            age
+        );
+    }
+    println!(
+        "{} certificates pending execution in epoch {}: {} pending, {} partially executed, {} executed. Oldest waiting for {:?}",
+        audit.entries.len(),
+        epoch,
+        audit.count(PendingExecutionState::Pending),
+        audit.count(PendingExecutionState::PartiallyExecuted),
+        audit.count(PendingExecutionState::Executed),
+        audit.oldest_age(now_ms),
+    );
+    Ok(())
+}
+
+/// Resets pending certificates that are stuck after an interrupted execution, so that they are
+/// executed from scratch when the node restarts. The node must be stopped.
+/// Dry run with: cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live requeue-pending-execution --all-partially-executed
+/// Add --confirm to actually requeue the certificates.
+pub fn requeue_pending_execution(
+    path: &Path,
+    opt: RequeuePendingExecutionOptions,
+) -> anyhow::Result<()> {
+    let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None);
+    let epoch = if let Some(epoch) = opt.epoch {
+        epoch
+    } else {
+        get_sui_system_state(&perpetual_db)?.epoch()
+    };
+    let epoch_store = AuthorityEpochTables::open(epoch, &path.join("store"), None);
+    let audit =
+        epoch_store.audit_pending_execution(|digest| perpetual_db.is_tx_executed(digest))?;
+
+    let mut to_requeue = vec![];
+    for entry in &audit.entries {
+        let requested = opt.digest.contains(&entry.digest)
+            || (opt.all_partially_executed
+                && entry.state == PendingExecutionState::PartiallyExecuted);
+        if !requested {
+            continue;
+        }
+        if entry.state == PendingExecutionState::Executed {
+            bail!(
+                "Certificate {:?} has already been executed and cannot be requeued!",
+                entry.digest
+            );
+        }
+        to_requeue.push(entry.digest);
+    }
+    for digest in &opt.digest {
+        if !to_requeue.contains(digest) {
+            bail!("Certificate {:?} is not pending execution!", digest);
+        }
+    }
+
+    println!(
+        "The following certificates will be requeued:\n{:#?}",
+        to_requeue
+    );
+    if opt.confirm {
+        println!("Proceeding to requeue certificates in 5s ..");
+        std::thread::sleep(std::time::Duration::from_secs(5));
+        for digest in &to_requeue {
+            epoch_store.requeue_pending_execution_subtle(digest)?;
+        }
+        println!("Done!");
+    }
+    Ok(())
+}
+
 pub fn remove_object_lock(path: &Path, opt: RemoveObjectLockOptions) -> anyhow::Result<()> {
     let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None);
     let key = ObjectKey(opt.id, SequenceNumber::from_u64(opt.version));
@@ -446,6 +591,28 @@ pub fn reset_db_to_genesis(path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Rebuilds the owner, coin and transaction-to-address indexes of a fullnode, to recover from
+/// their corruption without resyncing the node. The node must be stopped.
+/// Run with: cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live rebuild-indexes --confirm
+pub fn rebuild_secondary_indexes(path: &Path, opt: RebuildIndexesOptions) -> anyhow::Result<()> {
+    println!(
+        "Rebuilding the owner, coin and transaction-to-address indexes in {:?}",
+        path.join("indexes")
+    );
+    if !opt.confirm {
+        println!("Their current contents will be dropped. Run again with --confirm to proceed.");
+        return Ok(());
+    }
+    let num_threads = opt.num_threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+    });
+    rebuild_indexes(path, num_threads)?;
+    println!("Done!");
+    Ok(())
+}
+
 /// Force sets the highest executed checkpoint.
 /// NOTE: Does not force re-execution of transactions.
 /// Run with: cargo run --package sui-tool -- db-tool --db-path /opt/sui/db/authorities_db/live rewind-checkpoint-execution --epoch 3 --checkpoint-sequence-number 300000
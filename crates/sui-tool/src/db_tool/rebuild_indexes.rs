@@ -0,0 +1,180 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_storage::indexes::CoinInfo;
+use sui_storage::IndexStoreTables;
+use sui_types::base_types::{ObjectInfo, TxSequenceNumber};
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::object::Owner;
+use typed_store::rocks::MetricConf;
+use typed_store::traits::Map;
+
+/// Number of index entries written to the db in a single batch.
+const BATCH_SIZE: usize = 10_000;
+
+/// Recomputes the owner index, the coin index and the transaction-to-address index of a fullnode
+/// from the authoritative tables, replacing their current contents. The owner and coin indexes
+/// are derived from the live object set, while the transaction-to-address index is derived from
+/// the effects of the indexed transactions, split between `num_threads` threads by sequence
+/// number. The node must not be running.
+pub fn rebuild_indexes(path: &Path, num_threads: usize) -> anyhow::Result<()> {
+    let perpetual_db = AuthorityPerpetualTables::open(&path.join("store"), None);
+    let index_db = IndexStoreTables::open_tables_read_write(
+        path.join("indexes"),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    let num_transactions = index_db
+        .transaction_order()
+        .unbounded_iter()
+        .skip_to_last()
+        .next()
+        .map(|(seq, _)| seq + 1)
+        .unwrap_or(0);
+
+    index_db.owner_index().unsafe_clear()?;
+    index_db.coin_index().unsafe_clear()?;
+    index_db.transactions_to_addr().unsafe_clear()?;
+
+    let progress = MultiProgress::new();
+    let objects_bar = progress.add(ProgressBar::new_spinner().with_style(
+        ProgressStyle::with_template("[{elapsed_precise}] {spinner} {pos} live objects").unwrap(),
+    ));
+    let transactions_bar = progress.add(
+        ProgressBar::new(num_transactions).with_style(
+            ProgressStyle::with_template("[{elapsed_precise}] {wide_bar} {pos}/{len} transactions")
+                .unwrap(),
+        ),
+    );
+    let missing_effects = AtomicU64::new(0);
+
+    let chunk_size = (num_transactions / num_threads.max(1) as u64 + 1) as usize;
+    std::thread::scope(|s| {
+        let (perpetual_db, index_db) = (&perpetual_db, &index_db);
+        let (objects_bar, transactions_bar) = (&objects_bar, &transactions_bar);
+        let missing_effects = &missing_effects;
+
+        let objects = s.spawn(move || rebuild_object_indexes(perpetual_db, index_db, objects_bar));
+        let transactions: Vec<_> = (0..num_transactions)
+            .step_by(chunk_size)
+            .map(|start| {
+                let range = start..num_transactions.min(start + chunk_size as u64);
+                s.spawn(move || {
+                    rebuild_transactions_to_addr(
+                        perpetual_db,
+                        index_db,
+                        range,
+                        transactions_bar,
+                        missing_effects,
+                    )
+                })
+            })
+            .collect();
+
+        objects
+            .join()
+            .expect("Rebuilding object indexes panicked")?;
+        for handle in transactions {
+            handle
+                .join()
+                .expect("Rebuilding transaction-to-address index panicked")?;
+        }
+        Ok::<(), anyhow::Error>(())
+    })?;
+
+    objects_bar.finish_with_message("Owner and coin indexes are rebuilt");
+    transactions_bar.finish_with_message("Transaction-to-address index is rebuilt");
+    let missing_effects = missing_effects.load(Ordering::Relaxed);
+    if missing_effects > 0 {
+        println!(
+            "Skipped {missing_effects} indexed transactions whose effects were not found, \
+             probably because they were pruned"
+        );
+    }
+    Ok(())
+}
+
+fn rebuild_object_indexes(
+    perpetual_db: &AuthorityPerpetualTables,
+    index_db: &IndexStoreTables,
+    progress: &ProgressBar,
+) -> anyhow::Result<()> {
+    let mut batch = index_db.owner_index().batch();
+    let mut batch_size = 0;
+    for object in perpetual_db.iter_live_object_set(false) {
+        progress.inc(1);
+        let LiveObject::Normal(object) = object else {
+            continue;
+        };
+        let Owner::AddressOwner(owner) = object.owner else {
+            continue;
+        };
+
+        let object_info = ObjectInfo::new(&object.compute_object_reference(), &object);
+        batch.insert_batch(
+            index_db.owner_index(),
+            std::iter::once(((owner, object.id()), object_info)),
+        )?;
+        if let Some(type_tag) = object.coin_type_maybe() {
+            let info =
+                CoinInfo::from_object(&object).expect("already checked that this is a coin type");
+            batch.insert_batch(
+                index_db.coin_index(),
+                std::iter::once(((owner, type_tag.to_string(), object.id()), info)),
+            )?;
+        }
+
+        batch_size += 1;
+        if batch_size >= BATCH_SIZE {
+            std::mem::replace(&mut batch, index_db.owner_index().batch()).write()?;
+            batch_size = 0;
+        }
+    }
+    batch.write()?;
+    Ok(())
+}
+
+fn rebuild_transactions_to_addr(
+    perpetual_db: &AuthorityPerpetualTables,
+    index_db: &IndexStoreTables,
+    range: Range<TxSequenceNumber>,
+    progress: &ProgressBar,
+    missing_effects: &AtomicU64,
+) -> anyhow::Result<()> {
+    let mut batch = index_db.transactions_to_addr().batch();
+    let mut batch_size = 0;
+    for (sequence, digest) in index_db.transaction_order().range_iter(range) {
+        progress.inc(1);
+        let Some(effects) = perpetual_db.get_effects(&digest)? else {
+            missing_effects.fetch_add(1, Ordering::Relaxed);
+            continue;
+        };
+
+        batch.insert_batch(
+            index_db.transactions_to_addr(),
+            effects
+                .all_changed_objects()
+                .into_iter()
+                .filter_map(|(_, owner, _)| {
+                    owner
+                        .get_address_owner_address()
+                        .ok()
+                        .map(|addr| ((addr, sequence), digest))
+                }),
+        )?;
+
+        batch_size += 1;
+        if batch_size >= BATCH_SIZE {
+            std::mem::replace(&mut batch, index_db.transactions_to_addr().batch()).write()?;
+            batch_size = 0;
+        }
+    }
+    batch.write()?;
+    Ok(())
+}
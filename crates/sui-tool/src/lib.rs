@@ -152,6 +152,9 @@ impl std::fmt::Display for OwnerOutput {
             Owner::Shared { .. } => {
                 write!(f, "shared")
             }
+            Owner::ConsensusV2 { authenticator, .. } => {
+                write!(f, "consensus({})", authenticator.as_single_owner())
+            }
         }
     }
 }
@@ -770,6 +773,7 @@ pub async fn download_db_snapshot(
                 &committee_store,
                 usize::MAX,
                 false,
+                None,
                 &Registry::default(),
             )
             .await?;
@@ -989,6 +993,7 @@ pub async fn state_sync_from_archive(
         &committee_store,
         usize::MAX,
         false,
+        None,
         &Registry::default(),
     )
     .await?;
@@ -30,8 +30,27 @@ use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
 use sui_types::transaction::{
     Argument, CallArg, Command, InputObjectKind, ObjectArg, TransactionData, TransactionKind,
 };
+use sui_types::transaction_analyzer::TransactionAnalysis;
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
+/// Checks the size and shape of `tx_data` against the limits in `protocol_config`, failing if it
+/// would be rejected, and otherwise returning the analysis, including any warnings about it.
+pub fn validate_transaction(
+    tx_data: &TransactionData,
+    protocol_config: &ProtocolConfig,
+) -> anyhow::Result<TransactionAnalysis> {
+    let analysis = TransactionAnalysis::new(tx_data, protocol_config);
+    if let Some(usage) = analysis.exceeded_limits().next() {
+        bail!(
+            "Transaction exceeds the limit on {}: {} > {}",
+            usage.limit,
+            usage.used,
+            usage.max.unwrap_or_default()
+        );
+    }
+    Ok(analysis)
+}
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -346,6 +365,9 @@ impl TransactionBuilder {
             Owner::AddressOwner(_) | Owner::ObjectOwner(_) | Owner::Immutable => {
                 ObjectArg::ImmOrOwnedObject(obj_ref)
             }
+            Owner::ConsensusV2 { .. } => {
+                bail!("Consensus-owned object {id} cannot be used as an argument yet")
+            }
         })
     }
 
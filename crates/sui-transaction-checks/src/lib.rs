@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod deny;
+pub mod verifier_cache;
 
 pub use checked::*;
 
@@ -35,6 +36,8 @@ mod checked {
     use tracing::error;
     use tracing::instrument;
 
+    use crate::verifier_cache::VerifierCache;
+
     // Entry point for all checks related to gas.
     // Called on both signing and execution.
     // On success the gas part of the transaction (gas data and gas coins)
@@ -66,6 +69,7 @@ mod checked {
         transaction: &TransactionData,
         transaction_deny_config: &TransactionDenyConfig,
         metrics: &Arc<BytecodeVerifierMetrics>,
+        verifier_cache: &VerifierCache,
     ) -> SuiResult<(SuiGasStatus, InputObjects)> {
         transaction.check_version_supported(protocol_config)?;
         transaction.validity_check(protocol_config)?;
@@ -80,7 +84,12 @@ mod checked {
         )?;
 
         // Runs verifier, which could be expensive.
-        check_non_system_packages_to_be_published(transaction, protocol_config, metrics)?;
+        check_non_system_packages_to_be_published(
+            transaction,
+            protocol_config,
+            metrics,
+            verifier_cache,
+        )?;
 
         let objects = check_input_objects(store, &input_objects, protocol_config)?;
         let gas_status = get_gas_status(
@@ -109,10 +118,16 @@ mod checked {
         transaction: &TransactionData,
         gas_object: Object,
         metrics: &Arc<BytecodeVerifierMetrics>,
+        verifier_cache: &VerifierCache,
     ) -> SuiResult<(SuiGasStatus, InputObjects)> {
         transaction.check_version_supported(protocol_config)?;
         transaction.validity_check_no_gas_check(protocol_config)?;
-        check_non_system_packages_to_be_published(transaction, protocol_config, metrics)?;
+        check_non_system_packages_to_be_published(
+            transaction,
+            protocol_config,
+            metrics,
+            verifier_cache,
+        )?;
         let receiving_objects = transaction.receiving_objects();
         let mut input_objects = transaction.input_objects()?;
         let mut objects = check_input_objects(store, &input_objects, protocol_config)?;
@@ -293,6 +308,10 @@ mod checked {
                         object_id: *object_id
                     }
                     .into()),
+                    Owner::ConsensusV2 { .. } => fp_bail!(UserInputError::Unsupported(format!(
+                        "Consensus-owned object {object_id} cannot be received"
+                    ))
+                    .into()),
                 };
             }
 
@@ -491,6 +510,11 @@ mod checked {
                         // specifies it as an owned object. This is inconsistent.
                         return Err(UserInputError::NotSharedObjectError);
                     }
+                    Owner::ConsensusV2 { .. } => {
+                        return Err(UserInputError::Unsupported(format!(
+                            "Consensus-owned object {object_id} cannot be used as an input yet"
+                        )));
+                    }
                 };
             }
             InputObjectKind::SharedMoveObject {
@@ -542,6 +566,12 @@ mod checked {
                             UserInputError::SharedObjectStartingVersionMismatch
                         )
                     }
+                    Owner::ConsensusV2 { .. } => {
+                        return Err(UserInputError::Unsupported(format!(
+                            "Consensus-owned object {} cannot be used as an input yet",
+                            object.id()
+                        )));
+                    }
                 }
             }
         };
@@ -554,6 +584,7 @@ mod checked {
         transaction: &TransactionData,
         protocol_config: &ProtocolConfig,
         metrics: &Arc<BytecodeVerifierMetrics>,
+        verifier_cache: &VerifierCache,
     ) -> UserInputResult<()> {
         // Only meter non-system programmable transaction blocks
         if transaction.is_system_tx() {
@@ -576,7 +607,14 @@ mod checked {
 
         let verifier_status = pt
             .non_system_packages_to_be_published()
-            .try_for_each(|module_bytes| verifier.meter_module_bytes(protocol_config, module_bytes))
+            .try_for_each(|module_bytes| {
+                verifier_cache.meter_module_bytes(
+                    verifier.as_mut(),
+                    protocol_config,
+                    module_bytes,
+                    metrics,
+                )
+            })
             .map_err(|e| UserInputError::PackageVerificationTimedout { err: e.to_string() });
 
         match verifier_status {
@@ -0,0 +1,148 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroUsize;
+
+use lru::LruCache;
+use parking_lot::Mutex;
+use sui_execution::verifier::Verifier;
+use sui_protocol_config::ProtocolConfig;
+use sui_types::error::SuiResult;
+use sui_types::metrics::BytecodeVerifierMetrics;
+use sui_types::move_package::MovePackage;
+
+// Number of package verification results to remember. Packages are usually re-verified because the
+// same publish or upgrade is submitted repeatedly (e.g. retried by a client, or signed and then
+// dry-run), so only a small working set needs to be kept.
+pub const DEFAULT_VERIFIER_CACHE_SIZE: usize = 1_000;
+
+/// Digest of the package's modules, and the protocol version, which determines the verifier's
+/// configuration (including its metering limits).
+type VerifierCacheKey = ([u8; 32], u64);
+
+/// Remembers the outcome of running the metered bytecode verifier over a package, so that the same
+/// package is not re-verified each time it shows up in a transaction. Both successes and failures
+/// are cached: verification is deterministic given the package's modules and the verifier config.
+pub struct VerifierCache {
+    inner: Mutex<LruCache<VerifierCacheKey, SuiResult<()>>>,
+}
+
+impl VerifierCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(LruCache::new(
+                NonZeroUsize::new(capacity).expect("Verifier cache capacity must be non-zero"),
+            )),
+        }
+    }
+
+    /// Meter the package made up of `module_bytes` with `verifier`, unless its result is already
+    /// cached for this protocol version.
+    pub fn meter_module_bytes(
+        &self,
+        verifier: &mut dyn Verifier,
+        protocol_config: &ProtocolConfig,
+        module_bytes: &[Vec<u8>],
+        metrics: &BytecodeVerifierMetrics,
+    ) -> SuiResult<()> {
+        let digest = MovePackage::compute_digest_for_modules_and_deps(
+            module_bytes,
+            [],
+            /* hash_modules */ true,
+        );
+        let key = (digest, protocol_config.version.as_u64());
+
+        if let Some(result) = self.inner.lock().get(&key) {
+            metrics.verifier_cache_hits.inc();
+            return result.clone();
+        }
+
+        metrics.verifier_cache_misses.inc();
+        let result = verifier.meter_module_bytes(protocol_config, module_bytes);
+        self.inner.lock().put(key, result.clone());
+        result
+    }
+}
+
+impl Default for VerifierCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_VERIFIER_CACHE_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    // These bytes do not deserialize to modules, so the verifier lets them through without metering
+    // them, but they still make up distinct packages to cache.
+    fn package(byte: u8) -> Vec<Vec<u8>> {
+        vec![vec![byte; 4]]
+    }
+
+    fn meter(
+        cache: &VerifierCache,
+        protocol_config: &ProtocolConfig,
+        module_bytes: &[Vec<u8>],
+        metrics: &Arc<BytecodeVerifierMetrics>,
+    ) {
+        let mut verifier =
+            sui_execution::verifier(protocol_config, /* is_metered */ true, metrics);
+        cache
+            .meter_module_bytes(verifier.as_mut(), protocol_config, module_bytes, metrics)
+            .unwrap();
+    }
+
+    fn metrics() -> Arc<BytecodeVerifierMetrics> {
+        Arc::new(BytecodeVerifierMetrics::new(&prometheus::Registry::new()))
+    }
+
+    #[test]
+    fn reuses_results_for_the_same_package() {
+        let metrics = metrics();
+        let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let cache = VerifierCache::default();
+
+        meter(&cache, &protocol_config, &package(1), &metrics);
+        meter(&cache, &protocol_config, &package(1), &metrics);
+        meter(&cache, &protocol_config, &package(2), &metrics);
+
+        assert_eq!(metrics.verifier_cache_misses.get(), 2);
+        assert_eq!(metrics.verifier_cache_hits.get(), 1);
+    }
+
+    #[test]
+    fn reverifies_packages_when_the_protocol_version_changes() {
+        let metrics = metrics();
+        let old_config = ProtocolConfig::get_for_min_version();
+        let new_config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let cache = VerifierCache::default();
+
+        meter(&cache, &old_config, &package(1), &metrics);
+        meter(&cache, &new_config, &package(1), &metrics);
+        assert_eq!(metrics.verifier_cache_misses.get(), 2);
+
+        meter(&cache, &new_config, &package(1), &metrics);
+        assert_eq!(metrics.verifier_cache_hits.get(), 1);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_packages() {
+        let metrics = metrics();
+        let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+        let cache = VerifierCache::new(2);
+
+        meter(&cache, &protocol_config, &package(1), &metrics);
+        meter(&cache, &protocol_config, &package(2), &metrics);
+        meter(&cache, &protocol_config, &package(1), &metrics);
+        assert_eq!(metrics.verifier_cache_hits.get(), 1);
+
+        // Package 2 is the least recently used, so it makes room for package 3.
+        meter(&cache, &protocol_config, &package(3), &metrics);
+        meter(&cache, &protocol_config, &package(1), &metrics);
+        assert_eq!(metrics.verifier_cache_hits.get(), 2);
+        meter(&cache, &protocol_config, &package(2), &metrics);
+        assert_eq!(metrics.verifier_cache_misses.get(), 4);
+    }
+}
@@ -50,6 +50,10 @@ pub struct SuiInitArgs {
     pub protocol_version: Option<u64>,
     #[clap(long = "max-gas")]
     pub max_gas: Option<u64>,
+    #[clap(long = "shared-object-deletion")]
+    pub shared_object_deletion: Option<bool>,
+    #[clap(long = "early-unused-result-check")]
+    pub early_unused_result_check: Option<bool>,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -293,6 +297,9 @@ impl SuiValue {
                 let obj_ref = obj.compute_object_reference();
                 Ok(ObjectArg::ImmOrOwnedObject(obj_ref))
             }
+            Owner::ConsensusV2 { .. } => {
+                bail!("Consensus-owned object {id} cannot be used as an argument yet")
+            }
         }
     }
 
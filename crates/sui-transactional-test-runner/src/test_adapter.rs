@@ -256,6 +256,8 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
                     accounts,
                     protocol_version,
                     max_gas,
+                    shared_object_deletion,
+                    early_unused_result_check,
                 },
             )) => {
                 let map = verify_and_create_named_address_mapping(named_addresses).unwrap();
@@ -270,6 +272,12 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter<'a> {
                 if let Some(mx_tx_gas_override) = max_gas {
                     protocol_config.set_max_tx_gas_for_testing(mx_tx_gas_override)
                 }
+                if let Some(enable) = shared_object_deletion {
+                    protocol_config.set_shared_object_deletion_for_testing(enable)
+                }
+                if let Some(enable) = early_unused_result_check {
+                    protocol_config.set_early_unused_result_check_for_testing(enable)
+                }
                 (map, accounts, protocol_config)
             }
             None => (
@@ -35,6 +35,7 @@ use crate::sui_serde::{to_sui_struct_tag_string, HexAccountAddress};
 use crate::transaction::Transaction;
 use crate::transaction::VerifiedTransaction;
 use crate::zk_login_authenticator::ZkLoginAuthenticator;
+use crate::zk_login_util::zk_login_address;
 use crate::MOVE_STDLIB_ADDRESS;
 use crate::SUI_CLOCK_OBJECT_ID;
 use crate::SUI_FRAMEWORK_ADDRESS;
@@ -44,7 +45,6 @@ use fastcrypto::encoding::decode_bytes_hex;
 use fastcrypto::encoding::{Encoding, Hex};
 use fastcrypto::hash::HashFunction;
 use fastcrypto::traits::AllowedRng;
-use fastcrypto_zkp::bn254::utils::big_int_str_to_bytes;
 use move_binary_format::binary_views::BinaryIndexedView;
 use move_binary_format::file_format::SignatureToken;
 use move_bytecode_utils::resolve_struct;
@@ -610,18 +610,11 @@ impl From<&MultiSigPublicKey> for SuiAddress {
     }
 }
 
-/// Sui address for [struct ZkLoginAuthenticator] is defined as the black2b hash of
-/// [zklogin_flag || iss_bytes_length || iss_bytes || address_seed in bytes] where
-/// AddressParams contains iss and aud string.
+/// Sui address for [struct ZkLoginAuthenticator] is derived from its iss and address seed, see
+/// [fn zk_login_address].
 impl From<&ZkLoginAuthenticator> for SuiAddress {
     fn from(authenticator: &ZkLoginAuthenticator) -> Self {
-        let mut hasher = DefaultHash::default();
-        hasher.update([SignatureScheme::ZkLoginAuthenticator.flag()]);
-        let iss_bytes = authenticator.get_iss().as_bytes();
-        hasher.update([iss_bytes.len() as u8]);
-        hasher.update(iss_bytes);
-        hasher.update(big_int_str_to_bytes(authenticator.get_address_seed()).unwrap());
-        SuiAddress(hasher.finalize().digest)
+        zk_login_address(authenticator.get_iss(), authenticator.get_address_seed()).unwrap()
     }
 }
 
@@ -0,0 +1,22 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Emit the test vectors for Sui's signed structures as JSON, for SDKs to check against.
+//!
+//! Usage: cargo run -p sui-types --features test-utils --bin generate-test-vectors [-- <OUTPUT>]
+//!
+//! Prints the vectors to stdout, or writes them to OUTPUT if one is given.
+
+use std::fs;
+
+use sui_types::test_vectors;
+
+fn main() {
+    let vectors = test_vectors::generate();
+    let content = serde_json::to_string_pretty(&vectors).unwrap();
+
+    match std::env::args().nth(1) {
+        Some(path) => fs::write(&path, content + "\n").unwrap(),
+        None => println!("{content}"),
+    }
+}
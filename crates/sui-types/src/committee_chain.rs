@@ -0,0 +1,175 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Following the committee handoffs from a trusted committee to the committees of later epochs,
+//! for clients that verify data offline, like light clients and bridges, without access to the
+//! on-chain system state.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::AuthorityName;
+use crate::committee::{Committee, EpochId, StakeUnit};
+use crate::error::{SuiError, SuiResult};
+use crate::messages_checkpoint::CertifiedCheckpointSummary;
+
+#[cfg(test)]
+#[path = "unit_tests/committee_chain_tests.rs"]
+mod committee_chain_tests;
+
+/// The last checkpoints of consecutive epochs, each certified by the committee of its epoch and
+/// carrying the committee of the next one. Starting from a trusted committee, they prove the
+/// committees of all the following epochs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommitteeChainProof {
+    pub end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+impl CommitteeChainProof {
+    pub fn new(end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>) -> Self {
+        Self {
+            end_of_epoch_checkpoints,
+        }
+    }
+
+    /// Serializes the proof into a blob that can be handed to [`CommitteeChain::verify_proof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Serializing a committee chain proof cannot fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SuiResult<Self> {
+        bcs::from_bytes(bytes).map_err(|e| SuiError::InvalidCommitteeChain {
+            error: format!("Failed to deserialize committee chain proof: {e}"),
+        })
+    }
+}
+
+/// Committees verified so far, keyed by epoch. The committee the chain starts from is trusted,
+/// every following one was handed off by an end of epoch checkpoint certified by its
+/// predecessor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitteeChain {
+    committees: BTreeMap<EpochId, Committee>,
+}
+
+impl CommitteeChain {
+    pub fn new(trusted_committee: Committee) -> Self {
+        Self {
+            committees: BTreeMap::from([(trusted_committee.epoch, trusted_committee)]),
+        }
+    }
+
+    /// Committee of the latest epoch in the chain.
+    pub fn latest_committee(&self) -> &Committee {
+        self.committees
+            .values()
+            .next_back()
+            .expect("Committee chain always contains its trusted committee")
+    }
+
+    pub fn committee(&self, epoch: EpochId) -> Option<&Committee> {
+        self.committees.get(&epoch)
+    }
+
+    pub fn committees(&self) -> impl Iterator<Item = &Committee> {
+        self.committees.values()
+    }
+
+    /// Checks that `checkpoint` is certified by the committee of its epoch.
+    pub fn verify_checkpoint(&self, checkpoint: &CertifiedCheckpointSummary) -> SuiResult {
+        checkpoint.verify_authority_signatures(self.committee_at(checkpoint.epoch())?)
+    }
+
+    /// Verifies `checkpoint` and, if it is the last checkpoint of its epoch, adds the committee
+    /// it hands off to. Returns whether the chain learned a new committee.
+    pub fn add_checkpoint(&mut self, checkpoint: &CertifiedCheckpointSummary) -> SuiResult<bool> {
+        self.verify_checkpoint(checkpoint)?;
+        let Some(next_committee) = checkpoint.handoff_committee() else {
+            return Ok(false);
+        };
+        if self.committees.contains_key(&next_committee.epoch) {
+            return Ok(false);
+        }
+        self.committees.insert(next_committee.epoch, next_committee);
+        Ok(true)
+    }
+
+    /// Extends the chain with the handoff in `checkpoint`, which must be the last checkpoint of
+    /// the latest epoch in the chain.
+    pub fn add_end_of_epoch_checkpoint(
+        &mut self,
+        checkpoint: &CertifiedCheckpointSummary,
+    ) -> SuiResult {
+        let latest_epoch = self.latest_committee().epoch;
+        if checkpoint.epoch() != latest_epoch {
+            return Err(SuiError::InvalidCommitteeChain {
+                error: format!(
+                    "Checkpoint {} is from epoch {}, expected the latest epoch {}",
+                    checkpoint.sequence_number(),
+                    checkpoint.epoch(),
+                    latest_epoch,
+                ),
+            });
+        }
+        if checkpoint.next_epoch_committee().is_none() {
+            return Err(SuiError::InvalidCommitteeChain {
+                error: format!(
+                    "Checkpoint {} is not the last checkpoint of epoch {}",
+                    checkpoint.sequence_number(),
+                    latest_epoch,
+                ),
+            });
+        }
+        self.add_checkpoint(checkpoint)?;
+        Ok(())
+    }
+
+    /// Follows the handoffs in `proof`, which must start at the latest epoch in the chain.
+    pub fn verify_proof(&mut self, proof: &CommitteeChainProof) -> SuiResult {
+        for checkpoint in &proof.end_of_epoch_checkpoints {
+            self.add_end_of_epoch_checkpoint(checkpoint)?;
+        }
+        Ok(())
+    }
+
+    /// Total stake of `signers` in the committee of `epoch`. Signers that are not members of the
+    /// committee have no stake, and every signer is counted once.
+    pub fn stake<'a>(
+        &self,
+        epoch: EpochId,
+        signers: impl IntoIterator<Item = &'a AuthorityName>,
+    ) -> SuiResult<StakeUnit> {
+        let committee = self.committee_at(epoch)?;
+        Ok(signers
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|signer| committee.weight(signer))
+            .sum())
+    }
+
+    /// Whether `signers` hold a quorum of the stake in the committee of `epoch`.
+    pub fn has_quorum<'a>(
+        &self,
+        epoch: EpochId,
+        signers: impl IntoIterator<Item = &'a AuthorityName>,
+    ) -> SuiResult<bool> {
+        Ok(self.stake(epoch, signers)? >= self.committee_at(epoch)?.quorum_threshold())
+    }
+
+    /// Whether `signers` hold enough stake in the committee of `epoch` to include at least one
+    /// honest validator.
+    pub fn has_validity<'a>(
+        &self,
+        epoch: EpochId,
+        signers: impl IntoIterator<Item = &'a AuthorityName>,
+    ) -> SuiResult<bool> {
+        Ok(self.stake(epoch, signers)? >= self.committee_at(epoch)?.validity_threshold())
+    }
+
+    fn committee_at(&self, epoch: EpochId) -> SuiResult<&Committee> {
+        self.committee(epoch)
+            .ok_or(SuiError::MissingCommitteeAtEpoch(epoch))
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::normalized;
+use move_bytecode_utils::layout::TypeLayoutBuilder;
+use move_core_types::{
+    language_storage::TypeTag,
+    value::{MoveTypeLayout, MoveValue},
+};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    error::{SuiError, SuiResult},
+    execution::TypeLayoutStore,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/dev_inspect_tests.rs"]
+mod dev_inspect_tests;
+
+/// Decodes the values returned by a call to a Move function in a dev-inspect transaction, checking
+/// them against the function's signature, instantiated with the type arguments of the call.
+#[derive(Clone, Debug)]
+pub struct ReturnValueDecoder {
+    types: Vec<TypeTag>,
+    layouts: Vec<MoveTypeLayout>,
+}
+
+impl ReturnValueDecoder {
+    /// Looks up the layouts of the return types of `function` in `store`. References are returned
+    /// by value by dev-inspect, so they are decoded as the type they refer to.
+    pub fn new(
+        function: &normalized::Function,
+        type_arguments: &[TypeTag],
+        store: &impl TypeLayoutStore,
+    ) -> SuiResult<Self> {
+        if function.type_parameters.len() != type_arguments.len() {
+            return Err(SuiError::TypeError {
+                error: format!(
+                    "Expected {} type arguments, got {}",
+                    function.type_parameters.len(),
+                    type_arguments.len()
+                ),
+            });
+        }
+
+        let type_arguments: Vec<_> = type_arguments
+            .iter()
+            .cloned()
+            .map(normalized::Type::from)
+            .collect();
+        let mut types = Vec::with_capacity(function.return_.len());
+        let mut layouts = Vec::with_capacity(function.return_.len());
+        for return_ in &function.return_ {
+            let type_ = match return_ {
+                normalized::Type::Reference(t) | normalized::Type::MutableReference(t) => {
+                    t.as_ref()
+                }
+                t => t,
+            };
+            let type_ = type_
+                .subst(&type_arguments)
+                .into_type_tag()
+                .ok_or_else(|| SuiError::TypeError {
+                    error: format!("Unsupported return type {return_}"),
+                })?;
+            let layout = TypeLayoutBuilder::build_with_types(&type_, &store).map_err(|e| {
+                SuiError::FailObjectLayout {
+                    st: format!("{type_}: {e}"),
+                }
+            })?;
+            types.push(type_);
+            layouts.push(layout);
+        }
+
+        Ok(Self { types, layouts })
+    }
+
+    /// The number of values returned by the function.
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// The type of the `index`-th value returned by the function.
+    pub fn type_(&self, index: usize) -> Option<&TypeTag> {
+        self.types.get(index)
+    }
+
+    /// Decodes `bytes`, the `index`-th value returned by the function, which dev-inspect reported
+    /// to be of type `type_`.
+    pub fn decode_value(
+        &self,
+        index: usize,
+        bytes: &[u8],
+        type_: &TypeTag,
+    ) -> SuiResult<MoveValue> {
+        let layout = self.check_type(index, type_)?;
+        MoveValue::simple_deserialize(bytes, layout).map_err(|e| {
+            SuiError::ObjectDeserializationError {
+                error: format!("Return value {index} is not a valid {type_}: {e}"),
+            }
+        })
+    }
+
+    /// Decodes `bytes`, the `index`-th value returned by the function, which dev-inspect reported
+    /// to be of type `type_`, into a `T`. The BCS encoding of `T` must match the layout of the
+    /// return type.
+    pub fn decode<T: DeserializeOwned>(
+        &self,
+        index: usize,
+        bytes: &[u8],
+        type_: &TypeTag,
+    ) -> SuiResult<T> {
+        self.decode_value(index, bytes, type_)?;
+        bcs::from_bytes(bytes).map_err(|e| SuiError::ObjectDeserializationError {
+            error: format!(
+                "Return value {index} cannot be decoded as a {}: {e}",
+                std::any::type_name::<T>()
+            ),
+        })
+    }
+
+    fn check_type(&self, index: usize, type_: &TypeTag) -> SuiResult<&MoveTypeLayout> {
+        let expected = self.types.get(index).ok_or_else(|| SuiError::TypeError {
+            error: format!(
+                "Function returns {} values, there is no return value {index}",
+                self.types.len()
+            ),
+        })?;
+        if expected != type_ {
+            return Err(SuiError::TypeError {
+                error: format!("Expected return value {index} of type {expected}, got {type_}"),
+            });
+        }
+        Ok(&self.layouts[index])
+    }
+}
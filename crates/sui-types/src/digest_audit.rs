@@ -0,0 +1,242 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Audit of the digests of the structures that Sui hashes: transactions, effects, events,
+//! checkpoints and objects.
+//!
+//! Digests are computed over the BCS encoding of these structures, so any change to their
+//! encoding, such as reordering the variants of an enum they contain, silently changes the digests
+//! of existing data. The audit builds a fixed instance of each structure, checks that its encoding
+//! and digest survive a serialization round-trip, and compares the digests against a snapshot
+//! file checked into the repository.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::io::Write;
+use std::path::Path;
+
+use move_core_types::ident_str;
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sui_protocol_config::ProtocolVersion;
+
+use crate::base_types::{ExecutionDigests, ObjectDigest, ObjectID, SuiAddress};
+use crate::crypto::default_hash;
+use crate::digests::{TransactionDigest, TransactionEventsDigest};
+use crate::effects::{TransactionEffects, TransactionEvents};
+use crate::event::Event;
+use crate::execution_status::{ExecutionFailureStatus, ExecutionStatus};
+use crate::gas::GasCostSummary;
+use crate::gas_coin::GAS;
+use crate::message_envelope::Message;
+use crate::messages_checkpoint::{CheckpointContents, CheckpointSummary};
+use crate::object::{MoveObject, Object, Owner, OBJECT_START_VERSION};
+use crate::test_vectors::{gas_object_ref, transfer_sui_data};
+use crate::transaction::SenderSignedData;
+use crate::utils::{keys, to_sender_signed_transaction};
+use crate::SUI_FRAMEWORK_ADDRESS;
+
+/// The digests of the audited structures, by name (`<kind>/<instance>`), as rendered in Base58.
+pub type DigestSnapshot = BTreeMap<String, String>;
+
+/// Build every audited structure and return its digest, panicking if the encoding or the digest
+/// of one of them does not survive a serialization round-trip.
+pub fn audit() -> DigestSnapshot {
+    let mut audit = DigestAudit::default();
+    let sender = SuiAddress::from(&keys()[0].public());
+
+    // Transactions
+    let data = transfer_sui_data(sender);
+    let tx_digest = TransactionDigest::new(default_hash(&data));
+    audit.record("transaction_data/transfer_sui", &data, |data| {
+        TransactionDigest::new(default_hash(data))
+    });
+    let signed = to_sender_signed_transaction(data, &keys()[0]).into_data();
+    audit.record("sender_signed_data/transfer_sui", &signed, SenderSignedData::digest);
+
+    // Events
+    let events = TransactionEvents {
+        data: vec![currency_created_event(sender)],
+    };
+    audit.record("transaction_events/currency_created", &events, |e| e.digest());
+
+    // Effects
+    let success = effects(tx_digest, ExecutionStatus::Success, Some(events.digest()));
+    audit.record("transaction_effects/success", &success, Message::digest);
+    let failure = effects(
+        tx_digest,
+        ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::InsufficientCoinBalance,
+            command: Some(0),
+        },
+        None,
+    );
+    audit.record("transaction_effects/failure", &failure, Message::digest);
+
+    // Checkpoints
+    let contents = CheckpointContents::new_with_causally_ordered_transactions([
+        ExecutionDigests::new(tx_digest, success.digest()),
+    ]);
+    audit.record("checkpoint_contents/single_transaction", &contents, |c| *c.digest());
+    let summary = CheckpointSummary::new(
+        /* epoch */ 0,
+        /* sequence_number */ 1,
+        /* network_total_transactions */ 2,
+        &contents,
+        /* previous_digest */ None,
+        GasCostSummary::new(1_000_000, 2_000_000, 1_500_000, 15_000),
+        /* end_of_epoch_data */ None,
+        /* timestamp_ms */ 1_700_000_000_000,
+    );
+    audit.record("checkpoint_summary/single_transaction", &summary, Message::digest);
+
+    // Objects, one for each kind of owner
+    let id = ObjectID::from_single_byte(0x99);
+    let objects = [
+        (
+            "address_owned",
+            Object::with_id_owner_gas_for_testing(id, sender, 1_000),
+        ),
+        (
+            "object_owned",
+            Object::with_object_owner_for_testing(id, ObjectID::from_single_byte(0x98)),
+        ),
+        (
+            "shared",
+            Object::new_move(
+                MoveObject::new_gas_coin(OBJECT_START_VERSION, id, 1_000),
+                Owner::Shared {
+                    initial_shared_version: OBJECT_START_VERSION,
+                },
+                TransactionDigest::genesis(),
+            ),
+        ),
+        ("immutable", Object::immutable_with_id_for_testing(id)),
+    ];
+    for (name, object) in &objects {
+        audit.record(&format!("object/{name}"), object, Object::digest);
+    }
+
+    audit.digests
+}
+
+/// Check the digests of the audited structures against the snapshot in `path`, which is created
+/// if it does not exist yet. Panics if the digest of a structure in the snapshot is missing or has
+/// changed. Structures that are new to the audit are added to the snapshot.
+pub fn check_digest_snapshot(path: &Path) {
+    let digests = audit();
+
+    match std::fs::read_to_string(path) {
+        Ok(content) => {
+            let snapshot: DigestSnapshot = serde_yaml::from_str(&content)
+                .unwrap_or_else(|e| panic!("Invalid digest snapshot {path:?}: {e}"));
+            for (name, expected) in snapshot {
+                match digests.get(&name) {
+                    None => panic!(
+                        "{name} is no longer audited. Removing structures from the digest \
+                         audit is not allowed."
+                    ),
+                    Some(actual) if actual == &expected => continue,
+                    Some(actual) => panic!(
+                        "The digest of {name} changed from {expected} to {actual}. The encoding \
+                         of a digest-bearing structure (or of one of its fields) changed, which \
+                         changes the digests of existing data."
+                    ),
+                }
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+        Err(e) => panic!("Error reading digest snapshot {path:?}: {e}"),
+    }
+
+    let mut file = std::fs::File::create(path).unwrap();
+    write!(file, "{}", serde_yaml::to_string(&digests).unwrap()).unwrap();
+}
+
+#[derive(Default)]
+struct DigestAudit {
+    digests: DigestSnapshot,
+}
+
+impl DigestAudit {
+    /// Record the digest of `value`, after checking that its encoding and digest are unchanged by
+    /// deserializing and serializing it again.
+    fn record<T, D>(&mut self, name: &str, value: &T, digest: impl Fn(&T) -> D)
+    where
+        T: Serialize + DeserializeOwned,
+        D: Display + PartialEq + std::fmt::Debug,
+    {
+        let bytes = bcs::to_bytes(value).expect("Audited structures must serialize");
+        let decoded: T = bcs::from_bytes(&bytes)
+            .unwrap_or_else(|e| panic!("Failed to deserialize {name}: {e}"));
+        assert_eq!(
+            bcs::to_bytes(&decoded).unwrap(),
+            bytes,
+            "The encoding of {name} changed after a round-trip",
+        );
+
+        let expected = digest(value);
+        assert_eq!(
+            digest(&decoded),
+            expected,
+            "The digest of {name} changed after a round-trip",
+        );
+        let previous = self.digests.insert(name.to_string(), expected.to_string());
+        assert!(previous.is_none(), "{name} is audited twice");
+    }
+}
+
+/// The event emitted when creating the currency for SUI.
+fn currency_created_event(sender: SuiAddress) -> Event {
+    let type_ = StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: ident_str!("coin").to_owned(),
+        name: ident_str!("CurrencyCreated").to_owned(),
+        type_params: vec![GAS::type_tag()],
+    };
+    let decimals: u8 = 9;
+    Event::new(
+        &SUI_FRAMEWORK_ADDRESS,
+        ident_str!("coin"),
+        sender,
+        type_,
+        bcs::to_bytes(&decimals).unwrap(),
+    )
+}
+
+/// Effects of the transaction with `digest`, which mutated its gas object.
+fn effects(
+    digest: TransactionDigest,
+    status: ExecutionStatus,
+    events_digest: Option<TransactionEventsDigest>,
+) -> TransactionEffects {
+    let (gas_id, gas_version, _) = gas_object_ref();
+    let gas_object = (
+        (gas_id, gas_version.next(), ObjectDigest::new([0x43; 32])),
+        Owner::AddressOwner(SuiAddress::from(&keys()[0].public())),
+    );
+
+    TransactionEffects::new_from_execution(
+        ProtocolVersion::MIN,
+        status,
+        /* executed_epoch */ 0,
+        GasCostSummary::new(1_000_000, 2_000_000, 1_500_000, 15_000),
+        /* modified_at_versions */ vec![(gas_id, gas_version)],
+        /* shared_objects */ vec![],
+        digest,
+        /* created */ vec![],
+        /* mutated */ vec![gas_object],
+        /* unwrapped */ vec![],
+        /* deleted */ vec![],
+        /* unwrapped_then_deleted */ vec![],
+        /* wrapped */ vec![],
+        gas_object,
+        events_digest,
+        /* dependencies */ vec![],
+    )
+}
+
+#[cfg(test)]
+#[path = "unit_tests/digest_audit_tests.rs"]
+mod digest_audit_tests;
@@ -0,0 +1,334 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A compact encoding of [`TransactionEffects`] for light clients and bridges that receive
+//! effects over constrained links.
+//!
+//! Effects repeat a lot of data: the same object IDs appear in `modified_at_versions` and in the
+//! lists of changed objects, every written object has the transaction's lamport version, and most
+//! objects are owned by the sender. The compact form stores each object ID, object digest and
+//! owner once, in tables that the rest of the effects index into, and encodes versions relative
+//! to the lamport version. It converts to and from the canonical form without loss, so the
+//! canonical effects, and their digest, can always be recovered from it.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use serde::{Deserialize, Serialize};
+
+use super::effects_v1::TransactionEffectsV1;
+use super::{TransactionEffects, TransactionEffectsAPI};
+use crate::base_types::{EpochId, ObjectDigest, ObjectID, ObjectRef, SequenceNumber};
+use crate::digests::{TransactionDigest, TransactionEventsDigest};
+use crate::error::{SuiError, SuiResult};
+use crate::execution_status::ExecutionStatus;
+use crate::gas::GasCostSummary;
+use crate::object::Owner;
+
+#[cfg(test)]
+#[path = "../unit_tests/compact_effects_tests.rs"]
+mod compact_effects_tests;
+
+/// [`TransactionEffects`] with deduplicated object IDs, object digests and owners, and
+/// delta-encoded versions. See the module documentation.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct CompactTransactionEffects {
+    status: ExecutionStatus,
+    executed_epoch: EpochId,
+    gas_used: GasCostSummary,
+    transaction_digest: TransactionDigest,
+    /// The highest version in the effects, which is the version of every object written by the
+    /// transaction. Other versions are encoded relative to it.
+    lamport_version: SequenceNumber,
+    object_ids: Vec<ObjectID>,
+    object_digests: Vec<ObjectDigest>,
+    owners: Vec<Owner>,
+    modified_at_versions: Vec<(u32, CompactVersion)>,
+    shared_objects: Vec<CompactObjectRef>,
+    created: Vec<(CompactObjectRef, u32)>,
+    mutated: Vec<(CompactObjectRef, u32)>,
+    unwrapped: Vec<(CompactObjectRef, u32)>,
+    deleted: Vec<CompactObjectRef>,
+    unwrapped_then_deleted: Vec<CompactObjectRef>,
+    wrapped: Vec<CompactObjectRef>,
+    gas_object: (CompactObjectRef, u32),
+    events_digest: Option<TransactionEventsDigest>,
+    dependencies: Vec<TransactionDigest>,
+}
+
+/// An object reference, with its ID and digest as indices into the tables of the effects.
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+struct CompactObjectRef {
+    id: u32,
+    version: CompactVersion,
+    digest: u32,
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
+enum CompactVersion {
+    /// The lamport version of the transaction.
+    Lamport,
+    /// Lower than the lamport version by this much.
+    Delta(u32),
+    /// Too far below the lamport version to be encoded as a delta.
+    Absolute(SequenceNumber),
+}
+
+/// Assigns each distinct value an index, in order of first appearance.
+struct Table<T> {
+    entries: Vec<T>,
+    indices: HashMap<T, u32>,
+}
+
+/// Builds the tables of a [`CompactTransactionEffects`] while encoding canonical effects.
+struct Encoder {
+    lamport_version: SequenceNumber,
+    object_ids: Table<ObjectID>,
+    object_digests: Table<ObjectDigest>,
+    owners: Table<Owner>,
+}
+
+/// Resolves the indices and versions of a [`CompactTransactionEffects`].
+struct Decoder<'a> {
+    effects: &'a CompactTransactionEffects,
+}
+
+impl CompactTransactionEffects {
+    pub fn new(effects: &TransactionEffects) -> Self {
+        let shared_objects: Vec<_> = effects
+            .input_shared_objects()
+            .into_iter()
+            .map(|(object_ref, _)| object_ref)
+            .collect();
+        let created = effects.created();
+        let mutated = effects.mutated();
+        let unwrapped = effects.unwrapped();
+        let deleted = effects.deleted();
+        let unwrapped_then_deleted = effects.unwrapped_then_deleted();
+        let wrapped = effects.wrapped();
+        let gas_object = effects.gas_object();
+
+        let lamport_version = shared_objects
+            .iter()
+            .chain(deleted.iter())
+            .chain(unwrapped_then_deleted.iter())
+            .chain(wrapped.iter())
+            .chain(
+                created
+                    .iter()
+                    .chain(mutated.iter())
+                    .chain(unwrapped.iter())
+                    .chain([&gas_object])
+                    .map(|(object_ref, _)| object_ref),
+            )
+            .map(|(_, version, _)| *version)
+            .max()
+            .unwrap_or_default();
+
+        let mut encoder = Encoder {
+            lamport_version,
+            object_ids: Table::new(),
+            object_digests: Table::new(),
+            owners: Table::new(),
+        };
+        let modified_at_versions = effects
+            .modified_at_versions()
+            .into_iter()
+            .map(|(id, version)| (encoder.object_ids.insert(id), encoder.version(version)))
+            .collect();
+        let shared_objects = encoder.refs(&shared_objects);
+        let created = encoder.owned_refs(&created);
+        let mutated = encoder.owned_refs(&mutated);
+        let unwrapped = encoder.owned_refs(&unwrapped);
+        let deleted = encoder.refs(&deleted);
+        let unwrapped_then_deleted = encoder.refs(&unwrapped_then_deleted);
+        let wrapped = encoder.refs(&wrapped);
+        let gas_object = encoder.owned_ref(&gas_object);
+
+        Self {
+            status: effects.status().clone(),
+            executed_epoch: effects.executed_epoch(),
+            gas_used: effects.gas_cost_summary().clone(),
+            transaction_digest: *effects.transaction_digest(),
+            lamport_version,
+            object_ids: encoder.object_ids.entries,
+            object_digests: encoder.object_digests.entries,
+            owners: encoder.owners.entries,
+            modified_at_versions,
+            shared_objects,
+            created,
+            mutated,
+            unwrapped,
+            deleted,
+            unwrapped_then_deleted,
+            wrapped,
+            gas_object,
+            events_digest: effects.events_digest().copied(),
+            dependencies: effects.dependencies().to_vec(),
+        }
+    }
+
+    /// Recovers the canonical form of the effects. Fails if the compact effects are malformed,
+    /// which can only happen if they were not produced by [`CompactTransactionEffects::new`].
+    pub fn to_effects(&self) -> SuiResult<TransactionEffects> {
+        let decoder = Decoder { effects: self };
+        let modified_at_versions = self
+            .modified_at_versions
+            .iter()
+            .map(|(id, version)| Ok((decoder.object_id(*id)?, decoder.version(*version)?)))
+            .collect::<SuiResult<_>>()?;
+
+        Ok(TransactionEffects::V1(TransactionEffectsV1::new(
+            self.status.clone(),
+            self.executed_epoch,
+            self.gas_used.clone(),
+            modified_at_versions,
+            decoder.refs(&self.shared_objects)?,
+            self.transaction_digest,
+            decoder.owned_refs(&self.created)?,
+            decoder.owned_refs(&self.mutated)?,
+            decoder.owned_refs(&self.unwrapped)?,
+            decoder.refs(&self.deleted)?,
+            decoder.refs(&self.unwrapped_then_deleted)?,
+            decoder.refs(&self.wrapped)?,
+            decoder.owned_ref(&self.gas_object)?,
+            self.events_digest,
+            self.dependencies.clone(),
+        )))
+    }
+
+    pub fn transaction_digest(&self) -> &TransactionDigest {
+        &self.transaction_digest
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Serializing compact effects cannot fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SuiResult<Self> {
+        bcs::from_bytes(bytes).map_err(|e| SuiError::InvalidCompactEffects {
+            error: format!("Failed to deserialize compact effects: {e}"),
+        })
+    }
+}
+
+impl From<&TransactionEffects> for CompactTransactionEffects {
+    fn from(effects: &TransactionEffects) -> Self {
+        Self::new(effects)
+    }
+}
+
+impl<T: Copy + Eq + Hash> Table<T> {
+    fn new() -> Self {
+        Self {
+            entries: vec![],
+            indices: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, value: T) -> u32 {
+        *self.indices.entry(value).or_insert_with(|| {
+            self.entries.push(value);
+            // Effects are limited to far fewer distinct values than this.
+            u32::try_from(self.entries.len() - 1).expect("Too many distinct values in effects")
+        })
+    }
+}
+
+impl Encoder {
+    fn version(&self, version: SequenceNumber) -> CompactVersion {
+        if version == self.lamport_version {
+            return CompactVersion::Lamport;
+        }
+        // The lamport version is the highest in the effects, so this cannot underflow.
+        match u32::try_from(self.lamport_version.value() - version.value()) {
+            Ok(delta) => CompactVersion::Delta(delta),
+            Err(_) => CompactVersion::Absolute(version),
+        }
+    }
+
+    fn object_ref(&mut self, (id, version, digest): &ObjectRef) -> CompactObjectRef {
+        CompactObjectRef {
+            id: self.object_ids.insert(*id),
+            version: self.version(*version),
+            digest: self.object_digests.insert(*digest),
+        }
+    }
+
+    fn owned_ref(&mut self, (object_ref, owner): &(ObjectRef, Owner)) -> (CompactObjectRef, u32) {
+        (self.object_ref(object_ref), self.owners.insert(*owner))
+    }
+
+    fn refs(&mut self, refs: &[ObjectRef]) -> Vec<CompactObjectRef> {
+        refs.iter().map(|r| self.object_ref(r)).collect()
+    }
+
+    fn owned_refs(&mut self, refs: &[(ObjectRef, Owner)]) -> Vec<(CompactObjectRef, u32)> {
+        refs.iter().map(|r| self.owned_ref(r)).collect()
+    }
+}
+
+impl Decoder<'_> {
+    fn entry<T: Copy>(table: &[T], index: u32, name: &str) -> SuiResult<T> {
+        table
+            .get(index as usize)
+            .copied()
+            .ok_or_else(|| SuiError::InvalidCompactEffects {
+                error: format!(
+                    "{name} index {index} out of bounds ({} entries)",
+                    table.len()
+                ),
+            })
+    }
+
+    fn object_id(&self, index: u32) -> SuiResult<ObjectID> {
+        Self::entry(&self.effects.object_ids, index, "Object ID")
+    }
+
+    fn version(&self, version: CompactVersion) -> SuiResult<SequenceNumber> {
+        let lamport_version = self.effects.lamport_version;
+        Ok(match version {
+            CompactVersion::Lamport => lamport_version,
+            CompactVersion::Delta(delta) => lamport_version
+                .value()
+                .checked_sub(delta as u64)
+                .map(SequenceNumber::from_u64)
+                .ok_or_else(|| SuiError::InvalidCompactEffects {
+                    error: format!(
+                        "Version delta {delta} exceeds lamport version {lamport_version}"
+                    ),
+                })?,
+            CompactVersion::Absolute(version) => version,
+        })
+    }
+
+    fn object_ref(&self, object_ref: &CompactObjectRef) -> SuiResult<ObjectRef> {
+        Ok((
+            self.object_id(object_ref.id)?,
+            self.version(object_ref.version)?,
+            Self::entry(
+                &self.effects.object_digests,
+                object_ref.digest,
+                "Object digest",
+            )?,
+        ))
+    }
+
+    fn owned_ref(
+        &self,
+        (object_ref, owner): &(CompactObjectRef, u32),
+    ) -> SuiResult<(ObjectRef, Owner)> {
+        Ok((
+            self.object_ref(object_ref)?,
+            Self::entry(&self.effects.owners, *owner, "Owner")?,
+        ))
+    }
+
+    fn refs(&self, refs: &[CompactObjectRef]) -> SuiResult<Vec<ObjectRef>> {
+        refs.iter().map(|r| self.object_ref(r)).collect()
+    }
+
+    fn owned_refs(&self, refs: &[(CompactObjectRef, u32)]) -> SuiResult<Vec<(ObjectRef, Owner)>> {
+        refs.iter().map(|r| self.owned_ref(r)).collect()
+    }
+}
@@ -124,10 +124,13 @@ impl TransactionEffectsAPI for TransactionEffectsV1 {
 
     fn input_shared_objects(&self) -> Vec<(ObjectRef, InputSharedObjectKind)> {
         let modified: HashSet<_> = self.modified_at_versions.iter().map(|(r, _)| r).collect();
+        let deleted: HashSet<_> = self.deleted.iter().map(|(id, _, _)| id).collect();
         self.shared_objects
             .iter()
             .map(|r| {
-                let kind = if modified.contains(&r.0) {
+                let kind = if deleted.contains(&r.0) {
+                    InputSharedObjectKind::MutateDeleted
+                } else if modified.contains(&r.0) {
                     InputSharedObjectKind::Mutate
                 } else {
                     InputSharedObjectKind::ReadOnly
@@ -199,6 +202,10 @@ impl TransactionEffectsAPI for TransactionEffectsV1 {
             InputSharedObjectKind::Mutate => {
                 self.modified_at_versions.push((obj_ref.0, obj_ref.1));
             }
+            InputSharedObjectKind::MutateDeleted => {
+                self.modified_at_versions.push((obj_ref.0, obj_ref.1));
+                self.deleted.push(obj_ref);
+            }
             InputSharedObjectKind::ReadOnly => (),
         }
     }
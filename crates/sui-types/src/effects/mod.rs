@@ -23,7 +23,12 @@ use serde::{Deserialize, Serialize};
 use shared_crypto::intent::IntentScope;
 use sui_protocol_config::{ProtocolConfig, ProtocolVersion, SupportedProtocolVersions};
 
+mod compact;
 mod effects_v1;
+mod object_change;
+
+pub use compact::CompactTransactionEffects;
+pub use object_change::{ObjectChange, ObjectChangeKind};
 
 // Since `std::mem::size_of` may not be stable across platforms, we use rough constants
 // We need these for estimating effects sizes
@@ -265,6 +270,8 @@ impl TransactionEffects {
 pub enum InputSharedObjectKind {
     Mutate,
     ReadOnly,
+    /// The shared object was taken by value and deleted by the transaction.
+    MutateDeleted,
 }
 
 #[enum_dispatch]
@@ -276,8 +283,7 @@ pub trait TransactionEffectsAPI {
     /// Returns the list of shared objects used in the input, with full object reference
     /// and use kind. This is needed in effects because in transaction we only have object ID
     /// for shared objects. Their version and digest can only be figured out after sequencing.
-    /// Also provides the use kind to indicate whether the object was mutated or read-only.
-    /// Down the road it could also indicate use-of-deleted.
+    /// Also provides the use kind to indicate whether the object was mutated, deleted or read-only.
     fn input_shared_objects(&self) -> Vec<(ObjectRef, InputSharedObjectKind)>;
     fn created(&self) -> Vec<(ObjectRef, Owner)>;
     fn mutated(&self) -> Vec<(ObjectRef, Owner)>;
@@ -0,0 +1,127 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use crate::base_types::{ObjectID, ObjectRef, SequenceNumber};
+use crate::object::{Object, Owner};
+
+use super::{TransactionEffects, TransactionEffectsAPI};
+
+#[cfg(test)]
+#[path = "../unit_tests/object_change_tests.rs"]
+mod object_change_tests;
+
+/// How a transaction changed an object, as classified from its effects and input objects.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum ObjectChangeKind {
+    Created {
+        owner: Owner,
+    },
+    /// Modified in place, without changing its owner (or with an owner that is not known because
+    /// its input object was not provided).
+    Mutated {
+        owner: Owner,
+        previous_version: SequenceNumber,
+    },
+    /// Modified, and given a new owner.
+    Transferred {
+        old_owner: Owner,
+        new_owner: Owner,
+        previous_version: SequenceNumber,
+    },
+    /// Taken out of another object, and available in the object store again.
+    Unwrapped {
+        owner: Owner,
+    },
+    Deleted,
+    /// Put inside another object, and no longer available in the object store.
+    Wrapped,
+    /// Taken out of another object, and deleted in the same transaction.
+    UnwrappedThenDeleted,
+}
+
+/// An object touched by a transaction, and how it was changed.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ObjectChange {
+    /// Reference to the object after the transaction, or to its tombstone if it was deleted or
+    /// wrapped.
+    pub object_ref: ObjectRef,
+    pub kind: ObjectChangeKind,
+}
+
+impl TransactionEffects {
+    /// Classifies every object changed by the transaction. `input_objects` are the objects as they
+    /// were before the transaction, and are used to tell objects that were transferred apart from
+    /// objects that were only mutated. Mutated objects that are missing from `input_objects` are
+    /// classified as `Mutated`.
+    ///
+    /// Objects are listed in the same order as `all_changed_objects` followed by
+    /// `all_removed_objects`, then unwrapped-then-deleted objects.
+    pub fn object_changes<'a>(
+        &self,
+        input_objects: impl IntoIterator<Item = &'a Object>,
+    ) -> Vec<ObjectChange> {
+        let old_owners: BTreeMap<ObjectID, Owner> = input_objects
+            .into_iter()
+            .map(|o| (o.id(), o.owner))
+            .collect();
+        let modified_at_versions: BTreeMap<_, _> =
+            self.modified_at_versions().into_iter().collect();
+
+        let mutated = self.mutated().into_iter().map(|(object_ref, owner)| {
+            // The previous version is always known for mutated objects.
+            let previous_version = modified_at_versions
+                .get(&object_ref.0)
+                .copied()
+                .unwrap_or_default();
+            let kind = match old_owners.get(&object_ref.0) {
+                Some(old_owner) if *old_owner != owner => ObjectChangeKind::Transferred {
+                    old_owner: *old_owner,
+                    new_owner: owner,
+                    previous_version,
+                },
+                _ => ObjectChangeKind::Mutated {
+                    owner,
+                    previous_version,
+                },
+            };
+            ObjectChange { object_ref, kind }
+        });
+        let created = self
+            .created()
+            .into_iter()
+            .map(|(object_ref, owner)| ObjectChange {
+                object_ref,
+                kind: ObjectChangeKind::Created { owner },
+            });
+        let unwrapped = self
+            .unwrapped()
+            .into_iter()
+            .map(|(object_ref, owner)| ObjectChange {
+                object_ref,
+                kind: ObjectChangeKind::Unwrapped { owner },
+            });
+        let removed = [
+            (self.deleted(), ObjectChangeKind::Deleted),
+            (self.wrapped(), ObjectChangeKind::Wrapped),
+            (
+                self.unwrapped_then_deleted(),
+                ObjectChangeKind::UnwrappedThenDeleted,
+            ),
+        ]
+        .into_iter()
+        .flat_map(|(object_refs, kind)| {
+            object_refs.into_iter().map(move |object_ref| ObjectChange {
+                object_ref,
+                kind: kind.clone(),
+            })
+        });
+
+        mutated
+            .chain(created)
+            .chain(unwrapped)
+            .chain(removed)
+            .collect()
+    }
+}
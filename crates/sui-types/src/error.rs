@@ -357,6 +357,14 @@ pub enum SuiError {
     },
     #[error("Signatures in a certificate must form a quorum")]
     CertificateRequiresQuorum,
+    #[error("Invalid finality proof: {error}")]
+    InvalidFinalityProof { error: String },
+    #[error("Invalid event proof: {error}")]
+    InvalidEventProof { error: String },
+    #[error("Invalid committee chain: {error}")]
+    InvalidCommitteeChain { error: String },
+    #[error("Invalid compact transaction effects: {error}")]
+    InvalidCompactEffects { error: String },
     #[error("Transaction certificate processing failed: {err}")]
     ErrorWhileProcessingCertificate { err: String },
     #[error(
@@ -0,0 +1,129 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-contained proofs that a transaction emitted an event, for off-chain consumers that need to
+//! convince a third party, knowing only the committee of the epoch the transaction executed in.
+
+use serde::{Deserialize, Serialize};
+
+use crate::committee::Committee;
+use crate::committee_chain::CommitteeChain;
+use crate::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
+use crate::error::{SuiError, SuiResult};
+use crate::event::{Event, EventID};
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+
+#[cfg(test)]
+#[path = "unit_tests/event_proof_tests.rs"]
+mod event_proof_tests;
+
+/// An event emitted by a transaction, together with the chain of commitments that proves it:
+/// the events of the transaction hash to the events digest of its effects, the contents of the
+/// checkpoint include the digest of the effects, and the committee signed the checkpoint, which
+/// commits to its contents.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EventProof {
+    /// The position of the proven event among the events of the transaction.
+    pub event_seq: u64,
+    pub events: TransactionEvents,
+    pub effects: TransactionEffects,
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub checkpoint_contents: CheckpointContents,
+}
+
+impl EventProof {
+    pub fn new(
+        event_seq: u64,
+        events: TransactionEvents,
+        effects: TransactionEffects,
+        checkpoint: CertifiedCheckpointSummary,
+        checkpoint_contents: CheckpointContents,
+    ) -> Self {
+        Self {
+            event_seq,
+            events,
+            effects,
+            checkpoint,
+            checkpoint_contents,
+        }
+    }
+
+    /// Serializes the proof into a blob that can be handed to [`verify_event_proof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Serializing an event proof cannot fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SuiResult<Self> {
+        bcs::from_bytes(bytes).map_err(|e| SuiError::InvalidEventProof {
+            error: format!("Failed to deserialize event proof: {e}"),
+        })
+    }
+
+    /// The ID of the proven event, which identifies it across the whole chain.
+    pub fn event_id(&self) -> EventID {
+        (*self.effects.transaction_digest(), self.event_seq).into()
+    }
+
+    /// The proven event, if the proof is well-formed. Only meaningful once the proof is verified.
+    pub fn event(&self) -> Option<&Event> {
+        usize::try_from(self.event_seq)
+            .ok()
+            .and_then(|seq| self.events.data.get(seq))
+    }
+
+    /// Checks that the checkpoint is signed by `committee`, that it includes the effects, and that
+    /// the effects commit to the events, returning the proven event.
+    pub fn verify(&self, committee: &Committee) -> SuiResult<&Event> {
+        self.checkpoint
+            .verify_with_contents(committee, Some(&self.checkpoint_contents))?;
+
+        let digests = self.effects.execution_digests();
+        if !self.checkpoint_contents.iter().any(|d| *d == digests) {
+            return Err(SuiError::InvalidEventProof {
+                error: format!(
+                    "Checkpoint {} does not include transaction {} with effects {}",
+                    self.checkpoint.sequence_number(),
+                    digests.transaction,
+                    digests.effects,
+                ),
+            });
+        }
+
+        let events_digest = self.events.digest();
+        if self.effects.events_digest() != Some(&events_digest) {
+            return Err(SuiError::InvalidEventProof {
+                error: format!(
+                    "Effects of transaction {} do not commit to events {:?}",
+                    digests.transaction, events_digest,
+                ),
+            });
+        }
+
+        self.event().ok_or_else(|| SuiError::InvalidEventProof {
+            error: format!(
+                "Transaction {} emitted {} events, there is no event {}",
+                digests.transaction,
+                self.events.data.len(),
+                self.event_seq,
+            ),
+        })
+    }
+
+    /// Checks the proof against the committee that `chain` verified for the epoch of its
+    /// checkpoint.
+    pub fn verify_with_committee_chain(&self, chain: &CommitteeChain) -> SuiResult<&Event> {
+        let epoch = self.checkpoint.epoch();
+        let committee = chain
+            .committee(epoch)
+            .ok_or(SuiError::MissingCommitteeAtEpoch(epoch))?;
+        self.verify(committee)
+    }
+}
+
+/// Verifies a serialized [`EventProof`] against the committee of the epoch the transaction
+/// executed in, returning the ID of the event it proves and the event itself.
+pub fn verify_event_proof(bytes: &[u8], committee: &Committee) -> SuiResult<(EventID, Event)> {
+    let proof = EventProof::from_bytes(bytes)?;
+    let event = proof.verify(committee)?.clone();
+    Ok((proof.event_id(), event))
+}
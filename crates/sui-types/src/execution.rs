@@ -10,7 +10,7 @@ use move_core_types::{
 };
 use move_vm_types::loaded_data::runtime_types::Type;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     base_types::{ObjectID, SequenceNumber, SuiAddress},
@@ -99,6 +99,35 @@ pub struct ExecutionResultsV2 {
     pub deleted_object_ids: BTreeSet<ObjectID>,
     /// All Move events emitted in this transaction.
     pub user_events: Vec<Event>,
+    /// Objects wrapped or unwrapped in this transaction, and the objects they were wrapped in or
+    /// unwrapped from. Only populated when the `record_object_lineage` feature is enabled.
+    pub object_lineage: BTreeMap<ObjectID, ObjectLineage>,
+}
+
+/// Summary of the child objects (e.g. dynamic fields) that a transaction looked up in storage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChildObjectAccessStats {
+    /// Number of distinct child objects looked up, whether or not they existed. This is the count
+    /// that is checked against the object runtime's cached objects limit.
+    pub num_accessed: u64,
+    /// Number of child objects that existed and were loaded.
+    pub num_loaded: u64,
+    /// Total size in bytes of the child objects that were loaded.
+    pub loaded_bytes: u64,
+}
+
+/// How an object's reachability from storage changed in a transaction, by being wrapped in, or
+/// unwrapped from another object.
+///
+/// The container is the outermost object that holds the wrapped value in storage (e.g. the
+/// dynamic field of a `Bag`), or `None` if it could not be determined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectLineage {
+    /// The object existed in storage before the transaction, and was wrapped by it.
+    Wrapped { container: Option<ObjectID> },
+    /// The object was wrapped before the transaction, and was unwrapped by it, even if it was
+    /// subsequently deleted.
+    Unwrapped { container: Option<ObjectID> },
 }
 
 #[derive(Clone, Debug)]
@@ -182,6 +182,12 @@ pub enum ExecutionFailureStatus {
         Please run the Sui Move Verifier for more information."
     )]
     SuiMoveVerificationTimedout,
+
+    #[error(
+        "Shared object operation not allowed. \
+        Shared objects taken by value must be deleted or re-shared."
+    )]
+    SharedObjectOperationNotAllowed,
     // NOTE: if you want to add a new enum,
     // please add it at the end for Rust SDK backward compatibility.
 }
@@ -0,0 +1,100 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-contained proofs that a transaction is final, for light clients and bridges that need to
+//! check finality offline, knowing only the committee of the epoch the transaction executed in.
+
+use serde::{Deserialize, Serialize};
+
+use crate::base_types::ExecutionDigests;
+use crate::committee::Committee;
+use crate::committee_chain::CommitteeChain;
+use crate::effects::{CertifiedTransactionEffects, TransactionEffects, TransactionEffectsAPI};
+use crate::error::{SuiError, SuiResult};
+use crate::message_envelope::Message;
+use crate::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointContents};
+
+#[cfg(test)]
+#[path = "unit_tests/finality_proof_tests.rs"]
+mod finality_proof_tests;
+
+/// The effects certificate of a transaction, together with the certified checkpoint that
+/// includes it and the contents of that checkpoint, which prove the inclusion.
+///
+/// The effects certificate proves that a quorum of the committee executed the transaction with
+/// these effects, and the checkpoint proves that the transaction is settled in the canonical
+/// history of the chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FinalityProof {
+    pub effects: CertifiedTransactionEffects,
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub checkpoint_contents: CheckpointContents,
+}
+
+impl FinalityProof {
+    pub fn new(
+        effects: CertifiedTransactionEffects,
+        checkpoint: CertifiedCheckpointSummary,
+        checkpoint_contents: CheckpointContents,
+    ) -> Self {
+        Self {
+            effects,
+            checkpoint,
+            checkpoint_contents,
+        }
+    }
+
+    /// Serializes the proof into a blob that can be handed to [`verify_finality_proof`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("Serializing a finality proof cannot fail")
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> SuiResult<Self> {
+        bcs::from_bytes(bytes).map_err(|e| SuiError::InvalidFinalityProof {
+            error: format!("Failed to deserialize finality proof: {e}"),
+        })
+    }
+
+    /// Checks that the proof is signed by `committee` and that the checkpoint includes the
+    /// transaction with exactly these effects.
+    pub fn verify(&self, committee: &Committee) -> SuiResult {
+        self.effects.verify_authority_signatures(committee)?;
+        self.checkpoint
+            .verify_with_contents(committee, Some(&self.checkpoint_contents))?;
+
+        let digests = ExecutionDigests::new(
+            *self.effects.transaction_digest(),
+            self.effects.data().digest(),
+        );
+        if !self.checkpoint_contents.iter().any(|d| *d == digests) {
+            return Err(SuiError::InvalidFinalityProof {
+                error: format!(
+                    "Checkpoint {} does not include transaction {} with effects {}",
+                    self.checkpoint.sequence_number(),
+                    digests.transaction,
+                    digests.effects,
+                ),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the proof against the committee that `chain` verified for the epoch of its
+    /// checkpoint.
+    pub fn verify_with_committee_chain(&self, chain: &CommitteeChain) -> SuiResult {
+        let epoch = self.checkpoint.epoch();
+        let committee = chain
+            .committee(epoch)
+            .ok_or(SuiError::MissingCommitteeAtEpoch(epoch))?;
+        self.verify(committee)
+    }
+}
+
+/// Verifies a serialized [`FinalityProof`] against the committee of the epoch the transaction
+/// executed in, returning the effects it proves final.
+pub fn verify_finality_proof(bytes: &[u8], committee: &Committee) -> SuiResult<TransactionEffects> {
+    let proof = FinalityProof::from_bytes(bytes)?;
+    proof.verify(committee)?;
+    Ok(proof.effects.into_data())
+}
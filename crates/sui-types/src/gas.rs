@@ -11,7 +11,7 @@ pub mod checked {
     use crate::{
         effects::{TransactionEffects, TransactionEffectsAPI},
         error::{ExecutionError, SuiResult, UserInputError, UserInputResult},
-        gas_model::{gas_v2::SuiGasStatus as SuiGasStatusV2, tables::GasStatus},
+        gas_model::{gas_v2::SuiGasStatus as SuiGasStatusV2, quantities::Mist, tables::GasStatus},
         object::Object,
         sui_serde::{BigInt, Readable},
     };
@@ -167,17 +167,16 @@ pub mod checked {
         /// Portion of the storage rebate that gets passed on to the transaction sender. The remainder
         /// will be burned, then re-minted + added to the storage fund at the next epoch change
         pub fn sender_rebate(&self, storage_rebate_rate: u64) -> u64 {
-            // we round storage rebate such that `>= x.5` goes to x+1 (rounds up) and
-            // `< x.5` goes to x (truncates). We replicate `f32/64::round()`
-            const BASIS_POINTS: u128 = 10000;
-            (((self.storage_rebate as u128 * storage_rebate_rate as u128)
-            + (BASIS_POINTS / 2)) // integer rounding adds half of the BASIS_POINTS (denominator)
-            / BASIS_POINTS) as u64
+            Mist::new(self.storage_rebate)
+                .portion(storage_rebate_rate)
+                .value()
         }
 
         /// Get net gas usage, positive number means used gas; negative number means refund.
         pub fn net_gas_usage(&self) -> i64 {
-            self.gas_used() as i64 - self.storage_rebate as i64
+            Mist::new(self.gas_used())
+                .checked_signed_sub(Mist::new(self.storage_rebate))
+                .expect("Net gas usage out of range")
         }
 
         pub fn new_from_txn_effects<'a>(
@@ -240,14 +239,15 @@ pub mod checked {
     pub fn deduct_gas(gas_object: &mut Object, charge_or_rebate: i64) {
         // The object must be a gas coin as we have checked in transaction handle phase.
         let gas_coin = gas_object.data.try_as_move_mut().unwrap();
-        let balance = gas_coin.get_coin_value_unsafe();
+        let balance = Mist::new(gas_coin.get_coin_value_unsafe());
+        let amount = Mist::new(charge_or_rebate.unsigned_abs());
         let new_balance = if charge_or_rebate < 0 {
-            balance + (-charge_or_rebate as u64)
+            balance + amount
         } else {
-            assert!(balance >= charge_or_rebate as u64);
-            balance - charge_or_rebate as u64
+            assert!(balance >= amount);
+            balance - amount
         };
-        gas_coin.set_coin_value_unsafe(new_balance)
+        gas_coin.set_coin_value_unsafe(new_balance.value())
     }
 
     pub fn get_gas_balance(gas_object: &Object) -> UserInputResult<u64> {
@@ -9,6 +9,7 @@ mod checked {
     use crate::error::{UserInputError, UserInputResult};
     use crate::gas::{self, GasCostSummary, SuiGasStatusAPI};
     use crate::gas_model::gas_predicates::{cost_table_for_version, txn_base_cost_as_multiplier};
+    use crate::gas_model::quantities::{GasQuantity, Mist};
     use crate::gas_model::units_types::CostTable;
     use crate::{
         error::{ExecutionError, ExecutionErrorKind},
@@ -69,13 +70,8 @@ mod checked {
 
     /// Portion of the storage rebate that gets passed on to the transaction sender. The remainder
     /// will be burned, then re-minted + added to the storage fund at the next epoch change
-    fn sender_rebate(storage_rebate: u64, storage_rebate_rate: u64) -> u64 {
-        // we round storage rebate such that `>= x.5` goes to x+1 (rounds up) and
-        // `< x.5` goes to x (truncates). We replicate `f32/64::round()`
-        const BASIS_POINTS: u128 = 10000;
-        (((storage_rebate as u128 * storage_rebate_rate as u128)
-        + (BASIS_POINTS / 2)) // integer rounding adds half of the BASIS_POINTS (denominator)
-        / BASIS_POINTS) as u64
+    fn sender_rebate(storage_rebate: Mist, storage_rebate_rate: u64) -> Mist {
+        storage_rebate.portion(storage_rebate_rate)
     }
 
     /// A list of constant costs of various operations in Sui.
@@ -152,11 +148,11 @@ mod checked {
         cost_table: SuiCostTable,
         // Gas budget for this gas status instance.
         // Typically the gas budget as defined in the `TransactionData::GasData`
-        gas_budget: u64,
+        gas_budget: Mist,
         // Computation cost after execution. This is the result of the gas used by the `GasStatus`
         // properly bucketized.
         // Starts at 0 and it is assigned in `bucketize_computation`.
-        computation_cost: u64,
+        computation_cost: Mist,
         // Whether to charge or go unmetered
         charge: bool,
         // Gas price for computation.
@@ -180,17 +176,17 @@ mod checked {
         /// It tracks `total_storage_cost = storage_bytes * obj_data_cost_refundable` as
         /// described in `storage_gas_price`
         /// It will be multiplied by the storage gas price.
-        storage_cost: u64,
+        storage_cost: Mist,
         /// storage_rebate is the total storage rebate (in Sui) accumulated in this transaction.
         /// This is an accumulator computed at the end of execution while determining storage charges.
         /// It is the sum of all `storage_rebate` of all objects mutated or deleted during
         /// execution. The value is in Sui.
-        storage_rebate: u64,
+        storage_rebate: Mist,
         // storage rebate rate as defined in the ProtocolConfig
         rebate_rate: u64,
         /// Amount of storage rebate accumulated when we are running in unmetered mode (i.e. system transaction).
         /// This allows us to track how much storage rebate we need to retain in system transactions.
-        unmetered_storage_rebate: u64,
+        unmetered_storage_rebate: Mist,
         /// Rounding value to round up gas charges.
         gas_rounding_step: Option<u64>,
     }
@@ -210,16 +206,16 @@ mod checked {
             let gas_rounding_step = gas_rounding_step.map(|val| val.max(1));
             SuiGasStatus {
                 gas_status: move_gas_status,
-                gas_budget,
+                gas_budget: Mist::new(gas_budget),
                 charge,
-                computation_cost: 0,
+                computation_cost: Mist::ZERO,
                 gas_price,
                 reference_gas_price,
                 storage_gas_price,
-                storage_cost: 0,
-                storage_rebate: 0,
+                storage_cost: Mist::ZERO,
+                storage_rebate: Mist::ZERO,
                 rebate_rate,
-                unmetered_storage_rebate: 0,
+                unmetered_storage_rebate: Mist::ZERO,
                 gas_rounding_step,
                 cost_table,
             }
@@ -335,18 +331,20 @@ mod checked {
 
         fn bucketize_computation(&mut self) -> Result<(), ExecutionError> {
             let gas_used = self.gas_status.gas_used_pre_gas_price();
-            let gas_used = if let Some(gas_rounding) = self.gas_rounding_step {
+            let gas_units = if let Some(gas_rounding) = self.gas_rounding_step {
                 if gas_used > 0 && gas_used % gas_rounding == 0 {
-                    gas_used * self.gas_price
+                    gas_used
                 } else {
-                    ((gas_used / gas_rounding) + 1) * gas_rounding * self.gas_price
+                    ((gas_used / gas_rounding) + 1) * gas_rounding
                 }
             } else {
-                let bucket_cost = get_bucket_cost(&self.cost_table.computation_bucket, gas_used);
                 // charge extra on top of `computation_cost` to make the total computation
                 // cost a bucket value
-                bucket_cost * self.gas_price
+                get_bucket_cost(&self.cost_table.computation_bucket, gas_used)
             };
+            let gas_used = GasQuantity::new(gas_units)
+                .checked_mul_price(self.gas_price)
+                .expect("Overflow in computation cost");
             if self.gas_budget <= gas_used {
                 self.computation_cost = self.gas_budget;
                 Err(ExecutionErrorKind::InsufficientGas.into())
@@ -365,27 +363,27 @@ mod checked {
             assert!(sender_rebate <= self.storage_rebate);
             let non_refundable_storage_fee = self.storage_rebate - sender_rebate;
             GasCostSummary {
-                computation_cost: self.computation_cost,
-                storage_cost: self.storage_cost,
-                storage_rebate: sender_rebate,
-                non_refundable_storage_fee,
+                computation_cost: self.computation_cost.value(),
+                storage_cost: self.storage_cost.value(),
+                storage_rebate: sender_rebate.value(),
+                non_refundable_storage_fee: non_refundable_storage_fee.value(),
             }
         }
 
         fn gas_budget(&self) -> u64 {
-            self.gas_budget
+            self.gas_budget.value()
         }
 
         fn storage_gas_units(&self) -> u64 {
-            self.storage_cost
+            self.storage_cost.value()
         }
 
         fn storage_rebate(&self) -> u64 {
-            self.storage_rebate
+            self.storage_rebate.value()
         }
 
         fn unmetered_storage_rebate(&self) -> u64 {
-            self.unmetered_storage_rebate
+            self.unmetered_storage_rebate.value()
         }
 
         fn gas_used(&self) -> u64 {
@@ -393,9 +391,9 @@ mod checked {
         }
 
         fn reset_storage_cost_and_rebate(&mut self) {
-            self.storage_cost = 0;
-            self.storage_rebate = 0;
-            self.unmetered_storage_rebate = 0;
+            self.storage_cost = Mist::ZERO;
+            self.storage_rebate = Mist::ZERO;
+            self.unmetered_storage_rebate = Mist::ZERO;
         }
 
         fn charge_storage_read(&mut self, size: usize) -> Result<(), ExecutionError> {
@@ -421,6 +419,7 @@ mod checked {
         /// (`track_storage_mutation`).
         /// Return the new storage rebate (cost of object storage) according to `new_size`.
         fn track_storage_mutation(&mut self, new_size: usize, storage_rebate: u64) -> u64 {
+            let storage_rebate = Mist::new(storage_rebate);
             if self.is_unmetered() {
                 self.unmetered_storage_rebate += storage_rebate;
                 return 0;
@@ -428,12 +427,13 @@ mod checked {
             self.storage_rebate += storage_rebate;
             // compute and track cost (based on size)
             let new_size = new_size as u64;
-            let storage_cost =
-                new_size * self.cost_table.storage_per_byte_cost * self.storage_gas_price;
+            let storage_cost = GasQuantity::new(new_size * self.cost_table.storage_per_byte_cost)
+                .checked_mul_price(self.storage_gas_price)
+                .expect("Overflow in storage cost");
             // track rebate
             self.storage_cost += storage_cost;
             // return the new object rebate (object storage cost)
-            storage_cost
+            storage_cost.value()
         }
 
         fn charge_storage_and_rebate(&mut self) -> Result<(), ExecutionError> {
@@ -458,8 +458,8 @@ mod checked {
         }
 
         fn adjust_computation_on_out_of_gas(&mut self) {
-            self.storage_rebate = 0;
-            self.storage_cost = 0;
+            self.storage_rebate = Mist::ZERO;
+            self.storage_cost = Mist::ZERO;
             self.computation_cost = self.gas_budget;
         }
     }
@@ -3,5 +3,6 @@
 
 pub mod gas_predicates;
 pub mod gas_v2;
+pub mod quantities;
 pub mod tables;
 pub mod units_types;
@@ -0,0 +1,99 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed amounts for gas charging and balance computations.
+//!
+//! Gas is metered in units of gas ([`GasQuantity`]), which are paid for in MIST ([`Mist`]) at a
+//! gas price. Keeping the two apart, and apart from plain `u64`s, means that adding a quantity of
+//! gas to an amount of MIST, or forgetting to apply a price, is a type error.
+//!
+//! Neither type implements the arithmetic operators, so that every computation chooses how it
+//! handles overflow: `checked_*` methods return `None`, and `saturating_*` methods clamp to the
+//! bounds of the type. Modules annotated with `#[sui_macros::with_checked_arithmetic]` rewrite
+//! operators into calls to the `checked_*` methods, so operators on these types are available
+//! there, and panic on overflow like they do for integers.
+
+use std::fmt;
+
+#[cfg(test)]
+#[path = "../unit_tests/quantities_tests.rs"]
+mod quantities_tests;
+
+/// Rates applied to amounts, like the storage rebate rate, are expressed in basis points.
+const BASIS_POINTS: u128 = 10_000;
+
+/// A quantity of computation or storage gas units, before a gas price is applied.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GasQuantity(u64);
+
+/// An amount of SUI, denominated in MIST.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mist(u64);
+
+macro_rules! impl_amount {
+    ($ty:ident) => {
+        impl $ty {
+            pub const ZERO: Self = Self(0);
+            pub const MAX: Self = Self(u64::MAX);
+
+            pub const fn new(value: u64) -> Self {
+                Self(value)
+            }
+
+            pub const fn value(self) -> u64 {
+                self.0
+            }
+
+            pub fn checked_add(self, other: Self) -> Option<Self> {
+                self.0.checked_add(other.0).map(Self)
+            }
+
+            pub fn checked_sub(self, other: Self) -> Option<Self> {
+                self.0.checked_sub(other.0).map(Self)
+            }
+
+            pub fn saturating_add(self, other: Self) -> Self {
+                Self(self.0.saturating_add(other.0))
+            }
+
+            pub fn saturating_sub(self, other: Self) -> Self {
+                Self(self.0.saturating_sub(other.0))
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+impl_amount!(GasQuantity);
+impl_amount!(Mist);
+
+impl GasQuantity {
+    /// Cost of this quantity of gas at `gas_price` MIST per unit, or `None` if it overflows.
+    pub fn checked_mul_price(self, gas_price: u64) -> Option<Mist> {
+        self.0.checked_mul(gas_price).map(Mist)
+    }
+
+    pub fn saturating_mul_price(self, gas_price: u64) -> Mist {
+        Mist(self.0.saturating_mul(gas_price))
+    }
+}
+
+impl Mist {
+    /// Portion of this amount at `rate` basis points, rounded to the nearest MIST, with halves
+    /// rounded up (replicating `f64::round`). Rates above 100% saturate at [`Mist::MAX`].
+    pub fn portion(self, rate: u64) -> Mist {
+        // Cannot overflow: the product of two `u64`s is at most `u128::MAX - 2 * u64::MAX`.
+        let portion = (self.0 as u128 * rate as u128 + BASIS_POINTS / 2) / BASIS_POINTS;
+        Mist(u64::try_from(portion).unwrap_or(u64::MAX))
+    }
+
+    /// Signed difference `self - other`, or `None` if it does not fit in an `i64`.
+    pub fn checked_signed_sub(self, other: Mist) -> Option<i64> {
+        i64::try_from(self.0 as i128 - other.0 as i128).ok()
+    }
+}
@@ -8,7 +8,7 @@ use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::gas_algebra::{AbstractMemorySize, InternalGas, NumArgs, NumBytes};
 use move_core_types::language_storage::ModuleId;
 
-use crate::gas_model::gas_predicates::charge_input_as_memory;
+use crate::gas_model::gas_predicates::{charge_input_as_memory, cost_table_for_version};
 use move_core_types::vm_status::StatusCode;
 #[cfg(debug_assertions)]
 use move_vm_profiler::GasProfiler;
@@ -920,7 +920,18 @@ pub fn initial_cost_schedule_v4() -> CostTable {
 // representation to whatever is there, so instead we perform this translation from our gas units
 // and cost schedule to the one expected by the Move unit tests.
 pub fn initial_cost_schedule_for_unit_tests() -> move_vm_test_utils::gas_schedule::CostTable {
-    let table = initial_cost_schedule_v4();
+    unit_test_cost_schedule(initial_cost_schedule_v4())
+}
+
+/// The cost schedule of `gas_model_version`, for metering Move unit tests the way transactions
+/// are metered on chain.
+pub fn cost_schedule_for_unit_tests(
+    gas_model_version: u64,
+) -> move_vm_test_utils::gas_schedule::CostTable {
+    unit_test_cost_schedule(cost_table_for_version(gas_model_version))
+}
+
+fn unit_test_cost_schedule(table: CostTable) -> move_vm_test_utils::gas_schedule::CostTable {
     move_vm_test_utils::gas_schedule::CostTable {
         instruction_tiers: table
             .instruction_tiers
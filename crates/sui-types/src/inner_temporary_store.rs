@@ -3,7 +3,7 @@
 
 use crate::base_types::VersionDigest;
 use crate::effects::TransactionEvents;
-use crate::execution::DynamicallyLoadedObjectMetadata;
+use crate::execution::{ChildObjectAccessStats, DynamicallyLoadedObjectMetadata, ObjectLineage};
 use crate::{
     base_types::ObjectID,
     object::{Object, Owner},
@@ -29,6 +29,11 @@ pub struct InnerTemporaryStore {
     pub max_binary_format_version: u32,
     pub no_extraneous_module_bytes: bool,
     pub runtime_packages_loaded_from_db: BTreeMap<ObjectID, Object>,
+    /// Objects wrapped or unwrapped by the transaction, and the objects they were wrapped in or
+    /// unwrapped from, when the `record_object_lineage` feature is enabled.
+    pub object_lineage: BTreeMap<ObjectID, ObjectLineage>,
+    /// Child objects looked up by the transaction.
+    pub child_object_access: ChildObjectAccessStats,
 }
 
 pub struct TemporaryModuleResolver<'a, R> {
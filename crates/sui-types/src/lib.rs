@@ -33,16 +33,20 @@ pub mod clock;
 pub mod coin;
 pub mod collection_types;
 pub mod committee;
+pub mod committee_chain;
 pub mod crypto;
+pub mod dev_inspect;
 pub mod digests;
 pub mod display;
 pub mod dynamic_field;
 pub mod effects;
 pub mod event;
+pub mod event_proof;
 pub mod executable_transaction;
 pub mod execution;
 pub mod execution_mode;
 pub mod execution_status;
+pub mod finality_proof;
 pub mod gas;
 pub mod gas_coin;
 pub mod gas_model;
@@ -67,8 +71,10 @@ pub mod storage;
 pub mod sui_serde;
 pub mod sui_system_state;
 pub mod transaction;
+pub mod transaction_analyzer;
 pub mod transfer;
 pub mod type_resolver;
+pub mod type_tag;
 pub mod versioned;
 pub mod zk_login_authenticator;
 pub mod zk_login_util;
@@ -79,6 +85,12 @@ pub mod epoch_data;
 #[path = "./unit_tests/utils.rs"]
 pub mod utils;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_vectors;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod digest_audit;
+
 /// 0x1-- account address where Move stdlib modules are stored
 /// Same as the ObjectID
 pub const MOVE_STDLIB_ADDRESS: AccountAddress = AccountAddress::ONE;
@@ -145,23 +157,11 @@ pub fn sui_framework_address_concat_string(suffix: &str) -> String {
 }
 
 pub fn parse_sui_struct_tag(s: &str) -> anyhow::Result<StructTag> {
-    use move_command_line_common::types::ParsedStructType;
-    ParsedStructType::parse(s)?.into_struct_tag(&resolve_address)
+    Ok(type_tag::parse_struct_tag(s)?)
 }
 
 pub fn parse_sui_type_tag(s: &str) -> anyhow::Result<TypeTag> {
-    use move_command_line_common::types::ParsedType;
-    ParsedType::parse(s)?.into_type_tag(&resolve_address)
-}
-
-fn resolve_address(addr: &str) -> Option<AccountAddress> {
-    match addr {
-        "deepbook" => Some(DEEPBOOK_ADDRESS),
-        "std" => Some(MOVE_STDLIB_ADDRESS),
-        "sui" => Some(SUI_FRAMEWORK_ADDRESS),
-        "sui_system" => Some(SUI_SYSTEM_ADDRESS),
-        _ => None,
-    }
+    Ok(type_tag::parse_type_tag(s)?)
 }
 
 pub trait MoveTypeTagTrait {
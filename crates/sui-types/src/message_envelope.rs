@@ -6,6 +6,7 @@ use crate::committee::{Committee, EpochId};
 use crate::crypto::{
     AuthorityKeyPair, AuthorityQuorumSignInfo, AuthoritySignInfo, AuthoritySignInfoTrait,
     AuthoritySignature, AuthorityStrongQuorumSignInfo, EmptySignInfo, Signer,
+    VerificationObligation,
 };
 use crate::error::SuiResult;
 use crate::executable_transaction::CertificateProof;
@@ -350,6 +351,22 @@ where
     pub fn epoch(&self) -> EpochId {
         self.auth_signature.epoch
     }
+
+    /// Checks the epoch of the message and adds the committee signatures of this envelope to
+    /// `obligation`, so that they can be verified together with those of other envelopes.
+    pub fn add_to_verification_obligation<'a>(
+        &self,
+        committee: &'a Committee,
+        obligation: &mut VerificationObligation<'a>,
+    ) -> SuiResult {
+        // The signatures only cover the epoch they were made in, not the one the message belongs
+        // to: check it as `verify_authority_signatures` does, so that a batch never accepts an
+        // envelope that would be rejected on its own.
+        self.data.verify_epoch(self.auth_sig().epoch)?;
+        let idx = obligation.add_message(self.data(), self.epoch(), Intent::sui_app(T::SCOPE));
+        self.auth_signature
+            .add_to_verification_obligation(committee, obligation, idx)
+    }
 }
 
 impl<T, const S: bool> Envelope<T, AuthorityQuorumSignInfo<S>>
@@ -407,6 +424,23 @@ where
     }
 }
 
+/// Verifies the committee signatures of all `envelopes` at once, using batch verification.
+/// Fails if any of the envelopes does not verify, without identifying which one.
+/// Internal signatures of the messages (e.g. user signatures of a transaction) are not checked.
+pub fn batch_verify_committee_sigs<T, const S: bool>(
+    committee: &Committee,
+    envelopes: &[Envelope<T, AuthorityQuorumSignInfo<S>>],
+) -> SuiResult
+where
+    T: Message + Serialize,
+{
+    let mut obligation = VerificationObligation::default();
+    for envelope in envelopes {
+        envelope.add_to_verification_obligation(committee, &mut obligation)?;
+    }
+    obligation.verify_all()
+}
+
 /// TrustedEnvelope is a serializable wrapper around Envelope which is
 /// `Into<VerifiedEnvelope>` - in other words it models a verified message which has been
 /// written to the db (or some other trusted store), and may be read back from the db without
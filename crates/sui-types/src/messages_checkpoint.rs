@@ -122,7 +122,7 @@ pub struct EndOfEpochData {
     pub epoch_commitments: Vec<CheckpointCommitment>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct CheckpointSummary {
     pub epoch: EpochId,
     pub sequence_number: CheckpointSequenceNumber,
@@ -217,6 +217,16 @@ impl CheckpointSummary {
             .map(|e| e.next_epoch_committee.as_slice())
     }
 
+    /// The committee of the next epoch, if this is the last checkpoint of its epoch.
+    pub fn handoff_committee(&self) -> Option<Committee> {
+        self.next_epoch_committee().map(|next_epoch_committee| {
+            Committee::new(
+                self.epoch.saturating_add(1),
+                next_epoch_committee.iter().cloned().collect(),
+            )
+        })
+    }
+
     pub fn report_checkpoint_age_ms(&self, metrics: &Histogram) {
         SystemTime::now()
             .duration_since(self.timestamp())
@@ -638,6 +648,7 @@ mod tests {
     use rand::SeedableRng;
 
     use super::*;
+    use crate::message_envelope::batch_verify_committee_sigs;
     use crate::utils::make_committee_key;
 
     // TODO use the file name as a seed
@@ -768,4 +779,35 @@ mod tests {
                 .is_err()
         )
     }
+    #[test]
+    fn test_batch_verify_checks_checkpoint_epoch() {
+        let mut rng = StdRng::from_seed(RNG_SEED);
+        let (keys, committee) = make_committee_key(&mut rng);
+
+        let set = CheckpointContents::new_with_causally_ordered_transactions(
+            [ExecutionDigests::random()].into_iter(),
+        );
+
+        let certify = |epoch| {
+            let summary =
+                CheckpointSummary::new(epoch, 1, 0, &set, None, GasCostSummary::default(), None, 0);
+            let sign_infos: Vec<_> = keys
+                .iter()
+                .map(|k| {
+                    SignedCheckpointSummary::sign(committee.epoch, &summary, k, k.public().into())
+                })
+                .collect();
+            CertifiedCheckpointSummary::new(summary, sign_infos, &committee).expect("Cert is OK")
+        };
+
+        let checkpoint_cert = certify(committee.epoch);
+        assert!(batch_verify_committee_sigs(&committee, &[checkpoint_cert.clone()]).is_ok());
+
+        // The signatures are valid, but for a checkpoint from another epoch.
+        let other_epoch_cert = certify(committee.epoch + 1);
+        assert!(matches!(
+            batch_verify_committee_sigs(&committee, &[checkpoint_cert, other_epoch_cert]),
+            Err(SuiError::WrongEpoch { .. })
+        ));
+    }
 }
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use prometheus::{
-    register_histogram_with_registry, register_int_counter_vec_with_registry, Histogram,
-    IntCounterVec,
+    exponential_buckets, register_histogram_with_registry, register_int_counter_vec_with_registry,
+    register_int_counter_with_registry, Histogram, IntCounter, IntCounterVec,
 };
 
 pub struct LimitsMetrics {
@@ -15,6 +15,10 @@ pub struct LimitsMetrics {
     pub excessive_transferred_move_object_ids: IntCounterVec,
     pub excessive_object_runtime_cached_objects: IntCounterVec,
     pub excessive_object_runtime_store_entries: IntCounterVec,
+    /// Number of child objects (e.g. dynamic fields) loaded from storage per transaction
+    pub child_objects_loaded_per_tx: Histogram,
+    /// Total size in bytes of the child objects loaded from storage per transaction
+    pub child_object_bytes_loaded_per_tx: Histogram,
 }
 
 impl LimitsMetrics {
@@ -69,6 +73,20 @@ impl LimitsMetrics {
                 registry,
             )
                 .unwrap(),
+            child_objects_loaded_per_tx: register_histogram_with_registry!(
+                "child_objects_loaded_per_tx",
+                "Number of child objects loaded from storage by each transaction",
+                exponential_buckets(1.0, 2.0, 12).unwrap(),
+                registry,
+            )
+                .unwrap(),
+            child_object_bytes_loaded_per_tx: register_histogram_with_registry!(
+                "child_object_bytes_loaded_per_tx",
+                "Total size in bytes of the child objects loaded from storage by each transaction",
+                exponential_buckets(256.0, 4.0, 10).unwrap(),
+                registry,
+            )
+                .unwrap(),
         }
     }
 }
@@ -84,6 +102,12 @@ pub struct BytecodeVerifierMetrics {
     pub verifier_runtime_per_module_timeout_latency: Histogram,
     /// Bytecode verifier runtime latency for each programmable transaction block which timed out
     pub verifier_runtime_per_ptb_timeout_latency: Histogram,
+    /// Bytecode verifier meter units used by each module verified
+    pub verifier_meter_units_per_module: Histogram,
+    /// Number of packages whose verification result was found in the verifier cache
+    pub verifier_cache_hits: IntCounter,
+    /// Number of packages that had to be verified because their result was not cached
+    pub verifier_cache_misses: IntCounter,
 }
 
 impl BytecodeVerifierMetrics {
@@ -133,6 +157,25 @@ impl BytecodeVerifierMetrics {
                 Self::LATENCY_SEC_BUCKETS.to_vec(),
                 registry
             ).unwrap(),
+            verifier_meter_units_per_module: register_histogram_with_registry!(
+                "verifier_meter_units_per_module",
+                "Meter units used by the bytecode verifier to verify each module",
+                exponential_buckets(1_000.0, 4.0, 10).unwrap(),
+                registry
+            )
+            .unwrap(),
+            verifier_cache_hits: register_int_counter_with_registry!(
+                "verifier_cache_hits",
+                "Number of packages whose bytecode verification result was cached",
+                registry
+            )
+            .unwrap(),
+            verifier_cache_misses: register_int_counter_with_registry!(
+                "verifier_cache_misses",
+                "Number of packages whose bytecode verification result was not cached",
+                registry
+            )
+            .unwrap(),
         }
     }
 }
@@ -563,6 +563,34 @@ pub enum Owner {
     },
     /// Object is immutable, and hence ownership doesn't matter.
     Immutable,
+    /// Object is exclusively owned by a single address, and is sequenced via consensus.
+    /// Objects are not given this owner until the protocol supports consensus-owned objects.
+    ConsensusV2 {
+        /// The version at which the object most recently became a consensus object. It serves
+        /// the same function as `initial_shared_version`, except it may change if the owner of
+        /// the object changes.
+        start_version: SequenceNumber,
+        /// How transactions using the object are authenticated
+        authenticator: Authenticator,
+    },
+}
+
+/// How transactions using a consensus-owned object are authenticated.
+#[derive(
+    Eq, PartialEq, Debug, Clone, Copy, Deserialize, Serialize, Hash, JsonSchema, Ord, PartialOrd,
+)]
+#[cfg_attr(feature = "fuzzing", derive(proptest_derive::Arbitrary))]
+pub enum Authenticator {
+    /// The object is fully owned by a single address.
+    SingleOwner(SuiAddress),
+}
+
+impl Authenticator {
+    pub fn as_single_owner(&self) -> &SuiAddress {
+        match self {
+            Self::SingleOwner(address) => address,
+        }
+    }
 }
 
 impl Owner {
@@ -571,9 +599,10 @@ impl Owner {
     pub fn get_address_owner_address(&self) -> SuiResult<SuiAddress> {
         match self {
             Self::AddressOwner(address) => Ok(*address),
-            Self::Shared { .. } | Self::Immutable | Self::ObjectOwner(_) => {
-                Err(SuiError::UnexpectedOwnerType)
-            }
+            Self::Shared { .. }
+            | Self::Immutable
+            | Self::ObjectOwner(_)
+            | Self::ConsensusV2 { .. } => Err(SuiError::UnexpectedOwnerType),
         }
     }
 
@@ -582,7 +611,9 @@ impl Owner {
     pub fn get_owner_address(&self) -> SuiResult<SuiAddress> {
         match self {
             Self::AddressOwner(address) | Self::ObjectOwner(address) => Ok(*address),
-            Self::Shared { .. } | Self::Immutable => Err(SuiError::UnexpectedOwnerType),
+            Self::Shared { .. } | Self::Immutable | Self::ConsensusV2 { .. } => {
+                Err(SuiError::UnexpectedOwnerType)
+            }
         }
     }
 
@@ -601,13 +632,40 @@ impl Owner {
     pub fn is_shared(&self) -> bool {
         matches!(self, Owner::Shared { .. })
     }
+
+    pub fn is_consensus_owned(&self) -> bool {
+        matches!(self, Owner::ConsensusV2 { .. })
+    }
+
+    /// Whether transactions using the object are sequenced via consensus, which is the case of
+    /// shared and consensus-owned objects.
+    pub fn is_consensus(&self) -> bool {
+        match self {
+            Self::Shared { .. } | Self::ConsensusV2 { .. } => true,
+            Self::AddressOwner(_) | Self::ObjectOwner(_) | Self::Immutable => false,
+        }
+    }
+
+    /// The version from which the object is sequenced via consensus, if it is.
+    pub fn start_version(&self) -> Option<SequenceNumber> {
+        match self {
+            Self::Shared {
+                initial_shared_version,
+            } => Some(*initial_shared_version),
+            Self::ConsensusV2 { start_version, .. } => Some(*start_version),
+            Self::AddressOwner(_) | Self::ObjectOwner(_) | Self::Immutable => None,
+        }
+    }
 }
 
 impl PartialEq<SuiAddress> for Owner {
     fn eq(&self, other: &SuiAddress) -> bool {
         match self {
             Self::AddressOwner(address) => address == other,
-            Self::ObjectOwner(_) | Self::Shared { .. } | Self::Immutable => false,
+            Self::ObjectOwner(_)
+            | Self::Shared { .. }
+            | Self::Immutable
+            | Self::ConsensusV2 { .. } => false,
         }
     }
 }
@@ -617,7 +675,10 @@ impl PartialEq<ObjectID> for Owner {
         let other_id: SuiAddress = (*other).into();
         match self {
             Self::ObjectOwner(id) => id == &other_id,
-            Self::AddressOwner(_) | Self::Shared { .. } | Self::Immutable => false,
+            Self::AddressOwner(_)
+            | Self::Shared { .. }
+            | Self::Immutable
+            | Self::ConsensusV2 { .. } => false,
         }
     }
 }
@@ -637,6 +698,17 @@ impl Display for Owner {
             Self::Shared { .. } => {
                 write!(f, "Shared")
             }
+            Self::ConsensusV2 {
+                start_version,
+                authenticator,
+            } => {
+                write!(
+                    f,
+                    "ConsensusV2 ( start version: {}, owner: {} )",
+                    start_version,
+                    authenticator.as_single_owner()
+                )
+            }
         }
     }
 }
@@ -18,6 +18,10 @@ use crate::{
     SUI_FRAMEWORK_PACKAGE_ID,
 };
 
+#[cfg(test)]
+#[path = "unit_tests/programmable_transaction_builder_tests.rs"]
+mod programmable_transaction_builder_tests;
+
 #[derive(PartialEq, Eq, Hash)]
 enum BuilderArg {
     Object(ObjectID),
@@ -67,6 +71,11 @@ impl ProgrammableTransactionBuilder {
         ))
     }
 
+    /// A pure argument for a Move `Option<T>`, which has the same encoding as a Rust `Option<T>`.
+    pub fn pure_option<T: Serialize>(&mut self, value: Option<T>) -> anyhow::Result<Argument> {
+        self.pure(value)
+    }
+
     pub fn obj(&mut self, obj_arg: ObjectArg) -> anyhow::Result<Argument> {
         let id = obj_arg.id();
         let obj_arg = if let Some(old_value) = self.inputs.get(&BuilderArg::Object(id)) {
@@ -122,6 +131,12 @@ impl ProgrammableTransactionBuilder {
         }
     }
 
+    /// An argument for a `Receiving<T>` parameter, to receive the object at `object_ref` that was
+    /// sent to an object in this transaction.
+    pub fn receiving(&mut self, object_ref: ObjectRef) -> anyhow::Result<Argument> {
+        self.obj(ObjectArg::Receiving(object_ref))
+    }
+
     pub fn make_obj_vec(
         &mut self,
         objs: impl IntoIterator<Item = ObjectArg>,
@@ -133,12 +148,40 @@ impl ProgrammableTransactionBuilder {
         Ok(self.command(Command::MakeMoveVec(None, make_vec_args)))
     }
 
+    /// Makes a vector out of `args`, which can be inputs or the results of earlier commands. The
+    /// type of the elements must be given if the vector is empty.
+    pub fn make_move_vec(&mut self, type_: Option<TypeTag>, args: Vec<Argument>) -> Argument {
+        self.command(Command::MakeMoveVec(type_, args))
+    }
+
     pub fn command(&mut self, command: Command) -> Argument {
         let i = self.commands.len();
         self.commands.push(command);
         Argument::Result(i as u16)
     }
 
+    /// The `index`-th value returned by the command that produced `result`, for commands that
+    /// return more than one value.
+    /// Will fail if `result` is not the result of a command added to this builder.
+    pub fn nested_result(&self, result: Argument, index: u16) -> anyhow::Result<Argument> {
+        let Argument::Result(i) = result else {
+            anyhow::bail!("{result:?} is not the result of a command");
+        };
+        anyhow::ensure!(
+            (i as usize) < self.commands.len(),
+            "Command {i} has not been added to the transaction"
+        );
+        Ok(Argument::NestedResult(i, index))
+    }
+
+    /// The first `count` values returned by the command that produced `result`, in order.
+    /// Will fail if `result` is not the result of a command added to this builder.
+    pub fn nested_results(&self, result: Argument, count: u16) -> anyhow::Result<Vec<Argument>> {
+        (0..count)
+            .map(|index| self.nested_result(result, index))
+            .collect()
+    }
+
     /// Will fail to generate if given an empty ObjVec
     pub fn move_call(
         &mut self,
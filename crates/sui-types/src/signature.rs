@@ -5,6 +5,7 @@ use crate::committee::EpochId;
 use crate::crypto::{SignatureScheme, SuiSignature};
 use crate::multisig_legacy::MultiSigLegacy;
 use crate::zk_login_authenticator::ZkLoginAuthenticator;
+use crate::zk_login_util::ZkLoginProviderRegistry;
 use crate::{base_types::SuiAddress, crypto::Signature, error::SuiResult, multisig::MultiSig};
 pub use enum_dispatch::enum_dispatch;
 use fastcrypto::{
@@ -22,7 +23,7 @@ use std::hash::Hash;
 pub struct VerifyParams {
     // map from JwkId (iss, kid) => JWK
     pub oidc_provider_jwks: ImHashMap<JwkId, JWK>,
-    pub supported_providers: Vec<OIDCProvider>,
+    pub supported_providers: ZkLoginProviderRegistry,
     pub zk_login_env: ZkLoginEnv,
 }
 
@@ -34,7 +35,7 @@ impl VerifyParams {
     ) -> Self {
         Self {
             oidc_provider_jwks,
-            supported_providers,
+            supported_providers: ZkLoginProviderRegistry::new(supported_providers),
             zk_login_env,
         }
     }
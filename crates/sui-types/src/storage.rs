@@ -8,7 +8,7 @@ use crate::digests::{
 };
 use crate::effects::{TransactionEffects, TransactionEvents};
 use crate::error::SuiError;
-use crate::execution::{DynamicallyLoadedObjectMetadata, ExecutionResults};
+use crate::execution::{ChildObjectAccessStats, DynamicallyLoadedObjectMetadata, ExecutionResults};
 use crate::message_envelope::Message;
 use crate::messages_checkpoint::{
     CheckpointContents, CheckpointSequenceNumber, FullCheckpointContents, VerifiedCheckpoint,
@@ -149,6 +149,10 @@ pub trait Storage {
         &mut self,
         loaded_runtime_objects: BTreeMap<ObjectID, DynamicallyLoadedObjectMetadata>,
     );
+
+    /// Save how many child objects were accessed at runtime, for diagnostics. Ignored by execution
+    /// versions that do not track it.
+    fn save_child_object_access_stats(&mut self, _stats: ChildObjectAccessStats) {}
 }
 
 pub type PackageFetchResults<Package> = Result<Vec<Package>, Vec<ObjectID>>;
@@ -0,0 +1,326 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic test vectors for the structures that Sui signs, for SDKs in other languages to
+//! check that they serialize, hash and sign them exactly like this crate does.
+//!
+//! Every vector is derived from fixed seeds, so the output only changes when the encoding of one
+//! of the structures (or the way it is signed) changes.
+
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::traits::KeyPair;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use shared_crypto::intent::{Intent, IntentMessage};
+use sui_protocol_config::ProtocolVersion;
+
+use crate::base_types::{ExecutionDigests, ObjectDigest, ObjectID, SequenceNumber, SuiAddress};
+use crate::crypto::{
+    default_hash, AuthorityPublicKeyBytes, EncodeDecodeBase64, Signature, SignatureScheme,
+    SuiKeyPair,
+};
+use crate::digests::{TransactionDigest, TransactionEffectsDigest};
+use crate::effects::{SignedTransactionEffects, TransactionEffects, TransactionEffectsAPI};
+use crate::execution_status::ExecutionStatus;
+use crate::gas::GasCostSummary;
+use crate::message_envelope::Message;
+use crate::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary,
+};
+use crate::multisig::{MultiSig, MultiSigPublicKey};
+use crate::object::Owner;
+use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use crate::signature::GenericSignature;
+use crate::transaction::{TransactionData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
+use crate::utils::{get_zklogin_user_address, keys, make_committee_key, sign_zklogin_tx};
+
+/// All the test vectors, grouped by the kind of structure they exercise.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVectors {
+    pub transaction_data: Vec<TestVector>,
+    pub effects: Vec<TestVector>,
+    pub checkpoints: Vec<TestVector>,
+    pub multisig: Vec<TestVector>,
+    pub zklogin: Vec<TestVector>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// Name of the vector, unique within its group.
+    pub name: String,
+    /// Name of the Rust type that `bcs` is the encoding of.
+    pub type_name: String,
+    /// BCS encoding of the value, in hex.
+    pub bcs: String,
+    /// Digest of the value, in Base58, for types that have one.
+    pub digest: Option<String>,
+    /// User signatures over the value, for transaction data.
+    pub signatures: Vec<SignatureVector>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureVector {
+    pub scheme: SignatureScheme,
+    /// Public key of the signer (flag followed by the key bytes), in Base64. Not set for
+    /// signatures that embed their public keys, like multisig and zkLogin.
+    pub public_key: Option<String>,
+    /// Address of the signer.
+    pub address: SuiAddress,
+    /// Serialized signature, in Base64, as it is submitted alongside a transaction.
+    pub signature: String,
+}
+
+impl TestVector {
+    fn new<T: Serialize>(name: &str, value: &T, digest: Option<String>) -> Self {
+        let type_name = std::any::type_name::<T>();
+        Self {
+            name: name.to_string(),
+            type_name: type_name
+                .rsplit("::")
+                .next()
+                .unwrap_or(type_name)
+                .to_string(),
+            bcs: Hex::encode(bcs::to_bytes(value).expect("Test vectors must serialize")),
+            digest,
+            signatures: vec![],
+        }
+    }
+
+    fn with_signature(mut self, signature: SignatureVector) -> Self {
+        self.signatures.push(signature);
+        self
+    }
+}
+
+impl SignatureVector {
+    fn new(
+        scheme: SignatureScheme,
+        public_key: Option<String>,
+        address: SuiAddress,
+        signature: &GenericSignature,
+    ) -> Self {
+        Self {
+            scheme,
+            public_key,
+            address,
+            signature: Base64::encode(signature.as_ref()),
+        }
+    }
+}
+
+/// Generate all test vectors.
+pub fn generate() -> TestVectors {
+    let (transaction_data, digests) = transaction_data_vectors();
+    let (effects, effects_digest) = effects_vectors(digests[0]);
+
+    TestVectors {
+        transaction_data,
+        effects,
+        checkpoints: checkpoint_vectors(ExecutionDigests::new(digests[0], effects_digest)),
+        multisig: multisig_vectors(),
+        zklogin: zklogin_vectors(),
+    }
+}
+
+/// Transfer the gas coin from `sender` to a fixed recipient.
+pub(crate) fn transfer_sui_data(sender: SuiAddress) -> TransactionData {
+    let pt = {
+        let mut builder = ProgrammableTransactionBuilder::new();
+        builder.transfer_sui(SuiAddress::from(ObjectID::from_single_byte(0xaa)), None);
+        builder.finish()
+    };
+
+    TransactionData::new_programmable(
+        sender,
+        vec![gas_object_ref()],
+        pt,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+    )
+}
+
+pub(crate) fn gas_object_ref() -> (ObjectID, SequenceNumber, ObjectDigest) {
+    (
+        ObjectID::from_single_byte(0x99),
+        SequenceNumber::from_u64(1),
+        ObjectDigest::new([0x42; 32]),
+    )
+}
+
+fn transaction_data_digest(data: &TransactionData) -> TransactionDigest {
+    TransactionDigest::new(default_hash(data))
+}
+
+fn sign_transaction_data(data: &TransactionData, key: &SuiKeyPair) -> SignatureVector {
+    let message = IntentMessage::new(Intent::sui_transaction(), data.clone());
+    let signature = GenericSignature::Signature(Signature::new_secure(&message, key));
+    let public_key = key.public();
+
+    SignatureVector::new(
+        public_key.scheme(),
+        Some(public_key.encode_base64()),
+        SuiAddress::from(&public_key),
+        &signature,
+    )
+}
+
+/// A transfer signed by each of the single key schemes, returning the vectors and the digests of
+/// the transactions.
+fn transaction_data_vectors() -> (Vec<TestVector>, Vec<TransactionDigest>) {
+    keys()
+        .iter()
+        .map(|key| {
+            let public_key = key.public();
+            let data = transfer_sui_data(SuiAddress::from(&public_key));
+            let digest = transaction_data_digest(&data);
+            let name = format!("transfer_sui_{}", public_key.scheme());
+
+            let vector = TestVector::new(&name, &data, Some(digest.to_string()))
+                .with_signature(sign_transaction_data(&data, key));
+            (vector, digest)
+        })
+        .unzip()
+}
+
+/// Effects of the transaction with `digest`, both on their own and signed by a validator. Returns
+/// the vectors and the digest of the effects.
+fn effects_vectors(digest: TransactionDigest) -> (Vec<TestVector>, TransactionEffectsDigest) {
+    let (gas_id, gas_version, _) = gas_object_ref();
+    let sender = SuiAddress::from(&keys()[0].public());
+    let gas_object = (
+        (gas_id, gas_version.next(), ObjectDigest::new([0x43; 32])),
+        Owner::AddressOwner(sender),
+    );
+
+    let effects = TransactionEffects::new_from_execution(
+        ProtocolVersion::MIN,
+        ExecutionStatus::Success,
+        /* executed_epoch */ 0,
+        GasCostSummary::new(1_000_000, 2_000_000, 1_500_000, 15_000),
+        /* modified_at_versions */ vec![(gas_id, gas_version)],
+        /* shared_objects */ vec![],
+        digest,
+        /* created */ vec![],
+        /* mutated */ vec![gas_object],
+        /* unwrapped */ vec![],
+        /* deleted */ vec![],
+        /* unwrapped_then_deleted */ vec![],
+        /* wrapped */ vec![],
+        gas_object,
+        /* events_digest */ None,
+        /* dependencies */ vec![],
+    );
+    let effects_digest = effects.digest();
+
+    let (authority_keys, _) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let authority_key = &authority_keys[0];
+    let signed = SignedTransactionEffects::new(
+        effects.executed_epoch(),
+        effects.clone(),
+        authority_key,
+        AuthorityPublicKeyBytes::from(authority_key.public()),
+    );
+
+    let vectors = vec![
+        TestVector::new("transfer_sui", &effects, Some(effects_digest.to_string())),
+        TestVector::new("transfer_sui_signed", signed.auth_sig(), None),
+    ];
+    (vectors, effects_digest)
+}
+
+/// A checkpoint containing `transaction`, along with its contents, the committee that certified
+/// it and the certificate.
+fn checkpoint_vectors(transaction: ExecutionDigests) -> Vec<TestVector> {
+    let (authority_keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+
+    let contents = CheckpointContents::new_with_causally_ordered_transactions([transaction]);
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        /* sequence_number */ 1,
+        /* network_total_transactions */ 2,
+        &contents,
+        /* previous_digest */ None,
+        GasCostSummary::new(1_000_000, 2_000_000, 1_500_000, 15_000),
+        /* end_of_epoch_data */ None,
+        /* timestamp_ms */ 1_700_000_000_000,
+    );
+    let certified = CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+        summary.clone(),
+        &authority_keys,
+        &committee,
+    );
+
+    vec![
+        TestVector::new("committee", &committee, None),
+        TestVector::new("contents", &contents, Some(contents.digest().to_string())),
+        TestVector::new("summary", &summary, Some(summary.digest().to_string())),
+        TestVector::new("summary_certificate", certified.auth_sig(), None),
+    ]
+}
+
+/// A 2-of-3 multisig over all single key schemes, signed by each pair of its members.
+fn multisig_vectors() -> Vec<TestVector> {
+    let keys = keys();
+    let multisig_pk = MultiSigPublicKey::new(
+        keys.iter().map(|key| key.public()).collect(),
+        vec![1, 1, 1],
+        2,
+    )
+    .expect("Multisig public key must be valid");
+    let address = SuiAddress::from(&multisig_pk);
+    let data = transfer_sui_data(address);
+    let message = IntentMessage::new(Intent::sui_transaction(), data.clone());
+    let digest = transaction_data_digest(&data);
+
+    [(0, 1), (0, 2), (1, 2)]
+        .into_iter()
+        .map(|(i, j)| {
+            let multisig = MultiSig::combine(
+                vec![
+                    Signature::new_secure(&message, &keys[i]),
+                    Signature::new_secure(&message, &keys[j]),
+                ],
+                multisig_pk.clone(),
+            )
+            .expect("Multisig must combine");
+
+            let name = format!(
+                "transfer_sui_{}_{}",
+                keys[i].public().scheme(),
+                keys[j].public().scheme()
+            );
+            TestVector::new(&name, &data, Some(digest.to_string())).with_signature(
+                SignatureVector::new(
+                    SignatureScheme::MultiSig,
+                    None,
+                    address,
+                    &GenericSignature::MultiSig(multisig),
+                ),
+            )
+        })
+        .collect()
+}
+
+/// A transfer from a zkLogin address, with a fixed proof.
+fn zklogin_vectors() -> Vec<TestVector> {
+    let address = get_zklogin_user_address();
+    let data = transfer_sui_data(address);
+    let digest = transaction_data_digest(&data);
+    let (_, _, signature) = sign_zklogin_tx(data.clone());
+
+    vec![
+        TestVector::new("transfer_sui", &data, Some(digest.to_string())).with_signature(
+            SignatureVector::new(
+                SignatureScheme::ZkLoginAuthenticator,
+                None,
+                address,
+                &signature,
+            ),
+        ),
+    ]
+}
+
+#[cfg(test)]
+#[path = "unit_tests/test_vectors_tests.rs"]
+mod test_vectors_tests;
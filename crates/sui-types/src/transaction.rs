@@ -1632,6 +1632,11 @@ impl TransactionData {
                 Owner::ObjectOwner(_) => {
                     return Err(anyhow::anyhow!("Upgrade capability controlled by object"))
                 }
+                Owner::ConsensusV2 { .. } => {
+                    return Err(anyhow::anyhow!(
+                        "Upgrade capability is consensus-owned, which is not supported yet"
+                    ))
+                }
             };
             builder.obj(capability_arg).unwrap();
             let upgrade_arg = builder.pure(upgrade_policy).unwrap();
@@ -0,0 +1,367 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Static analysis of the size and shape of a transaction, before it is signed or submitted.
+//!
+//! [`TransactionAnalysis`] reports how close a transaction is to each of the protocol's limits on
+//! its size and shape, and warns about patterns that are legal to submit, but are likely mistakes
+//! that will cause the transaction to be rejected or to fail during execution.
+
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+
+use move_core_types::language_storage::TypeTag;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::transaction::{
+    Argument, CallArg, Command, ProgrammableTransaction, TransactionData, TransactionDataAPI,
+    TransactionKind,
+};
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_analyzer_tests.rs"]
+mod transaction_analyzer_tests;
+
+/// Report on the size and shape of a transaction, relative to the limits in a protocol config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionAnalysis {
+    /// Size of the BCS serialized transaction data, excluding signatures.
+    pub size_bytes: usize,
+    pub limits: Vec<LimitUsage>,
+    pub warnings: Vec<TransactionWarning>,
+}
+
+/// Limits on the size and shape of transactions, set by the protocol config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    TransactionSize,
+    InputObjects,
+    GasPaymentObjects,
+    Commands,
+    /// Per command.
+    Arguments,
+    /// Per command, counting nested type arguments.
+    TypeArguments,
+    /// Per publish or upgrade command.
+    ModulesInPublish,
+    PublishOrUpgradeCommands,
+}
+
+/// How much of a limit a transaction uses. For limits that apply per command, `used` is the
+/// largest usage by any command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LimitUsage {
+    pub limit: Limit,
+    pub used: u64,
+    /// Largest usage that is allowed, if the protocol config sets a limit.
+    pub max: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionWarning {
+    /// A pure input larger than the protocol allows.
+    OversizedPureInput { input: u16, size: usize, max: usize },
+    /// An input that no command refers to.
+    UnusedInput { input: u16 },
+    /// The result of a command that must be consumed, but that no later command refers to.
+    DanglingResult { command: u16 },
+    /// An argument referring to an input that does not exist, or to the result of a command that
+    /// does not run before it.
+    InvalidArgument { command: u16, argument: Argument },
+}
+
+impl TransactionAnalysis {
+    pub fn new(tx_data: &TransactionData, config: &ProtocolConfig) -> Self {
+        let size_bytes = bcs::serialized_size(tx_data).expect("Transaction data is serializable");
+
+        let input_objects = tx_data
+            .input_objects()
+            .map_or_else(|_| count_object_inputs(tx_data), |objects| objects.len())
+            + tx_data.receiving_objects().len();
+
+        let mut limits = vec![
+            LimitUsage::new(
+                Limit::TransactionSize,
+                size_bytes,
+                config.max_tx_size_bytes_as_option(),
+            ),
+            LimitUsage::new(
+                Limit::InputObjects,
+                input_objects,
+                config.max_input_objects_as_option(),
+            ),
+            LimitUsage::new(
+                Limit::GasPaymentObjects,
+                tx_data.gas().len(),
+                exclusive(config.max_gas_payment_objects_as_option()),
+            ),
+        ];
+
+        let mut warnings = vec![];
+        if let TransactionKind::ProgrammableTransaction(pt) = tx_data.kind() {
+            limits.extend(programmable_limits(pt, config));
+            warnings.extend(programmable_warnings(pt, config));
+        }
+
+        Self {
+            size_bytes,
+            limits,
+            warnings,
+        }
+    }
+
+    /// Limits that the transaction exceeds, and will be rejected for.
+    pub fn exceeded_limits(&self) -> impl Iterator<Item = &LimitUsage> {
+        self.limits.iter().filter(|usage| usage.is_exceeded())
+    }
+
+    pub fn is_within_limits(&self) -> bool {
+        self.exceeded_limits().next().is_none()
+    }
+}
+
+impl LimitUsage {
+    fn new(limit: Limit, used: usize, max: Option<u64>) -> Self {
+        Self {
+            limit,
+            used: used as u64,
+            max,
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.max.is_some_and(|max| self.used > max)
+    }
+}
+
+/// Limits on programmable transactions, which are checked in the same way as
+/// `ProgrammableTransaction::validity_check`.
+fn programmable_limits(pt: &ProgrammableTransaction, config: &ProtocolConfig) -> Vec<LimitUsage> {
+    let mut arguments = 0;
+    let mut type_arguments = 0;
+    let mut modules = 0;
+    let mut publishes = 0;
+
+    for command in &pt.commands {
+        match command {
+            Command::MoveCall(call) => {
+                arguments = arguments.max(call.arguments.len());
+                let count = call.type_arguments.iter().map(count_type_arguments).sum();
+                type_arguments = type_arguments.max(count);
+            }
+            Command::TransferObjects(args, _)
+            | Command::SplitCoins(_, args)
+            | Command::MergeCoins(_, args) => arguments = arguments.max(args.len()),
+            Command::MakeMoveVec(ty, args) => {
+                arguments = arguments.max(args.len());
+                type_arguments = type_arguments.max(ty.as_ref().map_or(0, count_type_arguments));
+            }
+            Command::Publish(m, _) | Command::Upgrade(m, _, _, _) => {
+                modules = modules.max(m.len());
+                publishes += 1;
+            }
+        }
+    }
+
+    vec![
+        LimitUsage::new(
+            Limit::Commands,
+            pt.commands.len(),
+            exclusive(config.max_programmable_tx_commands_as_option()),
+        ),
+        LimitUsage::new(
+            Limit::Arguments,
+            arguments,
+            exclusive(config.max_arguments_as_option()),
+        ),
+        LimitUsage::new(
+            Limit::TypeArguments,
+            type_arguments,
+            exclusive(config.max_type_arguments_as_option()),
+        ),
+        LimitUsage::new(
+            Limit::ModulesInPublish,
+            modules,
+            exclusive(config.max_modules_in_publish_as_option()),
+        ),
+        LimitUsage::new(
+            Limit::PublishOrUpgradeCommands,
+            publishes,
+            config.max_publish_or_upgrade_per_ptb_as_option(),
+        ),
+    ]
+}
+
+fn programmable_warnings(
+    pt: &ProgrammableTransaction,
+    config: &ProtocolConfig,
+) -> Vec<TransactionWarning> {
+    let mut warnings = vec![];
+
+    if let Some(max) = exclusive(config.max_pure_argument_size_as_option()) {
+        for (input, arg) in pt.inputs.iter().enumerate() {
+            match arg {
+                CallArg::Pure(bytes) if bytes.len() as u64 > max => {
+                    warnings.push(TransactionWarning::OversizedPureInput {
+                        input: input as u16,
+                        size: bytes.len(),
+                        max: max as usize,
+                    })
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let mut used_inputs = BTreeSet::new();
+    let mut used_results = BTreeSet::new();
+    for (command, cmd) in pt.commands.iter().enumerate() {
+        let command = command as u16;
+        for argument in command_arguments(cmd) {
+            let valid = match *argument {
+                Argument::GasCoin => true,
+                Argument::Input(input) => {
+                    used_inputs.insert(input);
+                    (input as usize) < pt.inputs.len()
+                }
+                Argument::Result(result) | Argument::NestedResult(result, _) => {
+                    used_results.insert(result);
+                    result < command
+                }
+            };
+
+            if !valid {
+                warnings.push(TransactionWarning::InvalidArgument {
+                    command,
+                    argument: *argument,
+                });
+            }
+        }
+    }
+
+    warnings.extend(
+        (0..pt.inputs.len() as u16)
+            .filter(|input| !used_inputs.contains(input))
+            .map(|input| TransactionWarning::UnusedInput { input }),
+    );
+
+    // The results of Move calls may have `drop`, or there may be none, which can't be known
+    // without the signature of the function, so only commands whose results never have `drop`
+    // are checked.
+    warnings.extend(
+        pt.commands
+            .iter()
+            .enumerate()
+            .filter(|(_, cmd)| {
+                matches!(
+                    cmd,
+                    Command::SplitCoins(_, _)
+                        | Command::MakeMoveVec(_, _)
+                        | Command::Publish(_, _)
+                        | Command::Upgrade(_, _, _, _)
+                )
+            })
+            .map(|(command, _)| command as u16)
+            .filter(|command| !used_results.contains(command))
+            .map(|command| TransactionWarning::DanglingResult { command }),
+    );
+
+    warnings
+}
+
+fn command_arguments(command: &Command) -> Vec<&Argument> {
+    match command {
+        Command::MoveCall(call) => call.arguments.iter().collect(),
+        Command::TransferObjects(args, arg)
+        | Command::SplitCoins(arg, args)
+        | Command::MergeCoins(arg, args) => std::iter::once(arg).chain(args).collect(),
+        Command::MakeMoveVec(_, args) => args.iter().collect(),
+        Command::Publish(_, _) => vec![],
+        Command::Upgrade(_, _, _, ticket) => vec![ticket],
+    }
+}
+
+/// Number of type tags in `tag`, including itself.
+fn count_type_arguments(tag: &TypeTag) -> usize {
+    1 + match tag {
+        TypeTag::Vector(t) => count_type_arguments(t),
+        TypeTag::Struct(s) => s.type_params.iter().map(count_type_arguments).sum(),
+        _ => 0,
+    }
+}
+
+/// Counts object inputs directly, for transactions whose input objects can't be listed, because
+/// they are malformed.
+fn count_object_inputs(tx_data: &TransactionData) -> usize {
+    let objects = match tx_data.kind() {
+        TransactionKind::ProgrammableTransaction(pt) => pt
+            .inputs
+            .iter()
+            .filter(|arg| matches!(arg, CallArg::Object(_)))
+            .count(),
+        _ => 0,
+    };
+    objects + tx_data.gas().len()
+}
+
+/// Largest usage allowed by limits that must not be reached.
+fn exclusive(max: Option<impl Into<u64>>) -> Option<u64> {
+    max.map(|max| max.into().saturating_sub(1))
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Limit::TransactionSize => "transaction size (bytes)",
+            Limit::InputObjects => "input objects",
+            Limit::GasPaymentObjects => "gas payment objects",
+            Limit::Commands => "commands",
+            Limit::Arguments => "arguments per command",
+            Limit::TypeArguments => "type arguments per command",
+            Limit::ModulesInPublish => "modules per publish or upgrade",
+            Limit::PublishOrUpgradeCommands => "publish or upgrade commands",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Display for TransactionWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionWarning::OversizedPureInput { input, size, max } => write!(
+                f,
+                "Pure input {input} is {size} bytes, larger than the maximum of {max}"
+            ),
+            TransactionWarning::UnusedInput { input } => {
+                write!(f, "Input {input} is not used by any command")
+            }
+            TransactionWarning::DanglingResult { command } => write!(
+                f,
+                "Result of command {command} is not used, so execution will fail"
+            ),
+            TransactionWarning::InvalidArgument { command, argument } => write!(
+                f,
+                "Command {command} refers to {argument:?}, which is not available"
+            ),
+        }
+    }
+}
+
+impl Display for TransactionAnalysis {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for usage in &self.limits {
+            write!(f, "{}: {}", usage.limit, usage.used)?;
+            if let Some(max) = usage.max {
+                write!(f, " / {max}")?;
+            }
+            if usage.is_exceeded() {
+                write!(f, " (exceeded)")?;
+            }
+            writeln!(f)?;
+        }
+        for warning in &self.warnings {
+            writeln!(f, "Warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
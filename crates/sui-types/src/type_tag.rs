@@ -0,0 +1,310 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Parsing and printing of Move types, as they appear in CLI arguments, RPC requests and filters.
+//!
+//! The parser accepts addresses in their short (`0x2::sui::SUI`) and full (`0x00..02::sui::SUI`)
+//! hex forms, as well as the well-known named addresses (`std`, `sui`, `sui_system`, `deepbook`),
+//! and arbitrarily nested type parameters. Errors point at the byte offset in the input where
+//! parsing failed.
+
+use std::fmt::{self, Write};
+
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, TypeTag};
+use thiserror::Error;
+
+use crate::{DEEPBOOK_ADDRESS, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS};
+
+/// Types nested deeper than this are rejected, to bound the parser's recursion on untrusted input.
+const MAX_TYPE_NESTING_DEPTH: usize = 128;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Failed to parse type at position {position}: {reason}")]
+pub struct TypeTagParseError {
+    /// Byte offset into the input where parsing failed.
+    pub position: usize,
+    pub reason: String,
+}
+
+/// Parse a type, e.g. `u64`, `vector<0x2::sui::SUI>` or `0x2::coin::Coin<0x2::sui::SUI>`.
+pub fn parse_type_tag(s: &str) -> Result<TypeTag, TypeTagParseError> {
+    let mut parser = Parser::new(s);
+    let tag = parser.type_tag(0)?;
+    parser.end()?;
+    Ok(tag)
+}
+
+/// Parse a struct type, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+pub fn parse_struct_tag(s: &str) -> Result<StructTag, TypeTagParseError> {
+    let mut parser = Parser::new(s);
+    let (start, word) = parser.word("address")?;
+    let tag = parser.struct_tag(start, word, 0)?;
+    parser.end()?;
+    Ok(tag)
+}
+
+/// Print a type with addresses in their short form, without leading zeros (`0x2::sui::SUI`).
+pub fn to_short_string(tag: &TypeTag) -> String {
+    format_type_tag(tag, AddressForm::Short)
+}
+
+/// Print a type with addresses in their full, 32 byte form (`0x00..02::sui::SUI`).
+pub fn to_canonical_string(tag: &TypeTag) -> String {
+    format_type_tag(tag, AddressForm::Full)
+}
+
+/// Print a struct type with addresses in their short form, without leading zeros.
+pub fn struct_to_short_string(tag: &StructTag) -> String {
+    format_struct_tag(tag, AddressForm::Short)
+}
+
+/// Print a struct type with addresses in their full, 32 byte form.
+pub fn struct_to_canonical_string(tag: &StructTag) -> String {
+    format_struct_tag(tag, AddressForm::Full)
+}
+
+/// Resolve the named addresses that can stand in for a hex address in a type.
+fn resolve_address(addr: &str) -> Option<AccountAddress> {
+    match addr {
+        "deepbook" => Some(DEEPBOOK_ADDRESS),
+        "std" => Some(MOVE_STDLIB_ADDRESS),
+        "sui" => Some(SUI_FRAMEWORK_ADDRESS),
+        "sui_system" => Some(SUI_SYSTEM_ADDRESS),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AddressForm {
+    Short,
+    Full,
+}
+
+fn format_type_tag(tag: &TypeTag, form: AddressForm) -> String {
+    let mut s = String::new();
+    write_type_tag(&mut s, tag, form).expect("Writing to a String cannot fail");
+    s
+}
+
+fn format_struct_tag(tag: &StructTag, form: AddressForm) -> String {
+    let mut s = String::new();
+    write_struct_tag(&mut s, tag, form).expect("Writing to a String cannot fail");
+    s
+}
+
+fn write_type_tag(f: &mut impl Write, tag: &TypeTag, form: AddressForm) -> fmt::Result {
+    match tag {
+        TypeTag::Vector(inner) => {
+            write!(f, "vector<")?;
+            write_type_tag(f, inner, form)?;
+            write!(f, ">")
+        }
+        TypeTag::Struct(s) => write_struct_tag(f, s, form),
+        prim => write!(f, "{prim}"),
+    }
+}
+
+fn write_struct_tag(f: &mut impl Write, tag: &StructTag, form: AddressForm) -> fmt::Result {
+    match form {
+        AddressForm::Short => write!(f, "0x{}", tag.address.short_str_lossless())?,
+        AddressForm::Full => write!(f, "0x{}", tag.address.to_canonical_string())?,
+    }
+    write!(f, "::{}::{}", tag.module, tag.name)?;
+
+    let mut params = tag.type_params.iter();
+    if let Some(first) = params.next() {
+        write!(f, "<")?;
+        write_type_tag(f, first, form)?;
+        for param in params {
+            write!(f, ", ")?;
+            write_type_tag(f, param, form)?;
+        }
+        write!(f, ">")?;
+    }
+
+    Ok(())
+}
+
+struct Parser<'s> {
+    input: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn new(input: &'s str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(position: usize, reason: impl Into<String>) -> TypeTagParseError {
+        TypeTagParseError {
+            position,
+            reason: reason.into(),
+        }
+    }
+
+    /// Error describing what was found at the current position, instead of `expected`.
+    fn unexpected(&self, expected: &str) -> TypeTagParseError {
+        let reason = match self.rest().chars().next() {
+            None => format!("unexpected end of input, expected {expected}"),
+            Some(c) => format!("unexpected '{c}', expected {expected}"),
+        };
+        Self::error(self.pos, reason)
+    }
+
+    /// Consume `token` if it is next in the input (after whitespace).
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), TypeTagParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("'{token}'")))
+        }
+    }
+
+    /// Consume the next run of alphanumeric characters and underscores, returning it along with
+    /// its starting position.
+    fn word(&mut self, expected: &str) -> Result<(usize, &'s str), TypeTagParseError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+
+        if len == 0 {
+            return Err(self.unexpected(expected));
+        }
+
+        let start = self.pos;
+        self.pos += len;
+        Ok((start, &rest[..len]))
+    }
+
+    fn end(&mut self) -> Result<(), TypeTagParseError> {
+        self.skip_whitespace();
+        if self.rest().is_empty() {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of input"))
+        }
+    }
+
+    fn type_tag(&mut self, depth: usize) -> Result<TypeTag, TypeTagParseError> {
+        let (start, word) = self.word("type")?;
+        if depth > MAX_TYPE_NESTING_DEPTH {
+            return Err(Self::error(
+                start,
+                format!("type is nested more than {MAX_TYPE_NESTING_DEPTH} levels deep"),
+            ));
+        }
+
+        Ok(match word {
+            "bool" => TypeTag::Bool,
+            "u8" => TypeTag::U8,
+            "u16" => TypeTag::U16,
+            "u32" => TypeTag::U32,
+            "u64" => TypeTag::U64,
+            "u128" => TypeTag::U128,
+            "u256" => TypeTag::U256,
+            "address" => TypeTag::Address,
+            "signer" => TypeTag::Signer,
+            "vector" => {
+                self.expect("<")?;
+                let inner = self.type_tag(depth + 1)?;
+                self.expect(">")?;
+                TypeTag::Vector(Box::new(inner))
+            }
+            _ => TypeTag::Struct(Box::new(self.struct_tag(start, word, depth)?)),
+        })
+    }
+
+    /// Parse the remainder of a struct type, whose address (`addr`, found at `start`) has already
+    /// been consumed.
+    fn struct_tag(
+        &mut self,
+        start: usize,
+        addr: &str,
+        depth: usize,
+    ) -> Result<StructTag, TypeTagParseError> {
+        let address = Self::address(start, addr)?;
+        self.expect("::")?;
+        let module = self.identifier()?;
+        self.expect("::")?;
+        let name = self.identifier()?;
+
+        let mut type_params = vec![];
+        if self.eat("<") {
+            loop {
+                type_params.push(self.type_tag(depth + 1)?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(">")?;
+        }
+
+        Ok(StructTag {
+            address,
+            module,
+            name,
+            type_params,
+        })
+    }
+
+    fn address(start: usize, addr: &str) -> Result<AccountAddress, TypeTagParseError> {
+        let Some(hex) = addr.strip_prefix("0x").or_else(|| addr.strip_prefix("0X")) else {
+            return resolve_address(addr)
+                .ok_or_else(|| Self::error(start, format!("unknown type or address '{addr}'")));
+        };
+
+        if let Some(offset) = hex.find(|c: char| !c.is_ascii_hexdigit()) {
+            return Err(Self::error(
+                start + 2 + offset,
+                format!("invalid hex digit in address '{addr}'"),
+            ));
+        }
+
+        if hex.is_empty() || hex.len() > AccountAddress::LENGTH * 2 {
+            return Err(Self::error(
+                start,
+                format!(
+                    "address '{addr}' must have between 1 and {} hex digits",
+                    AccountAddress::LENGTH * 2
+                ),
+            ));
+        }
+
+        let padded = format!("{hex:0>width$}", width = AccountAddress::LENGTH * 2);
+        AccountAddress::from_hex(padded)
+            .map_err(|_| Self::error(start, format!("invalid address '{addr}'")))
+    }
+
+    fn identifier(&mut self) -> Result<Identifier, TypeTagParseError> {
+        let (start, word) = self.word("identifier")?;
+        Identifier::new(word)
+            .map_err(|_| Self::error(start, format!("invalid identifier '{word}'")))
+    }
+}
+
+#[cfg(test)]
+#[path = "unit_tests/type_tag_tests.rs"]
+mod type_tag_tests;
@@ -17,7 +17,11 @@ use crate::crypto::{
 };
 use crate::digests::Digest;
 use crate::id::{ID, UID};
-use crate::{gas_coin::GasCoin, object::Object, SUI_FRAMEWORK_ADDRESS};
+use crate::{
+    gas_coin::GasCoin,
+    object::{Authenticator, Object, Owner},
+    SUI_FRAMEWORK_ADDRESS,
+};
 use shared_crypto::intent::{Intent, IntentMessage, IntentScope};
 use sui_protocol_config::ProtocolConfig;
 
@@ -465,3 +469,33 @@ fn next_lexicographical_digest() {
         Some(Digest::from(output))
     );
 }
+
+#[test]
+fn consensus_v2_owner() {
+    let address = SuiAddress::random_for_testing_only();
+    let owner = Owner::ConsensusV2 {
+        start_version: SequenceNumber::from_u64(5),
+        authenticator: Authenticator::SingleOwner(address),
+    };
+    assert!(owner.is_consensus_owned());
+    assert!(owner.is_consensus());
+    assert!(!owner.is_address_owned());
+    assert!(!owner.is_shared());
+    assert_eq!(owner.start_version(), Some(SequenceNumber::from_u64(5)));
+    assert!(owner.get_address_owner_address().is_err());
+    assert!(owner.get_owner_address().is_err());
+    assert_ne!(owner, address);
+
+    // The variant is appended, so the encoding of the other owners is unchanged
+    let bytes = bcs::to_bytes(&owner).unwrap();
+    assert_eq!(bytes[0], 4);
+    assert_eq!(bcs::from_bytes::<Owner>(&bytes).unwrap(), owner);
+
+    let shared = Owner::Shared {
+        initial_shared_version: SequenceNumber::from_u64(3),
+    };
+    assert!(shared.is_consensus());
+    assert!(!shared.is_consensus_owned());
+    assert_eq!(shared.start_version(), Some(SequenceNumber::from_u64(3)));
+    assert_eq!(Owner::AddressOwner(address).start_version(), None);
+}
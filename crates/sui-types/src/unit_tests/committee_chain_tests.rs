@@ -0,0 +1,93 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::utils::{make_epoch_checkpoint, make_test_epochs};
+
+#[test]
+fn test_verify_committee_chain_proof() {
+    let epochs = make_test_epochs(3);
+    let proof = CommitteeChainProof::new(vec![
+        make_epoch_checkpoint(&epochs[0], 10, Some(&epochs[1])),
+        make_epoch_checkpoint(&epochs[1], 20, Some(&epochs[2])),
+    ]);
+
+    let mut chain = CommitteeChain::new(epochs[0].committee.clone());
+    assert!(matches!(
+        chain.verify_checkpoint(&make_epoch_checkpoint(&epochs[2], 30, None)),
+        Err(SuiError::MissingCommitteeAtEpoch(2))
+    ));
+
+    let proof = CommitteeChainProof::from_bytes(&proof.to_bytes()).unwrap();
+    chain.verify_proof(&proof).unwrap();
+    assert_eq!(chain.latest_committee(), &epochs[2].committee);
+    assert_eq!(chain.committees().count(), 3);
+    chain
+        .verify_checkpoint(&make_epoch_checkpoint(&epochs[2], 30, None))
+        .unwrap();
+}
+
+#[test]
+fn test_reject_invalid_handoffs() {
+    let epochs = make_test_epochs(3);
+    let mut chain = CommitteeChain::new(epochs[0].committee.clone());
+
+    // Skips the handoff from epoch 0.
+    let skipping = CommitteeChainProof::new(vec![make_epoch_checkpoint(
+        &epochs[1],
+        20,
+        Some(&epochs[2]),
+    )]);
+    assert!(matches!(
+        chain.verify_proof(&skipping),
+        Err(SuiError::InvalidCommitteeChain { .. })
+    ));
+
+    // Not the last checkpoint of its epoch.
+    let not_end_of_epoch =
+        CommitteeChainProof::new(vec![make_epoch_checkpoint(&epochs[0], 5, None)]);
+    assert!(matches!(
+        chain.verify_proof(&not_end_of_epoch),
+        Err(SuiError::InvalidCommitteeChain { .. })
+    ));
+
+    // Claims to be from epoch 0, but is signed by the committee of epoch 1.
+    let forged = CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+        make_epoch_checkpoint(&epochs[0], 10, Some(&epochs[1])).into_data(),
+        &epochs[1].keys,
+        &Committee::new(
+            0,
+            epochs[1].committee.voting_rights.iter().cloned().collect(),
+        ),
+    );
+    assert!(chain
+        .verify_proof(&CommitteeChainProof::new(vec![forged]))
+        .is_err());
+
+    assert_eq!(chain.latest_committee().epoch, 0);
+    assert!(CommitteeChainProof::from_bytes(&[0, 1, 2]).is_err());
+}
+
+#[test]
+fn test_stake_weighted_quorum() {
+    let epochs = make_test_epochs(1);
+    let chain = CommitteeChain::new(epochs[0].committee.clone());
+    let names: Vec<_> = epochs[0].committee.names().collect();
+
+    assert!(chain.has_quorum(0, names.iter().copied()).unwrap());
+    assert!(!chain.has_quorum(0, []).unwrap());
+
+    // Every member has the same stake, so one member alone is not a quorum, and counting it
+    // twice does not help.
+    let one = [names[0], names[0]];
+    assert_eq!(
+        chain.stake(0, one).unwrap(),
+        epochs[0].committee.weight(names[0])
+    );
+    assert!(!chain.has_quorum(0, one).unwrap());
+
+    assert!(matches!(
+        chain.has_validity(1, names.iter().copied()),
+        Err(SuiError::MissingCommitteeAtEpoch(1))
+    ));
+}
@@ -0,0 +1,138 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{ObjectDigest, SuiAddress};
+use crate::message_envelope::Message;
+
+fn object_ref(id: ObjectID, version: u64) -> ObjectRef {
+    (
+        id,
+        SequenceNumber::from_u64(version),
+        ObjectDigest::random(),
+    )
+}
+
+/// Effects of a transaction at lamport version 10 that touches a shared object, mutates the gas
+/// coin and another object owned by the sender, creates an object, and deletes one.
+fn effects() -> TransactionEffects {
+    let sender = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+    let (gas, owned, shared, deleted) = (
+        ObjectID::random(),
+        ObjectID::random(),
+        ObjectID::random(),
+        ObjectID::random(),
+    );
+    let gas_object = (object_ref(gas, 10), sender);
+
+    TransactionEffects::V1(TransactionEffectsV1::new(
+        ExecutionStatus::Success,
+        /* executed_epoch */ 3,
+        GasCostSummary::new(1_000, 2_000, 500, 5),
+        /* modified_at_versions */
+        vec![(gas, 9.into()), (owned, 2.into()), (deleted, 7.into())],
+        /* shared_objects */ vec![object_ref(shared, 4)],
+        TransactionDigest::random(),
+        /* created */ vec![(object_ref(ObjectID::random(), 10), sender)],
+        /* mutated */ vec![gas_object, (object_ref(owned, 10), sender)],
+        /* unwrapped */ vec![],
+        /* deleted */
+        vec![(deleted, 10.into(), ObjectDigest::OBJECT_DIGEST_DELETED)],
+        /* unwrapped_then_deleted */ vec![],
+        /* wrapped */ vec![],
+        gas_object,
+        /* events_digest */ None,
+        /* dependencies */ vec![TransactionDigest::random(), TransactionDigest::random()],
+    ))
+}
+
+#[test]
+fn test_round_trip() {
+    let effects = effects();
+    let compact = CompactTransactionEffects::new(&effects);
+    assert_eq!(compact.transaction_digest(), effects.transaction_digest());
+
+    let restored = CompactTransactionEffects::from_bytes(&compact.to_bytes()).unwrap();
+    assert_eq!(restored, compact);
+    let restored = restored.to_effects().unwrap();
+    assert_eq!(restored, effects);
+    assert_eq!(restored.digest(), effects.digest());
+
+    let empty = TransactionEffects::default();
+    let compact = CompactTransactionEffects::new(&empty);
+    assert_eq!(compact.to_effects().unwrap(), empty);
+}
+
+#[test]
+fn test_compact_encoding() {
+    let effects = effects();
+    let compact = CompactTransactionEffects::new(&effects);
+
+    // The gas coin and the sender's object appear twice, but are stored once.
+    assert_eq!(compact.object_ids.len(), 5);
+    assert_eq!(compact.owners.len(), 1);
+    assert_eq!(compact.lamport_version, SequenceNumber::from_u64(10));
+    assert_eq!(compact.mutated[0].0.version, CompactVersion::Lamport);
+    assert_eq!(compact.modified_at_versions[0].1, CompactVersion::Delta(1));
+
+    let canonical = bcs::to_bytes(&effects).unwrap();
+    assert!(
+        compact.to_bytes().len() < canonical.len(),
+        "{} >= {}",
+        compact.to_bytes().len(),
+        canonical.len()
+    );
+}
+
+#[test]
+fn test_versions_too_far_apart_for_a_delta() {
+    let sender = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+    let gas = ObjectID::random();
+    let gas_object = (object_ref(gas, 1 << 40), sender);
+    let effects = TransactionEffects::V1(TransactionEffectsV1::new(
+        ExecutionStatus::Success,
+        /* executed_epoch */ 0,
+        GasCostSummary::default(),
+        /* modified_at_versions */ vec![(gas, 1.into())],
+        /* shared_objects */ vec![],
+        TransactionDigest::random(),
+        /* created */ vec![],
+        /* mutated */ vec![gas_object],
+        /* unwrapped */ vec![],
+        /* deleted */ vec![],
+        /* unwrapped_then_deleted */ vec![],
+        /* wrapped */ vec![],
+        gas_object,
+        /* events_digest */ None,
+        /* dependencies */ vec![],
+    ));
+
+    let compact = CompactTransactionEffects::new(&effects);
+    assert_eq!(
+        compact.modified_at_versions,
+        vec![(0, CompactVersion::Absolute(1.into()))]
+    );
+    assert_eq!(compact.to_effects().unwrap(), effects);
+}
+
+#[test]
+fn test_malformed() {
+    let mut compact = CompactTransactionEffects::new(&effects());
+    compact.owners.clear();
+    assert!(matches!(
+        compact.to_effects(),
+        Err(SuiError::InvalidCompactEffects { .. })
+    ));
+
+    let mut compact = CompactTransactionEffects::new(&effects());
+    compact.modified_at_versions[0].1 = CompactVersion::Delta(11);
+    assert!(matches!(
+        compact.to_effects(),
+        Err(SuiError::InvalidCompactEffects { .. })
+    ));
+
+    assert!(matches!(
+        CompactTransactionEffects::from_bytes(&[1, 2, 3]),
+        Err(SuiError::InvalidCompactEffects { .. })
+    ));
+}
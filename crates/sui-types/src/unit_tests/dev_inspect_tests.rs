@@ -0,0 +1,84 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::{AbilitySet, Visibility};
+
+use super::*;
+use crate::in_memory_storage::InMemoryStorage;
+
+// fun f<T>(): (u64, &T, vector<T>)
+fn function() -> normalized::Function {
+    use normalized::Type;
+    normalized::Function {
+        visibility: Visibility::Public,
+        is_entry: false,
+        type_parameters: vec![AbilitySet::EMPTY],
+        parameters: vec![],
+        return_: vec![
+            Type::U64,
+            Type::Reference(Box::new(Type::TypeParameter(0))),
+            Type::Vector(Box::new(Type::TypeParameter(0))),
+        ],
+        code: vec![],
+    }
+}
+
+#[test]
+fn test_decode_return_values() {
+    let store = InMemoryStorage::new(vec![]);
+    let decoder = ReturnValueDecoder::new(&function(), &[TypeTag::U8], &store).unwrap();
+    assert_eq!(decoder.len(), 3);
+    assert_eq!(decoder.type_(1), Some(&TypeTag::U8));
+
+    let u64_bytes = bcs::to_bytes(&42u64).unwrap();
+    assert_eq!(
+        decoder.decode::<u64>(0, &u64_bytes, &TypeTag::U64).unwrap(),
+        42
+    );
+    assert_eq!(
+        decoder.decode_value(1, &[7], &TypeTag::U8).unwrap(),
+        MoveValue::U8(7)
+    );
+
+    let vector_type = TypeTag::Vector(Box::new(TypeTag::U8));
+    let vector_bytes = bcs::to_bytes(&vec![1u8, 2, 3]).unwrap();
+    assert_eq!(
+        decoder
+            .decode::<Vec<u8>>(2, &vector_bytes, &vector_type)
+            .unwrap(),
+        vec![1, 2, 3]
+    );
+}
+
+#[test]
+fn test_decode_return_values_errors() {
+    let store = InMemoryStorage::new(vec![]);
+    assert!(matches!(
+        ReturnValueDecoder::new(&function(), &[], &store),
+        Err(SuiError::TypeError { .. })
+    ));
+
+    let decoder = ReturnValueDecoder::new(&function(), &[TypeTag::U8], &store).unwrap();
+    let u64_bytes = bcs::to_bytes(&42u64).unwrap();
+
+    // Not the type of the function's return value
+    assert!(matches!(
+        decoder.decode::<u64>(1, &u64_bytes, &TypeTag::U64),
+        Err(SuiError::TypeError { .. })
+    ));
+    // No such return value
+    assert!(matches!(
+        decoder.decode::<u64>(3, &u64_bytes, &TypeTag::U64),
+        Err(SuiError::TypeError { .. })
+    ));
+    // Bytes that are not a `u64`
+    assert!(matches!(
+        decoder.decode::<u64>(0, &[1, 2], &TypeTag::U64),
+        Err(SuiError::ObjectDeserializationError { .. })
+    ));
+    // Rust type that does not match the layout of a `u64`
+    assert!(matches!(
+        decoder.decode::<u32>(0, &u64_bytes, &TypeTag::U64),
+        Err(SuiError::ObjectDeserializationError { .. })
+    ));
+}
@@ -0,0 +1,32 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_audit_is_deterministic() {
+    let digests = audit();
+    assert_eq!(digests, audit());
+    assert!(digests.keys().any(|name| name.starts_with("transaction_effects/")));
+    assert!(digests.keys().any(|name| name.starts_with("object/")));
+}
+
+#[test]
+fn test_digests_match_snapshot() {
+    let mut path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.extend(["tests", "staged", "digests.yaml"]);
+    check_digest_snapshot(&path);
+}
+
+#[test]
+#[should_panic(expected = "The digest of object/immutable changed")]
+fn test_changed_digest_is_rejected() {
+    let path = std::env::temp_dir().join(format!("digests-{}.yaml", std::process::id()));
+    let mut digests = audit();
+    digests.insert(
+        "object/immutable".to_string(),
+        ObjectDigest::new([0; 32]).to_string(),
+    );
+    std::fs::write(&path, serde_yaml::to_string(&digests).unwrap()).unwrap();
+    check_digest_snapshot(&path);
+}
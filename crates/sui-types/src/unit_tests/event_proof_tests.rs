@@ -0,0 +1,148 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sui_protocol_config::ProtocolVersion;
+
+use super::*;
+use crate::base_types::{random_object_ref, TransactionDigest};
+use crate::crypto::AuthorityKeyPair;
+use crate::execution_status::ExecutionStatus;
+use crate::gas::GasCostSummary;
+use crate::messages_checkpoint::CheckpointSummary;
+use crate::object::Owner;
+use crate::utils::make_committee_key;
+
+fn effects_with_events(events: &TransactionEvents) -> TransactionEffects {
+    let gas_object = random_object_ref();
+    TransactionEffects::new_from_execution(
+        ProtocolVersion::MAX,
+        ExecutionStatus::Success,
+        /* executed_epoch */ 0,
+        GasCostSummary::default(),
+        vec![(gas_object.0, gas_object.1)],
+        vec![],
+        TransactionDigest::random(),
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        (gas_object, Owner::AddressOwner(Default::default())),
+        Some(events.digest()),
+        vec![],
+    )
+}
+
+fn event_proof(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    event_seq: u64,
+    events: TransactionEvents,
+    effects: TransactionEffects,
+    included: &[&TransactionEffects],
+) -> EventProof {
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        included.iter().map(|e| e.execution_digests()),
+    );
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        /* sequence_number */ 1,
+        /* network_total_transactions */ included.len() as u64,
+        &contents,
+        /* previous_digest */ None,
+        GasCostSummary::default(),
+        /* end_of_epoch_data */ None,
+        /* timestamp_ms */ 0,
+    );
+    EventProof::new(
+        event_seq,
+        events,
+        effects,
+        CertifiedCheckpointSummary::new_from_keypairs_for_testing(summary, keys, committee),
+        contents,
+    )
+}
+
+fn events() -> TransactionEvents {
+    TransactionEvents {
+        data: vec![Event::random_for_testing(), Event::random_for_testing()],
+    }
+}
+
+#[test]
+fn test_verify_event_proof() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let events = events();
+    let effects = effects_with_events(&events);
+    let other = effects_with_events(&TransactionEvents::default());
+    let proof = event_proof(
+        &keys,
+        &committee,
+        1,
+        events.clone(),
+        effects.clone(),
+        &[&other, &effects],
+    );
+
+    let (id, event) = verify_event_proof(&proof.to_bytes(), &committee).unwrap();
+    assert_eq!(id, (*effects.transaction_digest(), 1).into());
+    assert_eq!(event, events.data[1]);
+}
+
+#[test]
+fn test_reject_transaction_not_in_checkpoint() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let events = events();
+    let effects = effects_with_events(&events);
+    let other = effects_with_events(&TransactionEvents::default());
+    let proof = event_proof(&keys, &committee, 0, events, effects, &[&other]);
+
+    assert!(matches!(
+        verify_event_proof(&proof.to_bytes(), &committee),
+        Err(SuiError::InvalidEventProof { .. })
+    ));
+}
+
+#[test]
+fn test_reject_events_not_in_effects() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let effects = effects_with_events(&events());
+    let proof = event_proof(&keys, &committee, 0, events(), effects.clone(), &[&effects]);
+
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidEventProof { .. })
+    ));
+}
+
+#[test]
+fn test_reject_missing_event() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let events = events();
+    let effects = effects_with_events(&events);
+    let proof = event_proof(&keys, &committee, 2, events, effects.clone(), &[&effects]);
+
+    assert!(matches!(
+        proof.verify(&committee),
+        Err(SuiError::InvalidEventProof { .. })
+    ));
+}
+
+#[test]
+fn test_reject_wrong_committee() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let (_, other_committee) = make_committee_key(&mut rng);
+    let events = events();
+    let effects = effects_with_events(&events);
+    let proof = event_proof(&keys, &committee, 0, events, effects.clone(), &[&effects]);
+
+    assert!(proof.verify(&other_committee).is_err());
+    assert!(matches!(
+        verify_event_proof(&[0, 1, 2], &committee),
+        Err(SuiError::InvalidEventProof { .. })
+    ));
+}
@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use super::*;
+use crate::base_types::{random_object_ref, TransactionDigest};
+use crate::crypto::AuthorityKeyPair;
+use crate::gas::GasCostSummary;
+use crate::messages_checkpoint::CheckpointSummary;
+use crate::utils::make_committee_key;
+
+fn certified_effects(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+) -> CertifiedTransactionEffects {
+    let mut effects = TransactionEffects::default();
+    *effects.transaction_digest_mut_for_testing() = TransactionDigest::random();
+    effects.unsafe_add_deleted_object_for_testing(random_object_ref());
+    CertifiedTransactionEffects::new_from_keypairs_for_testing(effects, keys, committee)
+}
+
+fn finality_proof(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    effects: CertifiedTransactionEffects,
+    included: &[&CertifiedTransactionEffects],
+) -> FinalityProof {
+    let contents = CheckpointContents::new_with_causally_ordered_transactions(
+        included
+            .iter()
+            .map(|e| ExecutionDigests::new(*e.transaction_digest(), e.data().digest())),
+    );
+    let summary = CheckpointSummary::new(
+        committee.epoch,
+        /* sequence_number */ 1,
+        /* network_total_transactions */ included.len() as u64,
+        &contents,
+        /* previous_digest */ None,
+        GasCostSummary::default(),
+        /* end_of_epoch_data */ None,
+        /* timestamp_ms */ 0,
+    );
+    FinalityProof::new(
+        effects,
+        CertifiedCheckpointSummary::new_from_keypairs_for_testing(summary, keys, committee),
+        contents,
+    )
+}
+
+#[test]
+fn test_verify_finality_proof() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let effects = certified_effects(&keys, &committee);
+    let other = certified_effects(&keys, &committee);
+    let proof = finality_proof(&keys, &committee, effects.clone(), &[&other, &effects]);
+
+    let verified = verify_finality_proof(&proof.to_bytes(), &committee).unwrap();
+    assert_eq!(verified, *effects.data());
+}
+
+#[test]
+fn test_reject_transaction_not_in_checkpoint() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let effects = certified_effects(&keys, &committee);
+    let other = certified_effects(&keys, &committee);
+    let proof = finality_proof(&keys, &committee, effects, &[&other]);
+
+    assert!(matches!(
+        verify_finality_proof(&proof.to_bytes(), &committee),
+        Err(SuiError::InvalidFinalityProof { .. })
+    ));
+}
+
+#[test]
+fn test_reject_wrong_committee() {
+    let mut rng = StdRng::from_seed([0; 32]);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let (_, other_committee) = make_committee_key(&mut rng);
+    let effects = certified_effects(&keys, &committee);
+    let proof = finality_proof(&keys, &committee, effects.clone(), &[&effects]);
+
+    assert!(verify_finality_proof(&proof.to_bytes(), &other_committee).is_err());
+}
+
+#[test]
+fn test_reject_tampered_contents() {
+    let (keys, committee) = make_committee_key(&mut StdRng::from_seed([0; 32]));
+    let effects = certified_effects(&keys, &committee);
+    let other = certified_effects(&keys, &committee);
+    let mut proof = finality_proof(&keys, &committee, effects.clone(), &[&other]);
+
+    // Swapping in contents that include the transaction breaks the checkpoint signature.
+    proof.checkpoint_contents =
+        CheckpointContents::new_with_causally_ordered_transactions([ExecutionDigests::new(
+            *effects.transaction_digest(),
+            effects.data().digest(),
+        )]);
+    assert!(proof.verify(&committee).is_err());
+
+    assert!(matches!(
+        verify_finality_proof(&[0, 1, 2], &committee),
+        Err(SuiError::InvalidFinalityProof { .. })
+    ));
+}
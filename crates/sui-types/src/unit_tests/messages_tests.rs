@@ -19,6 +19,7 @@ use crate::digests::TransactionEventsDigest;
 use crate::effects::{SignedTransactionEffects, TransactionEffects, TransactionEffectsAPI};
 use crate::execution_status::ExecutionStatus;
 use crate::gas::GasCostSummary;
+use crate::message_envelope::batch_verify_committee_sigs;
 use crate::object::Owner;
 use fastcrypto::traits::AggregateAuthenticator;
 use fastcrypto::traits::KeyPair;
@@ -1384,3 +1385,46 @@ fn check_approx_effects_components_size() {
         "Update APPROX_SIZE_OF_EXECUTION_STATUS constant"
     );
 }
+
+#[test]
+fn test_batch_verify_committee_sigs() {
+    let (_, sec1): (_, AuthorityKeyPair) = get_key_pair();
+    let (_, sec2): (_, AuthorityKeyPair) = get_key_pair();
+    let (a_sender, sender_sec): (_, AccountKeyPair) = get_key_pair();
+
+    let mut authorities: BTreeMap<AuthorityPublicKeyBytes, u64> = BTreeMap::new();
+    authorities.insert(AuthorityPublicKeyBytes::from(sec1.public()), 1);
+    authorities.insert(AuthorityPublicKeyBytes::from(sec2.public()), 1);
+    let committee = Committee::new_for_testing_with_normalized_voting_power(0, authorities);
+    let keypairs = [sec1, sec2];
+
+    let gas_price = 10;
+    let mut certs: Vec<_> = (0..3)
+        .map(|_| {
+            let transaction = Transaction::from_data_and_signer(
+                TransactionData::new_transfer(
+                    SuiAddress::random_for_testing_only(),
+                    random_object_ref(),
+                    a_sender,
+                    random_object_ref(),
+                    TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+                    gas_price,
+                ),
+                Intent::sui_transaction(),
+                vec![&sender_sec],
+            );
+            CertifiedTransaction::new_from_keypairs_for_testing(
+                transaction.into_data(),
+                &keypairs,
+                &committee,
+            )
+        })
+        .collect();
+
+    assert!(batch_verify_committee_sigs(&committee, &certs).is_ok());
+
+    // Swap in the signatures of another certificate, so that only the middle one is invalid.
+    *certs[1].auth_sig_mut_for_testing() = certs[0].auth_sig().clone();
+    assert!(batch_verify_committee_sigs(&committee, &certs).is_err());
+    assert!(batch_verify_committee_sigs(&committee, &certs[2..]).is_ok());
+}
@@ -0,0 +1,183 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{ObjectDigest, SuiAddress};
+use crate::digests::TransactionDigest;
+use crate::effects::TransactionEffectsV1;
+use crate::execution_status::ExecutionStatus;
+use crate::gas::GasCostSummary;
+
+fn object_ref(id: ObjectID, version: u64) -> ObjectRef {
+    (
+        id,
+        SequenceNumber::from_u64(version),
+        ObjectDigest::random(),
+    )
+}
+
+fn tombstone(id: ObjectID, version: u64, digest: ObjectDigest) -> ObjectRef {
+    (id, SequenceNumber::from_u64(version), digest)
+}
+
+/// The input object `id` at `version`, owned by `owner`.
+fn input(id: ObjectID, version: u64, owner: SuiAddress) -> Object {
+    Object::with_id_owner_version_for_testing(id, SequenceNumber::from_u64(version), owner)
+}
+
+#[test]
+fn test_object_change_kinds() {
+    let (sender, recipient) = (
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    );
+    let [gas, mutated, transferred, created, unwrapped, deleted, wrapped, unwrapped_then_deleted] =
+        [(); 8].map(|_| ObjectID::random());
+
+    let gas_object = (object_ref(gas, 10), Owner::AddressOwner(sender));
+    let effects = TransactionEffects::V1(TransactionEffectsV1::new(
+        ExecutionStatus::Success,
+        /* executed_epoch */ 0,
+        GasCostSummary::default(),
+        /* modified_at_versions */
+        vec![
+            (gas, 9.into()),
+            (mutated, 2.into()),
+            (transferred, 3.into()),
+            (deleted, 4.into()),
+            (wrapped, 5.into()),
+        ],
+        /* shared_objects */ vec![],
+        TransactionDigest::random(),
+        /* created */ vec![(object_ref(created, 10), Owner::AddressOwner(recipient))],
+        /* mutated */
+        vec![
+            gas_object,
+            (object_ref(mutated, 10), Owner::AddressOwner(sender)),
+            (object_ref(transferred, 10), Owner::AddressOwner(recipient)),
+        ],
+        /* unwrapped */ vec![(object_ref(unwrapped, 10), Owner::AddressOwner(sender))],
+        /* deleted */
+        vec![tombstone(deleted, 10, ObjectDigest::OBJECT_DIGEST_DELETED)],
+        /* unwrapped_then_deleted */
+        vec![tombstone(
+            unwrapped_then_deleted,
+            10,
+            ObjectDigest::OBJECT_DIGEST_DELETED,
+        )],
+        /* wrapped */ vec![tombstone(wrapped, 10, ObjectDigest::OBJECT_DIGEST_WRAPPED)],
+        gas_object,
+        /* events_digest */ None,
+        /* dependencies */ vec![],
+    ));
+
+    let inputs = [
+        input(gas, 9, sender),
+        input(mutated, 2, sender),
+        input(transferred, 3, sender),
+        input(deleted, 4, sender),
+        input(wrapped, 5, sender),
+    ];
+    let kinds: Vec<_> = effects
+        .object_changes(&inputs)
+        .into_iter()
+        .map(|change| (change.object_ref.0, change.kind))
+        .collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            (
+                gas,
+                ObjectChangeKind::Mutated {
+                    owner: Owner::AddressOwner(sender),
+                    previous_version: 9.into(),
+                }
+            ),
+            (
+                mutated,
+                ObjectChangeKind::Mutated {
+                    owner: Owner::AddressOwner(sender),
+                    previous_version: 2.into(),
+                }
+            ),
+            (
+                transferred,
+                ObjectChangeKind::Transferred {
+                    old_owner: Owner::AddressOwner(sender),
+                    new_owner: Owner::AddressOwner(recipient),
+                    previous_version: 3.into(),
+                }
+            ),
+            (
+                created,
+                ObjectChangeKind::Created {
+                    owner: Owner::AddressOwner(recipient),
+                }
+            ),
+            (
+                unwrapped,
+                ObjectChangeKind::Unwrapped {
+                    owner: Owner::AddressOwner(sender),
+                }
+            ),
+            (deleted, ObjectChangeKind::Deleted),
+            (wrapped, ObjectChangeKind::Wrapped),
+            (
+                unwrapped_then_deleted,
+                ObjectChangeKind::UnwrappedThenDeleted
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_transfer_needs_input_object() {
+    let (sender, recipient) = (
+        SuiAddress::random_for_testing_only(),
+        SuiAddress::random_for_testing_only(),
+    );
+    let (gas, object) = (ObjectID::random(), ObjectID::random());
+    let gas_object = (object_ref(gas, 4), Owner::AddressOwner(sender));
+
+    let effects = TransactionEffects::V1(TransactionEffectsV1::new(
+        ExecutionStatus::Success,
+        /* executed_epoch */ 0,
+        GasCostSummary::default(),
+        /* modified_at_versions */ vec![(gas, 3.into()), (object, 1.into())],
+        /* shared_objects */ vec![],
+        TransactionDigest::random(),
+        /* created */ vec![],
+        /* mutated */
+        vec![
+            gas_object,
+            (object_ref(object, 4), Owner::ObjectOwner(recipient)),
+        ],
+        /* unwrapped */ vec![],
+        /* deleted */ vec![],
+        /* unwrapped_then_deleted */ vec![],
+        /* wrapped */ vec![],
+        gas_object,
+        /* events_digest */ None,
+        /* dependencies */ vec![],
+    ));
+
+    // Without its input object, an object's change of owner can't be detected.
+    assert_eq!(
+        effects.object_changes([])[1].kind,
+        ObjectChangeKind::Mutated {
+            owner: Owner::ObjectOwner(recipient),
+            previous_version: 1.into(),
+        }
+    );
+
+    // Becoming the child of another object counts as a transfer too.
+    assert_eq!(
+        effects.object_changes(&[input(object, 1, sender)])[1].kind,
+        ObjectChangeKind::Transferred {
+            old_owner: Owner::AddressOwner(sender),
+            new_owner: Owner::ObjectOwner(recipient),
+            previous_version: 1.into(),
+        }
+    );
+}
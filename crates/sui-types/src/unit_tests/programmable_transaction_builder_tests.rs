@@ -0,0 +1,90 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{random_object_ref, SequenceNumber};
+
+#[test]
+fn test_nested_results() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let amounts = vec![builder.pure(1u64).unwrap(), builder.pure(2u64).unwrap()];
+    let coins = builder.command(Command::SplitCoins(Argument::GasCoin, amounts));
+
+    assert_eq!(
+        builder.nested_results(coins, 2).unwrap(),
+        vec![Argument::NestedResult(0, 0), Argument::NestedResult(0, 1)]
+    );
+    assert_eq!(
+        builder.nested_result(coins, 1).unwrap(),
+        Argument::NestedResult(0, 1)
+    );
+
+    // Only results of commands already in the transaction can be indexed.
+    assert!(builder.nested_result(Argument::Result(1), 0).is_err());
+    assert!(builder.nested_result(Argument::GasCoin, 0).is_err());
+    assert!(builder.nested_result(Argument::Input(0), 0).is_err());
+}
+
+#[test]
+fn test_pure_option() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let some = builder.pure_option(Some(42u64)).unwrap();
+    let none = builder.pure_option(None::<u64>).unwrap();
+
+    assert_eq!((some, none), (Argument::Input(0), Argument::Input(1)));
+
+    // Move options are vectors of zero or one elements.
+    let tx = builder.finish();
+    assert_eq!(
+        tx.inputs,
+        vec![
+            CallArg::Pure(bcs::to_bytes(&vec![42u64]).unwrap()),
+            CallArg::Pure(bcs::to_bytes(&Vec::<u64>::new()).unwrap()),
+        ]
+    );
+}
+
+#[test]
+fn test_receiving() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let object_ref = random_object_ref();
+    let arg = builder.receiving(object_ref).unwrap();
+    // The same object can not also be used as an owned object.
+    assert!(builder
+        .obj(ObjectArg::ImmOrOwnedObject(object_ref))
+        .is_err());
+
+    let tx = builder.finish();
+    assert_eq!(arg, Argument::Input(0));
+    assert_eq!(
+        tx.inputs,
+        vec![CallArg::Object(ObjectArg::Receiving(object_ref))]
+    );
+}
+
+#[test]
+fn test_make_move_vec() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let shared = builder
+        .obj(ObjectArg::SharedObject {
+            id: ObjectID::random(),
+            initial_shared_version: SequenceNumber::from_u64(1),
+            mutable: false,
+        })
+        .unwrap();
+    let coin = builder.command(Command::SplitCoins(Argument::GasCoin, vec![]));
+    let vec = builder.make_move_vec(None, vec![shared, coin]);
+    let empty = builder.make_move_vec(Some(TypeTag::U64), vec![]);
+
+    assert_eq!(vec, Argument::Result(1));
+    assert_eq!(empty, Argument::Result(2));
+    let tx = builder.finish();
+    assert_eq!(
+        tx.commands[1],
+        Command::MakeMoveVec(None, vec![Argument::Input(0), Argument::Result(0)])
+    );
+    assert_eq!(
+        tx.commands[2],
+        Command::MakeMoveVec(Some(TypeTag::U64), vec![])
+    );
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn test_checked_and_saturating_arithmetic() {
+    let one = Mist::new(1);
+    assert_eq!(Mist::new(2).checked_add(one), Some(Mist::new(3)));
+    assert_eq!(Mist::MAX.checked_add(one), None);
+    assert_eq!(Mist::ZERO.checked_sub(one), None);
+
+    assert_eq!(Mist::MAX.saturating_add(one), Mist::MAX);
+    assert_eq!(Mist::ZERO.saturating_sub(one), Mist::ZERO);
+
+    let units = GasQuantity::new(5);
+    assert_eq!(
+        units.checked_sub(GasQuantity::new(2)),
+        Some(GasQuantity::new(3))
+    );
+    assert_eq!(GasQuantity::MAX.checked_add(GasQuantity::new(1)), None);
+}
+
+#[test]
+fn test_price_conversion() {
+    let units = GasQuantity::new(1_000);
+    assert_eq!(units.checked_mul_price(750), Some(Mist::new(750_000)));
+    assert_eq!(
+        GasQuantity::ZERO.checked_mul_price(u64::MAX),
+        Some(Mist::ZERO)
+    );
+
+    assert_eq!(GasQuantity::MAX.checked_mul_price(2), None);
+    assert_eq!(GasQuantity::MAX.saturating_mul_price(2), Mist::MAX);
+}
+
+#[test]
+fn test_portion_rounding() {
+    // 99% of 150 is 148.5, which rounds up.
+    assert_eq!(Mist::new(150).portion(9_900), Mist::new(149));
+    // 99% of 140 is 138.6, and 99% of 110 is 108.9.
+    assert_eq!(Mist::new(140).portion(9_900), Mist::new(139));
+    assert_eq!(Mist::new(110).portion(9_900), Mist::new(109));
+    // 99% of 1_020 is 1_009.8, and 1% of 1_020 is 10.2.
+    assert_eq!(Mist::new(1_020).portion(9_900), Mist::new(1_010));
+    assert_eq!(Mist::new(1_020).portion(100), Mist::new(10));
+
+    assert_eq!(Mist::MAX.portion(10_000), Mist::MAX);
+    assert_eq!(Mist::MAX.portion(0), Mist::ZERO);
+    assert_eq!(Mist::MAX.portion(20_000), Mist::MAX);
+}
+
+#[test]
+fn test_signed_difference() {
+    assert_eq!(Mist::new(10).checked_signed_sub(Mist::new(3)), Some(7));
+    assert_eq!(Mist::new(3).checked_signed_sub(Mist::new(10)), Some(-7));
+    assert_eq!(
+        Mist::new(0).checked_signed_sub(Mist::new(i64::MAX as u64 + 1)),
+        Some(i64::MIN)
+    );
+    assert_eq!(Mist::MAX.checked_signed_sub(Mist::ZERO), None);
+}
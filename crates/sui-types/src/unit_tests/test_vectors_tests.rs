@@ -0,0 +1,77 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use fastcrypto::encoding::{Base64, Encoding, Hex};
+use fastcrypto::traits::ToFromBytes;
+use shared_crypto::intent::{Intent, IntentMessage};
+
+use super::*;
+use crate::signature::{AuthenticatorTrait, VerifyParams};
+use crate::transaction::TransactionDataAPI;
+
+fn decode<T: serde::de::DeserializeOwned>(vector: &TestVector) -> T {
+    bcs::from_bytes(&Hex::decode(&vector.bcs).unwrap()).unwrap()
+}
+
+#[test]
+fn test_vectors_are_deterministic() {
+    let vectors = generate();
+    assert_eq!(vectors, generate());
+
+    let json = serde_json::to_string(&vectors).unwrap();
+    assert_eq!(serde_json::from_str::<TestVectors>(&json).unwrap(), vectors);
+}
+
+#[test]
+fn test_transaction_vectors_verify() {
+    let vectors = generate();
+    assert_eq!(vectors.transaction_data.len(), 3);
+    assert_eq!(vectors.multisig.len(), 3);
+
+    for vector in vectors.transaction_data.iter().chain(&vectors.multisig) {
+        let data: TransactionData = decode(vector);
+        assert_eq!(vector.type_name, "TransactionData");
+        assert_eq!(
+            vector.digest,
+            Some(transaction_data_digest(&data).to_string())
+        );
+
+        let message = IntentMessage::new(Intent::sui_transaction(), data.clone());
+        let [signature] = vector.signatures.as_slice() else {
+            panic!("Expected a single signature for {}", vector.name);
+        };
+        assert_eq!(signature.address, data.sender());
+
+        let generic =
+            GenericSignature::from_bytes(&Base64::decode(&signature.signature).unwrap()).unwrap();
+        generic
+            .verify_authenticator(&message, data.sender(), None, &VerifyParams::default())
+            .unwrap_or_else(|e| panic!("Invalid signature for {}: {e}", vector.name));
+    }
+}
+
+#[test]
+fn test_digests_match_contents() {
+    let vectors = generate();
+
+    let effects: TransactionEffects = decode(&vectors.effects[0]);
+    assert_eq!(
+        vectors.effects[0].digest,
+        Some(effects.digest().to_string())
+    );
+    assert_eq!(
+        effects.transaction_digest().to_string(),
+        vectors.transaction_data[0].digest.clone().unwrap()
+    );
+
+    let contents: CheckpointContents = decode(&vectors.checkpoints[1]);
+    let summary: CheckpointSummary = decode(&vectors.checkpoints[2]);
+    assert_eq!(summary.content_digest, *contents.digest());
+    assert_eq!(
+        vectors.checkpoints[2].digest,
+        Some(summary.digest().to_string())
+    );
+
+    let zklogin: TransactionData = decode(&vectors.zklogin[0]);
+    assert_eq!(zklogin.sender(), get_zklogin_user_address());
+}
@@ -0,0 +1,109 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::base_types::{random_object_ref, SuiAddress};
+use crate::programmable_transaction_builder::ProgrammableTransactionBuilder;
+
+fn transaction(pt: ProgrammableTransaction) -> TransactionData {
+    TransactionData::new_programmable(
+        SuiAddress::random_for_testing_only(),
+        vec![random_object_ref()],
+        pt,
+        1_000_000,
+        1_000,
+    )
+}
+
+fn usage(analysis: &TransactionAnalysis, limit: Limit) -> LimitUsage {
+    *analysis
+        .limits
+        .iter()
+        .find(|usage| usage.limit == limit)
+        .unwrap()
+}
+
+#[test]
+fn test_well_formed_transaction() {
+    let mut builder = ProgrammableTransactionBuilder::new();
+    builder.transfer_sui(SuiAddress::random_for_testing_only(), Some(100));
+    let tx_data = transaction(builder.finish());
+
+    let config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let analysis = TransactionAnalysis::new(&tx_data, &config);
+
+    assert!(analysis.is_within_limits());
+    assert!(analysis.warnings.is_empty(), "{analysis}");
+    assert_eq!(analysis.size_bytes, bcs::serialized_size(&tx_data).unwrap());
+    assert_eq!(usage(&analysis, Limit::Commands).used, 2);
+    assert_eq!(
+        usage(&analysis, Limit::Commands).max,
+        Some(config.max_programmable_tx_commands() as u64 - 1)
+    );
+    // The gas coin is the only input object.
+    assert_eq!(usage(&analysis, Limit::InputObjects).used, 1);
+}
+
+#[test]
+fn test_exceeded_limits() {
+    let mut config = ProtocolConfig::get_for_max_version_UNSAFE();
+    config.set_max_programmable_tx_commands_for_testing(3);
+    config.set_max_input_objects_for_testing(1);
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    for _ in 0..3 {
+        builder.transfer_sui(SuiAddress::random_for_testing_only(), None);
+    }
+    builder
+        .transfer_object(SuiAddress::random_for_testing_only(), random_object_ref())
+        .unwrap();
+    let analysis = TransactionAnalysis::new(&transaction(builder.finish()), &config);
+
+    let exceeded: Vec<_> = analysis
+        .exceeded_limits()
+        .map(|usage| usage.limit)
+        .collect();
+    assert_eq!(exceeded, vec![Limit::InputObjects, Limit::Commands]);
+    assert!(!analysis.is_within_limits());
+}
+
+#[test]
+fn test_warnings() {
+    let mut config = ProtocolConfig::get_for_max_version_UNSAFE();
+    config.set_max_pure_argument_size_for_testing(16);
+
+    let mut builder = ProgrammableTransactionBuilder::new();
+    let unused = builder.pure(vec![0u8; 32]).unwrap();
+    let amount = builder.pure(100u64).unwrap();
+    // Splits off a coin that is never transferred.
+    builder.command(Command::SplitCoins(Argument::GasCoin, vec![amount]));
+    builder.command(Command::MergeCoins(
+        Argument::GasCoin,
+        vec![Argument::Result(5), Argument::Input(7)],
+    ));
+    let analysis = TransactionAnalysis::new(&transaction(builder.finish()), &config);
+
+    let Argument::Input(unused) = unused else {
+        panic!("Pure values are inputs");
+    };
+    assert_eq!(
+        analysis.warnings,
+        vec![
+            TransactionWarning::OversizedPureInput {
+                input: unused,
+                size: 33,
+                max: 15,
+            },
+            TransactionWarning::InvalidArgument {
+                command: 1,
+                argument: Argument::Result(5),
+            },
+            TransactionWarning::InvalidArgument {
+                command: 1,
+                argument: Argument::Input(7),
+            },
+            TransactionWarning::UnusedInput { input: unused },
+            TransactionWarning::DanglingResult { command: 0 },
+        ]
+    );
+}
@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::language_storage::{StructTag, TypeTag};
+
+use crate::gas_coin::GAS;
+use crate::type_tag::{
+    parse_struct_tag, parse_type_tag, struct_to_canonical_string, struct_to_short_string,
+    to_canonical_string, to_short_string,
+};
+use crate::{DEEPBOOK_ADDRESS, MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
+
+const SUI: &str = "0x2::sui::SUI";
+const SUI_LONG: &str =
+    "0x0000000000000000000000000000000000000000000000000000000000000002::sui::SUI";
+
+fn coin(param: TypeTag) -> StructTag {
+    StructTag {
+        address: SUI_FRAMEWORK_ADDRESS,
+        module: "coin".parse().unwrap(),
+        name: "Coin".parse().unwrap(),
+        type_params: vec![param],
+    }
+}
+
+#[test]
+fn test_parse_primitives() {
+    for (s, tag) in [
+        ("bool", TypeTag::Bool),
+        ("u8", TypeTag::U8),
+        ("u16", TypeTag::U16),
+        ("u32", TypeTag::U32),
+        ("u64", TypeTag::U64),
+        ("u128", TypeTag::U128),
+        ("u256", TypeTag::U256),
+        ("address", TypeTag::Address),
+        ("signer", TypeTag::Signer),
+        ("vector<u8>", TypeTag::Vector(Box::new(TypeTag::U8))),
+    ] {
+        assert_eq!(parse_type_tag(s).unwrap(), tag, "parsing {s}");
+    }
+}
+
+#[test]
+fn test_parse_address_forms() {
+    let sui = GAS::type_();
+    assert_eq!(parse_struct_tag(SUI).unwrap(), sui);
+    assert_eq!(parse_struct_tag(SUI_LONG).unwrap(), sui);
+    assert_eq!(parse_struct_tag("0x02::sui::SUI").unwrap(), sui);
+    assert_eq!(parse_struct_tag("sui::sui::SUI").unwrap(), sui);
+
+    assert_eq!(
+        parse_struct_tag("std::ascii::String").unwrap().address,
+        MOVE_STDLIB_ADDRESS,
+    );
+    assert_eq!(
+        parse_struct_tag("0xdee9::clob::Pool").unwrap().address,
+        DEEPBOOK_ADDRESS,
+    );
+}
+
+#[test]
+fn test_parse_nested_generics() {
+    let expected = TypeTag::Vector(Box::new(TypeTag::Struct(Box::new(coin(TypeTag::Struct(
+        Box::new(coin(GAS::type_tag())),
+    ))))));
+
+    for s in [
+        "vector<0x2::coin::Coin<0x2::coin::Coin<0x2::sui::SUI>>>",
+        " vector < 0x2 :: coin :: Coin < 0x2::coin::Coin<0x2::sui::SUI> > > ",
+    ] {
+        assert_eq!(parse_type_tag(s).unwrap(), expected, "parsing {s}");
+    }
+
+    let pair = parse_struct_tag("0x42::pair::Pair<u64, vector<0x2::sui::SUI>>").unwrap();
+    assert_eq!(
+        pair.type_params,
+        vec![TypeTag::U64, TypeTag::Vector(Box::new(GAS::type_tag()))],
+    );
+}
+
+#[test]
+fn test_parse_error_positions() {
+    for (s, position) in [
+        ("", 0),
+        ("u64 u8", 4),
+        ("vector<u8", 9),
+        ("vector<>", 7),
+        ("0x2::sui", 8),
+        ("0x2:sui::SUI", 3),
+        ("0x2g::sui::SUI", 3),
+        ("foo::sui::SUI", 0),
+        ("0x2::sui::SUI<>", 14),
+        ("0x2::sui::SUI<u8,>", 17),
+        ("0x2::coin::Coin<0x2::sui::SUI>>", 30),
+        ("0x2::0sui::SUI", 5),
+    ] {
+        let err = parse_type_tag(s).unwrap_err();
+        assert_eq!(err.position, position, "parsing {s:?}: {err}");
+    }
+
+    let err = parse_struct_tag("u64").unwrap_err();
+    assert_eq!(err.position, 0);
+
+    let too_long = format!("0x{}::sui::SUI", "0".repeat(65));
+    let err = parse_struct_tag(&too_long).unwrap_err();
+    assert_eq!(err.position, 0);
+}
+
+#[test]
+fn test_parse_rejects_deep_nesting() {
+    let depth = 1000;
+    let s = format!("{}u8{}", "vector<".repeat(depth), ">".repeat(depth));
+    assert!(parse_type_tag(&s).is_err());
+}
+
+#[test]
+fn test_format_round_trip() {
+    let tag = TypeTag::Struct(Box::new(coin(GAS::type_tag())));
+
+    let short = to_short_string(&tag);
+    assert_eq!(short, format!("0x2::coin::Coin<{SUI}>"));
+    assert_eq!(parse_type_tag(&short).unwrap(), tag);
+
+    let long = to_canonical_string(&tag);
+    assert_eq!(long, format!("{}::coin::Coin<{SUI_LONG}>", &SUI_LONG[..66]),);
+    assert_eq!(parse_type_tag(&long).unwrap(), tag);
+
+    let sui = GAS::type_();
+    assert_eq!(struct_to_short_string(&sui), SUI);
+    assert_eq!(struct_to_canonical_string(&sui), SUI_LONG);
+}
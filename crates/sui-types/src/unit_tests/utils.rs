@@ -8,14 +8,15 @@ use crate::transaction::{SenderSignedData, TEST_ONLY_GAS_UNIT_FOR_TRANSFER};
 use crate::SuiAddress;
 use crate::{
     base_types::{dbg_addr, ExecutionDigests, ObjectID},
-    committee::Committee,
+    committee::{Committee, ProtocolVersion},
     crypto::{
         get_key_pair, get_key_pair_from_rng, AccountKeyPair, AuthorityKeyPair,
         AuthorityPublicKeyBytes, DefaultHash, Signature, SignatureScheme,
     },
     gas::GasCostSummary,
     messages_checkpoint::{
-        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, SignedCheckpointSummary,
+        CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, EndOfEpochData,
+        SignedCheckpointSummary,
     },
     object::Object,
     signature::GenericSignature,
@@ -153,6 +154,66 @@ pub fn mock_certified_checkpoint<'a>(
     CertifiedCheckpointSummary::new(summary, sign_infos, &committee).expect("Cert is OK")
 }
 
+/// The committee of an epoch, with the keys of its members.
+pub struct TestEpoch {
+    pub keys: Vec<AuthorityKeyPair>,
+    pub committee: Committee,
+}
+
+/// Committees of `count` consecutive epochs, starting at epoch 0.
+pub fn make_test_epochs(count: u64) -> Vec<TestEpoch> {
+    let mut rng = StdRng::from_seed([0; 32]);
+    (0..count)
+        .map(|epoch| {
+            let (keys, committee) = make_committee_key(&mut rng);
+            TestEpoch {
+                keys,
+                committee: Committee::new(epoch, committee.voting_rights.into_iter().collect()),
+            }
+        })
+        .collect()
+}
+
+/// An empty checkpoint of `epoch`, certified by its committee. If `next_epoch` is given, it is the
+/// last checkpoint of `epoch`, handing off to the committee of `next_epoch`.
+pub fn make_epoch_checkpoint(
+    epoch: &TestEpoch,
+    sequence_number: u64,
+    next_epoch: Option<&TestEpoch>,
+) -> CertifiedCheckpointSummary {
+    let contents = CheckpointContents::new_with_causally_ordered_transactions([]);
+    make_epoch_checkpoint_with_contents(epoch, sequence_number, &contents, next_epoch)
+}
+
+/// Like `make_epoch_checkpoint`, for a checkpoint with the given `contents`.
+pub fn make_epoch_checkpoint_with_contents(
+    epoch: &TestEpoch,
+    sequence_number: u64,
+    contents: &CheckpointContents,
+    next_epoch: Option<&TestEpoch>,
+) -> CertifiedCheckpointSummary {
+    let end_of_epoch_data = next_epoch.map(|next| EndOfEpochData {
+        next_epoch_committee: next.committee.voting_rights.clone(),
+        next_epoch_protocol_version: ProtocolVersion::MIN,
+        epoch_commitments: vec![],
+    });
+    let summary = CheckpointSummary::new(
+        epoch.committee.epoch,
+        sequence_number,
+        /* network_total_transactions */ 0,
+        contents,
+        /* previous_digest */ None,
+        GasCostSummary::default(),
+        end_of_epoch_data,
+        /* timestamp_ms */ 0,
+    );
+    CertifiedCheckpointSummary::new_from_keypairs_for_testing(
+        summary,
+        &epoch.keys,
+        &epoch.committee,
+    )
+}
+
 mod zk_login {
     use fastcrypto_zkp::bn254::{utils::big_int_str_to_bytes, zk_login::ZkLoginInputs};
 
@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::utils::make_zklogin_tx;
+
+// Address seed of the zkLogin inputs used by `make_zklogin_tx`.
+const ADDRESS_SEED: &str =
+    "16657007263003735230240998439420301694514420923267872433517882233836276100450";
+
+fn twitch_account() -> ZkLoginAccount {
+    ZkLoginAccount::new(
+        OIDCProvider::Twitch,
+        "rs1bh065i9ya4ydvifixl4kss0uhpt".to_string(),
+        ADDRESS_SEED.to_string(),
+    )
+}
+
+#[test]
+fn test_zk_login_address_matches_authenticator() {
+    let (user_address, _tx, authenticator) = make_zklogin_tx();
+    assert_eq!(twitch_account().address().unwrap(), user_address);
+    assert_eq!(SuiAddress::try_from(&authenticator).unwrap(), user_address);
+
+    assert!(matches!(
+        zk_login_address(&twitch_account().iss(), "not a number"),
+        Err(SuiError::InvalidAddress)
+    ));
+}
+
+#[test]
+fn test_provider_registry() {
+    let (user_address, _tx, _authenticator) = make_zklogin_tx();
+    let registry = ZkLoginProviderRegistry::new([OIDCProvider::Twitch]);
+    let account = twitch_account();
+
+    assert_eq!(
+        registry.provider(&account.iss()).unwrap(),
+        &OIDCProvider::Twitch
+    );
+    assert_eq!(registry.derive_address(&account).unwrap(), user_address);
+
+    let google_account = ZkLoginAccount {
+        provider: OIDCProvider::Google,
+        ..account.clone()
+    };
+    assert!(registry.provider(&google_account.iss()).is_err());
+    assert!(registry.derive_address(&google_account).is_err());
+
+    // The address does not depend on the registry it is derived with.
+    let registry = ZkLoginProviderRegistry::new([OIDCProvider::Google, OIDCProvider::Twitch]);
+    assert_eq!(registry.providers().count(), 2);
+    assert_eq!(registry.derive_address(&account).unwrap(), user_address);
+    assert!(ZkLoginProviderRegistry::default().is_empty());
+}
@@ -9,7 +9,6 @@ use crate::{
     signature::{AuthenticatorTrait, VerifyParams},
 };
 use fastcrypto::{error::FastCryptoError, traits::ToFromBytes};
-use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
 use fastcrypto_zkp::bn254::{zk_login::ZkLoginInputs, zk_login_api::verify_zk_login};
 use once_cell::sync::OnceCell;
 use schemars::JsonSchema;
@@ -107,17 +106,9 @@ impl AuthenticatorTrait for ZkLoginAuthenticator {
             return Err(SuiError::InvalidAddress);
         }
 
-        if !aux_verify_data.supported_providers.contains(
-            &OIDCProvider::from_iss(self.inputs.get_iss()).map_err(|_| {
-                SuiError::InvalidSignature {
-                    error: "Unknown provider".to_string(),
-                }
-            })?,
-        ) {
-            return Err(SuiError::InvalidSignature {
-                error: format!("OIDC provider not supported: {}", self.inputs.get_iss()),
-            });
-        }
+        aux_verify_data
+            .supported_providers
+            .provider(self.inputs.get_iss())?;
 
         // Verify the ephemeral signature over the intent message of the transaction data.
         if self
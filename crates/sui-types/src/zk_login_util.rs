@@ -1,5 +1,119 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use fastcrypto::hash::HashFunction;
+use fastcrypto_zkp::bn254::utils::big_int_str_to_bytes;
+use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::base_types::SuiAddress;
+use crate::crypto::{DefaultHash, SignatureScheme};
+use crate::error::{SuiError, SuiResult};
+
+#[cfg(test)]
+#[path = "unit_tests/zk_login_util_tests.rs"]
+mod zk_login_util_tests;
+
 // Used in tests or anywhere that fetching up to date JWKs is not possible. This is an example response from https://id.twitch.tv/oauth2/keys
 pub const DEFAULT_JWK_BYTES: &[u8] = r#"{"keys":[{"alg":"RS256","e":"AQAB","kid":"1","kty":"RSA","n":"6lq9MQ-q6hcxr7kOUp-tHlHtdcDsVLwVIw13iXUCvuDOeCi0VSuxCCUY6UmMjy53dX00ih2E4Y4UvlrmmurK0eG26b-HMNNAvCGsVXHU3RcRhVoHDaOwHwU72j7bpHn9XbP3Q3jebX6KIfNbei2MiR0Wyb8RZHE-aZhRYO8_-k9G2GycTpvc-2GBsP8VHLUKKfAs2B6sW3q3ymU6M0L-cFXkZ9fHkn9ejs-sqZPhMJxtBPBxoUIUQFTgv4VXTSv914f_YkNw-EjuwbgwXMvpyr06EyfImxHoxsZkFYB-qBYHtaMxTnFsZBr6fn8Ha2JqT1hoP7Z5r5wxDu3GQhKkHw","use":"sig"}]}"#.as_bytes();
+
+/// Sui address of a zkLogin account, defined as the Blake2b hash of
+/// [zklogin_flag || iss_bytes_length || iss_bytes || address_seed in bytes].
+pub fn zk_login_address(iss: &str, address_seed: &str) -> SuiResult<SuiAddress> {
+    let address_seed = big_int_str_to_bytes(address_seed).map_err(|_| SuiError::InvalidAddress)?;
+    let mut hasher = DefaultHash::default();
+    hasher.update([SignatureScheme::ZkLoginAuthenticator.flag()]);
+    let iss_bytes = iss.as_bytes();
+    hasher.update([iss_bytes.len() as u8]);
+    hasher.update(iss_bytes);
+    hasher.update(address_seed);
+    Ok(SuiAddress::from_bytes(hasher.finalize().digest).expect("Digest is a valid address"))
+}
+
+/// A zkLogin account: a user of an OIDC provider, as seen by the app the JWTs are issued to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZkLoginAccount {
+    pub provider: OIDCProvider,
+    /// Client ID of the app, the `aud` claim of the JWTs the provider issues to it.
+    pub aud: String,
+    /// Commitment to the user's `sub` claim, `aud` and the user's salt.
+    pub address_seed: String,
+}
+
+impl ZkLoginAccount {
+    pub fn new(provider: OIDCProvider, aud: String, address_seed: String) -> Self {
+        Self {
+            provider,
+            aud,
+            address_seed,
+        }
+    }
+
+    /// Issuer of the JWTs of this account, the `iss` claim.
+    pub fn iss(&self) -> String {
+        self.provider.get_config().iss
+    }
+
+    pub fn address(&self) -> SuiResult<SuiAddress> {
+        zk_login_address(&self.iss(), &self.address_seed)
+    }
+}
+
+/// OIDC providers whose users may sign with zkLogin, keyed by the issuer (`iss`) that identifies
+/// them in JWTs and zkLogin inputs.
+#[derive(Clone, Debug, Default)]
+pub struct ZkLoginProviderRegistry {
+    providers: BTreeMap<String, OIDCProvider>,
+}
+
+impl ZkLoginProviderRegistry {
+    pub fn new(providers: impl IntoIterator<Item = OIDCProvider>) -> Self {
+        Self {
+            providers: providers
+                .into_iter()
+                .map(|provider| (provider.get_config().iss, provider))
+                .collect(),
+        }
+    }
+
+    /// Registry of the providers supported at `protocol_config`.
+    pub fn from_protocol_config(protocol_config: &ProtocolConfig) -> SuiResult<Self> {
+        let providers = protocol_config
+            .zklogin_supported_providers()
+            .iter()
+            .map(|name| {
+                OIDCProvider::from_str(name).map_err(|_| SuiError::UnsupportedFeatureError {
+                    error: format!("Unknown OIDC provider: {name}"),
+                })
+            })
+            .collect::<SuiResult<Vec<_>>>()?;
+        Ok(Self::new(providers))
+    }
+
+    pub fn providers(&self) -> impl Iterator<Item = &OIDCProvider> {
+        self.providers.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// The supported provider that issues JWTs with `iss`.
+    pub fn provider(&self, iss: &str) -> SuiResult<&OIDCProvider> {
+        self.providers
+            .get(iss)
+            .ok_or_else(|| SuiError::InvalidSignature {
+                error: format!("OIDC provider not supported: {iss}"),
+            })
+    }
+
+    /// Derives the address of `account`, if its provider is supported.
+    pub fn derive_address(&self, account: &ZkLoginAccount) -> SuiResult<SuiAddress> {
+        let iss = account.iss();
+        self.provider(&iss)?;
+        zk_login_address(&iss, &account.address_seed)
+    }
+}
@@ -10,6 +10,7 @@ use std::{
 
 use anyhow::{anyhow, ensure};
 use bip32::DerivationPath;
+use chrono::{NaiveDate, TimeZone, Utc};
 use clap::*;
 use colored::Colorize;
 use fastcrypto::{
@@ -23,7 +24,7 @@ use prometheus::Registry;
 use serde::Serialize;
 use serde_json::{json, Value};
 use sui_move::build::resolve_lock_file_path;
-use sui_protocol_config::ProtocolConfig;
+use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_source_validation::{BytecodeSourceVerifier, SourceMode};
 
 use shared_crypto::intent::Intent;
@@ -54,6 +55,7 @@ use sui_types::{
     parse_sui_type_tag,
     signature::GenericSignature,
     transaction::{SenderSignedData, Transaction, TransactionData, TransactionDataAPI},
+    transaction_analyzer::TransactionAnalysis,
 };
 
 use tabled::{
@@ -65,6 +67,8 @@ use tabled::{
 };
 use tracing::info;
 
+use crate::transaction_history::{FunctionFilter, HistoryEntry, HistoryFilter, TransactionHistory};
+
 macro_rules! serialize_or_execute {
     ($tx_data:expr, $serialize_unsigned:expr, $serialize_signed:expr, $context:expr, $result_variant:ident) => {{
         assert!(
@@ -72,6 +76,7 @@ macro_rules! serialize_or_execute {
             "Cannot specify both --serialize-unsigned-transaction and --serialize-signed-transaction"
         );
         if $serialize_unsigned {
+            preview_transaction($context, &$tx_data).await?;
             SuiClientCommandResult::SerializedUnsignedTransaction($tx_data)
         } else {
             let signature = $context.config.keystore.sign_secure(
@@ -201,6 +206,34 @@ pub enum SuiClientCommands {
         address: Option<SuiAddress>,
     },
 
+    /// Show the transactions sent by or affecting an address, newest first. Transactions are
+    /// cached locally, so only new transactions are fetched from the network.
+    #[clap(name = "history")]
+    History {
+        /// Address to show the history of. Defaults to the active address.
+        #[clap(name = "address")]
+        address: Option<SuiAddress>,
+        /// Only show transactions calling a Move function in this package, module
+        /// (`package::module`), or this function (`package::module::function`).
+        #[clap(long)]
+        function: Option<FunctionFilter>,
+        /// Only show transactions of this kind, e.g. `ProgrammableTransaction`.
+        #[clap(long)]
+        kind: Option<String>,
+        /// Only show transactions executed on or after this day (UTC), as `YYYY-MM-DD`.
+        #[clap(long)]
+        from: Option<NaiveDate>,
+        /// Only show transactions executed on or before this day (UTC), as `YYYY-MM-DD`.
+        #[clap(long)]
+        to: Option<NaiveDate>,
+        /// Maximum number of transactions to show.
+        #[clap(long, default_value = "50")]
+        limit: usize,
+        /// Only show cached transactions, without connecting to the network.
+        #[clap(long)]
+        offline: bool,
+    },
+
     /// Merge two coin objects into one coin
     MergeCoin {
         /// Coin to merge into, in 20 bytes Hex string
@@ -1082,6 +1115,33 @@ impl SuiClientCommands {
                     .collect();
                 SuiClientCommandResult::Gas(coins)
             }
+            SuiClientCommands::History {
+                address,
+                function,
+                kind,
+                from,
+                to,
+                limit,
+                offline,
+            } => {
+                let address = address.unwrap_or(context.active_address()?);
+                let filter = HistoryFilter {
+                    function,
+                    kind,
+                    from,
+                    to,
+                };
+                let env = context.config.get_active_env()?.alias.clone();
+                let path = TransactionHistory::cache_path(context.config.path(), &env, address);
+
+                let mut history = TransactionHistory::load(&path)?;
+                if !offline {
+                    let client = context.get_client().await?;
+                    history.sync(&client, address, &filter, limit).await?;
+                    history.save(&path)?;
+                }
+                SuiClientCommandResult::History(history.entries(&filter, limit))
+            }
             SuiClientCommands::ChainIdentifier => {
                 let ci = context
                     .get_client()
@@ -1444,6 +1504,39 @@ impl Display for SuiClientCommandResult {
                 }
                 write!(f, "{}", table)?;
             }
+            SuiClientCommandResult::History(entries) => {
+                if entries.is_empty() {
+                    write!(f, "No transactions found for this address")?;
+                    return Ok(());
+                }
+
+                let mut builder = TableBuilder::default();
+                builder.set_header(vec!["digest", "time", "kind", "functions", "status"]);
+                for entry in entries {
+                    let time = entry
+                        .timestamp_ms
+                        .and_then(|ms| Utc.timestamp_millis_opt(ms as i64).single())
+                        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_default();
+                    let functions = entry
+                        .functions
+                        .iter()
+                        .map(|f| f.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let status = if entry.success { "success" } else { "failure" };
+                    builder.push_record(vec![
+                        entry.digest.to_string(),
+                        time,
+                        entry.kind.clone(),
+                        functions,
+                        status.to_string(),
+                    ]);
+                }
+                let mut table = builder.build();
+                table.with(TableStyle::rounded());
+                write!(f, "{}", table)?;
+            }
             SuiClientCommandResult::NewAddress(new_address) => {
                 let mut builder = TableBuilder::default();
 
@@ -1666,6 +1759,26 @@ async fn construct_move_call_transaction(
         .await
 }
 
+/// Prints the size and shape of `tx_data`, relative to the limits of the network's protocol
+/// version, to stderr, so that it does not interfere with the serialized transaction.
+async fn preview_transaction(
+    context: &WalletContext,
+    tx_data: &TransactionData,
+) -> Result<(), anyhow::Error> {
+    let client = context.get_client().await?;
+    let version = client
+        .read_api()
+        .get_protocol_config(None)
+        .await?
+        .protocol_version;
+    // Fall back to the latest limits known to this binary, if it is older than the network.
+    let protocol_config = ProtocolConfig::get_for_version_if_supported(version, Chain::Unknown)
+        .unwrap_or_else(ProtocolConfig::get_for_max_version_UNSAFE);
+
+    eprint!("{}", TransactionAnalysis::new(tx_data, &protocol_config));
+    Ok(())
+}
+
 fn convert_number_to_string(value: Value) -> Value {
     match value {
         Value::Number(n) => Value::String(n.to_string()),
@@ -1758,7 +1871,7 @@ impl SuiClientCommandResult {
         let line = if pretty {
             format!("{self}")
         } else {
-            format!("{:?}", self)
+            unwrap_err_to_string(|| Ok(serde_json::to_string_pretty(&self.json_output())?))
         };
         // Log line by line
         for line in line.lines() {
@@ -1768,6 +1881,87 @@ impl SuiClientCommandResult {
         }
     }
 
+    /// The versioned, stable JSON representation of this result, printed with `--json`.
+    pub fn json_output(&self) -> ClientJsonOutput<'_> {
+        use ClientCommandOutput as O;
+        use SuiClientCommandResult::*;
+        let (command, result) = match self {
+            ActiveAddress(address) => (
+                "active-address",
+                O::ActiveAddress(ActiveAddressOutput { address: *address }),
+            ),
+            ActiveEnv(env) => (
+                "active-env",
+                O::ActiveEnv(ActiveEnvOutput { env: env.clone() }),
+            ),
+            Addresses(addresses) => ("addresses", O::Addresses(addresses)),
+            ChainIdentifier(chain_identifier) => (
+                "chain-identifier",
+                O::ChainIdentifier(ChainIdentifierOutput {
+                    chain_identifier: chain_identifier.clone(),
+                }),
+            ),
+            DynamicFieldQuery(page) => ("dynamic-field", O::DynamicFields(page)),
+            Envs(envs, active) => ("envs", O::Envs(EnvsOutput { envs, active })),
+            Gas(gas_coins) => (
+                "gas",
+                O::Gas(gas_coins.iter().map(GasCoinOutput::from).collect()),
+            ),
+            History(entries) => ("history", O::History(entries)),
+            NewAddress(new_address) => ("new-address", O::NewAddress(new_address)),
+            NewEnv(env) => ("new-env", O::NewEnv(env)),
+            Object(object) => ("object", O::Object(object)),
+            RawObject(object) => ("object", O::Object(object)),
+            Objects(objects) => ("objects", O::Objects(objects)),
+            SerializedSignedTransaction(sender_signed_data) => (
+                "serialized-signed-transaction",
+                O::SerializedTransaction(SerializedTransactionOutput {
+                    tx_bytes: Base64::encode(bcs::to_bytes(sender_signed_data).unwrap()),
+                }),
+            ),
+            SerializedUnsignedTransaction(tx_data) => (
+                "serialized-unsigned-transaction",
+                O::SerializedTransaction(SerializedTransactionOutput {
+                    tx_bytes: Base64::encode(bcs::to_bytes(tx_data).unwrap()),
+                }),
+            ),
+            Switch(response) => ("switch", O::Switch(response)),
+            SyncClientState => ("sync-client-state", O::Empty),
+            VerifyBytecodeMeter {
+                max_module_ticks,
+                max_function_ticks,
+                used_function_ticks,
+                used_module_ticks,
+            } => (
+                "verify-bytecode-meter",
+                O::VerifyBytecodeMeter(VerifyBytecodeMeterOutput {
+                    max_module_ticks: *max_module_ticks,
+                    max_function_ticks: *max_function_ticks,
+                    used_function_ticks: *used_function_ticks,
+                    used_module_ticks: *used_module_ticks,
+                }),
+            ),
+            VerifySource => ("verify-source", O::Empty),
+            Call(response) => ("call", O::TransactionBlock(response)),
+            ExecuteSignedTx(response) => ("execute-signed-tx", O::TransactionBlock(response)),
+            MergeCoin(response) => ("merge-coin", O::TransactionBlock(response)),
+            Pay(response) => ("pay", O::TransactionBlock(response)),
+            PayAllSui(response) => ("pay-all-sui", O::TransactionBlock(response)),
+            PaySui(response) => ("pay-sui", O::TransactionBlock(response)),
+            Publish(response) => ("publish", O::TransactionBlock(response)),
+            SplitCoin(response) => ("split-coin", O::TransactionBlock(response)),
+            TransactionBlock(response) => ("tx-block", O::TransactionBlock(response)),
+            Transfer(response) => ("transfer", O::TransactionBlock(response)),
+            TransferSui(response) => ("transfer-sui", O::TransactionBlock(response)),
+            Upgrade(response) => ("upgrade", O::TransactionBlock(response)),
+        };
+        ClientJsonOutput {
+            version: CLIENT_JSON_OUTPUT_VERSION,
+            command,
+            result,
+        }
+    }
+
     pub fn tx_block_response(&self) -> Option<&SuiTransactionBlockResponse> {
         use SuiClientCommandResult::*;
         match self {
@@ -1860,6 +2054,92 @@ impl ObjectsOutput {
     }
 }
 
+/// Version of the JSON schema printed by `sui client --json`. It must be bumped whenever a change
+/// to [ClientJsonOutput], or to any of the output types it contains, could break existing
+/// consumers of the output.
+pub const CLIENT_JSON_OUTPUT_VERSION: u32 = 1;
+
+/// JSON representation of the result of a `sui client` command, printed when `--json` is passed.
+/// Unlike the human-readable output, which may be reformatted at any time, its shape only changes
+/// along with [CLIENT_JSON_OUTPUT_VERSION].
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientJsonOutput<'a> {
+    /// Version of the schema of this output, see [CLIENT_JSON_OUTPUT_VERSION].
+    pub version: u32,
+    /// Name of the command that produced this output, e.g. `gas` or `transfer-sui`.
+    pub command: &'static str,
+    /// The command specific output, `null` for commands that only report success.
+    pub result: ClientCommandOutput<'a>,
+}
+
+/// Command specific part of [ClientJsonOutput]. Commands that execute a transaction all output
+/// the full transaction block response, as returned by the JSON-RPC API.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ClientCommandOutput<'a> {
+    ActiveAddress(ActiveAddressOutput),
+    ActiveEnv(ActiveEnvOutput),
+    Addresses(&'a AddressesOutput),
+    ChainIdentifier(ChainIdentifierOutput),
+    DynamicFields(&'a DynamicFieldPage),
+    Envs(EnvsOutput<'a>),
+    Gas(Vec<GasCoinOutput>),
+    History(&'a [HistoryEntry]),
+    NewAddress(&'a NewAddressOutput),
+    NewEnv(&'a SuiEnv),
+    Object(&'a SuiObjectResponse),
+    Objects(&'a [SuiObjectResponse]),
+    SerializedTransaction(SerializedTransactionOutput),
+    Switch(&'a SwitchResponse),
+    TransactionBlock(&'a SuiTransactionBlockResponse),
+    VerifyBytecodeMeter(VerifyBytecodeMeterOutput),
+    Empty,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAddressOutput {
+    pub address: Option<SuiAddress>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveEnvOutput {
+    /// Alias of the active environment.
+    pub env: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainIdentifierOutput {
+    pub chain_identifier: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvsOutput<'a> {
+    pub envs: &'a [SuiEnv],
+    /// Alias of the active environment.
+    pub active: &'a Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SerializedTransactionOutput {
+    /// Base64 encoding of the BCS serialized transaction.
+    pub tx_bytes: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyBytecodeMeterOutput {
+    pub max_module_ticks: u128,
+    pub max_function_ticks: u128,
+    pub used_function_ticks: u128,
+    pub used_module_ticks: u128,
+}
+
 #[derive(Serialize)]
 #[serde(untagged)]
 pub enum SuiClientCommandResult {
@@ -1872,6 +2152,7 @@ pub enum SuiClientCommandResult {
     Envs(Vec<SuiEnv>, Option<String>),
     ExecuteSignedTx(SuiTransactionBlockResponse),
     Gas(Vec<GasCoin>),
+    History(Vec<HistoryEntry>),
     MergeCoin(SuiTransactionBlockResponse),
     NewAddress(NewAddressOutput),
     NewEnv(SuiEnv),
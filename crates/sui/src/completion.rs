@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shell completion scripts for the `sui` binary.
+//!
+//! Completions of commands and flags are generated by clap from the command definitions. With
+//! dynamic completion, the script also completes the values of flags that take an environment
+//! alias or an address, by calling back into `sui completion --candidates <kind>`, which reads
+//! them from the client config.
+
+use std::collections::BTreeSet;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::bail;
+use clap::{Command, ValueEnum};
+use clap_complete::Shell;
+use sui_config::PersistedConfig;
+use sui_keys::keystore::AccountKeystore;
+use sui_sdk::sui_client_config::SuiClientConfig;
+
+#[path = "unit_tests/completion_tests.rs"]
+#[cfg(test)]
+mod completion_tests;
+
+const BIN_NAME: &str = "sui";
+
+/// Wraps the `_sui` completion function generated by clap, and takes over completion for `sui`.
+const BASH_DYNAMIC: &str = r#"
+_sui_dynamic() {
+    local kind
+    case "${COMP_WORDS[COMP_CWORD-1]}" in
+@CASES@
+    esac
+    if [[ -n "${kind}" ]]; then
+        COMPREPLY=($(compgen -W "$(sui completion --candidates "${kind}" 2>/dev/null)" -- "${COMP_WORDS[COMP_CWORD]}"))
+        return 0
+    fi
+    _sui "$@"
+}
+
+complete -F _sui_dynamic -o bashdefault -o default sui
+"#;
+
+/// Wraps the `_sui` completion function generated by clap, and takes over completion for `sui`
+/// when the script is sourced.
+const ZSH_DYNAMIC: &str = r#"
+_sui_dynamic() {
+    local kind
+    case "${words[CURRENT-1]}" in
+@CASES@
+    esac
+    if [[ -n "${kind}" ]]; then
+        local -a candidates
+        candidates=(${(f)"$(sui completion --candidates "${kind}" 2>/dev/null)"})
+        compadd -a candidates
+        return
+    fi
+    _sui "$@"
+}
+
+compdef _sui_dynamic sui
+"#;
+
+/// Values that flags are completed with, read from the client config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Candidates {
+    /// Aliases of the configured environments.
+    Envs,
+    /// Addresses in the keystore.
+    Addresses,
+}
+
+impl Candidates {
+    const ALL: [Candidates; 2] = [Candidates::Envs, Candidates::Addresses];
+
+    fn name(self) -> &'static str {
+        match self {
+            Candidates::Envs => "envs",
+            Candidates::Addresses => "addresses",
+        }
+    }
+
+    /// Ids of the arguments that take these values, in any command.
+    fn arg_ids(self) -> &'static [&'static str] {
+        match self {
+            Candidates::Envs => &["env"],
+            Candidates::Addresses => &["address", "recipient", "recipients", "to"],
+        }
+    }
+}
+
+/// Writes the completion script for `command` in `shell` to `out`. With `dynamic`, the script
+/// also completes environments and addresses from the client config, which is supported for
+/// bash, zsh and fish.
+pub fn generate(
+    shell: Shell,
+    dynamic: bool,
+    mut command: Command,
+    out: &mut impl Write,
+) -> Result<(), anyhow::Error> {
+    if dynamic && !matches!(shell, Shell::Bash | Shell::Zsh | Shell::Fish) {
+        bail!("Dynamic completion is not supported for {shell}, only for bash, zsh and fish");
+    }
+
+    clap_complete::generate(shell, &mut command, BIN_NAME, out);
+    if !dynamic {
+        return Ok(());
+    }
+
+    let flags: Vec<_> = Candidates::ALL
+        .into_iter()
+        .map(|candidates| (candidates, dynamic_flags(&command, candidates)))
+        .filter(|(_, flags)| !flags.is_empty())
+        .collect();
+
+    match shell {
+        Shell::Bash | Shell::Zsh => {
+            let cases = flags
+                .iter()
+                .map(|(candidates, flags)| {
+                    let patterns: Vec<_> = flags.iter().map(|flag| format!("--{flag}")).collect();
+                    format!(
+                        "        {}) kind={} ;;",
+                        patterns.join("|"),
+                        candidates.name()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            let template = if shell == Shell::Bash {
+                BASH_DYNAMIC
+            } else {
+                ZSH_DYNAMIC
+            };
+            write!(out, "{}", template.replace("@CASES@", &cases))?;
+        }
+        Shell::Fish => {
+            writeln!(out)?;
+            for (candidates, flags) in &flags {
+                for flag in flags {
+                    writeln!(
+                        out,
+                        "complete -c {BIN_NAME} -l {flag} -f -a \"({BIN_NAME} completion --candidates {} 2>/dev/null)\"",
+                        candidates.name()
+                    )?;
+                }
+            }
+        }
+        _ => unreachable!("Dynamic completion is only generated for bash, zsh and fish"),
+    }
+    Ok(())
+}
+
+/// Prints the values of `candidates` in the client config at `config_path`, one per line. Prints
+/// nothing if there is no client config yet, rather than prompting to create one.
+pub fn print_candidates(
+    candidates: Candidates,
+    config_path: &Path,
+    out: &mut impl Write,
+) -> Result<(), anyhow::Error> {
+    if !config_path.exists() {
+        return Ok(());
+    }
+
+    let config: SuiClientConfig = PersistedConfig::read(config_path)?;
+    let values: Vec<String> = match candidates {
+        Candidates::Envs => config.envs.iter().map(|env| env.alias.clone()).collect(),
+        Candidates::Addresses => config
+            .keystore
+            .addresses()
+            .iter()
+            .map(|address| address.to_string())
+            .collect(),
+    };
+
+    for value in values {
+        writeln!(out, "{value}")?;
+    }
+    Ok(())
+}
+
+/// Long flags of `command` and all of its subcommands that take `candidates` as values.
+fn dynamic_flags(command: &Command, candidates: Candidates) -> BTreeSet<String> {
+    let mut flags: BTreeSet<String> = command
+        .get_arguments()
+        .filter(|arg| candidates.arg_ids().contains(&arg.get_id().as_str()))
+        .filter_map(|arg| arg.get_long())
+        .map(str::to_string)
+        .collect();
+
+    for subcommand in command.get_subcommands() {
+        flags.extend(dynamic_flags(subcommand, candidates));
+    }
+    flags
+}
@@ -10,15 +10,17 @@ use fastcrypto::secp256k1::recoverable::Secp256k1Sig;
 use fastcrypto::traits::{KeyPair, ToFromBytes};
 use fastcrypto_zkp::bn254::utils::get_oidc_url;
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
+use inquire::Password;
 use json_to_table::{json_to_table, Orientation};
 use num_bigint::BigUint;
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use rusoto_core::Region;
 use rusoto_kms::{Kms, KmsClient, SignRequest};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 use shared_crypto::intent::{Intent, IntentMessage};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Display, Formatter};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -28,14 +30,15 @@ use sui_keys::keypair_file::{
     write_keypair_to_file,
 };
 use sui_keys::keystore::{AccountKeystore, Keystore};
+use sui_keys::keystore_archive::KeystoreArchive;
 use sui_types::base_types::SuiAddress;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{get_authority_key_pair, EncodeDecodeBase64, SignatureScheme, SuiKeyPair};
-use sui_types::crypto::{DefaultHash, PublicKey, Signature};
+use sui_types::crypto::{DefaultHash, PublicKey, Signature, SuiSignature};
 use sui_types::multisig::{MultiSig, MultiSigPublicKey, ThresholdUnit, WeightUnit};
 use sui_types::multisig_legacy::{MultiSigLegacy, MultiSigPublicKeyLegacy};
 use sui_types::signature::{AuthenticatorTrait, GenericSignature, VerifyParams};
-use sui_types::transaction::TransactionData;
+use sui_types::transaction::{TransactionData, TransactionDataAPI};
 use tracing::info;
 
 use rand::Rng;
@@ -69,6 +72,15 @@ pub enum KeyToolCommand {
         #[clap(long)]
         tx_bytes: Option<String>,
     },
+    /// Export all the keys in sui.keystore to an archive at `path`, encrypted with a passphrase.
+    /// The passphrase is prompted for, or read from the first line of `passphrase_file`. Use
+    /// `import-keystore` to add the keys to the keystore of another machine.
+    ExportKeystore {
+        #[clap(long)]
+        path: PathBuf,
+        #[clap(long)]
+        passphrase_file: Option<PathBuf>,
+    },
     /// Generate a new keypair with key scheme flag {ed25519 | secp256k1 | secp256r1}
     /// with optional derivation path, default to m/44'/784'/0'/0'/0' for ed25519 or
     /// m/54'/784'/0'/0/0 for secp256k1 or m/74'/784'/0'/0/0 for secp256r1. Word
@@ -93,6 +105,15 @@ pub enum KeyToolCommand {
         key_scheme: SignatureScheme,
         derivation_path: Option<DerivationPath>,
     },
+    /// Add the keys of an archive written by `export-keystore` to sui.keystore. The passphrase of
+    /// the archive is prompted for, or read from the first line of `passphrase_file`. Keys whose
+    /// address is already in sui.keystore are skipped.
+    ImportKeystore {
+        #[clap(long)]
+        path: PathBuf,
+        #[clap(long)]
+        passphrase_file: Option<PathBuf>,
+    },
     /// List all keys by its Sui address, Base64 encoded public key, key scheme name in
     /// sui.keystore.
     List,
@@ -140,6 +161,13 @@ pub enum KeyToolCommand {
         #[clap(long)]
         threshold: ThresholdUnit,
     },
+    /// Workflow for signing a transaction with a MultiSig address: create the address from its
+    /// members, collect the members' partial signatures from files, check how close they are to the
+    /// threshold, and combine them into a MultiSig signature.
+    MultiSig {
+        #[clap(subcommand)]
+        cmd: MultiSigCommand,
+    },
 
     /// Read the content at the provided file path. The accepted format can be
     /// [enum SuiKeyPair] (Base64 encoded of 33-byte `flag || privkey`) or `type AuthorityKeyPair`
@@ -206,6 +234,65 @@ pub enum KeyToolCommand {
     },
 }
 
+/// Subcommands of `sui keytool multi-sig`. The MultiSig address is described by a config file
+/// listing its members' public keys, their weights and the threshold, written by `address` and
+/// read by the other subcommands. Partial signatures are read from files containing either a
+/// Base64 encoded `flag || sig || pk`, or the JSON output of `sui keytool --json sign`.
+#[derive(Subcommand)]
+#[clap(rename_all = "kebab-case")]
+pub enum MultiSigCommand {
+    /// Create a MultiSig address from a list of public keys `flag || pk` in Base64, their weights
+    /// and the threshold, and write its config to `config`.
+    Address {
+        #[clap(long)]
+        threshold: ThresholdUnit,
+        #[clap(long, num_args(1..))]
+        pks: Vec<PublicKey>,
+        #[clap(long, num_args(1..))]
+        weights: Vec<WeightUnit>,
+        #[clap(long)]
+        config: PathBuf,
+    },
+    /// Check the partial signatures collected so far for a transaction (Base64 encoded
+    /// transaction bytes), and show which members have signed and the weight collected towards
+    /// the threshold.
+    Status {
+        #[clap(long)]
+        config: PathBuf,
+        #[clap(long)]
+        tx_bytes: String,
+        #[clap(long, num_args(0..))]
+        sig_files: Vec<PathBuf>,
+    },
+    /// Combine the partial signatures collected for a transaction into a MultiSig signature, once
+    /// their weight reaches the threshold. The result can be used as the signature field for
+    /// `sui client execute-signed-tx`.
+    Combine {
+        #[clap(long)]
+        config: PathBuf,
+        #[clap(long)]
+        tx_bytes: String,
+        #[clap(long, num_args(1..))]
+        sig_files: Vec<PathBuf>,
+    },
+}
+
+/// Config file of a MultiSig address, as written by `sui keytool multi-sig address`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigConfig {
+    multisig_address: SuiAddress,
+    threshold: ThresholdUnit,
+    members: Vec<MultiSigMember>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigMember {
+    public_base64_key: String,
+    weight: WeightUnit,
+}
+
 // Command Output types
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -225,6 +312,21 @@ pub struct DecodedMultiSigOutput {
     transaction_result: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportKeystoreOutput {
+    path: PathBuf,
+    exported_addresses: Vec<SuiAddress>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportKeystoreOutput {
+    imported: Vec<Key>,
+    /// Addresses of the keys of the archive that were already in the keystore.
+    skipped_addresses: Vec<SuiAddress>,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Key {
@@ -276,6 +378,25 @@ pub struct MultiSigOutput {
     weight: u8,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigStatus {
+    multisig_address: SuiAddress,
+    threshold: ThresholdUnit,
+    collected_weight: u16,
+    threshold_reached: bool,
+    members: Vec<MultiSigMemberStatus>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigMemberStatus {
+    address: SuiAddress,
+    public_base64_key: String,
+    weight: WeightUnit,
+    signed: bool,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ConvertOutput {
@@ -326,13 +447,16 @@ pub enum CommandOutput {
     DecodeMultiSig(DecodedMultiSigOutput),
     DecodeTxBytes(TransactionData),
     Error(String),
+    ExportKeystore(ExportKeystoreOutput),
     Generate(Key),
     Import(Key),
+    ImportKeystore(ImportKeystoreOutput),
     List(Vec<Key>),
     LoadKeypair(KeypairData),
     MultiSigAddress(MultiSigAddress),
     MultiSigCombinePartialSig(MultiSigCombinePartialSig),
     MultiSigCombinePartialSigLegacy(MultiSigCombinePartialSigLegacyOutput),
+    MultiSigStatus(MultiSigStatus),
     PrivateKeyBase64(PrivateKeyBase64),
     Show(Key),
     Sign(SignData),
@@ -407,6 +531,29 @@ impl KeyToolCommand {
                 CommandOutput::DecodeTxBytes(tx_data)
             }
 
+            KeyToolCommand::ExportKeystore {
+                path,
+                passphrase_file,
+            } => {
+                let addresses = keystore.addresses();
+                if addresses.is_empty() {
+                    return Err(anyhow!("There are no keys to export in the keystore"));
+                }
+                let keys = addresses
+                    .iter()
+                    .map(|address| keystore.get_key(address))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let passphrase = read_passphrase(passphrase_file.as_deref(), true)?;
+                if passphrase.is_empty() {
+                    return Err(anyhow!("The passphrase of the archive cannot be empty"));
+                }
+                KeystoreArchive::encrypt(keys, &passphrase)?.write(&path)?;
+                CommandOutput::ExportKeystore(ExportKeystoreOutput {
+                    path,
+                    exported_addresses: addresses,
+                })
+            }
+
             KeyToolCommand::Generate {
                 key_scheme,
                 derivation_path,
@@ -470,6 +617,41 @@ impl KeyToolCommand {
                 }
             }
 
+            KeyToolCommand::ImportKeystore {
+                path,
+                passphrase_file,
+            } => {
+                let archive = KeystoreArchive::read(&path)?;
+                let passphrase = read_passphrase(passphrase_file.as_deref(), false)?;
+                let keys = archive.decrypt(&passphrase)?;
+
+                let mut archived = BTreeSet::new();
+                for key in &keys {
+                    let address = SuiAddress::from(&key.public());
+                    if !archived.insert(address) {
+                        return Err(anyhow!(
+                            "The archive contains the key of {address} more than once"
+                        ));
+                    }
+                }
+
+                let existing: BTreeSet<_> = keystore.addresses().into_iter().collect();
+                let mut output = ImportKeystoreOutput {
+                    imported: vec![],
+                    skipped_addresses: vec![],
+                };
+                for key in keys {
+                    let address = SuiAddress::from(&key.public());
+                    if existing.contains(&address) {
+                        output.skipped_addresses.push(address);
+                    } else {
+                        output.imported.push(Key::from(&key));
+                        keystore.add_key(key)?;
+                    }
+                }
+                CommandOutput::ImportKeystore(output)
+            }
+
             KeyToolCommand::List => {
                 let keys = keystore
                     .keys()
@@ -579,6 +761,8 @@ impl KeyToolCommand {
                 )
             }
 
+            KeyToolCommand::MultiSig { cmd } => cmd.execute()?,
+
             KeyToolCommand::Show { file } => {
                 let res = read_keypair_from_file(&file);
                 match res {
@@ -810,6 +994,246 @@ impl KeyToolCommand {
     }
 }
 
+impl MultiSigCommand {
+    pub fn execute(self) -> Result<CommandOutput, anyhow::Error> {
+        Ok(match self {
+            MultiSigCommand::Address {
+                threshold,
+                pks,
+                weights,
+                config,
+            } => {
+                let multisig_pk = MultiSigPublicKey::new(pks.clone(), weights.clone(), threshold)?;
+                let address: SuiAddress = (&multisig_pk).into();
+                let members = pks
+                    .iter()
+                    .zip(&weights)
+                    .map(|(pk, w)| MultiSigMember {
+                        public_base64_key: pk.encode_base64(),
+                        weight: *w,
+                    })
+                    .collect();
+                let multisig_config = MultiSigConfig {
+                    multisig_address: address,
+                    threshold,
+                    members,
+                };
+                fs::write(&config, serde_json::to_string_pretty(&multisig_config)?)
+                    .map_err(|e| anyhow!("Cannot write MultiSig config to {:?}: {e}", config))?;
+
+                CommandOutput::MultiSigAddress(MultiSigAddress {
+                    multisig_address: address.to_string(),
+                    multisig: pks
+                        .into_iter()
+                        .zip(weights)
+                        .map(|(pk, w)| MultiSigOutput {
+                            address: (&pk).into(),
+                            public_base64_key: pk.encode_base64(),
+                            weight: w,
+                        })
+                        .collect(),
+                })
+            }
+
+            MultiSigCommand::Status {
+                config,
+                tx_bytes,
+                sig_files,
+            } => {
+                let multisig_pk = read_multisig_config(&config)?;
+                let tx_data = decode_tx_bytes(&tx_bytes)?;
+                let sigs = collect_partial_sigs(&multisig_pk, tx_data, &sig_files)?;
+
+                let members = multisig_pk
+                    .pubkeys()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (pk, w))| MultiSigMemberStatus {
+                        address: pk.into(),
+                        public_base64_key: pk.encode_base64(),
+                        weight: *w,
+                        signed: sigs.contains_key(&(i as u8)),
+                    })
+                    .collect();
+                let collected_weight = collected_weight(&multisig_pk, &sigs);
+                let threshold = *multisig_pk.threshold();
+
+                CommandOutput::MultiSigStatus(MultiSigStatus {
+                    multisig_address: (&multisig_pk).into(),
+                    threshold,
+                    collected_weight,
+                    threshold_reached: collected_weight >= threshold,
+                    members,
+                })
+            }
+
+            MultiSigCommand::Combine {
+                config,
+                tx_bytes,
+                sig_files,
+            } => {
+                let multisig_pk = read_multisig_config(&config)?;
+                let address: SuiAddress = (&multisig_pk).into();
+                let tx_data = decode_tx_bytes(&tx_bytes)?;
+                let sigs = collect_partial_sigs(&multisig_pk, tx_data.clone(), &sig_files)?;
+
+                let collected_weight = collected_weight(&multisig_pk, &sigs);
+                let threshold = *multisig_pk.threshold();
+                if collected_weight < threshold {
+                    return Err(anyhow!(
+                        "Not enough signatures: collected weight {collected_weight} is below the \
+                        threshold {threshold}"
+                    ));
+                }
+
+                // `sigs` is ordered by the index of the signers, as MultiSig expects.
+                let multisig = MultiSig::combine(sigs.into_values().collect(), multisig_pk)?;
+                let generic_sig: GenericSignature = multisig.into();
+                generic_sig
+                    .verify_authenticator(
+                        &IntentMessage::new(Intent::sui_transaction(), tx_data),
+                        address,
+                        None,
+                        &VerifyParams::default(),
+                    )
+                    .map_err(|e| {
+                        anyhow!("Combined MultiSig is invalid for the transaction: {e}")
+                    })?;
+
+                let multisig_serialized = generic_sig.encode_base64();
+                CommandOutput::MultiSigCombinePartialSig(MultiSigCombinePartialSig {
+                    multisig_address: address,
+                    multisig_parsed: generic_sig,
+                    multisig_serialized,
+                })
+            }
+        })
+    }
+}
+
+fn read_multisig_config(path: &Path) -> Result<MultiSigPublicKey, anyhow::Error> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read MultiSig config at {:?}: {e}", path))?;
+    let config: MultiSigConfig = serde_json::from_str(&content)
+        .map_err(|e| anyhow!("Invalid MultiSig config at {:?}: {e}", path))?;
+
+    let (pks, weights) = config
+        .members
+        .into_iter()
+        .map(|member| {
+            PublicKey::decode_base64(&member.public_base64_key)
+                .map(|pk| (pk, member.weight))
+                .map_err(|e| anyhow!("Invalid public key {}: {e}", member.public_base64_key))
+        })
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .unzip();
+    let multisig_pk = MultiSigPublicKey::new(pks, weights, config.threshold)?;
+
+    let address = SuiAddress::from(&multisig_pk);
+    if address != config.multisig_address {
+        return Err(anyhow!(
+            "MultiSig config at {:?} is for address {}, but its members define address {address}",
+            path,
+            config.multisig_address
+        ));
+    }
+    Ok(multisig_pk)
+}
+
+fn decode_tx_bytes(tx_bytes: &str) -> Result<TransactionData, anyhow::Error> {
+    let tx_bytes =
+        Base64::decode(tx_bytes).map_err(|e| anyhow!("Invalid base64 tx bytes: {:?}", e))?;
+    Ok(bcs::from_bytes(&tx_bytes)?)
+}
+
+/// Read partial signatures for `tx_data` from `sig_files`, checking that each one is a valid
+/// signature from a member of the MultiSig. Returns the signatures by the index of their signer.
+fn collect_partial_sigs(
+    multisig_pk: &MultiSigPublicKey,
+    tx_data: TransactionData,
+    sig_files: &[PathBuf],
+) -> Result<BTreeMap<u8, Signature>, anyhow::Error> {
+    let address = SuiAddress::from(multisig_pk);
+    if tx_data.sender() != address {
+        return Err(anyhow!(
+            "Transaction sender {} is not the MultiSig address {address}",
+            tx_data.sender()
+        ));
+    }
+
+    let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
+    let mut sigs = BTreeMap::new();
+    for file in sig_files {
+        let sig = read_partial_sig(file)?;
+        let pk = sig.to_public_key()?;
+        let index = multisig_pk.get_index(&pk).ok_or_else(|| {
+            anyhow!(
+                "Signature in {:?} is from {}, which is not a member of the MultiSig",
+                file,
+                SuiAddress::from(&pk)
+            )
+        })?;
+        sig.verify_secure(&intent_msg, (&pk).into(), pk.scheme())
+            .map_err(|e| {
+                anyhow!(
+                    "Signature in {:?} is invalid for the transaction: {e}",
+                    file
+                )
+            })?;
+        if sigs.insert(index, sig).is_some() {
+            return Err(anyhow!(
+                "Signature in {:?} is from {}, who has already signed",
+                file,
+                SuiAddress::from(&pk)
+            ));
+        }
+    }
+    Ok(sigs)
+}
+
+fn read_partial_sig(path: &Path) -> Result<Signature, anyhow::Error> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow!("Cannot read signature at {:?}: {e}", path))?;
+    let content = content.trim();
+    // Accept the output of `sui keytool --json sign` as well as a bare signature.
+    let encoded = match serde_json::from_str::<serde_json::Value>(content) {
+        Ok(json) => json
+            .get("suiSignature")
+            .and_then(|sig| sig.as_str())
+            .ok_or_else(|| anyhow!("No suiSignature found in {:?}", path))?
+            .to_string(),
+        Err(_) => content.to_string(),
+    };
+    Signature::decode_base64(&encoded).map_err(|e| anyhow!("Invalid signature in {:?}: {e}", path))
+}
+
+/// The passphrase of a keystore archive, read from the first line of `passphrase_file`, or
+/// prompted for (twice if `confirm`) when there is none.
+fn read_passphrase(passphrase_file: Option<&Path>, confirm: bool) -> Result<String, anyhow::Error> {
+    if let Some(file) = passphrase_file {
+        let content = fs::read_to_string(file)
+            .map_err(|e| anyhow!("Cannot read passphrase at {:?}: {e}", file))?;
+        return Ok(content.lines().next().unwrap_or_default().to_string());
+    }
+
+    let prompt = Password::new("Keystore archive passphrase:");
+    let prompt = if confirm {
+        prompt
+    } else {
+        prompt.without_confirmation()
+    };
+    prompt
+        .prompt()
+        .map_err(|e| anyhow!("Cannot read passphrase: {e}"))
+}
+
+fn collected_weight(multisig_pk: &MultiSigPublicKey, sigs: &BTreeMap<u8, Signature>) -> u16 {
+    sigs.keys()
+        .map(|i| multisig_pk.pubkeys()[*i as usize].1 as u16)
+        .sum()
+}
+
 impl From<&SuiKeyPair> for Key {
     fn from(skp: &SuiKeyPair) -> Self {
         Key::from(skp.public())
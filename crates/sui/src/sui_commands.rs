@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client_commands::SuiClientCommands;
+use crate::completion::{self, Candidates};
 use crate::console::start_console;
 use crate::fire_drill::{run_fire_drill, FireDrill};
 use crate::genesis_ceremony::{run, Ceremony};
@@ -115,7 +116,8 @@ pub enum SuiCommand {
         config: Option<PathBuf>,
         #[clap(subcommand)]
         cmd: Option<SuiClientCommands>,
-        /// Return command outputs in json format.
+        /// Return command outputs in json format, with a versioned schema that is stable across
+        /// changes to the human-readable output.
         #[clap(long, global = true)]
         json: bool,
         #[clap(short = 'y', long = "yes")]
@@ -155,6 +157,22 @@ pub enum SuiCommand {
         #[clap(subcommand)]
         fire_drill: FireDrill,
     },
+
+    /// Generate a shell completion script for the Sui CLI.
+    #[clap(name = "completion")]
+    Completion {
+        /// Shell to generate the completion script for.
+        #[clap(value_enum, required_unless_present = "candidates")]
+        shell: Option<clap_complete::Shell>,
+        /// Also complete environment aliases and addresses from the client config, by calling
+        /// back into the CLI while completing (bash, zsh and fish only).
+        #[clap(long)]
+        dynamic: bool,
+        /// Print the values that flags of this kind complete to, one per line. Used by the
+        /// dynamic completion script.
+        #[clap(long, value_enum, hide = true, conflicts_with_all = ["shell", "dynamic"])]
+        candidates: Option<Candidates>,
+    },
 }
 
 impl SuiCommand {
@@ -320,6 +338,20 @@ impl SuiCommand {
                 cmd,
             } => execute_move_command(package_path, build_config, cmd),
             SuiCommand::FireDrill { fire_drill } => run_fire_drill(fire_drill).await,
+            SuiCommand::Completion {
+                shell,
+                dynamic,
+                candidates,
+            } => {
+                if let Some(candidates) = candidates {
+                    let config_path = sui_config_dir()?.join(SUI_CLIENT_CONFIG);
+                    completion::print_candidates(candidates, &config_path, &mut stdout())
+                } else if let Some(shell) = shell {
+                    completion::generate(shell, dynamic, SuiCommand::command(), &mut stdout())
+                } else {
+                    bail!("A shell is required to generate completions")
+                }
+            }
         }
     }
 }
@@ -0,0 +1,372 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transaction history of an address, shown by `sui client history`.
+//!
+//! The transactions sent by and to an address are fetched from the network, newest first, and
+//! summarized in a cache file, one per environment and address. Later runs only fetch the
+//! transactions that are newer than the cached ones, or older ones if more are needed to fill the
+//! output, and the cache can be displayed without a connection to the network.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use sui_json_rpc_types::{
+    SuiCommand, SuiExecutionStatus, SuiTransactionBlockDataAPI, SuiTransactionBlockEffectsAPI,
+    SuiTransactionBlockKind, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionFilter,
+};
+use sui_sdk::SuiClient;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_history_tests.rs"]
+mod transaction_history_tests;
+
+/// Number of transactions requested from the network at a time.
+const PAGE_SIZE: usize = 50;
+
+/// Summary of a transaction block in the history of an address.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub digest: TransactionDigest,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    pub timestamp_ms: Option<u64>,
+    pub sender: SuiAddress,
+    /// Kind of the transaction, e.g. `ProgrammableTransaction`.
+    pub kind: String,
+    /// Move functions called by the transaction, in order.
+    pub functions: Vec<MoveFunction>,
+    pub success: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MoveFunction {
+    pub package: ObjectID,
+    pub module: String,
+    pub function: String,
+}
+
+/// Selects the transactions to show from an address's history.
+#[derive(Clone, Debug, Default)]
+pub struct HistoryFilter {
+    /// Only transactions calling a function in this package, module (`package::module`), or
+    /// this function (`package::module::function`).
+    pub function: Option<FunctionFilter>,
+    /// Only transactions of this kind, case insensitive.
+    pub kind: Option<String>,
+    /// Only transactions executed on or after this day (UTC).
+    pub from: Option<NaiveDate>,
+    /// Only transactions executed on or before this day (UTC).
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionFilter {
+    pub package: ObjectID,
+    pub module: Option<String>,
+    pub function: Option<String>,
+}
+
+/// The summarized history of an address, as cached between runs.
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionHistory {
+    entries: BTreeMap<TransactionDigest, HistoryEntry>,
+    /// Transactions sent by the address.
+    sent: Stream,
+    /// Transactions that changed objects owned by the address.
+    received: Stream,
+}
+
+/// How much of the history of an address has been fetched by one query, newest first. Cached
+/// transactions are always a contiguous range, from `newest` to just before `older_cursor`.
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Stream {
+    newest: Option<TransactionDigest>,
+    older_cursor: Option<TransactionDigest>,
+    complete: bool,
+}
+
+impl HistoryEntry {
+    /// Summarizes a transaction block response, which must include its input and effects.
+    pub fn from_response(response: &SuiTransactionBlockResponse) -> Option<Self> {
+        let data = &response.transaction.as_ref()?.data;
+        let effects = response.effects.as_ref()?;
+        let kind = data.transaction();
+        let functions = match kind {
+            SuiTransactionBlockKind::ProgrammableTransaction(pt) => pt
+                .commands
+                .iter()
+                .filter_map(|command| match command {
+                    SuiCommand::MoveCall(call) => Some(MoveFunction {
+                        package: call.package,
+                        module: call.module.clone(),
+                        function: call.function.clone(),
+                    }),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        Some(Self {
+            digest: response.digest,
+            checkpoint: response.checkpoint,
+            timestamp_ms: response.timestamp_ms,
+            sender: *data.sender(),
+            kind: kind.name().to_string(),
+            functions,
+            success: matches!(effects.status(), SuiExecutionStatus::Success),
+        })
+    }
+}
+
+impl Display for MoveFunction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{}::{}", self.package, self.module, self.function)
+    }
+}
+
+impl FromStr for FunctionFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split("::");
+        let package = parts.next().unwrap_or_default();
+        let package = ObjectID::from_str(package)
+            .map_err(|e| anyhow!("Invalid package ID '{package}': {e}"))?;
+        let module = parts.next().map(str::to_string);
+        let function = parts.next().map(str::to_string);
+        if parts.next().is_some() {
+            return Err(anyhow!(
+                "Expected 'package', 'package::module' or 'package::module::function', got '{s}'"
+            ));
+        }
+        Ok(Self {
+            package,
+            module,
+            function,
+        })
+    }
+}
+
+impl FunctionFilter {
+    fn matches(&self, function: &MoveFunction) -> bool {
+        self.package == function.package
+            && self.module.as_ref().map_or(true, |m| *m == function.module)
+            && self
+                .function
+                .as_ref()
+                .map_or(true, |f| *f == function.function)
+    }
+}
+
+impl HistoryFilter {
+    pub fn matches(&self, entry: &HistoryEntry) -> bool {
+        if let Some(function) = &self.function {
+            if !entry.functions.iter().any(|f| function.matches(f)) {
+                return false;
+            }
+        }
+        if let Some(kind) = &self.kind {
+            if !entry.kind.eq_ignore_ascii_case(kind) {
+                return false;
+            }
+        }
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+
+        // Transactions without a timestamp can't be placed in a date range.
+        let Some(timestamp_ms) = entry.timestamp_ms else {
+            return false;
+        };
+        let start_of = |day: NaiveDate| {
+            Utc.from_utc_datetime(&day.and_time(NaiveTime::MIN))
+                .timestamp_millis() as u64
+        };
+        self.from
+            .map_or(true, |from| timestamp_ms >= start_of(from))
+            && self.to.map_or(true, |to| {
+                to.succ_opt()
+                    .map_or(true, |next_day| timestamp_ms < start_of(next_day))
+            })
+    }
+}
+
+impl TransactionHistory {
+    /// Path of the cache of the history of `address` on the environment `env`, next to the
+    /// client's configuration at `config_path`.
+    pub fn cache_path(config_path: &Path, env: &str, address: SuiAddress) -> PathBuf {
+        config_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join("history")
+            .join(env)
+            .join(format!("{address}.json"))
+    }
+
+    /// Reads the cached history at `path`, or returns an empty history if there is none.
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read history cache {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse history cache {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), anyhow::Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write history cache {}", path.display()))
+    }
+
+    /// Fetches the transactions of `address` that are newer than the cached ones, then older ones
+    /// until there are at least `limit` transactions matching `filter`, or no more transactions.
+    pub async fn sync(
+        &mut self,
+        client: &SuiClient,
+        address: SuiAddress,
+        filter: &HistoryFilter,
+        limit: usize,
+    ) -> Result<(), anyhow::Error> {
+        let queries = [
+            TransactionFilter::FromAddress(address),
+            TransactionFilter::ToAddress(address),
+        ];
+
+        for (query, stream) in queries.iter().zip([&mut self.sent, &mut self.received]) {
+            stream
+                .fetch_newer(client, query, &mut self.entries, limit)
+                .await?;
+        }
+
+        while self.entries(filter, limit).len() < limit
+            && !(self.sent.complete && self.received.complete)
+        {
+            for (query, stream) in queries.iter().zip([&mut self.sent, &mut self.received]) {
+                stream.fetch_older(client, query, &mut self.entries).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Up to `limit` of the cached transactions matching `filter`, newest first.
+    pub fn entries(&self, filter: &HistoryFilter, limit: usize) -> Vec<HistoryEntry> {
+        let mut entries: Vec<_> = self
+            .entries
+            .values()
+            .filter(|entry| filter.matches(entry))
+            .cloned()
+            .collect();
+        entries.sort_by(|a, b| {
+            (b.checkpoint, b.timestamp_ms, b.digest).cmp(&(a.checkpoint, a.timestamp_ms, a.digest))
+        });
+        entries.truncate(limit);
+        entries
+    }
+}
+
+impl Stream {
+    /// Fetches the transactions newer than the newest cached one. If nothing was cached yet, only
+    /// fetches the `limit` newest transactions.
+    async fn fetch_newer(
+        &mut self,
+        client: &SuiClient,
+        query: &TransactionFilter,
+        entries: &mut BTreeMap<TransactionDigest, HistoryEntry>,
+        limit: usize,
+    ) -> Result<(), anyhow::Error> {
+        let mut cursor = None;
+        let mut newest = None;
+        let mut fetched = 0;
+        loop {
+            let page = client
+                .read_api()
+                .query_transaction_blocks(
+                    history_query(query),
+                    cursor,
+                    Some(PAGE_SIZE),
+                    /* descending_order */ true,
+                )
+                .await?;
+
+            for response in &page.data {
+                if self.newest == Some(response.digest) {
+                    self.newest = newest.or(self.newest);
+                    return Ok(());
+                }
+                newest.get_or_insert(response.digest);
+                entries.extend(HistoryEntry::from_response(response).map(|e| (e.digest, e)));
+                fetched += 1;
+            }
+
+            // Either the first fetch, or the newest cached transaction is no longer available
+            // from the network: start over from the newest transactions.
+            if !page.has_next_page || (self.newest.is_none() && fetched >= limit) {
+                self.newest = newest;
+                self.older_cursor = page.next_cursor;
+                self.complete = !page.has_next_page;
+                return Ok(());
+            }
+            cursor = page.next_cursor;
+        }
+    }
+
+    /// Fetches a page of transactions older than the cached ones.
+    async fn fetch_older(
+        &mut self,
+        client: &SuiClient,
+        query: &TransactionFilter,
+        entries: &mut BTreeMap<TransactionDigest, HistoryEntry>,
+    ) -> Result<(), anyhow::Error> {
+        if self.complete {
+            return Ok(());
+        }
+        let page = client
+            .read_api()
+            .query_transaction_blocks(
+                history_query(query),
+                self.older_cursor,
+                Some(PAGE_SIZE),
+                /* descending_order */ true,
+            )
+            .await?;
+
+        entries.extend(
+            page.data
+                .iter()
+                .filter_map(HistoryEntry::from_response)
+                .map(|e| (e.digest, e)),
+        );
+        self.older_cursor = page.next_cursor;
+        self.complete = !page.has_next_page;
+        Ok(())
+    }
+}
+
+fn history_query(filter: &TransactionFilter) -> SuiTransactionBlockResponseQuery {
+    SuiTransactionBlockResponseQuery::new(
+        Some(filter.clone()),
+        Some(
+            SuiTransactionBlockResponseOptions::new()
+                .with_input()
+                .with_effects(),
+        ),
+    )
+}
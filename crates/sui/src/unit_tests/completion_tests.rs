@@ -0,0 +1,82 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use sui_config::Config;
+use sui_keys::keystore::{AccountKeystore, FileBasedKeystore, Keystore};
+use sui_sdk::sui_client_config::{SuiClientConfig, SuiEnv};
+use sui_types::crypto::SignatureScheme;
+
+use crate::completion::{dynamic_flags, generate, print_candidates, Candidates};
+use crate::sui_commands::SuiCommand;
+
+fn script(shell: Shell, dynamic: bool) -> String {
+    let mut out = vec![];
+    generate(shell, dynamic, SuiCommand::command(), &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn test_dynamic_flags() {
+    let command = SuiCommand::command();
+
+    let envs = dynamic_flags(&command, Candidates::Envs);
+    assert!(envs.contains("env"), "{envs:?}");
+
+    let addresses = dynamic_flags(&command, Candidates::Addresses);
+    for flag in ["address", "recipient", "recipients", "to"] {
+        assert!(addresses.contains(flag), "{flag} not in {addresses:?}");
+    }
+}
+
+#[test]
+fn test_generate_scripts() {
+    let bash = script(Shell::Bash, false);
+    assert!(bash.contains("_sui()"));
+    assert!(!bash.contains("_sui_dynamic"));
+
+    let bash = script(Shell::Bash, true);
+    assert!(bash.contains("--env) kind=envs ;;"));
+    assert!(bash.ends_with("complete -F _sui_dynamic -o bashdefault -o default sui\n"));
+
+    let zsh = script(Shell::Zsh, true);
+    assert!(zsh.ends_with("compdef _sui_dynamic sui\n"));
+
+    let fish = script(Shell::Fish, true);
+    assert!(fish.contains(
+        "complete -c sui -l env -f -a \"(sui completion --candidates envs 2>/dev/null)\""
+    ));
+
+    let mut out = vec![];
+    assert!(generate(Shell::PowerShell, true, SuiCommand::command(), &mut out).is_err());
+}
+
+#[test]
+fn test_print_candidates() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("client.yaml");
+
+    // Nothing to complete before the client is configured.
+    let mut out = vec![];
+    print_candidates(Candidates::Envs, &config_path, &mut out).unwrap();
+    assert!(out.is_empty());
+
+    let mut keystore = FileBasedKeystore::new(&dir.path().join("sui.keystore")).unwrap();
+    let (address, _, _) = keystore
+        .generate_and_add_new_key(SignatureScheme::ED25519, None, None)
+        .unwrap();
+
+    let mut config = SuiClientConfig::new(Keystore::from(keystore));
+    config.add_env(SuiEnv::devnet());
+    config.add_env(SuiEnv::localnet());
+    config.persisted(&config_path).save().unwrap();
+
+    let mut out = vec![];
+    print_candidates(Candidates::Envs, &config_path, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "devnet\nlocal\n");
+
+    let mut out = vec![];
+    print_candidates(Candidates::Addresses, &config_path, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), format!("{address}\n"));
+}
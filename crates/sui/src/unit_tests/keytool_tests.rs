@@ -7,7 +7,9 @@ use crate::keytool::read_authority_keypair_from_file;
 use crate::keytool::read_keypair_from_file;
 
 use super::write_keypair_to_file;
+use super::CommandOutput;
 use super::KeyToolCommand;
+use super::MultiSigCommand;
 use anyhow::Ok;
 use fastcrypto::encoding::Base64;
 use fastcrypto::encoding::Encoding;
@@ -494,3 +496,178 @@ async fn test_sign_command() -> Result<(), anyhow::Error> {
     .await?;
     Ok(())
 }
+
+#[test]
+async fn test_multisig_workflow() -> Result<(), anyhow::Error> {
+    let keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(3));
+    let pks = keystore.keys();
+    let dir = TempDir::new().unwrap();
+    let config = dir.path().join("multisig.json");
+
+    let CommandOutput::MultiSigAddress(output) = MultiSigCommand::Address {
+        threshold: 2,
+        pks: pks.clone(),
+        weights: vec![1, 1, 1],
+        config: config.clone(),
+    }
+    .execute()?
+    else {
+        panic!("Expected a MultiSig address");
+    };
+    let multisig_address = SuiAddress::from_str(&output.multisig_address)?;
+
+    let gas = (
+        ObjectID::random(),
+        SequenceNumber::new(),
+        ObjectDigest::random(),
+    );
+    let tx_data = TransactionData::new_pay_sui(
+        multisig_address,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![10000],
+        gas,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+    )
+    .unwrap();
+    let tx_bytes = Base64::encode(bcs::to_bytes(&tx_data)?);
+
+    // Collect signatures from the last two members, one as a bare signature and one as the JSON
+    // output of `keytool sign`.
+    let mut sig_files = vec![];
+    for (i, pk) in pks.iter().enumerate().skip(1) {
+        let sig = keystore
+            .sign_secure(&pk.into(), &tx_data, Intent::sui_transaction())
+            .unwrap()
+            .encode_base64();
+        let content = if i == 1 {
+            sig
+        } else {
+            serde_json::json!({ "suiSignature": sig }).to_string()
+        };
+        let file = dir.path().join(format!("sig{i}"));
+        std::fs::write(&file, content)?;
+        sig_files.push(file);
+    }
+
+    let status = |sig_files: &[std::path::PathBuf]| {
+        let CommandOutput::MultiSigStatus(status) = MultiSigCommand::Status {
+            config: config.clone(),
+            tx_bytes: tx_bytes.clone(),
+            sig_files: sig_files.to_vec(),
+        }
+        .execute()
+        .unwrap() else {
+            panic!("Expected a MultiSig status");
+        };
+        status
+    };
+
+    let partial = status(&sig_files[1..]);
+    assert_eq!(partial.multisig_address, multisig_address);
+    assert_eq!(partial.collected_weight, 1);
+    assert!(!partial.threshold_reached);
+    assert_eq!(
+        partial.members.iter().map(|m| m.signed).collect::<Vec<_>>(),
+        vec![false, false, true]
+    );
+
+    // Not enough weight to combine yet, and signing twice does not count twice.
+    assert!(MultiSigCommand::Combine {
+        config: config.clone(),
+        tx_bytes: tx_bytes.clone(),
+        sig_files: sig_files[1..].to_vec(),
+    }
+    .execute()
+    .is_err());
+    assert!(MultiSigCommand::Combine {
+        config: config.clone(),
+        tx_bytes: tx_bytes.clone(),
+        sig_files: vec![sig_files[1].clone(), sig_files[1].clone()],
+    }
+    .execute()
+    .is_err());
+
+    let complete = status(&sig_files);
+    assert_eq!(complete.collected_weight, 2);
+    assert!(complete.threshold_reached);
+
+    // Signatures can be passed in any order.
+    sig_files.reverse();
+    let CommandOutput::MultiSigCombinePartialSig(combined) = MultiSigCommand::Combine {
+        config: config.clone(),
+        tx_bytes: tx_bytes.clone(),
+        sig_files: sig_files.clone(),
+    }
+    .execute()?
+    else {
+        panic!("Expected a combined MultiSig");
+    };
+    assert_eq!(combined.multisig_address, multisig_address);
+
+    // Signatures over a different transaction are rejected.
+    let other_tx_data = TransactionData::new_pay_sui(
+        multisig_address,
+        vec![gas],
+        vec![SuiAddress::random_for_testing_only()],
+        vec![20000],
+        gas,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        1,
+    )
+    .unwrap();
+    assert!(MultiSigCommand::Status {
+        config,
+        tx_bytes: Base64::encode(bcs::to_bytes(&other_tx_data)?),
+        sig_files,
+    }
+    .execute()
+    .is_err());
+    Ok(())
+}
+
+#[test]
+async fn test_export_import_keystore() -> Result<(), anyhow::Error> {
+    let temp_dir = TempDir::new().unwrap();
+    let archive = temp_dir.path().join("sui.keystore.archive");
+    let passphrase_file = temp_dir.path().join("passphrase");
+    std::fs::write(&passphrase_file, "correct horse battery staple\n")?;
+
+    let mut keystore = Keystore::from(InMemKeystore::new_insecure_for_tests(2));
+    KeyToolCommand::ExportKeystore {
+        path: archive.clone(),
+        passphrase_file: Some(passphrase_file.clone()),
+    }
+    .execute(&mut keystore)
+    .await?;
+
+    // Only one of the keys is new to the other keystore.
+    let mut other = Keystore::from(InMemKeystore::new_insecure_for_tests(0));
+    let existing = keystore.get_key(&keystore.addresses()[0])?.encode_base64();
+    other.add_key(SuiKeyPair::decode_base64(&existing).unwrap())?;
+    let CommandOutput::ImportKeystore(output) = KeyToolCommand::ImportKeystore {
+        path: archive.clone(),
+        passphrase_file: Some(passphrase_file),
+    }
+    .execute(&mut other)
+    .await?
+    else {
+        panic!("Expected the imported keys");
+    };
+    assert_eq!(output.imported.len(), 1);
+    assert_eq!(output.skipped_addresses, vec![keystore.addresses()[0]]);
+    assert_eq!(other.addresses(), keystore.addresses());
+
+    // A wrong passphrase is rejected.
+    let wrong_passphrase_file = temp_dir.path().join("wrong_passphrase");
+    std::fs::write(&wrong_passphrase_file, "wrong")?;
+    assert!(KeyToolCommand::ImportKeystore {
+        path: archive,
+        passphrase_file: Some(wrong_passphrase_file),
+    }
+    .execute(&mut other)
+    .await
+    .is_err());
+    Ok(())
+}
@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::digests::TransactionDigest;
+
+use super::{FunctionFilter, HistoryEntry, HistoryFilter, MoveFunction, TransactionHistory};
+
+// 2023-10-02T12:00:00Z
+const NOON: u64 = 1_696_248_000_000;
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+fn entry(checkpoint: u64, timestamp_ms: u64, functions: &[(&str, &str)]) -> HistoryEntry {
+    HistoryEntry {
+        digest: TransactionDigest::random(),
+        checkpoint: Some(checkpoint),
+        timestamp_ms: Some(timestamp_ms),
+        sender: SuiAddress::ZERO,
+        kind: "ProgrammableTransaction".to_string(),
+        functions: functions
+            .iter()
+            .map(|(module, function)| MoveFunction {
+                package: ObjectID::from_single_byte(2),
+                module: module.to_string(),
+                function: function.to_string(),
+            })
+            .collect(),
+        success: true,
+    }
+}
+
+fn history(entries: &[HistoryEntry]) -> TransactionHistory {
+    let mut history = TransactionHistory::default();
+    history
+        .entries
+        .extend(entries.iter().map(|e| (e.digest, e.clone())));
+    history
+}
+
+#[test]
+fn test_parse_function_filter() {
+    let filter = FunctionFilter::from_str("0x2::coin::split").unwrap();
+    assert_eq!(filter.package, ObjectID::from_single_byte(2));
+    assert_eq!(filter.module.as_deref(), Some("coin"));
+    assert_eq!(filter.function.as_deref(), Some("split"));
+
+    let filter = FunctionFilter::from_str("0x2").unwrap();
+    assert_eq!(filter.module, None);
+    assert_eq!(filter.function, None);
+
+    assert!(FunctionFilter::from_str("coin::split").is_err());
+    assert!(FunctionFilter::from_str("0x2::coin::split::extra").is_err());
+}
+
+#[test]
+fn test_filter_by_function_and_kind() {
+    let split = entry(1, NOON, &[("coin", "split")]);
+    let join = entry(2, NOON, &[("coin", "join")]);
+    let transfer = entry(3, NOON, &[("transfer", "public_transfer")]);
+    let history = history(&[split.clone(), join.clone(), transfer]);
+
+    let by = |function: &str| HistoryFilter {
+        function: Some(FunctionFilter::from_str(function).unwrap()),
+        ..Default::default()
+    };
+    assert_eq!(
+        history.entries(&by("0x2::coin::split"), 10),
+        vec![split.clone()]
+    );
+    assert_eq!(history.entries(&by("0x2::coin"), 10), vec![join, split]);
+    assert_eq!(history.entries(&by("0x2"), 10).len(), 3);
+    assert!(history.entries(&by("0x3"), 10).is_empty());
+
+    let kind = |kind: &str| HistoryFilter {
+        kind: Some(kind.to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        history.entries(&kind("programmabletransaction"), 10).len(),
+        3
+    );
+    assert!(history.entries(&kind("Genesis"), 10).is_empty());
+}
+
+#[test]
+fn test_filter_by_date() {
+    let before = entry(1, NOON - DAY_MS, &[]);
+    let during = entry(2, NOON, &[]);
+    let after = entry(3, NOON + DAY_MS, &[]);
+    let mut untimed = entry(4, NOON, &[]);
+    untimed.timestamp_ms = None;
+    let history = history(&[before, during.clone(), after, untimed]);
+
+    let day = NaiveDate::from_ymd_opt(2023, 10, 2).unwrap();
+    let filter = HistoryFilter {
+        from: Some(day),
+        to: Some(day),
+        ..Default::default()
+    };
+    assert_eq!(history.entries(&filter, 10), vec![during]);
+
+    let filter = HistoryFilter {
+        from: Some(day),
+        ..Default::default()
+    };
+    assert_eq!(history.entries(&filter, 10).len(), 2);
+}
+
+#[test]
+fn test_entries_are_newest_first_and_limited() {
+    let entries: Vec<_> = (0..5).map(|i| entry(i, NOON + i, &[])).collect();
+    let history = history(&entries);
+
+    let newest = history.entries(&HistoryFilter::default(), 2);
+    assert_eq!(newest, vec![entries[4].clone(), entries[3].clone()]);
+}
+
+#[test]
+fn test_cache_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path =
+        TransactionHistory::cache_path(&dir.path().join("client.yaml"), "devnet", SuiAddress::ZERO);
+    assert!(path.starts_with(dir.path().join("history").join("devnet")));
+
+    // Nothing cached yet.
+    let empty = TransactionHistory::load(&path).unwrap();
+    assert!(empty.entries(&HistoryFilter::default(), 10).is_empty());
+
+    let entries = vec![entry(2, NOON, &[("coin", "split")]), entry(1, NOON, &[])];
+    history(&entries).save(&path).unwrap();
+    let loaded = TransactionHistory::load(&path).unwrap();
+    assert_eq!(loaded.entries(&HistoryFilter::default(), 10), entries);
+}
@@ -4,9 +4,9 @@
 use anyhow::anyhow;
 use fastcrypto::jwt_utils::parse_and_validate_jwt;
 use fastcrypto::traits::EncodeDecodeBase64;
-use fastcrypto_zkp::bn254::utils::{gen_address_seed, get_zk_login_address};
+use fastcrypto_zkp::bn254::utils::gen_address_seed;
 use fastcrypto_zkp::bn254::utils::{get_proof, get_salt};
-use fastcrypto_zkp::bn254::zk_login::ZkLoginInputs;
+use fastcrypto_zkp::bn254::zk_login::{OIDCProvider, ZkLoginInputs};
 use regex::Regex;
 use reqwest::Client;
 use serde_json::json;
@@ -21,6 +21,14 @@ use sui_types::committee::EpochId;
 use sui_types::signature::GenericSignature;
 use sui_types::transaction::Transaction;
 use sui_types::zk_login_authenticator::ZkLoginAuthenticator;
+use sui_types::zk_login_util::{ZkLoginAccount, ZkLoginProviderRegistry};
+
+/// OIDC providers the keytool can sign in with.
+pub(crate) const SUPPORTED_PROVIDERS: [OIDCProvider; 3] = [
+    OIDCProvider::Google,
+    OIDCProvider::Twitch,
+    OIDCProvider::Facebook,
+];
 
 /// Read a line from stdin, parse the id_token field and return.
 pub fn read_cli_line() -> Result<String, anyhow::Error> {
@@ -86,10 +94,13 @@ pub async fn perform_zk_login_test_tx(
     let (sub, aud) = parse_and_validate_jwt(parsed_token)?;
     let address_seed = gen_address_seed(&user_salt, "sub", &sub, &aud)?;
     let zk_login_inputs = ZkLoginInputs::from_reader(reader, &address_seed)?;
-    let zklogin_address = SuiAddress::from_bytes(get_zk_login_address(
-        zk_login_inputs.get_address_seed(),
-        zk_login_inputs.get_iss(),
-    )?)?;
+    let registry = ZkLoginProviderRegistry::new(SUPPORTED_PROVIDERS);
+    let account = ZkLoginAccount::new(
+        registry.provider(zk_login_inputs.get_iss())?.clone(),
+        aud,
+        address_seed,
+    );
+    let zklogin_address = registry.derive_address(&account)?;
     println!("ZkLogin Address: {:?}", zklogin_address);
 
     // Request some coin from faucet and build a test transaction.
@@ -19,7 +19,7 @@ use tokio::time::sleep;
 
 use sui::client_commands::SwitchResponse;
 use sui::{
-    client_commands::{SuiClientCommandResult, SuiClientCommands},
+    client_commands::{SuiClientCommandResult, SuiClientCommands, CLIENT_JSON_OUTPUT_VERSION},
     sui_commands::SuiCommand,
 };
 use sui_config::{
@@ -1456,6 +1456,43 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_json_output() -> Result<(), anyhow::Error> {
+    let mut test_cluster = TestClusterBuilder::new().build().await;
+    let address = test_cluster.get_address_0();
+    let context = &mut test_cluster.wallet;
+
+    let resp = SuiClientCommands::ActiveAddress {}.execute(context).await?;
+    assert_eq!(
+        serde_json::to_value(resp.json_output())?,
+        json!({
+            "version": CLIENT_JSON_OUTPUT_VERSION,
+            "command": "active-address",
+            "result": { "address": address },
+        })
+    );
+
+    let resp = SuiClientCommands::Gas {
+        address: Some(address),
+    }
+    .execute(context)
+    .await?;
+    let SuiClientCommandResult::Gas(gas_coins) = &resp else {
+        panic!("Command failed")
+    };
+    let output = serde_json::to_value(resp.json_output())?;
+    assert_eq!(output["version"], json!(CLIENT_JSON_OUTPUT_VERSION));
+    assert_eq!(output["command"], json!("gas"));
+    let coins = output["result"].as_array().unwrap();
+    assert_eq!(coins.len(), gas_coins.len());
+    for (coin, gas_coin) in coins.iter().zip(gas_coins) {
+        assert_eq!(coin["gasCoinId"], json!(gas_coin.id()));
+        assert_eq!(coin["gasBalance"], json!(gas_coin.value()));
+    }
+
+    Ok(())
+}
+
 fn get_gas_value(o: &SuiObjectData) -> u64 {
     GasCoin::try_from(o).unwrap().value()
 }
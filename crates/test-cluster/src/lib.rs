@@ -51,6 +51,8 @@ use tokio::time::{timeout, Instant};
 use tokio::{task::JoinHandle, time::sleep};
 use tracing::info;
 
+pub mod scenario;
+
 const NUM_VALIDATOR: usize = 4;
 
 pub struct FullNodeHandle {
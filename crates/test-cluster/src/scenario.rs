@@ -0,0 +1,322 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A DSL for end-to-end tests of flows between several actors, each with their own account,
+//! executed against a `TestCluster`:
+//!
+//! ```ignore
+//! let scenario = ScenarioBuilder::new()
+//!     .with_actor("alice", 10 * MIST_PER_SUI)
+//!     .with_actor("bob", 10 * MIST_PER_SUI)
+//!     .build()
+//!     .await;
+//!
+//! let package = scenario.publish_examples("alice", "basics").await;
+//! let response = scenario.call("alice", package, "counter", "create").execute().await;
+//! let counter = created_objects(&response)[0];
+//!
+//! scenario
+//!     .call("bob", package, "counter", "increment")
+//!     .object_arg(counter)
+//!     .execute()
+//!     .await;
+//! scenario.advance_epoch().await;
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use sui_json_rpc_types::{
+    get_new_package_obj_from_response, SuiTransactionBlockEffectsAPI, SuiTransactionBlockResponse,
+};
+use sui_test_transaction_builder::TestTransactionBuilder;
+use sui_types::base_types::{ObjectID, SuiAddress};
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::object::{Object, Owner};
+use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
+use sui_types::transaction::{Argument, ObjectArg};
+use sui_types::{Identifier, TypeTag};
+
+use crate::{TestCluster, TestClusterBuilder};
+
+pub struct ScenarioBuilder {
+    cluster_builder: TestClusterBuilder,
+    actors: Vec<(String, u64)>,
+}
+
+impl ScenarioBuilder {
+    pub fn new() -> Self {
+        Self {
+            cluster_builder: TestClusterBuilder::new(),
+            actors: vec![],
+        }
+    }
+
+    /// Runs the scenario on a cluster built by `cluster_builder`, instead of the default one.
+    pub fn with_cluster(mut self, cluster_builder: TestClusterBuilder) -> Self {
+        self.cluster_builder = cluster_builder;
+        self
+    }
+
+    /// Adds an actor `name`, with a new account funded with a coin of `balance` MIST.
+    pub fn with_actor(mut self, name: &str, balance: u64) -> Self {
+        self.actors.push((name.to_string(), balance));
+        self
+    }
+
+    pub async fn build(self) -> Scenario {
+        let mut scenario = Scenario {
+            cluster: self.cluster_builder.build().await,
+            actors: BTreeMap::new(),
+        };
+        for (name, balance) in self.actors {
+            scenario.create_actor(&name, balance).await;
+        }
+        scenario
+    }
+}
+
+impl Default for ScenarioBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct Scenario {
+    pub cluster: TestCluster,
+    actors: BTreeMap<String, Actor>,
+}
+
+struct Actor {
+    address: SuiAddress,
+    keypair: AccountKeyPair,
+}
+
+impl Scenario {
+    /// Adds an actor `name`, with a new account funded with a coin of `balance` MIST.
+    pub async fn create_actor(&mut self, name: &str, balance: u64) -> SuiAddress {
+        assert!(
+            !self.actors.contains_key(name),
+            "Actor {name} already exists"
+        );
+        let (address, keypair) = get_key_pair();
+        self.actors
+            .insert(name.to_string(), Actor { address, keypair });
+        self.fund(name, balance).await;
+        address
+    }
+
+    pub fn address(&self, name: &str) -> SuiAddress {
+        self.actor(name).address
+    }
+
+    /// Sends a new coin of `amount` MIST to `name`, from the cluster's wallet.
+    pub async fn fund(&self, name: &str, amount: u64) {
+        let tx = self
+            .cluster
+            .test_transaction_builder()
+            .await
+            .transfer_sui(Some(amount), self.address(name))
+            .build();
+        self.cluster.sign_and_execute_transaction(&tx).await;
+    }
+
+    /// Publishes the package at `path` on behalf of `name`, and returns its ID.
+    pub async fn publish(&self, name: &str, path: PathBuf) -> ObjectID {
+        let response = self.execute(name, |builder| builder.publish(path)).await;
+        assert_success(&response);
+        get_new_package_obj_from_response(&response)
+            .expect("Publish must create a package")
+            .0
+    }
+
+    /// Publishes one of the packages in `sui_programmability/examples` on behalf of `name`.
+    pub async fn publish_examples(&self, name: &str, subpath: &'static str) -> ObjectID {
+        let response = self
+            .execute(name, |builder| builder.publish_examples(subpath))
+            .await;
+        assert_success(&response);
+        get_new_package_obj_from_response(&response)
+            .expect("Publish must create a package")
+            .0
+    }
+
+    /// Starts a call to `package::module::function` on behalf of `name`. Its arguments are added
+    /// to the returned `Call`, which is then executed.
+    pub fn call(&self, name: &str, package: ObjectID, module: &str, function: &str) -> Call<'_> {
+        Call {
+            scenario: self,
+            sender: name.to_string(),
+            package,
+            module: Identifier::new(module).unwrap(),
+            function: Identifier::new(function).unwrap(),
+            type_args: vec![],
+            builder: ProgrammableTransactionBuilder::new(),
+            args: vec![],
+        }
+    }
+
+    /// Closes the current epoch and waits for the whole network to reach the next one.
+    pub async fn advance_epoch(&self) {
+        self.cluster.trigger_reconfiguration().await;
+    }
+
+    /// The latest version of object `id`, as seen by the fullnode.
+    pub async fn object(&self, id: ObjectID) -> Object {
+        self.cluster
+            .get_object_from_fullnode_store(&id)
+            .await
+            .unwrap_or_else(|| panic!("Object {id} not found"))
+    }
+
+    /// Total balance of the SUI coins owned by `name`, in MIST.
+    pub async fn balance(&self, name: &str) -> u128 {
+        self.cluster
+            .sui_client()
+            .coin_read_api()
+            .get_balance(self.address(name), None)
+            .await
+            .unwrap()
+            .total_balance
+    }
+
+    pub async fn assert_owner(&self, id: ObjectID, name: &str) {
+        assert_eq!(
+            self.object(id).await.owner,
+            Owner::AddressOwner(self.address(name)),
+            "Object {id} is not owned by {name}"
+        );
+    }
+
+    fn actor(&self, name: &str) -> &Actor {
+        self.actors
+            .get(name)
+            .unwrap_or_else(|| panic!("Unknown actor {name}"))
+    }
+
+    /// Builds a transaction sent by `name`, paying for gas with one of its coins, signs it with its
+    /// key and executes it. The transaction may fail.
+    async fn execute(
+        &self,
+        name: &str,
+        build: impl FnOnce(TestTransactionBuilder) -> TestTransactionBuilder,
+    ) -> SuiTransactionBlockResponse {
+        let actor = self.actor(name);
+        let gas = self
+            .cluster
+            .wallet
+            .get_one_gas_object_owned_by_address(actor.address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("Actor {name} has no coin to pay for gas"));
+        let rgp = self.cluster.get_reference_gas_price().await;
+        let tx = build(TestTransactionBuilder::new(actor.address, gas, rgp))
+            .build_and_sign(&actor.keypair);
+        self.cluster
+            .wallet
+            .execute_transaction_may_fail(tx)
+            .await
+            .unwrap()
+    }
+}
+
+/// A Move call being built by a `Scenario`.
+pub struct Call<'a> {
+    scenario: &'a Scenario,
+    sender: String,
+    package: ObjectID,
+    module: Identifier,
+    function: Identifier,
+    type_args: Vec<TypeTag>,
+    builder: ProgrammableTransactionBuilder,
+    args: Vec<CallArgument>,
+}
+
+/// Objects are only looked up when the call is executed, to pass their latest versions.
+enum CallArgument {
+    Input(Argument),
+    Object(ObjectID),
+}
+
+impl Call<'_> {
+    pub fn type_arg(mut self, type_arg: TypeTag) -> Self {
+        self.type_args.push(type_arg);
+        self
+    }
+
+    pub fn pure_arg<T: Serialize>(mut self, value: T) -> Self {
+        let arg = self.builder.pure(value).unwrap();
+        self.args.push(CallArgument::Input(arg));
+        self
+    }
+
+    /// Passes object `id`, mutably if it is shared. The object must not be the coin the sender
+    /// pays for gas with.
+    pub fn object_arg(mut self, id: ObjectID) -> Self {
+        self.args.push(CallArgument::Object(id));
+        self
+    }
+
+    /// Executes the call, which must succeed.
+    pub async fn execute(self) -> SuiTransactionBlockResponse {
+        let response = self.execute_may_fail().await;
+        assert_success(&response);
+        response
+    }
+
+    pub async fn execute_may_fail(mut self) -> SuiTransactionBlockResponse {
+        let mut args = vec![];
+        for arg in self.args {
+            args.push(match arg {
+                CallArgument::Input(arg) => arg,
+                CallArgument::Object(id) => {
+                    let object = self.scenario.object(id).await;
+                    let object_arg = match object.owner {
+                        Owner::Shared {
+                            initial_shared_version,
+                        } => ObjectArg::SharedObject {
+                            id,
+                            initial_shared_version,
+                            mutable: true,
+                        },
+                        _ => ObjectArg::ImmOrOwnedObject(object.compute_object_reference()),
+                    };
+                    self.builder.obj(object_arg).unwrap()
+                }
+            });
+        }
+        self.builder.programmable_move_call(
+            self.package,
+            self.module,
+            self.function,
+            self.type_args,
+            args,
+        );
+        let pt = self.builder.finish();
+        self.scenario
+            .execute(&self.sender, |builder| builder.programmable(pt))
+            .await
+    }
+}
+
+/// IDs of the objects created by a transaction.
+pub fn created_objects(response: &SuiTransactionBlockResponse) -> Vec<ObjectID> {
+    response
+        .effects
+        .as_ref()
+        .unwrap()
+        .created()
+        .iter()
+        .map(|o| o.reference.object_id)
+        .collect()
+}
+
+fn assert_success(response: &SuiTransactionBlockResponse) {
+    assert!(
+        response.status_ok().unwrap(),
+        "Transaction {} failed: {:?}",
+        response.digest,
+        response.effects.as_ref().unwrap().status()
+    );
+}
@@ -593,6 +593,10 @@ impl RocksDBBatch {
         delegate_batch_call!(self.size_in_bytes())
     }
 
+    fn len(&self) -> usize {
+        delegate_batch_call!(self.len())
+    }
+
     pub fn delete_cf<K: AsRef<[u8]>>(&mut self, cf: &impl AsColumnFamilyRef, key: K) {
         delegate_batch_call!(self.delete_cf(cf, key))
     }
@@ -783,7 +787,7 @@ impl<K, V> DBMap<K, V> {
         DBBatch::new(
             &self.rocksdb,
             batch,
-            self.opts.writeopts(),
+            self.opts.sync_to_disk,
             &self.db_metrics,
             &self.write_sample_interval,
         )
@@ -1152,9 +1156,23 @@ impl<K, V> DBMap<K, V> {
 pub struct DBBatch {
     rocksdb: Arc<RocksDB>,
     batch: RocksDBBatch,
-    opts: WriteOptions,
+    /// Whether the write is synced to disk.
+    sync: bool,
+    /// Whether the write bypasses the WAL.
+    disable_wal: bool,
     db_metrics: Arc<DBMetrics>,
     write_sample_interval: SamplingInterval,
+    /// The operations of the batch, recorded if it can be appended to another batch.
+    ops: Option<Vec<BatchOp>>,
+}
+
+/// An operation of a batch on the column family it names, recorded so that it can be replayed
+/// into another batch.
+enum BatchOp {
+    Put(String, Vec<u8>, Vec<u8>),
+    Delete(String, Vec<u8>),
+    DeleteRange(String, Vec<u8>, Vec<u8>),
+    Merge(String, Vec<u8>, Vec<u8>),
 }
 
 impl DBBatch {
@@ -1164,19 +1182,34 @@ impl DBBatch {
     pub fn new(
         dbref: &Arc<RocksDB>,
         batch: RocksDBBatch,
-        opts: WriteOptions,
+        sync: bool,
         db_metrics: &Arc<DBMetrics>,
         write_sample_interval: &SamplingInterval,
     ) -> Self {
         DBBatch {
             rocksdb: dbref.clone(),
             batch,
-            opts,
+            sync,
+            disable_wal: false,
             db_metrics: db_metrics.clone(),
             write_sample_interval: write_sample_interval.clone(),
+            ops: None,
         }
     }
 
+    /// Records the operations of the batch, so that it can be appended to another batch. Must be
+    /// called before any operation is added to the batch.
+    pub fn appendable(mut self) -> Self {
+        assert!(self.is_empty(), "batch already has operations");
+        self.ops = Some(Vec::new());
+        self
+    }
+
+    /// Sets whether the batch bypasses the WAL when written, in which case it is lost on a crash.
+    pub fn disable_wal(&mut self, disable: bool) {
+        self.disable_wal = disable;
+    }
+
     /// Consume the batch and write its operations to the database
     #[instrument(level = "trace", skip_all, err)]
     pub fn write(self) -> Result<(), TypedStoreError> {
@@ -1194,7 +1227,10 @@ impl DBBatch {
         } else {
             None
         };
-        self.rocksdb.write(self.batch, &self.opts)?;
+        let mut opts = WriteOptions::default();
+        opts.set_sync(self.sync);
+        opts.disable_wal(self.disable_wal);
+        self.rocksdb.write(self.batch, &opts)?;
         self.db_metrics
             .op_metrics
             .rocksdb_batch_commit_bytes
@@ -1208,6 +1244,77 @@ impl DBBatch {
         }
         Ok(())
     }
+
+    /// Appends the operations of `others` to this batch, so that they are written atomically with
+    /// the operations already in it. All batches must belong to the same database, and `others`
+    /// must be [`appendable`](Self::appendable). The batch is then written with the strictest write
+    /// options of the batches: synced if any of them is, and through the WAL unless all bypass it.
+    pub fn append(
+        &mut self,
+        others: impl IntoIterator<Item = DBBatch>,
+    ) -> Result<(), TypedStoreError> {
+        let others: Vec<_> = others.into_iter().collect();
+        if others
+            .iter()
+            .any(|other| !Arc::ptr_eq(&self.rocksdb, &other.rocksdb))
+        {
+            return Err(TypedStoreError::CrossDBBatch);
+        }
+        if others.iter().any(|other| other.ops.is_none()) {
+            return Err(TypedStoreError::RocksDBError(
+                "cannot append a batch that does not record its operations".to_string(),
+            ));
+        }
+        for other in others {
+            self.sync |= other.sync;
+            self.disable_wal &= other.disable_wal;
+            for op in other.ops.into_iter().flatten() {
+                self.replay(op)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn replay(&mut self, op: BatchOp) -> Result<(), TypedStoreError> {
+        let cf_name = match &op {
+            BatchOp::Put(cf, ..)
+            | BatchOp::Delete(cf, ..)
+            | BatchOp::DeleteRange(cf, ..)
+            | BatchOp::Merge(cf, ..) => cf,
+        };
+        let cf = self
+            .rocksdb
+            .cf_handle(cf_name)
+            .ok_or_else(|| TypedStoreError::UnregisteredColumn(cf_name.clone()))?;
+        match &op {
+            BatchOp::Put(_, key, value) => self.batch.put_cf(&cf, key, value),
+            BatchOp::Delete(_, key) => self.batch.delete_cf(&cf, key),
+            BatchOp::DeleteRange(_, from, to) => self.batch.delete_range_cf(&cf, from, to)?,
+            BatchOp::Merge(_, key, value) => self.batch.merge_cf(&cf, key, value),
+        }
+        drop(cf);
+        self.record(|| op);
+        Ok(())
+    }
+
+    fn record(&mut self, op: impl FnOnce() -> BatchOp) {
+        if let Some(ops) = &mut self.ops {
+            ops.push(op());
+        }
+    }
+
+    /// The number of operations in the batch.
+    pub fn len(&self) -> usize {
+        self.batch.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn size_in_bytes(&self) -> usize {
+        self.batch.size_in_bytes()
+    }
 }
 
 // TODO: Remove this entire implementation once we switch to sally
@@ -1225,7 +1332,8 @@ impl DBBatch {
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|k| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                self.batch.delete_cf(&db.cf(), k_buf);
+                self.batch.delete_cf(&db.cf(), &k_buf);
+                self.record(|| BatchOp::Delete(db.cf.clone(), k_buf));
 
                 Ok(())
             })?;
@@ -1254,7 +1362,8 @@ impl DBBatch {
         let from_buf = be_fix_int_ser(from)?;
         let to_buf = be_fix_int_ser(to)?;
 
-        self.batch.delete_range_cf(&db.cf(), from_buf, to_buf)?;
+        self.batch.delete_range_cf(&db.cf(), &from_buf, &to_buf)?;
+        self.record(|| BatchOp::DeleteRange(db.cf.clone(), from_buf, to_buf));
         Ok(())
     }
 
@@ -1273,7 +1382,8 @@ impl DBBatch {
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
                 let v_buf = bcs::to_bytes(v.borrow())?;
-                self.batch.put_cf(&db.cf(), k_buf, v_buf);
+                self.batch.put_cf(&db.cf(), &k_buf, &v_buf);
+                self.record(|| BatchOp::Put(db.cf.clone(), k_buf, v_buf));
                 Ok(())
             })?;
         Ok(self)
@@ -1294,7 +1404,8 @@ impl DBBatch {
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
                 let v_buf = bcs::to_bytes(v.borrow())?;
-                self.batch.merge_cf(&db.cf(), k_buf, v_buf);
+                self.batch.merge_cf(&db.cf(), &k_buf, &v_buf);
+                self.record(|| BatchOp::Merge(db.cf.clone(), k_buf, v_buf));
                 Ok(())
             })?;
         Ok(self)
@@ -1313,7 +1424,8 @@ impl DBBatch {
             .into_iter()
             .try_for_each::<_, Result<_, TypedStoreError>>(|(k, v)| {
                 let k_buf = be_fix_int_ser(k.borrow())?;
-                self.batch.merge_cf(&db.cf(), k_buf, v);
+                self.batch.merge_cf(&db.cf(), &k_buf, &v);
+                self.record(|| BatchOp::Merge(db.cf.clone(), k_buf, v.as_ref().to_vec()));
                 Ok(())
             })?;
         Ok(self)
@@ -456,6 +456,59 @@ async fn test_insert_batch_across_cf(#[values(true, false)] is_transactional: bo
     }
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_append_batch(#[values(true, false)] is_transactional: bool) {
+    let rocks = open_rocksdb(temp_dir(), &["First_CF", "Second_CF"], is_transactional);
+    let db_cf_1 = DBMap::reopen(&rocks, Some("First_CF"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+    let db_cf_2 = DBMap::reopen(&rocks, Some("Second_CF"), &ReadWriteOptions::default())
+        .expect("Failed to open storage");
+    db_cf_1.insert(&0, &"0".to_string()).unwrap();
+
+    let mut batch = db_cf_1.batch();
+    batch
+        .insert_batch(&db_cf_1, (1..50).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert");
+    let mut other = db_cf_2.batch().appendable();
+    other
+        .insert_batch(&db_cf_2, (50..100).map(|i| (i, i.to_string())))
+        .expect("Failed to batch insert")
+        .delete_batch(&db_cf_1, [0])
+        .expect("Failed to batch delete");
+
+    // The batch bypasses the WAL, but the appended ones don't, so it no longer does either.
+    batch.disable_wal(true);
+    batch
+        .append([other, db_cf_1.batch().appendable()])
+        .expect("Failed to append batch");
+    assert!(!batch.disable_wal);
+    assert_eq!(batch.len(), 100);
+    batch.write().expect("Failed to execute batch");
+
+    assert_eq!(db_cf_1.get(&0).unwrap(), None);
+    for i in 1..50 {
+        assert_eq!(db_cf_1.get(&i).unwrap(), Some(i.to_string()));
+    }
+    for i in 50..100 {
+        assert_eq!(db_cf_2.get(&i).unwrap(), Some(i.to_string()));
+    }
+
+    // Only batches recording their operations can be appended.
+    let mut other = db_cf_1.batch();
+    other
+        .insert_batch(&db_cf_1, [(0, "0".to_string())])
+        .expect("Failed to batch insert");
+    assert!(db_cf_1.batch().append([other]).is_err());
+
+    // Batches can only be appended to batches of the same database.
+    let other_db = open_map::<_, i32, String>(temp_dir(), None, is_transactional);
+    assert!(matches!(
+        db_cf_1.batch().append([other_db.batch()]),
+        Err(TypedStoreError::CrossDBBatch)
+    ));
+}
+
 #[rstest]
 #[tokio::test]
 async fn test_insert_batch_across_different_db(#[values(true, false)] is_transactional: bool) {
@@ -7,9 +7,10 @@ use clap::Parser;
 use crossbeam::channel::{bounded, select};
 use lsp_server::{Connection, Message, Notification, Request, Response};
 use lsp_types::{
-    notification::Notification as _, request::Request as _, CompletionOptions, Diagnostic,
-    HoverProviderCapability, OneOf, SaveOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
-    TextDocumentSyncOptions, TypeDefinitionProviderCapability, WorkDoneProgressOptions,
+    notification::Notification as _, request::Request as _, CodeActionProviderCapability,
+    CompletionOptions, Diagnostic, HoverProviderCapability, OneOf, SaveOptions,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TypeDefinitionProviderCapability, WorkDoneProgressOptions,
 };
 use std::{
     collections::BTreeMap,
@@ -21,6 +22,7 @@ use std::{
 use move_analyzer::{
     completion::on_completion_request,
     context::Context,
+    diagnostics::on_code_action_request,
     symbols,
     vfs::{on_text_document_sync_notification, VirtualFileSystem},
 };
@@ -107,6 +109,8 @@ fn main() {
         )),
         references_provider: Some(OneOf::Left(symbols::DEFS_AND_REFS_SUPPORT)),
         document_symbol_provider: Some(OneOf::Left(true)),
+        // The server offers the fixes suggested by the compiler for its diagnostics.
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
         ..Default::default()
     })
     .expect("could not serialize server capabilities");
@@ -241,6 +245,9 @@ fn on_request(context: &Context, request: &Request) {
         lsp_types::request::DocumentSymbolRequest::METHOD => {
             symbols::on_document_symbol_request(context, request, &context.symbols.lock().unwrap());
         }
+        lsp_types::request::CodeActionRequest::METHOD => {
+            on_code_action_request(context, request);
+        }
         _ => eprintln!("handle request '{}' from client", request.method),
     }
 }
@@ -1,10 +1,15 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::utils::get_loc;
+use crate::{context::Context, utils::get_loc};
 use codespan_reporting::{diagnostic::Severity, files::SimpleFiles};
-use lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range};
+use lsp_server::Request;
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, Diagnostic,
+    DiagnosticRelatedInformation, DiagnosticSeverity, Location, Range, TextEdit, WorkspaceEdit,
+};
 use move_command_line_common::files::FileHash;
+use move_compiler::diagnostics::Fix;
 use move_ir_types::location::Loc;
 use move_symbol_pool::Symbol;
 use std::collections::{BTreeMap, HashMap};
@@ -18,13 +23,14 @@ pub fn lsp_diagnostics(
         (Loc, String),
         Vec<(Loc, String)>,
         Vec<String>,
+        Vec<Fix>,
     )>,
     files: &SimpleFiles<Symbol, String>,
     file_id_mapping: &HashMap<FileHash, usize>,
     file_name_mapping: &BTreeMap<FileHash, Symbol>,
 ) -> BTreeMap<Symbol, Vec<Diagnostic>> {
     let mut lsp_diagnostics = BTreeMap::new();
-    for (s, _, (loc, msg), labels, _) in diagnostics {
+    for (s, _, (loc, msg), labels, _, fixes) in diagnostics {
         let fpath = file_name_mapping.get(&loc.file_hash()).unwrap();
         if let Some(start) = get_loc(&loc.file_hash(), loc.start(), files, file_id_mapping) {
             if let Some(end) = get_loc(&loc.file_hash(), loc.end(), files, file_id_mapping) {
@@ -57,24 +63,92 @@ pub fn lsp_diagnostics(
                             .collect(),
                     )
                 };
+                let mut lsp_diagnostic = Diagnostic::new(
+                    range,
+                    Some(severity(*s)),
+                    None,
+                    None,
+                    msg.to_string(),
+                    related_info_opt,
+                    None,
+                );
+                let code_actions: Vec<CodeAction> = fixes
+                    .iter()
+                    .filter_map(|fix| code_action(fix, files, file_id_mapping, file_name_mapping))
+                    .collect();
+                if !code_actions.is_empty() {
+                    // the client gives the data back in the code action requests for this
+                    // diagnostic (see `on_code_action_request`)
+                    lsp_diagnostic.data = Some(serde_json::to_value(code_actions).unwrap());
+                }
                 lsp_diagnostics
                     .entry(*fpath)
                     .or_insert_with(Vec::new)
-                    .push(Diagnostic::new(
-                        range,
-                        Some(severity(*s)),
-                        None,
-                        None,
-                        msg.to_string(),
-                        related_info_opt,
-                        None,
-                    ));
+                    .push(lsp_diagnostic);
             }
         }
     }
     lsp_diagnostics
 }
 
+/// Converts a fix from the compiler to a code action, without the diagnostic it fixes.
+fn code_action(
+    fix: &Fix,
+    files: &SimpleFiles<Symbol, String>,
+    file_id_mapping: &HashMap<FileHash, usize>,
+    file_name_mapping: &BTreeMap<FileHash, Symbol>,
+) -> Option<CodeAction> {
+    let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+    for (loc, new_text) in &fix.edits {
+        let start = get_loc(&loc.file_hash(), loc.start(), files, file_id_mapping)?;
+        let end = get_loc(&loc.file_hash(), loc.end(), files, file_id_mapping)?;
+        let path = file_name_mapping.get(&loc.file_hash())?;
+        changes
+            .entry(Url::from_file_path(path.as_str()).unwrap())
+            .or_default()
+            .push(TextEdit::new(Range::new(start, end), new_text.clone()));
+    }
+    Some(CodeAction {
+        title: fix.message.clone(),
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit::new(changes)),
+        ..Default::default()
+    })
+}
+
+/// Handles a code action request by responding with the fixes of the diagnostics in the request's
+/// context, which are carried in the data of these diagnostics.
+pub fn on_code_action_request(context: &Context, request: &Request) {
+    let parameters = serde_json::from_value::<CodeActionParams>(request.params.clone())
+        .expect("could not deserialize code action request");
+
+    let mut actions = vec![];
+    for diagnostic in parameters.context.diagnostics {
+        let Some(data) = &diagnostic.data else {
+            continue;
+        };
+        let Ok(code_actions) = serde_json::from_value::<Vec<CodeAction>>(data.clone()) else {
+            continue;
+        };
+        for code_action in code_actions {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                diagnostics: Some(vec![diagnostic.clone()]),
+                ..code_action
+            }));
+        }
+    }
+
+    let result = serde_json::to_value(actions).expect("could not serialize code action response");
+    let response = lsp_server::Response::new_ok(request.id.clone(), result);
+    if let Err(err) = context
+        .connection
+        .sender
+        .send(lsp_server::Message::Response(response))
+    {
+        eprintln!("could not send code action response: {:?}", err);
+    }
+}
+
 /// Produces empty diagnostics in the format understood by the language server for all files that
 /// the language server is aware of.
 pub fn lsp_empty_diagnostics(
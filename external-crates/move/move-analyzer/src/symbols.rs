@@ -648,7 +648,9 @@ impl Symbolicator {
         let mut typed_ast = None;
         let mut diagnostics = None;
         build_plan.compile_with_driver(&mut std::io::sink(), |compiler| {
-            let (files, compilation_result) = compiler.run::<PASS_TYPING>()?;
+            // in IDE mode, the typed AST is produced even if some modules have errors, so that
+            // symbols are still available for the rest of the package
+            let (files, compilation_result) = compiler.set_ide_mode(true).run::<PASS_TYPING>()?;
             let (_, compiler) = match compilation_result {
                 Ok(v) => v,
                 Err(diags) => {
@@ -666,6 +668,7 @@ impl Symbolicator {
             let (units, diags) = match compilation_result {
                 Ok(v) => v,
                 Err(diags) => {
+                    // includes the errors in the typed AST, which can still be symbolicated
                     let failure = false;
                     diagnostics = Some((diags, failure));
                     eprintln!("bytecode compilation failed");
@@ -873,12 +876,12 @@ impl Symbolicator {
                 fun.signature
                     .parameters
                     .iter()
-                    .map(|(n, _)| n.value.name)
+                    .map(|(_, n, _)| n.value.name)
                     .collect(),
                 fun.signature
                     .parameters
                     .iter()
-                    .map(|(_, t)| t.clone())
+                    .map(|(_, _, t)| t.clone())
                     .collect(),
                 fun.signature.return_type.clone(),
                 fun.acquires
@@ -1085,7 +1088,7 @@ impl Symbolicator {
         // function body)
         let mut scope = OrdMap::new();
 
-        for (pname, ptype) in &fun.signature.parameters {
+        for (_, pname, ptype) in &fun.signature.parameters {
             self.add_type_id_use_def(ptype, references, use_defs);
 
             // add definition of the parameter
@@ -1403,11 +1406,11 @@ impl Symbolicator {
                 self.exp_symbols(t, scope, references, use_defs);
                 self.exp_symbols(f, scope, references, use_defs);
             }
-            E::While(cond, body) => {
+            E::While(_, cond, body) => {
                 self.exp_symbols(cond, scope, references, use_defs);
                 self.exp_symbols(body, scope, references, use_defs);
             }
-            E::Loop { has_break: _, body } => {
+            E::Loop { body, .. } => {
                 self.exp_symbols(body, scope, references, use_defs);
             }
             E::Block(sequence) => {
@@ -1437,6 +1440,9 @@ impl Symbolicator {
             E::Abort(exp) => {
                 self.exp_symbols(exp, scope, references, use_defs);
             }
+            E::Break(_, Some(exp)) => {
+                self.exp_symbols(exp, scope, references, use_defs);
+            }
             E::Dereference(exp) => {
                 self.exp_symbols(exp, scope, references, use_defs);
             }
@@ -3677,3 +3683,64 @@ fn symbols_test() {
         None,
     );
 }
+
+#[test]
+/// Tests if symbolication information is still computed for a package with errors in some of its
+/// modules.
+fn partial_symbols_test() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("tests/partial-symbols");
+
+    let (symbols_opt, diagnostics) = Symbolicator::get_symbols(path.as_path()).unwrap();
+    let symbols = symbols_opt.unwrap();
+
+    // the typing error in M2 and the parsing error in M3 are reported
+    for fname in ["M2.move", "M3.move"] {
+        let (_, diags) = diagnostics
+            .iter()
+            .find(|(f, _)| f.as_str().ends_with(fname))
+            .unwrap();
+        assert!(!diags.is_empty());
+    }
+
+    let mut fpath = path.clone();
+    fpath.push("sources/M1.move");
+    let cpath = dunce::canonicalize(&fpath).unwrap();
+
+    let mod_symbols = symbols.file_use_defs.get(&cpath).unwrap();
+
+    // function def name in a module without errors
+    assert_use_def(
+        mod_symbols,
+        &symbols.file_name_mapping,
+        0,
+        1,
+        15,
+        1,
+        15,
+        "M1.move",
+        "fun PartialSymbols::M1::valid(x: u64): u64",
+        None,
+    );
+
+    let mut fpath = path.clone();
+    fpath.push("sources/M2.move");
+    let cpath = dunce::canonicalize(&fpath).unwrap();
+
+    let mod_symbols = symbols.file_use_defs.get(&cpath).unwrap();
+
+    // function call with an invalid argument
+    assert_use_def(
+        mod_symbols,
+        &symbols.file_name_mapping,
+        0,
+        4,
+        12,
+        1,
+        15,
+        "M1.move",
+        "fun PartialSymbols::M1::valid(x: u64): u64",
+        None,
+    );
+}
@@ -0,0 +1,5 @@
+module PartialSymbols::M1 {
+    public fun valid(x: u64): u64 {
+        x + 1
+    }
+}
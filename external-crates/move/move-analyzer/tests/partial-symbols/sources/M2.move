@@ -0,0 +1,7 @@
+module PartialSymbols::M2 {
+    use PartialSymbols::M1;
+
+    public fun invalid(): u64 {
+        M1::valid(true)
+    }
+}
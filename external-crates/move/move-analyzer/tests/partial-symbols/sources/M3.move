@@ -0,0 +1,3 @@
+module PartialSymbols::M3 {
+    public fun broken( {
+}
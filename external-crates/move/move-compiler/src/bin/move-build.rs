@@ -8,6 +8,7 @@ use clap::*;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
     command_line::{self as cli},
+    diagnostics::explanations,
     shared::{self, Flags, NumericalAddress},
 };
 
@@ -61,6 +62,13 @@ pub struct Options {
     )]
     pub named_addresses: Vec<(String, NumericalAddress)>,
 
+    /// Print the explanation of a diagnostic code, e.g. 'E03003', instead of compiling
+    #[clap(
+        name = "DIAGNOSTIC_CODE",
+        long = cli::EXPLAIN,
+    )]
+    pub explain: Option<String>,
+
     #[clap(flatten)]
     pub flags: Flags,
 }
@@ -73,7 +81,11 @@ pub fn main() -> anyhow::Result<()> {
         emit_source_map,
         flags,
         named_addresses,
+        explain,
     } = Options::parse();
+    if let Some(code) = explain {
+        return explanations::print_explanation(&code);
+    }
 
     let interface_files_dir = format!("{}/generated_interface_files", out_dir);
     let named_addr_map = verify_and_create_named_address_mapping(named_addresses)?;
@@ -8,6 +8,7 @@ use clap::*;
 use move_command_line_common::files::verify_and_create_named_address_mapping;
 use move_compiler::{
     command_line::{self as cli},
+    diagnostics::explanations,
     shared::{self, Flags, NumericalAddress},
 };
 
@@ -53,6 +54,13 @@ pub struct Options {
     )]
     pub named_addresses: Vec<(String, NumericalAddress)>,
 
+    /// Print the explanation of a diagnostic code, e.g. 'E03003', instead of checking
+    #[clap(
+        name = "DIAGNOSTIC_CODE",
+        long = cli::EXPLAIN,
+    )]
+    pub explain: Option<String>,
+
     #[clap(flatten)]
     pub flags: Flags,
 }
@@ -64,7 +72,11 @@ pub fn main() -> anyhow::Result<()> {
         out_dir,
         flags,
         named_addresses,
+        explain,
     } = Options::parse();
+    if let Some(code) = explain {
+        return explanations::print_explanation(&code);
+    }
     let named_addr_map = verify_and_create_named_address_mapping(named_addresses)?;
     let _files = move_compiler::Compiler::from_files(source_files, dependencies, named_addr_map)
         .set_interface_files_dir_opt(out_dir)
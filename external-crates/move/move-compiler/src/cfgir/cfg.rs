@@ -304,7 +304,7 @@ fn unreachable_loc(sp!(_, cmd_): &Command) -> Option<Loc> {
         | C::IgnoreAndPop { exp: e, .. }
         | C::JumpIf { cond: e, .. } => unreachable_loc_exp(e),
         C::Jump { .. } => None,
-        C::Break | C::Continue => panic!("ICE break/continue not translated to jumps"),
+        C::Break(_) | C::Continue(_) => panic!("ICE break/continue not translated to jumps"),
     }
 }
 
@@ -449,7 +449,7 @@ fn maybe_unmark_infinite_loop_starts(
         | C::Assign(_, _)
         | C::Mutate(_, _)
         | C::IgnoreAndPop { .. } => (),
-        C::Break | C::Continue => panic!("ICE break/continue not translated to jumps"),
+        C::Break(_) | C::Continue(_) => panic!("ICE break/continue not translated to jumps"),
     }
 }
 
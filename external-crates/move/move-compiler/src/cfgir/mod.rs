@@ -22,7 +22,7 @@ use crate::{
 };
 use cfg::*;
 use move_ir_types::location::*;
-use optimize::optimize;
+use optimize::{aborting_operations, optimize};
 use std::collections::{BTreeMap, BTreeSet};
 
 pub struct CFGContext<'a> {
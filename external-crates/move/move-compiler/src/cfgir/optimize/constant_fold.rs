@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    cfgir::cfg::MutForwardCFG,
+    cfgir::{ast::BasicBlocks, cfg::MutForwardCFG},
     hlir::ast::{
         BaseType, BaseType_, Command, Command_, Exp, ExpListItem, FunctionSignature, SingleType,
         TypeName, TypeName_, UnannotatedExp_, Value, Value_, Var,
@@ -67,7 +67,7 @@ fn optimize_cmd(sp!(_, cmd_): &mut Command) -> Option<bool> {
         }
 
         C::Jump { .. } => false,
-        C::Break | C::Continue => panic!("ICE break/continue not translated to jumps"),
+        C::Break(_) | C::Continue(_) => panic!("ICE break/continue not translated to jumps"),
     })
 }
 
@@ -439,3 +439,111 @@ fn foldable_exps(e: &Exp) -> Option<Vec<Value>> {
         _ => Some(vec![sp(e.exp.loc, foldable_exp(e)?)]),
     }
 }
+
+//**************************************************************************************************
+// Aborting operations
+//**************************************************************************************************
+
+/// Returns the operations on values that could not be folded because they would abort at
+/// runtime, e.g. on overflow or division by zero, along with the reason they abort.
+/// Expected to be called after `optimize`, which folds every other operation on values.
+pub fn aborting_operations(blocks: &BasicBlocks) -> Vec<(Loc, String)> {
+    use Command_ as C;
+    let mut ops = vec![];
+    for cmd in blocks.values().flatten() {
+        match &cmd.value {
+            C::Assign(_, e) => exp_aborting_operations(e, &mut ops),
+            C::Return { exp: e, .. }
+            | C::Abort(e)
+            | C::JumpIf { cond: e, .. }
+            | C::IgnoreAndPop { exp: e, .. } => exp_aborting_operations(e, &mut ops),
+            C::Mutate(el, er) => {
+                exp_aborting_operations(er, &mut ops);
+                exp_aborting_operations(el, &mut ops);
+            }
+            C::Jump { .. } | C::Break(_) | C::Continue(_) => (),
+        }
+    }
+    ops
+}
+
+fn exp_aborting_operations(e: &Exp, ops: &mut Vec<(Loc, String)>) {
+    use UnannotatedExp_ as E;
+    match &e.exp.value {
+        E::Unit { .. }
+        | E::Value(_)
+        | E::Constant(_)
+        | E::UnresolvedError
+        | E::Spec(_, _)
+        | E::BorrowLocal(_, _)
+        | E::Move { .. }
+        | E::Copy { .. }
+        | E::Unreachable => (),
+
+        E::ModuleCall(mcall) => exp_aborting_operations(&mcall.arguments, ops),
+        E::Builtin(_, e)
+        | E::Freeze(e)
+        | E::Dereference(e)
+        | E::Borrow(_, e, _)
+        | E::UnaryExp(_, e)
+        | E::Vector(_, _, _, e) => exp_aborting_operations(e, ops),
+
+        E::Pack(_, _, fields) => fields
+            .iter()
+            .for_each(|(_, _, e)| exp_aborting_operations(e, ops)),
+
+        E::ExpList(es) => es.iter().for_each(|item| match item {
+            ExpListItem::Single(e, _) | ExpListItem::Splat(_, e, _) => {
+                exp_aborting_operations(e, ops)
+            }
+        }),
+
+        E::BinopExp(e1, op, e2) => {
+            exp_aborting_operations(e1, ops);
+            exp_aborting_operations(e2, ops);
+            if let (Some(v1), Some(v2)) = (foldable_exp(e1), foldable_exp(e2)) {
+                ops.push((e.exp.loc, binary_op_abort_reason(op, &v1, &v2)));
+            }
+        }
+
+        E::Cast(e1, bt) => {
+            exp_aborting_operations(e1, ops);
+            if foldable_exp(e1).is_some() {
+                let msg = format!("This cast overflows. The value does not fit in a '{}'", bt);
+                ops.push((e.exp.loc, msg));
+            }
+        }
+    }
+}
+
+fn binary_op_abort_reason(sp!(_, op_): &BinOp, v1: &Value_, v2: &Value_) -> String {
+    use BinOp_ as B;
+    let ty = match v1 {
+        Value_::U8(_) => BuiltinTypeName_::U8,
+        Value_::U16(_) => BuiltinTypeName_::U16,
+        Value_::U32(_) => BuiltinTypeName_::U32,
+        Value_::U64(_) => BuiltinTypeName_::U64,
+        Value_::U128(_) => BuiltinTypeName_::U128,
+        Value_::U256(_) => BuiltinTypeName_::U256,
+        _ => panic!(
+            "ICE unknown binary op. combo that does not fold: {:?} {} {:?}",
+            v1, op_, v2
+        ),
+    };
+    match op_ {
+        B::Add | B::Mul => format!(
+            "This '{}' overflows. The result does not fit in a '{}'",
+            op_, ty
+        ),
+        B::Sub => "This '-' underflows. The result is less than zero".to_owned(),
+        B::Div | B::Mod => format!("This '{}' divides by zero", op_),
+        B::Shl | B::Shr => format!(
+            "This '{}' shifts by at least the number of bits in a '{}'",
+            op_, ty
+        ),
+        _ => panic!(
+            "ICE unknown binary op. combo that does not fold: {:?} {} {:?}",
+            v1, op_, v2
+        ),
+    }
+}
@@ -8,6 +8,8 @@ mod simplify_jumps;
 
 use crate::{cfgir::cfg::MutForwardCFG, hlir::ast::*, shared::unique_map::UniqueMap};
 
+pub use constant_fold::aborting_operations;
+
 pub type Optimization =
     fn(&FunctionSignature, &UniqueMap<Var, SingleType>, &mut MutForwardCFG) -> bool;
 
@@ -9,17 +9,24 @@ use crate::{
         cfg::{ImmForwardCFG, MutForwardCFG},
     },
     diag,
-    diagnostics::Diagnostics,
-    expansion::ast::{AbilitySet, ModuleIdent},
+    diagnostics::{Diagnostic, Diagnostics},
+    expansion::ast::{AbilitySet, AttributeName_, ModuleIdent},
     hlir::ast::{self as H, Label, Value, Value_, Var},
+    naming::ast::BlockLabel,
     parser::ast::{ConstantName, FunctionName, StructName},
-    shared::{unique_map::UniqueMap, CompilationEnv},
+    shared::{
+        known_attributes::{KnownAttribute, TestingAttribute},
+        shortest_cycle,
+        unique_map::UniqueMap,
+        CompilationEnv,
+    },
     FullyCompiledProgram,
 };
 use cfgir::ast::LoopInfo;
 use move_core_types::{account_address::AccountAddress as MoveAddress, value::MoveValue};
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
+use petgraph::{algo::tarjan_scc as petgraph_scc, graphmap::DiGraphMap};
 use std::{
     collections::{BTreeMap, BTreeSet},
     mem,
@@ -33,8 +40,8 @@ struct Context<'env> {
     env: &'env mut CompilationEnv,
     struct_declared_abilities: UniqueMap<ModuleIdent, UniqueMap<StructName, AbilitySet>>,
     start: Option<Label>,
-    loop_begin: Option<Label>,
-    loop_end: Option<Label>,
+    // The begin and end of each loop around the block being translated
+    named_loops: BTreeMap<BlockLabel, (Label, Label)>,
     next_label: Option<Label>,
     label_count: usize,
     blocks: BasicBlocks,
@@ -68,8 +75,7 @@ impl<'env> Context<'env> {
             env,
             struct_declared_abilities,
             next_label: None,
-            loop_begin: None,
-            loop_end: None,
+            named_loops: BTreeMap::new(),
             start: None,
             label_count: 0,
             blocks: BasicBlocks::new(),
@@ -104,8 +110,7 @@ impl<'env> Context<'env> {
         let block_info = mem::take(&mut self.block_info);
         self.loop_bounds = BTreeMap::new();
         self.label_count = 0;
-        self.loop_begin = None;
-        self.loop_end = None;
+        self.named_loops = BTreeMap::new();
 
         // Blocks will eventually be ordered and outputted to bytecode the label. But labels are
         // initially created depth first
@@ -198,7 +203,7 @@ fn module(
     } = mdef;
 
     context.env.add_warning_filter_scope(warning_filter.clone());
-    let constants = hconstants.map(|name, c| constant(context, Some(module_ident), name, c));
+    let constants = constants(context, Some(module_ident), hconstants);
     let functions = hfunctions.map(|name, f| function(context, Some(module_ident), name, f));
     context.env.pop_warning_filter_scope();
     (
@@ -238,7 +243,7 @@ fn script(context: &mut Context, hscript: H::Script) -> G::Script {
         function: hfunction,
     } = hscript;
     context.env.add_warning_filter_scope(warning_filter.clone());
-    let constants = hconstants.map(|name, c| constant(context, None, name, c));
+    let constants = constants(context, None, hconstants);
     let function = function(context, None, function_name, hfunction);
     context.env.pop_warning_filter_scope();
     G::Script {
@@ -256,33 +261,233 @@ fn script(context: &mut Context, hscript: H::Script) -> G::Script {
 // Functions
 //**************************************************************************************************
 
-fn constant(
+fn constants(
+    context: &mut Context,
+    module: Option<ModuleIdent>,
+    mut hconstants: UniqueMap<ConstantName, H::Constant>,
+) -> UniqueMap<ConstantName, G::Constant> {
+    // Constants can use the other constants of their module, so they are folded in the order of
+    // their dependencies, after replacing each use by the value of the constant used.
+    let mut dependencies: BTreeMap<ConstantName, BTreeMap<ConstantName, Loc>> = BTreeMap::new();
+    for (name, c) in hconstants.key_cloned_iter_mut() {
+        let uses = dependencies.entry(name).or_default();
+        constant_uses_block(&mut c.value.1, &mut |e| {
+            if let H::UnannotatedExp_::Constant(used) = &e.exp.value {
+                uses.entry(*used).or_insert(e.exp.loc);
+            }
+        });
+    }
+
+    let mut graph = DiGraphMap::new();
+    for (user, uses) in &dependencies {
+        graph.add_node(user);
+        // constants of other modules were reported as visibility errors in typing
+        for used in uses.keys().filter(|used| dependencies.contains_key(used)) {
+            graph.add_edge(user, used, ());
+        }
+    }
+    // - get the strongly connected components, in the order of their dependencies
+    // - report those that are cycles, their constants cannot be folded
+    let mut order = vec![];
+    let mut cyclic = BTreeSet::new();
+    for scc in petgraph_scc(&graph) {
+        if scc.len() > 1 || graph.contains_edge(scc[0], scc[0]) {
+            let diag = cycle_error(&dependencies, &graph, scc[0]);
+            context.env.add_diag(diag);
+            cyclic.extend(scc.iter().map(|name| **name));
+        }
+        order.extend(scc.into_iter().copied());
+    }
+
+    let mut values: BTreeMap<ConstantName, Option<Value_>> = BTreeMap::new();
+    for name in order {
+        let c = hconstants.get_mut(&name).unwrap();
+        if cyclic.contains(&name) {
+            values.insert(name, None);
+            continue;
+        }
+        context
+            .env
+            .add_warning_filter_scope(c.warning_filter.clone());
+        let value = fold_constant(context, module, name, c, &values);
+        context.env.pop_warning_filter_scope();
+        values.insert(name, value);
+    }
+
+    hconstants.map(|name, c| {
+        let H::Constant {
+            warning_filter,
+            index,
+            attributes,
+            loc,
+            signature,
+            value: _,
+        } = c;
+        let value = values.remove(&name).flatten().map(move_value_from_value_);
+        G::Constant {
+            warning_filter,
+            index,
+            attributes,
+            loc,
+            signature,
+            value,
+        }
+    })
+}
+
+fn cycle_error(
+    dependencies: &BTreeMap<ConstantName, BTreeMap<ConstantName, Loc>>,
+    graph: &DiGraphMap<&ConstantName, ()>,
+    cycle_node: &ConstantName,
+) -> Diagnostic {
+    let cycle = shortest_cycle(graph, cycle_node);
+    let cycle_strings = cycle
+        .iter()
+        .map(|c| format!("'{}'", c))
+        .collect::<Vec<_>>()
+        .join(" uses ");
+    let len = cycle.len();
+    let (user, used) = if len == 1 {
+        (cycle[0], cycle[0])
+    } else {
+        (cycle[len - 2], cycle[len - 1])
+    };
+    let used_loc = dependencies[user][used];
+
+    let use_msg = format!("Invalid use of constant '{}' in constant '{}'.", used, user);
+    let cycle_msg = format!("Using this constant creates a cycle: {}", cycle_strings);
+    diag!(
+        TypeSafety::CyclicData,
+        (used_loc, use_msg),
+        (used_loc, cycle_msg)
+    )
+}
+
+/// Folds the constant, after replacing the uses of other constants by their folded values.
+/// Returns `None` if the constant, or one of the constants it uses, could not be folded
+fn fold_constant(
     context: &mut Context,
     module: Option<ModuleIdent>,
     name: ConstantName,
-    c: H::Constant,
-) -> G::Constant {
-    let H::Constant {
-        warning_filter,
-        index,
-        attributes,
-        loc,
-        signature,
-        value: (locals, block),
-    } = c;
+    c: &mut H::Constant,
+    values: &BTreeMap<ConstantName, Option<Value_>>,
+) -> Option<Value_> {
+    let (locals, mut block) = mem::replace(&mut c.value, (UniqueMap::new(), H::Block::new()));
+    let mut foldable = true;
+    constant_uses_block(&mut block, &mut |e| {
+        if let H::UnannotatedExp_::Constant(used) = &e.exp.value {
+            // The constants used were folded first, and an error was reported for those without a
+            // value
+            match values.get(used).cloned().flatten() {
+                Some(v_) => e.exp.value = H::UnannotatedExp_::Value(sp(e.exp.loc, v_)),
+                None => foldable = false,
+            }
+        }
+    });
+    if !foldable {
+        return None;
+    }
+    let final_value = constant_(
+        context,
+        module,
+        name,
+        c.loc,
+        c.signature.clone(),
+        locals,
+        block,
+    );
+    match final_value?.exp.value {
+        H::UnannotatedExp_::Value(sp!(_, v_)) => Some(v_),
+        _ => None,
+    }
+}
 
-    context.env.add_warning_filter_scope(warning_filter.clone());
-    let final_value = constant_(context, module, name, loc, signature.clone(), locals, block);
-    let value = final_value.and_then(move_value_from_exp);
+/// Calls `f` on every use of a constant in the block
+fn constant_uses_block<F: FnMut(&mut H::Exp)>(block: &mut H::Block, f: &mut F) {
+    use H::Statement_ as S;
+    for sp!(_, stmt_) in block {
+        match stmt_ {
+            S::Command(cmd) => constant_uses_command(cmd, f),
+            S::IfElse {
+                cond,
+                if_block,
+                else_block,
+            } => {
+                constant_uses_exp(cond, f);
+                constant_uses_block(if_block, f);
+                constant_uses_block(else_block, f);
+            }
+            S::While {
+                cond: (cond_block, cond),
+                block,
+                ..
+            } => {
+                constant_uses_block(cond_block, f);
+                constant_uses_exp(cond, f);
+                constant_uses_block(block, f);
+            }
+            S::Loop { block, .. } => constant_uses_block(block, f),
+        }
+    }
+}
 
-    context.env.pop_warning_filter_scope();
-    G::Constant {
-        warning_filter,
-        index,
-        attributes,
-        loc,
-        signature,
-        value,
+fn constant_uses_command<F: FnMut(&mut H::Exp)>(sp!(_, cmd_): &mut H::Command, f: &mut F) {
+    use H::Command_ as C;
+    match cmd_ {
+        C::Assign(_, e) => constant_uses_exp(e, f),
+        C::Abort(e) => constant_uses_exp(e, f),
+        C::Mutate(el, er) => {
+            constant_uses_exp(el, f);
+            constant_uses_exp(er, f);
+        }
+        C::Return { exp, .. } | C::IgnoreAndPop { exp, .. } | C::JumpIf { cond: exp, .. } => {
+            constant_uses_exp(exp, f)
+        }
+        C::Break(_) | C::Continue(_) | C::Jump { .. } => (),
+    }
+}
+
+fn constant_uses_exp<F: FnMut(&mut H::Exp)>(e: &mut H::Exp, f: &mut F) {
+    use H::UnannotatedExp_ as E;
+    if matches!(e.exp.value, E::Constant(_)) {
+        return f(e);
+    }
+    match &mut e.exp.value {
+        E::Constant(_)
+        | E::Unit { .. }
+        | E::Value(_)
+        | E::Move { .. }
+        | E::Copy { .. }
+        | E::BorrowLocal(_, _)
+        | E::Unreachable
+        | E::Spec(_, _)
+        | E::UnresolvedError => (),
+        E::ModuleCall(call) => constant_uses_exp(&mut call.arguments, f),
+        E::Builtin(_, e)
+        | E::Freeze(e)
+        | E::Vector(_, _, _, e)
+        | E::Dereference(e)
+        | E::UnaryExp(_, e)
+        | E::Borrow(_, e, _)
+        | E::Cast(e, _) => constant_uses_exp(e, f),
+        E::BinopExp(el, _, er) => {
+            constant_uses_exp(el, f);
+            constant_uses_exp(er, f);
+        }
+        E::Pack(_, _, fields) => {
+            for (_, _, e) in fields {
+                constant_uses_exp(e, f)
+            }
+        }
+        E::ExpList(items) => {
+            for item in items {
+                match item {
+                    H::ExpListItem::Single(e, _) | H::ExpListItem::Splat(_, e, _) => {
+                        constant_uses_exp(e, f)
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -334,6 +539,18 @@ fn constant_(
     );
     cfgir::optimize(&fake_signature, &locals, &mut cfg);
 
+    // Operations that abort can't be folded, report them precisely rather than the expression
+    // containing them.
+    let aborting_operations = cfgir::aborting_operations(&blocks);
+    if !aborting_operations.is_empty() {
+        for (loc, msg) in aborting_operations {
+            context
+                .env
+                .add_diag(diag!(BytecodeGeneration::UnfoldableConstant, (loc, msg)));
+        }
+        return None;
+    }
+
     if blocks.len() != 1 {
         context.env.add_diag(diag!(
             BytecodeGeneration::UnfoldableConstant,
@@ -376,14 +593,6 @@ fn check_constant_value(context: &mut Context, e: &H::Exp) {
     }
 }
 
-fn move_value_from_exp(e: H::Exp) -> Option<MoveValue> {
-    use H::UnannotatedExp_ as E;
-    match e.exp.value {
-        E::Value(v) => Some(move_value_from_value(v)),
-        _ => None,
-    }
-}
-
 pub(crate) fn move_value_from_value(sp!(_, v_): Value) -> MoveValue {
     move_value_from_value_(v_)
 }
@@ -425,7 +634,19 @@ fn function(
         body,
     } = f;
     context.env.add_warning_filter_scope(warning_filter.clone());
-    let body = function_body(context, module, name, &signature, &acquires, body);
+    // Tests expected to fail abort on purpose, so their aborting operations are not reported
+    let expects_failure = attributes.contains_key_(&AttributeName_::Known(
+        KnownAttribute::Testing(TestingAttribute::ExpectedFailure),
+    ));
+    let body = function_body(
+        context,
+        module,
+        name,
+        &signature,
+        &acquires,
+        expects_failure,
+        body,
+    );
     context.env.pop_warning_filter_scope();
     G::Function {
         warning_filter,
@@ -445,6 +666,7 @@ fn function_body(
     name: FunctionName,
     signature: &H::FunctionSignature,
     acquires: &BTreeMap<StructName, Loc>,
+    expects_failure: bool,
     sp!(loc, tb_): H::FunctionBody,
 ) -> G::FunctionBody {
     use G::FunctionBody_ as GB;
@@ -455,8 +677,7 @@ fn function_body(
     assert!(context.block_ordering.is_empty());
     assert!(context.block_info.is_empty());
     assert!(context.loop_bounds.is_empty());
-    assert!(context.loop_begin.is_none());
-    assert!(context.loop_end.is_none());
+    assert!(context.named_loops.is_empty());
     let b_ = match tb_ {
         HB::Native => GB::Native,
         HB::Defined { locals, body } => {
@@ -481,6 +702,13 @@ fn function_body(
             // do not optimize if there are errors, warnings are okay
             if !context.env.has_errors() {
                 cfgir::optimize(signature, &locals, &mut cfg);
+                if !expects_failure {
+                    for (loc, msg) in cfgir::aborting_operations(cfg.blocks()) {
+                        context
+                            .env
+                            .add_diag(diag!(BytecodeGeneration::ArithmeticAbort, (loc, msg)));
+                    }
+                }
             }
 
             let block_info = block_info
@@ -550,15 +778,14 @@ fn block_(context: &mut Context, cur_label: &mut Label, blocks: H::Block) -> Bas
     }
 
     macro_rules! loop_block {
-        (begin: $begin:expr, end: $end:expr, body: $body:expr, $block:expr) => {{
+        (name: $name:expr, begin: $begin:expr, end: $end:expr, body: $body:expr, $block:expr) => {{
+            let name = $name;
             let begin = $begin;
-            let old_begin = mem::replace(&mut context.loop_begin, Some(begin));
-            let old_end = mem::replace(&mut context.loop_end, Some($end));
+            context.named_loops.insert(name, (begin, $end));
             let old_next = mem::replace(&mut context.next_label, Some(begin));
             block(context, $body, $block);
             context.next_label = old_next;
-            context.loop_end = old_end;
-            context.loop_begin = old_begin;
+            context.named_loops.remove(&name);
         }};
     }
 
@@ -597,6 +824,7 @@ fn block_(context: &mut Context, cur_label: &mut Label, blocks: H::Block) -> Bas
                 context.next_label = old_next;
             }
             S::While {
+                name,
                 cond: (hcond_block, cond),
                 block: loop_block,
             } => {
@@ -636,11 +864,19 @@ fn block_(context: &mut Context, cur_label: &mut Label, blocks: H::Block) -> Bas
                 finish_block!(next_label: loop_end);
 
                 // Loop body
-                loop_block!(begin: loop_cond, end: loop_end, body: loop_body, loop_block)
+                loop_block!(
+                    name: name,
+                    begin: loop_cond,
+                    end: loop_end,
+                    body: loop_body,
+                    loop_block
+                )
             }
 
             S::Loop {
-                block: loop_block, ..
+                name,
+                block: loop_block,
+                ..
             } => {
                 let loop_body = context.new_label();
                 let loop_end = context.new_label();
@@ -666,7 +902,13 @@ fn block_(context: &mut Context, cur_label: &mut Label, blocks: H::Block) -> Bas
                 finish_block!(next_label: loop_end);
 
                 // Loop body
-                loop_block!(begin: loop_body, end: loop_end, body: loop_body, loop_block)
+                loop_block!(
+                    name: name,
+                    begin: loop_body,
+                    end: loop_end,
+                    body: loop_body,
+                    loop_block
+                )
             }
         }
     }
@@ -682,15 +924,15 @@ fn command(context: &Context, sp!(_, hc_): &mut H::Command) {
         | C::Abort(_)
         | C::Return { .. }
         | C::IgnoreAndPop { .. } => {}
-        C::Continue => {
+        C::Continue(name) => {
             *hc_ = C::Jump {
-                target: context.loop_begin.unwrap(),
+                target: context.named_loops[name].0,
                 from_user: true,
             }
         }
-        C::Break => {
+        C::Break(name) => {
             *hc_ = C::Jump {
-                target: context.loop_end.unwrap(),
+                target: context.named_loops[name].1,
                 from_user: true,
             }
         }
@@ -754,8 +996,14 @@ fn visit_function(
         acquires,
         body,
     } = fdef;
-    let G::FunctionBody_::Defined { locals, start, blocks, block_info } = &body.value else {
-        return
+    let G::FunctionBody_::Defined {
+        locals,
+        start,
+        blocks,
+        block_info,
+    } = &body.value
+    else {
+        return;
     };
     context.env.add_warning_filter_scope(warning_filter.clone());
     let (cfg, infinite_loop_starts) = ImmForwardCFG::new(*start, blocks, block_info.iter());
@@ -13,7 +13,9 @@ use crate::{
     },
     expansion,
     expansion::ast as E,
-    hlir, interface_generator, naming, parser,
+    hlir, interface_generator,
+    naming::{self, cache::NamingCache},
+    parser,
     parser::{comments::*, *},
     shared::{
         CompilationEnv, Flags, IndexedPackagePath, NamedAddressMap, NamedAddressMaps,
@@ -34,6 +36,7 @@ use std::{
     fs::File,
     io::{Read, Write},
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 use tempfile::NamedTempFile;
 
@@ -65,6 +68,7 @@ pub struct Compiler<'a> {
     known_warning_filters: BTreeSet<KnownFiltersInfo>,
     package_configs: BTreeMap<Symbol, PackageConfig>,
     default_config: Option<PackageConfig>,
+    naming_cache: Option<Arc<Mutex<NamingCache>>>,
 }
 
 pub struct SteppedCompiler<'a, const P: Pass> {
@@ -172,6 +176,7 @@ impl<'a> Compiler<'a> {
             known_warning_filters: BTreeSet::new(),
             package_configs,
             default_config: None,
+            naming_cache: None,
         })
     }
 
@@ -199,6 +204,12 @@ impl<'a> Compiler<'a> {
         self
     }
 
+    /// Keeps going after errors in some modules, see `Flags::ide_mode`
+    pub fn set_ide_mode(mut self, ide_mode: bool) -> Self {
+        self.flags = self.flags.set_ide_mode(ide_mode);
+        self
+    }
+
     pub fn set_interface_files_dir(mut self, dir: String) -> Self {
         assert!(self.interface_files_dir_opt.is_none());
         self.interface_files_dir_opt = Some(dir);
@@ -269,6 +280,14 @@ impl<'a> Compiler<'a> {
         self
     }
 
+    /// Reuses the results of naming for the modules that did not change since a previous
+    /// compilation with the same cache, and keeps the results of this one for the next
+    pub fn set_naming_cache(mut self, naming_cache: Arc<Mutex<NamingCache>>) -> Self {
+        assert!(self.naming_cache.is_none());
+        self.naming_cache = Some(naming_cache);
+        self
+    }
+
     pub fn run<const TARGET: Pass>(
         self,
     ) -> anyhow::Result<(
@@ -288,6 +307,7 @@ impl<'a> Compiler<'a> {
             known_warning_filters,
             package_configs,
             default_config,
+            naming_cache,
         } = self;
         generate_interface_files_for_deps(
             &mut deps,
@@ -299,6 +319,9 @@ impl<'a> Compiler<'a> {
         if let Some(filter) = warning_filter {
             compilation_env.add_warning_filter_scope(filter);
         }
+        if let Some(naming_cache) = naming_cache {
+            compilation_env.set_naming_cache(naming_cache);
+        }
         for KnownFiltersInfo {
             filter_attr_name,
             filters,
@@ -306,6 +329,7 @@ impl<'a> Compiler<'a> {
         {
             compilation_env.add_custom_known_filters(filters, filter_attr_name)?;
         }
+        compilation_env.resolve_lint_levels()?;
         let (source_text, pprog_and_comments_res) =
             parse_program(&mut compilation_env, maps, targets, deps)?;
         let res: Result<_, Diagnostics> = pprog_and_comments_res.and_then(|(pprog, comments)| {
@@ -322,8 +346,9 @@ impl<'a> Compiler<'a> {
     }
 
     pub fn check_and_report(self) -> anyhow::Result<FilesSourceText> {
+        let format = self.flags.diagnostics_format();
         let (files, res) = self.check()?;
-        unwrap_or_report_diagnostics(&files, res);
+        unwrap_or_report_diagnostics_with_format(format, &files, res);
         Ok(files)
     }
 
@@ -341,9 +366,10 @@ impl<'a> Compiler<'a> {
     }
 
     pub fn build_and_report(self) -> anyhow::Result<(FilesSourceText, Vec<AnnotatedCompiledUnit>)> {
+        let format = self.flags.diagnostics_format();
         let (files, units_res) = self.build()?;
-        let (units, warnings) = unwrap_or_report_diagnostics(&files, units_res);
-        report_warnings(&files, warnings);
+        let (units, warnings) = unwrap_or_report_diagnostics_with_format(format, &files, units_res);
+        report_warnings_with_format(format, &files, warnings);
         Ok((files, units))
     }
 }
@@ -457,17 +483,20 @@ macro_rules! ast_stepped_compilers {
                 }
 
                 pub fn check_and_report(self, files: &FilesSourceText)  {
+                    let format = self.compilation_env.flags().diagnostics_format();
                     let errors_result = self.check();
-                    unwrap_or_report_diagnostics(&files, errors_result);
+                    unwrap_or_report_diagnostics_with_format(format, &files, errors_result);
                 }
 
                 pub fn build_and_report(
                     self,
                     files: &FilesSourceText,
                 ) -> Vec<AnnotatedCompiledUnit> {
+                    let format = self.compilation_env.flags().diagnostics_format();
                     let units_result = self.build();
-                    let (units, warnings) = unwrap_or_report_diagnostics(&files, units_result);
-                    report_warnings(&files, warnings);
+                    let (units, warnings) =
+                        unwrap_or_report_diagnostics_with_format(format, &files, units_result);
+                    report_warnings_with_format(format, &files, warnings);
                     units
                 }
             }
@@ -612,8 +641,15 @@ pub fn sanity_check_compiled_units(
     compiled_units: &[AnnotatedCompiledUnit],
 ) {
     let ice_errors = compiled_unit::verify_units(compiled_units);
-    if !ice_errors.is_empty() {
-        report_diagnostics(&files, ice_errors)
+    report_verification_diagnostics(&files, ice_errors)
+}
+
+/// Reports the diagnostics of the bytecode verifier, failing only if any of them is an error
+fn report_verification_diagnostics(files: &FilesSourceText, diags: Diagnostics) {
+    match diags.max_severity() {
+        None => (),
+        Some(Severity::Warning) => report_warnings(files, diags),
+        Some(_) => report_diagnostics(files, diags),
     }
 }
 
@@ -676,9 +712,7 @@ pub fn output_compiled_units(
         emit_unit!(path, unit);
     }
 
-    if !ice_errors.is_empty() {
-        report_diagnostics(&files, ice_errors)
-    }
+    report_verification_diagnostics(&files, ice_errors);
     Ok(())
 }
 
@@ -874,6 +908,7 @@ fn run(
     match cur {
         PassResult::Parser(prog) => {
             let prog = parser::merge_spec_modules::program(compilation_env, prog);
+            let prog = parser::cfg_filter::program(compilation_env, prog);
             let prog = unit_test::filter_test_members::program(compilation_env, prog);
             let prog = verification::ast_filter::program(compilation_env, prog);
             let eprog = expansion::translate::program(compilation_env, pre_compiled_lib, prog);
@@ -887,7 +922,11 @@ fn run(
             )
         }
         PassResult::Expansion(eprog) => {
-            let nprog = naming::translate::program(compilation_env, pre_compiled_lib, eprog);
+            let (nprog, symbol_index) =
+                naming::translate::program(compilation_env, pre_compiled_lib, eprog);
+            if let Some(symbol_index) = symbol_index {
+                compilation_env.set_symbol_index(symbol_index);
+            }
             compilation_env.check_diags_at_or_above_severity(Severity::Bug)?;
             run(
                 compilation_env,
@@ -899,7 +938,13 @@ fn run(
         }
         PassResult::Naming(nprog) => {
             let tprog = typing::translate::program(compilation_env, pre_compiled_lib, nprog);
-            compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
+            if compilation_env.flags().ide_mode() {
+                // the typed program is kept even if it has errors, which are reported only when
+                // compiling past typing
+                compilation_env.check_diags_at_or_above_severity(Severity::Bug)?;
+            } else {
+                compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
+            }
             run(
                 compilation_env,
                 pre_compiled_lib,
@@ -909,6 +954,8 @@ fn run(
             )
         }
         PassResult::Typing(tprog) => {
+            // errors from typing onwards, if they were not reported yet
+            compilation_env.check_diags_at_or_above_severity(Severity::BlockingError)?;
             let hprog = hlir::translate::program(compilation_env, pre_compiled_lib, tprog);
             compilation_env.check_diags_at_or_above_severity(Severity::Bug)?;
             run(
@@ -28,6 +28,14 @@ pub const VERIFY_SHORT: char = 'v';
 
 pub const BYTECODE_VERSION: &str = "bytecode-version";
 
+pub const JSON_ERRORS: &str = "json-errors";
+
+pub const CROSS_ADDRESS_FRIENDS: &str = "cross-address-friends";
+
+pub const WARN_SHADOWING: &str = "warn-shadowing";
+
+pub const EXPLAIN: &str = "explain";
+
 pub const COLOR_MODE_ENV_VAR: &str = "COLOR_MODE";
 
 pub const MOVE_COMPILED_INTERFACES_DIR: &str = "mv_interfaces";
@@ -10,11 +10,11 @@ use crate::{
     parser::ast::{FunctionName, ModuleName},
     shared::{unique_map::UniqueMap, Name, NumericalAddress},
 };
-use move_binary_format::file_format as F;
+use move_binary_format::{access::ModuleAccess, file_format as F};
 use move_bytecode_source_map::source_map::SourceMap;
 use move_core_types::{
     account_address::AccountAddress, identifier::Identifier as MoveCoreIdentifier,
-    language_storage::ModuleId,
+    language_storage::ModuleId, vm_status::StatusCode,
 };
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
@@ -245,6 +245,35 @@ fn bytecode_verifier_mismatch_bug(
 fn verify_module(sm: &SourceMap, loc: Loc, cm: &F::CompiledModule) -> Diagnostics {
     match move_bytecode_verifier::verifier::verify_module_unmetered(cm) {
         Ok(_) => Diagnostics::new(),
+        // Friends out of the current address are only compiled when the `cross_address_friends`
+        // flag permits them. The bytecode verifier rejects them before checking anything else
+        Err(e)
+            if e.major_status()
+                == StatusCode::INVALID_FRIEND_DECL_WITH_MODULES_OUTSIDE_ACCOUNT_ADDRESS =>
+        {
+            let friends = cm
+                .friend_decls
+                .iter()
+                .filter(|h| cm.address_identifier_at(h.address) != cm.address())
+                .map(|h| {
+                    format!(
+                        "'{}::{}'",
+                        cm.address_identifier_at(h.address).to_hex_literal(),
+                        cm.identifier_at(h.name)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let msg = format!(
+                "The bytecode verifier rejects this module, as it declares {} out of its address \
+                 as friends. It can only be published where the verifier allows such friends",
+                friends
+            );
+            Diagnostics::from(vec![diag!(
+                BytecodeGeneration::UnverifiedCrossAddressFriend,
+                (loc, msg),
+            )])
+        }
         Err(e) => bytecode_verifier_mismatch_bug(
             sm,
             loc,
@@ -7,8 +7,10 @@
 //**************************************************************************************************
 
 use crate::shared::FILTER_ALL;
+use serde::Serialize;
 
-#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Severity {
     Warning = 0,
     NonblockingError = 1,
@@ -204,6 +206,12 @@ codes!(
         InvalidAttribute: { msg: "invalid attribute", severity: NonblockingError },
         InvalidVisibilityModifier:
             { msg: "invalid visibility modifier", severity: NonblockingError },
+        InvalidSyntaxMethod:
+            { msg: "invalid 'syntax' method declaration", severity: NonblockingError },
+        CrossAddressFriend:
+            { msg: "friend declared out of the current address", severity: Warning },
+        InvalidUseFun: { msg: "invalid 'use fun' declaration", severity: NonblockingError },
+        InvalidEnum: { msg: "invalid 'enum' declaration", severity: NonblockingError },
     ],
     // errors name resolution, mostly expansion/translate and naming/translate
     NameResolution: [
@@ -219,6 +227,17 @@ codes!(
         UnboundField: { msg: "unbound field", severity: BlockingError },
         ReservedName: { msg: "invalid use of reserved name", severity: BlockingError },
         UnboundMacro: { msg: "unbound macro", severity: BlockingError },
+        InvalidMacroCall: { msg: "invalid macro call", severity: BlockingError },
+        PositionalCallMismatch: { msg: "positional call mismatch", severity: BlockingError },
+        PositionalFieldsArity: {
+            msg: "incorrect number of positional fields",
+            severity: BlockingError,
+        },
+        UnboundLabel: { msg: "unbound label", severity: BlockingError },
+        ShadowedLabel: { msg: "shadowed label", severity: NonblockingError },
+        ShadowedLocal: { msg: "shadowed local", severity: Warning },
+        DeprecatedUsage: { msg: "use of deprecated item", severity: Warning },
+        UnboundVariant: { msg: "unbound variant", severity: BlockingError },
     ],
     // errors for typing rules. mostly typing/translate
     TypeSafety: [
@@ -250,6 +269,10 @@ codes!(
                 (NOTE: this may become an error in the future)",
             severity: Warning
         },
+        TestOnlyUse: { msg: "invalid use of test-only member", severity: NonblockingError },
+        UnsupportedMatch: { msg: "'match' not yet supported", severity: BlockingError },
+        UnsupportedIndex: { msg: "index syntax not yet supported", severity: BlockingError },
+        UnsupportedEnum: { msg: "'enum' not yet supported", severity: BlockingError },
     ],
     // errors for ability rules. mostly typing/translate
     AbilitySafety: [
@@ -276,6 +299,9 @@ codes!(
     ],
     BytecodeGeneration: [
         UnfoldableConstant: { msg: "cannot compute constant value", severity: NonblockingError },
+        ArithmeticAbort: { msg: "operation always aborts", severity: Warning },
+        UnverifiedCrossAddressFriend:
+            { msg: "module not verified due to friends out of its address", severity: Warning },
     ],
     // errors for any unused code or items
     UnusedItem: [
@@ -290,6 +316,7 @@ codes!(
         StructField: { msg: "unused struct field", severity: Warning },
         FunTypeParam: { msg: "unused function type parameter", severity: Warning },
         Constant: { msg: "unused constant", severity: Warning },
+        Friend: { msg: "unused friend declaration", severity: Warning },
     ],
     Attributes: [
         Duplicate: { msg: "invalid duplicate attribute", severity: NonblockingError },
@@ -299,7 +326,8 @@ codes!(
         InvalidTest: { msg: "unable to generate test", severity: NonblockingError },
         InvalidBytecodeInst:
             { msg: "unknown bytecode instruction function", severity: NonblockingError },
-        ValueWarning: { msg: "potential issue with attribute value", severity: Warning }
+        ValueWarning: { msg: "potential issue with attribute value", severity: Warning },
+        UnknownFeature: { msg: "unknown feature", severity: NonblockingError },
     ],
     Tests: [
         TestFailed: { msg: "test failure", severity: BlockingError },
@@ -313,7 +341,17 @@ codes!(
             msg: "feature is not supported in specified edition",
             severity: BlockingError,
         },
-    ]
+    ],
+    // errors for string literals, `"..."`. mostly parser/lexer and expansion/string
+    StringLiterals: [
+        Unterminated: { msg: "unterminated string literal", severity: NonblockingError },
+        InvalidEscape: { msg: "invalid escape sequence", severity: NonblockingError },
+        InvalidUtf8: { msg: "invalid UTF-8 string literal", severity: NonblockingError },
+        UnboundStringModule: {
+            msg: "string literal requires the 'std::string' module",
+            severity: BlockingError,
+        },
+    ],
 );
 
 //**************************************************************************************************
@@ -386,6 +424,10 @@ impl DiagnosticInfo {
         self.severity
     }
 
+    pub(crate) fn set_severity(&mut self, severity: Severity) {
+        self.severity = severity
+    }
+
     pub fn category(&self) -> u8 {
         self.category
     }
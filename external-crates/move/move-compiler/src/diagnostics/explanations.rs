@@ -0,0 +1,293 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Long-form explanations of diagnostic codes, as printed by `--explain <code>`
+
+use crate::diagnostics::codes::{
+    AbilitySafety, DiagnosticCode, DiagnosticInfo, MoveSafety, NameResolution, ReferenceSafety,
+    TypeSafety, UnusedItem,
+};
+
+/// A diagnostic code with its explanation
+pub struct Explanation {
+    /// The name of the code, e.g. `NameResolution::UnboundModuleMember`
+    pub name: &'static str,
+    pub info: DiagnosticInfo,
+    pub text: &'static str,
+}
+
+macro_rules! explanations {
+    ($($cat:ident::$code:ident => $text:expr),* $(,)?) => {
+        /// All explained codes, in the order of their categories and codes
+        pub fn explanations() -> Vec<Explanation> {
+            vec![$(
+                Explanation {
+                    name: concat!(stringify!($cat), "::", stringify!($code)),
+                    info: $cat::$code.into_info(),
+                    text: $text,
+                },
+            )*]
+        }
+    };
+}
+
+/// Finds the explanation of a code, given either as rendered in diagnostics (e.g. `E03003`) or by
+/// its name (e.g. `NameResolution::UnboundModuleMember`). Both are matched case-insensitively
+pub fn find_explanation(code: &str) -> Option<Explanation> {
+    let code = code.trim();
+    explanations().into_iter().find(|explanation| {
+        let (rendered, _) = explanation.info.clone().render();
+        rendered.eq_ignore_ascii_case(code) || explanation.name.eq_ignore_ascii_case(code)
+    })
+}
+
+/// Renders the explanation of a code, or returns `None` if the code is unknown or has no
+/// explanation
+pub fn explain(code: &str) -> Option<String> {
+    let Explanation { name, info, text } = find_explanation(code)?;
+    let (rendered, message) = info.render();
+    Some(format!(
+        "[{rendered}] {message} ({name})\n\n{}\n",
+        text.trim()
+    ))
+}
+
+/// Prints the explanation of a code to stdout, failing if there is none. Entry point for the
+/// `--explain` flag of the command line tools
+pub fn print_explanation(code: &str) -> anyhow::Result<()> {
+    match explain(code) {
+        Some(explanation) => {
+            print!("{explanation}");
+            Ok(())
+        }
+        None => anyhow::bail!("No explanation available for diagnostic code '{code}'"),
+    }
+}
+
+explanations!(
+    NameResolution::UnboundModule => r#"
+A module was used, but no module with that name could be found.
+
+Modules are referred to by their address and name, e.g. `0x2::coin`, or by an alias introduced
+with `use`. The module must be part of the sources or dependencies of the package.
+
+    module 0x42::m {
+        fun f() { 0x42::missing::g() } // error: there is no module '0x42::missing'
+    }
+
+Check the spelling of the module and its address, and that the package declaring it is a
+dependency.
+"#,
+    NameResolution::UnboundModuleMember => r#"
+A module was found, but it does not declare the function, struct, or constant being used.
+
+    module 0x42::a {
+        public fun f() {}
+    }
+    module 0x42::b {
+        fun g() { 0x42::a::h() } // error: '0x42::a' has no member 'h'
+    }
+
+Check the spelling of the member, and that it is declared in the module being referred to rather
+than in one of its neighbors.
+"#,
+    NameResolution::UnboundType => r#"
+A type was used, but no type with that name is in scope.
+
+Structs declared in another module must be imported with `use`, or referred to by their full
+path. Type parameters are only in scope inside the item declaring them.
+
+    module 0x42::m {
+        fun f(): Coin { abort 0 } // error: 'Coin' is not in scope
+    }
+
+Import the type, e.g. `use sui::coin::Coin;`, or declare the missing type parameter, e.g.
+`fun f<Coin>()`.
+"#,
+    NameResolution::UnboundVariable => r#"
+A local variable was used, but no local or parameter with that name is in scope.
+
+Locals are only in scope after their `let` and until the end of the enclosing block.
+
+    fun f(): u64 {
+        { let x = 0; };
+        x // error: 'x' is no longer in scope
+    }
+
+Declare the local in the block where it is used, or check its spelling.
+"#,
+    NameResolution::TooFewTypeArguments => r#"
+A type or function was given fewer type arguments than it declares type parameters.
+
+    struct Box<T, U> { t: T, u: U }
+    fun f(b: Box<u64>) {} // error: 'Box' expects 2 type arguments
+
+Provide one type argument for each type parameter of the declaration.
+"#,
+    TypeSafety::Visibility => r#"
+A function was called from a module that is not allowed to call it.
+
+Functions are private by default, and can only be called from their own module. `public(friend)`
+and `public(package)` functions can also be called from friend modules and modules of the same
+package, respectively. `public` functions can be called from anywhere.
+
+    module 0x42::a {
+        fun f() {}
+    }
+    module 0x42::b {
+        fun g() { 0x42::a::f() } // error: 'f' is private to '0x42::a'
+    }
+
+Make the function visible to the caller, or call a public function wrapping it.
+"#,
+    TypeSafety::SubtypeError => r#"
+A value was used where a value of a different type was expected.
+
+    fun f(x: u64) {}
+    fun g() { f(true) } // error: expected 'u64', found 'bool'
+
+Immutable references are also not accepted where mutable ones are expected, i.e. `&T` is not a
+subtype of `&mut T`.
+"#,
+    TypeSafety::UninferredType => r#"
+The type of an expression could not be inferred from its uses.
+
+    fun f() {
+        let v = vector[]; // error: the element type of 'v' is unknown
+    }
+
+Annotate the type, e.g. `let v: vector<u64> = vector[];` or `vector<u64>[]`.
+"#,
+    AbilitySafety::Constraint => r#"
+A type was used in a way that requires an ability it does not have.
+
+The abilities `copy`, `drop`, `store`, and `key` restrict what can be done with values of a type.
+They are required by type parameter constraints, e.g. `T: store`, and by operations, e.g. a value
+can only be discarded if its type has `drop`.
+
+    struct Token {}
+    struct Holder<T: store> { t: T }
+    fun f(h: Holder<Token>) {} // error: 'Token' does not have 'store'
+
+Add the ability to the declaration of the type, e.g. `struct Token has store {}`, if values of
+the type can safely be used that way.
+"#,
+    AbilitySafety::ImplicitlyCopyable => r#"
+A local was used after being moved, but its type does not have `copy`, so it cannot be copied
+implicitly.
+
+    struct Ticket {}
+    fun consume(t: Ticket) { let Ticket {} = t; }
+    fun f(t: Ticket) {
+        consume(t);
+        consume(t); // error: 't' was moved by the previous call
+    }
+
+Use each value without `copy` once, or add `copy` to the declaration of its type.
+"#,
+    MoveSafety::UnusedUndroppable => r#"
+A value whose type does not have `drop` was not consumed before going out of scope.
+
+Values without `drop` must be explicitly used: moved into a function, unpacked, stored, or
+returned.
+
+    struct Receipt {}
+    fun f() {
+        let r = Receipt {}; // error: 'r' is never consumed
+    }
+
+Consume the value, e.g. `let Receipt {} = r;`, or add `drop` to the declaration of its type.
+"#,
+    MoveSafety::UnassignedVariable => r#"
+A local was used, but it might not have been assigned a value on every path leading to its use.
+
+    fun f(b: bool): u64 {
+        let x;
+        if (b) x = 0;
+        x // error: 'x' is unassigned if 'b' is false
+    }
+
+Assign the local on every path, e.g. with an initial value.
+"#,
+    ReferenceSafety::Dangling => r#"
+A local was moved or modified while a reference to it was still in use, which could create a
+dangling reference.
+
+    fun f() {
+        let x = 0;
+        let r = &x;
+        move x; // error: 'x' is still borrowed by 'r'
+        *r;
+    }
+
+End all uses of the references before moving or modifying the local.
+"#,
+    ReferenceSafety::InvalidReturn => r#"
+A function returned a reference to one of its locals, which is destroyed when the function
+returns.
+
+    fun f(): &u64 {
+        let x = 0;
+        &x // error: 'x' is local to 'f'
+    }
+
+Return the value itself, or a reference derived from a reference parameter.
+"#,
+    UnusedItem::Variable => r#"
+A local or parameter is declared but never used.
+
+    fun f(x: u64) {} // warning: 'x' is unused
+
+Remove the binding, or prefix its name with an underscore, e.g. `_x`, if it is unused on
+purpose. The warning can also be suppressed with `#[allow(unused_variable)]`.
+"#,
+    UnusedItem::Function => r#"
+A private function is never called by the other functions of its module, so it is dead code.
+
+    module 0x42::m {
+        fun helper() {} // warning: 'helper' is never called
+    }
+
+Remove the function, make it visible if it is meant to be called from other modules, or suppress
+the warning with `#[allow(unused_function)]`.
+"#,
+    UnusedItem::Constant => r#"
+A constant is declared but never used in its module.
+
+    module 0x42::m {
+        const EUnused: u64 = 0; // warning: 'EUnused' is unused
+    }
+
+Remove the constant, or suppress the warning with `#[allow(unused_const)]`.
+"#,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_explanation() {
+        let by_name = find_explanation("NameResolution::UnboundModuleMember").unwrap();
+        let by_code = find_explanation("e03003").unwrap();
+        assert_eq!(by_name.name, by_code.name);
+        assert!(find_explanation("E99999").is_none());
+        assert!(explain("W09002")
+            .unwrap()
+            .starts_with("[W09002] unused variable"));
+    }
+
+    #[test]
+    fn test_explanations_are_unique() {
+        let explanations = explanations();
+        for (i, explanation) in explanations.iter().enumerate() {
+            assert!(
+                explanations[..i]
+                    .iter()
+                    .all(|other| other.info.id() != explanation.info.id()),
+                "duplicate explanation for {}",
+                explanation.name,
+            );
+        }
+    }
+}
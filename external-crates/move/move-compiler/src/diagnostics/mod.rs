@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod codes;
+pub mod explanations;
 
 use crate::{
     command_line::COLOR_MODE_ENV_VAR,
@@ -11,7 +12,7 @@ use crate::{
         WellKnownFilterName,
     },
     shared::{
-        ast_debug::AstDebug, FILTER_UNUSED_CONST, FILTER_UNUSED_FUNCTION,
+        ast_debug::AstDebug, FILTER_UNUSED_CONST, FILTER_UNUSED_FRIEND, FILTER_UNUSED_FUNCTION,
         FILTER_UNUSED_STRUCT_FIELD, FILTER_UNUSED_TYPE_PARAMETER,
     },
 };
@@ -27,8 +28,10 @@ use codespan_reporting::{
 use move_command_line_common::{env::read_env_var, files::FileHash};
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
+use serde::Serialize;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
+    io::Write,
     iter::FromIterator,
     ops::Range,
 };
@@ -52,6 +55,15 @@ pub struct Diagnostic {
     primary_label: (Loc, String),
     secondary_labels: Vec<(Loc, String)>,
     notes: Vec<String>,
+    fixes: Vec<Fix>,
+}
+
+/// A suggested fix for a diagnostic, which replaces the source text at the location of each edit
+/// with the text of that edit
+#[derive(PartialEq, Eq, Clone, Debug, Hash)]
+pub struct Fix {
+    pub message: String,
+    pub edits: Vec<(Loc, String)>,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
@@ -62,10 +74,60 @@ pub struct Diagnostics {
     severity_count: BTreeMap<Severity, usize>,
 }
 
+/// How diagnostics are reported
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum DiagnosticsFormat {
+    /// Rendered for humans, along with the source code they refer to
+    #[default]
+    Human,
+    /// One JSON object per line, for build systems and editors (see `JsonDiagnostic`)
+    Json,
+}
+
+/// A diagnostic, as reported in the JSON format
+#[derive(Serialize)]
+struct JsonDiagnostic {
+    code: String,
+    severity: Severity,
+    message: &'static str,
+    primary_label: JsonLabel,
+    secondary_labels: Vec<JsonLabel>,
+    notes: Vec<String>,
+    fixes: Vec<JsonFix>,
+}
+
+/// A label of a diagnostic, as reported in the JSON format. The location is given by the name of
+/// the file and the byte offsets of the start (inclusive) and end (exclusive) of the span
+#[derive(Serialize)]
+struct JsonLabel {
+    file: FileName,
+    start: usize,
+    end: usize,
+    message: String,
+}
+
+/// A fix of a diagnostic, as reported in the JSON format
+#[derive(Serialize)]
+struct JsonFix {
+    message: String,
+    edits: Vec<JsonEdit>,
+}
+
+/// An edit of a fix, as reported in the JSON format. The location is given as for `JsonLabel`
+#[derive(Serialize)]
+struct JsonEdit {
+    file: FileName,
+    start: usize,
+    end: usize,
+    new_text: String,
+}
+
 #[derive(PartialEq, Eq, Clone, Debug)]
 /// Used to filter out diagnostics, specifically used for warning suppression
 pub struct WarningFilters {
     filters: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
+    // warnings reported as errors, unless they are filtered
+    denied: BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
     for_dependency: bool, // if false, the filters are used for source code
 }
 
@@ -89,39 +151,75 @@ enum UnprefixedWarningFilters {
 //**************************************************************************************************
 
 pub fn report_diagnostics(files: &FilesSourceText, diags: Diagnostics) -> ! {
+    report_diagnostics_with_format(DiagnosticsFormat::Human, files, diags)
+}
+
+pub fn report_diagnostics_with_format(
+    format: DiagnosticsFormat,
+    files: &FilesSourceText,
+    diags: Diagnostics,
+) -> ! {
     let should_exit = true;
-    report_diagnostics_impl(files, diags, should_exit);
+    report_diagnostics_impl(format, files, diags, should_exit);
     std::process::exit(1)
 }
 
 pub fn report_warnings(files: &FilesSourceText, warnings: Diagnostics) {
+    report_warnings_with_format(DiagnosticsFormat::Human, files, warnings)
+}
+
+pub fn report_warnings_with_format(
+    format: DiagnosticsFormat,
+    files: &FilesSourceText,
+    warnings: Diagnostics,
+) {
     if warnings.is_empty() {
         return;
     }
     debug_assert!(warnings.max_severity().unwrap() == Severity::Warning);
-    report_diagnostics_impl(files, warnings, false)
+    report_diagnostics_impl(format, files, warnings, false)
 }
 
-fn report_diagnostics_impl(files: &FilesSourceText, diags: Diagnostics, should_exit: bool) {
-    let color_choice = match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
-        "NONE" => ColorChoice::Never,
-        "ANSI" => ColorChoice::AlwaysAnsi,
-        "ALWAYS" => ColorChoice::Always,
-        _ => ColorChoice::Auto,
-    };
-    let mut writer = StandardStream::stderr(color_choice);
-    output_diagnostics(&mut writer, files, diags);
+fn report_diagnostics_impl(
+    format: DiagnosticsFormat,
+    files: &FilesSourceText,
+    diags: Diagnostics,
+    should_exit: bool,
+) {
+    match format {
+        DiagnosticsFormat::Human => {
+            let color_choice = match read_env_var(COLOR_MODE_ENV_VAR).as_str() {
+                "NONE" => ColorChoice::Never,
+                "ANSI" => ColorChoice::AlwaysAnsi,
+                "ALWAYS" => ColorChoice::Always,
+                _ => ColorChoice::Auto,
+            };
+            let mut writer = StandardStream::stderr(color_choice);
+            output_diagnostics(&mut writer, files, diags);
+        }
+        DiagnosticsFormat::Json => {
+            output_json_diagnostics(&mut std::io::stderr().lock(), files, diags);
+        }
+    }
     if should_exit {
         std::process::exit(1);
     }
 }
 
 pub fn unwrap_or_report_diagnostics<T>(files: &FilesSourceText, res: Result<T, Diagnostics>) -> T {
+    unwrap_or_report_diagnostics_with_format(DiagnosticsFormat::Human, files, res)
+}
+
+pub fn unwrap_or_report_diagnostics_with_format<T>(
+    format: DiagnosticsFormat,
+    files: &FilesSourceText,
+    res: Result<T, Diagnostics>,
+) -> T {
     match res {
         Ok(t) => t,
         Err(diags) => {
             assert!(!diags.is_empty());
-            report_diagnostics(files, diags)
+            report_diagnostics_with_format(format, files, diags)
         }
     }
 }
@@ -138,6 +236,12 @@ pub fn report_diagnostics_to_color_buffer(files: &FilesSourceText, diags: Diagno
     writer.into_inner()
 }
 
+pub fn report_diagnostics_to_json_buffer(files: &FilesSourceText, diags: Diagnostics) -> Vec<u8> {
+    let mut buffer = vec![];
+    output_json_diagnostics(&mut buffer, files, diags);
+    buffer
+}
+
 fn output_diagnostics<W: WriteColor>(
     writer: &mut W,
     sources: &FilesSourceText,
@@ -156,21 +260,83 @@ fn render_diagnostics(
     writer: &mut dyn WriteColor,
     files: &SimpleFiles<Symbol, &str>,
     file_mapping: &FileMapping,
-    mut diags: Diagnostics,
+    diags: Diagnostics,
 ) {
+    for diag in sorted_unique_diagnostics(diags) {
+        let rendered = render_diagnostic(file_mapping, diag);
+        emit(writer, &Config::default(), files, &rendered).unwrap()
+    }
+}
+
+/// The diagnostics ordered by the location of their primary label, without duplicates
+fn sorted_unique_diagnostics(mut diags: Diagnostics) -> Vec<Diagnostic> {
     diags.diagnostics.sort_by(|e1, e2| {
         let loc1: &Loc = &e1.primary_label.0;
         let loc2: &Loc = &e2.primary_label.0;
         loc1.cmp(loc2)
     });
     let mut seen: HashSet<Diagnostic> = HashSet::new();
-    for diag in diags.diagnostics {
-        if seen.contains(&diag) {
-            continue;
+    diags
+        .diagnostics
+        .into_iter()
+        .filter(|diag| seen.insert(diag.clone()))
+        .collect()
+}
+
+fn output_json_diagnostics<W: Write>(writer: &mut W, files: &FilesSourceText, diags: Diagnostics) {
+    for diag in sorted_unique_diagnostics(diags) {
+        let json = json_diagnostic(files, diag);
+        serde_json::to_writer(&mut *writer, &json).unwrap();
+        writeln!(writer).unwrap();
+    }
+}
+
+fn json_diagnostic(files: &FilesSourceText, diag: Diagnostic) -> JsonDiagnostic {
+    let file_range = |loc: Loc| -> (FileName, Range<usize>) {
+        let (file, _) = files.get(&loc.file_hash()).unwrap();
+        (*file, loc.usize_range())
+    };
+    let mk_lbl = |(loc, message): (Loc, String)| -> JsonLabel {
+        let (file, Range { start, end }) = file_range(loc);
+        JsonLabel {
+            file,
+            start,
+            end,
+            message,
         }
-        seen.insert(diag.clone());
-        let rendered = render_diagnostic(file_mapping, diag);
-        emit(writer, &Config::default(), files, &rendered).unwrap()
+    };
+    let mk_fix = |Fix { message, edits }: Fix| -> JsonFix {
+        let edits = edits
+            .into_iter()
+            .map(|(loc, new_text)| {
+                let (file, Range { start, end }) = file_range(loc);
+                JsonEdit {
+                    file,
+                    start,
+                    end,
+                    new_text,
+                }
+            })
+            .collect();
+        JsonFix { message, edits }
+    };
+    let Diagnostic {
+        info,
+        primary_label,
+        secondary_labels,
+        notes,
+        fixes,
+    } = diag;
+    let severity = info.severity();
+    let (code, message) = info.render();
+    JsonDiagnostic {
+        code,
+        severity,
+        message,
+        primary_label: mk_lbl(primary_label),
+        secondary_labels: secondary_labels.into_iter().map(mk_lbl).collect(),
+        notes,
+        fixes: fixes.into_iter().map(mk_fix).collect(),
     }
 }
 
@@ -195,6 +361,8 @@ fn render_diagnostic(
         primary_label,
         secondary_labels,
         notes,
+        // fixes are suggested in the messages of the diagnostics that have them
+        fixes: _,
     } = diag;
     let mut diag = csr::diagnostic::Diagnostic::new(info.severity().into_codespan_severity());
     let (code, message) = info.render();
@@ -240,6 +408,11 @@ impl Diagnostics {
         self.diagnostics.len()
     }
 
+    /// The number of diagnostics filtered in source code
+    pub fn source_filtered_len(&self) -> usize {
+        self.filtered_source_diagnostics.len()
+    }
+
     pub fn add(&mut self, diag: Diagnostic) {
         *self.severity_count.entry(diag.info.severity()).or_insert(0) += 1;
         self.diagnostics.push(diag)
@@ -279,6 +452,7 @@ impl Diagnostics {
         (Loc, String),
         Vec<(Loc, String)>,
         Vec<String>,
+        Vec<Fix>,
     )> {
         let mut v = vec![];
         for diag in self.into_vec() {
@@ -287,6 +461,7 @@ impl Diagnostics {
                 primary_label,
                 secondary_labels,
                 notes,
+                fixes,
             } = diag;
             let csr_diag = (
                 info.severity().into_codespan_severity(),
@@ -294,6 +469,7 @@ impl Diagnostics {
                 primary_label,
                 secondary_labels,
                 notes,
+                fixes,
             );
             v.push(csr_diag)
         }
@@ -337,6 +513,7 @@ impl Diagnostic {
                 .map(|(loc, msg)| (loc, msg.to_string()))
                 .collect(),
             notes: notes.into_iter().map(|msg| msg.to_string()).collect(),
+            fixes: vec![],
         }
     }
 
@@ -375,6 +552,14 @@ impl Diagnostic {
         self.notes.push(msg.to_string())
     }
 
+    pub fn add_fix(&mut self, fix: Fix) {
+        self.fixes.push(fix)
+    }
+
+    pub fn set_severity(&mut self, severity: Severity) {
+        self.info.set_severity(severity)
+    }
+
     pub fn info(&self) -> &DiagnosticInfo {
         &self.info
     }
@@ -408,6 +593,7 @@ impl WarningFilters {
     pub fn new_for_source() -> Self {
         Self {
             filters: BTreeMap::new(),
+            denied: BTreeMap::new(),
             for_dependency: false,
         }
     }
@@ -415,6 +601,7 @@ impl WarningFilters {
     pub fn new_for_dependency() -> Self {
         Self {
             filters: BTreeMap::new(),
+            denied: BTreeMap::new(),
             for_dependency: true,
         }
     }
@@ -430,6 +617,14 @@ impl WarningFilters {
             .is_some_and(|filters| filters.is_filtered_by_info(info))
     }
 
+    /// Whether the diagnostic is a warning that should be reported as an error
+    pub fn is_denied(&self, diag: &Diagnostic) -> bool {
+        let prefix = diag.info.external_prefix();
+        self.denied
+            .get(&prefix)
+            .is_some_and(|denied| denied.is_filtered_by_info(&diag.info))
+    }
+
     pub fn union(&mut self, other: &Self) {
         for (prefix, filters) in &other.filters {
             self.filters
@@ -437,6 +632,12 @@ impl WarningFilters {
                 .or_insert_with(UnprefixedWarningFilters::new)
                 .union(filters);
         }
+        for (prefix, denied) in &other.denied {
+            self.denied
+                .entry(*prefix)
+                .or_insert_with(UnprefixedWarningFilters::new)
+                .union(denied);
+        }
         // if there is a dependency code filter on the stack, it means we are filtering dependent
         // code and this information must be preserved when stacking up additional filters (which
         // involves union of the current filter with the new one)
@@ -444,9 +645,21 @@ impl WarningFilters {
     }
 
     pub fn add(&mut self, filter: WarningFilter) {
+        Self::add_to(&mut self.filters, filter)
+    }
+
+    /// Report the warnings matched by the filter as errors, unless they are filtered
+    pub fn add_denied(&mut self, filter: WarningFilter) {
+        Self::add_to(&mut self.denied, filter)
+    }
+
+    fn add_to(
+        filters: &mut BTreeMap<ExternalPrefix, UnprefixedWarningFilters>,
+        filter: WarningFilter,
+    ) {
         let (prefix, category, code, name) = match filter {
             WarningFilter::All(prefix) => {
-                self.filters.insert(prefix, UnprefixedWarningFilters::All);
+                filters.insert(prefix, UnprefixedWarningFilters::All);
                 return;
             }
             WarningFilter::Category {
@@ -461,7 +674,7 @@ impl WarningFilters {
                 name,
             } => (prefix, category, Some(code), name),
         };
-        self.filters
+        filters
             .entry(prefix)
             .or_insert(UnprefixedWarningFilters::Empty)
             .add(category, code, name)
@@ -473,6 +686,7 @@ impl WarningFilters {
                 None,
                 UnprefixedWarningFilters::unused_warnings_filter_for_test(),
             )]),
+            denied: BTreeMap::new(),
             for_dependency: false,
         }
     }
@@ -561,6 +775,7 @@ impl UnprefixedWarningFilters {
         let unused_field_info = UnusedItem::StructField.into_info();
         let unused_fn_tparam_info = UnusedItem::FunTypeParam.into_info();
         let unused_const_info = UnusedItem::Constant.into_info();
+        let unused_friend_info = UnusedItem::Friend.into_info();
         let filtered_codes = BTreeMap::from([
             (
                 (unused_fun_info.category(), unused_fun_info.code()),
@@ -581,6 +796,10 @@ impl UnprefixedWarningFilters {
                 (unused_const_info.category(), unused_const_info.code()),
                 Some(FILTER_UNUSED_CONST),
             ),
+            (
+                (unused_friend_info.category(), unused_friend_info.code()),
+                Some(FILTER_UNUSED_FRIEND),
+            ),
         ]);
         Self::Specified {
             categories: BTreeMap::new(),
@@ -657,8 +876,87 @@ impl AstDebug for WarningFilters {
     }
 }
 
+impl Fix {
+    pub fn new(
+        message: impl ToString,
+        edits: impl IntoIterator<Item = (Loc, impl ToString)>,
+    ) -> Self {
+        Fix {
+            message: message.to_string(),
+            edits: edits
+                .into_iter()
+                .map(|(loc, text)| (loc, text.to_string()))
+                .collect(),
+        }
+    }
+}
+
 impl<C: DiagnosticCode> From<C> for DiagnosticInfo {
     fn from(value: C) -> Self {
         value.into_info()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_diagnostics() {
+        let source = "module 0x42::m { fun f(): T { abort 0 } }";
+        let file_hash = FileHash::new(source);
+        let files = FilesSourceText::from([(file_hash, ("m.move".into(), source.to_string()))]);
+        let unbound = Loc::new(file_hash, 26, 27);
+        let module = Loc::new(file_hash, 0, 6);
+
+        let mut warning = diag!(UnusedItem::Alias, (module, "unused"));
+        warning.add_note("a note");
+        warning.add_fix(Fix::new("Remove it", [(module, "")]));
+        let error = diag!(
+            NameResolution::UnboundType,
+            (unbound, "Unbound type 'T' in current scope"),
+            (module, "In this module"),
+        );
+        // reported in order of their primary labels, without duplicates
+        let diags: Diagnostics = vec![error.clone(), warning, error].into();
+
+        let json = String::from_utf8(report_diagnostics_to_json_buffer(&files, diags)).unwrap();
+        let lines: Vec<serde_json::Value> = json
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                serde_json::json!({
+                    "code": "W09001",
+                    "severity": "warning",
+                    "message": "unused alias",
+                    "primary_label": {"file": "m.move", "start": 0, "end": 6, "message": "unused"},
+                    "secondary_labels": [],
+                    "notes": ["a note"],
+                    "fixes": [{
+                        "message": "Remove it",
+                        "edits": [{"file": "m.move", "start": 0, "end": 6, "new_text": ""}],
+                    }],
+                }),
+                serde_json::json!({
+                    "code": "E03004",
+                    "severity": "blocking_error",
+                    "message": "unbound type",
+                    "primary_label": {
+                        "file": "m.move",
+                        "start": 26,
+                        "end": 27,
+                        "message": "Unbound type 'T' in current scope",
+                    },
+                    "secondary_labels": [
+                        {"file": "m.move", "start": 0, "end": 6, "message": "In this module"},
+                    ],
+                    "notes": [],
+                    "fixes": [],
+                }),
+            ]
+        );
+    }
+}
@@ -30,6 +30,16 @@ pub enum FeatureGate {
     PublicPackage,
     PostFixAbilities,
     StructTypeVisibility,
+    StringLiterals,
+    DotCall,
+    MacroFuns,
+    PositionalFields,
+    BlockLabels,
+    Match,
+    SyntaxMethods,
+    ModuleTypeAliases,
+    LetMut,
+    Enums,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, PartialOrd, Ord, Default)]
@@ -51,25 +61,26 @@ pub fn check_feature(
 ) -> bool {
     let supports_feature = edition.supports(feature);
     if !supports_feature {
-        let valid_editions = valid_editions_for_feature(feature)
+        // Features are never removed by later editions, so the first edition that supports a
+        // feature is the one to upgrade to.
+        let required = valid_editions_for_feature(feature)
             .into_iter()
-            .map(|e| e.to_string())
-            .collect::<Vec<_>>()
-            .join(", ");
+            .next()
+            .expect("Every feature is supported by some edition");
         let mut diag = diag!(
             Editions::FeatureTooNew,
             (
                 loc,
                 format!(
-                    "{feature} not supported by current edition '{edition}', \
-                    only '{valid_editions}' support this feature"
+                    "Using {feature} requires edition '{required}' or later, \
+                    but the current edition is '{edition}'"
                 )
             )
         );
-        diag.add_note(
-            "You can update the edition in the 'Move.toml', \
-            or via command line flag if invoking the compiler directly.",
-        );
+        diag.add_note(format!(
+            "To enable it, set `edition = \"{required}\"` in the [package] section of the \
+            'Move.toml', or via command line flag if invoking the compiler directly."
+        ));
         env.add_diag(diag);
     }
     supports_feature
@@ -94,6 +105,16 @@ const E2024_ALPHA_FEATURES: &[FeatureGate] = &[
     FeatureGate::PublicPackage,
     FeatureGate::PostFixAbilities,
     FeatureGate::StructTypeVisibility,
+    FeatureGate::StringLiterals,
+    FeatureGate::DotCall,
+    FeatureGate::MacroFuns,
+    FeatureGate::PositionalFields,
+    FeatureGate::BlockLabels,
+    FeatureGate::Match,
+    FeatureGate::SyntaxMethods,
+    FeatureGate::ModuleTypeAliases,
+    FeatureGate::LetMut,
+    FeatureGate::Enums,
 ];
 
 impl Edition {
@@ -264,8 +285,22 @@ impl Serialize for Flavor {
 }
 
 impl Display for FeatureGate {
-    fn fmt(&self, _: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Ok(())
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeatureGate::PublicPackage => write!(f, "'public(package)' visibility"),
+            FeatureGate::PostFixAbilities => write!(f, "postfix ability declarations"),
+            FeatureGate::StructTypeVisibility => write!(f, "struct visibility modifiers"),
+            FeatureGate::StringLiterals => write!(f, "string literals"),
+            FeatureGate::DotCall => write!(f, "method call syntax"),
+            FeatureGate::MacroFuns => write!(f, "macro functions"),
+            FeatureGate::PositionalFields => write!(f, "positional fields"),
+            FeatureGate::BlockLabels => write!(f, "loop labels and 'break' with a value"),
+            FeatureGate::Match => write!(f, "'match' expressions"),
+            FeatureGate::SyntaxMethods => write!(f, "'syntax' methods"),
+            FeatureGate::ModuleTypeAliases => write!(f, "module-level type aliases"),
+            FeatureGate::LetMut => write!(f, "'mut' modifiers on local variables"),
+            FeatureGate::Enums => write!(f, "'enum' declarations"),
+        }
     }
 }
 
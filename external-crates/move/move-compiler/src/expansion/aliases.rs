@@ -130,6 +130,14 @@ impl AliasMapBuilder {
             .unwrap();
         result
     }
+
+    /// The member aliases added by `add_member_alias`, i.e. the ones declared by a `use`
+    pub fn explicit_member_aliases(&self) -> impl Iterator<Item = (Name, &(ModuleIdent, Name))> {
+        self.members
+            .key_cloned_iter()
+            .filter(|(_, (_, is_implicit))| !is_implicit)
+            .map(|(alias, (ident_member, _))| (alias, ident_member))
+    }
 }
 
 impl AliasMap {
@@ -180,6 +188,16 @@ impl AliasMap {
         }
     }
 
+    /// The module aliased by `n`, without marking the alias as used
+    pub fn module_alias_peek(&self, n: &Name) -> Option<&ModuleIdent> {
+        self.modules.get(n).map(|(_, ident)| ident)
+    }
+
+    /// The member aliased by `n`, without marking the alias as used
+    pub fn member_alias_peek(&self, n: &Name) -> Option<&(ModuleIdent, Name)> {
+        self.members.get(n).map(|(_, member)| member)
+    }
+
     pub fn member_alias_get(&mut self, n: &Name) -> Option<(ModuleIdent, Name)> {
         match self.members.get_mut(n) {
             None => None,
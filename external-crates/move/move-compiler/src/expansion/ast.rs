@@ -5,8 +5,9 @@
 use crate::{
     diagnostics::WarningFilters,
     parser::ast::{
-        self as P, Ability, Ability_, BinOp, ConstantName, Field, FunctionName, ModuleName,
-        QuantKind, SpecApplyPattern, StructName, UnaryOp, Var, ENTRY_MODIFIER,
+        self as P, Ability, Ability_, BinOp, BlockLabel, ConstantName, DocComment, EnumName, Field,
+        FunctionName, ModuleName, Mutability, QuantKind, SpecApplyPattern, StructName, UnaryOp,
+        Var, VariantName, ENTRY_MODIFIER, MACRO_MODIFIER,
     },
     shared::{
         ast_debug::*, known_attributes::KnownAttribute, unique_map::UniqueMap,
@@ -122,13 +123,35 @@ pub struct ModuleDefinition {
     // package name metadata from compiler arguments, not used for any language rules
     pub package_name: Option<Symbol>,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub loc: Loc,
     pub is_source_module: bool,
     pub friends: UniqueMap<ModuleIdent, Friend>,
     pub structs: UniqueMap<StructName, StructDefinition>,
+    pub enums: UniqueMap<EnumName, EnumDefinition>,
     pub functions: UniqueMap<FunctionName, Function>,
     pub constants: UniqueMap<ConstantName, Constant>,
     pub specs: Vec<SpecBlock>,
+    /// The struct aliases declared by a `use` at the top level of the module, mapped to the
+    /// struct they alias. Their uses in types (outside of specs) are resolved during naming
+    pub type_aliases: UniqueMap<Name, (ModuleIdent, Name)>,
+    /// The method aliases declared by a `use fun` at the top level of the module, in declaration
+    /// order. Their function and type are resolved and checked against each other during naming
+    pub use_funs: Vec<UseFun>,
+}
+
+//**************************************************************************************************
+// Use Fun
+//**************************************************************************************************
+
+/// `use fun function as ty.method`
+#[derive(Debug, Clone)]
+pub struct UseFun {
+    pub attributes: Attributes,
+    pub loc: Loc,
+    pub function: ModuleAccess,
+    pub ty: ModuleAccess,
+    pub method: Name,
 }
 
 //**************************************************************************************************
@@ -160,6 +183,7 @@ pub struct StructDefinition {
     // index in the original order as defined in the source file
     pub index: usize,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub loc: Loc,
     pub abilities: AbilitySet,
     pub type_parameters: Vec<StructTypeParameter>,
@@ -169,9 +193,42 @@ pub struct StructDefinition {
 #[derive(Debug, Clone, PartialEq)]
 pub enum StructFields {
     Defined(Fields<Type>),
+    Positional(Vec<Type>),
     Native(Loc),
 }
 
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDefinition {
+    pub warning_filter: WarningFilters,
+    // index in the original order as defined in the source file
+    pub index: usize,
+    pub attributes: Attributes,
+    pub doc: DocComment,
+    pub loc: Loc,
+    pub abilities: AbilitySet,
+    pub type_parameters: Vec<StructTypeParameter>,
+    pub variants: UniqueMap<VariantName, VariantDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantDefinition {
+    // index in the original order as defined in the source file
+    pub index: usize,
+    pub loc: Loc,
+    pub fields: VariantFields,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantFields {
+    Defined(Fields<Type>),
+    Positional(Vec<Type>),
+    Empty,
+}
+
 //**************************************************************************************************
 // Functions
 //**************************************************************************************************
@@ -187,7 +244,7 @@ pub enum Visibility {
 #[derive(PartialEq, Clone, Debug)]
 pub struct FunctionSignature {
     pub type_parameters: Vec<(Name, AbilitySet)>,
-    pub parameters: Vec<(Var, Type)>,
+    pub parameters: Vec<(Mutability, Var, Type)>,
     pub return_type: Type,
 }
 
@@ -207,9 +264,11 @@ pub struct Function {
     // index in the original order as defined in the source file
     pub index: usize,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub loc: Loc,
     pub visibility: Visibility,
     pub entry: Option<Loc>,
+    pub macro_: Option<Loc>,
     pub signature: FunctionSignature,
     pub acquires: Vec<ModuleAccess>,
     pub body: FunctionBody,
@@ -226,6 +285,7 @@ pub struct Constant {
     // index in the original order as defined in the source file
     pub index: usize,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub loc: Loc,
     pub signature: Type,
     pub value: Exp,
@@ -342,6 +402,9 @@ pub struct AbilitySet(UniqueSet<Ability>);
 pub enum ModuleAccess_ {
     Name(Name),
     ModuleAccess(ModuleIdent, Name),
+    /// The variant `Enum::Variant` of the enum `m::Enum`. The enum is boxed to keep module
+    /// accesses small
+    Variant(Box<Spanned<(ModuleIdent, Name)>>, Name),
 }
 pub type ModuleAccess = Spanned<ModuleAccess_>;
 
@@ -363,13 +426,38 @@ pub type Type = Spanned<Type_>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum LValue_ {
-    Var(ModuleAccess, Option<Vec<Type>>),
-    Unpack(ModuleAccess, Option<Vec<Type>>, Fields<LValue>),
+    // The mutability is always `None` for assignments, as only bindings declare locals
+    Var(Mutability, ModuleAccess, Option<Vec<Type>>),
+    Unpack(ModuleAccess, Option<Vec<Type>>, FieldBindings),
 }
 pub type LValue = Spanned<LValue_>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldBindings {
+    Named(Fields<LValue>),
+    Positional(Vec<LValue>),
+}
+
 pub type LValueList_ = Vec<LValue>;
 pub type LValueList = Spanned<LValueList_>;
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchPattern_ {
+    PositionalConstructor(ModuleAccess, Option<Vec<Type>>, Spanned<Vec<MatchPattern>>),
+    FieldConstructor(ModuleAccess, Option<Vec<Type>>, Fields<MatchPattern>),
+    Binder(Var),
+    Literal(Value),
+}
+pub type MatchPattern = Spanned<MatchPattern_>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm_ {
+    pub pattern: MatchPattern,
+    pub guard: Option<Box<Exp>>,
+    pub rhs: Box<Exp>,
+}
+pub type MatchArm = Spanned<MatchArm_>;
+
 pub type LValueWithRange_ = (LValue, Exp);
 pub type LValueWithRange = Spanned<LValueWithRange_>;
 pub type LValueWithRangeList_ = Vec<LValueWithRange>;
@@ -380,6 +468,7 @@ pub type LValueWithRangeList = Spanned<LValueWithRangeList_>;
 pub enum ExpDotted_ {
     Exp(Exp),
     Dot(Box<ExpDotted>, Name),
+    Index(Box<ExpDotted>, Box<Exp>), // outside of specs only
 }
 pub type ExpDotted = Spanned<ExpDotted_>;
 
@@ -412,6 +501,8 @@ pub type Value = Spanned<Value_>;
 #[allow(clippy::large_enum_variant)]
 pub enum Exp_ {
     Value(Value),
+    // "..." string literal, already decoded into UTF-8 bytes. Lowered during naming
+    StringLiteral(Vec<u8>),
     Move(Var),
     Copy(Var),
 
@@ -426,8 +517,9 @@ pub enum Exp_ {
     Vector(Loc, Option<Vec<Type>>, Spanned<Vec<Exp>>),
 
     IfElse(Box<Exp>, Box<Exp>, Box<Exp>),
-    While(Box<Exp>, Box<Exp>),
-    Loop(Box<Exp>),
+    While(Option<BlockLabel>, Box<Exp>, Box<Exp>),
+    Loop(Option<BlockLabel>, Box<Exp>),
+    Match(Box<Exp>, Spanned<Vec<MatchArm>>),
     Block(Sequence),
     Lambda(LValueList, Box<Exp>), // spec only
     Quant(
@@ -444,8 +536,8 @@ pub enum Exp_ {
 
     Return(Box<Exp>),
     Abort(Box<Exp>),
-    Break,
-    Continue,
+    Break(Option<BlockLabel>, Option<Box<Exp>>),
+    Continue(Option<BlockLabel>),
 
     Dereference(Box<Exp>),
     UnaryExp(UnaryOp, Box<Exp>),
@@ -458,6 +550,8 @@ pub enum Exp_ {
 
     Borrow(bool, Box<Exp>),
     ExpDotted(Box<ExpDotted>),
+    // e.f(args), resolved against the type of e during typing
+    MethodCall(Box<ExpDotted>, Name, Option<Vec<Type>>, Spanned<Vec<Exp>>),
     Index(Box<Exp>, Box<Exp>), // spec only (no mutation needed right now)
 
     Cast(Box<Exp>, Type),
@@ -819,6 +913,7 @@ impl fmt::Display for ModuleAccess_ {
         match self {
             Name(n) => write!(f, "{}", n),
             ModuleAccess(m, n) => write!(f, "{}::{}", m, n),
+            Variant(e, v) => write!(f, "{}::{}::{}", e.value.0, e.value.1, v),
         }
     }
 }
@@ -968,13 +1063,17 @@ impl AstDebug for ModuleDefinition {
         let ModuleDefinition {
             package_name,
             attributes,
+            doc: _doc,
             loc: _loc,
             is_source_module,
             friends,
             structs,
+            enums,
             functions,
             constants,
             specs,
+            type_aliases,
+            use_funs,
             warning_filter,
         } = self;
         warning_filter.ast_debug(w);
@@ -991,10 +1090,22 @@ impl AstDebug for ModuleDefinition {
             w.write(&format!("friend {};", mident));
             w.new_line();
         }
+        for (alias, (mident, n)) in type_aliases.key_cloned_iter() {
+            w.write(&format!("use {}::{} as {};", mident, n, alias));
+            w.new_line();
+        }
+        for use_fun in use_funs {
+            use_fun.ast_debug(w);
+            w.new_line();
+        }
         for sdef in structs.key_cloned_iter() {
             sdef.ast_debug(w);
             w.new_line();
         }
+        for edef in enums.key_cloned_iter() {
+            edef.ast_debug(w);
+            w.new_line();
+        }
         for cdef in constants.key_cloned_iter() {
             cdef.ast_debug(w);
             w.new_line();
@@ -1010,6 +1121,20 @@ impl AstDebug for ModuleDefinition {
     }
 }
 
+impl AstDebug for UseFun {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        let UseFun {
+            attributes,
+            loc: _,
+            function,
+            ty,
+            method,
+        } = self;
+        attributes.ast_debug(w);
+        w.write(&format!("use fun {} as {}.{};", function, ty, method));
+    }
+}
+
 pub fn ability_modifiers_ast_debug(w: &mut AstWriter, abilities: &AbilitySet) {
     if !abilities.is_empty() {
         w.write(" has ");
@@ -1027,6 +1152,7 @@ impl AstDebug for (StructName, &StructDefinition) {
             StructDefinition {
                 index,
                 attributes,
+                doc: _doc,
                 loc: _loc,
                 abilities,
                 type_parameters,
@@ -1043,19 +1169,75 @@ impl AstDebug for (StructName, &StructDefinition) {
         w.write(&format!("struct#{index} {name}"));
         type_parameters.ast_debug(w);
         ability_modifiers_ast_debug(w, abilities);
-        if let StructFields::Defined(fields) = fields {
-            w.block(|w| {
+        match fields {
+            StructFields::Defined(fields) => w.block(|w| {
                 w.list(fields, ",", |w, (_, f, idx_st)| {
                     let (idx, st) = idx_st;
                     w.write(&format!("{}#{}: ", idx, f));
                     st.ast_debug(w);
                     true
                 });
-            })
+            }),
+            StructFields::Positional(tys) => {
+                w.write("(");
+                w.comma(tys, |w, ty| ty.ast_debug(w));
+                w.write(")");
+            }
+            StructFields::Native(_) => (),
         }
     }
 }
 
+impl AstDebug for (EnumName, &EnumDefinition) {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        let (
+            name,
+            EnumDefinition {
+                index,
+                attributes,
+                doc: _doc,
+                loc: _loc,
+                abilities,
+                type_parameters,
+                variants,
+                warning_filter,
+            },
+        ) = self;
+        warning_filter.ast_debug(w);
+        attributes.ast_debug(w);
+        w.write(&format!("enum#{index} {name}"));
+        type_parameters.ast_debug(w);
+        ability_modifiers_ast_debug(w, abilities);
+        w.block(|w| {
+            w.list(variants, ",", |w, (_, v, vdef)| {
+                let VariantDefinition {
+                    index,
+                    loc: _,
+                    fields,
+                } = vdef;
+                w.write(&format!("{}#{}", index, v));
+                match fields {
+                    VariantFields::Defined(fields) => w.block(|w| {
+                        w.list(fields, ",", |w, (_, f, idx_st)| {
+                            let (idx, st) = idx_st;
+                            w.write(&format!("{}#{}: ", idx, f));
+                            st.ast_debug(w);
+                            true
+                        });
+                    }),
+                    VariantFields::Positional(tys) => {
+                        w.write("(");
+                        w.comma(tys, |w, ty| ty.ast_debug(w));
+                        w.write(")");
+                    }
+                    VariantFields::Empty => (),
+                }
+                true
+            });
+        });
+    }
+}
+
 impl AstDebug for SpecBlock_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         w.write(" spec ");
@@ -1249,9 +1431,11 @@ impl AstDebug for (FunctionName, &Function) {
             Function {
                 index,
                 attributes,
+                doc: _doc,
                 loc: _loc,
                 visibility,
                 entry,
+                macro_,
                 signature,
                 acquires,
                 body,
@@ -1265,6 +1449,9 @@ impl AstDebug for (FunctionName, &Function) {
         if entry.is_some() {
             w.write(&format!("{} ", ENTRY_MODIFIER));
         }
+        if macro_.is_some() {
+            w.write(&format!("{} ", MACRO_MODIFIER));
+        }
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
         }
@@ -1297,7 +1484,10 @@ impl AstDebug for FunctionSignature {
         } = self;
         type_parameters.ast_debug(w);
         w.write("(");
-        w.comma(parameters, |w, (v, st)| {
+        w.comma(parameters, |w, (mut_, v, st)| {
+            if mut_.is_some() {
+                w.write("mut ");
+            }
             w.write(&format!("{}: ", v));
             st.ast_debug(w);
         });
@@ -1314,6 +1504,7 @@ impl AstDebug for (ConstantName, &Constant) {
                 warning_filter,
                 index,
                 attributes,
+                doc: _doc,
                 loc: _loc,
                 signature,
                 value,
@@ -1428,6 +1619,7 @@ impl AstDebug for ModuleAccess_ {
         w.write(&match self {
             ModuleAccess_::Name(n) => format!("{}", n),
             ModuleAccess_::ModuleAccess(m, n) => format!("{}::{}", m, n),
+            ModuleAccess_::Variant(e, v) => format!("{}::{}::{}", e.value.0, e.value.1, v),
         })
     }
 }
@@ -1487,6 +1679,7 @@ impl AstDebug for Exp_ {
                 trailing: _trailing,
             } => w.write("/*()*/"),
             E::Value(v) => v.ast_debug(w),
+            E::StringLiteral(bytes) => w.write(&format!("{:?}", String::from_utf8_lossy(bytes))),
             E::Move(v) => w.write(&format!("move {}", v)),
             E::Copy(v) => w.write(&format!("copy {}", v)),
             E::Name(ma, tys_opt) => {
@@ -1545,16 +1738,30 @@ impl AstDebug for Exp_ {
                 w.write(" else ");
                 f.ast_debug(w);
             }
-            E::While(b, e) => {
+            E::While(name, b, e) => {
+                if let Some(name) = name {
+                    w.write(&format!("'{name}: "));
+                }
                 w.write("while (");
                 b.ast_debug(w);
                 w.write(")");
                 e.ast_debug(w);
             }
-            E::Loop(e) => {
+            E::Loop(name, e) => {
+                if let Some(name) = name {
+                    w.write(&format!("'{name}: "));
+                }
                 w.write("loop ");
                 e.ast_debug(w);
             }
+            E::Match(subject, sp!(_, arms)) => {
+                w.write("match (");
+                subject.ast_debug(w);
+                w.write(") ");
+                w.block(|w| {
+                    w.comma(arms, |w, arm| arm.ast_debug(w));
+                });
+            }
             E::Block(seq) => w.block(|w| seq.ast_debug(w)),
             E::Lambda(sp!(_, bs), e) => {
                 w.write("fun ");
@@ -1605,8 +1812,22 @@ impl AstDebug for Exp_ {
                 w.write("abort ");
                 e.ast_debug(w);
             }
-            E::Break => w.write("break"),
-            E::Continue => w.write("continue"),
+            E::Break(name, e) => {
+                w.write("break");
+                if let Some(name) = name {
+                    w.write(&format!(" '{name}"));
+                }
+                if let Some(e) = e {
+                    w.write(" ");
+                    e.ast_debug(w);
+                }
+            }
+            E::Continue(name) => {
+                w.write("continue");
+                if let Some(name) = name {
+                    w.write(&format!(" '{name}"));
+                }
+            }
             E::Dereference(e) => {
                 w.write("*");
                 e.ast_debug(w)
@@ -1631,6 +1852,18 @@ impl AstDebug for Exp_ {
                 e.ast_debug(w);
             }
             E::ExpDotted(ed) => ed.ast_debug(w),
+            E::MethodCall(ed, n, tys_opt, sp!(_, rhs)) => {
+                ed.ast_debug(w);
+                w.write(&format!(".{}", n));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(rhs, |w, e| e.ast_debug(w));
+                w.write(")");
+            }
             E::Cast(e, ty) => {
                 w.write("(");
                 e.ast_debug(w);
@@ -1673,6 +1906,12 @@ impl AstDebug for ExpDotted_ {
                 e.ast_debug(w);
                 w.write(&format!(".{}", n))
             }
+            D::Index(e, i) => {
+                e.ast_debug(w);
+                w.write("[");
+                i.ast_debug(w);
+                w.write("]")
+            }
         }
     }
 }
@@ -1694,7 +1933,10 @@ impl AstDebug for LValue_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         use LValue_ as L;
         match self {
-            L::Var(v, tys_opt) => {
+            L::Var(mut_, v, tys_opt) => {
+                if mut_.is_some() {
+                    w.write("mut ");
+                }
                 w.write(&format!("{}", v));
                 if let Some(ss) = tys_opt {
                     w.write("<");
@@ -1709,6 +1951,16 @@ impl AstDebug for LValue_ {
                     ss.ast_debug(w);
                     w.write(">");
                 }
+                fields.ast_debug(w);
+            }
+        }
+    }
+}
+
+impl AstDebug for FieldBindings {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        match self {
+            FieldBindings::Named(fields) => {
                 w.write("{");
                 w.comma(fields, |w, (_, f, idx_b)| {
                     let (idx, b) = idx_b;
@@ -1717,7 +1969,61 @@ impl AstDebug for LValue_ {
                 });
                 w.write("}");
             }
+            FieldBindings::Positional(lvalues) => {
+                w.write("(");
+                w.comma(lvalues, |w, b| b.ast_debug(w));
+                w.write(")");
+            }
+        }
+    }
+}
+
+impl AstDebug for MatchPattern_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        use MatchPattern_ as P;
+        match self {
+            P::PositionalConstructor(ma, tys_opt, sp!(_, patterns)) => {
+                ma.ast_debug(w);
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(patterns, |w, p| p.ast_debug(w));
+                w.write(")");
+            }
+            P::FieldConstructor(ma, tys_opt, fields) => {
+                ma.ast_debug(w);
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_p)| {
+                    let (idx, p) = idx_p;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    p.ast_debug(w);
+                });
+                w.write("}");
+            }
+            P::Binder(v) => w.write(&format!("{}", v)),
+            P::Literal(v) => v.ast_debug(w),
+        }
+    }
+}
+
+impl AstDebug for MatchArm_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        self.pattern.ast_debug(w);
+        if let Some(guard) = &self.guard {
+            w.write(" if (");
+            guard.ast_debug(w);
+            w.write(")");
         }
+        w.write(" => ");
+        self.rhs.ast_debug(w);
     }
 }
 
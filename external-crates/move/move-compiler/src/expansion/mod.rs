@@ -6,4 +6,5 @@ mod aliases;
 pub mod ast;
 mod byte_string;
 mod hex_string;
+mod string;
 pub(crate) mod translate;
@@ -0,0 +1,198 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{diag, diagnostics::Diagnostics, parser::syntax::make_loc};
+use move_command_line_common::files::FileHash;
+use move_ir_types::location::*;
+
+struct Context {
+    file_hash: FileHash,
+    start_offset: usize,
+    diags: Diagnostics,
+}
+
+impl Context {
+    fn new(file_hash: FileHash, start_offset: usize) -> Self {
+        Self {
+            file_hash,
+            start_offset,
+            diags: Diagnostics::new(),
+        }
+    }
+
+    fn make_loc(&self, start: usize, end: usize) -> Loc {
+        make_loc(
+            self.file_hash,
+            self.start_offset + 1 + start, // add 1 for the beginning of the string
+            self.start_offset + 1 + end,
+        )
+    }
+
+    fn error(&mut self, start: usize, end: usize, err_text: String) {
+        let loc = self.make_loc(start, end);
+        self.diags
+            .add(diag!(StringLiterals::InvalidEscape, (loc, err_text)))
+    }
+
+    fn has_diags(&self) -> bool {
+        !self.diags.is_empty()
+    }
+
+    fn get_diags(self) -> Diagnostics {
+        self.diags
+    }
+}
+
+/// Decodes the text of a string literal (without the surrounding quotes) into its UTF-8 bytes.
+/// In addition to the escapes supported by byte strings, unicode escapes of the form `\u{...}`
+/// are supported. The resulting bytes must be valid UTF-8.
+pub fn decode(loc: Loc, text: &str) -> Result<Vec<u8>, Diagnostics> {
+    let file_hash = loc.file_hash();
+    let start_offset = loc.start() as usize;
+    let mut context = Context::new(file_hash, start_offset);
+    let mut buffer = vec![];
+    let chars: Vec<_> = text.chars().collect();
+    decode_(&mut context, &mut buffer, chars);
+    if context.has_diags() {
+        return Err(context.get_diags());
+    }
+    if let Err(e) = std::str::from_utf8(&buffer) {
+        let msg = format!(
+            "Invalid UTF-8 string. The bytes starting at byte offset {} of the decoded string \
+             are not valid UTF-8",
+            e.valid_up_to()
+        );
+        let mut diag = diag!(StringLiterals::InvalidUtf8, (loc, msg));
+        diag.add_note(
+            "If arbitrary bytes are needed, use a byte string, e.g. b\"...\", instead of a \
+             string literal",
+        );
+        let mut diags = Diagnostics::new();
+        diags.add(diag);
+        return Err(diags);
+    }
+    Ok(buffer)
+}
+
+fn decode_(context: &mut Context, buffer: &mut Vec<u8>, chars: Vec<char>) {
+    let len = chars.len();
+    let mut i = 0;
+    macro_rules! next_char {
+        () => {{
+            let c = chars[i];
+            i += 1;
+            c
+        }};
+    }
+    macro_rules! next_char_opt {
+        () => {{
+            if i < len {
+                Some(next_char!())
+            } else {
+                None
+            }
+        }};
+    }
+    while i < len {
+        let cur = i;
+        let c = next_char!();
+        if c != '\\' {
+            push(buffer, c);
+            continue;
+        }
+
+        match next_char!() {
+            'n' => push(buffer, '\n'),
+            'r' => push(buffer, '\r'),
+            't' => push(buffer, '\t'),
+            '\\' => push(buffer, '\\'),
+            '0' => push(buffer, '\0'),
+            '"' => push(buffer, '"'),
+            'x' => {
+                let hex: String = match (next_char_opt!(), next_char_opt!()) {
+                    (Some(d0), Some(d1)) => [d0, d1].iter().collect(),
+                    (d0_opt, _) => {
+                        let h = d0_opt.map(|d0| d0.to_string()).unwrap_or_default();
+                        let err_text = format!(
+                            "Invalid escape: '\\x{}'. Hex literals are represented by two \
+                             symbols: [\\x00-\\xFF].",
+                            h
+                        );
+                        context.error(cur, len, err_text);
+                        return;
+                    }
+                };
+                match hex::decode(hex) {
+                    Ok(hex_buffer) => buffer.extend(hex_buffer),
+                    Err(hex::FromHexError::InvalidHexCharacter { c, index }) => {
+                        let err_text = format!("Invalid hexadecimal character: '{}'", c);
+                        context.error(cur + 2 + index, cur + 2 + index, err_text);
+                    }
+                    Err(_) => unreachable!("ICE unexpected error parsing hex string value"),
+                }
+            }
+            'u' => {
+                if next_char_opt!() != Some('{') {
+                    let err_text = "Invalid escape: '\\u'. Unicode escapes are represented as \
+                                    '\\u{...}' with up to six hexadecimal digits"
+                        .to_string();
+                    context.error(cur, (cur + 3).min(len), err_text);
+                    continue;
+                }
+                let digits_start = i;
+                let mut digits = String::new();
+                loop {
+                    match next_char_opt!() {
+                        Some('}') => break,
+                        Some(d) => digits.push(d),
+                        None => {
+                            let err_text = "Invalid escape: missing closing '}' for unicode escape"
+                                .to_string();
+                            context.error(cur, len, err_text);
+                            return;
+                        }
+                    }
+                }
+                let escape_end = i;
+                if digits.is_empty() || digits.len() > 6 {
+                    let err_text = format!(
+                        "Invalid unicode escape '\\u{{{}}}'. Expected between one and six \
+                         hexadecimal digits",
+                        digits
+                    );
+                    context.error(cur, escape_end, err_text);
+                    continue;
+                }
+                if let Some(idx) = digits.chars().position(|d| !d.is_ascii_hexdigit()) {
+                    let c = digits.chars().nth(idx).unwrap();
+                    let err_text = format!("Invalid hexadecimal character: '{}'", c);
+                    context.error(digits_start + idx, digits_start + idx, err_text);
+                    continue;
+                }
+                let code = u32::from_str_radix(&digits, 16).unwrap();
+                match char::from_u32(code) {
+                    Some(ch) => {
+                        let mut encoded = [0; 4];
+                        buffer.extend(ch.encode_utf8(&mut encoded).as_bytes())
+                    }
+                    None => {
+                        let err_text = format!(
+                            "Invalid unicode escape '\\u{{{}}}'. '{:#X}' is not a valid unicode \
+                             scalar value",
+                            digits, code
+                        );
+                        context.error(cur, escape_end, err_text);
+                    }
+                }
+            }
+            c => {
+                context.error(cur, cur + 2, format!("Invalid escape sequence: '\\{}'", c));
+            }
+        }
+    }
+}
+
+fn push(buffer: &mut Vec<u8>, ch: char) {
+    assert!(ch.is_ascii(), "ICE ascii-only support is gated at parsing");
+    buffer.extend(vec![ch as u8]);
+}
@@ -9,10 +9,11 @@ use crate::{
     expansion::{
         aliases::{AliasMap, AliasSet},
         ast::{self as E, Address, Fields, ModuleIdent, ModuleIdent_, SpecId},
-        byte_string, hex_string,
+        byte_string, hex_string, string,
     },
     parser::ast::{
-        self as P, Ability, ConstantName, Field, FunctionName, ModuleName, StructName, Var,
+        self as P, Ability, ConstantName, EnumName, Field, FunctionName, ModuleName, StructName,
+        Var, VariantName,
     },
     shared::{known_attributes::AttributePosition, unique_map::UniqueMap, *},
     FullyCompiledProgram,
@@ -41,6 +42,9 @@ struct Context<'env, 'map> {
     current_package: Option<Symbol>,
     in_spec_context: bool,
     exp_specs: BTreeMap<SpecId, E::SpecBlock>,
+    /// The struct aliases declared at the top level of the current module, which are resolved
+    /// during naming when used in types (see `E::ModuleDefinition::type_aliases`)
+    module_type_aliases: BTreeMap<Symbol, (ModuleIdent_, Symbol)>,
     // Cached warning filters for all available prefixes. Used by non-source defs
     // and dependency packages
     all_filter_alls: WarningFilters,
@@ -67,6 +71,7 @@ impl<'env, 'map> Context<'env, 'map> {
             current_package: None,
             in_spec_context: false,
             exp_specs: BTreeMap::new(),
+            module_type_aliases: BTreeMap::new(),
             all_filter_alls,
         }
     }
@@ -75,6 +80,15 @@ impl<'env, 'map> Context<'env, 'map> {
         self.address.as_ref().unwrap()
     }
 
+    /// Outside of specs, `e[i]` is resolved to an index function only in editions supporting
+    /// 'syntax' methods
+    fn index_syntax_enabled(&self) -> bool {
+        !self.in_spec_context
+            && self
+                .env
+                .supports_feature(self.current_package, &FeatureGate::SyntaxMethods)
+    }
+
     /// Resets the alias map and reports errors for aliases that were unused
     pub fn set_to_outer_scope(&mut self, outer_scope: OldAliasMap) {
         let AliasSet { modules, members } = self.aliases.set_to_outer_scope(outer_scope);
@@ -407,6 +421,7 @@ fn module_(
     mdef: P::ModuleDefinition,
 ) -> (ModuleIdent, E::ModuleDefinition) {
     let P::ModuleDefinition {
+        doc,
         attributes,
         loc,
         address,
@@ -444,6 +459,11 @@ fn module_(
         .into_iter()
         .filter_map(|member| aliases_from_member(context, &mut new_scope, &current_module, member))
         .collect::<Vec<_>>();
+    let type_aliases = module_type_aliases(context, package_name, &new_scope);
+    context.module_type_aliases = type_aliases
+        .key_cloned_iter()
+        .map(|(alias, (mident, n))| (alias.value, (mident.value, n.value)))
+        .collect();
     let old_aliases = context.aliases.add_and_shadow_all(new_scope);
     assert!(
         old_aliases.is_empty(),
@@ -454,19 +474,23 @@ fn module_(
     let mut functions = UniqueMap::new();
     let mut constants = UniqueMap::new();
     let mut structs = UniqueMap::new();
+    let mut enums = UniqueMap::new();
     let mut specs = vec![];
+    let mut use_funs = vec![];
     for member in members {
         match member {
-            P::ModuleMember::Use(_) => unreachable!(),
+            P::ModuleMember::Use(u) => use_funs.extend(use_fun(context, u)),
             P::ModuleMember::Friend(f) => friend(context, &mut friends, f),
             P::ModuleMember::Function(mut f) => {
-                if !context.is_source_definition {
+                // macro bodies are kept, as they are expanded at call sites in other packages
+                if !context.is_source_definition && f.macro_.is_none() {
                     f.body.value = P::FunctionBody_::Native
                 }
                 function(context, &mut functions, f)
             }
             P::ModuleMember::Constant(c) => constant(context, &mut constants, c),
             P::ModuleMember::Struct(s) => struct_def(context, &mut structs, s),
+            P::ModuleMember::Enum(e) => enum_def(context, &mut enums, e),
             P::ModuleMember::Spec(s) => specs.push(spec(context, s)),
         }
     }
@@ -474,17 +498,22 @@ fn module_(
     check_visibility_modifiers(context, &functions, &friends, package_name);
 
     context.set_to_outer_scope(old_aliases);
+    context.module_type_aliases = BTreeMap::new();
 
     let def = E::ModuleDefinition {
         package_name,
         attributes,
+        doc,
         loc,
         is_source_module: context.is_source_definition,
         friends,
         structs,
+        enums,
         constants,
         functions,
         specs,
+        type_aliases,
+        use_funs,
         warning_filter,
     };
     context.env.pop_warning_filter_scope();
@@ -638,6 +667,18 @@ fn script_(context: &mut Context, package_name: Option<Symbol>, pscript: P::Scri
             ));
         }
     }
+    if let Some(loc) = function.macro_ {
+        context.env.add_diag(diag!(
+            Declarations::InvalidScript,
+            (
+                loc,
+                format!(
+                    "Invalid '{}' function. 'script' functions cannot be macros",
+                    P::MACRO_MODIFIER
+                )
+            )
+        ));
+    }
     let specs = specs(context, pspecs);
     context.set_to_outer_scope(old_aliases);
     context.env.pop_warning_filter_scope();
@@ -971,6 +1012,9 @@ fn module_members(
             P::ModuleMember::Struct(s) => {
                 cur_members.insert(s.name.0, ModuleMemberKind::Struct);
             }
+            P::ModuleMember::Enum(e) => {
+                cur_members.insert(e.name.0, ModuleMemberKind::Enum);
+            }
             P::ModuleMember::Spec(
                 sp!(
                     _,
@@ -999,6 +1043,33 @@ fn module_members(
     members.add(mident, cur_members).unwrap();
 }
 
+/// The aliases of structs declared by a `use` at the top level of a module, in editions where
+/// they are resolved during naming
+fn module_type_aliases(
+    context: &Context,
+    package_name: Option<Symbol>,
+    scope: &AliasMapBuilder,
+) -> UniqueMap<Name, (ModuleIdent, Name)> {
+    let mut type_aliases = UniqueMap::new();
+    if !context
+        .env
+        .supports_feature(package_name, &FeatureGate::ModuleTypeAliases)
+    {
+        return type_aliases;
+    }
+    for (alias, (mident, member)) in scope.explicit_member_aliases() {
+        let is_struct = context
+            .module_members
+            .get(mident)
+            .and_then(|members| members.get(member))
+            .is_some_and(|kind| matches!(kind, ModuleMemberKind::Struct | ModuleMemberKind::Enum));
+        if is_struct {
+            type_aliases.add(alias, (*mident, *member)).unwrap();
+        }
+    }
+    type_aliases
+}
+
 fn module_self_aliases(acc: &mut AliasMapBuilder, current_module: &ModuleIdent) {
     let self_name = sp(current_module.loc, ModuleName::SELF_NAME.into());
     acc.add_implicit_module_alias(self_name, *current_module)
@@ -1025,6 +1096,11 @@ fn aliases_from_member(
     }
 
     match member {
+        // method aliases are resolved once all of the aliases of the module are known
+        u @ P::ModuleMember::Use(P::UseDecl {
+            use_: P::Use::Fun { .. },
+            ..
+        }) => Some(u),
         P::ModuleMember::Use(u) => {
             use_(context, acc, u);
             None
@@ -1048,6 +1124,11 @@ fn aliases_from_member(
             check_name_and_add_implicit_alias!(ModuleMemberKind::Struct, n);
             Some(P::ModuleMember::Struct(s))
         }
+        P::ModuleMember::Enum(e) => {
+            let n = e.name.0;
+            check_name_and_add_implicit_alias!(ModuleMemberKind::Enum, n);
+            Some(P::ModuleMember::Enum(e))
+        }
         P::ModuleMember::Spec(s) => {
             let sp!(
                 _,
@@ -1174,9 +1255,44 @@ fn use_(context: &mut Context, acc: &mut AliasMapBuilder, u: P::UseDecl) {
                 }
             }
         }
+        P::Use::Fun { loc, .. } => {
+            let msg = "Invalid 'use fun'. Method aliases can only be declared at the top level of \
+                       a module";
+            context
+                .env
+                .add_diag(diag!(Declarations::InvalidUseFun, (loc, msg)));
+        }
     }
 }
 
+// Resolves the function and the type of a `use fun` declared at the top level of a module. Whether
+// they exist, and match, is checked during naming
+fn use_fun(context: &mut Context, u: P::UseDecl) -> Option<E::UseFun> {
+    let P::UseDecl {
+        use_: u,
+        attributes,
+    } = u;
+    let P::Use::Fun {
+        loc,
+        function,
+        ty,
+        method,
+    } = u
+    else {
+        unreachable!("ICE only 'use fun' declarations are kept as module members")
+    };
+    let attributes = flatten_attributes(context, AttributePosition::Use, attributes);
+    let function = name_access_chain(context, Access::ApplyPositional, *function);
+    let ty = type_name(context, *ty);
+    Some(E::UseFun {
+        attributes,
+        loc,
+        function: function?,
+        ty: ty?,
+        method,
+    })
+}
+
 fn duplicate_module_alias(context: &mut Context, old_loc: Loc, alias: Name) {
     let msg = format!(
         "Duplicate module alias '{}'. Module aliases must be unique within a given namespace",
@@ -1236,6 +1352,7 @@ fn struct_def_(
     pstruct: P::StructDefinition,
 ) -> (StructName, E::StructDefinition) {
     let P::StructDefinition {
+        doc,
         attributes,
         loc,
         name,
@@ -1256,6 +1373,7 @@ fn struct_def_(
         warning_filter,
         index,
         attributes,
+        doc,
         loc,
         abilities,
         type_parameters,
@@ -1273,6 +1391,11 @@ fn struct_fields(
 ) -> E::StructFields {
     let pfields_vec = match pfields {
         P::StructFields::Native(loc) => return E::StructFields::Native(loc),
+        P::StructFields::Positional(tys) => {
+            return E::StructFields::Positional(
+                tys.into_iter().map(|t| type_(context, t)).collect(),
+            )
+        }
         P::StructFields::Defined(v) => v,
     };
     let mut field_map = UniqueMap::new();
@@ -1295,6 +1418,132 @@ fn struct_fields(
     E::StructFields::Defined(field_map)
 }
 
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+fn enum_def(
+    context: &mut Context,
+    enums: &mut UniqueMap<EnumName, E::EnumDefinition>,
+    penum: P::EnumDefinition,
+) {
+    let (ename, edef) = enum_def_(context, enums.len(), penum);
+    if let Err(_old_loc) = enums.add(ename, edef) {
+        assert!(context.env.has_errors())
+    }
+}
+
+fn enum_def_(
+    context: &mut Context,
+    index: usize,
+    penum: P::EnumDefinition,
+) -> (EnumName, E::EnumDefinition) {
+    let P::EnumDefinition {
+        doc,
+        attributes,
+        loc,
+        name,
+        abilities: abilities_vec,
+        type_parameters: pty_params,
+        variants: pvariants,
+    } = penum;
+    // enums take the same attributes as structs
+    let attributes = flatten_attributes(context, AttributePosition::Struct, attributes);
+    let warning_filter = warning_filter(context, &attributes);
+    context.env.add_warning_filter_scope(warning_filter.clone());
+    let type_parameters = struct_type_parameters(context, pty_params);
+    let old_aliases = context
+        .aliases
+        .shadow_for_type_parameters(type_parameters.iter().map(|tp| &tp.name));
+    let abilities = ability_set(context, "modifier", abilities_vec);
+    let mut variants = UniqueMap::new();
+    for (idx, pvariant) in pvariants.into_iter().enumerate() {
+        let P::VariantDefinition {
+            loc: vloc,
+            name: vname,
+            fields: pfields,
+        } = pvariant;
+        // variant names follow the same rules as enum names
+        let _ = check_valid_module_member_name_impl(
+            context,
+            ModuleMemberKind::Enum,
+            &vname.0,
+            NameCase::Variant,
+        );
+        let fields = variant_fields(context, &name, &vname, pfields);
+        let vdef = E::VariantDefinition {
+            index: idx,
+            loc: vloc,
+            fields,
+        };
+        if let Err((vname, old_loc)) = variants.add(vname, vdef) {
+            let msg = format!(
+                "Duplicate definition for variant '{}' in enum '{}'",
+                vname, name
+            );
+            context.env.add_diag(diag!(
+                Declarations::DuplicateItem,
+                (vname.loc(), msg),
+                (old_loc, "Variant previously defined here"),
+            ));
+        }
+    }
+    if variants.is_empty() {
+        let msg = format!("Invalid enum declaration. Enum '{}' has no variants", name);
+        context
+            .env
+            .add_diag(diag!(Declarations::InvalidEnum, (loc, msg)));
+    }
+    let edef = E::EnumDefinition {
+        warning_filter,
+        index,
+        attributes,
+        doc,
+        loc,
+        abilities,
+        type_parameters,
+        variants,
+    };
+    context.set_to_outer_scope(old_aliases);
+    context.env.pop_warning_filter_scope();
+    (name, edef)
+}
+
+fn variant_fields(
+    context: &mut Context,
+    ename: &EnumName,
+    vname: &VariantName,
+    pfields: P::VariantFields,
+) -> E::VariantFields {
+    let pfields_vec = match pfields {
+        P::VariantFields::Empty => return E::VariantFields::Empty,
+        P::VariantFields::Positional(tys) => {
+            return E::VariantFields::Positional(
+                tys.into_iter().map(|t| type_(context, t)).collect(),
+            )
+        }
+        P::VariantFields::Defined(v) => v,
+    };
+    let mut field_map = UniqueMap::new();
+    for (idx, (field, pt)) in pfields_vec.into_iter().enumerate() {
+        let t = type_(context, pt);
+        if let Err((field, old_loc)) = field_map.add(field, (idx, t)) {
+            context.env.add_diag(diag!(
+                Declarations::DuplicateItem,
+                (
+                    field.loc(),
+                    format!(
+                        "Duplicate definition for field '{}' in variant '{}::{}'",
+                        field, ename, vname
+                    ),
+                ),
+                (old_loc, "Field previously defined here"),
+            ));
+        }
+    }
+    E::VariantFields::Defined(field_map)
+}
+
 //**************************************************************************************************
 // Friends
 //**************************************************************************************************
@@ -1358,6 +1607,7 @@ fn constant_(
 ) -> (ConstantName, E::Constant) {
     assert!(context.exp_specs.is_empty());
     let P::Constant {
+        doc,
         attributes: pattributes,
         loc,
         name,
@@ -1374,6 +1624,7 @@ fn constant_(
         warning_filter,
         index,
         attributes,
+        doc,
         loc,
         signature,
         value,
@@ -1403,11 +1654,13 @@ fn function_(
     pfunction: P::Function,
 ) -> (FunctionName, E::Function) {
     let P::Function {
+        doc,
         attributes: pattributes,
         loc,
         name,
         visibility: pvisibility,
         entry,
+        macro_,
         signature: psignature,
         body: pbody,
         acquires,
@@ -1428,9 +1681,11 @@ fn function_(
         warning_filter,
         index,
         attributes,
+        doc,
         loc,
         visibility,
         entry,
+        macro_,
         signature,
         acquires,
         body,
@@ -1469,9 +1724,9 @@ fn function_signature(
         .shadow_for_type_parameters(type_parameters.iter().map(|(name, _)| name));
     let parameters = pparams
         .into_iter()
-        .map(|(v, t)| (v, type_(context, t)))
+        .map(|(mut_, v, t)| (mut_, v, type_(context, t)))
         .collect::<Vec<_>>();
-    for (v, _) in &parameters {
+    for (_, v, _) in &parameters {
         check_valid_local_name(context, v)
     }
     let return_type = type_(context, pret_ty);
@@ -1791,7 +2046,7 @@ fn type_(context: &mut Context, sp!(loc, pt_): P::Type) -> E::Type {
         PT::Multiple(ts) => ET::Multiple(types(context, ts)),
         PT::Apply(pn, ptyargs) => {
             let tyargs = types(context, ptyargs);
-            match name_access_chain(context, Access::Type, *pn) {
+            match type_name(context, *pn) {
                 None => {
                     assert!(context.env.has_errors());
                     ET::UnresolvedError
@@ -1817,6 +2072,33 @@ fn type_(context: &mut Context, sp!(loc, pt_): P::Type) -> E::Type {
     sp(loc, t_)
 }
 
+/// Resolves the name of a type, except for the uses of struct aliases declared at the top level of
+/// the module (outside of specs), which are left for naming to resolve
+fn type_name(context: &mut Context, sp!(loc, ptn_): P::NameAccessChain) -> Option<E::ModuleAccess> {
+    use E::ModuleAccess_ as EN;
+    use P::NameAccessChain_ as PN;
+
+    match ptn_ {
+        PN::One(n)
+            if !context.in_spec_context && context.module_type_aliases.contains_key(&n.value) =>
+        {
+            // still looked up here, to mark it as used and to find aliases shadowing it
+            let tn_ = match context.aliases.member_alias_get(&n) {
+                Some((mident, mem))
+                    if context.module_type_aliases.get(&n.value)
+                        == Some(&(mident.value, mem.value)) =>
+                {
+                    EN::Name(n)
+                }
+                Some((mident, mem)) => EN::ModuleAccess(mident, mem),
+                None => EN::Name(n),
+            };
+            Some(sp(loc, tn_))
+        }
+        ptn_ => name_access_chain(context, Access::Type, sp(loc, ptn_)),
+    }
+}
+
 fn types(context: &mut Context, pts: Vec<P::Type>) -> Vec<E::Type> {
     pts.into_iter().map(|pt| type_(context, pt)).collect()
 }
@@ -1863,15 +2145,28 @@ fn name_access_chain(
         }
 
         (_, PN::Two(sp!(_, LN::Name(n1)), n2)) => match context.aliases.module_alias_get(&n1) {
-            None => {
-                context.env.add_diag(diag!(
-                    NameResolution::UnboundModule,
-                    (n1.loc, format!("Unbound module alias '{}'", n1))
-                ));
-                return None;
-            }
             Some(mident) => EN::ModuleAccess(mident, n2),
+            // `Enum::Variant`, if `Enum` is an alias of an enum
+            None => match enum_alias_get(context, &n1) {
+                Some((mident, enum_name)) => {
+                    EN::Variant(Box::new(sp(n1.loc, (mident, enum_name))), n2)
+                }
+                None => {
+                    context.env.add_diag(diag!(
+                        NameResolution::UnboundModule,
+                        (n1.loc, format!("Unbound module alias '{}'", n1))
+                    ));
+                    return None;
+                }
+            },
         },
+        // `m::Enum::Variant`, if `m` is an alias of a module declaring the enum `Enum`
+        (_, PN::Three(sp!(ident_loc, (sp!(_, LN::Name(n1)), n2)), n3))
+            if is_enum(context, context.aliases.module_alias_peek(&n1), &n2) =>
+        {
+            let mident = context.aliases.module_alias_get(&n1).unwrap();
+            EN::Variant(Box::new(sp(ident_loc, (mident, n2))), n3)
+        }
         (_, PN::Three(sp!(ident_loc, (ln, n2)), n3)) => {
             let addr = address(context, /* suggest_declaration */ false, ln);
             let mident = sp(ident_loc, ModuleIdent_::new(addr, ModuleName(n2)));
@@ -1881,6 +2176,23 @@ fn name_access_chain(
     Some(sp(loc, tn_))
 }
 
+/// The enum aliased by `n`, if `n` is an alias of an enum
+fn enum_alias_get(context: &mut Context, n: &Name) -> Option<(ModuleIdent, Name)> {
+    let (mident, member) = context.aliases.member_alias_peek(n)?;
+    if !is_enum(context, Some(mident), member) {
+        return None;
+    }
+    context.aliases.member_alias_get(n)
+}
+
+/// Whether `n` is an enum declared in the module `mident_opt`
+fn is_enum(context: &Context, mident_opt: Option<&ModuleIdent>, n: &Name) -> bool {
+    mident_opt
+        .and_then(|mident| context.module_members.get(mident))
+        .and_then(|members| members.get(n))
+        .is_some_and(|kind| matches!(kind, ModuleMemberKind::Enum))
+}
+
 fn name_access_chain_to_module_ident(
     context: &mut Context,
     sp!(loc, pn_): P::NameAccessChain,
@@ -2018,6 +2330,9 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
     use P::Exp_ as PE;
     let e_ = match pe_ {
         PE::Unit => EE::Unit { trailing: false },
+        PE::Value(sp!(vloc, P::Value_::String(s))) if !context.in_spec_context => {
+            string_literal(context, vloc, s)
+        }
         PE::Value(pv) => match value(context, pv) {
             Some(v) => EE::Value(v),
             None => {
@@ -2091,8 +2406,9 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
             };
             EE::IfElse(eb, et, ef)
         }
-        PE::While(pb, ploop) => EE::While(exp(context, *pb), exp(context, *ploop)),
-        PE::Loop(ploop) => EE::Loop(exp(context, *ploop)),
+        PE::While(name, pb, ploop) => EE::While(name, exp(context, *pb), exp(context, *ploop)),
+        PE::Loop(name, ploop) => EE::Loop(name, exp(context, *ploop)),
+        PE::Match(psubject, parms) => match_(context, *psubject, parms),
         PE::Block(seq) => EE::Block(sequence(context, loc, seq)),
         PE::Lambda(pbs, pe) => {
             if !context.in_spec_context {
@@ -2163,8 +2479,8 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
             EE::Return(ev)
         }
         PE::Abort(pe) => EE::Abort(exp(context, *pe)),
-        PE::Break => EE::Break,
-        PE::Continue => EE::Continue,
+        PE::Break(name, pe_opt) => EE::Break(name, pe_opt.map(|pe| exp(context, *pe))),
+        PE::Continue(name) => EE::Continue(name),
         PE::Dereference(pe) => EE::Dereference(exp(context, *pe)),
         PE::UnaryExp(op, pe) => EE::UnaryExp(op, exp(context, *pe)),
         PE::BinopExp(pl, op, pr) => {
@@ -2189,18 +2505,9 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
                 EE::UnresolvedError
             }
         },
+        PE::DotCall(plhs, n, ptys_opt, prs) => method_call(context, *plhs, n, ptys_opt, prs),
         PE::Cast(e, ty) => EE::Cast(exp(context, *e), type_(context, ty)),
-        PE::Index(e, i) => {
-            if context.in_spec_context {
-                EE::Index(exp(context, *e), exp(context, *i))
-            } else {
-                let msg = "`_[_]` index operator only allowed in specifications";
-                context
-                    .env
-                    .add_diag(diag!(Syntax::SpecContextRestricted, (loc, msg)));
-                EE::UnresolvedError
-            }
-        }
+        PE::Index(e, i) => index(context, loc, e, i),
         PE::Annotate(e, ty) => EE::Annotate(exp(context, *e), type_(context, ty)),
         PE::Spec(_) if context.in_spec_context => {
             context.env.add_diag(diag!(
@@ -2218,6 +2525,79 @@ fn exp_(context: &mut Context, sp!(loc, pe_): P::Exp) -> E::Exp {
     sp(loc, e_)
 }
 
+// `exp_` recurses once per operand of an operator chain, so the rarer expressions are translated
+// by the functions below, to keep its stack frame small
+
+fn string_literal(context: &mut Context, loc: Loc, s: Symbol) -> E::Exp_ {
+    match string::decode(loc, &s) {
+        Ok(bytes) => E::Exp_::StringLiteral(bytes),
+        Err(e) => {
+            context.env.add_diags(e);
+            E::Exp_::UnresolvedError
+        }
+    }
+}
+
+fn match_(
+    context: &mut Context,
+    psubject: P::Exp,
+    sp!(aloc, parms): Spanned<Vec<P::MatchArm>>,
+) -> E::Exp_ {
+    let subject = exp(context, psubject);
+    let arms: Option<Vec<E::MatchArm>> = parms
+        .into_iter()
+        .map(|parm| match_arm(context, parm))
+        .collect();
+    match arms {
+        Some(arms) => E::Exp_::Match(subject, sp(aloc, arms)),
+        None => {
+            assert!(context.env.has_errors());
+            E::Exp_::UnresolvedError
+        }
+    }
+}
+
+// In specs, indexing is a builtin operation on vectors. Elsewhere, it is a dotted expression, if
+// the index syntax is enabled
+fn index(context: &mut Context, loc: Loc, pe: Box<P::Exp>, pi: Box<P::Exp>) -> E::Exp_ {
+    if context.in_spec_context {
+        return E::Exp_::Index(exp(context, *pe), exp(context, *pi));
+    }
+    if !context.index_syntax_enabled() {
+        let msg = "`_[_]` index operator only allowed in specifications";
+        context
+            .env
+            .add_diag(diag!(Syntax::SpecContextRestricted, (loc, msg)));
+        return E::Exp_::UnresolvedError;
+    }
+    match exp_dotted(context, sp(loc, P::Exp_::Index(pe, pi))) {
+        Some(edotted) => E::Exp_::ExpDotted(Box::new(edotted)),
+        None => {
+            assert!(context.env.has_errors());
+            E::Exp_::UnresolvedError
+        }
+    }
+}
+
+fn method_call(
+    context: &mut Context,
+    plhs: P::Exp,
+    n: Name,
+    ptys_opt: Option<Vec<P::Type>>,
+    sp!(rloc, prs): Spanned<Vec<P::Exp>>,
+) -> E::Exp_ {
+    let lhs_opt = exp_dotted(context, plhs);
+    let tys_opt = optional_types(context, ptys_opt);
+    let ers = sp(rloc, exps(context, prs));
+    match lhs_opt {
+        Some(edotted) => E::Exp_::MethodCall(Box::new(edotted), n, tys_opt, ers),
+        None => {
+            assert!(context.env.has_errors());
+            E::Exp_::UnresolvedError
+        }
+    }
+}
+
 fn exp_dotted(context: &mut Context, sp!(loc, pdotted_): P::Exp) -> Option<E::ExpDotted> {
     use E::ExpDotted_ as EE;
     use P::Exp_ as PE;
@@ -2226,6 +2606,11 @@ fn exp_dotted(context: &mut Context, sp!(loc, pdotted_): P::Exp) -> Option<E::Ex
             let lhs = exp_dotted(context, *plhs)?;
             EE::Dot(Box::new(lhs), field)
         }
+        // in specs, indexing is instead a builtin operation on vectors, handled by `exp_`
+        PE::Index(plhs, pindex) if context.index_syntax_enabled() => {
+            let lhs = exp_dotted(context, *plhs)?;
+            EE::Index(Box::new(lhs), exp(context, *pindex))
+        }
         pe_ => EE::Exp(exp_(context, sp(loc, pe_))),
     };
     Some(sp(loc, edotted_))
@@ -2307,6 +2692,14 @@ fn value(context: &mut Context, sp!(loc, pvalue_): P::Value) -> Option<E::Value>
                 return None;
             }
         },
+        // Outside of expressions (e.g. attributes), string literals are their UTF-8 bytes
+        PV::String(s) => match string::decode(loc, &s) {
+            Ok(v) => EV::Bytearray(v),
+            Err(e) => {
+                context.env.add_diags(e);
+                return None;
+            }
+        },
     };
     Some(sp(loc, value_))
 }
@@ -2354,6 +2747,62 @@ fn fields<T>(
     fmap
 }
 
+//**************************************************************************************************
+// Match
+//**************************************************************************************************
+
+fn match_arm(context: &mut Context, sp!(loc, parm_): P::MatchArm) -> Option<E::MatchArm> {
+    let P::MatchArm_ {
+        pattern: ppattern,
+        guard: pguard,
+        rhs: prhs,
+    } = parm_;
+    let pattern = match_pattern(context, ppattern);
+    let guard = pguard.map(|pguard| exp(context, *pguard));
+    let rhs = exp(context, *prhs);
+    let arm = E::MatchArm_ {
+        pattern: pattern?,
+        guard,
+        rhs,
+    };
+    Some(sp(loc, arm))
+}
+
+fn match_pattern(
+    context: &mut Context,
+    sp!(loc, ppattern_): P::MatchPattern,
+) -> Option<E::MatchPattern> {
+    use E::MatchPattern_ as EP;
+    use P::MatchPattern_ as PP;
+    let pattern_ = match ppattern_ {
+        PP::PositionalConstructor(ptn, ptys_opt, sp!(ploc, ppatterns)) => {
+            let tn = name_access_chain(context, Access::ApplyPositional, ptn)?;
+            let tys_opt = optional_types(context, ptys_opt);
+            let patterns: Option<Vec<E::MatchPattern>> = ppatterns
+                .into_iter()
+                .map(|pp| match_pattern(context, pp))
+                .collect();
+            EP::PositionalConstructor(tn, tys_opt, sp(ploc, patterns?))
+        }
+        PP::FieldConstructor(ptn, ptys_opt, sp!(_, pfields)) => {
+            let tn = name_access_chain(context, Access::ApplyNamed, ptn)?;
+            let tys_opt = optional_types(context, ptys_opt);
+            let vfields: Option<Vec<(Field, E::MatchPattern)>> = pfields
+                .into_iter()
+                .map(|(f, pp)| Some((f, match_pattern(context, pp)?)))
+                .collect();
+            let fields = fields(context, loc, "pattern", "pattern", vfields?);
+            EP::FieldConstructor(tn, tys_opt, fields)
+        }
+        PP::Binder(v) => {
+            check_valid_local_name(context, &v);
+            EP::Binder(v)
+        }
+        PP::Literal(pv) => EP::Literal(value(context, pv)?),
+    };
+    Some(sp(loc, pattern_))
+}
+
 //**************************************************************************************************
 // LValues
 //**************************************************************************************************
@@ -2382,11 +2831,11 @@ fn bind(context: &mut Context, sp!(loc, pb_): P::Bind) -> Option<E::LValue> {
     use E::LValue_ as EL;
     use P::Bind_ as PB;
     let b_ = match pb_ {
-        PB::Var(v) => {
+        PB::Var(mut_, v) => {
             check_valid_local_name(context, &v);
-            EL::Var(sp(loc, E::ModuleAccess_::Name(v.0)), None)
+            EL::Var(mut_, sp(loc, E::ModuleAccess_::Name(v.0)), None)
         }
-        PB::Unpack(ptn, ptys_opt, pfields) => {
+        PB::Unpack(ptn, ptys_opt, P::FieldBindings::Named(pfields)) => {
             let tn = name_access_chain(context, Access::ApplyNamed, *ptn)?;
             let tys_opt = optional_types(context, ptys_opt);
             let vfields: Option<Vec<(Field, E::LValue)>> = pfields
@@ -2394,7 +2843,14 @@ fn bind(context: &mut Context, sp!(loc, pb_): P::Bind) -> Option<E::LValue> {
                 .map(|(f, pb)| Some((f, bind(context, pb)?)))
                 .collect();
             let fields = fields(context, loc, "deconstruction binding", "binding", vfields?);
-            EL::Unpack(tn, tys_opt, fields)
+            EL::Unpack(tn, tys_opt, E::FieldBindings::Named(fields))
+        }
+        PB::Unpack(ptn, ptys_opt, P::FieldBindings::Positional(pbinds)) => {
+            let tn = name_access_chain(context, Access::ApplyPositional, *ptn)?;
+            let tys_opt = optional_types(context, ptys_opt);
+            let binds: Option<Vec<E::LValue>> =
+                pbinds.into_iter().map(|pb| bind(context, pb)).collect();
+            EL::Unpack(tn, tys_opt, E::FieldBindings::Positional(binds?))
         }
     };
     Some(sp(loc, b_))
@@ -2424,6 +2880,10 @@ fn lvalues(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<LValue> {
             let dotted = exp_dotted(context, sp(loc, pdotted_))?;
             L::FieldMutate(Box::new(dotted))
         }
+        pdotted_ @ PE::Index(_, _) if context.index_syntax_enabled() => {
+            let dotted = exp_dotted(context, sp(loc, pdotted_))?;
+            L::FieldMutate(Box::new(dotted))
+        }
         _ => L::Assigns(sp(loc, vec![assign(context, sp(loc, e_))?])),
     };
     Some(al)
@@ -2469,12 +2929,14 @@ fn assign(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<E::LValue> {
         PE::Name(pn, ptys_opt) => {
             let en = name_access_chain(context, Access::Term, pn)?;
             match &en.value {
-                E::ModuleAccess_::ModuleAccess(m, n) if !context.in_spec_context => {
+                E::ModuleAccess_::ModuleAccess(..) | E::ModuleAccess_::Variant(..)
+                    if !context.in_spec_context =>
+                {
                     let msg = format!(
                         "Unexpected assignment of module access without fields outside of a spec \
                          context.\nIf you are trying to unpack a struct, try adding fields, e.g. \
-                         '{}::{} {{}}'",
-                        m, n,
+                         '{} {{}}'",
+                        en.value,
                     );
                     context
                         .env
@@ -2483,7 +2945,7 @@ fn assign(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<E::LValue> {
                 }
                 _ => {
                     let tys_opt = optional_types(context, ptys_opt);
-                    EL::Var(en, tys_opt)
+                    EL::Var(None, en, tys_opt)
                 }
             }
         }
@@ -2491,7 +2953,20 @@ fn assign(context: &mut Context, sp!(loc, e_): P::Exp) -> Option<E::LValue> {
             let en = name_access_chain(context, Access::ApplyNamed, pn)?;
             let tys_opt = optional_types(context, ptys_opt);
             let efields = assign_unpack_fields(context, loc, pfields)?;
-            EL::Unpack(en, tys_opt, efields)
+            EL::Unpack(en, tys_opt, E::FieldBindings::Named(efields))
+        }
+        // an assignment to a call, `S(a1, ..., an) = e`, is a deconstructing assignment of a
+        // struct with positional fields
+        PE::Call(pn, false, ptys_opt, sp!(_, pargs))
+            if context
+                .env
+                .supports_feature(context.current_package, &FeatureGate::PositionalFields) =>
+        {
+            let en = name_access_chain(context, Access::ApplyPositional, pn)?;
+            let tys_opt = optional_types(context, ptys_opt);
+            let eargs: Option<Vec<E::LValue>> =
+                pargs.into_iter().map(|e| assign(context, e)).collect();
+            EL::Unpack(en, tys_opt, E::FieldBindings::Positional(eargs?))
         }
         _ => {
             context.env.add_diag(diag!(
@@ -2565,10 +3040,12 @@ fn unbound_names_exp(unbound: &mut BTreeSet<Name>, sp!(_, e_): &E::Exp) {
     use E::Exp_ as EE;
     match e_ {
         EE::Value(_)
-        | EE::Break
-        | EE::Continue
+        | EE::StringLiteral(_)
+        | EE::Break(_, None)
+        | EE::Continue(_)
         | EE::UnresolvedError
         | EE::Name(sp!(_, E::ModuleAccess_::ModuleAccess(..)), _)
+        | EE::Name(sp!(_, E::ModuleAccess_::Variant(..)), _)
         | EE::Unit { .. } => (),
         EE::Copy(v) | EE::Move(v) => {
             unbound.insert(v.0);
@@ -2579,17 +3056,34 @@ fn unbound_names_exp(unbound: &mut BTreeSet<Name>, sp!(_, e_): &E::Exp) {
         EE::Call(_, _, _, sp!(_, es_)) | EE::Vector(_, _, sp!(_, es_)) => {
             unbound_names_exps(unbound, es_)
         }
+        EE::MethodCall(ed, _, _, sp!(_, es_)) => {
+            unbound_names_exps(unbound, es_);
+            unbound_names_dotted(unbound, ed)
+        }
         EE::Pack(_, _, es) => unbound_names_exps(unbound, es.iter().map(|(_, _, (_, e))| e)),
         EE::IfElse(econd, et, ef) => {
             unbound_names_exp(unbound, ef);
             unbound_names_exp(unbound, et);
             unbound_names_exp(unbound, econd)
         }
-        EE::While(econd, eloop) => {
+        EE::While(_, econd, eloop) => {
             unbound_names_exp(unbound, eloop);
             unbound_names_exp(unbound, econd)
         }
-        EE::Loop(eloop) => unbound_names_exp(unbound, eloop),
+        EE::Loop(_, eloop) => unbound_names_exp(unbound, eloop),
+        EE::Match(esubject, sp!(_, arms)) => {
+            for sp!(_, arm) in arms {
+                let mut arm_unbound = BTreeSet::new();
+                unbound_names_exp(&mut arm_unbound, &arm.rhs);
+                if let Some(guard) = &arm.guard {
+                    unbound_names_exp(&mut arm_unbound, guard);
+                }
+                // remove anything bound by the pattern
+                unbound_names_pattern(&mut arm_unbound, &arm.pattern);
+                unbound.extend(arm_unbound);
+            }
+            unbound_names_exp(unbound, esubject)
+        }
 
         EE::Block(seq) => unbound_names_sequence(unbound, seq),
         EE::Lambda(ls, er) => {
@@ -2615,6 +3109,7 @@ fn unbound_names_exp(unbound: &mut BTreeSet<Name>, sp!(_, e_): &E::Exp) {
         }
         EE::Return(e)
         | EE::Abort(e)
+        | EE::Break(_, Some(e))
         | EE::Dereference(e)
         | EE::UnaryExp(_, e)
         | EE::Borrow(_, e)
@@ -2681,15 +3176,35 @@ fn unbound_names_binds_with_range(
 fn unbound_names_bind(unbound: &mut BTreeSet<Name>, sp!(_, l_): &E::LValue) {
     use E::LValue_ as EL;
     match l_ {
-        EL::Var(sp!(_, E::ModuleAccess_::Name(n)), _) => {
+        EL::Var(_, sp!(_, E::ModuleAccess_::Name(n)), _) => {
             unbound.remove(n);
         }
-        EL::Var(sp!(_, E::ModuleAccess_::ModuleAccess(..)), _) => {
+        EL::Var(_, sp!(_, E::ModuleAccess_::ModuleAccess(..)), _)
+        | EL::Var(_, sp!(_, E::ModuleAccess_::Variant(..)), _) => {
             // Qualified vars are not considered in unbound set.
         }
-        EL::Unpack(_, _, efields) => efields
+        EL::Unpack(_, _, E::FieldBindings::Named(efields)) => efields
             .iter()
             .for_each(|(_, _, (_, l))| unbound_names_bind(unbound, l)),
+        EL::Unpack(_, _, E::FieldBindings::Positional(lvalues)) => {
+            lvalues.iter().for_each(|l| unbound_names_bind(unbound, l))
+        }
+    }
+}
+
+fn unbound_names_pattern(unbound: &mut BTreeSet<Name>, sp!(_, p_): &E::MatchPattern) {
+    use E::MatchPattern_ as EP;
+    match p_ {
+        EP::Binder(v) => {
+            unbound.remove(&v.0);
+        }
+        EP::Literal(_) => (),
+        EP::PositionalConstructor(_, _, sp!(_, patterns)) => patterns
+            .iter()
+            .for_each(|p| unbound_names_pattern(unbound, p)),
+        EP::FieldConstructor(_, _, fields) => fields
+            .iter()
+            .for_each(|(_, _, (_, p))| unbound_names_pattern(unbound, p)),
     }
 }
 
@@ -2702,15 +3217,19 @@ fn unbound_names_assigns(unbound: &mut BTreeSet<Name>, sp!(_, ls_): &E::LValueLi
 fn unbound_names_assign(unbound: &mut BTreeSet<Name>, sp!(_, l_): &E::LValue) {
     use E::LValue_ as EL;
     match l_ {
-        EL::Var(sp!(_, E::ModuleAccess_::Name(n)), _) => {
+        EL::Var(_, sp!(_, E::ModuleAccess_::Name(n)), _) => {
             unbound.insert(*n);
         }
-        EL::Var(sp!(_, E::ModuleAccess_::ModuleAccess(..)), _) => {
+        EL::Var(_, sp!(_, E::ModuleAccess_::ModuleAccess(..)), _)
+        | EL::Var(_, sp!(_, E::ModuleAccess_::Variant(..)), _) => {
             // Qualified vars are not considered in unbound set.
         }
-        EL::Unpack(_, _, efields) => efields
+        EL::Unpack(_, _, E::FieldBindings::Named(efields)) => efields
             .iter()
             .for_each(|(_, _, (_, l))| unbound_names_assign(unbound, l)),
+        EL::Unpack(_, _, E::FieldBindings::Positional(lvalues)) => lvalues
+            .iter()
+            .for_each(|l| unbound_names_assign(unbound, l)),
     }
 }
 
@@ -2719,6 +3238,10 @@ fn unbound_names_dotted(unbound: &mut BTreeSet<Name>, sp!(_, edot_): &E::ExpDott
     match edot_ {
         ED::Exp(e) => unbound_names_exp(unbound, e),
         ED::Dot(d, _) => unbound_names_dotted(unbound, d),
+        ED::Index(d, i) => {
+            unbound_names_dotted(unbound, d);
+            unbound_names_exp(unbound, i)
+        }
     }
 }
 
@@ -2759,6 +3282,7 @@ enum ModuleMemberKind {
     Constant,
     Function,
     Struct,
+    Enum,
     Schema,
 }
 
@@ -2768,6 +3292,7 @@ impl ModuleMemberKind {
             ModuleMemberKind::Constant => NameCase::Constant,
             ModuleMemberKind::Function => NameCase::Function,
             ModuleMemberKind::Struct => NameCase::Struct,
+            ModuleMemberKind::Enum => NameCase::Enum,
             ModuleMemberKind::Schema => NameCase::Schema,
         }
     }
@@ -2778,6 +3303,8 @@ enum NameCase {
     Constant,
     Function,
     Struct,
+    Enum,
+    Variant,
     Schema,
     Module,
     ModuleMemberAlias(ModuleMemberKind),
@@ -2792,11 +3319,14 @@ impl NameCase {
             NameCase::Constant => "constant",
             NameCase::Function => "function",
             NameCase::Struct => "struct",
+            NameCase::Enum => "enum",
+            NameCase::Variant => "variant",
             NameCase::Schema => "schema",
             NameCase::Module => "module",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Function) => "function alias",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Constant) => "constant alias",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Struct) => "struct alias",
+            NameCase::ModuleMemberAlias(ModuleMemberKind::Enum) => "enum alias",
             NameCase::ModuleMemberAlias(ModuleMemberKind::Schema) => "schema alias",
             NameCase::ModuleAlias => "module alias",
             NameCase::Variable => "variable",
@@ -2861,7 +3391,7 @@ fn check_valid_module_member_name_impl(
                 return Err(());
             }
         }
-        M::Constant | M::Struct | M::Schema => {
+        M::Constant | M::Struct | M::Enum | M::Schema => {
             if !is_valid_struct_constant_or_schema_name(&n.value) {
                 let msg = format!(
                     "Invalid {} name '{}'. {} names must start with 'A'..'Z'",
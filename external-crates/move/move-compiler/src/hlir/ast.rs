@@ -7,7 +7,7 @@ use crate::{
     expansion::ast::{
         ability_modifiers_ast_debug, AbilitySet, Attributes, Friend, ModuleIdent, SpecId,
     },
-    naming::ast::{BuiltinTypeName, BuiltinTypeName_, StructTypeParameter, TParam},
+    naming::ast::{BlockLabel, BuiltinTypeName, BuiltinTypeName_, StructTypeParameter, TParam},
     parser::ast::{
         self as P, BinOp, ConstantName, Field, FunctionName, StructName, UnaryOp, ENTRY_MODIFIER,
     },
@@ -197,10 +197,12 @@ pub enum Statement_ {
         else_block: Block,
     },
     While {
+        name: BlockLabel,
         cond: (Block, Box<Exp>),
         block: Block,
     },
     Loop {
+        name: BlockLabel,
         block: Block,
         has_break: bool,
     },
@@ -230,8 +232,8 @@ pub enum Command_ {
         from_user: bool,
         exp: Exp,
     },
-    Break,
-    Continue,
+    Break(BlockLabel),
+    Continue(BlockLabel),
     IgnoreAndPop {
         pop_num: usize,
         exp: Exp,
@@ -421,7 +423,7 @@ impl Command_ {
     pub fn is_terminal(&self) -> bool {
         use Command_::*;
         match self {
-            Break | Continue => panic!("ICE break/continue not translated to jumps"),
+            Break(_) | Continue(_) => panic!("ICE break/continue not translated to jumps"),
             Assign(_, _) | Mutate(_, _) | IgnoreAndPop { .. } => false,
             Abort(_) | Return { .. } | Jump { .. } | JumpIf { .. } => true,
         }
@@ -430,7 +432,7 @@ impl Command_ {
     pub fn is_exit(&self) -> bool {
         use Command_::*;
         match self {
-            Break | Continue => panic!("ICE break/continue not translated to jumps"),
+            Break(_) | Continue(_) => panic!("ICE break/continue not translated to jumps"),
             Assign(_, _) | Mutate(_, _) | IgnoreAndPop { .. } | Jump { .. } | JumpIf { .. } => {
                 false
             }
@@ -441,7 +443,7 @@ impl Command_ {
     pub fn is_unit(&self) -> bool {
         use Command_::*;
         match self {
-            Break | Continue => panic!("ICE break/continue not translated to jumps"),
+            Break(_) | Continue(_) => panic!("ICE break/continue not translated to jumps"),
             Assign(ls, e) => ls.is_empty() && e.is_unit(),
             IgnoreAndPop { exp: e, .. } => e.is_unit(),
 
@@ -454,7 +456,7 @@ impl Command_ {
 
         let mut successors = BTreeSet::new();
         match self {
-            Break | Continue => panic!("ICE break/continue not translated to jumps"),
+            Break(_) | Continue(_) => panic!("ICE break/continue not translated to jumps"),
             Mutate(_, _) | Assign(_, _) | IgnoreAndPop { .. } => {
                 panic!("ICE Should not be last command in block")
             }
@@ -1136,14 +1138,20 @@ impl AstDebug for Statement_ {
                 w.write(" else ");
                 w.block(|w| else_block.ast_debug(w));
             }
-            S::While { cond, block } => {
-                w.write("while (");
+            S::While { name, cond, block } => {
+                name.ast_debug(w);
+                w.write(": while (");
                 cond.ast_debug(w);
                 w.write(")");
                 w.block(|w| block.ast_debug(w))
             }
-            S::Loop { block, has_break } => {
-                w.write("loop");
+            S::Loop {
+                name,
+                block,
+                has_break,
+            } => {
+                name.ast_debug(w);
+                w.write(": loop");
                 if *has_break {
                     w.write("#has_break");
                 }
@@ -1181,8 +1189,14 @@ impl AstDebug for Command_ {
                 w.write("return ");
                 e.ast_debug(w);
             }
-            C::Break => w.write("break"),
-            C::Continue => w.write("continue"),
+            C::Break(name) => {
+                w.write("break ");
+                name.ast_debug(w);
+            }
+            C::Continue(name) => {
+                w.write("continue ");
+                name.ast_debug(w);
+            }
             C::IgnoreAndPop { pop_num, exp } => {
                 w.write("pop ");
                 w.comma(0..*pop_num, |w, _| w.write("_"));
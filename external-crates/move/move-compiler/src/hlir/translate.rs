@@ -84,6 +84,9 @@ struct Context<'env> {
     function_locals: UniqueMap<H::Var, H::SingleType>,
     signature: Option<H::FunctionSignature>,
     tmp_counter: usize,
+    /// The loops around the expression being translated, with their type and the temporaries
+    /// their 'break' values are bound to
+    loop_results: BTreeMap<N::BlockLabel, (H::Type, Vec<(H::Var, H::SingleType)>)>,
     /// collects all struct fields used in the current module
     pub used_fields: BTreeMap<Symbol, BTreeSet<Symbol>>,
 }
@@ -130,6 +133,7 @@ impl<'env> Context<'env> {
             function_locals: UniqueMap::new(),
             signature: None,
             tmp_counter: 0,
+            loop_results: BTreeMap::new(),
             used_fields: BTreeMap::new(),
         }
     }
@@ -327,7 +331,7 @@ fn function_signature(context: &mut Context, sig: N::FunctionSignature) -> H::Fu
     let parameters = sig
         .parameters
         .into_iter()
-        .map(|(v, tty)| {
+        .map(|(_, v, tty)| {
             let ty = single_type(context, tty);
             context.bind_local(v, ty.clone());
             (translate_var(v), ty)
@@ -452,6 +456,7 @@ fn struct_def(
         warning_filter,
         index,
         attributes,
+        doc: _,
         abilities,
         type_parameters,
         fields,
@@ -649,28 +654,45 @@ fn statement(context: &mut Context, result: &mut Block, e: T::Exp) {
                 else_block,
             }
         }
-        TE::While(tb, loop_body) => {
+        TE::While(name, tb, loop_body) => {
             let mut cond_block = Block::new();
             let cond_exp = exp(context, &mut cond_block, None, *tb);
 
-            let mut loop_block = Block::new();
-            let el = exp_(context, &mut loop_block, None, *loop_body);
-            ignore_and_pop(&mut loop_block, el);
+            let unit = sp(eloc, H::Type_::Unit);
+            let loop_block = statement_loop_body(context, name, unit, vec![], *loop_body);
 
             S::While {
+                name,
                 cond: (cond_block, cond_exp),
                 block: loop_block,
             }
         }
         TE::Loop {
+            name,
             body: loop_body,
             has_break,
         } => {
-            let loop_block = statement_loop_body(context, *loop_body);
-
-            S::Loop {
+            let loop_ty = type_(context, ty);
+            let tmps = if has_break {
+                make_temps(context, eloc, loop_ty.clone())
+            } else {
+                vec![]
+            };
+            let loop_block =
+                statement_loop_body(context, name, loop_ty.clone(), tmps.clone(), *loop_body);
+            let loop_ = S::Loop {
+                name,
                 block: loop_block,
                 has_break,
+            };
+            if tmps.is_empty() {
+                loop_
+            } else {
+                // the value the loop is exited with is unused
+                result.push_back(sp(eloc, loop_));
+                let eresult = H::exp(loop_ty, sp(eloc, use_tmps(tmps)));
+                ignore_and_pop(result, eresult);
+                return;
             }
         }
         TE::Block(seq) => {
@@ -688,10 +710,19 @@ fn statement(context: &mut Context, result: &mut Block, e: T::Exp) {
     result.push_back(sp(eloc, stmt_))
 }
 
-fn statement_loop_body(context: &mut Context, body: T::Exp) -> Block {
+// Translates the body of loop `name`, of type `loop_ty`. Its 'break' values are bound to `tmps`
+fn statement_loop_body(
+    context: &mut Context,
+    name: N::BlockLabel,
+    loop_ty: H::Type,
+    tmps: Vec<(H::Var, H::SingleType)>,
+    body: T::Exp,
+) -> Block {
+    context.loop_results.insert(name, (loop_ty, tmps));
     let mut loop_block = Block::new();
     let el = exp_(context, &mut loop_block, None, body);
     ignore_and_pop(&mut loop_block, el);
+    context.loop_results.remove(&name);
     loop_block
 }
 
@@ -1136,15 +1167,14 @@ fn exp_impl(
 
     let res = match e_ {
         // Statement-like expressions
-        TE::While(tb, loop_body) => {
+        TE::While(name, tb, loop_body) => {
             let mut cond_block = Block::new();
             let cond_exp = exp(context, &mut cond_block, None, *tb);
 
-            let mut loop_block = Block::new();
-            let el = exp_(context, &mut loop_block, None, *loop_body);
-            ignore_and_pop(&mut loop_block, el);
+            let loop_block = statement_loop_body(context, name, ty.clone(), vec![], *loop_body);
 
             let s_ = S::While {
+                name,
                 cond: (cond_block, cond_exp),
                 block: loop_block,
             };
@@ -1154,22 +1184,32 @@ fn exp_impl(
             }
         }
         TE::Loop {
+            name,
             has_break,
             body: loop_body,
         } => {
-            let loop_block = statement_loop_body(context, *loop_body);
+            let tmps = if has_break {
+                make_temps(context, eloc, ty.clone())
+            } else {
+                vec![]
+            };
+            let loop_block =
+                statement_loop_body(context, name, ty.clone(), tmps.clone(), *loop_body);
 
             let s_ = S::Loop {
+                name,
                 block: loop_block,
                 has_break,
             };
             result.push_back(sp(eloc, s_));
             if !has_break {
                 HE::Unreachable
-            } else {
+            } else if tmps.is_empty() {
                 HE::Unit {
                     case: H::UnitCase::Implicit,
                 }
+            } else {
+                use_tmps(tmps)
             }
         }
         TE::Block(seq) => return block(context, result, eloc, None, seq),
@@ -1194,13 +1234,18 @@ fn exp_impl(
             result.push_back(sp(eloc, S::Command(c)));
             HE::Unreachable
         }
-        TE::Break => {
-            let c = sp(eloc, C::Break);
+        TE::Break(name, tvalue_opt) => {
+            if let Some(tvalue) = tvalue_opt {
+                let (loop_ty, tmps) = context.loop_results[&name].clone();
+                let value = exp_(context, result, Some(&loop_ty), *tvalue);
+                bind_exp_(result, eloc, tmps, value);
+            }
+            let c = sp(eloc, C::Break(name));
             result.push_back(sp(eloc, S::Command(c)));
             HE::Unreachable
         }
-        TE::Continue => {
-            let c = sp(eloc, C::Continue);
+        TE::Continue(name) => {
+            let c = sp(eloc, C::Continue(name));
             result.push_back(sp(eloc, S::Command(c)));
             HE::Unreachable
         }
@@ -1471,7 +1516,7 @@ fn struct_name(sp!(_, t): &H::Type) -> Option<StructName> {
         H::SingleType_::Base(bt) => bt,
         H::SingleType_::Ref(_, bt) => bt,
     };
-    let H::BaseType_::Apply(_, tname ,_ ) = &bt.value else {
+    let H::BaseType_::Apply(_, tname, _) = &bt.value else {
         return None;
     };
     if let H::TypeName_::ModuleType(_, struct_name) = tname.value {
@@ -1580,7 +1625,11 @@ fn bind_exp_impl_(
         .collect();
     let asgn = sp(loc, C::Assign(lvalues, Box::new(e)));
     result.push_back(sp(loc, S::Command(asgn)));
+    use_tmps(tmps)
+}
 
+fn use_tmps(tmps: Vec<(H::Var, H::SingleType)>) -> H::UnannotatedExp_ {
+    use H::UnannotatedExp_ as E;
     let mut etemps = tmps
         .into_iter()
         .map(|(var, st)| {
@@ -1823,10 +1872,10 @@ fn bind_for_short_circuit(e: &T::Exp) -> bool {
         TE::Block(seq) => bind_for_short_circuit_sequence(seq),
         TE::Annotate(el, _) => bind_for_short_circuit(el),
 
-        TE::Break
-        | TE::Continue
+        TE::Break(_, _)
+        | TE::Continue(_)
         | TE::IfElse(_, _, _)
-        | TE::While(_, _)
+        | TE::While(_, _, _)
         | TE::Loop { .. }
         | TE::Return(_)
         | TE::Abort(_)
@@ -1926,8 +1975,8 @@ fn check_trailing_unit(context: &mut Context, block: &mut Block) {
     fn divergent_block(block: &Block) -> bool {
         matches!(
             block.back(),
-            Some(hcmd!(_, C::Break))
-                | Some(hcmd!(_, C::Continue))
+            Some(hcmd!(_, C::Break(_)))
+                | Some(hcmd!(_, C::Continue(_)))
                 | Some(hcmd!(_, C::Abort(_)))
                 | Some(hcmd!(_, C::Return { .. }))
                 | Some(hignored!(_, E::Unreachable))
@@ -1987,10 +2036,10 @@ fn check_trailing_unit(context: &mut Context, block: &mut Block) {
         {
             invalid_trailing_unit!(context, *loc, *uloc)
         }
-        (hcmd!(loc, C::Break), trailing!(uloc))
-        | (hcmd!(loc, C::Break), trailing_returned!(uloc))
-        | (hcmd!(loc, C::Continue), trailing!(uloc))
-        | (hcmd!(loc, C::Continue), trailing_returned!(uloc))
+        (hcmd!(loc, C::Break(_)), trailing!(uloc))
+        | (hcmd!(loc, C::Break(_)), trailing_returned!(uloc))
+        | (hcmd!(loc, C::Continue(_)), trailing!(uloc))
+        | (hcmd!(loc, C::Continue(_)), trailing_returned!(uloc))
         | (hcmd!(loc, C::Abort(_)), trailing!(uloc))
         | (hcmd!(loc, C::Abort(_)), trailing_returned!(uloc))
         | (hcmd!(loc, C::Return { .. }), trailing!(uloc))
@@ -2018,6 +2067,7 @@ fn check_trailing_unit_statement(context: &mut Context, sp!(_, s_): &mut H::Stat
         S::While {
             cond: (cond_block, _),
             block,
+            ..
         } => {
             check_trailing_unit(context, cond_block);
             check_trailing_unit(context, block)
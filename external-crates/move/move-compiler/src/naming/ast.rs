@@ -6,10 +6,11 @@ use crate::{
     diagnostics::WarningFilters,
     expansion::ast::{
         ability_constraints_ast_debug, ability_modifiers_ast_debug, AbilitySet, Attributes, Fields,
-        Friend, ModuleIdent, SpecId, Value, Value_, Visibility,
+        Friend, ModuleIdent, ModuleIdent_, SpecId, Value, Value_, Visibility,
     },
     parser::ast::{
-        Ability_, BinOp, ConstantName, Field, FunctionName, StructName, UnaryOp, ENTRY_MODIFIER,
+        Ability_, BinOp, ConstantName, DocComment, EnumName, Field, FunctionName, Mutability,
+        StructName, UnaryOp, VariantName, ENTRY_MODIFIER, MACRO_MODIFIER,
     },
     shared::{ast_debug::*, unique_map::UniqueMap, *},
 };
@@ -67,15 +68,57 @@ pub struct ModuleDefinition {
     // package name metadata from compiler arguments, not used for any language rules
     pub package_name: Option<Symbol>,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub is_source_module: bool,
     pub friends: UniqueMap<ModuleIdent, Friend>,
     pub structs: UniqueMap<StructName, StructDefinition>,
+    pub enums: UniqueMap<EnumName, EnumDefinition>,
     pub constants: UniqueMap<ConstantName, Constant>,
     pub functions: UniqueMap<FunctionName, Function>,
+    // functions declared with `#[syntax(...)]`, by the struct they implement the syntax for
+    pub syntax_methods: SyntaxMethods,
+    // method aliases declared with `use fun`
+    pub use_funs: UseFuns,
     // module dependencies referenced in specs
     pub spec_dependencies: BTreeSet<(ModuleIdent, Neighbor)>,
 }
 
+//**************************************************************************************************
+// Syntax Methods
+//**************************************************************************************************
+
+pub type SyntaxMethods = BTreeMap<StructName, SyntaxMethod>;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SyntaxMethod {
+    pub index: IndexSyntaxMethods,
+}
+
+/// The functions `e[i]` resolves to when `e` is (a reference to) an instance of the struct. The
+/// function taking `&S` is used in immutable contexts, e.g. `&e[i]` or `e[i].f`, and the one
+/// taking `&mut S` in mutable ones, e.g. `&mut e[i]` or `e[i] = v`
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct IndexSyntaxMethods {
+    pub index: Option<FunctionName>,
+    pub index_mut: Option<FunctionName>,
+}
+
+//**************************************************************************************************
+// Use Funs
+//**************************************************************************************************
+
+/// The method aliases declared in a module, by the struct they are declared for, and then by the
+/// name of the method. They take precedence over the functions of the module declaring the struct
+pub type UseFuns = BTreeMap<(ModuleIdent_, Symbol), BTreeMap<Symbol, UseFun>>;
+
+/// `use fun m::f as ty.method`, where `m::f` takes (a reference to) `ty` as first parameter
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UseFun {
+    pub loc: Loc,
+    pub module: ModuleIdent,
+    pub function: FunctionName,
+}
+
 //**************************************************************************************************
 // Structs
 //**************************************************************************************************
@@ -86,6 +129,7 @@ pub struct StructDefinition {
     // index in the original order as defined in the source file
     pub index: usize,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub abilities: AbilitySet,
     pub type_parameters: Vec<StructTypeParameter>,
     pub fields: StructFields,
@@ -103,6 +147,38 @@ pub enum StructFields {
     Native(Loc),
 }
 
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EnumDefinition {
+    pub warning_filter: WarningFilters,
+    // index in the original order as defined in the source file
+    pub index: usize,
+    pub attributes: Attributes,
+    pub doc: DocComment,
+    pub loc: Loc,
+    pub abilities: AbilitySet,
+    pub type_parameters: Vec<StructTypeParameter>,
+    pub variants: UniqueMap<VariantName, VariantDefinition>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VariantDefinition {
+    // index in the original order as defined in the source file
+    pub index: usize,
+    pub loc: Loc,
+    pub fields: VariantFields,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VariantFields {
+    // positional fields are named by their index, as for structs
+    Defined(Fields<Type>),
+    Empty,
+}
+
 //**************************************************************************************************
 // Functions
 //**************************************************************************************************
@@ -110,7 +186,7 @@ pub enum StructFields {
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct FunctionSignature {
     pub type_parameters: Vec<TParam>,
-    pub parameters: Vec<(Var, Type)>,
+    pub parameters: Vec<(Mutability, Var, Type)>,
     pub return_type: Type,
 }
 
@@ -127,8 +203,10 @@ pub struct Function {
     // index in the original order as defined in the source file
     pub index: usize,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub visibility: Visibility,
     pub entry: Option<Loc>,
+    pub macro_: Option<Loc>,
     pub signature: FunctionSignature,
     pub acquires: BTreeMap<StructName, Loc>,
     pub body: FunctionBody,
@@ -144,6 +222,7 @@ pub struct Constant {
     // index in the original order as defined in the source file
     pub index: usize,
     pub attributes: Attributes,
+    pub doc: DocComment,
     pub loc: Loc,
     pub signature: Type,
     pub value: Exp,
@@ -226,21 +305,69 @@ pub struct Var_ {
 }
 pub type Var = Spanned<Var_>;
 
+/// The label of a loop. Every loop gets one in naming, even if it has none in the source, so that
+/// each `break` and `continue` refers to the loop it exits or restarts.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, PartialOrd, Ord)]
+pub struct BlockLabel(pub Var);
+
 #[derive(Debug, PartialEq, Clone)]
 #[allow(clippy::large_enum_variant)]
 pub enum LValue_ {
     Ignore,
-    Var { var: Var, unused_binding: bool },
+    Var {
+        mut_: Mutability,
+        var: Var,
+        unused_binding: bool,
+    },
     Unpack(ModuleIdent, StructName, Option<Vec<Type>>, Fields<LValue>),
+    UnpackVariant(
+        ModuleIdent,
+        EnumName,
+        VariantName,
+        Option<Vec<Type>>,
+        Fields<LValue>,
+    ),
 }
 pub type LValue = Spanned<LValue_>;
 pub type LValueList_ = Vec<LValue>;
 pub type LValueList = Spanned<LValueList_>;
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum MatchPattern_ {
+    Constructor(
+        ModuleIdent,
+        StructName,
+        Option<Vec<Type>>,
+        Fields<MatchPattern>,
+    ),
+    Variant(
+        ModuleIdent,
+        EnumName,
+        VariantName,
+        Option<Vec<Type>>,
+        Fields<MatchPattern>,
+    ),
+    Binder(Var),
+    Literal(Value),
+    Wildcard,
+    ErrorPat,
+}
+pub type MatchPattern = Spanned<MatchPattern_>;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm_ {
+    pub pattern: MatchPattern,
+    pub guard: Option<Box<Exp>>,
+    pub rhs: Box<Exp>,
+}
+pub type MatchArm = Spanned<MatchArm_>;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum ExpDotted_ {
     Exp(Box<Exp>),
     Dot(Box<ExpDotted>, Field),
+    // resolved to a vector borrow, or to the index syntax method of the struct, during typing
+    Index(Box<ExpDotted>, Box<Exp>),
 }
 pub type ExpDotted = Spanned<ExpDotted_>;
 
@@ -271,12 +398,23 @@ pub enum Exp_ {
         Option<Vec<Type>>,
         Spanned<Vec<Exp>>,
     ),
+    // m::f!(args), replaced by the body of the macro m::f at the end of naming
+    MacroCall(
+        ModuleIdent,
+        FunctionName,
+        Option<Vec<Type>>,
+        Spanned<Vec<Exp>>,
+    ),
+    // the expanded body of a macro call. Each type parameter of the macro is replaced by a type
+    // argument (a type variable if none was given), listed with the constraints of the parameter
+    MacroExpansion(Vec<(Type, AbilitySet)>, Box<Exp>),
     Builtin(BuiltinFunction, Spanned<Vec<Exp>>),
     Vector(Loc, Option<Type>, Spanned<Vec<Exp>>),
 
     IfElse(Box<Exp>, Box<Exp>, Box<Exp>),
-    While(Box<Exp>, Box<Exp>),
-    Loop(Box<Exp>),
+    While(BlockLabel, Box<Exp>, Box<Exp>),
+    Loop(BlockLabel, Box<Exp>),
+    Match(Box<Exp>, Spanned<Vec<MatchArm>>),
     Block(Sequence),
 
     Assign(LValueList, Box<Exp>),
@@ -285,14 +423,21 @@ pub enum Exp_ {
 
     Return(Box<Exp>),
     Abort(Box<Exp>),
-    Break,
-    Continue,
+    Break(BlockLabel, Option<Box<Exp>>),
+    Continue(BlockLabel),
 
     Dereference(Box<Exp>),
     UnaryExp(UnaryOp, Box<Exp>),
     BinopExp(Box<Exp>, BinOp, Box<Exp>),
 
     Pack(ModuleIdent, StructName, Option<Vec<Type>>, Fields<Exp>),
+    PackVariant(
+        ModuleIdent,
+        EnumName,
+        VariantName,
+        Option<Vec<Type>>,
+        Fields<Exp>,
+    ),
     ExpList(Vec<Exp>),
     Unit {
         trailing: bool,
@@ -300,6 +445,8 @@ pub enum Exp_ {
 
     DerefBorrow(ExpDotted),
     Borrow(bool, ExpDotted),
+    // e.f(args), where f is resolved against the type of e during typing
+    MethodCall(ExpDotted, Name, Option<Vec<Type>>, Spanned<Vec<Exp>>),
 
     Cast(Box<Exp>, Type),
     Annotate(Box<Exp>, Type),
@@ -785,11 +932,15 @@ impl AstDebug for ModuleDefinition {
             warning_filter,
             package_name,
             attributes,
+            doc: _doc,
             is_source_module,
             friends,
             structs,
+            enums,
             constants,
             functions,
+            syntax_methods,
+            use_funs,
             spec_dependencies,
         } = self;
         warning_filter.ast_debug(w);
@@ -811,10 +962,33 @@ impl AstDebug for ModuleDefinition {
             w.write(&format!("friend {};", mident));
             w.new_line();
         }
+        for (sname, method) in syntax_methods {
+            let IndexSyntaxMethods { index, index_mut } = &method.index;
+            if let Some(f) = index {
+                w.writeln(&format!("syntax(index) {sname} = {f};"));
+            }
+            if let Some(f) = index_mut {
+                w.writeln(&format!("syntax(index) mut {sname} = {f};"));
+            }
+        }
+        for ((m, sname), methods) in use_funs {
+            for (method, use_fun) in methods {
+                let UseFun {
+                    module, function, ..
+                } = use_fun;
+                w.writeln(&format!(
+                    "use fun {module}::{function} as {m}::{sname}.{method};"
+                ));
+            }
+        }
         for sdef in structs.key_cloned_iter() {
             sdef.ast_debug(w);
             w.new_line();
         }
+        for edef in enums.key_cloned_iter() {
+            edef.ast_debug(w);
+            w.new_line();
+        }
         for cdef in constants.key_cloned_iter() {
             cdef.ast_debug(w);
             w.new_line();
@@ -834,6 +1008,7 @@ impl AstDebug for (StructName, &StructDefinition) {
                 warning_filter,
                 index,
                 attributes,
+                doc: _doc,
                 abilities,
                 type_parameters,
                 fields,
@@ -860,6 +1035,45 @@ impl AstDebug for (StructName, &StructDefinition) {
     }
 }
 
+impl AstDebug for (EnumName, &EnumDefinition) {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        let (
+            name,
+            EnumDefinition {
+                warning_filter,
+                index,
+                attributes,
+                doc: _doc,
+                loc: _loc,
+                abilities,
+                type_parameters,
+                variants,
+            },
+        ) = self;
+        warning_filter.ast_debug(w);
+        attributes.ast_debug(w);
+        w.write(&format!("enum#{index} {name}"));
+        type_parameters.ast_debug(w);
+        ability_modifiers_ast_debug(w, abilities);
+        w.block(|w| {
+            w.list(variants, ",", |w, (_, v, vdef)| {
+                w.write(&format!("{}#{}", vdef.index, v));
+                if let VariantFields::Defined(fields) = &vdef.fields {
+                    w.block(|w| {
+                        w.list(fields, ",", |w, (_, f, idx_st)| {
+                            let (idx, st) = idx_st;
+                            w.write(&format!("{}#{}: ", idx, f));
+                            st.ast_debug(w);
+                            true
+                        })
+                    })
+                }
+                true
+            })
+        })
+    }
+}
+
 impl AstDebug for (FunctionName, &Function) {
     fn ast_debug(&self, w: &mut AstWriter) {
         let (
@@ -868,8 +1082,10 @@ impl AstDebug for (FunctionName, &Function) {
                 warning_filter,
                 index,
                 attributes,
+                doc: _doc,
                 visibility,
                 entry,
+                macro_,
                 signature,
                 acquires,
                 body,
@@ -881,6 +1097,9 @@ impl AstDebug for (FunctionName, &Function) {
         if entry.is_some() {
             w.write(&format!("{} ", ENTRY_MODIFIER));
         }
+        if macro_.is_some() {
+            w.write(&format!("{} ", MACRO_MODIFIER));
+        }
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
         }
@@ -907,7 +1126,10 @@ impl AstDebug for FunctionSignature {
         } = self;
         type_parameters.ast_debug(w);
         w.write("(");
-        w.comma(parameters, |w, (v, st)| {
+        w.comma(parameters, |w, (mut_, v, st)| {
+            if mut_.is_some() {
+                w.write("mut ");
+            }
             v.ast_debug(w);
             w.write(": ");
             st.ast_debug(w);
@@ -917,6 +1139,13 @@ impl AstDebug for FunctionSignature {
     }
 }
 
+impl AstDebug for BlockLabel {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        w.write("'");
+        self.0.value.ast_debug(w)
+    }
+}
+
 impl AstDebug for Var_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         let Self { name, id, color } = self;
@@ -960,6 +1189,7 @@ impl AstDebug for (ConstantName, &Constant) {
                 warning_filter,
                 index,
                 attributes,
+                doc: _doc,
                 loc: _loc,
                 signature,
                 value,
@@ -1132,6 +1362,26 @@ impl AstDebug for Exp_ {
                 w.comma(rhs, |w, e| e.ast_debug(w));
                 w.write(")");
             }
+            E::MacroCall(m, f, tys_opt, sp!(_, rhs)) => {
+                w.write(&format!("{}::{}!", m, f));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(rhs, |w, e| e.ast_debug(w));
+                w.write(")");
+            }
+            E::MacroExpansion(tys, e) => {
+                w.write("macro<");
+                w.comma(tys, |w, (ty, abilities)| {
+                    ty.ast_debug(w);
+                    ability_constraints_ast_debug(w, abilities);
+                });
+                w.write("> ");
+                e.ast_debug(w);
+            }
             E::Builtin(bf, sp!(_, rhs)) => {
                 bf.ast_debug(w);
                 w.write("(");
@@ -1164,6 +1414,21 @@ impl AstDebug for Exp_ {
                 });
                 w.write("}");
             }
+            E::PackVariant(m, e, v, tys_opt, fields) => {
+                w.write(&format!("{}::{}::{}", m, e, v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_e)| {
+                    let (idx, e) = idx_e;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    e.ast_debug(w);
+                });
+                w.write("}");
+            }
             E::IfElse(b, t, f) => {
                 w.write("if (");
                 b.ast_debug(w);
@@ -1172,16 +1437,26 @@ impl AstDebug for Exp_ {
                 w.write(" else ");
                 f.ast_debug(w);
             }
-            E::While(b, e) => {
-                w.write("while (");
+            E::While(name, b, e) => {
+                name.ast_debug(w);
+                w.write(": while (");
                 b.ast_debug(w);
                 w.write(")");
                 e.ast_debug(w);
             }
-            E::Loop(e) => {
-                w.write("loop ");
+            E::Loop(name, e) => {
+                name.ast_debug(w);
+                w.write(": loop ");
                 e.ast_debug(w);
             }
+            E::Match(subject, sp!(_, arms)) => {
+                w.write("match (");
+                subject.ast_debug(w);
+                w.write(") ");
+                w.block(|w| {
+                    w.comma(arms, |w, arm| arm.ast_debug(w));
+                });
+            }
             E::Block(seq) => w.block(|w| seq.ast_debug(w)),
             E::ExpList(es) => {
                 w.write("(");
@@ -1214,8 +1489,18 @@ impl AstDebug for Exp_ {
                 w.write("abort ");
                 e.ast_debug(w);
             }
-            E::Break => w.write("break"),
-            E::Continue => w.write("continue"),
+            E::Break(name, e) => {
+                w.write("break ");
+                name.ast_debug(w);
+                if let Some(e) = e {
+                    w.write(" ");
+                    e.ast_debug(w);
+                }
+            }
+            E::Continue(name) => {
+                w.write("continue ");
+                name.ast_debug(w);
+            }
             E::Dereference(e) => {
                 w.write("*");
                 e.ast_debug(w)
@@ -1243,6 +1528,18 @@ impl AstDebug for Exp_ {
                 w.write("(&*)");
                 ed.ast_debug(w)
             }
+            E::MethodCall(ed, n, tys_opt, sp!(_, rhs)) => {
+                ed.ast_debug(w);
+                w.write(&format!(".{}", n));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(rhs, |w, e| e.ast_debug(w));
+                w.write(")");
+            }
             E::Cast(e, ty) => {
                 w.write("(");
                 e.ast_debug(w);
@@ -1300,6 +1597,12 @@ impl AstDebug for ExpDotted_ {
                 e.ast_debug(w);
                 w.write(&format!(".{}", n))
             }
+            D::Index(e, i) => {
+                e.ast_debug(w);
+                w.write("[");
+                i.ast_debug(w);
+                w.write("]")
+            }
         }
     }
 }
@@ -1317,15 +1620,74 @@ impl AstDebug for Vec<LValue> {
     }
 }
 
+impl AstDebug for MatchPattern_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        use MatchPattern_ as P;
+        match self {
+            P::Constructor(m, s, tys_opt, fields) => {
+                w.write(&format!("{}::{}", m, s));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_p)| {
+                    let (idx, p) = idx_p;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    p.ast_debug(w);
+                });
+                w.write("}");
+            }
+            P::Variant(m, e, v, tys_opt, fields) => {
+                w.write(&format!("{}::{}::{}", m, e, v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_p)| {
+                    let (idx, p) = idx_p;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    p.ast_debug(w);
+                });
+                w.write("}");
+            }
+            P::Binder(v) => v.ast_debug(w),
+            P::Literal(v) => v.ast_debug(w),
+            P::Wildcard => w.write("_"),
+            P::ErrorPat => w.write("_#error"),
+        }
+    }
+}
+
+impl AstDebug for MatchArm_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        self.pattern.ast_debug(w);
+        if let Some(guard) = &self.guard {
+            w.write(" if (");
+            guard.ast_debug(w);
+            w.write(")");
+        }
+        w.write(" => ");
+        self.rhs.ast_debug(w);
+    }
+}
+
 impl AstDebug for LValue_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         use LValue_ as L;
         match self {
             L::Ignore => w.write("_"),
             L::Var {
+                mut_,
                 var,
                 unused_binding,
             } => {
+                if mut_.is_some() {
+                    w.write("mut ");
+                }
                 var.ast_debug(w);
                 if *unused_binding {
                     w.write("#unused");
@@ -1346,6 +1708,21 @@ impl AstDebug for LValue_ {
                 });
                 w.write("}");
             }
+            L::UnpackVariant(m, e, v, tys_opt, fields) => {
+                w.write(&format!("{}::{}::{}", m, e, v));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("{");
+                w.comma(fields, |w, (_, f, idx_b)| {
+                    let (idx, b) = idx_b;
+                    w.write(&format!("{}#{}: ", idx, f));
+                    b.ast_debug(w);
+                });
+                w.write("}");
+            }
         }
     }
 }
@@ -0,0 +1,212 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The results of naming kept between compilations of the same program, so that modules that did
+//! not change since the previous compilation are not translated again (see
+//! `Compiler::set_naming_cache`).
+//!
+//! A module is reused if its expansion AST, the configuration of its package, and the flags of the
+//! compilation are unchanged, and if the modules it resolved names in still have the same
+//! signatures. Only modules whose naming
+//! did not report any diagnostic are kept, so reusing a module never loses a diagnostic.
+
+use crate::{
+    expansion::ast::{ModuleIdent, ModuleIdent_},
+    naming::{ast as N, symbol_index::SymbolIndex},
+};
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    fmt::{self, Write},
+    hash::Hasher,
+};
+
+#[derive(Debug, Default)]
+pub struct NamingCache {
+    modules: BTreeMap<ModuleIdent, CachedModule>,
+}
+
+#[derive(Debug)]
+pub(super) struct CachedModule {
+    /// The hash of the expansion AST of the module, of the configuration of its package, and of
+    /// the flags of the compilation
+    pub key: u64,
+    /// The signature hash of each module the module resolved names in, `None` if it did not exist
+    pub dependencies: BTreeMap<ModuleIdent, Option<u64>>,
+    /// The module that string literals were lowered to calls into, if the module has any
+    pub string_module: Option<ModuleIdent>,
+    /// The constants the module referenced, by the module they are declared in
+    pub used_constants: BTreeSet<(ModuleIdent_, Symbol)>,
+    /// The functions the module referenced, by the module they are declared in
+    pub used_functions: BTreeSet<(ModuleIdent_, Symbol)>,
+    /// The translation of the module, before macros are expanded
+    pub mdef: N::ModuleDefinition,
+    /// The definitions and uses of names in the module, if they were recorded
+    pub symbols: Option<SymbolIndex>,
+}
+
+impl NamingCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// The translation of `m` from a previous compilation, if its key was `key`
+    pub(super) fn get(&self, m: &ModuleIdent, key: u64) -> Option<&CachedModule> {
+        self.modules.get(m).filter(|cached| cached.key == key)
+    }
+
+    pub(super) fn insert(&mut self, m: ModuleIdent, cached: CachedModule) {
+        self.modules.insert(m, cached);
+    }
+
+    pub(super) fn remove(&mut self, m: &ModuleIdent) {
+        self.modules.remove(m);
+    }
+
+    /// Drops the modules that are no longer part of the program
+    pub(super) fn retain(&mut self, modules: &BTreeSet<ModuleIdent>) {
+        self.modules.retain(|m, _| modules.contains(m))
+    }
+}
+
+/// Hashes the `Debug` representation of `value`. For the ASTs, it covers all of their contents,
+/// including the hash of the source file in each location they store
+pub(super) fn debug_hash(value: &impl fmt::Debug) -> u64 {
+    struct HashWriter(DefaultHasher);
+
+    impl Write for HashWriter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0.write(s.as_bytes());
+            Ok(())
+        }
+    }
+
+    let mut writer = HashWriter(DefaultHasher::new());
+    write!(writer, "{:?}", value).unwrap();
+    writer.0.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command_line::compiler::{Compiler, PASS_NAMING},
+        shared::{Flags, Identifier, NumericalAddress},
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn naming(path: &str, cache: &Arc<Mutex<NamingCache>>) -> N::Program {
+        naming_with_flags(path, cache, Flags::empty())
+    }
+
+    fn naming_with_flags(path: &str, cache: &Arc<Mutex<NamingCache>>, flags: Flags) -> N::Program {
+        let (_files, res) = Compiler::from_files(
+            vec![path],
+            vec![],
+            BTreeMap::<&str, NumericalAddress>::new(),
+        )
+        .set_flags(flags)
+        .set_naming_cache(cache.clone())
+        .run::<PASS_NAMING>()
+        .unwrap();
+        let (_comments, stepped) = res.unwrap();
+        stepped.into_ast().1
+    }
+
+    // A new ID is given to each type parameter when a module is translated, so it only stays the
+    // same if the translation is reused. This is the ID of the first type parameter of the first
+    // function of `0x42::m`
+    fn type_parameter_id(prog: &N::Program) -> N::TParamID {
+        let (_, mdef) = prog
+            .modules
+            .key_cloned_iter()
+            .find(|(m, _)| m.value.module.value().as_str() == "m")
+            .unwrap();
+        let (_, fdef) = mdef.functions.key_cloned_iter().next().unwrap();
+        fdef.signature.type_parameters[0].id
+    }
+
+    #[test]
+    fn reuses_unchanged_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("m.move");
+        let path = path.to_str().unwrap();
+        let cache = Arc::new(Mutex::new(NamingCache::new()));
+
+        std::fs::write(path, "module 0x42::m { public fun id<T>(x: T): T { x } }").unwrap();
+        let first = type_parameter_id(&naming(path, &cache));
+        assert_eq!(cache.lock().unwrap().len(), 1);
+        let second = type_parameter_id(&naming(path, &cache));
+        assert_eq!(first, second);
+
+        std::fs::write(path, "module 0x42::m { public fun id<T>(x: T): T {  x } }").unwrap();
+        let third = type_parameter_id(&naming(path, &cache));
+        assert_ne!(second, third);
+
+        // the error for the unbound variable would be lost if the module was reused
+        std::fs::write(path, "module 0x42::m { public fun id<T>(x: T): T { y } }").unwrap();
+        naming(path, &cache);
+        assert!(cache.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn retranslates_modules_when_flags_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("m.move");
+        let path = path.to_str().unwrap();
+        let cache = Arc::new(Mutex::new(NamingCache::new()));
+
+        std::fs::write(path, "module 0x42::m { public fun id<T>(x: T): T { x } }").unwrap();
+        let first = type_parameter_id(&naming(path, &cache));
+        let second = type_parameter_id(&naming(path, &cache));
+        assert_eq!(first, second);
+
+        let flags = Flags::empty().set_warn_shadowing(true);
+        let third = type_parameter_id(&naming_with_flags(path, &cache, flags.clone()));
+        assert_ne!(second, third);
+        let fourth = type_parameter_id(&naming_with_flags(path, &cache, flags));
+        assert_eq!(third, fourth);
+
+        let fifth = type_parameter_id(&naming_with_flags(path, &cache, Flags::testing()));
+        assert_ne!(fourth, fifth);
+    }
+
+    #[test]
+    fn retranslates_modules_when_dependency_visibility_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("m.move");
+        let path = path.to_str().unwrap();
+        let cache = Arc::new(Mutex::new(NamingCache::new()));
+        let user = "module 0x42::m { fun id<T>(x: T): T { 0x42::a::f(); x } }";
+
+        std::fs::write(
+            path,
+            format!("module 0x42::a {{ public fun f() {{}} }} {user}"),
+        )
+        .unwrap();
+        let first = type_parameter_id(&naming(path, &cache));
+        std::fs::write(path, format!("module 0x42::a {{ fun f() {{}} }} {user}")).unwrap();
+        let second = type_parameter_id(&naming(path, &cache));
+        assert_ne!(first, second);
+
+        std::fs::write(
+            path,
+            format!("module 0x42::a {{ #[test_only] fun f() {{}} }} {user}"),
+        )
+        .unwrap();
+        let third = type_parameter_id(&naming_with_flags(path, &cache, Flags::testing()));
+        let fourth = type_parameter_id(&naming_with_flags(path, &cache, Flags::testing()));
+        assert_eq!(third, fourth);
+        std::fs::write(path, format!("module 0x42::a {{ fun f() {{}} }} {user}")).unwrap();
+        let fifth = type_parameter_id(&naming_with_flags(path, &cache, Flags::testing()));
+        assert_ne!(fourth, fifth);
+    }
+}
@@ -0,0 +1,593 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Expansion of calls to `macro fun`s. Macro calls are resolved in naming like any other module
+//! call, and once every module has been named, each call `m::f!(args)` is replaced by a block that
+//! binds the arguments to the parameters of `m::f` and then evaluates its body.
+//!
+//! The body of a macro is named once, where it is declared, so its locals are resolved in that
+//! scope. Each expansion gives all of the locals of the body (including the parameters) a fresh
+//! color, so they can neither capture nor shadow the locals at the call site. Type parameters are
+//! replaced by the type arguments of the call, or by one fresh type variable each if there are
+//! none, and the expansion keeps their constraints so that typing can check them.
+//!
+//! The macro calls in the body of a macro are also expanded where it is declared, so that the
+//! macro can be type checked there, like any other function.
+
+use crate::{
+    diag,
+    diagnostics::codes::*,
+    expansion::ast::{ModuleIdent, ModuleIdent_, Visibility},
+    naming::ast::{self as N, TParamID},
+    parser::ast::FunctionName,
+    shared::*,
+    FullyCompiledProgram,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::collections::{BTreeMap, BTreeSet};
+
+//**************************************************************************************************
+// Context
+//**************************************************************************************************
+
+struct Macro {
+    defined_loc: Loc,
+    package: Option<Symbol>,
+    friends: BTreeSet<ModuleIdent_>,
+    visibility: Visibility,
+    signature: N::FunctionSignature,
+    body: N::Sequence,
+}
+
+struct Context<'env> {
+    env: &'env mut CompilationEnv,
+    macros: BTreeMap<(ModuleIdent_, Symbol), Macro>,
+    current_module: Option<ModuleIdent>,
+    current_package: Option<Symbol>,
+    /// The macros currently being expanded, innermost last, to detect recursive macros
+    expanding: Vec<(ModuleIdent_, Symbol)>,
+    /// The last color given to the locals of an expanded macro
+    last_color: u16,
+}
+
+/// How the body of a macro is instantiated for one of its calls
+struct Subst {
+    /// The color of the locals of this expansion, or `None` outside of any macro
+    color: Option<u16>,
+    tparams: BTreeMap<TParamID, N::Type>,
+    /// The locals of the body of the macro that are used, as they were named in its declaration
+    used_locals: BTreeSet<N::Var_>,
+}
+
+impl Subst {
+    fn none() -> Self {
+        Self {
+            color: None,
+            tparams: BTreeMap::new(),
+            used_locals: BTreeSet::new(),
+        }
+    }
+}
+
+impl<'env> Context<'env> {
+    fn new_color(&mut self) -> u16 {
+        self.last_color += 1;
+        self.last_color
+    }
+
+    fn can_call(&self, m: &ModuleIdent, macro_: &Macro) -> bool {
+        let in_module = self.current_module.as_ref() == Some(m);
+        match &macro_.visibility {
+            Visibility::Internal => in_module,
+            Visibility::Friend(_) => {
+                in_module
+                    || self
+                        .current_module
+                        .is_some_and(|cur| macro_.friends.contains(&cur.value))
+            }
+            Visibility::Package(_) => {
+                in_module
+                    || self.current_module.is_some_and(|cur| {
+                        cur.value.address == m.value.address
+                            && self.current_package == macro_.package
+                    })
+            }
+            Visibility::Public(_) => true,
+        }
+    }
+}
+
+//**************************************************************************************************
+// Entry
+//**************************************************************************************************
+
+pub fn program(
+    compilation_env: &mut CompilationEnv,
+    pre_compiled_lib: Option<&FullyCompiledProgram>,
+    prog: &mut N::Program,
+) {
+    let pre_compiled_modules = pre_compiled_lib.iter().flat_map(|pre_compiled| {
+        pre_compiled
+            .naming
+            .modules
+            .key_cloned_iter()
+            .filter(|(mident, _m)| !prog.modules.contains_key(mident))
+    });
+    let mut macros = BTreeMap::new();
+    for (mident, mdef) in prog.modules.key_cloned_iter().chain(pre_compiled_modules) {
+        for (fname, fdef) in mdef.functions.key_cloned_iter() {
+            let N::FunctionBody_::Defined(body) = &fdef.body.value else {
+                continue;
+            };
+            if fdef.macro_.is_none() {
+                continue;
+            }
+            let macro_ = Macro {
+                defined_loc: fname.loc(),
+                package: mdef.package_name,
+                friends: mdef
+                    .friends
+                    .key_cloned_iter()
+                    .map(|(m, _)| m.value)
+                    .collect(),
+                visibility: fdef.visibility,
+                signature: fdef.signature.clone(),
+                body: body.clone(),
+            };
+            macros.insert((mident.value, fname.value()), macro_);
+        }
+    }
+
+    let mut context = Context {
+        env: compilation_env,
+        macros,
+        current_module: None,
+        current_package: None,
+        expanding: vec![],
+        last_color: 0,
+    };
+    for (mident, mdef) in prog.modules.key_cloned_iter_mut() {
+        context.current_module = Some(mident);
+        context.current_package = mdef.package_name;
+        context
+            .env
+            .add_warning_filter_scope(mdef.warning_filter.clone());
+        for (fname, fdef) in mdef.functions.key_cloned_iter_mut() {
+            let is_macro = fdef.macro_.is_some();
+            // a macro that calls itself is reported in its own body
+            if is_macro {
+                context.expanding.push((mident.value, fname.value()));
+            }
+            function(&mut context, fdef);
+            if is_macro {
+                context.expanding.pop();
+            }
+        }
+        for (_, _, cdef) in &mut mdef.constants {
+            constant(&mut context, cdef);
+        }
+        context.env.pop_warning_filter_scope();
+    }
+    for script in prog.scripts.values_mut() {
+        context.current_module = None;
+        context.current_package = script.package_name;
+        context
+            .env
+            .add_warning_filter_scope(script.warning_filter.clone());
+        function(&mut context, &mut script.function);
+        for (_, _, cdef) in &mut script.constants {
+            constant(&mut context, cdef);
+        }
+        context.env.pop_warning_filter_scope();
+    }
+}
+
+fn function(context: &mut Context, fdef: &mut N::Function) {
+    context
+        .env
+        .add_warning_filter_scope(fdef.warning_filter.clone());
+    if let N::FunctionBody_::Defined(seq) = &mut fdef.body.value {
+        sequence(context, &mut Subst::none(), seq)
+    }
+    context.env.pop_warning_filter_scope();
+}
+
+fn constant(context: &mut Context, cdef: &mut N::Constant) {
+    context
+        .env
+        .add_warning_filter_scope(cdef.warning_filter.clone());
+    exp(context, &mut Subst::none(), &mut cdef.value);
+    context.env.pop_warning_filter_scope();
+}
+
+//**************************************************************************************************
+// Expansion
+//**************************************************************************************************
+
+fn macro_call(
+    context: &mut Context,
+    loc: Loc,
+    m: ModuleIdent,
+    f: FunctionName,
+    ty_args_opt: Option<Vec<N::Type>>,
+    sp!(argloc, args): Spanned<Vec<N::Exp>>,
+) -> N::Exp_ {
+    let key = (m.value, f.value());
+    let Some(macro_) = context.macros.get(&key) else {
+        assert!(context.env.has_errors());
+        return N::Exp_::UnresolvedError;
+    };
+    if context.expanding.contains(&key) {
+        let msg = format!(
+            "Invalid call to macro '{}::{}'. A macro cannot call itself, directly or through \
+             another macro, as it would be expanded forever",
+            m, f
+        );
+        context.env.add_diag(diag!(
+            NameResolution::InvalidMacroCall,
+            (loc, msg),
+            (macro_.defined_loc, "Macro declared here"),
+        ));
+        return N::Exp_::UnresolvedError;
+    }
+    if !context.can_call(&m, macro_) {
+        let vis_msg = match &macro_.visibility {
+            Visibility::Friend(_) => format!(
+                "This macro can only be called from a 'friend' of module '{}'",
+                m
+            ),
+            Visibility::Package(_) => format!(
+                "A '{}' macro can only be called from the same address and package as module \
+                 '{}'",
+                Visibility::PACKAGE,
+                m
+            ),
+            _ => format!(
+                "This macro is internal to its module. Only '{}', '{}', and '{}' macros can be \
+                 called outside of their module",
+                Visibility::PUBLIC,
+                Visibility::FRIEND,
+                Visibility::PACKAGE
+            ),
+        };
+        let vis_loc = macro_.visibility.loc().unwrap_or(macro_.defined_loc);
+        context.env.add_diag(diag!(
+            TypeSafety::Visibility,
+            (loc, format!("Invalid call to '{}::{}'", m, f)),
+            (vis_loc, vis_msg),
+        ));
+    }
+
+    let N::FunctionSignature {
+        type_parameters,
+        parameters,
+        return_type,
+    } = macro_.signature.clone();
+    let mut body = macro_.body.clone();
+    let ty_args = match ty_args_opt {
+        Some(ty_args) if ty_args.len() == type_parameters.len() => ty_args,
+        ty_args_opt => {
+            if let Some(ty_args) = ty_args_opt {
+                let code = if ty_args.len() > type_parameters.len() {
+                    NameResolution::TooManyTypeArguments
+                } else {
+                    NameResolution::TooFewTypeArguments
+                };
+                let msg = format!(
+                    "Invalid instantiation of '{}::{}'. Expected {} type argument(s) but got {}",
+                    m,
+                    f,
+                    type_parameters.len(),
+                    ty_args.len()
+                );
+                context.env.add_diag(diag!(code, (loc, msg)));
+            }
+            // left to be inferred, consistently across all of their uses
+            type_parameters
+                .iter()
+                .map(|_| sp(loc, N::Type_::Var(N::TVar::next())))
+                .collect()
+        }
+    };
+    if args.len() != parameters.len() {
+        let code = if args.len() > parameters.len() {
+            TypeSafety::TooManyArguments
+        } else {
+            TypeSafety::TooFewArguments
+        };
+        let msg = format!(
+            "Invalid call of '{}::{}'. The call expected {} argument(s) but got {}",
+            m,
+            f,
+            parameters.len(),
+            args.len()
+        );
+        context.env.add_diag(diag!(
+            code,
+            (loc, msg),
+            (argloc, format!("Found {} argument(s) here", args.len())),
+        ));
+        return N::Exp_::UnresolvedError;
+    }
+
+    let type_arguments = ty_args
+        .iter()
+        .cloned()
+        .zip(type_parameters.iter().map(|tp| tp.abilities.clone()))
+        .collect();
+    let mut subst = Subst {
+        color: Some(context.new_color()),
+        tparams: type_parameters
+            .iter()
+            .map(|tp| tp.id)
+            .zip(ty_args)
+            .collect(),
+        used_locals: BTreeSet::new(),
+    };
+    context.expanding.push(key);
+    sequence(context, &mut subst, &mut body);
+    context.expanding.pop();
+
+    // let param_1: ty_1 = arg_1; ... let param_n: ty_n = arg_n; body
+    for ((mut_, param, mut param_ty), arg) in parameters.into_iter().zip(args).rev() {
+        let unused_binding = !subst.used_locals.contains(&param.value);
+        let sp!(var_loc, mut var_) = param;
+        var_.color = subst.color.unwrap();
+        type_(&mut subst, &mut param_ty);
+        let arg_loc = arg.loc;
+        let lvalue_ = N::LValue_::Var {
+            mut_,
+            var: sp(var_loc, var_),
+            unused_binding,
+        };
+        let lvalues = sp(var_loc, vec![sp(var_loc, lvalue_)]);
+        let rhs = sp(arg_loc, N::Exp_::Annotate(Box::new(arg), param_ty));
+        body.push_front(sp(arg_loc, N::SequenceItem_::Bind(lvalues, rhs)));
+    }
+    let mut return_type = return_type;
+    type_(&mut subst, &mut return_type);
+    let block = sp(loc, N::Exp_::Block(body));
+    let annotated = sp(loc, N::Exp_::Annotate(Box::new(block), return_type));
+    N::Exp_::MacroExpansion(type_arguments, Box::new(annotated))
+}
+
+fn sequence(context: &mut Context, subst: &mut Subst, seq: &mut N::Sequence) {
+    for sp!(_, item_) in seq.iter_mut() {
+        match item_ {
+            N::SequenceItem_::Seq(e) => exp(context, subst, e),
+            N::SequenceItem_::Declare(lvalues, ty_opt) => {
+                lvalue_list(subst, lvalues);
+                if let Some(ty) = ty_opt {
+                    type_(subst, ty)
+                }
+            }
+            N::SequenceItem_::Bind(lvalues, e) => {
+                lvalue_list(subst, lvalues);
+                exp(context, subst, e)
+            }
+        }
+    }
+}
+
+fn exp(context: &mut Context, subst: &mut Subst, e: &mut N::Exp) {
+    let sp!(eloc, e_) = e;
+    match e_ {
+        N::Exp_::Value(_)
+        | N::Exp_::Constant(_, _)
+        | N::Exp_::Unit { .. }
+        | N::Exp_::Spec(_, _)
+        | N::Exp_::UnresolvedError => (),
+        N::Exp_::Move(v) | N::Exp_::Copy(v) | N::Exp_::Use(v) => var(subst, v),
+        N::Exp_::Break(name, e_opt) => {
+            block_label(subst, name);
+            if let Some(e) = e_opt {
+                exp(context, subst, e)
+            }
+        }
+        N::Exp_::Continue(name) => block_label(subst, name),
+
+        N::Exp_::MacroCall(m, f, ty_args_opt, args) => {
+            let mut ty_args_opt = ty_args_opt.take();
+            if let Some(ty_args) = &mut ty_args_opt {
+                types(subst, ty_args)
+            }
+            let mut args = std::mem::replace(args, sp(args.loc, vec![]));
+            // the arguments are part of the body being expanded, if any
+            for arg in &mut args.value {
+                exp(context, subst, arg)
+            }
+            let (m, f) = (*m, *f);
+            *e_ = macro_call(context, *eloc, m, f, ty_args_opt, args)
+        }
+        N::Exp_::MacroExpansion(ty_args, e) => {
+            for (ty, _) in ty_args {
+                type_(subst, ty)
+            }
+            exp(context, subst, e)
+        }
+        N::Exp_::ModuleCall(_, _, ty_args_opt, sp!(_, es)) => {
+            if let Some(ty_args) = ty_args_opt {
+                types(subst, ty_args)
+            }
+            exps(context, subst, es)
+        }
+        N::Exp_::Builtin(sp!(_, bf_), sp!(_, es)) => {
+            use N::BuiltinFunction_ as BF;
+            match bf_ {
+                BF::MoveTo(ty_opt)
+                | BF::MoveFrom(ty_opt)
+                | BF::BorrowGlobal(_, ty_opt)
+                | BF::Exists(ty_opt)
+                | BF::Freeze(ty_opt) => {
+                    if let Some(ty) = ty_opt {
+                        type_(subst, ty)
+                    }
+                }
+                BF::Assert(_) => (),
+            }
+            exps(context, subst, es)
+        }
+        N::Exp_::Vector(_, ty_opt, sp!(_, es)) => {
+            if let Some(ty) = ty_opt {
+                type_(subst, ty)
+            }
+            exps(context, subst, es)
+        }
+        N::Exp_::ExpList(es) => exps(context, subst, es),
+
+        N::Exp_::IfElse(econd, et, ef) => {
+            exp(context, subst, econd);
+            exp(context, subst, et);
+            exp(context, subst, ef)
+        }
+        N::Exp_::While(name, econd, ebody) => {
+            block_label(subst, name);
+            exp(context, subst, econd);
+            exp(context, subst, ebody)
+        }
+        N::Exp_::Loop(name, e) => {
+            block_label(subst, name);
+            exp(context, subst, e)
+        }
+        N::Exp_::Block(seq) => sequence(context, subst, seq),
+        N::Exp_::Match(esubject, sp!(_, arms)) => {
+            exp(context, subst, esubject);
+            for sp!(_, arm) in arms {
+                match_pattern(subst, &mut arm.pattern);
+                if let Some(guard) = &mut arm.guard {
+                    exp(context, subst, guard)
+                }
+                exp(context, subst, &mut arm.rhs)
+            }
+        }
+
+        N::Exp_::Assign(lvalues, e) => {
+            lvalue_list(subst, lvalues);
+            exp(context, subst, e)
+        }
+        N::Exp_::FieldMutate(ed, e) => {
+            exp_dotted(context, subst, ed);
+            exp(context, subst, e)
+        }
+        N::Exp_::Mutate(el, er) | N::Exp_::BinopExp(el, _, er) => {
+            exp(context, subst, el);
+            exp(context, subst, er)
+        }
+        N::Exp_::Return(e)
+        | N::Exp_::Abort(e)
+        | N::Exp_::Dereference(e)
+        | N::Exp_::UnaryExp(_, e) => exp(context, subst, e),
+        N::Exp_::Cast(e, ty) | N::Exp_::Annotate(e, ty) => {
+            exp(context, subst, e);
+            type_(subst, ty)
+        }
+
+        N::Exp_::Pack(_, _, ty_args_opt, fields)
+        | N::Exp_::PackVariant(_, _, _, ty_args_opt, fields) => {
+            if let Some(ty_args) = ty_args_opt {
+                types(subst, ty_args)
+            }
+            for (_, _, (_, e)) in fields {
+                exp(context, subst, e)
+            }
+        }
+        N::Exp_::DerefBorrow(ed) | N::Exp_::Borrow(_, ed) => exp_dotted(context, subst, ed),
+        N::Exp_::MethodCall(ed, _, ty_args_opt, sp!(_, es)) => {
+            exp_dotted(context, subst, ed);
+            if let Some(ty_args) = ty_args_opt {
+                types(subst, ty_args)
+            }
+            exps(context, subst, es)
+        }
+    }
+}
+
+fn exps(context: &mut Context, subst: &mut Subst, es: &mut [N::Exp]) {
+    for e in es {
+        exp(context, subst, e)
+    }
+}
+
+fn exp_dotted(context: &mut Context, subst: &mut Subst, sp!(_, ed_): &mut N::ExpDotted) {
+    match ed_ {
+        N::ExpDotted_::Exp(e) => exp(context, subst, e),
+        N::ExpDotted_::Dot(ed, _) => exp_dotted(context, subst, ed),
+        N::ExpDotted_::Index(ed, e) => {
+            exp_dotted(context, subst, ed);
+            exp(context, subst, e)
+        }
+    }
+}
+
+fn lvalue_list(subst: &mut Subst, sp!(_, lvalues): &mut N::LValueList) {
+    for lv in lvalues {
+        lvalue(subst, lv)
+    }
+}
+
+fn lvalue(subst: &mut Subst, sp!(_, lvalue_): &mut N::LValue) {
+    match lvalue_ {
+        N::LValue_::Ignore => (),
+        N::LValue_::Var { var: v, .. } => var(subst, v),
+        N::LValue_::Unpack(_, _, ty_args_opt, fields)
+        | N::LValue_::UnpackVariant(_, _, _, ty_args_opt, fields) => {
+            if let Some(ty_args) = ty_args_opt {
+                types(subst, ty_args)
+            }
+            for (_, _, (_, lv)) in fields {
+                lvalue(subst, lv)
+            }
+        }
+    }
+}
+
+fn match_pattern(subst: &mut Subst, sp!(_, pattern_): &mut N::MatchPattern) {
+    match pattern_ {
+        N::MatchPattern_::Literal(_) | N::MatchPattern_::Wildcard | N::MatchPattern_::ErrorPat => {}
+        N::MatchPattern_::Binder(v) => var(subst, v),
+        N::MatchPattern_::Constructor(_, _, ty_args_opt, fields)
+        | N::MatchPattern_::Variant(_, _, _, ty_args_opt, fields) => {
+            if let Some(ty_args) = ty_args_opt {
+                types(subst, ty_args)
+            }
+            for (_, _, (_, pat)) in fields {
+                match_pattern(subst, pat)
+            }
+        }
+    }
+}
+
+fn var(subst: &mut Subst, sp!(_, v_): &mut N::Var) {
+    if let Some(color) = subst.color {
+        subst.used_locals.insert(*v_);
+        v_.color = color;
+    }
+}
+
+// labels are colored like locals, so the loops of the body cannot be confused with the loops of
+// the calling function, or of other expansions
+fn block_label(subst: &mut Subst, N::BlockLabel(sp!(_, v_)): &mut N::BlockLabel) {
+    if let Some(color) = subst.color {
+        v_.color = color;
+    }
+}
+
+fn types(subst: &mut Subst, tys: &mut [N::Type]) {
+    for ty in tys {
+        type_(subst, ty)
+    }
+}
+
+fn type_(subst: &mut Subst, sp!(_, ty_): &mut N::Type) {
+    match ty_ {
+        N::Type_::Param(tp) => {
+            if let Some(sp!(_, replacement)) = subst.tparams.get(&tp.id) {
+                *ty_ = replacement.clone()
+            }
+        }
+        N::Type_::Ref(_, inner) => type_(subst, inner),
+        N::Type_::Apply(_, _, ty_args) => types(subst, ty_args),
+        N::Type_::Unit | N::Type_::Var(_) | N::Type_::Anything | N::Type_::UnresolvedError => {}
+    }
+}
@@ -3,5 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod ast;
+pub mod cache;
 pub(crate) mod fake_natives;
+mod macro_expand;
+pub mod module_info;
+pub mod symbol_index;
 pub(crate) mod translate;
@@ -0,0 +1,503 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The members declared by each module in a program, as used by naming to resolve module
+//! accesses. Built once from the expansion program (and any pre-compiled library), so that later
+//! passes and tooling can look up what a module member resolves to without re-deriving it.
+
+use crate::{
+    expansion::ast::{self as E, AbilitySet, ModuleIdent, ModuleIdent_},
+    shared::{
+        known_attributes::{DeprecationAttribute, KnownAttribute, TestingAttribute},
+        unique_map::UniqueMap,
+        TName,
+    },
+    FullyCompiledProgram,
+};
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, BTreeSet},
+    hash::{Hash, Hasher},
+};
+
+/// A struct declared in a module
+#[derive(Debug, Clone)]
+pub struct StructInfo {
+    pub decl_loc: Loc,
+    pub abilities: AbilitySet,
+    pub arity: usize,
+    /// The number of fields, if the struct is declared with positional fields
+    pub positional_fields: Option<usize>,
+}
+
+/// An enum declared in a module
+#[derive(Debug, Clone)]
+pub struct EnumInfo {
+    pub decl_loc: Loc,
+    pub abilities: AbilitySet,
+    pub arity: usize,
+    pub variants: BTreeMap<Symbol, VariantInfo>,
+}
+
+/// A variant of an enum
+#[derive(Debug, Clone)]
+pub struct VariantInfo {
+    pub decl_loc: Loc,
+    /// The fields of the variant: named, positional (with their number), or none
+    pub fields: VariantFieldsInfo,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VariantFieldsInfo {
+    Named,
+    Positional(usize),
+    Empty,
+}
+
+/// A module or module member declared with `#[deprecated]`
+#[derive(Debug, Clone)]
+pub struct Deprecation {
+    /// The location of the declaration of the deprecated module or member
+    pub decl_loc: Loc,
+    /// The note given with `#[deprecated(note = "...")]`, if any
+    pub note: Option<String>,
+}
+
+/// The members declared in a module, by name
+#[derive(Debug, Clone, Default)]
+pub struct ModuleInfo {
+    structs: BTreeMap<Symbol, StructInfo>,
+    enums: BTreeMap<Symbol, EnumInfo>,
+    functions: BTreeMap<Symbol, Loc>,
+    /// The visibility of each function
+    function_visibilities: BTreeMap<Symbol, E::Visibility>,
+    /// The functions declared as `macro fun`, a subset of `functions`
+    macros: BTreeSet<Symbol>,
+    /// The struct taken (by value or by reference) as first parameter, for the functions whose
+    /// first parameter is a struct
+    receivers: BTreeMap<Symbol, (ModuleIdent_, Symbol)>,
+    constants: BTreeMap<Symbol, Loc>,
+    /// The spec functions, global spec variables, and schemas declared in the spec blocks of the
+    /// module, which can only be used in specifications
+    spec_members: BTreeSet<Symbol>,
+    /// The deprecation of the module itself, which applies to all of its members
+    deprecation: Option<Deprecation>,
+    /// The deprecated members, by name, separately for each kind of member
+    deprecated_structs: BTreeMap<Symbol, Deprecation>,
+    deprecated_enums: BTreeMap<Symbol, Deprecation>,
+    deprecated_functions: BTreeMap<Symbol, Deprecation>,
+    deprecated_constants: BTreeMap<Symbol, Deprecation>,
+    /// Whether the module itself is declared with `#[test_only]`
+    test_only: bool,
+    /// The members declared with `#[test_only]`, separately for each kind of member
+    test_only_structs: BTreeSet<Symbol>,
+    test_only_enums: BTreeSet<Symbol>,
+    test_only_functions: BTreeSet<Symbol>,
+    test_only_constants: BTreeSet<Symbol>,
+}
+
+/// The members of every module in a program, including those of the pre-compiled library
+#[derive(Debug, Clone, Default)]
+pub struct ModuleInfoMap {
+    modules: BTreeMap<ModuleIdent, ModuleInfo>,
+}
+
+impl ModuleInfo {
+    fn new(mident: &ModuleIdent, mdef: &E::ModuleDefinition) -> Self {
+        let structs = mdef
+            .structs
+            .iter()
+            .map(|(sloc, s, sdef)| {
+                let info = StructInfo {
+                    decl_loc: sloc,
+                    abilities: sdef.abilities.clone(),
+                    arity: sdef.type_parameters.len(),
+                    positional_fields: match &sdef.fields {
+                        E::StructFields::Positional(tys) => Some(tys.len()),
+                        E::StructFields::Defined(_) | E::StructFields::Native(_) => None,
+                    },
+                };
+                (*s, info)
+            })
+            .collect();
+        let enums = mdef
+            .enums
+            .iter()
+            .map(|(eloc, e, edef)| {
+                let variants = edef
+                    .variants
+                    .iter()
+                    .map(|(vloc, v, vdef)| {
+                        let fields = match &vdef.fields {
+                            E::VariantFields::Defined(_) => VariantFieldsInfo::Named,
+                            E::VariantFields::Positional(tys) => {
+                                VariantFieldsInfo::Positional(tys.len())
+                            }
+                            E::VariantFields::Empty => VariantFieldsInfo::Empty,
+                        };
+                        let info = VariantInfo {
+                            decl_loc: vloc,
+                            fields,
+                        };
+                        (*v, info)
+                    })
+                    .collect();
+                let info = EnumInfo {
+                    decl_loc: eloc,
+                    abilities: edef.abilities.clone(),
+                    arity: edef.type_parameters.len(),
+                    variants,
+                };
+                (*e, info)
+            })
+            .collect();
+        let functions = mdef
+            .functions
+            .iter()
+            .map(|(nloc, n, _)| (*n, nloc))
+            .collect();
+        let function_visibilities = mdef
+            .functions
+            .iter()
+            .map(|(_, n, fdef)| (*n, fdef.visibility))
+            .collect();
+        let macros = mdef
+            .functions
+            .iter()
+            .filter(|(_, _, fdef)| fdef.macro_.is_some())
+            .map(|(_, n, _)| *n)
+            .collect();
+        let receivers = mdef
+            .functions
+            .iter()
+            .filter_map(|(_, n, fdef)| Some((*n, receiver(mdef, fdef)?)))
+            .collect();
+        let constants = mdef
+            .constants
+            .iter()
+            .map(|(nloc, n, _)| (*n, nloc))
+            .collect();
+        Self {
+            structs,
+            enums,
+            functions,
+            function_visibilities,
+            macros,
+            receivers,
+            constants,
+            spec_members: spec_members(&mdef.specs),
+            deprecation: deprecation(mident.loc, &mdef.attributes),
+            deprecated_structs: deprecated_members(&mdef.structs, |s| &s.attributes),
+            deprecated_enums: deprecated_members(&mdef.enums, |e| &e.attributes),
+            deprecated_functions: deprecated_members(&mdef.functions, |f| &f.attributes),
+            deprecated_constants: deprecated_members(&mdef.constants, |c| &c.attributes),
+            test_only: is_test_only(&mdef.attributes),
+            test_only_structs: test_only_members(&mdef.structs, |s| &s.attributes),
+            test_only_enums: test_only_members(&mdef.enums, |e| &e.attributes),
+            test_only_functions: test_only_members(&mdef.functions, |f| &f.attributes),
+            test_only_constants: test_only_members(&mdef.constants, |c| &c.attributes),
+        }
+    }
+
+    /// A hash of what naming can learn about the module when resolving names in other modules.
+    /// It does not depend on where the members are declared, which only shows in diagnostics
+    pub fn signature_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.structs.len().hash(&mut hasher);
+        for (n, sinfo) in &self.structs {
+            n.hash(&mut hasher);
+            sinfo.abilities.hash(&mut hasher);
+            sinfo.arity.hash(&mut hasher);
+            sinfo.positional_fields.hash(&mut hasher);
+        }
+        self.enums.len().hash(&mut hasher);
+        for (n, einfo) in &self.enums {
+            n.hash(&mut hasher);
+            einfo.abilities.hash(&mut hasher);
+            einfo.arity.hash(&mut hasher);
+            einfo.variants.len().hash(&mut hasher);
+            for (v, vinfo) in &einfo.variants {
+                v.hash(&mut hasher);
+                vinfo.fields.hash(&mut hasher);
+            }
+        }
+        self.functions.len().hash(&mut hasher);
+        self.functions.keys().for_each(|n| n.hash(&mut hasher));
+        for visibility in self.function_visibilities.values() {
+            std::mem::discriminant(visibility).hash(&mut hasher);
+        }
+        self.macros.hash(&mut hasher);
+        self.receivers.hash(&mut hasher);
+        self.constants.len().hash(&mut hasher);
+        self.constants.keys().for_each(|n| n.hash(&mut hasher));
+        self.spec_members.hash(&mut hasher);
+        // deprecations change the warnings reported in the modules using them
+        self.deprecation.as_ref().map(|d| &d.note).hash(&mut hasher);
+        for deprecated in [
+            &self.deprecated_structs,
+            &self.deprecated_enums,
+            &self.deprecated_functions,
+            &self.deprecated_constants,
+        ] {
+            deprecated.len().hash(&mut hasher);
+            for (n, d) in deprecated {
+                n.hash(&mut hasher);
+                d.note.hash(&mut hasher);
+            }
+        }
+        // so do test-only declarations, in the errors for uses from code that is not test-only
+        self.test_only.hash(&mut hasher);
+        for test_only in [
+            &self.test_only_structs,
+            &self.test_only_enums,
+            &self.test_only_functions,
+            &self.test_only_constants,
+        ] {
+            test_only.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// The struct named `n`, if the module declares one
+    pub fn struct_(&self, n: &Symbol) -> Option<&StructInfo> {
+        self.structs.get(n)
+    }
+
+    /// The enum named `n`, if the module declares one
+    pub fn enum_(&self, n: &Symbol) -> Option<&EnumInfo> {
+        self.enums.get(n)
+    }
+
+    /// The location of the declaration of the function named `n`, if the module declares one
+    pub fn function(&self, n: &Symbol) -> Option<Loc> {
+        self.functions.get(n).copied()
+    }
+
+    /// Whether the function named `n` is declared as a `macro fun`
+    pub fn is_macro(&self, n: &Symbol) -> bool {
+        self.macros.contains(n)
+    }
+
+    /// The struct taken as first parameter by the function named `n`, by value or by reference
+    pub fn receiver(&self, n: &Symbol) -> Option<&(ModuleIdent_, Symbol)> {
+        self.receivers.get(n)
+    }
+
+    /// The location of the declaration of the constant named `n`, if the module declares one
+    pub fn constant(&self, n: &Symbol) -> Option<Loc> {
+        self.constants.get(n).copied()
+    }
+
+    /// Whether the module declares a spec function, global spec variable, or schema named `n`
+    pub fn is_spec_member(&self, n: &Symbol) -> bool {
+        self.spec_members.contains(n)
+    }
+
+    /// The deprecation of the struct named `n`, or of the module if the struct is not deprecated
+    pub fn struct_deprecation(&self, n: &Symbol) -> Option<&Deprecation> {
+        self.deprecated_structs.get(n).or(self.deprecation.as_ref())
+    }
+
+    /// The deprecation of the enum named `n`, or of the module if the enum is not deprecated
+    pub fn enum_deprecation(&self, n: &Symbol) -> Option<&Deprecation> {
+        self.deprecated_enums.get(n).or(self.deprecation.as_ref())
+    }
+
+    /// The deprecation of the function named `n`, or of the module if the function is not
+    /// deprecated
+    pub fn function_deprecation(&self, n: &Symbol) -> Option<&Deprecation> {
+        self.deprecated_functions
+            .get(n)
+            .or(self.deprecation.as_ref())
+    }
+
+    /// The deprecation of the constant named `n`, or of the module if the constant is not
+    /// deprecated
+    pub fn constant_deprecation(&self, n: &Symbol) -> Option<&Deprecation> {
+        self.deprecated_constants
+            .get(n)
+            .or(self.deprecation.as_ref())
+    }
+
+    pub fn structs(&self) -> impl Iterator<Item = (&Symbol, &StructInfo)> {
+        self.structs.iter()
+    }
+
+    pub fn enums(&self) -> impl Iterator<Item = (&Symbol, &EnumInfo)> {
+        self.enums.iter()
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = (&Symbol, Loc)> {
+        self.functions.iter().map(|(n, loc)| (n, *loc))
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = (&Symbol, Loc)> {
+        self.constants.iter().map(|(n, loc)| (n, *loc))
+    }
+}
+
+impl ModuleInfoMap {
+    pub fn new(pre_compiled_lib: Option<&FullyCompiledProgram>, prog: &E::Program) -> Self {
+        let all_modules = prog
+            .modules
+            .key_cloned_iter()
+            .chain(pre_compiled_lib.iter().flat_map(|pre_compiled| {
+                pre_compiled
+                    .expansion
+                    .modules
+                    .key_cloned_iter()
+                    .filter(|(mident, _m)| !prog.modules.contains_key(mident))
+            }));
+        let modules = all_modules
+            .map(|(mident, mdef)| (mident, ModuleInfo::new(&mident, mdef)))
+            .collect();
+        Self { modules }
+    }
+
+    pub fn module(&self, m: &ModuleIdent) -> Option<&ModuleInfo> {
+        self.modules.get(m)
+    }
+
+    pub fn contains_module(&self, m: &ModuleIdent) -> bool {
+        self.modules.contains_key(m)
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = (&ModuleIdent, &ModuleInfo)> {
+        self.modules.iter()
+    }
+
+    /// The struct `m::n`, if both the module and the struct exist
+    pub fn struct_(&self, m: &ModuleIdent, n: &Symbol) -> Option<&StructInfo> {
+        self.module(m)?.struct_(n)
+    }
+
+    /// The enum `m::n`, if both the module and the enum exist
+    pub fn enum_(&self, m: &ModuleIdent, n: &Symbol) -> Option<&EnumInfo> {
+        self.module(m)?.enum_(n)
+    }
+
+    /// The location of the declaration of the function `m::n`, if both the module and the
+    /// function exist
+    pub fn function(&self, m: &ModuleIdent, n: &Symbol) -> Option<Loc> {
+        self.module(m)?.function(n)
+    }
+
+    /// The location of the declaration of the constant `m::n`, if both the module and the
+    /// constant exist
+    pub fn constant(&self, m: &ModuleIdent, n: &Symbol) -> Option<Loc> {
+        self.module(m)?.constant(n)
+    }
+}
+
+pub const DEPRECATED_ATTR: E::AttributeName_ = E::AttributeName_::Known(
+    KnownAttribute::Deprecation(DeprecationAttribute::Deprecated),
+);
+
+/// The deprecation declared by `attributes`, if any. Malformed `deprecated` attributes are reported
+/// by naming, and are taken as deprecations without a note.
+pub fn deprecation(decl_loc: Loc, attributes: &E::Attributes) -> Option<Deprecation> {
+    let attr = attributes.get_(&DEPRECATED_ATTR)?;
+    let note = match &attr.value {
+        E::Attribute_::Parameterized(_, inner) => inner
+            .get_(&E::AttributeName_::Unknown(
+                DeprecationAttribute::NOTE.into(),
+            ))
+            .and_then(|note| match &note.value {
+                E::Attribute_::Assigned(_, value) => match &value.value {
+                    E::AttributeValue_::Value(sp!(_, E::Value_::Bytearray(bytes))) => {
+                        Some(String::from_utf8_lossy(bytes).into_owned())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }),
+        E::Attribute_::Name(_) | E::Attribute_::Assigned(_, _) => None,
+    };
+    Some(Deprecation { decl_loc, note })
+}
+
+const TEST_ONLY_ATTR: E::AttributeName_ =
+    E::AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::TestOnly));
+
+fn is_test_only(attributes: &E::Attributes) -> bool {
+    attributes.contains_key_(&TEST_ONLY_ATTR)
+}
+
+fn test_only_members<K, V>(
+    members: &UniqueMap<K, V>,
+    attributes: impl Fn(&V) -> &E::Attributes,
+) -> BTreeSet<Symbol>
+where
+    K: TName<Key = Symbol, Loc = Loc>,
+{
+    members
+        .iter()
+        .filter(|(_, _, member)| is_test_only(attributes(member)))
+        .map(|(_, n, _)| *n)
+        .collect()
+}
+
+/// The struct that the first parameter of `fdef` is an instance of, or a reference to, if any
+fn receiver(mdef: &E::ModuleDefinition, fdef: &E::Function) -> Option<(ModuleIdent_, Symbol)> {
+    let (_, _, sp!(_, ty_)) = fdef.signature.parameters.first()?;
+    let ty_ = match ty_ {
+        E::Type_::Ref(_, inner) => &inner.value,
+        ty_ => ty_,
+    };
+    let E::Type_::Apply(sp!(_, access), _) = ty_ else {
+        return None;
+    };
+    match access {
+        E::ModuleAccess_::ModuleAccess(m, n) => Some((m.value, n.value)),
+        E::ModuleAccess_::Variant(_, _) => None,
+        // the uses of the struct aliases of the module are resolved during naming, unless shadowed
+        // by a type parameter
+        E::ModuleAccess_::Name(n) => {
+            let type_parameters = &fdef.signature.type_parameters;
+            if type_parameters.iter().any(|(tp, _)| tp.value == n.value) {
+                return None;
+            }
+            let (m, sn) = mdef.type_aliases.get_(&n.value)?;
+            Some((m.value, sn.value))
+        }
+    }
+}
+
+fn spec_members(specs: &[E::SpecBlock]) -> BTreeSet<Symbol> {
+    let mut members = BTreeSet::new();
+    for sp!(_, spec) in specs {
+        if let E::SpecBlockTarget_::Schema(name, _) = &spec.target.value {
+            members.insert(name.value);
+        }
+        for sp!(_, member) in &spec.members {
+            match member {
+                E::SpecBlockMember_::Function { name, .. } => {
+                    members.insert(name.0.value);
+                }
+                E::SpecBlockMember_::Variable {
+                    is_global: true,
+                    name,
+                    ..
+                } => {
+                    members.insert(name.value);
+                }
+                _ => (),
+            }
+        }
+    }
+    members
+}
+
+fn deprecated_members<K, V>(
+    members: &UniqueMap<K, V>,
+    attributes: impl Fn(&V) -> &E::Attributes,
+) -> BTreeMap<Symbol, Deprecation>
+where
+    K: TName<Key = Symbol, Loc = Loc>,
+{
+    members
+        .iter()
+        .filter_map(|(loc, n, member)| Some((*n, deprecation(loc, attributes(member))?)))
+        .collect()
+}
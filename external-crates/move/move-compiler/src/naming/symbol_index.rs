@@ -0,0 +1,127 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! The definitions and resolved uses of the names of a program, as seen by naming. It is only
+//! recorded in IDE mode (see `Flags::ide_mode`), for go-to-definition and find-references.
+
+use crate::{expansion::ast::ModuleIdent, naming::ast as N};
+use move_command_line_common::files::FileHash;
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+use std::collections::{BTreeMap, HashMap};
+
+/// A named entity of the program. Module members of scripts have no module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SymbolId {
+    Module(ModuleIdent),
+    Struct(ModuleIdent, Symbol),
+    Enum(ModuleIdent, Symbol),
+    /// A variant of an enum, by the enum it is declared in
+    Variant(ModuleIdent, Symbol, Symbol),
+    Function(Option<ModuleIdent>, Symbol),
+    Constant(Option<ModuleIdent>, Symbol),
+    /// A type parameter of a function or of a struct
+    TypeParameter(N::TParamID),
+    /// A parameter or local of a function, as it was before macro expansion (with color zero)
+    Local(Option<ModuleIdent>, Symbol, N::Var_),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    /// Where each symbol of the program is defined. Members of modules that are not part of the
+    /// program (e.g. those of a pre-compiled library) have no definition
+    definitions: BTreeMap<SymbolId, Loc>,
+    /// The symbol that the name at each location resolves to
+    uses: HashMap<Loc, SymbolId>,
+}
+
+impl SymbolIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn add_definition(&mut self, id: SymbolId, loc: Loc) {
+        self.definitions.insert(id, loc);
+    }
+
+    pub(crate) fn add_use(&mut self, loc: Loc, id: SymbolId) {
+        self.uses.insert(loc, id);
+    }
+
+    pub(crate) fn extend(&mut self, other: SymbolIndex) {
+        self.definitions.extend(other.definitions);
+        self.uses.extend(other.uses);
+    }
+
+    pub fn definition(&self, id: &SymbolId) -> Option<Loc> {
+        self.definitions.get(id).copied()
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = (&SymbolId, &Loc)> {
+        self.definitions.iter()
+    }
+
+    pub fn uses(&self) -> impl Iterator<Item = (&Loc, &SymbolId)> {
+        self.uses.iter()
+    }
+
+    /// The locations of all uses of `id`, in no particular order
+    pub fn references<'a>(&'a self, id: &'a SymbolId) -> impl Iterator<Item = Loc> + 'a {
+        self.uses
+            .iter()
+            .filter(move |(_, used)| *used == id)
+            .map(|(loc, _)| *loc)
+    }
+
+    /// The symbol defined or used at the innermost name containing the byte `offset` of a file
+    pub fn symbol_at(&self, file_hash: FileHash, offset: ByteIndex) -> Option<&SymbolId> {
+        let contains =
+            |loc: &Loc| loc.file_hash() == file_hash && loc.start() <= offset && offset < loc.end();
+        let defs = self.definitions.iter().map(|(id, loc)| (loc, id));
+        self.uses
+            .iter()
+            .chain(defs)
+            .filter(|(loc, _)| contains(loc))
+            .min_by_key(|(loc, _)| loc.end() - loc.start())
+            .map(|(_, id)| id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol_at_innermost_name() {
+        let file_hash = FileHash::new("fun f(x: u64): u64 { g(x) }");
+        let loc = |start, end| Loc::new(file_hash, start, end);
+        let f = SymbolId::Function(None, Symbol::from("f"));
+        let g = SymbolId::Function(None, Symbol::from("g"));
+        let x = SymbolId::Local(
+            None,
+            Symbol::from("f"),
+            N::Var_ {
+                name: Symbol::from("x"),
+                id: 0,
+                color: 0,
+            },
+        );
+
+        let mut index = SymbolIndex::new();
+        index.add_definition(f, loc(4, 5));
+        index.add_definition(x, loc(6, 7));
+        index.add_use(loc(21, 25), g);
+        index.add_use(loc(23, 24), x);
+
+        assert_eq!(index.symbol_at(file_hash, 4), Some(&f));
+        assert_eq!(index.symbol_at(file_hash, 6), Some(&x));
+        assert_eq!(index.symbol_at(file_hash, 21), Some(&g));
+        assert_eq!(index.symbol_at(file_hash, 23), Some(&x));
+        assert_eq!(index.symbol_at(file_hash, 10), None);
+        assert_eq!(index.symbol_at(FileHash::new(""), 4), None);
+
+        assert_eq!(index.definition(&x), Some(loc(6, 7)));
+        assert_eq!(index.definition(&g), None);
+        assert_eq!(index.references(&x).collect::<Vec<_>>(), vec![loc(23, 24)]);
+    }
+}
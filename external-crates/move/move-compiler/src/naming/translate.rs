@@ -4,16 +4,29 @@
 
 use crate::{
     diag,
-    diagnostics::codes::*,
+    diagnostics::{codes::*, Fix},
+    editions::{FeatureGate, Flavor},
     expansion::{
-        ast::{self as E, AbilitySet, ModuleIdent},
+        ast::{self as E, AbilitySet, ModuleIdent, ModuleIdent_},
         translate::is_valid_struct_constant_or_schema_name as is_constant_name,
     },
-    naming::ast::{self as N, Neighbor_},
-    parser::ast::{self as P, Ability_, ConstantName, Field, FunctionName, StructName},
+    naming::{
+        ast::{self as N, Neighbor_},
+        cache::{debug_hash, CachedModule, NamingCache},
+        module_info::{
+            deprecation, Deprecation, EnumInfo, ModuleInfo, ModuleInfoMap, StructInfo,
+            VariantFieldsInfo, DEPRECATED_ATTR,
+        },
+        symbol_index::{SymbolId, SymbolIndex},
+    },
+    parser::ast::{
+        self as P, Ability_, ConstantName, EnumName, Field, FunctionName, StructName, VariantName,
+    },
     shared::{unique_map::UniqueMap, *},
+    sui_mode::{INIT_FUNCTION_NAME, STD_ADDR_NAME, UTF_MODULE_NAME},
     FullyCompiledProgram,
 };
+use move_core_types::account_address::AccountAddress;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use std::collections::{BTreeMap, BTreeSet};
@@ -27,12 +40,27 @@ use super::{
 // Context
 //**************************************************************************************************
 
+const UTF8_FUNCTION_NAME: Symbol = symbol!("utf8");
+/// The name of the labels given to loops that have none in the source
+const IMPLICIT_LOOP_LABEL: Symbol = symbol!("loop");
+
 #[derive(Debug, Clone)]
 enum ResolvedType {
     TParam(Loc, N::TParam),
     BuiltinType,
+    /// An alias, declared at the given location, of the struct or enum `m::n`
+    ModuleType(Loc, Box<ModuleIdent>, Symbol),
 }
 
+/// A resolved variant `m::e::v`, with its type arguments if any, and how its fields are declared
+type ResolvedVariant = (
+    ModuleIdent,
+    EnumName,
+    VariantName,
+    Option<Vec<N::Type>>,
+    VariantFieldsInfo,
+);
+
 impl ResolvedType {
     fn error_msg(&self, n: &Name) -> (Loc, String) {
         match self {
@@ -41,6 +69,13 @@ impl ResolvedType {
                 format!("But '{}' was declared as a type parameter here", n),
             ),
             ResolvedType::BuiltinType => (n.loc, format!("But '{}' is a builtin type", n)),
+            ResolvedType::ModuleType(loc, m, sn) => (
+                *loc,
+                format!(
+                    "But '{}' was declared as an alias of '{}::{}' here",
+                    n, m, sn
+                ),
+            ),
         }
     }
 }
@@ -48,12 +83,11 @@ impl ResolvedType {
 struct Context<'env> {
     env: &'env mut CompilationEnv,
     current_module: Option<ModuleIdent>,
-    scoped_types: BTreeMap<ModuleIdent, BTreeMap<Symbol, (Loc, ModuleIdent, AbilitySet, usize)>>,
+    module_info: ModuleInfoMap,
     unscoped_types: BTreeMap<Symbol, ResolvedType>,
-    scoped_functions: BTreeMap<ModuleIdent, BTreeMap<Symbol, Loc>>,
     unscoped_constants: BTreeMap<Symbol, Loc>,
-    scoped_constants: BTreeMap<ModuleIdent, BTreeMap<Symbol, Loc>>,
-    local_scopes: Vec<BTreeMap<Symbol, u16>>,
+    /// The locals in scope, with their id and the location of their declaration
+    local_scopes: Vec<BTreeMap<Symbol, (u16, Loc)>>,
     local_count: BTreeMap<Symbol, u16>,
     used_locals: BTreeSet<N::Var_>,
     /// Type parameters used in a function (they have to be cleared after processing each function).
@@ -61,6 +95,32 @@ struct Context<'env> {
     /// Indicates if the compiler is currently translating a function (set to true before starting
     /// to translate a function and to false after translation is over).
     translating_fun: bool,
+    /// The location of the 'macro' modifier of the function being translated, if it is a macro
+    translating_macro: Option<Loc>,
+    /// The labels of the loops around the expression being translated, within the current
+    /// function, innermost last. Each is paired with its name in the source, if it has one
+    loop_scopes: Vec<(Option<Symbol>, N::BlockLabel)>,
+    /// The number of loops labeled so far in the current function
+    loop_count: u16,
+    /// The function being translated, if any
+    current_function: Option<FunctionName>,
+    /// The definitions and uses of names, only recorded in IDE mode
+    symbol_index: Option<SymbolIndex>,
+    /// The modules that names were resolved in, since the start of the current module
+    used_modules: BTreeSet<ModuleIdent>,
+    /// The module that string literals were lowered to calls into, since the start of the
+    /// current module
+    used_string_module: Option<ModuleIdent>,
+    /// The constants referenced so far, by the module they are declared in
+    used_constants: BTreeSet<(ModuleIdent_, Symbol)>,
+    /// The functions referenced so far, by the module they are declared in
+    used_functions: BTreeSet<(ModuleIdent_, Symbol)>,
+    /// The names bound in the specification being resolved, innermost scope last. Names in
+    /// specifications are only resolved in verification mode
+    spec_scopes: Vec<BTreeSet<Symbol>>,
+    /// The spec blocks inside the body of the function being translated, in verification mode.
+    /// They are resolved where they appear, as they can use the locals in scope there
+    function_specs: BTreeMap<E::SpecId, E::SpecBlock>,
 }
 
 impl<'env> Context<'env> {
@@ -70,62 +130,16 @@ impl<'env> Context<'env> {
         prog: &E::Program,
     ) -> Self {
         use ResolvedType as RT;
-        let all_modules = || {
-            prog.modules
-                .key_cloned_iter()
-                .chain(pre_compiled_lib.iter().flat_map(|pre_compiled| {
-                    pre_compiled
-                        .expansion
-                        .modules
-                        .key_cloned_iter()
-                        .filter(|(mident, _m)| !prog.modules.contains_key(mident))
-                }))
-        };
-        let scoped_types = all_modules()
-            .map(|(mident, mdef)| {
-                let mems = mdef
-                    .structs
-                    .key_cloned_iter()
-                    .map(|(s, sdef)| {
-                        let abilities = sdef.abilities.clone();
-                        let arity = sdef.type_parameters.len();
-                        let sname = s.value();
-                        (sname, (s.loc(), mident, abilities, arity))
-                    })
-                    .collect();
-                (mident, mems)
-            })
-            .collect();
-        let scoped_functions = all_modules()
-            .map(|(mident, mdef)| {
-                let mems = mdef
-                    .functions
-                    .iter()
-                    .map(|(nloc, n, _)| (*n, nloc))
-                    .collect();
-                (mident, mems)
-            })
-            .collect();
-        let scoped_constants = all_modules()
-            .map(|(mident, mdef)| {
-                let mems = mdef
-                    .constants
-                    .iter()
-                    .map(|(nloc, n, _)| (*n, nloc))
-                    .collect();
-                (mident, mems)
-            })
-            .collect();
+        let module_info = ModuleInfoMap::new(pre_compiled_lib, prog);
         let unscoped_types = N::BuiltinTypeName_::all_names()
             .iter()
             .map(|s| (*s, RT::BuiltinType))
             .collect();
+        let symbol_index = compilation_env.flags().ide_mode().then(SymbolIndex::new);
         Self {
             env: compilation_env,
             current_module: None,
-            scoped_types,
-            scoped_functions,
-            scoped_constants,
+            module_info,
             unscoped_types,
             unscoped_constants: BTreeMap::new(),
             local_scopes: vec![],
@@ -133,15 +147,44 @@ impl<'env> Context<'env> {
             used_locals: BTreeSet::new(),
             used_fun_tparams: BTreeSet::new(),
             translating_fun: false,
+            translating_macro: None,
+            loop_scopes: vec![],
+            loop_count: 0,
+            current_function: None,
+            symbol_index,
+            used_modules: BTreeSet::new(),
+            used_constants: BTreeSet::new(),
+            used_functions: BTreeSet::new(),
+            used_string_module: None,
+            spec_scopes: vec![],
+            function_specs: BTreeMap::new(),
+        }
+    }
+
+    fn record_definition(&mut self, id: SymbolId, loc: Loc) {
+        if let Some(index) = &mut self.symbol_index {
+            index.add_definition(id, loc)
+        }
+    }
+
+    fn record_use(&mut self, loc: Loc, id: SymbolId) {
+        if let Some(index) = &mut self.symbol_index {
+            index.add_use(loc, id)
         }
     }
 
+    /// The symbol of local `v` of the function being translated, if any
+    fn local_symbol(&self, v: N::Var_) -> Option<SymbolId> {
+        let f = self.current_function?;
+        Some(SymbolId::Local(self.current_module, f.value(), v))
+    }
+
     fn resolve_module(&mut self, m: &ModuleIdent) -> bool {
-        // NOTE: piggybacking on `scoped_functions` to provide a set of modules in the context。
-        // TODO: a better solution would be to have a single `BTreeMap<ModuleIdent, ModuleInfo>`
-        // in the context that can be used to resolve modules, types, and functions.
-        let resolved = self.scoped_functions.contains_key(m);
-        if !resolved {
+        let resolved = self.module_info.contains_module(m);
+        self.used_modules.insert(*m);
+        if resolved {
+            self.record_use(m.loc, SymbolId::Module(*m));
+        } else {
             self.env.add_diag(diag!(
                 NameResolution::UnboundModule,
                 (m.loc, format!("Unbound module '{}'", m))
@@ -150,23 +193,63 @@ impl<'env> Context<'env> {
         resolved
     }
 
+    /// Warns about a use of `m::n` outside of `m` if the member, or the module itself, is
+    /// deprecated. The module declaring the member may keep using it
+    fn check_deprecated_use(
+        &mut self,
+        loc: Loc,
+        kind: &str,
+        m: &ModuleIdent,
+        n: &Name,
+        deprecation: impl for<'a> FnOnce(&'a ModuleInfo, &Symbol) -> Option<&'a Deprecation>,
+    ) {
+        if self.current_module.as_ref() == Some(m) {
+            return;
+        }
+        let Some(Deprecation { decl_loc, note }) = self
+            .module_info
+            .module(m)
+            .and_then(|minfo| deprecation(minfo, &n.value))
+        else {
+            return;
+        };
+        let msg = match note {
+            None => format!("The {kind} '{m}::{n}' is deprecated"),
+            Some(note) => format!("The {kind} '{m}::{n}' is deprecated: {note}"),
+        };
+        self.env.add_diag(diag!(
+            NameResolution::DeprecatedUsage,
+            (loc, msg),
+            (*decl_loc, "Deprecated here"),
+        ));
+    }
+
+    /// The members of module `m`, reporting an error if it does not exist
+    fn resolve_module_info(&mut self, m: &ModuleIdent) -> Option<&ModuleInfo> {
+        if !self.resolve_module(m) {
+            return None;
+        }
+        self.module_info.module(m)
+    }
+
     fn resolve_module_type(
         &mut self,
         loc: Loc,
         m: &ModuleIdent,
         n: &Name,
     ) -> Option<(Loc, StructName, AbilitySet, usize)> {
-        let types = match self.scoped_types.get(m) {
-            None => {
-                self.env.add_diag(diag!(
-                    NameResolution::UnboundModule,
-                    (m.loc, format!("Unbound module '{}'", m)),
-                ));
-                return None;
+        let minfo = self.resolve_module_info(m)?;
+        let is_enum = minfo.enum_(&n.value).is_some();
+        match minfo.struct_(&n.value).cloned() {
+            None if is_enum => {
+                let msg = format!(
+                    "Invalid module access. Expected a struct, but '{}::{}' is an enum",
+                    m, n
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::NamePositionMismatch, (loc, msg)));
+                None
             }
-            Some(members) => members,
-        };
-        match types.get(&n.value) {
             None => {
                 let msg = format!(
                     "Invalid module access. Unbound struct '{}' in module '{}'",
@@ -176,8 +259,104 @@ impl<'env> Context<'env> {
                     .add_diag(diag!(NameResolution::UnboundModuleMember, (loc, msg)));
                 None
             }
-            Some((decl_loc, _, abilities, arity)) => {
-                Some((*decl_loc, StructName(*n), abilities.clone(), *arity))
+            Some(StructInfo {
+                decl_loc,
+                abilities,
+                arity,
+                ..
+            }) => {
+                self.record_use(n.loc, SymbolId::Struct(*m, n.value));
+                self.check_deprecated_use(loc, "struct", m, n, ModuleInfo::struct_deprecation);
+                Some((decl_loc, StructName(*n), abilities, arity))
+            }
+        }
+    }
+
+    fn resolve_module_enum(&mut self, loc: Loc, m: &ModuleIdent, n: &Name) -> Option<EnumInfo> {
+        match self.resolve_module_info(m)?.enum_(&n.value).cloned() {
+            None => {
+                let msg = format!(
+                    "Invalid module access. Unbound enum '{}' in module '{}'",
+                    n, m
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::UnboundModuleMember, (loc, msg)));
+                None
+            }
+            Some(einfo) => {
+                self.record_use(n.loc, SymbolId::Enum(*m, n.value));
+                self.check_deprecated_use(loc, "enum", m, n, ModuleInfo::enum_deprecation);
+                Some(einfo)
+            }
+        }
+    }
+
+    /// Resolves the variant `v` of the enum `m::e`, constructed or deconstructed by the `verb`,
+    /// along with how its fields are declared
+    fn resolve_variant_name(
+        &mut self,
+        loc: Loc,
+        verb: &str,
+        sp!(eloc, (m, e)): Spanned<(ModuleIdent, Name)>,
+        v: Name,
+        etys_opt: Option<Vec<E::Type>>,
+    ) -> Option<ResolvedVariant> {
+        let einfo = self.resolve_module_enum(eloc, &m, &e)?;
+        let Some(vinfo) = einfo.variants.get(&v.value) else {
+            let msg = format!(
+                "Invalid {}. Unbound variant '{}' in enum '{}::{}'",
+                verb, v, m, e
+            );
+            self.env.add_diag(diag!(
+                NameResolution::UnboundVariant,
+                (v.loc, msg),
+                (einfo.decl_loc, format!("Enum '{}' is declared here", e)),
+            ));
+            return None;
+        };
+        let fields = vinfo.fields;
+        self.record_use(v.loc, SymbolId::Variant(m, e.value, v.value));
+        let tys_opt = etys_opt.map(|etys| {
+            let tys = types(self, etys);
+            let name_f = || format!("{}::{}", &m, &e);
+            let decl_loc = Some(einfo.decl_loc);
+            check_type_argument_arity(self, loc, name_f, tys, einfo.arity, decl_loc)
+        });
+        Some((m, EnumName(e), VariantName(v), tys_opt, fields))
+    }
+
+    /// Checks that the variant `m::e::v`, constructed or deconstructed with positional or named
+    /// fields, is declared with fields of the same kind. Variants without fields are used with
+    /// named fields. Returns its number of positional fields (or `Some(None)` if it does not have
+    /// positional fields), or `None` after reporting an error
+    fn check_variant_fields(
+        &mut self,
+        loc: Loc,
+        verb: &str,
+        (m, e, v): (&ModuleIdent, &EnumName, &VariantName),
+        fields: VariantFieldsInfo,
+        positional: bool,
+    ) -> Option<Option<usize>> {
+        match (positional, fields) {
+            (true, VariantFieldsInfo::Positional(num_fields)) => Some(Some(num_fields)),
+            (false, VariantFieldsInfo::Named | VariantFieldsInfo::Empty) => Some(None),
+            (true, VariantFieldsInfo::Named | VariantFieldsInfo::Empty) => {
+                let msg = format!(
+                    "Invalid {}. Variant '{}::{}::{}' does not have positional fields",
+                    verb, m, e, v
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::PositionalCallMismatch, (loc, msg)));
+                None
+            }
+            (false, VariantFieldsInfo::Positional(_)) => {
+                let msg = format!(
+                    "Invalid {}. Variant '{}::{}::{}' has positional fields. Expected '{}::{}(...)'",
+                    verb, m, e, v, e, v
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::PositionalCallMismatch, (loc, msg)));
+                None
             }
         }
     }
@@ -188,17 +367,7 @@ impl<'env> Context<'env> {
         m: &ModuleIdent,
         n: &Name,
     ) -> Option<FunctionName> {
-        let functions = match self.scoped_functions.get(m) {
-            None => {
-                self.env.add_diag(diag!(
-                    NameResolution::UnboundModule,
-                    (m.loc, format!("Unbound module '{}'", m)),
-                ));
-                return None;
-            }
-            Some(members) => members,
-        };
-        match functions.get(&n.value).cloned() {
+        match self.resolve_module_info(m)?.function(&n.value) {
             None => {
                 let msg = format!(
                     "Invalid module access. Unbound function '{}' in module '{}'",
@@ -208,27 +377,29 @@ impl<'env> Context<'env> {
                     .add_diag(diag!(NameResolution::UnboundModuleMember, (loc, msg)));
                 None
             }
-            Some(_) => Some(FunctionName(*n)),
+            Some(_) => {
+                self.record_use(n.loc, SymbolId::Function(Some(*m), n.value));
+                self.used_functions.insert((m.value, n.value));
+                self.check_deprecated_use(loc, "function", m, n, ModuleInfo::function_deprecation);
+                Some(FunctionName(*n))
+            }
         }
     }
 
+    /// Whether `m::n` is declared as a `macro fun`
+    fn is_macro(&self, m: &ModuleIdent, n: &Name) -> bool {
+        self.module_info
+            .module(m)
+            .is_some_and(|minfo| minfo.is_macro(&n.value))
+    }
+
     fn resolve_module_constant(
         &mut self,
         loc: Loc,
         m: &ModuleIdent,
         n: Name,
     ) -> Option<ConstantName> {
-        let constants = match self.scoped_constants.get(m) {
-            None => {
-                self.env.add_diag(diag!(
-                    NameResolution::UnboundModule,
-                    (m.loc, format!("Unbound module '{}'", m)),
-                ));
-                return None;
-            }
-            Some(members) => members,
-        };
-        match constants.get(&n.value).cloned() {
+        match self.resolve_module_info(m)?.constant(&n.value) {
             None => {
                 let msg = format!(
                     "Invalid module access. Unbound constant '{}' in module '{}'",
@@ -238,10 +409,39 @@ impl<'env> Context<'env> {
                     .add_diag(diag!(NameResolution::UnboundModuleMember, (loc, msg)));
                 None
             }
-            Some(_) => Some(ConstantName(n)),
+            Some(_) => {
+                self.record_use(n.loc, SymbolId::Constant(Some(*m), n.value));
+                self.used_constants.insert((m.value, n.value));
+                self.check_deprecated_use(loc, "constant", m, &n, ModuleInfo::constant_deprecation);
+                Some(ConstantName(n))
+            }
         }
     }
 
+    /// Finds the `std::string` module (by name or by the `0x1` address) if it is available and
+    /// defines the `utf8` function used to lower string literals
+    fn string_module(&self) -> Option<ModuleIdent> {
+        self.module_info
+            .modules()
+            .find(|(m, minfo)| {
+                let sp!(_, E::ModuleIdent_ { address, module }) = m;
+                let is_std = match address {
+                    E::Address::Numerical(Some(sp!(_, n)), _)
+                    | E::Address::NamedUnassigned(sp!(_, n))
+                        if *n == STD_ADDR_NAME =>
+                    {
+                        true
+                    }
+                    E::Address::Numerical(_, sp!(_, a)) => a.into_inner() == AccountAddress::ONE,
+                    _ => false,
+                };
+                is_std
+                    && module.value() == UTF_MODULE_NAME
+                    && minfo.function(&UTF8_FUNCTION_NAME).is_some()
+            })
+            .map(|(m, _)| *m)
+    }
+
     fn resolve_unscoped_type(&mut self, n: &Name) -> Option<ResolvedType> {
         match self.unscoped_types.get(&n.value) {
             None => {
@@ -269,6 +469,10 @@ impl<'env> Context<'env> {
                     assert!(self.env.has_errors());
                     None
                 }
+                Some(ResolvedType::ModuleType(_, m, sn)) => {
+                    let ma = sp(nloc, EA::ModuleAccess(*m, sp(n.loc, sn)));
+                    self.resolve_struct_name(loc, verb, ma, etys_opt)
+                }
                 Some(rt) => {
                     self.env.add_diag(diag!(
                         NameResolution::NamePositionMismatch,
@@ -283,15 +487,63 @@ impl<'env> Context<'env> {
                     assert!(self.env.has_errors());
                     None
                 }
-                Some((_, _, _, arity)) => {
+                Some((decl_loc, _, _, arity)) => {
                     let tys_opt = etys_opt.map(|etys| {
                         let tys = types(self, etys);
                         let name_f = || format!("{}::{}", &m, &n);
-                        check_type_argument_arity(self, loc, name_f, tys, arity)
+                        check_type_argument_arity(self, loc, name_f, tys, arity, Some(decl_loc))
                     });
                     Some((m, StructName(n), tys_opt))
                 }
             },
+            EA::Variant(e, v) => {
+                let sp!(_, (m, e)) = *e;
+                let msg = format!(
+                    "Invalid {}. Expected a struct name, but got the variant '{}::{}::{}'",
+                    verb, m, e, v
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::NamePositionMismatch, (nloc, msg)));
+                None
+            }
+        }
+    }
+
+    /// Checks that the struct `m::n`, constructed or deconstructed with positional or named
+    /// fields, is declared with fields of the same kind. Returns its number of positional fields
+    /// (or `Some(None)` if it does not have positional fields), or `None` after reporting an error
+    fn check_positional_fields(
+        &mut self,
+        loc: Loc,
+        verb: &str,
+        m: &ModuleIdent,
+        n: &StructName,
+        positional: bool,
+    ) -> Option<Option<usize>> {
+        let positional_fields = self
+            .module_info
+            .struct_(m, &n.value())
+            .and_then(|sinfo| sinfo.positional_fields);
+        match (positional, positional_fields) {
+            (true, None) => {
+                let msg = format!(
+                    "Invalid {}. Struct '{}::{}' does not have positional fields",
+                    verb, m, n
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::PositionalCallMismatch, (loc, msg)));
+                None
+            }
+            (false, Some(_)) => {
+                let msg = format!(
+                    "Invalid {}. Struct '{}::{}' has positional fields. Expected '{}(...)'",
+                    verb, m, n, n
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::PositionalCallMismatch, (loc, msg)));
+                None
+            }
+            (_, num_fields) => Some(num_fields),
         }
     }
 
@@ -309,7 +561,13 @@ impl<'env> Context<'env> {
                     ));
                     None
                 }
-                Some(_) => Some((None, ConstantName(n))),
+                Some(_) => {
+                    self.record_use(n.loc, SymbolId::Constant(self.current_module, n.value));
+                    if let Some(m) = self.current_module {
+                        self.used_constants.insert((m.value, n.value));
+                    }
+                    Some((None, ConstantName(n)))
+                }
             },
             EA::ModuleAccess(m, n) => match self.resolve_module_constant(loc, &m, n) {
                 None => {
@@ -318,6 +576,16 @@ impl<'env> Context<'env> {
                 }
                 Some(cname) => Some((Some(m), cname)),
             },
+            EA::Variant(e, v) => {
+                let sp!(_, (m, e)) = *e;
+                let msg = format!(
+                    "Unexpected variant '{}::{}::{}'. Expected a constant",
+                    m, e, v
+                );
+                self.env
+                    .add_diag(diag!(NameResolution::NamePositionMismatch, (loc, msg)));
+                None
+            }
         }
     }
 
@@ -357,15 +625,43 @@ impl<'env> Context<'env> {
             .entry(name)
             .and_modify(|c| *c += 1)
             .or_insert(default);
-        self.local_scopes.last_mut().unwrap().insert(name, id);
+        self.local_scopes
+            .last_mut()
+            .unwrap()
+            .insert(name, (id, vloc));
         // all locals start at color zero
         // they will be incremented when substituted for macros
         let nvar_ = N::Var_ { name, id, color: 0 };
+        if let Some(symbol) = self.local_symbol(nvar_) {
+            self.record_definition(symbol, vloc);
+        }
         sp(vloc, nvar_)
     }
 
+    /// Reports a warning, if enabled, when a local declared by a `let` has the name of a local
+    /// or parameter that is already in scope
+    fn check_shadowed_local(&mut self, sp!(vloc, name): Name) {
+        if !self.env.flags().warn_shadowing() || name.starts_with('_') {
+            return;
+        }
+        let Some((_, prev_loc)) = self.local_scopes.last().unwrap().get(&name).copied() else {
+            return;
+        };
+        let msg = format!("Local '{name}' shadows an existing local or parameter");
+        self.env.add_diag(diag!(
+            NameResolution::ShadowedLocal,
+            (vloc, msg),
+            (prev_loc, "Previously declared here"),
+        ));
+    }
+
     fn resolve_local(&mut self, loc: Loc, verb: &str, sp!(vloc, name): Name) -> Option<N::Var> {
-        let id_opt = self.local_scopes.last().unwrap().get(&name).copied();
+        let id_opt = self
+            .local_scopes
+            .last()
+            .unwrap()
+            .get(&name)
+            .map(|(id, _)| *id);
         match id_opt {
             None => {
                 let msg = format!("Invalid {}. Unbound variable '{}'", verb, name);
@@ -378,21 +674,96 @@ impl<'env> Context<'env> {
                 // they will be incremented when substituted for macros
                 let nvar_ = N::Var_ { name, id, color: 0 };
                 self.used_locals.insert(nvar_);
+                if let Some(symbol) = self.local_symbol(nvar_) {
+                    self.record_use(vloc, symbol);
+                }
                 Some(sp(vloc, nvar_))
             }
         }
     }
+
+    fn enter_loop(&mut self, loc: Loc, label_opt: Option<P::BlockLabel>) -> N::BlockLabel {
+        if let Some(label) = label_opt {
+            let sp!(lloc, name) = label.0;
+            let prev_loc_opt = self
+                .loop_scopes
+                .iter()
+                .find(|(prev_name, _)| *prev_name == Some(name))
+                .map(|(_, prev)| prev.0.loc);
+            if let Some(prev_loc) = prev_loc_opt {
+                let msg = format!(
+                    "Invalid loop label. '{}' is already the label of a loop around this one",
+                    label
+                );
+                self.env.add_diag(diag!(
+                    NameResolution::ShadowedLabel,
+                    (lloc, msg),
+                    (prev_loc, "Previously declared here"),
+                ));
+            }
+        }
+        self.loop_count += 1;
+        let (lloc, name) = match label_opt {
+            Some(P::BlockLabel(sp!(lloc, name))) => (lloc, name),
+            None => (loc, IMPLICIT_LOOP_LABEL),
+        };
+        // all labels start at color zero
+        // they will be incremented when substituted for macros
+        let nvar_ = N::Var_ {
+            name,
+            id: self.loop_count,
+            color: 0,
+        };
+        let nlabel = N::BlockLabel(sp(lloc, nvar_));
+        self.loop_scopes
+            .push((label_opt.map(|label| label.value()), nlabel));
+        nlabel
+    }
+
+    fn exit_loop(&mut self) {
+        self.loop_scopes.pop();
+    }
+
+    // Returns the loop exited or restarted by a 'break' or a 'continue' (the `verb`), with the
+    // given label or around it if it has none
+    fn resolve_loop_label(
+        &mut self,
+        verb: &str,
+        label_opt: Option<P::BlockLabel>,
+    ) -> Option<N::BlockLabel> {
+        let Some(label) = label_opt else {
+            return self.loop_scopes.last().map(|(_, nlabel)| *nlabel);
+        };
+        let name = label.value();
+        let nlabel_opt = self
+            .loop_scopes
+            .iter()
+            .rev()
+            .find(|(loop_name, _)| *loop_name == Some(name))
+            .map(|(_, nlabel)| *nlabel);
+        if nlabel_opt.is_none() {
+            let msg = format!(
+                "Invalid '{}'. Unbound label '{}'. It must be the label of a loop around the '{}'",
+                verb, label, verb
+            );
+            self.env
+                .add_diag(diag!(NameResolution::UnboundLabel, (label.loc(), msg)));
+        }
+        nlabel_opt
+    }
 }
 
 //**************************************************************************************************
 // Entry
 //**************************************************************************************************
 
+/// Resolves the names of the program. In IDE mode, also returns the index of the definitions and
+/// uses of its names
 pub fn program(
     compilation_env: &mut CompilationEnv,
     pre_compiled_lib: Option<&FullyCompiledProgram>,
     prog: E::Program,
-) -> N::Program {
+) -> (N::Program, Option<SymbolIndex>) {
     let mut context = Context::new(compilation_env, pre_compiled_lib, &prog);
     let E::Program {
         modules: emodules,
@@ -400,14 +771,116 @@ pub fn program(
     } = prog;
     let modules = modules(&mut context, emodules);
     let scripts = scripts(&mut context, escripts);
-    N::Program { modules, scripts }
+    let symbol_index = context.symbol_index.take();
+    let mut prog = N::Program { modules, scripts };
+    super::macro_expand::program(compilation_env, pre_compiled_lib, &mut prog);
+    (prog, symbol_index)
 }
 
 fn modules(
     context: &mut Context,
     modules: UniqueMap<ModuleIdent, E::ModuleDefinition>,
 ) -> UniqueMap<ModuleIdent, N::ModuleDefinition> {
-    modules.map(|ident, mdef| module(context, ident, mdef))
+    let modules = match context.env.naming_cache() {
+        None => modules.map(|ident, mdef| module(context, ident, mdef)),
+        Some(cache) => {
+            let mut cache = cache.lock().unwrap();
+            cache.retain(&modules.key_cloned_iter().map(|(ident, _)| ident).collect());
+            modules.map(|ident, mdef| cached_module(context, &mut cache, ident, mdef))
+        }
+    };
+    for (_, mident, mdef) in &modules {
+        gen_unused_warnings(context, mident, mdef);
+    }
+    modules
+}
+
+// Reuses the translation of the module from a previous compilation if it is still valid, or
+// translates it and keeps the translation for the next compilation if it reported no diagnostics
+fn cached_module(
+    context: &mut Context,
+    cache: &mut NamingCache,
+    ident: ModuleIdent,
+    mdef: E::ModuleDefinition,
+) -> N::ModuleDefinition {
+    // the flags decide which diagnostics naming reports (e.g. for shadowing or cross-address
+    // friends) and which members are kept (in test and verification modes)
+    let package_config = context.env.package_config(mdef.package_name);
+    let key = debug_hash(&(&mdef, package_config, context.env.flags()));
+    if let Some(cached) = cache.get(&ident, key) {
+        let dependencies_unchanged = cached.dependencies.iter().all(|(m, signature)| {
+            let current = context
+                .module_info
+                .module(m)
+                .map(ModuleInfo::signature_hash);
+            current == *signature
+        });
+        let string_module_unchanged = cached
+            .string_module
+            .map_or(true, |m| context.string_module() == Some(m));
+        let has_symbols = context.symbol_index.is_none() || cached.symbols.is_some();
+        if dependencies_unchanged && string_module_unchanged && has_symbols {
+            if let (Some(index), Some(symbols)) = (&mut context.symbol_index, &cached.symbols) {
+                index.extend(symbols.clone());
+            }
+            context
+                .used_constants
+                .extend(cached.used_constants.iter().copied());
+            context
+                .used_functions
+                .extend(cached.used_functions.iter().copied());
+            return cached.mdef.clone();
+        }
+    }
+
+    let diag_count = context.env.count_diags_including_filtered();
+    context.used_modules = BTreeSet::new();
+    context.used_string_module = None;
+    // the symbols, constant uses and function uses of the module are recorded on their own, to be
+    // kept with its translation
+    let outer_index = context.symbol_index.as_mut().map(std::mem::take);
+    let outer_used_constants = std::mem::take(&mut context.used_constants);
+    let outer_used_functions = std::mem::take(&mut context.used_functions);
+    let nmdef = module(context, ident, mdef);
+    let symbols = context.symbol_index.take();
+    context.symbol_index = outer_index;
+    if let (Some(index), Some(symbols)) = (&mut context.symbol_index, &symbols) {
+        index.extend(symbols.clone());
+    }
+    let used_constants = std::mem::replace(&mut context.used_constants, outer_used_constants);
+    context
+        .used_constants
+        .extend(used_constants.iter().copied());
+    let used_functions = std::mem::replace(&mut context.used_functions, outer_used_functions);
+    context
+        .used_functions
+        .extend(used_functions.iter().copied());
+
+    if context.env.count_diags_including_filtered() == diag_count {
+        let dependencies = std::mem::take(&mut context.used_modules)
+            .into_iter()
+            .map(|m| {
+                let signature = context
+                    .module_info
+                    .module(&m)
+                    .map(ModuleInfo::signature_hash);
+                (m, signature)
+            })
+            .collect();
+        let cached = CachedModule {
+            key,
+            dependencies,
+            string_module: context.used_string_module.take(),
+            used_constants,
+            used_functions,
+            mdef: nmdef.clone(),
+            symbols,
+        };
+        cache.insert(ident, cached);
+    } else {
+        cache.remove(&ident);
+    }
+    nmdef
 }
 
 fn module(
@@ -416,47 +889,112 @@ fn module(
     mdef: E::ModuleDefinition,
 ) -> N::ModuleDefinition {
     context.current_module = Some(ident);
+    context.record_definition(SymbolId::Module(ident), ident.loc);
     let E::ModuleDefinition {
         loc,
         warning_filter,
         package_name,
         attributes,
+        doc,
         is_source_module,
         friends: efriends,
         structs: estructs,
+        enums: eenums,
         functions: efunctions,
         constants: econstants,
         specs,
+        type_aliases,
+        use_funs: euse_funs,
     } = mdef;
     context.env.add_warning_filter_scope(warning_filter.clone());
+    for (loc, name, _) in &estructs {
+        context.record_definition(SymbolId::Struct(ident, *name), loc);
+    }
+    for (loc, name, e) in &eenums {
+        context.record_definition(SymbolId::Enum(ident, *name), loc);
+        for (vloc, v, _) in &e.variants {
+            context.record_definition(SymbolId::Variant(ident, *name, *v), vloc);
+        }
+    }
+    for (loc, name, _) in &efunctions {
+        context.record_definition(SymbolId::Function(Some(ident), *name), loc);
+    }
+    for (loc, name, _) in &econstants {
+        context.record_definition(SymbolId::Constant(Some(ident), *name), loc);
+    }
+    attribute_constants(context, &attributes);
+    for (_, _, f) in &efunctions {
+        attribute_constants(context, &f.attributes);
+    }
+    for (_, _, c) in &econstants {
+        attribute_constants(context, &c.attributes);
+    }
+    deprecated_attribute(context, &attributes);
+    for (_, _, s) in &estructs {
+        deprecated_attribute(context, &s.attributes);
+    }
+    for (_, _, e) in &eenums {
+        deprecated_attribute(context, &e.attributes);
+    }
+    for (_, _, f) in &efunctions {
+        deprecated_attribute(context, &f.attributes);
+    }
+    for (_, _, c) in &econstants {
+        deprecated_attribute(context, &c.attributes);
+    }
     let mut spec_dependencies = BTreeSet::new();
     spec_blocks(&mut spec_dependencies, &specs);
+    if context.env.flags().is_verification() {
+        let targets = spec_targets(
+            estructs.iter().map(|(_, n, s)| (*n, s)),
+            efunctions.iter().map(|(_, n, f)| (*n, f)),
+        );
+        resolve_spec_blocks(context, &targets, &specs);
+    }
     let friends = efriends.filter_map(|mident, f| friend(context, mident, f));
+    let outer_unscoped = context.save_unscoped();
+    for (alias, (m, n)) in type_aliases {
+        context.bind_type(
+            alias.value,
+            ResolvedType::ModuleType(alias.loc, Box::new(m), n.value),
+        )
+    }
     let unscoped = context.save_unscoped();
     let structs = estructs.map(|name, s| {
         context.restore_unscoped(unscoped.clone());
         struct_def(context, name, s)
     });
+    let enums = eenums.map(|name, e| {
+        context.restore_unscoped(unscoped.clone());
+        enum_def(context, name, e)
+    });
     let functions = efunctions.map(|name, f| {
         context.restore_unscoped(unscoped.clone());
         function(context, &mut spec_dependencies, Some(ident), name, f)
     });
+    let syntax_methods = syntax_methods(context, package_name, ident, &functions);
+    context.restore_unscoped(unscoped.clone());
+    let use_funs = use_funs(context, euse_funs);
     let constants = econstants.map(|name, c| {
         context.restore_unscoped(unscoped.clone());
         constant(context, name, c)
     });
-    context.restore_unscoped(unscoped);
+    context.restore_unscoped(outer_unscoped);
     context.env.pop_warning_filter_scope();
     N::ModuleDefinition {
         loc,
         warning_filter,
         package_name,
         attributes,
+        doc,
         is_source_module,
         friends,
         structs,
+        enums,
         constants,
         functions,
+        syntax_methods,
+        use_funs,
         spec_dependencies,
     }
 }
@@ -465,6 +1003,7 @@ fn scripts(
     context: &mut Context,
     escripts: BTreeMap<Symbol, E::Script>,
 ) -> BTreeMap<Symbol, N::Script> {
+    context.current_module = None;
     escripts
         .into_iter()
         .map(|(n, s)| (n, script(context, s)))
@@ -487,9 +1026,18 @@ fn script(context: &mut Context, escript: E::Script) -> N::Script {
     spec_blocks(&mut spec_dependencies, &specs);
     let outer_unscoped = context.save_unscoped();
     for (loc, s, _) in &econstants {
-        context.bind_constant(*s, loc)
+        context.bind_constant(*s, loc);
+        context.record_definition(SymbolId::Constant(None, *s), loc);
     }
+    context.record_definition(
+        SymbolId::Function(None, function_name.value()),
+        function_name.loc(),
+    );
     let inner_unscoped = context.save_unscoped();
+    if context.env.flags().is_verification() {
+        let targets = spec_targets([], [(function_name.value(), &efunction)]);
+        resolve_spec_blocks(context, &targets, &specs);
+    }
     let constants = econstants.map(|name, c| {
         context.restore_unscoped(inner_unscoped.clone());
         constant(context, name, c)
@@ -526,21 +1074,38 @@ fn friend(context: &mut Context, mident: ModuleIdent, friend: E::Friend) -> Opti
         // NOTE: in alignment with the bytecode verifier, this constraint is a policy decision
         // rather than a technical requirement. The compiler, VM, and bytecode verifier DO NOT
         // rely on the assumption that friend modules must reside within the same account address.
-        let msg = "Cannot declare modules out of the current address as a friend";
-        context.env.add_diag(diag!(
-            Declarations::InvalidFriendDeclaration,
-            (friend.loc, "Invalid friend declaration"),
-            (mident.loc, msg),
-        ));
-        None
+        // The policy can be relaxed with the `cross_address_friends` flag.
+        match context.env.flags().cross_address_friends() {
+            CrossAddressFriends::Deny => {
+                let msg = "Cannot declare modules out of the current address as a friend";
+                context.env.add_diag(diag!(
+                    Declarations::InvalidFriendDeclaration,
+                    (friend.loc, "Invalid friend declaration"),
+                    (mident.loc, msg),
+                ));
+                return None;
+            }
+            CrossAddressFriends::Warn => {
+                let msg = format!(
+                    "Module '{}' is out of the current address. Modules of other addresses can \
+                     only be friends if the bytecode verifier of the chain allows it",
+                    mident
+                );
+                context
+                    .env
+                    .add_diag(diag!(Declarations::CrossAddressFriend, (mident.loc, msg)));
+            }
+            CrossAddressFriends::Allow => (),
+        }
     } else if &mident == current_mident {
         context.env.add_diag(diag!(
             Declarations::InvalidFriendDeclaration,
             (friend.loc, "Invalid friend declaration"),
             (mident.loc, "Cannot declare the module itself as a friend"),
         ));
-        None
-    } else if context.resolve_module(&mident) {
+        return None;
+    }
+    if context.resolve_module(&mident) {
         Some(friend)
     } else {
         assert!(context.env.has_errors());
@@ -563,9 +1128,11 @@ fn function(
         warning_filter,
         index,
         attributes,
+        doc,
         loc: _,
         visibility,
         entry,
+        macro_,
         signature,
         acquires,
         body,
@@ -578,9 +1145,14 @@ fn function(
     assert!(!context.translating_fun);
     context.env.add_warning_filter_scope(warning_filter.clone());
     spec_blocks(spec_dependencies, specs.values());
+    if context.env.flags().is_verification() {
+        context.function_specs = specs;
+    }
     context.local_scopes = vec![BTreeMap::new()];
     context.local_count = BTreeMap::new();
     context.translating_fun = true;
+    context.translating_macro = macro_;
+    context.current_function = Some(name);
     let signature = function_signature(context, signature);
     let acquires = function_acquires(context, acquires);
     let body = function_body(context, body);
@@ -601,8 +1173,10 @@ fn function(
         warning_filter,
         index,
         attributes,
+        doc,
         visibility,
         entry,
+        macro_,
         signature,
         acquires,
         body,
@@ -614,8 +1188,13 @@ fn function(
     context.local_count = BTreeMap::new();
     context.used_locals = BTreeSet::new();
     context.used_fun_tparams = BTreeSet::new();
+    assert!(context.loop_scopes.is_empty());
+    context.loop_count = 0;
+    context.function_specs = BTreeMap::new();
     context.env.pop_warning_filter_scope();
     context.translating_fun = false;
+    context.translating_macro = None;
+    context.current_function = None;
     f
 }
 
@@ -626,7 +1205,7 @@ fn function_signature(context: &mut Context, sig: E::FunctionSignature) -> N::Fu
     let parameters = sig
         .parameters
         .into_iter()
-        .map(|(param, param_ty)| {
+        .map(|(mut_, param, param_ty)| {
             if let Err((param, prev_loc)) = declared.add(param, ()) {
                 if !param.is_underscore() {
                     let msg = format!("Duplicate parameter with name '{}'", param);
@@ -640,7 +1219,7 @@ fn function_signature(context: &mut Context, sig: E::FunctionSignature) -> N::Fu
             let is_parameter = true;
             let nparam = context.declare_local(is_parameter, param.0);
             let nparam_ty = type_(context, param_ty);
-            (nparam, nparam_ty)
+            (mut_, nparam, nparam_ty)
         })
         .collect();
     let return_type = type_(context, sig.return_type);
@@ -688,6 +1267,10 @@ fn acquires_type(context: &mut Context, sp!(loc, en_): E::ModuleAccess) -> Optio
             let case = match context.resolve_unscoped_type(&n)? {
                 RT::BuiltinType => "builtin type",
                 RT::TParam(_, _) => "type parameter",
+                RT::ModuleType(_, m, sn) => {
+                    let ma = sp(loc, EN::ModuleAccess(*m, sp(n.loc, sn)));
+                    return acquires_type(context, ma);
+                }
             };
             let msg = format!(
                 "Invalid acquires item. Expected a struct name, but got a {}",
@@ -702,6 +1285,17 @@ fn acquires_type(context: &mut Context, sp!(loc, en_): E::ModuleAccess) -> Optio
             let (decl_loc, _, abilities, _) = context.resolve_module_type(loc, &m, &n)?;
             acquires_type_struct(context, loc, decl_loc, m, StructName(n), &abilities)
         }
+        EN::Variant(e, v) => {
+            let sp!(_, (m, e)) = *e;
+            let msg = format!(
+                "Invalid acquires item. Expected a struct name, but got the variant '{}::{}::{}'",
+                m, e, v
+            );
+            context
+                .env
+                .add_diag(diag!(NameResolution::NamePositionMismatch, (loc, msg)));
+            None
+        }
     }
 }
 
@@ -756,18 +1350,291 @@ fn acquires_type_struct(
 }
 
 //**************************************************************************************************
-// Structs
+// Deprecation
 //**************************************************************************************************
 
-fn struct_def(
+// Checks that a `deprecated` attribute is either `#[deprecated]` or has a single string note,
+// `#[deprecated(note = b"...")]`. The deprecation itself is recorded in the module info
+fn deprecated_attribute(context: &mut Context, attributes: &E::Attributes) {
+    let Some(sp!(attr_loc, attr_)) = attributes.get_(&DEPRECATED_ATTR) else {
+        return;
+    };
+    let valid = match attr_ {
+        E::Attribute_::Name(_) => true,
+        E::Attribute_::Assigned(_, _) => false,
+        E::Attribute_::Parameterized(_, inner) => {
+            inner.len() == 1 && deprecation(*attr_loc, attributes).is_some_and(|d| d.note.is_some())
+        }
+    };
+    if !valid {
+        let msg = format!(
+            "Expected '#[{0}]', or a note with '#[{0}({1} = b\"...\")]'",
+            known_attributes::DeprecationAttribute::DEPRECATED,
+            known_attributes::DeprecationAttribute::NOTE,
+        );
+        context
+            .env
+            .add_diag(diag!(Attributes::InvalidValue, (*attr_loc, msg)));
+    }
+}
+
+//**************************************************************************************************
+// Syntax Methods
+//**************************************************************************************************
+
+const SYNTAX_ATTR: E::AttributeName_ = E::AttributeName_::Known(
+    known_attributes::KnownAttribute::Syntax(known_attributes::SyntaxAttribute::Syntax),
+);
+
+// Collects the functions annotated with `#[syntax(...)]`, by the struct they implement the syntax
+// for. The struct is the one referenced by the first parameter, and must be declared in the same
+// module as the function
+fn syntax_methods(
     context: &mut Context,
-    _name: StructName,
-    sdef: E::StructDefinition,
-) -> N::StructDefinition {
+    package_name: Option<Symbol>,
+    mident: ModuleIdent,
+    functions: &UniqueMap<FunctionName, N::Function>,
+) -> N::SyntaxMethods {
+    let mut methods = N::SyntaxMethods::new();
+    // in declaration order, so duplicates are reported on the later declaration
+    let mut fdefs = functions.key_cloned_iter().collect::<Vec<_>>();
+    fdefs.sort_by_key(|(_, fdef)| fdef.index);
+    for (fname, fdef) in fdefs {
+        let Some(sp!(attr_loc, attr_)) = fdef.attributes.get_(&SYNTAX_ATTR) else {
+            continue;
+        };
+        if !context
+            .env
+            .check_feature(&FeatureGate::SyntaxMethods, package_name, *attr_loc)
+        {
+            continue;
+        }
+        let E::Attribute_::Parameterized(_, kinds) = attr_ else {
+            let msg = format!(
+                "Expected the kind of syntax implemented by the function, e.g. '#[{}({})]'",
+                known_attributes::SyntaxAttribute::SYNTAX,
+                known_attributes::SyntaxAttribute::INDEX,
+            );
+            context
+                .env
+                .add_diag(diag!(Attributes::InvalidUsage, (*attr_loc, msg)));
+            continue;
+        };
+        for (_, _, sp!(kind_loc, kind_)) in kinds {
+            match kind_ {
+                E::Attribute_::Name(n)
+                    if n.value.as_str() == known_attributes::SyntaxAttribute::INDEX =>
+                {
+                    index_syntax_method(context, mident, &mut methods, fname, fdef, *kind_loc)
+                }
+                _ => {
+                    let msg = format!(
+                        "Unknown syntax kind '{}'. Expected '{}'",
+                        kind_.attribute_name(),
+                        known_attributes::SyntaxAttribute::INDEX,
+                    );
+                    context
+                        .env
+                        .add_diag(diag!(Attributes::InvalidName, (*kind_loc, msg)));
+                }
+            }
+        }
+    }
+    methods
+}
+
+// An index function takes a reference to the struct, along with the index, and returns a
+// reference of the same mutability into it
+fn index_syntax_method(
+    context: &mut Context,
+    mident: ModuleIdent,
+    methods: &mut N::SyntaxMethods,
+    fname: FunctionName,
+    fdef: &N::Function,
+    attr_loc: Loc,
+) {
+    let N::FunctionSignature {
+        parameters,
+        return_type,
+        ..
+    } = &fdef.signature;
+    let subject = parameters.first().and_then(|(_, _, ty)| match &ty.value {
+        N::Type_::Ref(mut_, inner) => match &inner.value {
+            N::Type_::Apply(_, sp!(_, N::TypeName_::ModuleType(m, sname)), _) if m == &mident => {
+                Some((*mut_, *sname))
+            }
+            _ => None,
+        },
+        _ => None,
+    });
+    let Some((is_mut, sname)) = subject else {
+        let msg = format!(
+            "Invalid index function '{}'. Its first parameter must be a reference to a struct \
+             declared in module '{}'",
+            fname, mident
+        );
+        let param_loc = parameters.first().map_or(fname.loc(), |(_, _, ty)| ty.loc);
+        context.env.add_diag(diag!(
+            Declarations::InvalidSyntaxMethod,
+            (param_loc, msg),
+            (attr_loc, "Declared as an index function here"),
+        ));
+        return;
+    };
+    let ref_kind = if is_mut { "&mut" } else { "&" };
+    if !matches!(&return_type.value, N::Type_::Ref(ret_mut, _) if *ret_mut == is_mut) {
+        let msg = format!(
+            "Invalid index function '{}'. It must return a '{}' reference, as its first \
+             parameter is a '{}' reference",
+            fname, ref_kind, ref_kind
+        );
+        context.env.add_diag(diag!(
+            Declarations::InvalidSyntaxMethod,
+            (return_type.loc, msg),
+            (attr_loc, "Declared as an index function here"),
+        ));
+        return;
+    }
+    let index_methods = &mut methods.entry(sname).or_default().index;
+    let method = if is_mut {
+        &mut index_methods.index_mut
+    } else {
+        &mut index_methods.index
+    };
+    match method {
+        Some(prev) => {
+            let msg = format!(
+                "Duplicate '{}' index function for '{}::{}'",
+                ref_kind, mident, sname
+            );
+            context.env.add_diag(diag!(
+                Declarations::InvalidSyntaxMethod,
+                (fname.loc(), msg),
+                (prev.loc(), "Previously declared here"),
+            ));
+        }
+        None => *method = Some(fname),
+    }
+}
+
+//**************************************************************************************************
+// Use Funs
+//**************************************************************************************************
+
+// Resolves the method aliases declared with `use fun`, keeping those whose function takes the
+// struct they are declared for, or a reference to it, as first parameter
+fn use_funs(context: &mut Context, euse_funs: Vec<E::UseFun>) -> N::UseFuns {
+    let mut use_funs = N::UseFuns::new();
+    for euse_fun in euse_funs {
+        let E::UseFun {
+            attributes: _,
+            loc,
+            function,
+            ty,
+            method,
+        } = euse_fun;
+        let function = use_fun_function(context, function);
+        let ty = context.resolve_struct_name(loc, "'use fun'", ty, None);
+        let (Some((m, f, decl_loc)), Some((tm, sname, _))) = (function, ty) else {
+            assert!(context.env.has_errors());
+            continue;
+        };
+        let receiver = context
+            .module_info
+            .module(&m)
+            .and_then(|minfo| minfo.receiver(&f.value()));
+        if receiver != Some(&(tm.value, sname.value())) {
+            let msg = format!(
+                "Invalid 'use fun' for '{}::{}.{}'. The first parameter of '{}::{}' must be of \
+                 type '{}::{}', or a reference to it",
+                tm, sname, method, m, f, tm, sname
+            );
+            context.env.add_diag(diag!(
+                Declarations::InvalidUseFun,
+                (loc, msg),
+                (decl_loc, format!("'{}::{}' is declared here", m, f)),
+            ));
+            continue;
+        }
+        let methods = use_funs.entry((tm.value, sname.value())).or_default();
+        if let Some(prev) = methods.get(&method.value) {
+            let msg = format!("Duplicate 'use fun' for '{}::{}.{}'", tm, sname, method);
+            context.env.add_diag(diag!(
+                Declarations::DuplicateItem,
+                (loc, msg),
+                (prev.loc, "Previously declared here"),
+            ));
+            continue;
+        }
+        let use_fun = N::UseFun {
+            loc,
+            module: m,
+            function: f,
+        };
+        methods.insert(method.value, use_fun);
+    }
+    use_funs
+}
+
+// The function aliased by a `use fun`, along with the location of its declaration
+fn use_fun_function(
+    context: &mut Context,
+    sp!(loc, ma_): E::ModuleAccess,
+) -> Option<(ModuleIdent, FunctionName, Loc)> {
+    use E::ModuleAccess_ as EA;
+
+    match ma_ {
+        EA::Name(n) => {
+            let msg = format!("Unbound function '{}' in current scope", n);
+            context
+                .env
+                .add_diag(diag!(NameResolution::UnboundUnscopedName, (loc, msg)));
+            None
+        }
+        EA::ModuleAccess(m, n) => {
+            let f = context.resolve_module_function(loc, &m, &n)?;
+            if context.is_macro(&m, &n) {
+                let msg = format!(
+                    "Invalid 'use fun'. '{}::{}' is a macro, which cannot be called with the \
+                     method syntax",
+                    m, n
+                );
+                context
+                    .env
+                    .add_diag(diag!(Declarations::InvalidUseFun, (loc, msg)));
+                return None;
+            }
+            let decl_loc = context.module_info.function(&m, &n.value)?;
+            Some((m, f, decl_loc))
+        }
+        EA::Variant(e, v) => {
+            let sp!(_, (m, e)) = *e;
+            let msg = format!(
+                "Unexpected variant '{}::{}::{}'. Expected a function",
+                m, e, v
+            );
+            context
+                .env
+                .add_diag(diag!(NameResolution::NamePositionMismatch, (loc, msg)));
+            None
+        }
+    }
+}
+
+//**************************************************************************************************
+// Structs
+//**************************************************************************************************
+
+fn struct_def(
+    context: &mut Context,
+    _name: StructName,
+    sdef: E::StructDefinition,
+) -> N::StructDefinition {
     let E::StructDefinition {
         warning_filter,
         index,
         attributes,
+        doc,
         loc: _loc,
         abilities,
         type_parameters,
@@ -781,6 +1648,7 @@ fn struct_def(
         warning_filter,
         index,
         attributes,
+        doc,
         abilities,
         type_parameters,
         fields,
@@ -793,6 +1661,100 @@ fn struct_fields(context: &mut Context, efields: E::StructFields) -> N::StructFi
         E::StructFields::Defined(em) => {
             N::StructFields::Defined(em.map(|_f, (idx, t)| (idx, type_(context, t))))
         }
+        // positional fields are named by their index
+        E::StructFields::Positional(tys) => {
+            let fields = tys.into_iter().enumerate().map(|(idx, t)| {
+                let t = type_(context, t);
+                (Field::positional(t.loc, idx), (idx, t))
+            });
+            N::StructFields::Defined(
+                UniqueMap::maybe_from_iter(fields).expect("ICE positional fields are unique"),
+            )
+        }
+    }
+}
+
+// The fields of a struct or variant with positional fields, given in order as in
+// `S(e1, ..., en)`, named by their index as in its declaration. Returns `None` after reporting an
+// error if the number of fields is not the declared one
+fn positional_fields<T, F: FnOnce() -> String>(
+    context: &mut Context,
+    loc: Loc,
+    verb: &str,
+    name_f: F,
+    num_fields: usize,
+    args: Vec<Spanned<T>>,
+) -> Option<UniqueMap<Field, (usize, Spanned<T>)>> {
+    if args.len() != num_fields {
+        let msg = format!(
+            "Invalid {} of '{}'. Expected {} positional field(s) but got {}",
+            verb,
+            name_f(),
+            num_fields,
+            args.len()
+        );
+        context
+            .env
+            .add_diag(diag!(NameResolution::PositionalFieldsArity, (loc, msg)));
+        return None;
+    }
+    let fields = args
+        .into_iter()
+        .enumerate()
+        .map(|(idx, arg)| (Field::positional(arg.loc, idx), (idx, arg)));
+    Some(UniqueMap::maybe_from_iter(fields).expect("ICE positional fields are unique"))
+}
+
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+fn enum_def(context: &mut Context, _name: EnumName, edef: E::EnumDefinition) -> N::EnumDefinition {
+    let E::EnumDefinition {
+        warning_filter,
+        index,
+        attributes,
+        doc,
+        loc,
+        abilities,
+        type_parameters,
+        variants,
+    } = edef;
+    context.env.add_warning_filter_scope(warning_filter.clone());
+    let type_parameters = struct_type_parameters(context, type_parameters);
+    let variants = variants.map(|_, vdef| N::VariantDefinition {
+        index: vdef.index,
+        loc: vdef.loc,
+        fields: variant_fields(context, vdef.fields),
+    });
+    context.env.pop_warning_filter_scope();
+    N::EnumDefinition {
+        warning_filter,
+        index,
+        attributes,
+        doc,
+        loc,
+        abilities,
+        type_parameters,
+        variants,
+    }
+}
+
+fn variant_fields(context: &mut Context, efields: E::VariantFields) -> N::VariantFields {
+    match efields {
+        E::VariantFields::Empty => N::VariantFields::Empty,
+        E::VariantFields::Defined(em) => {
+            N::VariantFields::Defined(em.map(|_f, (idx, t)| (idx, type_(context, t))))
+        }
+        E::VariantFields::Positional(tys) => {
+            let fields = tys.into_iter().enumerate().map(|(idx, t)| {
+                let t = type_(context, t);
+                (Field::positional(t.loc, idx), (idx, t))
+            });
+            N::VariantFields::Defined(
+                UniqueMap::maybe_from_iter(fields).expect("ICE positional fields are unique"),
+            )
+        }
     }
 }
 
@@ -805,6 +1767,7 @@ fn constant(context: &mut Context, _name: ConstantName, econstant: E::Constant)
         warning_filter,
         index,
         attributes,
+        doc,
         loc,
         signature: esignature,
         value: evalue,
@@ -814,16 +1777,22 @@ fn constant(context: &mut Context, _name: ConstantName, econstant: E::Constant)
     assert!(context.used_locals.is_empty());
     context.env.add_warning_filter_scope(warning_filter.clone());
     context.local_scopes = vec![BTreeMap::new()];
+    // The signature and value are resolved as for any other item, including struct types, packs,
+    // and vectors. Which types and expressions are valid in constants is checked in typing, and
+    // whether the value can be folded is checked in cfgir
     let signature = type_(context, esignature);
     let value = exp_(context, evalue);
     context.local_scopes = vec![];
     context.local_count = BTreeMap::new();
     context.used_locals = BTreeSet::new();
+    assert!(context.loop_scopes.is_empty());
+    context.loop_count = 0;
     context.env.pop_warning_filter_scope();
     N::Constant {
         warning_filter,
         index,
         attributes,
+        doc,
         loc,
         signature,
         value,
@@ -875,6 +1844,7 @@ fn type_parameter(
     };
     let loc = name.loc;
     context.bind_type(name.value, ResolvedType::TParam(loc, tp.clone()));
+    context.record_definition(SymbolId::TypeParameter(id), loc);
     if let Err((name, old_loc)) = unique_tparams.add(name, ()) {
         let msg = format!("Duplicate type parameter declared with name '{}'", name);
         context.env.add_diag(diag!(
@@ -914,7 +1884,7 @@ fn type_(context: &mut Context, sp!(loc, ety_): E::Type) -> N::Type {
                 let name_f = || format!("{}", &bn_);
                 let arity = bn_.tparam_constraints(loc).len();
                 let tys = types(context, tys);
-                let tys = check_type_argument_arity(context, loc, name_f, tys, arity);
+                let tys = check_type_argument_arity(context, loc, name_f, tys, arity, None);
                 NT::builtin_(sp(loc, bn_), tys)
             }
             Some(RT::TParam(_, tp)) => {
@@ -928,36 +1898,94 @@ fn type_(context: &mut Context, sp!(loc, ety_): E::Type) -> N::Type {
                     if context.translating_fun {
                         context.used_fun_tparams.insert(tp.id);
                     }
+                    context.record_use(n.loc, SymbolId::TypeParameter(tp.id));
                     NT::Param(tp)
                 }
             }
+            Some(RT::ModuleType(_, m, sn)) if context.module_info.enum_(&m, &sn).is_some() => {
+                enum_type(context, loc, n.loc, *m, sp(n.loc, sn), tys)
+            }
+            Some(RT::ModuleType(_, m, sn)) => {
+                let n = sp(n.loc, sn);
+                match context.resolve_module_type(n.loc, &m, &n) {
+                    None => {
+                        assert!(context.env.has_errors());
+                        NT::UnresolvedError
+                    }
+                    Some((decl_loc, _, _, arity)) => {
+                        let tn = sp(n.loc, NN::ModuleType(*m, StructName(n)));
+                        let tys = types(context, tys);
+                        let name_f = || format!("{}", tn);
+                        let decl_loc = Some(decl_loc);
+                        let tys =
+                            check_type_argument_arity(context, loc, name_f, tys, arity, decl_loc);
+                        NT::Apply(None, tn, tys)
+                    }
+                }
+            }
         },
+        ET::Apply(sp!(nloc, EN::ModuleAccess(m, n)), tys)
+            if context.module_info.enum_(&m, &n.value).is_some() =>
+        {
+            enum_type(context, loc, nloc, m, n, tys)
+        }
         ET::Apply(sp!(nloc, EN::ModuleAccess(m, n)), tys) => {
             match context.resolve_module_type(nloc, &m, &n) {
                 None => {
                     assert!(context.env.has_errors());
                     NT::UnresolvedError
                 }
-                Some((_, _, _, arity)) => {
+                Some((decl_loc, _, _, arity)) => {
                     let tn = sp(nloc, NN::ModuleType(m, StructName(n)));
                     let tys = types(context, tys);
                     let name_f = || format!("{}", tn);
-                    let tys = check_type_argument_arity(context, loc, name_f, tys, arity);
+                    let decl_loc = Some(decl_loc);
+                    let tys = check_type_argument_arity(context, loc, name_f, tys, arity, decl_loc);
                     NT::Apply(None, tn, tys)
                 }
             }
         }
+        ET::Apply(sp!(nloc, EN::Variant(e, v)), _) => {
+            let sp!(_, (m, e)) = *e;
+            let msg = format!("Unexpected variant '{}::{}::{}'. Expected a type", m, e, v);
+            context
+                .env
+                .add_diag(diag!(NameResolution::NamePositionMismatch, (nloc, msg)));
+            NT::UnresolvedError
+        }
         ET::Fun(_, _) => panic!("ICE only allowed in spec context"),
     };
     sp(loc, ty_)
 }
 
+// Enum types are resolved and their type arguments checked, but they do not have a type name
+// yet: typing reports the declaration of every enum as not supported, so the type is an error
+fn enum_type(
+    context: &mut Context,
+    loc: Loc,
+    nloc: Loc,
+    m: ModuleIdent,
+    n: Name,
+    tys: Vec<E::Type>,
+) -> N::Type_ {
+    let tys = types(context, tys);
+    if let Some(einfo) = context.resolve_module_enum(nloc, &m, &n) {
+        let name_f = || format!("{}::{}", &m, &n);
+        let decl_loc = Some(einfo.decl_loc);
+        check_type_argument_arity(context, loc, name_f, tys, einfo.arity, decl_loc);
+    }
+    N::Type_::UnresolvedError
+}
+
+/// Checks that `ty_args` has `arity` type arguments, padding or truncating it if not. If the
+/// instantiated type is declared in a module, `decl_loc` is the location of its declaration
 fn check_type_argument_arity<F: FnOnce() -> String>(
     context: &mut Context,
     loc: Loc,
     name_f: F,
     mut ty_args: Vec<N::Type>,
     arity: usize,
+    decl_loc: Option<Loc>,
 ) -> Vec<N::Type> {
     let args_len = ty_args.len();
     if args_len != arity {
@@ -966,13 +1994,20 @@ fn check_type_argument_arity<F: FnOnce() -> String>(
         } else {
             NameResolution::TooFewTypeArguments
         };
+        let name = name_f();
         let msg = format!(
             "Invalid instantiation of '{}'. Expected {} type argument(s) but got {}",
-            name_f(),
-            arity,
-            args_len
+            name, arity, args_len
         );
-        context.env.add_diag(diag!(diag_code, (loc, msg)));
+        let mut diag = diag!(diag_code, (loc, msg));
+        if let Some(decl_loc) = decl_loc {
+            let decl_msg = format!(
+                "'{}' is declared with {} type parameter(s) here",
+                name, arity
+            );
+            diag.add_secondary_label((decl_loc, decl_msg));
+        }
+        context.env.add_diag(diag);
     }
 
     while ty_args.len() > arity {
@@ -1048,6 +2083,7 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
     let ne_ = match e_ {
         EE::Unit { trailing } => NE::Unit { trailing },
         EE::Value(val) => NE::Value(val),
+        EE::StringLiteral(bytes) => string_literal(context, eloc, bytes),
         EE::Move(v) => match context.resolve_local(eloc, "move", v.0) {
             None => {
                 debug_assert!(context.env.has_errors());
@@ -1075,13 +2111,25 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
                 }
             }
         }
+        // `E::V` constructs a variant without fields
+        EE::Name(sp!(_, E::ModuleAccess_::Variant(e, v)), None) => {
+            pack_empty_variant(context, eloc, *e, v)
+        }
         EE::Name(ma, None) => access_constant(context, ma),
 
         EE::IfElse(eb, et, ef) => {
             NE::IfElse(exp(context, *eb), exp(context, *et), exp(context, *ef))
         }
-        EE::While(eb, el) => NE::While(exp(context, *eb), exp(context, *el)),
-        EE::Loop(el) => NE::Loop(exp(context, *el)),
+        EE::While(label_opt, eb, el) => {
+            let nb = exp(context, *eb);
+            let (name, nl) = loop_body(context, eloc, label_opt, *el);
+            NE::While(name, nb, nl)
+        }
+        EE::Loop(label_opt, el) => {
+            let (name, nl) = loop_body(context, eloc, label_opt, *el);
+            NE::Loop(name, nl)
+        }
+        EE::Match(esubject, earms) => match_(context, *esubject, earms),
         EE::Block(seq) => NE::Block(sequence(context, seq)),
 
         EE::Assign(a, e) => {
@@ -1112,29 +2160,31 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
             NE::Mutate(nel, ner)
         }
 
-        EE::Return(es) => NE::Return(exp(context, *es)),
+        EE::Return(es) => {
+            check_macro_body(context, eloc, "return");
+            NE::Return(exp(context, *es))
+        }
         EE::Abort(es) => NE::Abort(exp(context, *es)),
-        EE::Break => NE::Break,
-        EE::Continue => NE::Continue,
+        EE::Break(label_opt, eopt) => {
+            let ne_opt = eopt.map(|e| exp(context, *e));
+            match loop_control(context, eloc, "break", label_opt) {
+                None => NE::UnresolvedError,
+                Some(name) => NE::Break(name, ne_opt),
+            }
+        }
+        EE::Continue(label_opt) => match loop_control(context, eloc, "continue", label_opt) {
+            None => NE::UnresolvedError,
+            Some(name) => NE::Continue(name),
+        },
 
         EE::Dereference(e) => NE::Dereference(exp(context, *e)),
         EE::UnaryExp(uop, e) => NE::UnaryExp(uop, exp(context, *e)),
         EE::BinopExp(e1, bop, e2) => NE::BinopExp(exp(context, *e1), bop, exp(context, *e2)),
 
-        EE::Pack(tn, etys_opt, efields) => {
-            match context.resolve_struct_name(eloc, "construction", tn, etys_opt) {
-                None => {
-                    assert!(context.env.has_errors());
-                    NE::UnresolvedError
-                }
-                Some((m, sn, tys_opt)) => NE::Pack(
-                    m,
-                    sn,
-                    tys_opt,
-                    efields.map(|_, (idx, e)| (idx, exp_(context, e))),
-                ),
-            }
+        EE::Pack(sp!(_, E::ModuleAccess_::Variant(e, v)), etys_opt, efields) => {
+            pack_variant(context, eloc, *e, v, etys_opt, efields)
         }
+        EE::Pack(tn, etys_opt, efields) => pack(context, eloc, tn, etys_opt, efields),
         EE::ExpList(es) => {
             assert!(es.len() > 1);
             NE::ExpList(exps(context, es))
@@ -1161,59 +2211,24 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
             }
             Some(d) => NE::DerefBorrow(d),
         },
+        EE::MethodCall(edot, n, tys_opt, rhs) => method_call(context, *edot, n, tys_opt, rhs),
 
         EE::Cast(e, t) => NE::Cast(exp(context, *e), type_(context, t)),
         EE::Annotate(e, t) => NE::Annotate(exp(context, *e), type_(context, t)),
 
-        EE::Call(sp!(mloc, ma_), true, tys_opt, rhs) => {
-            use E::ModuleAccess_ as EA;
-            use N::BuiltinFunction_ as BF;
-            assert!(tys_opt.is_none(), "ICE macros do not have type arguments");
-            let nes = call_args(context, rhs);
-            match ma_ {
-                EA::Name(n) if n.value.as_str() == BF::ASSERT_MACRO => {
-                    NE::Builtin(sp(mloc, BF::Assert(true)), nes)
-                }
-                ma_ => {
-                    context.env.add_diag(diag!(
-                        NameResolution::UnboundMacro,
-                        (mloc, format!("Unbound macro '{}'", ma_)),
-                    ));
-                    NE::UnresolvedError
-                }
-            }
+        EE::Call(ma, true, tys_opt, rhs) => macro_call(context, eloc, ma, tys_opt, rhs),
+        // `S(e1, ..., en)` constructs a struct with positional fields
+        EE::Call(sp!(mloc, E::ModuleAccess_::ModuleAccess(m, n)), false, tys_opt, rhs)
+            if context.module_info.struct_(&m, &n.value).is_some() =>
+        {
+            let tn = sp(mloc, E::ModuleAccess_::ModuleAccess(m, n));
+            pack_positional(context, eloc, tn, tys_opt, rhs)
         }
-        EE::Call(sp!(mloc, ma_), false, tys_opt, rhs) => {
-            use E::ModuleAccess_ as EA;
-            let ty_args = tys_opt.map(|tys| types(context, tys));
-            let nes = call_args(context, rhs);
-            match ma_ {
-                EA::Name(n) if N::BuiltinFunction_::all_names().contains(&n.value) => {
-                    match resolve_builtin_function(context, eloc, &n, ty_args) {
-                        None => {
-                            assert!(context.env.has_errors());
-                            NE::UnresolvedError
-                        }
-                        Some(f) => NE::Builtin(sp(mloc, f), nes),
-                    }
-                }
-
-                EA::Name(n) => {
-                    context.env.add_diag(diag!(
-                        NameResolution::UnboundUnscopedName,
-                        (n.loc, format!("Unbound function '{}' in current scope", n)),
-                    ));
-                    NE::UnresolvedError
-                }
-                EA::ModuleAccess(m, n) => match context.resolve_module_function(mloc, &m, &n) {
-                    None => {
-                        assert!(context.env.has_errors());
-                        NE::UnresolvedError
-                    }
-                    Some(_) => NE::ModuleCall(m, FunctionName(n), ty_args, nes),
-                },
-            }
+        // `E::V(e1, ..., en)` constructs a variant with positional fields
+        EE::Call(sp!(_, E::ModuleAccess_::Variant(e, v)), false, tys_opt, rhs) => {
+            pack_positional_variant(context, eloc, *e, v, tys_opt, rhs)
         }
+        EE::Call(ma, false, tys_opt, rhs) => function_call(context, eloc, ma, tys_opt, rhs),
         EE::Vector(vec_loc, tys_opt, rhs) => {
             let ty_args = tys_opt.map(|tys| types(context, tys));
             let nes = call_args(context, rhs);
@@ -1233,6 +2248,10 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
         }
 
         EE::Spec(u, unbound_names) => {
+            check_macro_body(context, eloc, "spec");
+            if let Some(spec) = context.function_specs.remove(&u) {
+                resolve_spec_block(context, &BTreeMap::new(), &spec)
+            }
             // Vars currently aren't shadowable by types/functions
             let used_locals = unbound_names
                 .into_iter()
@@ -1261,48 +2280,570 @@ fn exp_(context: &mut Context, e: E::Exp) -> N::Exp {
     sp(eloc, ne_)
 }
 
-fn access_constant(context: &mut Context, ma: E::ModuleAccess) -> N::Exp_ {
-    match context.resolve_constant(ma) {
-        None => {
-            assert!(context.env.has_errors());
-            N::Exp_::UnresolvedError
+// The helpers below translate the less common expressions, keeping them out of the frame of the
+// recursive `exp_`, which is deep for long chains of operators
+
+// Translates the body of a loop, inside of the scope of the loop's label
+fn loop_body(
+    context: &mut Context,
+    loc: Loc,
+    label_opt: Option<P::BlockLabel>,
+    ebody: E::Exp,
+) -> (N::BlockLabel, Box<N::Exp>) {
+    let name = context.enter_loop(loc, label_opt);
+    let nbody = exp(context, ebody);
+    context.exit_loop();
+    (name, nbody)
+}
+
+// Resolves the loop exited or restarted by a 'break' or a 'continue' (the `construct`)
+fn loop_control(
+    context: &mut Context,
+    loc: Loc,
+    construct: &str,
+    label_opt: Option<P::BlockLabel>,
+) -> Option<N::BlockLabel> {
+    if label_opt.is_none() && context.loop_scopes.is_empty() {
+        if context.translating_macro.is_some() {
+            check_macro_body(context, loc, construct);
+        } else {
+            let msg = format!(
+                "Invalid usage of '{}'. '{}' can only be used inside a loop body",
+                construct, construct
+            );
+            context
+                .env
+                .add_diag(diag!(TypeSafety::InvalidLoopControl, (loc, msg)));
         }
-        Some((m, c)) => N::Exp_::Constant(m, c),
+        return None;
     }
+    context.resolve_loop_label(construct, label_opt)
 }
 
-fn dotted(context: &mut Context, edot: E::ExpDotted) -> Option<N::ExpDotted> {
-    let sp!(loc, edot_) = edot;
-    let nedot_ = match edot_ {
-        E::ExpDotted_::Exp(e) => {
-            let ne = exp(context, e);
-            match &ne.value {
-                N::Exp_::UnresolvedError => return None,
-                _ => N::ExpDotted_::Exp(ne),
+fn function_call(
+    context: &mut Context,
+    loc: Loc,
+    sp!(mloc, ma_): E::ModuleAccess,
+    tys_opt: Option<Vec<E::Type>>,
+    rhs: Spanned<Vec<E::Exp>>,
+) -> N::Exp_ {
+    use E::ModuleAccess_ as EA;
+    let ty_args = tys_opt.map(|tys| types(context, tys));
+    let nes = call_args(context, rhs);
+    match ma_ {
+        EA::Name(n) if N::BuiltinFunction_::all_names().contains(&n.value) => {
+            match resolve_builtin_function(context, loc, &n, ty_args) {
+                None => {
+                    assert!(context.env.has_errors());
+                    N::Exp_::UnresolvedError
+                }
+                Some(f) => N::Exp_::Builtin(sp(mloc, f), nes),
             }
         }
-        E::ExpDotted_::Dot(d, f) => N::ExpDotted_::Dot(Box::new(dotted(context, *d)?), Field(f)),
-    };
-    Some(sp(loc, nedot_))
-}
 
-#[derive(Clone, Copy)]
-enum LValueCase {
-    Bind,
-    Assign,
+        EA::Name(n) => {
+            context.env.add_diag(diag!(
+                NameResolution::UnboundUnscopedName,
+                (n.loc, format!("Unbound function '{}' in current scope", n)),
+            ));
+            N::Exp_::UnresolvedError
+        }
+        EA::ModuleAccess(m, n) => match context.resolve_module_function(mloc, &m, &n) {
+            None => {
+                assert!(context.env.has_errors());
+                N::Exp_::UnresolvedError
+            }
+            Some(f) if context.is_macro(&m, &n) => {
+                let msg = format!(
+                    "Invalid call to macro '{}::{}'. Macros must be called with '!', e.g. \
+                     '{}!(...)'",
+                    m, f, f
+                );
+                context
+                    .env
+                    .add_diag(diag!(NameResolution::InvalidMacroCall, (mloc, msg)));
+                N::Exp_::UnresolvedError
+            }
+            Some(f) => N::Exp_::ModuleCall(m, f, ty_args, nes),
+        },
+        EA::Variant(_, _) => panic!("ICE variant constructions are handled in `exp_`"),
+    }
 }
 
-fn lvalue(
+fn macro_call(
     context: &mut Context,
-    seen_locals: &mut UniqueMap<Name, ()>,
-    case: LValueCase,
-    sp!(loc, l_): E::LValue,
-) -> Option<N::LValue> {
+    loc: Loc,
+    sp!(mloc, ma_): E::ModuleAccess,
+    tys_opt: Option<Vec<E::Type>>,
+    rhs: Spanned<Vec<E::Exp>>,
+) -> N::Exp_ {
+    use E::ModuleAccess_ as EA;
+    use N::BuiltinFunction_ as BF;
+    let ty_args = tys_opt.map(|tys| types(context, tys));
+    let nes = call_args(context, rhs);
+    match ma_ {
+        EA::Name(n) if n.value.as_str() == BF::ASSERT_MACRO => {
+            check_builtin_ty_args(context, loc, &n, 0, ty_args);
+            N::Exp_::Builtin(sp(mloc, BF::Assert(true)), nes)
+        }
+        EA::ModuleAccess(m, n) => match context.resolve_module_function(mloc, &m, &n) {
+            None => {
+                assert!(context.env.has_errors());
+                N::Exp_::UnresolvedError
+            }
+            Some(f) if context.is_macro(&m, &n) => N::Exp_::MacroCall(m, f, ty_args, nes),
+            Some(f) => {
+                let msg = format!(
+                    "Invalid macro call. '{}::{}' is not a macro and must be called without '!'",
+                    m, f
+                );
+                context
+                    .env
+                    .add_diag(diag!(NameResolution::InvalidMacroCall, (mloc, msg)));
+                N::Exp_::UnresolvedError
+            }
+        },
+        ma_ => {
+            context.env.add_diag(diag!(
+                NameResolution::UnboundMacro,
+                (mloc, format!("Unbound macro '{}'", ma_)),
+            ));
+            N::Exp_::UnresolvedError
+        }
+    }
+}
+
+fn method_call(
+    context: &mut Context,
+    edot: E::ExpDotted,
+    n: Name,
+    tys_opt: Option<Vec<E::Type>>,
+    rhs: Spanned<Vec<E::Exp>>,
+) -> N::Exp_ {
+    // methods are resolved during typing, from the type of the receiver. As a function can only be
+    // called from its own module unless it is public, conservatively assume that the method is a
+    // function of the current module
+    if let Some(m) = context.current_module {
+        context.used_functions.insert((m.value, n.value));
+    }
+    let d_opt = dotted(context, edot);
+    let ty_args = tys_opt.map(|tys| types(context, tys));
+    let nes = call_args(context, rhs);
+    match d_opt {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some(d) => N::Exp_::MethodCall(d, n, ty_args, nes),
+    }
+}
+
+fn match_(
+    context: &mut Context,
+    esubject: E::Exp,
+    sp!(aloc, earms): Spanned<Vec<E::MatchArm>>,
+) -> N::Exp_ {
+    let nsubject = exp(context, esubject);
+    let narms = earms
+        .into_iter()
+        .map(|earm| match_arm(context, earm))
+        .collect();
+    N::Exp_::Match(nsubject, sp(aloc, narms))
+}
+
+// `S { f1: e1, ..., fn: en }` constructs a struct with named fields
+fn pack(
+    context: &mut Context,
+    loc: Loc,
+    tn: E::ModuleAccess,
+    etys_opt: Option<Vec<E::Type>>,
+    efields: E::Fields<E::Exp>,
+) -> N::Exp_ {
+    let nfields = efields.map(|_, (idx, e)| (idx, exp_(context, e)));
+    let verb = "construction";
+    match context.resolve_struct_name(loc, verb, tn, etys_opt) {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some((m, sn, tys_opt)) => {
+            let positional = false;
+            if context
+                .check_positional_fields(loc, verb, &m, &sn, positional)
+                .is_some()
+            {
+                N::Exp_::Pack(m, sn, tys_opt, nfields)
+            } else {
+                N::Exp_::UnresolvedError
+            }
+        }
+    }
+}
+
+// `E::V` constructs a variant without fields
+fn pack_empty_variant(
+    context: &mut Context,
+    loc: Loc,
+    e: Spanned<(ModuleIdent, Name)>,
+    v: Name,
+) -> N::Exp_ {
+    let verb = "construction";
+    match context.resolve_variant_name(loc, verb, e, v, None) {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some((m, en, vn, tys_opt, VariantFieldsInfo::Empty)) => {
+            N::Exp_::PackVariant(m, en, vn, tys_opt, UniqueMap::new())
+        }
+        Some((m, en, vn, _, _)) => {
+            let msg = format!(
+                "Invalid {}. Variant '{}::{}::{}' has fields, which must be given",
+                verb, m, en, vn
+            );
+            context
+                .env
+                .add_diag(diag!(NameResolution::PositionalCallMismatch, (loc, msg)));
+            N::Exp_::UnresolvedError
+        }
+    }
+}
+
+// `E::V { f1: e1, ..., fn: en }` constructs a variant with named fields
+fn pack_variant(
+    context: &mut Context,
+    loc: Loc,
+    e: Spanned<(ModuleIdent, Name)>,
+    v: Name,
+    etys_opt: Option<Vec<E::Type>>,
+    efields: E::Fields<E::Exp>,
+) -> N::Exp_ {
+    let nfields = efields.map(|_, (idx, e)| (idx, exp_(context, e)));
+    let verb = "construction";
+    let resolved = context
+        .resolve_variant_name(loc, verb, e, v, etys_opt)
+        .and_then(|(m, en, vn, tys_opt, fields)| {
+            let positional = false;
+            context.check_variant_fields(loc, verb, (&m, &en, &vn), fields, positional)?;
+            Some((m, en, vn, tys_opt))
+        });
+    match resolved {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some((m, en, vn, tys_opt)) => N::Exp_::PackVariant(m, en, vn, tys_opt, nfields),
+    }
+}
+
+// `S(e1, ..., en)` constructs a struct with positional fields
+fn pack_positional(
+    context: &mut Context,
+    loc: Loc,
+    tn: E::ModuleAccess,
+    tys_opt: Option<Vec<E::Type>>,
+    rhs: Spanned<Vec<E::Exp>>,
+) -> N::Exp_ {
+    let nes = exps(context, rhs.value);
+    let verb = "construction";
+    let positional = true;
+    let nfields_opt = context
+        .resolve_struct_name(loc, verb, tn, tys_opt)
+        .and_then(|(m, sn, tys_opt)| {
+            let num_fields = context
+                .check_positional_fields(loc, verb, &m, &sn, positional)?
+                .unwrap();
+            let name_f = || format!("{}::{}", m, sn);
+            let nfields = positional_fields(context, loc, verb, name_f, num_fields, nes)?;
+            Some((m, sn, tys_opt, nfields))
+        });
+    match nfields_opt {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some((m, sn, tys_opt, nfields)) => N::Exp_::Pack(m, sn, tys_opt, nfields),
+    }
+}
+
+// `E::V(e1, ..., en)` constructs a variant with positional fields
+fn pack_positional_variant(
+    context: &mut Context,
+    loc: Loc,
+    e: Spanned<(ModuleIdent, Name)>,
+    v: Name,
+    tys_opt: Option<Vec<E::Type>>,
+    rhs: Spanned<Vec<E::Exp>>,
+) -> N::Exp_ {
+    let nes = exps(context, rhs.value);
+    let verb = "construction";
+    let positional = true;
+    let nfields_opt = context
+        .resolve_variant_name(loc, verb, e, v, tys_opt)
+        .and_then(|(m, en, vn, tys_opt, fields)| {
+            let num_fields = context
+                .check_variant_fields(loc, verb, (&m, &en, &vn), fields, positional)?
+                .unwrap();
+            let name_f = || format!("{}::{}::{}", m, en, vn);
+            let nfields = positional_fields(context, loc, verb, name_f, num_fields, nes)?;
+            Some((m, en, vn, tys_opt, nfields))
+        });
+    match nfields_opt {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some((m, en, vn, tys_opt, nfields)) => N::Exp_::PackVariant(m, en, vn, tys_opt, nfields),
+    }
+}
+
+// The body of a macro is expanded in place at each of its calls, so it cannot contain anything
+// that refers to the function around the call (or to the macro itself as a function)
+fn check_macro_body(context: &mut Context, loc: Loc, construct: &str) {
+    if let Some(macro_loc) = context.translating_macro {
+        let reason = match construct {
+            "return" => "it would return from the calling function",
+            "spec" => "the specification would be attached to the calling function",
+            _ => "it would refer to a loop around the call",
+        };
+        let msg = format!(
+            "Invalid '{}' in a macro. The body of a macro is expanded at each of its calls, \
+             where {}",
+            construct, reason
+        );
+        context.env.add_diag(diag!(
+            Declarations::InvalidFunction,
+            (loc, msg),
+            (macro_loc, "Declared as a macro here"),
+        ));
+    }
+}
+
+// String literals are lowered to `std::string::utf8(b"...")`. Constants cannot call functions
+// (and `String` is not a valid constant type), so inside constants the literal is lowered to its
+// `vector<u8>` bytes instead.
+fn string_literal(context: &mut Context, loc: Loc, bytes: Vec<u8>) -> N::Exp_ {
+    let bytes_ = N::Exp_::Value(sp(loc, E::Value_::Bytearray(bytes)));
+    if !context.translating_fun {
+        return bytes_;
+    }
+    match context.string_module() {
+        Some(m) => {
+            context.used_string_module = Some(m);
+            let utf8 = FunctionName(sp(loc, UTF8_FUNCTION_NAME));
+            N::Exp_::ModuleCall(m, utf8, None, sp(loc, vec![sp(loc, bytes_)]))
+        }
+        None => {
+            let msg = format!(
+                "Unable to resolve '{}::{}::{}' for this string literal",
+                STD_ADDR_NAME, UTF_MODULE_NAME, UTF8_FUNCTION_NAME
+            );
+            let mut diag = diag!(StringLiterals::UnboundStringModule, (loc, msg));
+            diag.add_note(
+                "String literals require the standard library as a dependency. \
+                 Use a byte string, e.g. b\"...\", for a 'vector<u8>' value instead",
+            );
+            context.env.add_diag(diag);
+            N::Exp_::UnresolvedError
+        }
+    }
+}
+
+fn access_constant(context: &mut Context, ma: E::ModuleAccess) -> N::Exp_ {
+    match context.resolve_constant(ma) {
+        None => {
+            assert!(context.env.has_errors());
+            N::Exp_::UnresolvedError
+        }
+        Some((m, c)) => N::Exp_::Constant(m, c),
+    }
+}
+
+fn dotted(context: &mut Context, edot: E::ExpDotted) -> Option<N::ExpDotted> {
+    let sp!(loc, edot_) = edot;
+    let nedot_ = match edot_ {
+        E::ExpDotted_::Exp(e) => {
+            let ne = exp(context, e);
+            match &ne.value {
+                N::Exp_::UnresolvedError => return None,
+                _ => N::ExpDotted_::Exp(ne),
+            }
+        }
+        E::ExpDotted_::Dot(d, f) => N::ExpDotted_::Dot(Box::new(dotted(context, *d)?), Field(f)),
+        E::ExpDotted_::Index(d, i) => {
+            N::ExpDotted_::Index(Box::new(dotted(context, *d)?), exp(context, *i))
+        }
+    };
+    Some(sp(loc, nedot_))
+}
+
+//**************************************************************************************************
+// Match
+//**************************************************************************************************
+
+fn match_arm(context: &mut Context, sp!(loc, earm_): E::MatchArm) -> N::MatchArm {
+    let E::MatchArm_ {
+        pattern: epattern,
+        guard: eguard,
+        rhs: erhs,
+    } = earm_;
+    // the bindings of the pattern are only in scope for the guard and the right-hand side
+    context.new_local_scope();
+    let pattern = match_pattern(context, &mut UniqueMap::new(), epattern);
+    let guard = eguard.map(|eguard| exp(context, *eguard));
+    let rhs = exp(context, *erhs);
+    context.close_local_scope();
+    let arm = N::MatchArm_ {
+        pattern,
+        guard,
+        rhs,
+    };
+    sp(loc, arm)
+}
+
+fn match_pattern(
+    context: &mut Context,
+    seen_binders: &mut UniqueMap<Name, ()>,
+    sp!(loc, epattern_): E::MatchPattern,
+) -> N::MatchPattern {
+    use E::MatchPattern_ as EP;
+    use N::MatchPattern_ as NP;
+    let pattern_ = match epattern_ {
+        EP::PositionalConstructor(
+            sp!(_, E::ModuleAccess_::Variant(e, v)),
+            etys_opt,
+            sp!(ploc, epatterns),
+        ) => {
+            let verb = "pattern";
+            let resolved = context
+                .resolve_variant_name(loc, verb, *e, v, etys_opt)
+                .and_then(|(m, en, vn, tys_opt, fields)| {
+                    let num_fields = context
+                        .check_variant_fields(loc, verb, (&m, &en, &vn), fields, true)?
+                        .unwrap();
+                    Some((m, en, vn, tys_opt, num_fields))
+                });
+            let npatterns: Vec<N::MatchPattern> = epatterns
+                .into_iter()
+                .map(|ep| match_pattern(context, seen_binders, ep))
+                .collect();
+            match resolved {
+                None => {
+                    assert!(context.env.has_errors());
+                    NP::ErrorPat
+                }
+                Some((m, en, vn, tys_opt, num_fields)) => {
+                    let name_f = || format!("{}::{}::{}", m, en, vn);
+                    match positional_fields(context, ploc, verb, name_f, num_fields, npatterns) {
+                        None => NP::ErrorPat,
+                        Some(nfields) => NP::Variant(m, en, vn, tys_opt, nfields),
+                    }
+                }
+            }
+        }
+        EP::PositionalConstructor(tn, etys_opt, sp!(ploc, epatterns)) => {
+            let verb = "pattern";
+            let resolved = context
+                .resolve_struct_name(loc, verb, tn, etys_opt)
+                .and_then(|(m, sn, tys_opt)| {
+                    let num_fields = context
+                        .check_positional_fields(loc, verb, &m, &sn, /* positional */ true)?
+                        .unwrap();
+                    Some((m, sn, tys_opt, num_fields))
+                });
+            let npatterns: Vec<N::MatchPattern> = epatterns
+                .into_iter()
+                .map(|ep| match_pattern(context, seen_binders, ep))
+                .collect();
+            match resolved {
+                None => {
+                    assert!(context.env.has_errors());
+                    NP::ErrorPat
+                }
+                Some((m, sn, tys_opt, num_fields)) => {
+                    let name_f = || format!("{}::{}", m, sn);
+                    match positional_fields(context, ploc, verb, name_f, num_fields, npatterns) {
+                        None => NP::ErrorPat,
+                        Some(nfields) => NP::Constructor(m, sn, tys_opt, nfields),
+                    }
+                }
+            }
+        }
+        EP::FieldConstructor(sp!(_, E::ModuleAccess_::Variant(e, v)), etys_opt, efields) => {
+            let verb = "pattern";
+            let resolved = context
+                .resolve_variant_name(loc, verb, *e, v, etys_opt)
+                .and_then(|(m, en, vn, tys_opt, fields)| {
+                    context.check_variant_fields(loc, verb, (&m, &en, &vn), fields, false)?;
+                    Some((m, en, vn, tys_opt))
+                });
+            let nfields =
+                efields.map(|_, (idx, ep)| (idx, match_pattern(context, seen_binders, ep)));
+            match resolved {
+                None => {
+                    assert!(context.env.has_errors());
+                    NP::ErrorPat
+                }
+                Some((m, en, vn, tys_opt)) => NP::Variant(m, en, vn, tys_opt, nfields),
+            }
+        }
+        EP::FieldConstructor(tn, etys_opt, efields) => {
+            let verb = "pattern";
+            let resolved = context
+                .resolve_struct_name(loc, verb, tn, etys_opt)
+                .and_then(|(m, sn, tys_opt)| {
+                    context
+                        .check_positional_fields(loc, verb, &m, &sn, /* positional */ false)?;
+                    Some((m, sn, tys_opt))
+                });
+            let nfields =
+                efields.map(|_, (idx, ep)| (idx, match_pattern(context, seen_binders, ep)));
+            match resolved {
+                None => {
+                    assert!(context.env.has_errors());
+                    NP::ErrorPat
+                }
+                Some((m, sn, tys_opt)) => NP::Constructor(m, sn, tys_opt, nfields),
+            }
+        }
+        EP::Binder(v) if v.is_underscore() => NP::Wildcard,
+        EP::Binder(v) => {
+            if let Err((var, prev_loc)) = seen_binders.add(v.0, ()) {
+                let msg = format!(
+                    "Duplicate declaration for local '{}' in a given 'match' arm",
+                    &var
+                );
+                context.env.add_diag(diag!(
+                    Declarations::DuplicateItem,
+                    (var.loc, msg),
+                    (prev_loc, "Previously declared here"),
+                ));
+            }
+            let is_parameter = false;
+            NP::Binder(context.declare_local(is_parameter, v.0))
+        }
+        EP::Literal(v) => NP::Literal(v),
+    };
+    sp(loc, pattern_)
+}
+
+#[derive(Clone, Copy)]
+enum LValueCase {
+    Bind,
+    Assign,
+}
+
+fn lvalue(
+    context: &mut Context,
+    seen_locals: &mut UniqueMap<Name, ()>,
+    case: LValueCase,
+    sp!(loc, l_): E::LValue,
+) -> Option<N::LValue> {
     use LValueCase as C;
     use E::LValue_ as EL;
     use N::LValue_ as NL;
     let nl_ = match l_ {
-        EL::Var(sp!(_, E::ModuleAccess_::Name(n)), None) => {
+        EL::Var(mut_, sp!(_, E::ModuleAccess_::Name(n)), None) => {
             let v = P::Var(n);
             if v.is_underscore() {
                 NL::Ignore
@@ -1330,24 +2871,66 @@ fn lvalue(
                 }
                 let nv = match case {
                     C::Bind => {
+                        context.check_shadowed_local(n);
                         let is_parameter = false;
                         context.declare_local(is_parameter, n)
                     }
                     C::Assign => context.resolve_local(loc, "assignment", n)?,
                 };
                 NL::Var {
+                    mut_,
                     var: nv,
                     // set later
                     unused_binding: false,
                 }
             }
         }
+        EL::Unpack(sp!(_, E::ModuleAccess_::Variant(e, v)), etys_opt, efields) => {
+            let msg = match case {
+                C::Bind => "deconstructing binding",
+                C::Assign => "deconstructing assignment",
+            };
+            let (m, en, vn, tys_opt, fields) =
+                context.resolve_variant_name(loc, msg, *e, v, etys_opt)?;
+            let positional = matches!(efields, E::FieldBindings::Positional(_));
+            let num_fields_opt =
+                context.check_variant_fields(loc, msg, (&m, &en, &vn), fields, positional)?;
+            let efields = match efields {
+                E::FieldBindings::Named(efields) => efields,
+                E::FieldBindings::Positional(elvalues) => {
+                    let num_fields = num_fields_opt.unwrap();
+                    let name_f = || format!("{}::{}::{}", m, en, vn);
+                    positional_fields(context, loc, msg, name_f, num_fields, elvalues)?
+                }
+            };
+            let nfields =
+                UniqueMap::maybe_from_opt_iter(efields.into_iter().map(|(k, (idx, inner))| {
+                    Some((k, (idx, lvalue(context, seen_locals, case, inner)?)))
+                }))?;
+            NL::UnpackVariant(
+                m,
+                en,
+                vn,
+                tys_opt,
+                nfields.expect("ICE fields were already unique"),
+            )
+        }
         EL::Unpack(tn, etys_opt, efields) => {
             let msg = match case {
                 C::Bind => "deconstructing binding",
                 C::Assign => "deconstructing assignment",
             };
             let (m, sn, tys_opt) = context.resolve_struct_name(loc, msg, tn, etys_opt)?;
+            let positional = matches!(efields, E::FieldBindings::Positional(_));
+            let num_fields_opt = context.check_positional_fields(loc, msg, &m, &sn, positional)?;
+            let efields = match efields {
+                E::FieldBindings::Named(efields) => efields,
+                E::FieldBindings::Positional(elvalues) => {
+                    let num_fields = num_fields_opt.unwrap();
+                    let name_f = || format!("{}::{}", m, sn);
+                    positional_fields(context, loc, msg, name_f, num_fields, elvalues)?
+                }
+            };
             let nfields =
                 UniqueMap::maybe_from_opt_iter(efields.into_iter().map(|(k, (idx, inner))| {
                     Some((k, (idx, lvalue(context, seen_locals, case, inner)?)))
@@ -1359,7 +2942,7 @@ fn lvalue(
                 nfields.expect("ICE fields were already unique"),
             )
         }
-        EL::Var(_, _) => panic!("unexpected specification construct"),
+        EL::Var(_, _, _) => panic!("unexpected specification construct"),
     };
     Some(sp(loc, nl_))
 }
@@ -1509,7 +3092,7 @@ fn remove_unused_bindings_function(
         // no warnings for natives
         N::FunctionBody_::Native => return,
     }
-    for (v, _) in &mut f.signature.parameters {
+    for (_, v, _) in &mut f.signature.parameters {
         if !used.contains(&v.value) {
             report_unused_local(context, v);
         }
@@ -1562,12 +3145,14 @@ fn remove_unused_bindings_lvalue(
         N::LValue_::Var {
             var,
             unused_binding,
+            ..
         } if used.contains(&var.value) => {
             debug_assert!(!*unused_binding);
         }
         N::LValue_::Var {
             var,
             unused_binding,
+            ..
         } => {
             debug_assert!(!*unused_binding);
             if report {
@@ -1575,7 +3160,7 @@ fn remove_unused_bindings_lvalue(
             }
             *unused_binding = true;
         }
-        N::LValue_::Unpack(_, _, _, lvalues) => {
+        N::LValue_::Unpack(_, _, _, lvalues) | N::LValue_::UnpackVariant(_, _, _, _, lvalues) => {
             for (_, _, (_, lvalue)) in lvalues {
                 remove_unused_bindings_lvalue(context, used, lvalue, report)
             }
@@ -1594,29 +3179,40 @@ fn remove_unused_bindings_exp(
         | N::Exp_::Copy(_)
         | N::Exp_::Use(_)
         | N::Exp_::Constant(_, _)
-        | N::Exp_::Break
-        | N::Exp_::Continue
+        | N::Exp_::Break(_, None)
+        | N::Exp_::Continue(_)
         | N::Exp_::Unit { .. }
         | N::Exp_::Spec(_, _)
         | N::Exp_::UnresolvedError => (),
         N::Exp_::Return(e)
         | N::Exp_::Abort(e)
+        | N::Exp_::Break(_, Some(e))
         | N::Exp_::Dereference(e)
         | N::Exp_::UnaryExp(_, e)
         | N::Exp_::Cast(e, _)
         | N::Exp_::Assign(_, e)
-        | N::Exp_::Loop(e)
-        | N::Exp_::Annotate(e, _) => remove_unused_bindings_exp(context, used, e),
+        | N::Exp_::Loop(_, e)
+        | N::Exp_::Annotate(e, _)
+        | N::Exp_::MacroExpansion(_, e) => remove_unused_bindings_exp(context, used, e),
         N::Exp_::IfElse(econd, et, ef) => {
             remove_unused_bindings_exp(context, used, econd);
             remove_unused_bindings_exp(context, used, et);
             remove_unused_bindings_exp(context, used, ef);
         }
-        N::Exp_::While(econd, ebody) => {
+        N::Exp_::While(_, econd, ebody) => {
             remove_unused_bindings_exp(context, used, econd);
             remove_unused_bindings_exp(context, used, ebody)
         }
         N::Exp_::Block(s) => remove_unused_bindings_seq(context, used, s),
+        N::Exp_::Match(esubject, sp!(_, arms)) => {
+            remove_unused_bindings_exp(context, used, esubject);
+            for sp!(_, arm) in arms {
+                if let Some(guard) = &mut arm.guard {
+                    remove_unused_bindings_exp(context, used, guard)
+                }
+                remove_unused_bindings_exp(context, used, &mut arm.rhs)
+            }
+        }
         N::Exp_::FieldMutate(ed, e) => {
             remove_unused_bindings_exp_dotted(context, used, ed);
             remove_unused_bindings_exp(context, used, e)
@@ -1625,7 +3221,7 @@ fn remove_unused_bindings_exp(
             remove_unused_bindings_exp(context, used, el);
             remove_unused_bindings_exp(context, used, er)
         }
-        N::Exp_::Pack(_, _, _, fields) => {
+        N::Exp_::Pack(_, _, _, fields) | N::Exp_::PackVariant(_, _, _, _, fields) => {
             for (_, _, (_, e)) in fields {
                 remove_unused_bindings_exp(context, used, e)
             }
@@ -1633,6 +3229,7 @@ fn remove_unused_bindings_exp(
         N::Exp_::Builtin(_, sp!(_, es))
         | N::Exp_::Vector(_, _, sp!(_, es))
         | N::Exp_::ModuleCall(_, _, _, sp!(_, es))
+        | N::Exp_::MacroCall(_, _, _, sp!(_, es))
         | N::Exp_::ExpList(es) => {
             for e in es {
                 remove_unused_bindings_exp(context, used, e)
@@ -1642,6 +3239,12 @@ fn remove_unused_bindings_exp(
         N::Exp_::DerefBorrow(ed) | N::Exp_::Borrow(_, ed) => {
             remove_unused_bindings_exp_dotted(context, used, ed)
         }
+        N::Exp_::MethodCall(ed, _, _, sp!(_, es)) => {
+            remove_unused_bindings_exp_dotted(context, used, ed);
+            for e in es {
+                remove_unused_bindings_exp(context, used, e)
+            }
+        }
     }
 }
 
@@ -1653,6 +3256,10 @@ fn remove_unused_bindings_exp_dotted(
     match ed_ {
         N::ExpDotted_::Exp(e) => remove_unused_bindings_exp(context, used, e),
         N::ExpDotted_::Dot(ed, _) => remove_unused_bindings_exp_dotted(context, used, ed),
+        N::ExpDotted_::Index(ed, e) => {
+            remove_unused_bindings_exp_dotted(context, used, ed);
+            remove_unused_bindings_exp(context, used, e)
+        }
     }
 }
 
@@ -1671,9 +3278,101 @@ fn report_unused_local(context: &mut Context, sp!(loc, unused_): &N::Var) {
     let msg = format!(
         "Unused {kind} '{name}'. Consider removing or prefixing with an underscore: '_{name}'",
     );
+    let mut diag = diag!(UnusedItem::Variable, (*loc, msg));
+    let start = Loc::new(loc.file_hash(), loc.start(), loc.start());
+    diag.add_fix(Fix::new(
+        format!("Prefix with an underscore: '_{name}'"),
+        [(start, "_")],
+    ));
+    context.env.add_diag(diag);
+}
+
+//**************************************************************************************************
+// Unused constants and functions
+//**************************************************************************************************
+
+// Constants named in attributes, e.g. the abort code of an `expected_failure`, are used
+fn attribute_constants(context: &mut Context, attributes: &E::Attributes) {
+    for (_, _, attr) in attributes {
+        match &attr.value {
+            E::Attribute_::Name(_) => (),
+            E::Attribute_::Parameterized(_, attrs) => attribute_constants(context, attrs),
+            E::Attribute_::Assigned(_, val) => {
+                let E::AttributeValue_::ModuleAccess(sp!(_, ma_)) = &val.value else {
+                    continue;
+                };
+                if let E::ModuleAccess_::ModuleAccess(m, n) = ma_ {
+                    // conservatively assume that each `ModuleAccess` refers to a constant name
+                    context.used_constants.insert((m.value, n.value));
+                }
+            }
+        }
+    }
+}
+
+/// Generates warnings for unused constants and unused (private) functions. Names are resolved
+/// before macros are expanded, so the constants and functions used in the bodies of macros are
+/// seen even if the macros are never called.
+fn gen_unused_warnings(context: &mut Context, mident: &ModuleIdent_, mdef: &N::ModuleDefinition) {
+    if !mdef.is_source_module {
+        // generate warnings only for modules compiled in this pass rather than for all modules
+        // including pre-compiled libraries for which we do not have source code available and
+        // cannot be analyzed in this pass
+        return;
+    }
+
     context
         .env
-        .add_diag(diag!(UnusedItem::Variable, (*loc, msg)));
+        .add_warning_filter_scope(mdef.warning_filter.clone());
+    for (loc, name, c) in &mdef.constants {
+        if context.used_constants.contains(&(*mident, *name)) {
+            continue;
+        }
+        context
+            .env
+            .add_warning_filter_scope(c.warning_filter.clone());
+        let msg = format!("The constant '{name}' is never used. Consider removing it.");
+        context
+            .env
+            .add_diag(diag!(UnusedItem::Constant, (loc, msg)));
+        context.env.pop_warning_filter_scope();
+    }
+
+    let is_sui_mode = context.env.package_config(mdef.package_name).flavor == Flavor::Sui;
+    for (loc, name, fun) in &mdef.functions {
+        if fun.entry.is_some()
+            || !matches!(fun.visibility, E::Visibility::Internal)
+            || context.used_functions.contains(&(*mident, *name))
+        {
+            continue;
+        }
+        if fun.attributes.iter().any(|(_, n, _)| {
+            n == &E::AttributeName_::Known(known_attributes::KnownAttribute::Testing(
+                known_attributes::TestingAttribute::Test,
+            ))
+        }) {
+            // functions with #[test] attribute are implicitly used
+            continue;
+        }
+        if is_sui_mode && *name == INIT_FUNCTION_NAME {
+            // a Sui-specific filter to avoid signaling that the init function is unused
+            continue;
+        }
+        context
+            .env
+            .add_warning_filter_scope(fun.warning_filter.clone());
+        // TODO: postponing handling of friend functions until we decide what to do with them
+        // vis-a-vis ideas around package-private
+        let msg = format!(
+            "The non-'public', non-'entry' function '{name}' is never called. \
+             Consider removing it."
+        );
+        context
+            .env
+            .add_diag(diag!(UnusedItem::Function, (loc, msg)));
+        context.env.pop_warning_filter_scope();
+    }
+    context.env.pop_warning_filter_scope();
 }
 
 //**************************************************************************************************
@@ -1746,6 +3445,9 @@ fn spec_block_member(
                             E::ModuleAccess_::ModuleAccess(mident, _) => {
                                 used.insert((*mident, sp(maccess.loc, Neighbor_::Friend)));
                             }
+                            E::ModuleAccess_::Variant(e, _) => {
+                                used.insert((e.value.0, sp(maccess.loc, Neighbor_::Friend)));
+                            }
                         },
                     }
                 }
@@ -1783,7 +3485,7 @@ fn spec_lvalues(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, lvs_): &E::
 
 fn spec_lvalue(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, lv_): &E::LValue) {
     match lv_ {
-        E::LValue_::Var(m, tys_opt) => {
+        E::LValue_::Var(_, m, tys_opt) => {
             spec_module_access(used, m);
             if let Some(tys) = tys_opt {
                 spec_types(used, tys)
@@ -1794,8 +3496,44 @@ fn spec_lvalue(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, lv_): &E::LV
             if let Some(tys) = tys_opt {
                 spec_types(used, tys)
             }
-            for (_, _, (_, field_lv)) in fields {
-                spec_lvalue(used, field_lv)
+            match fields {
+                E::FieldBindings::Named(fields) => {
+                    for (_, _, (_, field_lv)) in fields {
+                        spec_lvalue(used, field_lv)
+                    }
+                }
+                E::FieldBindings::Positional(lvalues) => {
+                    for field_lv in lvalues {
+                        spec_lvalue(used, field_lv)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn spec_match_pattern(
+    used: &mut BTreeSet<(ModuleIdent, Neighbor)>,
+    sp!(_, pat_): &E::MatchPattern,
+) {
+    match pat_ {
+        E::MatchPattern_::Binder(_) | E::MatchPattern_::Literal(_) => (),
+        E::MatchPattern_::PositionalConstructor(m, tys_opt, sp!(_, pats)) => {
+            spec_module_access(used, m);
+            if let Some(tys) = tys_opt {
+                spec_types(used, tys)
+            }
+            for pat in pats {
+                spec_match_pattern(used, pat)
+            }
+        }
+        E::MatchPattern_::FieldConstructor(m, tys_opt, fields) => {
+            spec_module_access(used, m);
+            if let Some(tys) = tys_opt {
+                spec_types(used, tys)
+            }
+            for (_, _, (_, pat)) in fields {
+                spec_match_pattern(used, pat)
             }
         }
     }
@@ -1832,21 +3570,26 @@ fn spec_module_access(
         E::ModuleAccess_::ModuleAccess(m, _) => {
             used.insert((*m, sp(*loc, Neighbor_::Dependency)));
         }
+        E::ModuleAccess_::Variant(e, _) => {
+            used.insert((e.value.0, sp(*loc, Neighbor_::Dependency)));
+        }
     }
 }
 
 fn spec_exp(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, e_): &E::Exp) {
     match e_ {
         E::Exp_::Value(_)
+        | E::Exp_::StringLiteral(_)
         | E::Exp_::Move(_)
         | E::Exp_::Copy(_)
-        | E::Exp_::Break
-        | E::Exp_::Continue
+        | E::Exp_::Break(_, None)
+        | E::Exp_::Continue(_)
         | E::Exp_::Unit { .. }
         | E::Exp_::Spec(_, _)
         | E::Exp_::UnresolvedError => (),
 
-        E::Exp_::Loop(einner)
+        E::Exp_::Loop(_, einner)
+        | E::Exp_::Break(_, Some(einner))
         | E::Exp_::Return(einner)
         | E::Exp_::Abort(einner)
         | E::Exp_::Dereference(einner)
@@ -1895,11 +3638,21 @@ fn spec_exp(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, e_): &E::Exp) {
             spec_exp(used, etrue);
             spec_exp(used, efalse);
         }
-        E::Exp_::While(econd, ebody) => {
+        E::Exp_::While(_, econd, ebody) => {
             spec_exp(used, econd);
             spec_exp(used, ebody)
         }
         E::Exp_::Block(seq) => spec_sequence(used, seq),
+        E::Exp_::Match(esubject, sp!(_, arms)) => {
+            spec_exp(used, esubject);
+            for sp!(_, arm) in arms {
+                spec_match_pattern(used, &arm.pattern);
+                if let Some(guard) = &arm.guard {
+                    spec_exp(used, guard)
+                }
+                spec_exp(used, &arm.rhs)
+            }
+        }
         E::Exp_::Lambda(lvs, ebody) => {
             spec_lvalues(used, lvs);
             spec_exp(used, ebody)
@@ -1934,6 +3687,15 @@ fn spec_exp(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, e_): &E::Exp) {
             }
         }
         E::Exp_::ExpDotted(edotted) => spec_exp_dotted(used, edotted),
+        E::Exp_::MethodCall(edotted, _, tys_opt, sp!(_, args_)) => {
+            spec_exp_dotted(used, edotted);
+            if let Some(tys) = tys_opt {
+                spec_types(used, tys)
+            }
+            for arg in args_ {
+                spec_exp(used, arg)
+            }
+        }
         E::Exp_::Cast(e, ty) | E::Exp_::Annotate(e, ty) => {
             spec_exp(used, e);
             spec_type(used, ty)
@@ -1945,5 +3707,456 @@ fn spec_exp_dotted(used: &mut BTreeSet<(ModuleIdent, Neighbor)>, sp!(_, edotted_
     match edotted_ {
         E::ExpDotted_::Exp(e) => spec_exp(used, e),
         E::ExpDotted_::Dot(edotted, _) => spec_exp_dotted(used, edotted),
+        E::ExpDotted_::Index(edotted, e) => {
+            spec_exp_dotted(used, edotted);
+            spec_exp(used, e)
+        }
+    }
+}
+
+//**************************************************************************************************
+// Spec name resolution
+//**************************************************************************************************
+
+/// The functions and constants that the prover provides to specifications
+const SPEC_BUILTINS: &[&str] = &[
+    "$spec_domain",
+    "EXECUTION_FAILURE",
+    "MAX_U8",
+    "MAX_U16",
+    "MAX_U32",
+    "MAX_U64",
+    "MAX_U128",
+    "MAX_U256",
+    "TRACE",
+    "borrow_global",
+    "borrow_global_mut",
+    "bv2int",
+    "concat",
+    "contains",
+    "exists",
+    "global",
+    "in_range",
+    "index_of",
+    "int2bv",
+    "len",
+    "max_u8",
+    "max_u16",
+    "max_u32",
+    "max_u64",
+    "max_u128",
+    "max_u256",
+    "old",
+    "range",
+    "update",
+    "update_field",
+    "vec",
+];
+
+/// The names bound in the specs of each function and struct: their parameters and fields
+fn spec_targets<'a>(
+    structs: impl IntoIterator<Item = (Symbol, &'a E::StructDefinition)>,
+    functions: impl IntoIterator<Item = (Symbol, &'a E::Function)>,
+) -> BTreeMap<Symbol, BTreeSet<Symbol>> {
+    let struct_fields = structs.into_iter().map(|(n, s)| {
+        let fields = match &s.fields {
+            E::StructFields::Defined(fields) => fields.iter().map(|(_, f, _)| *f).collect(),
+            E::StructFields::Positional(_) | E::StructFields::Native(_) => BTreeSet::new(),
+        };
+        (n, fields)
+    });
+    let function_params = functions.into_iter().map(|(n, f)| {
+        let params = f.signature.parameters.iter().map(|(_, v, _)| v.value());
+        (n, params.collect())
+    });
+    struct_fields.chain(function_params).collect()
+}
+
+fn resolve_spec_blocks(
+    context: &mut Context,
+    targets: &BTreeMap<Symbol, BTreeSet<Symbol>>,
+    specs: &[E::SpecBlock],
+) {
+    for spec in specs {
+        resolve_spec_block(context, targets, spec)
+    }
+}
+
+fn resolve_spec_block(
+    context: &mut Context,
+    targets: &BTreeMap<Symbol, BTreeSet<Symbol>>,
+    sp!(_, sb_): &E::SpecBlock,
+) {
+    use E::SpecBlockMember_ as M;
+    use E::SpecBlockTarget_ as T;
+    let mut scope = match &sb_.target.value {
+        T::Member(_, Some(signature)) => signature
+            .parameters
+            .iter()
+            .map(|(_, v, _)| v.value())
+            .collect(),
+        T::Member(name, None) => targets.get(&name.value).cloned().unwrap_or_default(),
+        T::Code | T::Module | T::Schema(_, _) => BTreeSet::new(),
+    };
+    // lets and the variables of schemas are in scope in the whole block
+    for sp!(_, member) in &sb_.members {
+        match member {
+            M::Let { name, .. }
+            | M::Variable {
+                is_global: false,
+                name,
+                ..
+            } => {
+                scope.insert(name.value);
+            }
+            _ => (),
+        }
+    }
+    context.spec_scopes.push(scope);
+    for member in &sb_.members {
+        resolve_spec_block_member(context, member)
+    }
+    context.spec_scopes.pop();
+}
+
+fn resolve_spec_block_member(context: &mut Context, sp!(_, sbm_): &E::SpecBlockMember) {
+    use E::SpecBlockMember_ as M;
+    match sbm_ {
+        M::Condition {
+            exp: e,
+            additional_exps: es,
+            ..
+        } => {
+            resolve_spec_exp(context, e);
+            es.iter().for_each(|e| resolve_spec_exp(context, e))
+        }
+        M::Function {
+            signature, body, ..
+        } => {
+            if let E::FunctionBody_::Defined(seq) = &body.value {
+                let params = signature.parameters.iter().map(|(_, v, _)| v.value());
+                context.spec_scopes.push(params.collect());
+                resolve_spec_sequence(context, seq);
+                context.spec_scopes.pop();
+            }
+        }
+        M::Variable { init, .. } => {
+            if let Some(e) = init {
+                resolve_spec_exp(context, e)
+            }
+        }
+        M::Let { def: e, .. } | M::Include { exp: e, .. } | M::Apply { exp: e, .. } => {
+            resolve_spec_exp(context, e)
+        }
+        M::Update { lhs, rhs } => {
+            resolve_spec_exp(context, lhs);
+            resolve_spec_exp(context, rhs);
+        }
+        M::Pragma { .. } => (),
+    }
+}
+
+/// Whether the unqualified name `n` is in scope in the specification being resolved
+fn is_spec_name_bound(context: &Context, n: &Symbol) -> bool {
+    context.spec_scopes.iter().any(|scope| scope.contains(n))
+        || context
+            .local_scopes
+            .last()
+            .is_some_and(|locals| locals.contains_key(n))
+        || context.unscoped_constants.contains_key(n)
+        || SPEC_BUILTINS.contains(&n.as_str())
+        || N::BuiltinFunction_::all_names().contains(n)
+        || n.as_str() == "result"
+        || n.strip_prefix("result_")
+            .is_some_and(|i| i.parse::<usize>().is_ok())
+        || context
+            .current_module
+            .and_then(|m| context.module_info.module(&m))
+            .is_some_and(|minfo| is_spec_module_member(minfo, n))
+}
+
+fn is_spec_module_member(minfo: &ModuleInfo, n: &Symbol) -> bool {
+    minfo.struct_(n).is_some()
+        || minfo.function(n).is_some()
+        || minfo.constant(n).is_some()
+        || minfo.is_spec_member(n)
+}
+
+fn resolve_spec_access(context: &mut Context, sp!(_, ma_): &E::ModuleAccess) {
+    match ma_ {
+        E::ModuleAccess_::Name(n) => {
+            if !is_spec_name_bound(context, &n.value) {
+                context.env.add_diag(diag!(
+                    NameResolution::UnboundUnscopedName,
+                    (n.loc, format!("Unbound name '{}' in specification", n)),
+                ))
+            }
+        }
+        E::ModuleAccess_::ModuleAccess(m, n) => {
+            if !context.resolve_module(m) {
+                return;
+            }
+            let minfo = context.module_info.module(m).unwrap();
+            if !is_spec_module_member(minfo, &n.value) {
+                let msg = format!(
+                    "Invalid module access. Unbound member '{}' in module '{}'",
+                    n, m
+                );
+                context
+                    .env
+                    .add_diag(diag!(NameResolution::UnboundModuleMember, (n.loc, msg)))
+            }
+        }
+        E::ModuleAccess_::Variant(e, v) => {
+            context.resolve_variant_name(v.loc, "module access", **e, *v, None);
+        }
+    }
+}
+
+fn resolve_spec_types(context: &mut Context, tys: &[E::Type]) {
+    for ty in tys {
+        resolve_spec_type(context, ty)
+    }
+}
+
+/// Only module accesses are resolved in types, as the type parameters in scope are not tracked
+fn resolve_spec_type(context: &mut Context, sp!(_, ty_): &E::Type) {
+    match ty_ {
+        E::Type_::Unit | E::Type_::UnresolvedError => (),
+        E::Type_::Multiple(tys) => resolve_spec_types(context, tys),
+        E::Type_::Apply(ma, tys) => {
+            if let E::ModuleAccess_::ModuleAccess(_, _) = &ma.value {
+                resolve_spec_access(context, ma)
+            }
+            resolve_spec_types(context, tys)
+        }
+        E::Type_::Ref(_, inner) => resolve_spec_type(context, inner),
+        E::Type_::Fun(ty_params, ty_ret) => {
+            resolve_spec_types(context, ty_params);
+            resolve_spec_type(context, ty_ret);
+        }
+    }
+}
+
+fn resolve_spec_type_args(context: &mut Context, tys_opt: &Option<Vec<E::Type>>) {
+    if let Some(tys) = tys_opt {
+        resolve_spec_types(context, tys)
+    }
+}
+
+fn resolve_spec_sequence(context: &mut Context, seq: &E::Sequence) {
+    context.spec_scopes.push(BTreeSet::new());
+    for sp!(_, item_) in seq {
+        match item_ {
+            E::SequenceItem_::Declare(lvs, _) => bind_spec_lvalues(context, lvs),
+            E::SequenceItem_::Bind(lvs, e) => {
+                resolve_spec_exp(context, e);
+                bind_spec_lvalues(context, lvs);
+            }
+            E::SequenceItem_::Seq(e) => resolve_spec_exp(context, e),
+        }
+    }
+    context.spec_scopes.pop();
+}
+
+/// Binds the names declared by `lvs` in the innermost scope
+fn bind_spec_lvalues(context: &mut Context, sp!(_, lvs_): &E::LValueList) {
+    for lv in lvs_ {
+        bind_spec_lvalue(context, lv)
+    }
+}
+
+fn bind_spec_lvalue(context: &mut Context, sp!(_, lv_): &E::LValue) {
+    match lv_ {
+        E::LValue_::Var(_, sp!(_, E::ModuleAccess_::Name(n)), _) => {
+            context.spec_scopes.last_mut().unwrap().insert(n.value);
+        }
+        E::LValue_::Var(_, ma, tys_opt) => {
+            resolve_spec_access(context, ma);
+            resolve_spec_type_args(context, tys_opt)
+        }
+        E::LValue_::Unpack(ma, tys_opt, fields) => {
+            resolve_spec_access(context, ma);
+            resolve_spec_type_args(context, tys_opt);
+            match fields {
+                E::FieldBindings::Named(fields) => {
+                    for (_, _, (_, field_lv)) in fields {
+                        bind_spec_lvalue(context, field_lv)
+                    }
+                }
+                E::FieldBindings::Positional(lvalues) => {
+                    for field_lv in lvalues {
+                        bind_spec_lvalue(context, field_lv)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Binds the names declared by `pat` in the innermost scope
+fn bind_spec_match_pattern(context: &mut Context, sp!(_, pat_): &E::MatchPattern) {
+    match pat_ {
+        E::MatchPattern_::Literal(_) => (),
+        E::MatchPattern_::Binder(v) => {
+            context.spec_scopes.last_mut().unwrap().insert(v.value());
+        }
+        E::MatchPattern_::PositionalConstructor(ma, tys_opt, sp!(_, pats)) => {
+            resolve_spec_access(context, ma);
+            resolve_spec_type_args(context, tys_opt);
+            for pat in pats {
+                bind_spec_match_pattern(context, pat)
+            }
+        }
+        E::MatchPattern_::FieldConstructor(ma, tys_opt, fields) => {
+            resolve_spec_access(context, ma);
+            resolve_spec_type_args(context, tys_opt);
+            for (_, _, (_, pat)) in fields {
+                bind_spec_match_pattern(context, pat)
+            }
+        }
+    }
+}
+
+fn resolve_spec_exp(context: &mut Context, sp!(_, e_): &E::Exp) {
+    match e_ {
+        E::Exp_::Value(_)
+        | E::Exp_::StringLiteral(_)
+        | E::Exp_::Break(_, None)
+        | E::Exp_::Continue(_)
+        | E::Exp_::Unit { .. }
+        | E::Exp_::Spec(_, _)
+        | E::Exp_::UnresolvedError => (),
+
+        E::Exp_::Move(v) | E::Exp_::Copy(v) => {
+            resolve_spec_access(context, &sp(v.loc(), E::ModuleAccess_::Name(v.0)))
+        }
+
+        E::Exp_::Loop(_, einner)
+        | E::Exp_::Break(_, Some(einner))
+        | E::Exp_::Return(einner)
+        | E::Exp_::Abort(einner)
+        | E::Exp_::Dereference(einner)
+        | E::Exp_::UnaryExp(_, einner)
+        | E::Exp_::Borrow(_, einner) => resolve_spec_exp(context, einner),
+
+        E::Exp_::Mutate(el, er) | E::Exp_::BinopExp(el, _, er) | E::Exp_::Index(el, er) => {
+            resolve_spec_exp(context, el);
+            resolve_spec_exp(context, er)
+        }
+
+        E::Exp_::Name(ma, tys_opt) => {
+            resolve_spec_access(context, ma);
+            resolve_spec_type_args(context, tys_opt)
+        }
+        E::Exp_::Call(ma, is_macro, tys_opt, sp!(_, args_)) => {
+            // macros are checked when calling them from code
+            if !is_macro {
+                resolve_spec_access(context, ma);
+            }
+            resolve_spec_type_args(context, tys_opt);
+            for arg in args_ {
+                resolve_spec_exp(context, arg)
+            }
+        }
+        E::Exp_::Pack(ma, tys_opt, fields) => {
+            resolve_spec_access(context, ma);
+            resolve_spec_type_args(context, tys_opt);
+            for (_, _, (_, arg)) in fields {
+                resolve_spec_exp(context, arg)
+            }
+        }
+        E::Exp_::Vector(_, tys_opt, sp!(_, args_)) => {
+            resolve_spec_type_args(context, tys_opt);
+            for arg in args_ {
+                resolve_spec_exp(context, arg)
+            }
+        }
+        E::Exp_::IfElse(econd, etrue, efalse) => {
+            resolve_spec_exp(context, econd);
+            resolve_spec_exp(context, etrue);
+            resolve_spec_exp(context, efalse);
+        }
+        E::Exp_::While(_, econd, ebody) => {
+            resolve_spec_exp(context, econd);
+            resolve_spec_exp(context, ebody)
+        }
+        E::Exp_::Block(seq) => resolve_spec_sequence(context, seq),
+        E::Exp_::Match(esubject, sp!(_, arms)) => {
+            resolve_spec_exp(context, esubject);
+            for sp!(_, arm) in arms {
+                context.spec_scopes.push(BTreeSet::new());
+                bind_spec_match_pattern(context, &arm.pattern);
+                if let Some(guard) = &arm.guard {
+                    resolve_spec_exp(context, guard)
+                }
+                resolve_spec_exp(context, &arm.rhs);
+                context.spec_scopes.pop();
+            }
+        }
+        E::Exp_::Lambda(lvs, ebody) => {
+            context.spec_scopes.push(BTreeSet::new());
+            bind_spec_lvalues(context, lvs);
+            resolve_spec_exp(context, ebody);
+            context.spec_scopes.pop();
+        }
+        E::Exp_::Quant(_, sp!(_, lvs_es_), ess, e_opt, inner) => {
+            context.spec_scopes.push(BTreeSet::new());
+            for sp!(_, (lv, e)) in lvs_es_ {
+                resolve_spec_exp(context, e);
+                bind_spec_lvalue(context, lv);
+            }
+            for es in ess {
+                for e in es {
+                    resolve_spec_exp(context, e)
+                }
+            }
+            if let Some(e) = e_opt {
+                resolve_spec_exp(context, e)
+            }
+            resolve_spec_exp(context, inner);
+            context.spec_scopes.pop();
+        }
+        E::Exp_::Assign(sp!(_, lvs_), er) => {
+            for sp!(_, lv_) in lvs_ {
+                if let E::LValue_::Var(_, ma, _) = lv_ {
+                    resolve_spec_access(context, ma)
+                }
+            }
+            resolve_spec_exp(context, er)
+        }
+        E::Exp_::FieldMutate(edotted, er) => {
+            resolve_spec_exp_dotted(context, edotted);
+            resolve_spec_exp(context, er)
+        }
+
+        E::Exp_::ExpList(es) => {
+            for e in es {
+                resolve_spec_exp(context, e)
+            }
+        }
+        E::Exp_::ExpDotted(edotted) => resolve_spec_exp_dotted(context, edotted),
+        E::Exp_::MethodCall(edotted, _, tys_opt, sp!(_, args_)) => {
+            resolve_spec_exp_dotted(context, edotted);
+            resolve_spec_type_args(context, tys_opt);
+            for arg in args_ {
+                resolve_spec_exp(context, arg)
+            }
+        }
+        E::Exp_::Cast(e, ty) | E::Exp_::Annotate(e, ty) => {
+            resolve_spec_exp(context, e);
+            resolve_spec_type(context, ty)
+        }
+    }
+}
+
+fn resolve_spec_exp_dotted(context: &mut Context, sp!(_, edotted_): &E::ExpDotted) {
+    match edotted_ {
+        E::ExpDotted_::Exp(e) => resolve_spec_exp(context, e),
+        E::ExpDotted_::Dot(edotted, _) => resolve_spec_exp_dotted(context, edotted),
+        E::ExpDotted_::Index(edotted, e) => {
+            resolve_spec_exp_dotted(context, edotted);
+            resolve_spec_exp(context, e)
+        }
     }
 }
@@ -104,6 +104,14 @@ pub struct Script {
 pub enum Use {
     Module(ModuleIdent, Option<ModuleName>),
     Members(ModuleIdent, Vec<(Name, Option<Name>)>),
+    /// `use fun function as ty.method`, declaring `method` as an alias of `function` when called
+    /// with the method syntax on values of type `ty`
+    Fun {
+        loc: Loc,
+        function: Box<NameAccessChain>,
+        ty: Box<NameAccessChain>,
+        method: Name,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -167,8 +175,12 @@ pub struct ModuleIdent_ {
 }
 pub type ModuleIdent = Spanned<ModuleIdent_>;
 
+// The documentation comments of an item, merged into one string, if it has any
+pub type DocComment = Option<String>;
+
 #[derive(Debug, Clone)]
 pub struct ModuleDefinition {
+    pub doc: DocComment,
     pub attributes: Vec<Attributes>,
     pub loc: Loc,
     pub address: Option<LeadingNameAccess>,
@@ -181,6 +193,7 @@ pub struct ModuleDefinition {
 pub enum ModuleMember {
     Function(Function),
     Struct(StructDefinition),
+    Enum(EnumDefinition),
     Use(UseDecl),
     Friend(FriendDecl),
     Constant(Constant),
@@ -205,6 +218,14 @@ pub struct FriendDecl {
 new_name!(Field);
 new_name!(StructName);
 
+impl Field {
+    /// The name given to the field at position `idx` of a struct declared with positional fields.
+    /// It must be a valid identifier, as it is used as the field name in the compiled module
+    pub fn positional(loc: Loc, idx: usize) -> Self {
+        Self(sp(loc, format!("pos{}", idx).into()))
+    }
+}
+
 pub type ResourceLoc = Option<Loc>;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -216,6 +237,7 @@ pub struct StructTypeParameter {
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct StructDefinition {
+    pub doc: DocComment,
     pub attributes: Vec<Attributes>,
     pub loc: Loc,
     pub abilities: Vec<Ability>,
@@ -227,9 +249,45 @@ pub struct StructDefinition {
 #[derive(Debug, PartialEq, Clone)]
 pub enum StructFields {
     Defined(Vec<(Field, Type)>),
+    // struct S(t1, ..., tn);
+    Positional(Vec<Type>),
     Native(Loc),
 }
 
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+new_name!(EnumName);
+new_name!(VariantName);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumDefinition {
+    pub doc: DocComment,
+    pub attributes: Vec<Attributes>,
+    pub loc: Loc,
+    pub abilities: Vec<Ability>,
+    pub name: EnumName,
+    pub type_parameters: Vec<StructTypeParameter>,
+    pub variants: Vec<VariantDefinition>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct VariantDefinition {
+    pub loc: Loc,
+    pub name: VariantName,
+    pub fields: VariantFields,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum VariantFields {
+    Defined(Vec<(Field, Type)>),
+    // V(t1, ..., tn)
+    Positional(Vec<Type>),
+    // V
+    Empty,
+}
+
 //**************************************************************************************************
 // Functions
 //**************************************************************************************************
@@ -238,11 +296,12 @@ new_name!(FunctionName);
 
 pub const NATIVE_MODIFIER: &str = "native";
 pub const ENTRY_MODIFIER: &str = "entry";
+pub const MACRO_MODIFIER: &str = "macro";
 
 #[derive(PartialEq, Clone, Debug)]
 pub struct FunctionSignature {
     pub type_parameters: Vec<(Name, Vec<Ability>)>,
-    pub parameters: Vec<(Var, Type)>,
+    pub parameters: Vec<(Mutability, Var, Type)>,
     pub return_type: Type,
 }
 
@@ -268,10 +327,12 @@ pub type FunctionBody = Spanned<FunctionBody_>;
 //  }
 // (public?) native foo<T1(: copyable?), ..., TN(: copyable?)>(x1: t1, ..., xn: tn): t1 * ... * tn;
 pub struct Function {
+    pub doc: DocComment,
     pub attributes: Vec<Attributes>,
     pub loc: Loc,
     pub visibility: Visibility,
     pub entry: Option<Loc>,
+    pub macro_: Option<Loc>,
     pub signature: FunctionSignature,
     pub acquires: Vec<NameAccessChain>,
     pub name: FunctionName,
@@ -286,6 +347,7 @@ new_name!(ConstantName);
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Constant {
+    pub doc: DocComment,
     pub attributes: Vec<Attributes>,
     pub loc: Loc,
     pub signature: Type,
@@ -465,22 +527,69 @@ pub type Type = Spanned<Type_>;
 //**************************************************************************************************
 
 new_name!(Var);
+new_name!(BlockLabel);
+
+// The location of the `mut` modifier of a local or parameter, if it is declared mutable
+pub type Mutability = Option<Loc>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Bind_ {
     // x
-    Var(Var),
+    // mut x
+    Var(Mutability, Var),
     // T { f1: b1, ... fn: bn }
     // T<t1, ... , tn> { f1: b1, ... fn: bn }
-    Unpack(Box<NameAccessChain>, Option<Vec<Type>>, Vec<(Field, Bind)>),
+    // T(b1, ..., bn)
+    Unpack(Box<NameAccessChain>, Option<Vec<Type>>, FieldBindings),
 }
 pub type Bind = Spanned<Bind_>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldBindings {
+    // { f1: b1, ... fn: bn }
+    Named(Vec<(Field, Bind)>),
+    // (b1, ..., bn)
+    Positional(Vec<Bind>),
+}
+
 // b1, ..., bn
 pub type BindList = Spanned<Vec<Bind>>;
 
 pub type BindWithRange = Spanned<(Bind, Exp)>;
 pub type BindWithRangeList = Spanned<Vec<BindWithRange>>;
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchPattern_ {
+    // T(p1, ..., pn)
+    // T<t1, ... , tn>(p1, ..., pn)
+    PositionalConstructor(
+        NameAccessChain,
+        Option<Vec<Type>>,
+        Spanned<Vec<MatchPattern>>,
+    ),
+    // T { f1: p1, ..., fn: pn }
+    // T<t1, ... , tn> { f1: p1, ..., fn: pn }
+    FieldConstructor(
+        NameAccessChain,
+        Option<Vec<Type>>,
+        Spanned<Vec<(Field, MatchPattern)>>,
+    ),
+    // x, or _
+    Binder(Var),
+    // a literal value, e.g. 0 or true
+    Literal(Value),
+}
+pub type MatchPattern = Spanned<MatchPattern_>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm_ {
+    pub pattern: MatchPattern,
+    pub guard: Option<Box<Exp>>,
+    pub rhs: Box<Exp>,
+}
+// pat [if (guard)] => rhs
+pub type MatchArm = Spanned<MatchArm_>;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Value_ {
     // @<num>
@@ -493,6 +602,8 @@ pub enum Value_ {
     HexString(Symbol),
     // b"(<ascii> | \n | \r | \t | \\ | \0 | \" | \x[0..9A..F][0..9A..F])+"
     ByteString(Symbol),
+    // "(<ascii> | \n | \r | \t | \\ | \0 | \" | \x[0..9A..F][0..9A..F] | \u{[0..9A..F]+})*"
+    String(Symbol),
 }
 pub type Value = Spanned<Value_>;
 
@@ -592,10 +703,12 @@ pub enum Exp_ {
 
     // if (eb) et else ef
     IfElse(Box<Exp>, Box<Exp>, Option<Box<Exp>>),
-    // while (eb) eloop
-    While(Box<Exp>, Box<Exp>),
-    // loop eloop
-    Loop(Box<Exp>),
+    // ['a:] while (eb) eloop
+    While(Option<BlockLabel>, Box<Exp>, Box<Exp>),
+    // ['a:] loop eloop
+    Loop(Option<BlockLabel>, Box<Exp>),
+    // match (e) { arm1, ..., armn }
+    Match(Box<Exp>, Spanned<Vec<MatchArm>>),
 
     // { seq }
     Block(Sequence),
@@ -621,10 +734,10 @@ pub enum Exp_ {
     Return(Option<Box<Exp>>),
     // abort e
     Abort(Box<Exp>),
-    // break
-    Break,
-    // continue
-    Continue,
+    // break ['a] [e]
+    Break(Option<BlockLabel>, Option<Box<Exp>>),
+    // continue ['a]
+    Continue(Option<BlockLabel>),
 
     // *e
     Dereference(Box<Exp>),
@@ -639,6 +752,8 @@ pub enum Exp_ {
 
     // e.f
     Dot(Box<Exp>, Name),
+    // e.f<t1, ..., tn>(earg,*)
+    DotCall(Box<Exp>, Name, Option<Vec<Type>>, Spanned<Vec<Exp>>),
     // e[e']
     Index(Box<Exp>, Box<Exp>), // spec only
 
@@ -1141,6 +1256,7 @@ impl AstDebug for Script {
 impl AstDebug for ModuleDefinition {
     fn ast_debug(&self, w: &mut AstWriter) {
         let ModuleDefinition {
+            doc: _doc,
             attributes,
             loc: _loc,
             address,
@@ -1170,6 +1286,7 @@ impl AstDebug for ModuleMember {
         match self {
             ModuleMember::Function(f) => f.ast_debug(w),
             ModuleMember::Struct(s) => s.ast_debug(w),
+            ModuleMember::Enum(e) => e.ast_debug(w),
             ModuleMember::Use(u) => u.ast_debug(w),
             ModuleMember::Friend(f) => f.ast_debug(w),
             ModuleMember::Constant(c) => c.ast_debug(w),
@@ -1206,6 +1323,12 @@ impl AstDebug for Use {
                     })
                 })
             }
+            Use::Fun {
+                loc: _,
+                function,
+                ty,
+                method,
+            } => w.write(&format!("use fun {} as {}.{}", function, ty, method)),
         }
         w.write(";")
     }
@@ -1226,6 +1349,7 @@ impl AstDebug for FriendDecl {
 impl AstDebug for StructDefinition {
     fn ast_debug(&self, w: &mut AstWriter) {
         let StructDefinition {
+            doc: _doc,
             attributes,
             loc: _loc,
             abilities,
@@ -1246,13 +1370,68 @@ impl AstDebug for StructDefinition {
 
         w.write(&format!("struct {}", name));
         type_parameters.ast_debug(w);
-        if let StructFields::Defined(fields) = fields {
-            w.block(|w| {
+        match fields {
+            StructFields::Defined(fields) => w.block(|w| {
                 w.semicolon(fields, |w, (f, st)| {
                     w.write(&format!("{}: ", f));
                     st.ast_debug(w);
                 });
-            })
+            }),
+            StructFields::Positional(tys) => {
+                w.write("(");
+                w.comma(tys, |w, ty| ty.ast_debug(w));
+                w.write(")");
+            }
+            StructFields::Native(_) => (),
+        }
+    }
+}
+
+impl AstDebug for EnumDefinition {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        let EnumDefinition {
+            doc: _doc,
+            attributes,
+            loc: _loc,
+            abilities,
+            name,
+            type_parameters,
+            variants,
+        } = self;
+        attributes.ast_debug(w);
+
+        w.list(abilities, " ", |w, ab_mod| {
+            ab_mod.ast_debug(w);
+            false
+        });
+
+        w.write(&format!("enum {}", name));
+        type_parameters.ast_debug(w);
+        w.block(|w| w.comma(variants, |w, variant| variant.ast_debug(w)));
+    }
+}
+
+impl AstDebug for VariantDefinition {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        let VariantDefinition {
+            loc: _loc,
+            name,
+            fields,
+        } = self;
+        w.write(&format!("{}", name));
+        match fields {
+            VariantFields::Defined(fields) => w.block(|w| {
+                w.comma(fields, |w, (f, st)| {
+                    w.write(&format!("{}: ", f));
+                    st.ast_debug(w);
+                });
+            }),
+            VariantFields::Positional(tys) => {
+                w.write("(");
+                w.comma(tys, |w, ty| ty.ast_debug(w));
+                w.write(")");
+            }
+            VariantFields::Empty => (),
         }
     }
 }
@@ -1470,10 +1649,12 @@ impl AstDebug for PragmaProperty_ {
 impl AstDebug for Function {
     fn ast_debug(&self, w: &mut AstWriter) {
         let Function {
+            doc: _doc,
             attributes,
             loc: _loc,
             visibility,
             entry,
+            macro_,
             signature,
             acquires,
             name,
@@ -1484,6 +1665,9 @@ impl AstDebug for Function {
         if entry.is_some() {
             w.write(&format!("{} ", ENTRY_MODIFIER));
         }
+        if macro_.is_some() {
+            w.write(&format!("{} ", MACRO_MODIFIER));
+        }
         if let FunctionBody_::Native = &body.value {
             w.write("native ");
         }
@@ -1516,7 +1700,10 @@ impl AstDebug for FunctionSignature {
         } = self;
         type_parameters.ast_debug(w);
         w.write("(");
-        w.comma(parameters, |w, (v, st)| {
+        w.comma(parameters, |w, (mut_, v, st)| {
+            if mut_.is_some() {
+                w.write("mut ");
+            }
             w.write(&format!("{}: ", v));
             st.ast_debug(w);
         });
@@ -1529,6 +1716,7 @@ impl AstDebug for FunctionSignature {
 impl AstDebug for Constant {
     fn ast_debug(&self, w: &mut AstWriter) {
         let Constant {
+            doc: _doc,
             attributes,
             loc: _loc,
             name,
@@ -1763,16 +1951,30 @@ impl AstDebug for Exp_ {
                     f.ast_debug(w);
                 }
             }
-            E::While(b, e) => {
+            E::While(name, b, e) => {
+                if let Some(name) = name {
+                    w.write(&format!("'{name}: "));
+                }
                 w.write("while (");
                 b.ast_debug(w);
                 w.write(")");
                 e.ast_debug(w);
             }
-            E::Loop(e) => {
+            E::Loop(name, e) => {
+                if let Some(name) = name {
+                    w.write(&format!("'{name}: "));
+                }
                 w.write("loop ");
                 e.ast_debug(w);
             }
+            E::Match(subject, sp!(_, arms)) => {
+                w.write("match (");
+                subject.ast_debug(w);
+                w.write(") ");
+                w.block(|w| {
+                    w.comma(arms, |w, arm| arm.ast_debug(w));
+                });
+            }
             E::Block(seq) => w.block(|w| seq.ast_debug(w)),
             E::Lambda(sp!(_, bs), e) => {
                 w.write("fun ");
@@ -1813,8 +2015,22 @@ impl AstDebug for Exp_ {
                 w.write("abort ");
                 e.ast_debug(w);
             }
-            E::Break => w.write("break"),
-            E::Continue => w.write("continue"),
+            E::Break(name, e) => {
+                w.write("break");
+                if let Some(name) = name {
+                    w.write(&format!(" '{name}"));
+                }
+                if let Some(e) = e {
+                    w.write(" ");
+                    e.ast_debug(w);
+                }
+            }
+            E::Continue(name) => {
+                w.write("continue");
+                if let Some(name) = name {
+                    w.write(&format!(" '{name}"));
+                }
+            }
             E::Dereference(e) => {
                 w.write("*");
                 e.ast_debug(w)
@@ -1842,6 +2058,18 @@ impl AstDebug for Exp_ {
                 e.ast_debug(w);
                 w.write(&format!(".{}", n));
             }
+            E::DotCall(e, n, tys_opt, sp!(_, rhs)) => {
+                e.ast_debug(w);
+                w.write(&format!(".{}", n));
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(rhs, |w, e| e.ast_debug(w));
+                w.write(")");
+            }
             E::Cast(e, ty) => {
                 w.write("(");
                 e.ast_debug(w);
@@ -1925,6 +2153,7 @@ impl AstDebug for Value_ {
             V::Bool(b) => format!("{}", b),
             V::HexString(s) => format!("x\"{}\"", s),
             V::ByteString(s) => format!("b\"{}\"", s),
+            V::String(s) => format!("\"{}\"", s),
         })
     }
 }
@@ -1956,14 +2185,77 @@ impl AstDebug for Bind_ {
     fn ast_debug(&self, w: &mut AstWriter) {
         use Bind_ as B;
         match self {
-            B::Var(v) => w.write(&format!("{}", v)),
+            B::Var(mut_, v) => {
+                if mut_.is_some() {
+                    w.write("mut ");
+                }
+                w.write(&format!("{}", v))
+            }
             B::Unpack(ma, tys_opt, fields) => {
+                ma.ast_debug(w);
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                fields.ast_debug(w);
+            }
+        }
+    }
+}
+
+impl AstDebug for MatchPattern_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        use MatchPattern_ as P;
+        match self {
+            P::PositionalConstructor(ma, tys_opt, sp!(_, patterns)) => {
+                ma.ast_debug(w);
+                if let Some(ss) = tys_opt {
+                    w.write("<");
+                    ss.ast_debug(w);
+                    w.write(">");
+                }
+                w.write("(");
+                w.comma(patterns, |w, p| p.ast_debug(w));
+                w.write(")");
+            }
+            P::FieldConstructor(ma, tys_opt, sp!(_, fields)) => {
                 ma.ast_debug(w);
                 if let Some(ss) = tys_opt {
                     w.write("<");
                     ss.ast_debug(w);
                     w.write(">");
                 }
+                w.write("{");
+                w.comma(fields, |w, (f, p)| {
+                    w.write(&format!("{}: ", f));
+                    p.ast_debug(w);
+                });
+                w.write("}");
+            }
+            P::Binder(v) => w.write(&format!("{}", v)),
+            P::Literal(v) => v.ast_debug(w),
+        }
+    }
+}
+
+impl AstDebug for MatchArm_ {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        self.pattern.ast_debug(w);
+        if let Some(guard) = &self.guard {
+            w.write(" if (");
+            guard.ast_debug(w);
+            w.write(")");
+        }
+        w.write(" => ");
+        self.rhs.ast_debug(w);
+    }
+}
+
+impl AstDebug for FieldBindings {
+    fn ast_debug(&self, w: &mut AstWriter) {
+        match self {
+            FieldBindings::Named(fields) => {
                 w.write("{");
                 w.comma(fields, |w, (f, b)| {
                     w.write(&format!("{}: ", f));
@@ -1971,6 +2263,11 @@ impl AstDebug for Bind_ {
                 });
                 w.write("}");
             }
+            FieldBindings::Positional(binds) => {
+                w.write("(");
+                w.comma(binds, |w, b| b.ast_debug(w));
+                w.write(")");
+            }
         }
     }
 }
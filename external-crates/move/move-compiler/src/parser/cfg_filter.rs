@@ -0,0 +1,127 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_ir_types::location::*;
+use move_symbol_pool::Symbol;
+
+use crate::{
+    diag,
+    parser::{
+        ast as P,
+        filter::{filter_program, FilterContext},
+    },
+    shared::{
+        known_attributes::{CfgAttribute, KnownAttribute},
+        CompilationEnv, Name,
+    },
+};
+
+struct Context<'env> {
+    env: &'env mut CompilationEnv,
+    current_package: Option<Symbol>,
+}
+
+impl<'env> Context<'env> {
+    fn new(compilation_env: &'env mut CompilationEnv) -> Self {
+        Self {
+            env: compilation_env,
+            current_package: None,
+        }
+    }
+
+    /// Whether `feature` is enabled for the current package. Features the package does not
+    /// declare are reported, and considered disabled.
+    fn is_enabled(&mut self, feature: Name) -> bool {
+        let features = &self.env.package_config(self.current_package).features;
+        if let Some(enabled) = features.get(&feature.value) {
+            return *enabled;
+        }
+        let msg = format!("Unknown feature '{}'", feature);
+        let note = if features.is_empty() {
+            "The package does not declare any features".to_string()
+        } else {
+            let declared = features
+                .keys()
+                .map(|f| format!("'{}'", f))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("The package declares the features: {}", declared)
+        };
+        let mut diag = diag!(Attributes::UnknownFeature, (feature.loc, msg));
+        diag.add_note(note);
+        self.env.add_diag(diag);
+        false
+    }
+}
+
+impl FilterContext for Context<'_> {
+    fn set_current_package(&mut self, package: Option<Symbol>) {
+        self.current_package = package;
+    }
+
+    fn should_remove_by_attributes(
+        &mut self,
+        attrs: &[P::Attributes],
+        _is_source_def: bool,
+    ) -> bool {
+        let features: Vec<_> = attrs
+            .iter()
+            .flat_map(|attrs| &attrs.value)
+            .filter_map(|attr| cfg_feature(self.env, attr))
+            .collect();
+        // every feature is checked, so that all unknown features are reported
+        let mut remove = false;
+        for feature in features {
+            remove |= !self.is_enabled(feature);
+        }
+        remove
+    }
+}
+
+//***************************************************************************
+// Filtering of conditionally compiled module members
+//***************************************************************************
+
+// This filters out all AST elements annotated with `#[cfg(feature = "<name>")]` from `prog`
+// if the feature is not enabled for the package they belong to.
+pub fn program(compilation_env: &mut CompilationEnv, prog: P::Program) -> P::Program {
+    let mut context = Context::new(compilation_env);
+    filter_program(&mut context, prog)
+}
+
+// The feature that a `cfg` attribute makes the annotated element conditional on. Returns `None`
+// for other attributes, and for malformed `cfg` attributes, which are reported.
+fn cfg_feature(env: &mut CompilationEnv, sp!(loc, attr): &P::Attribute) -> Option<Name> {
+    use P::{AttributeValue_ as PV, Attribute_ as PA, Value_ as V};
+    if !matches!(
+        KnownAttribute::resolve(attr.attribute_name().value),
+        Some(KnownAttribute::Cfg(CfgAttribute::Cfg))
+    ) {
+        return None;
+    }
+    let feature = match attr {
+        PA::Parameterized(_, sp!(_, inner)) if inner.len() == 1 => match &inner[0].value {
+            PA::Assigned(n, value) if n.value.as_str() == CfgAttribute::FEATURE => {
+                match &value.value {
+                    PV::Value(sp!(vloc, V::String(s) | V::ByteString(s))) => Some(sp(*vloc, *s)),
+                    _ => {
+                        let msg = "Expected the name of the feature as a string literal";
+                        env.add_diag(diag!(Attributes::InvalidValue, (value.loc, msg)));
+                        return None;
+                    }
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+    if feature.is_none() {
+        let msg = format!(
+            "Expected a feature, e.g. '#[{}({} = \"<name>\")]'",
+            CfgAttribute::CFG,
+            CfgAttribute::FEATURE
+        );
+        env.add_diag(diag!(Attributes::InvalidUsage, (*loc, msg)));
+    }
+    feature
+}
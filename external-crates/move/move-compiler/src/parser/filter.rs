@@ -3,11 +3,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use move_ir_types::location::sp;
+use move_symbol_pool::Symbol;
 
 use crate::parser::ast as P;
 
 /// A trait that decides whether to include a parsed element in the compilation
 pub trait FilterContext {
+    /// Called before filtering the definitions of each package
+    fn set_current_package(&mut self, _package: Option<Symbol>) {}
+
     /// Attribute-based node removal
     fn should_remove_by_attributes(
         &mut self,
@@ -77,6 +81,18 @@ pub trait FilterContext {
         }
     }
 
+    fn filter_map_enum(
+        &mut self,
+        enum_def: P::EnumDefinition,
+        is_source_def: bool,
+    ) -> Option<P::EnumDefinition> {
+        if self.should_remove_by_attributes(&enum_def.attributes, is_source_def) {
+            None
+        } else {
+            Some(enum_def)
+        }
+    }
+
     fn filter_map_spec(
         &mut self,
         spec: P::SpecBlock_,
@@ -138,6 +154,7 @@ pub fn filter_program<T: FilterContext>(context: &mut T, prog: P::Program) -> P:
                  named_address_map,
                  def,
              }| {
+                context.set_current_package(package);
                 Some(P::PackageDefinition {
                     package,
                     named_address_map,
@@ -155,6 +172,7 @@ pub fn filter_program<T: FilterContext>(context: &mut T, prog: P::Program) -> P:
                  named_address_map,
                  def,
              }| {
+                context.set_current_package(package);
                 Some(P::PackageDefinition {
                     package,
                     named_address_map,
@@ -271,6 +289,7 @@ fn filter_module<T: FilterContext>(
     let module_def = context.filter_map_module(module_def, is_source_def)?;
 
     let P::ModuleDefinition {
+        doc,
         attributes,
         loc,
         address,
@@ -285,6 +304,7 @@ fn filter_module<T: FilterContext>(
         .collect();
 
     Some(P::ModuleDefinition {
+        doc,
         attributes,
         loc,
         address,
@@ -308,6 +328,9 @@ fn filter_module_member<T: FilterContext>(
         PM::Struct(struct_def) => context
             .filter_map_struct(struct_def, is_source_def)
             .map(PM::Struct),
+        PM::Enum(enum_def) => context
+            .filter_map_enum(enum_def, is_source_def)
+            .map(PM::Enum),
         PM::Spec(sp!(spec_loc, spec)) => context
             .filter_map_spec(spec, is_source_def)
             .map(|new_spec| PM::Spec(sp(spec_loc, new_spec))),
@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    diag, diagnostics::Diagnostic, parser::syntax::make_loc, shared::CompilationEnv,
+    diag,
+    diagnostics::Diagnostic,
+    parser::{ast::DocComment, syntax::make_loc},
+    shared::CompilationEnv,
     FileCommentMap, MatchedFileCommentMap,
 };
 use move_command_line_common::files::FileHash;
@@ -16,7 +19,9 @@ pub enum Tok {
     NumValue,
     NumTypedValue,
     ByteStringValue,
+    StringValue,
     Identifier,
+    BlockLabel,
     Exclaim,
     ExclaimEqual,
     Percent,
@@ -43,6 +48,7 @@ pub enum Tok {
     Equal,
     EqualEqual,
     EqualEqualGreater,
+    EqualGreater,
     LessEqualEqualGreater,
     Greater,
     GreaterEqual,
@@ -90,7 +96,9 @@ impl fmt::Display for Tok {
             NumValue => "[Num]",
             NumTypedValue => "[NumTyped]",
             ByteStringValue => "[ByteString]",
+            StringValue => "[String]",
             Identifier => "[Identifier]",
+            BlockLabel => "[BlockLabel]",
             Exclaim => "!",
             ExclaimEqual => "!=",
             Percent => "%",
@@ -117,6 +125,7 @@ impl fmt::Display for Tok {
             Equal => "=",
             EqualEqual => "==",
             EqualEqualGreater => "==>",
+            EqualGreater => "=>",
             LessEqualEqualGreater => "<==>",
             Greater => ">",
             GreaterEqual => ">=",
@@ -347,9 +356,10 @@ impl<'input> Lexer<'input> {
     // for stale doc comments will be produced.
     //
     // Calling this function during parsing effectively marks a valid point for documentation
-    // comments. The documentation comments are not stored in the AST, but can be retrieved by
-    // using the start position of an item as an index into `matched_doc_comments`.
-    pub fn match_doc_comments(&mut self) {
+    // comments. The matched comments are returned, to be stored in the AST for items that keep
+    // them, and can also be retrieved by using the start position of an item as an index into
+    // `matched_doc_comments`.
+    pub fn match_doc_comments(&mut self) -> DocComment {
         let start = self.previous_end_loc() as u32;
         let end = self.cur_start as u32;
         let mut matched = vec![];
@@ -362,10 +372,15 @@ impl<'input> Lexer<'input> {
             })
             .collect::<Vec<String>>()
             .join("\n");
-        for span in matched {
-            self.doc_comments.remove(&span);
+        for span in &matched {
+            self.doc_comments.remove(span);
+        }
+        self.matched_doc_comments.insert(end, merged.clone());
+        if matched.is_empty() {
+            None
+        } else {
+            Some(merged)
         }
-        self.matched_doc_comments.insert(end, merged);
     }
 
     // At the end of parsing, checks whether there are any unmatched documentation comments,
@@ -456,6 +471,37 @@ fn find_token(
                 (get_name_token(&text[..len]), len)
             }
         }
+        '\'' => {
+            // a label, e.g. 'outer
+            match text[1..].chars().next() {
+                Some('A'..='Z' | 'a'..='z' | '_') => {
+                    (Tok::BlockLabel, 1 + get_name_len(&text[1..]))
+                }
+                _ => {
+                    let loc = make_loc(file_hash, start_offset, start_offset);
+                    return Err(Box::new(diag!(
+                        Syntax::InvalidCharacter,
+                        (
+                            loc,
+                            "Invalid character: '''. Expected a label name after it"
+                        )
+                    )));
+                }
+            }
+        }
+        '"' => {
+            let line = &text.lines().next().unwrap()[1..];
+            match get_string_len(line) {
+                Some(last_quote) => (Tok::StringValue, 1 + last_quote + 1),
+                None => {
+                    let loc = make_loc(file_hash, start_offset, start_offset + line.len() + 1);
+                    return Err(Box::new(diag!(
+                        StringLiterals::Unterminated,
+                        (loc, "Missing closing quote (\") after string")
+                    )));
+                }
+            }
+        }
         '&' => {
             if text.starts_with("&mut ") {
                 (Tok::AmpMut, 5)
@@ -477,6 +523,8 @@ fn find_token(
                 (Tok::EqualEqualGreater, 3)
             } else if text.starts_with("==") {
                 (Tok::EqualEqual, 2)
+            } else if text.starts_with("=>") {
+                (Tok::EqualGreater, 2)
             } else {
                 (Tok::Equal, 1)
             }
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod ast;
+pub(crate) mod cfg_filter;
 pub mod comments;
 pub(crate) mod filter;
 pub mod keywords;
@@ -101,11 +102,17 @@ pub(crate) fn parse_program(
         diags.extend(ds);
     }
 
-    // TODO fix this so it works likes other passes and the handling of errors is done outside of
-    // this function
-    let env_result = compilation_env.check_diags_at_or_above_severity(Severity::BlockingError);
-    if let Err(env_diags) = env_result {
-        diags.extend(env_diags)
+    if compilation_env.flags().ide_mode() {
+        // the files with errors are skipped so that the others are still compiled, and the
+        // diagnostics are reported along with the ones of the later passes
+        compilation_env.add_diags(std::mem::take(&mut diags));
+    } else {
+        // TODO fix this so it works likes other passes and the handling of errors is done outside
+        // of this function
+        let env_result = compilation_env.check_diags_at_or_above_severity(Severity::BlockingError);
+        if let Err(env_diags) = env_result {
+            diags.extend(env_diags)
+        }
     }
 
     let res = if diags.is_empty() {
@@ -348,6 +348,25 @@ fn parse_var(context: &mut Context) -> Result<Var, Box<Diagnostic>> {
     Ok(Var(parse_identifier(context)?))
 }
 
+// Parse an optional "mut" modifier of a local or parameter. "mut" is only a keyword when it is
+// followed by the name of the local:
+//      Mut = "mut"?
+fn parse_mut_opt(context: &mut Context) -> Result<Mutability, Box<Diagnostic>> {
+    const MUT_IDENT: &str = "mut";
+    if context.tokens.peek() != Tok::Identifier
+        || context.tokens.content() != MUT_IDENT
+        || context.tokens.lookahead()? != Tok::Identifier
+    {
+        return Ok(None);
+    }
+    let loc = current_token_loc(context.tokens);
+    consume_identifier(context.tokens, MUT_IDENT)?;
+    context
+        .env
+        .check_feature(&FeatureGate::LetMut, context.package_name, loc);
+    Ok(Some(loc))
+}
+
 // Parse a field name:
 //      Field = <Identifier>
 fn parse_field(context: &mut Context) -> Result<Field, Box<Diagnostic>> {
@@ -438,6 +457,7 @@ struct Modifiers {
     visibility: Option<Visibility>,
     entry: Option<Loc>,
     native: Option<Loc>,
+    macro_: Option<Loc>,
 }
 
 impl Modifiers {
@@ -446,14 +466,15 @@ impl Modifiers {
             visibility: None,
             entry: None,
             native: None,
+            macro_: None,
         }
     }
 }
 
-// Parse module member modifiers: visiblility and native.
+// Parse module member modifiers: visiblility, native, entry, and macro.
 // The modifiers are also used for script-functions
 //      ModuleMemberModifiers = <ModuleMemberModifier>*
-//      ModuleMemberModifier = <Visibility> | "native"
+//      ModuleMemberModifier = <Visibility> | "native" | "entry" | "macro"
 // ModuleMemberModifiers checks for uniqueness, meaning each individual ModuleMemberModifier can
 // appear only once
 fn parse_module_member_modifiers(context: &mut Context) -> Result<Modifiers, Box<Diagnostic>> {
@@ -501,6 +522,23 @@ fn parse_module_member_modifiers(context: &mut Context) -> Result<Modifiers, Box
                 }
                 mods.entry = Some(loc)
             }
+            Tok::Identifier if context.tokens.content() == MACRO_MODIFIER => {
+                let loc = current_token_loc(context.tokens);
+                context.tokens.advance()?;
+                context
+                    .env
+                    .check_feature(&FeatureGate::MacroFuns, context.package_name, loc);
+                if let Some(prev_loc) = mods.macro_ {
+                    let msg = format!("Duplicate '{}' modifier", MACRO_MODIFIER);
+                    let prev_msg = format!("'{}' modifier previously given here", MACRO_MODIFIER);
+                    context.env.add_diag(diag!(
+                        Declarations::DuplicateItem,
+                        (loc, msg),
+                        (prev_loc, prev_msg)
+                    ))
+                }
+                mods.macro_ = Some(loc)
+            }
             _ => break,
         }
     }
@@ -546,6 +584,14 @@ fn parse_visibility(context: &mut Context) -> Result<Visibility, Box<Diagnostic>
 //          <Value>
 //          | <NameAccessChain>
 fn parse_attribute_value(context: &mut Context) -> Result<AttributeValue, Box<Diagnostic>> {
+    // Strings in attributes are never lowered to code, so they are allowed in every edition
+    if context.tokens.peek() == Tok::StringValue {
+        let start_loc = context.tokens.start_loc();
+        let value_ = parse_string_content(context)?;
+        let end_loc = context.tokens.previous_end_loc();
+        let v = spanned(context.tokens.file_hash(), start_loc, end_loc, value_);
+        return Ok(sp(v.loc, AttributeValue_::Value(v)));
+    }
     if let Some(v) = maybe_parse_value(context)? {
         return Ok(sp(v.loc, AttributeValue_::Value(v)));
     }
@@ -637,31 +683,43 @@ fn parse_exp_field(context: &mut Context) -> Result<(Field, Exp), Box<Diagnostic
 }
 
 // Parse a field name optionally followed by a colon and a binding:
-//      BindField = <Field> <":" <Bind>>?
+//      BindField = <Mut> <Field> | <Field> <":" <Bind>>?
 //
 // If the binding is not specified, the default is to use a variable
 // with the same name as the field.
 fn parse_bind_field(context: &mut Context) -> Result<(Field, Bind), Box<Diagnostic>> {
+    let mut_ = parse_mut_opt(context)?;
     let f = parse_field(context)?;
-    let arg = if match_token(context.tokens, Tok::Colon)? {
+    let arg = if mut_.is_none() && match_token(context.tokens, Tok::Colon)? {
         parse_bind(context)?
     } else {
         let v = Var(f.0);
-        sp(v.loc(), Bind_::Var(v))
+        sp(v.loc(), Bind_::Var(mut_, v))
     };
     Ok((f, arg))
 }
 
 // Parse a binding:
 //      Bind =
-//          <Var>
+//          <Mut> <Var>
 //          | <NameAccessChain> <OptionalTypeArgs> "{" Comma<BindField> "}"
+//          | <NameAccessChain> <OptionalTypeArgs> "(" Comma<Bind> ")"
 fn parse_bind(context: &mut Context) -> Result<Bind, Box<Diagnostic>> {
     let start_loc = context.tokens.start_loc();
+    let mut_ = parse_mut_opt(context)?;
+    if mut_.is_some() {
+        let v = Bind_::Var(mut_, parse_var(context)?);
+        let end_loc = context.tokens.previous_end_loc();
+        return Ok(spanned(context.tokens.file_hash(), start_loc, end_loc, v));
+    }
     if context.tokens.peek() == Tok::Identifier {
         let next_tok = context.tokens.lookahead()?;
-        if next_tok != Tok::LBrace && next_tok != Tok::Less && next_tok != Tok::ColonColon {
-            let v = Bind_::Var(parse_var(context)?);
+        if next_tok != Tok::LBrace
+            && next_tok != Tok::LParen
+            && next_tok != Tok::Less
+            && next_tok != Tok::ColonColon
+        {
+            let v = Bind_::Var(None, parse_var(context)?);
             let end_loc = context.tokens.previous_end_loc();
             return Ok(spanned(context.tokens.file_hash(), start_loc, end_loc, v));
         }
@@ -671,13 +729,27 @@ fn parse_bind(context: &mut Context) -> Result<Bind, Box<Diagnostic>> {
     // it is possible that the user intention was to use a variable name.
     let ty = parse_name_access_chain(context, || "a variable or struct name")?;
     let ty_args = parse_optional_type_args(context)?;
-    let args = parse_comma_list(
-        context,
-        Tok::LBrace,
-        Tok::RBrace,
-        parse_bind_field,
-        "a field binding",
-    )?;
+    let args = if context.tokens.peek() == Tok::LParen {
+        let loc = current_token_loc(context.tokens);
+        context
+            .env
+            .check_feature(&FeatureGate::PositionalFields, context.package_name, loc);
+        FieldBindings::Positional(parse_comma_list(
+            context,
+            Tok::LParen,
+            Tok::RParen,
+            parse_bind,
+            "a binding",
+        )?)
+    } else {
+        FieldBindings::Named(parse_comma_list(
+            context,
+            Tok::LBrace,
+            Tok::RBrace,
+            parse_bind_field,
+            "a field binding",
+        )?)
+    };
     let end_loc = context.tokens.previous_end_loc();
     let unpack = Bind_::Unpack(Box::new(ty), ty_args, args);
     Ok(spanned(
@@ -753,6 +825,27 @@ fn parse_byte_string(context: &mut Context) -> Result<Value_, Box<Diagnostic>> {
     Ok(value_)
 }
 
+// Parse a string:
+//      String = <StringValue>
+fn parse_string(context: &mut Context) -> Result<Value_, Box<Diagnostic>> {
+    if context.tokens.peek() != Tok::StringValue {
+        return Err(unexpected_token_error(context.tokens, "a string value"));
+    }
+    let loc = current_token_loc(context.tokens);
+    context
+        .env
+        .check_feature(&FeatureGate::StringLiterals, context.package_name, loc);
+    parse_string_content(context)
+}
+
+// Parse the string at the current token, without checking that string literals are supported
+fn parse_string_content(context: &mut Context) -> Result<Value_, Box<Diagnostic>> {
+    let s = context.tokens.content();
+    let text = Symbol::from(&s[1..s.len() - 1]);
+    context.tokens.advance()?;
+    Ok(Value_::String(text))
+}
+
 // Parse a value:
 //      Value =
 //          "@" <LeadingAccessName>
@@ -761,6 +854,7 @@ fn parse_byte_string(context: &mut Context) -> Result<Value_, Box<Diagnostic>> {
 //          | <Number>
 //          | <NumberTyped>
 //          | <ByteString>
+//          | <String>
 fn maybe_parse_value(context: &mut Context) -> Result<Option<Value>, Box<Diagnostic>> {
     let start_loc = context.tokens.start_loc();
     let val = match context.tokens.peek() {
@@ -793,6 +887,7 @@ fn maybe_parse_value(context: &mut Context) -> Result<Option<Value>, Box<Diagnos
         }
 
         Tok::ByteStringValue => parse_byte_string(context)?,
+        Tok::StringValue => parse_string(context)?,
         _ => return Ok(None),
     };
     let end_loc = context.tokens.previous_end_loc();
@@ -889,8 +984,8 @@ fn parse_sequence(context: &mut Context) -> Result<Sequence, Box<Diagnostic>> {
 
 // Parse an expression term:
 //      Term =
-//          "break"
-//          | "continue"
+//          "break" <BlockLabel>? <Exp>?
+//          | "continue" <BlockLabel>?
 //          | "vector" ('<' Comma<Type> ">")? "[" Comma<Exp> "]"
 //          | <Value>
 //          | "(" Comma<Exp> ")"
@@ -900,16 +995,18 @@ fn parse_sequence(context: &mut Context) -> Result<Sequence, Box<Diagnostic>> {
 //          | "if" "(" <Exp> ")" <Exp> "else" "{" <Exp> "}"
 //          | "if" "(" <Exp> ")" "{" <Exp> "}"
 //          | "if" "(" <Exp> ")" <Exp> ("else" <Exp>)?
-//          | "while" "(" <Exp> ")" "{" <Exp> "}"
-//          | "while" "(" <Exp> ")" <Exp> (SpecBlock)?
-//          | "loop" <Exp>
-//          | "loop" "{" <Exp> "}"
+//          | (<BlockLabel> ":")? "while" "(" <Exp> ")" "{" <Exp> "}"
+//          | (<BlockLabel> ":")? "while" "(" <Exp> ")" <Exp> (SpecBlock)?
+//          | (<BlockLabel> ":")? "loop" <Exp>
+//          | (<BlockLabel> ":")? "loop" "{" <Exp> "}"
+//          | "match" "(" <Exp> ")" "{" Comma<MatchArm> "}"
 //          | "return" "{" <Exp> "}"
 //          | "return" <Exp>?
 //          | "abort" "{" <Exp> "}"
 //          | "abort" <Exp>
 fn parse_term(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
     const VECTOR_IDENT: &str = "vector";
+    const MATCH_IDENT: &str = "match";
 
     let start_loc = context.tokens.start_loc();
     let term = match context.tokens.peek() {
@@ -923,17 +1020,22 @@ fn parse_term(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
         }
         Tok::Break => {
             context.tokens.advance()?;
-            if at_start_of_exp(context) {
-                let mut diag = unexpected_token_error(context.tokens, "the end of an expression");
-                diag.add_note("'break' with a value is not yet supported");
-                return Err(diag);
-            }
-            Exp_::Break
+            let label = parse_block_label_opt(context)?;
+            let value = if at_start_of_exp(context) {
+                let e = parse_exp(context)?;
+                context
+                    .env
+                    .check_feature(&FeatureGate::BlockLabels, context.package_name, e.loc);
+                Some(Box::new(e))
+            } else {
+                None
+            };
+            Exp_::Break(label, value)
         }
 
         Tok::Continue => {
             context.tokens.advance()?;
-            Exp_::Continue
+            Exp_::Continue(parse_block_label_opt(context)?)
         }
 
         Tok::Identifier
@@ -967,6 +1069,36 @@ fn parse_term(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
             Exp_::Vector(vec_loc, tys_opt, args)
         }
 
+        // 'match' is only a keyword in editions that support it, otherwise `match(e)` is a call
+        Tok::Identifier
+            if context.tokens.content() == MATCH_IDENT
+                && matches!(context.tokens.lookahead(), Ok(Tok::LParen))
+                && context
+                    .env
+                    .supports_feature(context.package_name, &FeatureGate::Match) =>
+        {
+            consume_identifier(context.tokens, MATCH_IDENT)?;
+            consume_token(context.tokens, Tok::LParen)?;
+            let subject = parse_exp(context)?;
+            consume_token(context.tokens, Tok::RParen)?;
+            let arms_start_loc = context.tokens.start_loc();
+            let arms_ = parse_comma_list(
+                context,
+                Tok::LBrace,
+                Tok::RBrace,
+                parse_match_arm,
+                "a match arm",
+            )?;
+            let arms_end_loc = context.tokens.previous_end_loc();
+            let arms = spanned(
+                context.tokens.file_hash(),
+                arms_start_loc,
+                arms_end_loc,
+                arms_,
+            );
+            Exp_::Match(Box::new(subject), arms)
+        }
+
         Tok::Identifier => parse_name_exp(context)?,
 
         Tok::NumValue => {
@@ -978,9 +1110,12 @@ fn parse_term(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
             }
         }
 
-        Tok::AtSign | Tok::True | Tok::False | Tok::NumTypedValue | Tok::ByteStringValue => {
-            Exp_::Value(parse_value(context)?)
-        }
+        Tok::AtSign
+        | Tok::True
+        | Tok::False
+        | Tok::NumTypedValue
+        | Tok::ByteStringValue
+        | Tok::StringValue => Exp_::Value(parse_value(context)?),
 
         // "(" Comma<Exp> ")"
         // "(" <Exp> ":" <Type> ")"
@@ -1051,10 +1186,41 @@ fn parse_term(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
 fn is_control_exp(tok: Tok) -> bool {
     matches!(
         tok,
-        Tok::If | Tok::While | Tok::Loop | Tok::Return | Tok::Abort
+        Tok::If | Tok::While | Tok::Loop | Tok::Return | Tok::Abort | Tok::BlockLabel
     )
 }
 
+// Parse a label:
+//      BlockLabel = "'" <Identifier>
+fn parse_block_label(context: &mut Context) -> Result<BlockLabel, Box<Diagnostic>> {
+    if context.tokens.peek() != Tok::BlockLabel {
+        return Err(unexpected_token_error(context.tokens, "a label"));
+    }
+    let start_loc = context.tokens.start_loc();
+    // drop the leading '
+    let name = context.tokens.content()[1..].into();
+    context.tokens.advance()?;
+    let end_loc = context.tokens.previous_end_loc();
+    let label = BlockLabel(spanned(
+        context.tokens.file_hash(),
+        start_loc,
+        end_loc,
+        name,
+    ));
+    context
+        .env
+        .check_feature(&FeatureGate::BlockLabels, context.package_name, label.loc());
+    Ok(label)
+}
+
+fn parse_block_label_opt(context: &mut Context) -> Result<Option<BlockLabel>, Box<Diagnostic>> {
+    if context.tokens.peek() == Tok::BlockLabel {
+        Ok(Some(parse_block_label(context)?))
+    } else {
+        Ok(None)
+    }
+}
+
 // if there is a block, only parse the block, not any subsequent tokens
 // e.g.           if (cond) e1 else { e2 } + 1
 // should be,    (if (cond) e1 else { e2 }) + 1
@@ -1081,6 +1247,16 @@ fn parse_control_exp(context: &mut Context) -> Result<(Exp, bool), Box<Diagnosti
         }
     }
     let start_loc = context.tokens.start_loc();
+    let label = if context.tokens.peek() == Tok::BlockLabel {
+        let label = parse_block_label(context)?;
+        consume_token(context.tokens, Tok::Colon)?;
+        if !matches!(context.tokens.peek(), Tok::While | Tok::Loop) {
+            return Err(unexpected_token_error(context.tokens, "'while' or 'loop'"));
+        }
+        Some(label)
+    } else {
+        None
+    };
     let (exp_, ends_in_block) = match context.tokens.peek() {
         Tok::If => {
             context.tokens.advance()?;
@@ -1132,12 +1308,15 @@ fn parse_control_exp(context: &mut Context) -> Result<(Exp, bool), Box<Diagnosti
             } else {
                 (econd, ends_in_block)
             };
-            (Exp_::While(Box::new(econd), Box::new(eloop)), ends_in_block)
+            (
+                Exp_::While(label, Box::new(econd), Box::new(eloop)),
+                ends_in_block,
+            )
         }
         Tok::Loop => {
             context.tokens.advance()?;
             let (eloop, ends_in_block) = parse_exp_or_sequence(context)?;
-            (Exp_::Loop(Box::new(eloop)), ends_in_block)
+            (Exp_::Loop(label, Box::new(eloop)), ends_in_block)
         }
         Tok::Return => {
             context.tokens.advance()?;
@@ -1161,11 +1340,122 @@ fn parse_control_exp(context: &mut Context) -> Result<(Exp, bool), Box<Diagnosti
     Ok((exp, ends_in_block))
 }
 
+// Parse an arm of a match expression:
+//      MatchArm = <MatchPattern> ("if" "(" <Exp> ")")? "=>" <Exp>
+fn parse_match_arm(context: &mut Context) -> Result<MatchArm, Box<Diagnostic>> {
+    let start_loc = context.tokens.start_loc();
+    let pattern = parse_match_pattern(context)?;
+    let guard = if match_token(context.tokens, Tok::If)? {
+        consume_token(context.tokens, Tok::LParen)?;
+        let guard = parse_exp(context)?;
+        consume_token(context.tokens, Tok::RParen)?;
+        Some(Box::new(guard))
+    } else {
+        None
+    };
+    consume_token(context.tokens, Tok::EqualGreater)?;
+    let rhs = Box::new(parse_exp(context)?);
+    let end_loc = context.tokens.previous_end_loc();
+    let arm = MatchArm_ {
+        pattern,
+        guard,
+        rhs,
+    };
+    Ok(spanned(context.tokens.file_hash(), start_loc, end_loc, arm))
+}
+
+// Parse a pattern of a match arm:
+//      MatchPattern =
+//          <Var>
+//          | <Value>
+//          | <NameAccessChain> <OptionalTypeArgs> "(" Comma<MatchPattern> ")"
+//          | <NameAccessChain> <OptionalTypeArgs> "{" Comma<FieldPattern> "}"
+//          | <NameAccessChain> <OptionalTypeArgs>
+// where the last form, without fields, is only valid for a variant without fields, e.g. 'E::V'
+fn parse_match_pattern(context: &mut Context) -> Result<MatchPattern, Box<Diagnostic>> {
+    let start_loc = context.tokens.start_loc();
+    let pattern_ = if let Some(value) = maybe_parse_value(context)? {
+        MatchPattern_::Literal(value)
+    } else if context.tokens.peek() == Tok::Identifier
+        && !matches!(
+            context.tokens.lookahead()?,
+            Tok::LBrace | Tok::LParen | Tok::Less | Tok::ColonColon
+        )
+    {
+        MatchPattern_::Binder(parse_var(context)?)
+    } else {
+        let name = parse_name_access_chain(context, || "a pattern")?;
+        let tys = parse_optional_type_args(context)?;
+        let args_start_loc = context.tokens.start_loc();
+        if context.tokens.peek() == Tok::LParen {
+            let patterns = parse_comma_list(
+                context,
+                Tok::LParen,
+                Tok::RParen,
+                parse_match_pattern,
+                "a pattern",
+            )?;
+            let args_end_loc = context.tokens.previous_end_loc();
+            let patterns = spanned(
+                context.tokens.file_hash(),
+                args_start_loc,
+                args_end_loc,
+                patterns,
+            );
+            MatchPattern_::PositionalConstructor(name, tys, patterns)
+        } else if context.tokens.peek() != Tok::LBrace {
+            let fields = spanned(
+                context.tokens.file_hash(),
+                args_start_loc,
+                args_start_loc,
+                vec![],
+            );
+            MatchPattern_::FieldConstructor(name, tys, fields)
+        } else {
+            let fields = parse_comma_list(
+                context,
+                Tok::LBrace,
+                Tok::RBrace,
+                parse_field_pattern,
+                "a field pattern",
+            )?;
+            let args_end_loc = context.tokens.previous_end_loc();
+            let fields = spanned(
+                context.tokens.file_hash(),
+                args_start_loc,
+                args_end_loc,
+                fields,
+            );
+            MatchPattern_::FieldConstructor(name, tys, fields)
+        }
+    };
+    let end_loc = context.tokens.previous_end_loc();
+    Ok(spanned(
+        context.tokens.file_hash(),
+        start_loc,
+        end_loc,
+        pattern_,
+    ))
+}
+
+// Parse a field pattern:
+//      FieldPattern = <Field> (":" <MatchPattern>)?
+fn parse_field_pattern(context: &mut Context) -> Result<(Field, MatchPattern), Box<Diagnostic>> {
+    let f = parse_field(context)?;
+    let pattern = if match_token(context.tokens, Tok::Colon)? {
+        parse_match_pattern(context)?
+    } else {
+        let v = Var(f.0);
+        sp(v.loc(), MatchPattern_::Binder(v))
+    };
+    Ok((f, pattern))
+}
+
 // Parse a pack, call, or other reference to a name:
 //      NameExp =
 //          <NameAccessChain> <OptionalTypeArgs> "{" Comma<ExpField> "}"
 //          | <NameAccessChain> <OptionalTypeArgs> "(" Comma<Exp> ")"
-//          | <NameAccessChain> "!" "(" Comma<Exp> ")"
+//          | <NameAccessChain> "!" <OptionalTypeArgs> "(" Comma<Exp> ")"
 //          | <NameAccessChain> <OptionalTypeArgs>
 fn parse_name_exp(context: &mut Context) -> Result<Exp_, Box<Diagnostic>> {
     let n = parse_name_access_chain(context, || {
@@ -1180,6 +1470,7 @@ fn parse_name_exp(context: &mut Context) -> Result<Exp_, Box<Diagnostic>> {
     if context.tokens.peek() == Tok::Exclaim {
         context.tokens.advance()?;
         let is_macro = true;
+        tys = parse_optional_type_args(context)?;
         let rhs = parse_call_args(context)?;
         return Ok(Exp_::Call(n, is_macro, tys, rhs));
     }
@@ -1254,6 +1545,7 @@ fn at_start_of_exp(context: &mut Context) -> bool {
         Tok::NumValue
             | Tok::NumTypedValue
             | Tok::ByteStringValue
+            | Tok::StringValue
             | Tok::Identifier
             | Tok::AtSign
             | Tok::Copy
@@ -1273,6 +1565,7 @@ fn at_start_of_exp(context: &mut Context) -> bool {
             | Tok::Loop
             | Tok::Return
             | Tok::While
+            | Tok::BlockLabel
     )
 }
 
@@ -1475,6 +1768,7 @@ fn parse_unary_exp(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
 // Parse an expression term optionally followed by a chain of dot or index accesses:
 //      DotOrIndexChain =
 //          <DotOrIndexChain> "." <Identifier>
+//          | <DotOrIndexChain> "." <Identifier> <OptionalTypeArgs> "(" Comma<Exp> ")"
 //          | <DotOrIndexChain> "[" <Exp> "]"                      spec only
 //          | <Term>
 fn parse_dot_or_index_chain(context: &mut Context) -> Result<Exp, Box<Diagnostic>> {
@@ -1485,7 +1779,35 @@ fn parse_dot_or_index_chain(context: &mut Context) -> Result<Exp, Box<Diagnostic
             Tok::Period => {
                 context.tokens.advance()?;
                 let n = parse_identifier(context)?;
-                Exp_::Dot(Box::new(lhs), n)
+                // As for function names, a '<' directly after the method name starts its type
+                // arguments, in editions with method calls. Otherwise it is a comparison.
+                let is_call = match context.tokens.peek() {
+                    Tok::LParen => true,
+                    Tok::Less => {
+                        n.loc.end() as usize == context.tokens.start_loc()
+                            && context
+                                .env
+                                .supports_feature(context.package_name, &FeatureGate::DotCall)
+                    }
+                    _ => false,
+                };
+                if is_call {
+                    let call_loc = make_loc(
+                        context.tokens.file_hash(),
+                        start_loc,
+                        context.tokens.previous_end_loc(),
+                    );
+                    context.env.check_feature(
+                        &FeatureGate::DotCall,
+                        context.package_name,
+                        call_loc,
+                    );
+                    let tys = parse_optional_type_args(context)?;
+                    let args = parse_call_args(context)?;
+                    Exp_::DotCall(Box::new(lhs), n, tys, args)
+                } else {
+                    Exp_::Dot(Box::new(lhs), n)
+                }
             }
             Tok::LBracket => {
                 context.tokens.advance()?;
@@ -1647,7 +1969,7 @@ fn parse_quant_binding(context: &mut Context) -> Result<Spanned<(Bind, Exp)>, Bo
         context.tokens.file_hash(),
         start_loc,
         context.tokens.previous_end_loc(),
-        Bind_::Var(Var(ident)),
+        Bind_::Var(None, Var(ident)),
     );
     let range = if context.tokens.peek() == Tok::Colon {
         // This is a quantifier over the full domain of a type.
@@ -1883,6 +2205,7 @@ fn parse_struct_type_parameters(
 //          ("{" <Sequence> "}" | ";")
 //
 fn parse_function_decl(
+    doc: DocComment,
     attributes: Vec<Attributes>,
     start_loc: usize,
     modifiers: Modifiers,
@@ -1892,8 +2215,30 @@ fn parse_function_decl(
         visibility,
         mut entry,
         native,
+        macro_,
     } = modifiers;
 
+    if let Some(macro_loc) = macro_ {
+        // macros are expanded at their call sites, so they have no body of their own to run
+        let other_modifier = native
+            .map(|loc| (loc, NATIVE_MODIFIER))
+            .or_else(|| entry.map(|loc| (loc, ENTRY_MODIFIER)));
+        if let Some((loc, modifier)) = other_modifier {
+            let msg = format!(
+                "Invalid function declaration. '{}' functions cannot be '{}'",
+                MACRO_MODIFIER, modifier
+            );
+            context.env.add_diag(diag!(
+                Syntax::InvalidModifier,
+                (loc, msg),
+                (
+                    macro_loc,
+                    format!("'{}' modifier given here", MACRO_MODIFIER)
+                ),
+            ));
+        }
+    }
+
     if let Some(Visibility::Script(vloc)) = visibility {
         let msg = format!(
             "'{script}' is deprecated in favor of the '{entry}' modifier. \
@@ -1978,10 +2323,12 @@ fn parse_function_decl(
         context.tokens.previous_end_loc(),
     );
     Ok(Function {
+        doc,
         attributes,
         loc,
         visibility: visibility.unwrap_or(Visibility::Internal),
         entry,
+        macro_,
         signature,
         acquires,
         name,
@@ -1990,12 +2337,13 @@ fn parse_function_decl(
 }
 
 // Parse a function parameter:
-//      Parameter = <Var> ":" <Type>
-fn parse_parameter(context: &mut Context) -> Result<(Var, Type), Box<Diagnostic>> {
+//      Parameter = <Mut> <Var> ":" <Type>
+fn parse_parameter(context: &mut Context) -> Result<(Mutability, Var, Type), Box<Diagnostic>> {
+    let mut_ = parse_mut_opt(context)?;
     let v = parse_var(context)?;
     consume_token(context.tokens, Tok::Colon)?;
     let t = parse_type(context)?;
-    Ok((v, t))
+    Ok((mut_, v, t))
 }
 
 //**************************************************************************************************
@@ -2005,12 +2353,15 @@ fn parse_parameter(context: &mut Context) -> Result<(Var, Type), Box<Diagnostic>
 // Parse a struct definition:
 //      StructDecl =
 //          "struct" <StructDefName> ("has" <Ability> (, <Ability>)+)?
-//          ("{" Comma<FieldAnnot> "}" ("has" <Ability> (, <Ability>)+;)? | ";")
+//          ("{" Comma<FieldAnnot> "}" ("has" <Ability> (, <Ability>)+;)?
+//          | "(" Comma<Type> ")" (("has" <Ability> (, <Ability>)+)? ";")
+//          | ";")
 //      StructDefName =
 //          <Identifier> <OptionalTypeParameters>
 // Where the the two "has" statements are mutually exclusive -- a struct cannot be declared with
 // both infix and postfix ability declarations.
 fn parse_struct_decl(
+    doc: DocComment,
     attributes: Vec<Attributes>,
     start_loc: usize,
     modifiers: Modifiers,
@@ -2020,6 +2371,7 @@ fn parse_struct_decl(
         visibility,
         entry,
         native,
+        macro_,
     } = modifiers;
 
     check_struct_visibility(visibility, context);
@@ -2033,6 +2385,15 @@ fn parse_struct_decl(
             .env
             .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
     }
+    if let Some(loc) = macro_ {
+        let msg = format!(
+            "Invalid struct declaration. '{}' is used only on functions",
+            MACRO_MODIFIER
+        );
+        context
+            .env
+            .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
+    }
 
     consume_token(context.tokens, Tok::Struct)?;
 
@@ -2077,6 +2438,23 @@ fn parse_struct_decl(
             consume_token(context.tokens, Tok::Semicolon)?;
             StructFields::Native(loc)
         }
+        _ if context.tokens.peek() == Tok::LParen => {
+            let loc = current_token_loc(context.tokens);
+            context
+                .env
+                .check_feature(&FeatureGate::PositionalFields, context.package_name, loc);
+            let list = parse_comma_list(context, Tok::LParen, Tok::RParen, parse_type, "a type")?;
+            if context.tokens.peek() == Tok::Identifier && context.tokens.content() == "has" {
+                parse_postfix_ability_declarations(
+                    infix_ability_declaration_loc,
+                    &mut abilities,
+                    context,
+                )?;
+            } else {
+                consume_token(context.tokens, Tok::Semicolon)?;
+            }
+            StructFields::Positional(list)
+        }
         _ => {
             let list = parse_comma_list(
                 context,
@@ -2100,6 +2478,7 @@ fn parse_struct_decl(
         context.tokens.previous_end_loc(),
     );
     Ok(StructDefinition {
+        doc,
         attributes,
         loc,
         abilities,
@@ -2218,6 +2597,143 @@ fn check_struct_visibility(visibility: Option<Visibility>, context: &mut Context
     }
 }
 
+//**************************************************************************************************
+// Enums
+//**************************************************************************************************
+
+// 'enum' is not a keyword, so that it can still be used as a name in older editions
+const ENUM_IDENT: &str = "enum";
+
+// Parse an enum definition:
+//      EnumDecl =
+//          "enum" <StructDefName> ("has" <Ability> (, <Ability>)+)?
+//          "{" Comma<VariantDecl> "}"
+fn parse_enum_decl(
+    doc: DocComment,
+    attributes: Vec<Attributes>,
+    start_loc: usize,
+    modifiers: Modifiers,
+    context: &mut Context,
+) -> Result<EnumDefinition, Box<Diagnostic>> {
+    let Modifiers {
+        visibility,
+        entry,
+        native,
+        macro_,
+    } = modifiers;
+
+    let enum_loc = current_token_loc(context.tokens);
+    context
+        .env
+        .check_feature(&FeatureGate::Enums, context.package_name, enum_loc);
+    if !matches!(visibility, Some(Visibility::Public(_))) {
+        let (loc, vis_str) = match visibility {
+            Some(vis) => (vis.loc().unwrap(), format!("'{vis}'")),
+            None => (enum_loc, "Internal".to_owned()),
+        };
+        let msg =
+            format!("Invalid enum declaration. {vis_str} enum declarations are not yet supported");
+        let mut err = diag!(Syntax::InvalidModifier, (loc, msg));
+        err.add_note("Visibility annotations are required on enum declarations.");
+        context.env.add_diag(err);
+    }
+    for (loc, modifier) in [
+        (entry, ENTRY_MODIFIER),
+        (native, NATIVE_MODIFIER),
+        (macro_, MACRO_MODIFIER),
+    ] {
+        if let Some(loc) = loc {
+            let msg = format!("Invalid enum declaration. '{modifier}' is used only on functions");
+            context
+                .env
+                .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
+        }
+    }
+
+    consume_identifier(context.tokens, ENUM_IDENT)?;
+
+    // <StructDefName>
+    let name = EnumName(parse_identifier(context)?);
+    let type_parameters = parse_struct_type_parameters(context)?;
+
+    let abilities = if context.tokens.peek() == Tok::Identifier && context.tokens.content() == "has"
+    {
+        context.tokens.advance()?;
+        parse_list(
+            context,
+            |context| match context.tokens.peek() {
+                Tok::Comma => {
+                    context.tokens.advance()?;
+                    Ok(true)
+                }
+                Tok::LBrace => Ok(false),
+                _ => Err(unexpected_token_error(
+                    context.tokens,
+                    &format!("one of: '{}' or '{}'", Tok::Comma, Tok::LBrace),
+                )),
+            },
+            parse_ability,
+        )?
+    } else {
+        vec![]
+    };
+
+    let variants = parse_comma_list(
+        context,
+        Tok::LBrace,
+        Tok::RBrace,
+        parse_variant_decl,
+        "a variant",
+    )?;
+
+    let loc = make_loc(
+        context.tokens.file_hash(),
+        start_loc,
+        context.tokens.previous_end_loc(),
+    );
+    Ok(EnumDefinition {
+        doc,
+        attributes,
+        loc,
+        abilities,
+        name,
+        type_parameters,
+        variants,
+    })
+}
+
+// Parse a variant of an enum:
+//      VariantDecl =
+//          <Identifier> ("{" Comma<FieldAnnot> "}" | "(" Comma<Type> ")")?
+fn parse_variant_decl(context: &mut Context) -> Result<VariantDefinition, Box<Diagnostic>> {
+    context.tokens.match_doc_comments();
+    let start_loc = context.tokens.start_loc();
+    let name = VariantName(parse_identifier(context)?);
+    let fields = match context.tokens.peek() {
+        Tok::LBrace => VariantFields::Defined(parse_comma_list(
+            context,
+            Tok::LBrace,
+            Tok::RBrace,
+            parse_field_annot,
+            "a field",
+        )?),
+        Tok::LParen => VariantFields::Positional(parse_comma_list(
+            context,
+            Tok::LParen,
+            Tok::RParen,
+            parse_type,
+            "a type",
+        )?),
+        _ => VariantFields::Empty,
+    };
+    let loc = make_loc(
+        context.tokens.file_hash(),
+        start_loc,
+        context.tokens.previous_end_loc(),
+    );
+    Ok(VariantDefinition { loc, name, fields })
+}
+
 //**************************************************************************************************
 // Constants
 //**************************************************************************************************
@@ -2225,6 +2741,7 @@ fn check_struct_visibility(visibility: Option<Visibility>, context: &mut Context
 // Parse a constant:
 //      ConstantDecl = "const" <Identifier> ":" <Type> "=" <Exp> ";"
 fn parse_constant_decl(
+    doc: DocComment,
     attributes: Vec<Attributes>,
     start_loc: usize,
     modifiers: Modifiers,
@@ -2234,6 +2751,7 @@ fn parse_constant_decl(
         visibility,
         entry,
         native,
+        macro_,
     } = modifiers;
     if let Some(vis) = visibility {
         let msg = "Invalid constant declaration. Constants cannot have visibility modifiers as \
@@ -2257,6 +2775,15 @@ fn parse_constant_decl(
             .env
             .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
     }
+    if let Some(loc) = macro_ {
+        let msg = format!(
+            "Invalid constant declaration. '{}' is used only on functions",
+            MACRO_MODIFIER
+        );
+        context
+            .env
+            .add_diag(diag!(Syntax::InvalidModifier, (loc, msg)));
+    }
     consume_token(context.tokens, Tok::Const)?;
     let name = ConstantName(parse_identifier(context)?);
     consume_token(context.tokens, Tok::Colon)?;
@@ -2270,6 +2797,7 @@ fn parse_constant_decl(
         context.tokens.previous_end_loc(),
     );
     Ok(Constant {
+        doc,
         attributes,
         loc,
         signature,
@@ -2373,12 +2901,38 @@ fn parse_friend_decl(
 //      UseDecl =
 //          "use" <ModuleIdent> <UseAlias> ";" |
 //          "use" <ModuleIdent> :: <UseMember> ";" |
-//          "use" <ModuleIdent> :: "{" Comma<UseMember> "}" ";"
+//          "use" <ModuleIdent> :: "{" Comma<UseMember> "}" ";" |
+//          "use" "fun" <NameAccessChain> "as" <NameAccessChain> "." <Identifier> ";"
 fn parse_use_decl(
     attributes: Vec<Attributes>,
     context: &mut Context,
 ) -> Result<UseDecl, Box<Diagnostic>> {
+    let start_loc = context.tokens.start_loc();
     consume_token(context.tokens, Tok::Use)?;
+    if context.tokens.peek() == Tok::Fun {
+        context.tokens.advance()?;
+        let function = parse_name_access_chain(context, || "a function name")?;
+        consume_token(context.tokens, Tok::As)?;
+        let ty = parse_name_access_chain(context, || "a type name")?;
+        consume_token(context.tokens, Tok::Period)?;
+        let method = parse_identifier(context)?;
+        consume_token(context.tokens, Tok::Semicolon)?;
+        let loc = make_loc(
+            context.tokens.file_hash(),
+            start_loc,
+            context.tokens.previous_end_loc(),
+        );
+        context
+            .env
+            .check_feature(&FeatureGate::DotCall, context.package_name, loc);
+        let use_ = Use::Fun {
+            loc,
+            function: Box::new(function),
+            ty: Box::new(ty),
+            method,
+        };
+        return Ok(UseDecl { attributes, use_ });
+    }
     let ident = parse_module_ident(context)?;
     let alias_opt = parse_use_alias(context)?;
     let use_ = match (&alias_opt, context.tokens.peek()) {
@@ -2435,7 +2989,7 @@ fn parse_module(
     attributes: Vec<Attributes>,
     context: &mut Context,
 ) -> Result<ModuleDefinition, Box<Diagnostic>> {
-    context.tokens.match_doc_comments();
+    let doc = context.tokens.match_doc_comments();
     let start_loc = context.tokens.start_loc();
 
     let is_spec_module = if context.tokens.peek() == Tok::Spec {
@@ -2504,19 +3058,24 @@ fn parse_module(
                 Tok::Use => ModuleMember::Use(parse_use_decl(attributes, context)?),
                 Tok::Friend => ModuleMember::Friend(parse_friend_decl(attributes, context)?),
                 _ => {
-                    context.tokens.match_doc_comments();
+                    let doc = context.tokens.match_doc_comments();
                     let start_loc = context.tokens.start_loc();
                     let modifiers = parse_module_member_modifiers(context)?;
                     match context.tokens.peek() {
                         Tok::Const => ModuleMember::Constant(parse_constant_decl(
-                            attributes, start_loc, modifiers, context,
+                            doc, attributes, start_loc, modifiers, context,
                         )?),
                         Tok::Fun => ModuleMember::Function(parse_function_decl(
-                            attributes, start_loc, modifiers, context,
+                            doc, attributes, start_loc, modifiers, context,
                         )?),
                         Tok::Struct => ModuleMember::Struct(parse_struct_decl(
-                            attributes, start_loc, modifiers, context,
+                            doc, attributes, start_loc, modifiers, context,
                         )?),
+                        Tok::Identifier if context.tokens.content() == ENUM_IDENT => {
+                            ModuleMember::Enum(parse_enum_decl(
+                                doc, attributes, start_loc, modifiers, context,
+                            )?)
+                        }
                         _ => {
                             return Err(unexpected_token_error(
                                 context.tokens,
@@ -2543,6 +3102,7 @@ fn parse_module(
         context.tokens.previous_end_loc(),
     );
     let def = ModuleDefinition {
+        doc,
         attributes,
         loc,
         address,
@@ -2585,6 +3145,7 @@ fn parse_script(
     while context.tokens.peek() == Tok::Const {
         let start_loc = context.tokens.start_loc();
         constants.push(parse_constant_decl(
+            None,
             next_item_attributes,
             start_loc,
             Modifiers::empty(),
@@ -2593,12 +3154,17 @@ fn parse_script(
         next_item_attributes = parse_attributes(context)?;
     }
 
-    context.tokens.match_doc_comments(); // match doc comments to script function
+    let doc = context.tokens.match_doc_comments(); // match doc comments to script function
     let function_start_loc = context.tokens.start_loc();
     let modifiers = parse_module_member_modifiers(context)?;
     // don't need to check native modifier, it is checked later
-    let function =
-        parse_function_decl(next_item_attributes, function_start_loc, modifiers, context)?;
+    let function = parse_function_decl(
+        doc,
+        next_item_attributes,
+        function_start_loc,
+        modifiers,
+        context,
+    )?;
 
     let mut specs = vec![];
     while context.tokens.peek() == Tok::NumSign || context.tokens.peek() == Tok::Spec {
@@ -3255,9 +3821,12 @@ fn parse_spec_property(context: &mut Context) -> Result<PragmaProperty, Box<Diag
     let value = if context.tokens.peek() == Tok::Equal {
         context.tokens.advance()?;
         match context.tokens.peek() {
-            Tok::AtSign | Tok::True | Tok::False | Tok::NumTypedValue | Tok::ByteStringValue => {
-                Some(PragmaValue::Literal(parse_value(context)?))
-            }
+            Tok::AtSign
+            | Tok::True
+            | Tok::False
+            | Tok::NumTypedValue
+            | Tok::ByteStringValue
+            | Tok::StringValue => Some(PragmaValue::Literal(parse_value(context)?)),
             Tok::NumValue
                 if !context
                     .tokens
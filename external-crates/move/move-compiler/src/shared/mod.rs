@@ -6,12 +6,15 @@ use crate::{
     cfgir::visitor::{AbsIntVisitorObj, AbstractInterpreterVisitor},
     command_line as cli,
     diagnostics::{
-        codes::{Category, Declarations, DiagnosticsID, Severity, UnusedItem, WarningFilter},
-        Diagnostic, Diagnostics, WarningFilters,
+        codes::{
+            Category, Declarations, DiagnosticsID, NameResolution, Severity, UnusedItem,
+            WarningFilter,
+        },
+        Diagnostic, Diagnostics, DiagnosticsFormat, WarningFilters,
     },
     editions::{check_feature as edition_check_feature, Edition, FeatureGate, Flavor},
     expansion::ast as E,
-    naming::ast::ModuleDefinition,
+    naming::{ast::ModuleDefinition, cache::NamingCache, symbol_index::SymbolIndex},
     sui_mode,
     typing::visitor::{TypingVisitor, TypingVisitorObj},
 };
@@ -19,13 +22,18 @@ use clap::*;
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
 use petgraph::{algo::astar as petgraph_astar, graphmap::DiGraphMap};
+use serde::{Deserialize, Serialize};
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
     fmt,
     hash::Hash,
     rc::Rc,
-    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc, Mutex,
+    },
 };
 
 pub mod ast_debug;
@@ -152,7 +160,10 @@ pub const FILTER_UNUSED_TYPE_PARAMETER: &str = "unused_type_parameter";
 pub const FILTER_UNUSED_FUNCTION: &str = "unused_function";
 pub const FILTER_UNUSED_STRUCT_FIELD: &str = "unused_field";
 pub const FILTER_UNUSED_CONST: &str = "unused_const";
+pub const FILTER_UNUSED_FRIEND: &str = "unused_friend";
 pub const FILTER_DEAD_CODE: &str = "dead_code";
+pub const FILTER_SHADOWED_LOCAL: &str = "shadowed_local";
+pub const FILTER_DEPRECATED_USAGE: &str = "deprecated_usage";
 
 pub type NamedAddressMap = BTreeMap<Symbol, NumericalAddress>;
 
@@ -227,6 +238,10 @@ pub struct CompilationEnv {
     known_filter_names: BTreeMap<DiagnosticsID, KnownFilterInfo>,
     /// Attribute names (including externally provided ones) identifying known warning filters.
     known_filter_attributes: BTreeSet<E::AttributeName_>,
+    /// The definitions and uses of names recorded by naming, in IDE mode
+    symbol_index: Option<SymbolIndex>,
+    /// The results of naming from previous compilations of the program, if any are kept
+    naming_cache: Option<Arc<Mutex<NamingCache>>>,
     // TODO(tzakian): Remove the global counter and use this counter instead
     // pub counter: u64,
 }
@@ -328,7 +343,18 @@ impl CompilationEnv {
                 ]),
             ),
             known_code_filter!(FILTER_UNUSED_CONST, UnusedItem::Constant, filter_attr_name),
+            known_code_filter!(FILTER_UNUSED_FRIEND, UnusedItem::Friend, filter_attr_name),
             known_code_filter!(FILTER_DEAD_CODE, UnusedItem::DeadCode, filter_attr_name),
+            known_code_filter!(
+                FILTER_SHADOWED_LOCAL,
+                NameResolution::ShadowedLocal,
+                filter_attr_name
+            ),
+            known_code_filter!(
+                FILTER_DEPRECATED_USAGE,
+                NameResolution::DeprecatedUsage,
+                filter_attr_name
+            ),
         ]);
 
         let known_filter_names: BTreeMap<DiagnosticsID, KnownFilterInfo> = known_filters
@@ -360,6 +386,8 @@ impl CompilationEnv {
             known_filters,
             known_filter_names,
             known_filter_attributes: filter_attributes,
+            symbol_index: None,
+            naming_cache: None,
         }
     }
 
@@ -382,6 +410,10 @@ impl CompilationEnv {
                     diag.add_note(help)
                 }
             }
+            if filter.is_some_and(|filter| filter.is_denied(&diag)) {
+                diag.set_severity(Severity::NonblockingError);
+                diag.add_note("This warning is denied by the lint levels of the package")
+            }
             self.diags.add(diag)
         } else if !filter.unwrap().for_dependency() {
             // unwrap above is safe as the filter has been used (thus it must exist)
@@ -408,6 +440,11 @@ impl CompilationEnv {
         self.diags.len()
     }
 
+    /// The number of diagnostics reported so far, including those filtered in source code
+    pub fn count_diags_including_filtered(&self) -> usize {
+        self.diags.len() + self.diags.source_filtered_len()
+    }
+
     pub fn has_diags_at_or_above_severity(&self, threshold: Severity) -> bool {
         match self.diags.max_severity() {
             Some(max) if max >= threshold => true,
@@ -470,6 +507,52 @@ impl CompilationEnv {
         &self.known_filter_attributes
     }
 
+    /// Adds the lint levels of each package to its warning filter, failing if a level is given to
+    /// an unknown lint. Lints are resolved with every filter attribute, so custom known filters
+    /// must be added before.
+    pub fn resolve_lint_levels(&mut self) -> anyhow::Result<()> {
+        let mut package_filters = vec![];
+        let configs = self
+            .package_configs
+            .iter()
+            .map(|(package, config)| (Some(*package), config))
+            .chain([(None, &self.default_config)]);
+        for (package, config) in configs {
+            let mut filters = WarningFilters::new_for_source();
+            for (lint, level) in &config.lint_levels {
+                let lint_filters = self
+                    .known_filter_attributes
+                    .iter()
+                    .flat_map(|attr| self.filter_from_str(*lint, *attr))
+                    .collect::<BTreeSet<_>>();
+                if lint_filters.is_empty() {
+                    match package {
+                        Some(package) => anyhow::bail!(
+                            "Unknown lint '{lint}' in the lint levels of package '{package}'"
+                        ),
+                        None => anyhow::bail!("Unknown lint '{lint}' in the lint levels"),
+                    }
+                }
+                for filter in lint_filters {
+                    match level {
+                        LintLevel::Allow => filters.add(filter),
+                        LintLevel::Warn => (),
+                        LintLevel::Deny => filters.add_denied(filter),
+                    }
+                }
+            }
+            package_filters.push((package, filters));
+        }
+        for (package, filters) in package_filters {
+            let config = match package {
+                Some(package) => self.package_configs.get_mut(&package).unwrap(),
+                None => &mut self.default_config,
+            };
+            config.warning_filter.union(&filters);
+        }
+        Ok(())
+    }
+
     pub fn add_custom_known_filters(
         &mut self,
         filters: Vec<WarningFilter>,
@@ -523,6 +606,24 @@ impl CompilationEnv {
         self.visitors.clone()
     }
 
+    pub fn set_symbol_index(&mut self, symbol_index: SymbolIndex) {
+        self.symbol_index = Some(symbol_index);
+    }
+
+    /// The index of the definitions and uses of names, if naming recorded one (see
+    /// `Flags::ide_mode`)
+    pub fn take_symbol_index(&mut self) -> Option<SymbolIndex> {
+        self.symbol_index.take()
+    }
+
+    pub fn set_naming_cache(&mut self, naming_cache: Arc<Mutex<NamingCache>>) {
+        self.naming_cache = Some(naming_cache);
+    }
+
+    pub fn naming_cache(&self) -> Option<Arc<Mutex<NamingCache>>> {
+        self.naming_cache.clone()
+    }
+
     // Logs an error if the feature isn't supported. Returns `false` if the feature is not
     // supported, and `true` otherwise.
     pub fn check_feature(
@@ -611,10 +712,54 @@ pub struct Flags {
     )]
     shadow: bool,
 
+    /// If set, diagnostics are reported as JSON, one object per line, instead of being rendered
+    /// for humans
+    #[clap(
+        long = cli::JSON_ERRORS,
+    )]
+    json_errors: bool,
+
     /// Internal flag used by the model builder to maintain functions which would be otherwise
     /// included only in tests, without creating the unit test code regular tests do.
     #[clap(skip)]
     keep_testing_functions: bool,
+
+    /// Internal flag used by the IDE. Errors in one module do not stop the compilation of the
+    /// others: the program is translated up to typing, with the invalid parts replaced by errors,
+    /// and the diagnostics are only reported when compiling past typing.
+    #[clap(skip)]
+    ide_mode: bool,
+
+    /// How declaring a module of another address as a friend is treated. It is denied by default
+    /// as a policy, which the Move bytecode verifier also enforces unless configured otherwise
+    #[clap(
+        long = cli::CROSS_ADDRESS_FRIENDS,
+        value_enum,
+        default_value_t = CrossAddressFriends::Deny,
+    )]
+    cross_address_friends: CrossAddressFriends,
+
+    /// If set, a warning is reported when a `let` declares a local with the name of a local or
+    /// parameter already in scope
+    #[clap(
+        long = cli::WARN_SHADOWING,
+    )]
+    warn_shadowing: bool,
+}
+
+/// How the declaration of a module of another address as a friend is treated
+#[derive(
+    PartialEq, Eq, Clone, Copy, Debug, PartialOrd, Ord, Default, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum CrossAddressFriends {
+    /// The declaration is an error
+    #[default]
+    Deny,
+    /// The declaration is allowed, with a warning
+    Warn,
+    /// The declaration is allowed
+    Allow,
 }
 
 impl Flags {
@@ -625,6 +770,10 @@ impl Flags {
             shadow: false,
             bytecode_version: None,
             keep_testing_functions: false,
+            ide_mode: false,
+            json_errors: false,
+            cross_address_friends: CrossAddressFriends::Deny,
+            warn_shadowing: false,
         }
     }
 
@@ -635,6 +784,10 @@ impl Flags {
             shadow: false,
             bytecode_version: None,
             keep_testing_functions: false,
+            ide_mode: false,
+            json_errors: false,
+            cross_address_friends: CrossAddressFriends::Deny,
+            warn_shadowing: false,
         }
     }
 
@@ -645,6 +798,10 @@ impl Flags {
             shadow: true, // allows overlapping between sources and deps
             bytecode_version: None,
             keep_testing_functions: false,
+            ide_mode: false,
+            json_errors: false,
+            cross_address_friends: CrossAddressFriends::Deny,
+            warn_shadowing: false,
         }
     }
 
@@ -662,6 +819,34 @@ impl Flags {
         }
     }
 
+    pub fn set_ide_mode(self, value: bool) -> Self {
+        Self {
+            ide_mode: value,
+            ..self
+        }
+    }
+
+    pub fn set_json_errors(self, value: bool) -> Self {
+        Self {
+            json_errors: value,
+            ..self
+        }
+    }
+
+    pub fn set_cross_address_friends(self, value: CrossAddressFriends) -> Self {
+        Self {
+            cross_address_friends: value,
+            ..self
+        }
+    }
+
+    pub fn set_warn_shadowing(self, value: bool) -> Self {
+        Self {
+            warn_shadowing: value,
+            ..self
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         self == &Self::empty()
     }
@@ -685,6 +870,26 @@ impl Flags {
     pub fn bytecode_version(&self) -> Option<u32> {
         self.bytecode_version
     }
+
+    pub fn ide_mode(&self) -> bool {
+        self.ide_mode
+    }
+
+    pub fn cross_address_friends(&self) -> CrossAddressFriends {
+        self.cross_address_friends
+    }
+
+    pub fn warn_shadowing(&self) -> bool {
+        self.warn_shadowing
+    }
+
+    pub fn diagnostics_format(&self) -> DiagnosticsFormat {
+        if self.json_errors {
+            DiagnosticsFormat::Json
+        } else {
+            DiagnosticsFormat::Human
+        }
+    }
 }
 
 //**************************************************************************************************
@@ -697,6 +902,24 @@ pub struct PackageConfig {
     pub warning_filter: WarningFilters,
     pub flavor: Flavor,
     pub edition: Edition,
+    /// The levels of lints, by the name of their filter, e.g. as declared in the `[lints]` section
+    /// of the package manifest. They are added to `warning_filter` once all filters are known (see
+    /// `CompilationEnv::resolve_lint_levels`)
+    pub lint_levels: BTreeMap<Symbol, LintLevel>,
+    /// The features declared by the package, and whether each is enabled. Items annotated with
+    /// `#[cfg(feature = "<name>")]` are only compiled if the feature is enabled
+    pub features: BTreeMap<Symbol, bool>,
+}
+
+/// How the warnings of a lint are reported for a package
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintLevel {
+    /// The warnings are suppressed
+    Allow,
+    /// The warnings are reported as warnings
+    Warn,
+    /// The warnings are reported as errors
+    Deny,
 }
 
 impl Default for PackageConfig {
@@ -706,6 +929,42 @@ impl Default for PackageConfig {
             warning_filter: WarningFilters::new_for_source(),
             flavor: Flavor::default(),
             edition: Edition::default(),
+            lint_levels: BTreeMap::new(),
+            features: BTreeMap::new(),
+        }
+    }
+}
+
+impl LintLevel {
+    pub const ALLOW: &'static str = "allow";
+    pub const WARN: &'static str = "warn";
+    pub const DENY: &'static str = "deny";
+}
+
+impl FromStr for LintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        Ok(match s {
+            Self::ALLOW => Self::Allow,
+            Self::WARN => Self::Warn,
+            Self::DENY => Self::Deny,
+            _ => anyhow::bail!(
+                "Unknown lint level '{s}'. Expected one of: '{}', '{}', or '{}'",
+                Self::ALLOW,
+                Self::WARN,
+                Self::DENY,
+            ),
+        })
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Allow => write!(f, "{}", Self::ALLOW),
+            Self::Warn => write!(f, "{}", Self::WARN),
+            Self::Deny => write!(f, "{}", Self::DENY),
         }
     }
 }
@@ -765,6 +1024,9 @@ pub mod known_attributes {
         Verification(VerificationAttribute),
         Native(NativeAttribute),
         Diagnostic(DiagnosticAttribute),
+        Syntax(SyntaxAttribute),
+        Deprecation(DeprecationAttribute),
+        Cfg(CfgAttribute),
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -794,6 +1056,25 @@ pub mod known_attributes {
         Allow,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum SyntaxAttribute {
+        // The function implements a piece of syntax for the type of its first parameter,
+        // e.g. `#[syntax(index)]`
+        Syntax,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum DeprecationAttribute {
+        // Uses of the item from other modules are warned about, e.g. `#[deprecated(note = "...")]`
+        Deprecated,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum CfgAttribute {
+        // The item is only compiled if the feature is enabled, e.g. `#[cfg(feature = "...")]`
+        Cfg,
+    }
+
     impl fmt::Display for AttributePosition {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -825,6 +1106,11 @@ pub mod known_attributes {
                     Self::Native(NativeAttribute::BytecodeInstruction)
                 }
                 DiagnosticAttribute::ALLOW => Self::Diagnostic(DiagnosticAttribute::Allow),
+                SyntaxAttribute::SYNTAX => Self::Syntax(SyntaxAttribute::Syntax),
+                DeprecationAttribute::DEPRECATED => {
+                    Self::Deprecation(DeprecationAttribute::Deprecated)
+                }
+                CfgAttribute::CFG => Self::Cfg(CfgAttribute::Cfg),
                 _ => return None,
             })
         }
@@ -835,6 +1121,9 @@ pub mod known_attributes {
                 Self::Verification(a) => a.name(),
                 Self::Native(a) => a.name(),
                 Self::Diagnostic(a) => a.name(),
+                Self::Syntax(a) => a.name(),
+                Self::Deprecation(a) => a.name(),
+                Self::Cfg(a) => a.name(),
             }
         }
 
@@ -844,6 +1133,9 @@ pub mod known_attributes {
                 Self::Verification(a) => a.expected_positions(),
                 Self::Native(a) => a.expected_positions(),
                 Self::Diagnostic(a) => a.expected_positions(),
+                Self::Syntax(a) => a.expected_positions(),
+                Self::Deprecation(a) => a.expected_positions(),
+                Self::Cfg(a) => a.expected_positions(),
             }
         }
     }
@@ -971,4 +1263,75 @@ pub mod known_attributes {
             }
         }
     }
+
+    impl SyntaxAttribute {
+        pub const SYNTAX: &'static str = "syntax";
+        pub const INDEX: &'static str = "index";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                SyntaxAttribute::Syntax => Self::SYNTAX,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static SYNTAX_POSITIONS: Lazy<BTreeSet<AttributePosition>> =
+                Lazy::new(|| BTreeSet::from([AttributePosition::Function]));
+            match self {
+                SyntaxAttribute::Syntax => &SYNTAX_POSITIONS,
+            }
+        }
+    }
+
+    impl DeprecationAttribute {
+        pub const DEPRECATED: &'static str = "deprecated";
+        pub const NOTE: &'static str = "note";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                DeprecationAttribute::Deprecated => Self::DEPRECATED,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static DEPRECATED_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                BTreeSet::from([
+                    AttributePosition::Module,
+                    AttributePosition::Constant,
+                    AttributePosition::Struct,
+                    AttributePosition::Function,
+                ])
+            });
+            match self {
+                DeprecationAttribute::Deprecated => &DEPRECATED_POSITIONS,
+            }
+        }
+    }
+
+    impl CfgAttribute {
+        pub const CFG: &'static str = "cfg";
+        pub const FEATURE: &'static str = "feature";
+
+        pub const fn name(&self) -> &str {
+            match self {
+                CfgAttribute::Cfg => Self::CFG,
+            }
+        }
+
+        pub fn expected_positions(&self) -> &'static BTreeSet<AttributePosition> {
+            static CFG_POSITIONS: Lazy<BTreeSet<AttributePosition>> = Lazy::new(|| {
+                BTreeSet::from([
+                    AttributePosition::Module,
+                    AttributePosition::Use,
+                    AttributePosition::Friend,
+                    AttributePosition::Constant,
+                    AttributePosition::Struct,
+                    AttributePosition::Function,
+                ])
+            });
+            match self {
+                CfgAttribute::Cfg => &CFG_POSITIONS,
+            }
+        }
+    }
 }
@@ -960,11 +960,13 @@ fn command(context: &mut Context, code: &mut IR::BytecodeBlock, sp!(loc, cmd_):
             if_true,
             if_false,
         } => {
+            // the branch is mapped to the condition, rather than to the whole `if` or `while`
+            let cond_loc = cond.exp.loc;
             exp_(context, code, cond);
-            code.push(sp(loc, B::BrFalse(label(if_false))));
-            code.push(sp(loc, B::Branch(label(if_true))));
+            code.push(sp(cond_loc, B::BrFalse(label(if_false))));
+            code.push(sp(cond_loc, B::Branch(label(if_true))));
         }
-        C::Break | C::Continue => panic!("ICE break/continue not translated to jumps"),
+        C::Break(_) | C::Continue(_) => panic!("ICE break/continue not translated to jumps"),
     }
 }
 
@@ -1261,3 +1263,70 @@ fn binary_op(code: &mut IR::BytecodeBlock, sp!(loc, op_): BinOp) {
         },
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{command_line::compiler::Compiler, compiled_unit::AnnotatedCompiledUnit};
+    use move_binary_format::{access::ModuleAccess, file_format as F};
+    use move_symbol_pool::Symbol;
+    use std::collections::BTreeMap;
+
+    // Compiles `source` as the only file of the program and returns, for each instruction of its
+    // first function, the part of `source` it is mapped to
+    fn mapped_code(source: &str) -> Vec<(F::Bytecode, String)> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("m.move");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, source).unwrap();
+
+        let (_files, res) = Compiler::from_files(
+            vec![path],
+            vec![],
+            BTreeMap::<Symbol, crate::shared::NumericalAddress>::new(),
+        )
+        .build()
+        .unwrap();
+        let (mut units, _warnings) = res.unwrap();
+        let AnnotatedCompiledUnit::Module(unit) = units.pop().unwrap() else {
+            panic!("expected a module")
+        };
+        let module = &unit.named_module.module;
+        let fdef_idx = F::FunctionDefinitionIndex(0);
+        let code = &module.function_def_at(fdef_idx).code.as_ref().unwrap().code;
+        code.iter()
+            .enumerate()
+            .map(|(offset, instr)| {
+                let loc = unit
+                    .named_module
+                    .source_map
+                    .get_code_location(fdef_idx, offset as F::CodeOffset)
+                    .unwrap();
+                let span = source[loc.start() as usize..loc.end() as usize].to_owned();
+                (instr.clone(), span)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn source_map_spans_expressions() {
+        let code =
+            mapped_code("module 0x42::m { fun f(x: u64): u64 { if (x > 1) x * 2 else x + 3 } }");
+        let span_of = |instr: F::Bytecode| {
+            code.iter()
+                .find(|(i, _)| *i == instr)
+                .map(|(_, span)| span.as_str())
+        };
+        // operations are mapped to their operator, and operands to their own expression
+        assert_eq!(span_of(F::Bytecode::Gt), Some(">"));
+        assert_eq!(span_of(F::Bytecode::Mul), Some("*"));
+        assert_eq!(span_of(F::Bytecode::Add), Some("+"));
+        assert_eq!(span_of(F::Bytecode::LdU64(1)), Some("1"));
+        assert_eq!(span_of(F::Bytecode::LdU64(3)), Some("3"));
+        // branches are mapped to their condition, rather than to the whole `if`
+        let branch = code
+            .iter()
+            .find(|(i, _)| matches!(i, F::Bytecode::BrFalse(_)))
+            .map(|(_, span)| span.as_str());
+        assert_eq!(branch, Some("x > 1"));
+    }
+}
@@ -4,14 +4,20 @@
 
 use crate::{
     diag,
-    diagnostics::{codes::NameResolution, Diagnostic},
-    expansion::ast::{AbilitySet, ModuleIdent, ModuleIdent_, Visibility},
+    diagnostics::{codes::NameResolution, Diagnostic, Fix},
+    expansion::ast::{
+        AbilitySet, AttributeName_, Attributes, ModuleIdent, ModuleIdent_, Visibility,
+    },
     naming::ast::{
-        self as N, BuiltinTypeName_, FunctionSignature, StructDefinition, StructTypeParameter,
-        TParam, TParamID, TVar, Type, TypeName, TypeName_, Type_, Var,
+        self as N, BlockLabel, BuiltinTypeName_, FunctionSignature, StructDefinition,
+        StructTypeParameter, TParam, TParamID, TVar, Type, TypeName, TypeName_, Type_, Var,
     },
     parser::ast::{Ability_, ConstantName, Field, FunctionName, StructName},
-    shared::{unique_map::UniqueMap, *},
+    shared::{
+        known_attributes::{KnownAttribute, TestingAttribute},
+        unique_map::UniqueMap,
+        *,
+    },
     typing::ast as T,
     FullyCompiledProgram,
 };
@@ -44,15 +50,18 @@ pub struct FunctionInfo {
     pub visibility: Visibility,
     pub signature: FunctionSignature,
     pub acquires: BTreeMap<StructName, Loc>,
+    pub attributes: Attributes,
 }
 
 pub struct ConstantInfo {
     pub defined_loc: Loc,
     pub signature: Type,
+    pub attributes: Attributes,
 }
 
 pub struct ModuleInfo {
     pub package: Option<Symbol>,
+    pub attributes: Attributes,
     pub friends: UniqueMap<ModuleIdent, Loc>,
     pub structs: UniqueMap<StructName, StructDefinition>,
     pub functions: UniqueMap<FunctionName, FunctionInfo>,
@@ -65,37 +74,38 @@ pub struct ProgramInfo<const AFTER_TYPING: bool> {
 pub type NamingProgramInfo = ProgramInfo<false>;
 pub type TypingProgramInfo = ProgramInfo<true>;
 
-pub struct LoopInfo(LoopInfo_);
-
-enum LoopInfo_ {
-    NotInLoop,
-    BreakTypeUnknown,
-    BreakType(Box<Type>),
-}
-
 pub struct Context<'env> {
     pub modules: NamingProgramInfo,
     pub env: &'env mut CompilationEnv,
 
     pub current_module: Option<ModuleIdent>,
     pub current_function: Option<FunctionName>,
+    /// true if the current module or script is annotated with `#[test_only]`
+    pub current_module_is_test_only: bool,
+    /// true if the current function, struct or constant is annotated with `#[test]` or
+    /// `#[test_only]`
+    pub current_member_is_test: bool,
     pub current_script_constants: Option<UniqueMap<ConstantName, ConstantInfo>>,
+    /// The method aliases declared with `use fun` in the current module
+    pub use_funs: N::UseFuns,
     pub return_type: Option<Type>,
     locals: UniqueMap<Var, Type>,
 
     pub subst: Subst,
     pub constraints: Constraints,
 
-    loop_info: LoopInfo,
+    /// The loops around the expression being typed, with the type of the values they are exited
+    /// with, once a 'break' for them has been found
+    loop_breaks: BTreeMap<BlockLabel, Option<Type>>,
 
     /// collects all friends that should be added over the course of 'public(package)' calls
     /// structured as (defining module, new friend, location) where `new friend` is usually the
     /// context's current module. Note there may be more than one location in practice, but
     /// tracking a single one is sufficient for error reporting.
     pub new_friends: BTreeSet<(ModuleIdent, Loc)>,
-    /// collects all used module members (functions and constants) but it's a superset of these in
-    /// that it may contain other identifiers that do not in fact represent a function or a constant
-    pub used_module_members: BTreeMap<ModuleIdent_, BTreeSet<Symbol>>,
+    /// collects the friends that called a 'public(friend)' function of the module that declared
+    /// them, as (declaring module, friend)
+    pub used_friends: BTreeSet<(ModuleIdent_, ModuleIdent_)>,
 }
 
 macro_rules! program_info {
@@ -117,13 +127,16 @@ macro_rules! program_info {
                 visibility: fdef.visibility.clone(),
                 signature: fdef.signature.clone(),
                 acquires: fdef.acquires.clone(),
+                attributes: fdef.attributes.clone(),
             });
             let constants = mdef.constants.ref_map(|cname, cdef| ConstantInfo {
                 defined_loc: cname.loc(),
                 signature: cdef.signature.clone(),
+                attributes: cdef.attributes.clone(),
             });
             let minfo = ModuleInfo {
                 package: mdef.package_name,
+                attributes: mdef.attributes.clone(),
                 friends: mdef.friends.ref_map(|_, friend| friend.loc),
                 structs,
                 functions,
@@ -144,7 +157,34 @@ impl TypingProgramInfo {
 
 impl NamingProgramInfo {
     pub fn new(pre_compiled_lib: Option<&FullyCompiledProgram>, prog: &N::Program) -> Self {
-        program_info!(pre_compiled_lib, prog, naming)
+        let mut info = program_info!(pre_compiled_lib, prog, naming);
+        // macros are expanded at their call sites during naming, so from typing on they are not
+        // functions that can be called (e.g. as methods)
+        let macros = prog
+            .modules
+            .key_cloned_iter()
+            .chain(pre_compiled_lib.iter().flat_map(|pre_compiled| {
+                pre_compiled
+                    .naming
+                    .modules
+                    .key_cloned_iter()
+                    .filter(|(mident, _m)| !prog.modules.contains_key(mident))
+            }))
+            .flat_map(|(mident, mdef)| {
+                mdef.functions
+                    .key_cloned_iter()
+                    .filter(|(_, fdef)| fdef.macro_.is_some())
+                    .map(move |(fname, _)| (mident, fname))
+            })
+            .collect::<Vec<_>>();
+        for (mident, fname) in macros {
+            info.modules
+                .get_mut(&mident)
+                .unwrap()
+                .functions
+                .remove(&fname);
+        }
+        info
     }
 }
 
@@ -211,28 +251,32 @@ impl<'env> Context<'env> {
             subst: Subst::empty(),
             current_module: None,
             current_function: None,
+            current_module_is_test_only: false,
+            current_member_is_test: false,
             current_script_constants: None,
+            use_funs: N::UseFuns::new(),
             return_type: None,
             constraints: vec![],
             locals: UniqueMap::new(),
-            loop_info: LoopInfo(LoopInfo_::NotInLoop),
+            loop_breaks: BTreeMap::new(),
             modules,
             env,
             new_friends: BTreeSet::new(),
-            used_module_members: BTreeMap::new(),
+            used_friends: BTreeSet::new(),
         }
     }
 
     pub fn reset_for_module_item(&mut self) {
         assert!(
-            matches!(&self.loop_info, LoopInfo(LoopInfo_::NotInLoop)),
-            "ICE loop_info should be reset after the loop"
+            self.loop_breaks.is_empty(),
+            "ICE loop_breaks should be reset after the loop"
         );
         self.return_type = None;
         self.locals = UniqueMap::new();
         self.subst = Subst::empty();
         self.constraints = Constraints::new();
         self.current_function = None;
+        self.current_member_is_test = false;
     }
 
     pub fn bind_script_constants(&mut self, constants: &UniqueMap<ConstantName, N::Constant>) {
@@ -240,6 +284,7 @@ impl<'env> Context<'env> {
         self.current_script_constants = Some(constants.ref_map(|cname, cdef| ConstantInfo {
             defined_loc: cname.loc(),
             signature: cdef.signature.clone(),
+            attributes: cdef.attributes.clone(),
         }));
     }
 
@@ -322,6 +367,12 @@ impl<'env> Context<'env> {
         self.is_current_module(m) && matches!(&self.current_function, Some(curf) if curf == f)
     }
 
+    /// Test code is any code in a `#[test_only]` module, or in a `#[test]` or `#[test_only]`
+    /// function. Only test code can use `#[test_only]` members.
+    pub fn in_test_code(&self) -> bool {
+        self.current_module_is_test_only || self.current_member_is_test
+    }
+
     // `loc` indicates the location that caused the add to occur
     fn record_current_module_as_friend(&mut self, m: &ModuleIdent, loc: Loc) {
         if matches!(self.current_module, Some(current_mident) if m != &current_mident) {
@@ -329,6 +380,12 @@ impl<'env> Context<'env> {
         }
     }
 
+    fn record_current_module_as_used_friend(&mut self, m: &ModuleIdent) {
+        if let Some(current_mident) = self.current_module {
+            self.used_friends.insert((m.value, current_mident.value));
+        }
+    }
+
     fn current_module_shares_package_and_address(&self, m: &ModuleIdent) -> bool {
         self.current_module.is_some_and(|current_mident| {
             m.value.address == current_mident.value.address
@@ -366,7 +423,7 @@ impl<'env> Context<'env> {
         self.modules.struct_type_parameters(m, n)
     }
 
-    fn function_info(&self, m: &ModuleIdent, n: &FunctionName) -> &FunctionInfo {
+    pub fn function_info(&self, m: &ModuleIdent, n: &FunctionName) -> &FunctionInfo {
         self.modules.function_info(m, n)
     }
 
@@ -378,40 +435,30 @@ impl<'env> Context<'env> {
         constants.get(n).expect("ICE should have failed in naming")
     }
 
-    pub fn in_loop(&self) -> bool {
-        match &self.loop_info.0 {
-            LoopInfo_::NotInLoop => false,
-            LoopInfo_::BreakTypeUnknown | LoopInfo_::BreakType(_) => true,
-        }
+    pub fn get_break_type(&self, name: BlockLabel) -> Option<&Type> {
+        self.loop_breaks
+            .get(&name)
+            .expect("ICE break for a loop not being typed, should have failed in naming")
+            .as_ref()
     }
 
-    pub fn get_break_type(&self) -> Option<&Type> {
-        match &self.loop_info.0 {
-            LoopInfo_::NotInLoop | LoopInfo_::BreakTypeUnknown => None,
-            LoopInfo_::BreakType(t) => Some(t),
+    pub fn set_break_type(&mut self, name: BlockLabel, t: Type) {
+        if let Some(break_type) = self.loop_breaks.get_mut(&name) {
+            *break_type = Some(t)
         }
     }
 
-    pub fn set_break_type(&mut self, t: Type) {
-        match &self.loop_info.0 {
-            LoopInfo_::NotInLoop => (),
-            LoopInfo_::BreakTypeUnknown | LoopInfo_::BreakType(_) => {
-                self.loop_info.0 = LoopInfo_::BreakType(Box::new(t))
-            }
-        }
+    // `break_type` is the type the loop must be exited with, if it is already known
+    pub fn enter_loop(&mut self, name: BlockLabel, break_type: Option<Type>) {
+        let prev = self.loop_breaks.insert(name, break_type);
+        assert!(prev.is_none(), "ICE loop labels should be unique");
     }
 
-    pub fn enter_loop(&mut self) -> LoopInfo {
-        std::mem::replace(&mut self.loop_info, LoopInfo(LoopInfo_::BreakTypeUnknown))
-    }
-
-    // Reset loop info and return the loop's break type, if it has one
-    pub fn exit_loop(&mut self, old_info: LoopInfo) -> Option<Type> {
-        match std::mem::replace(&mut self.loop_info, old_info).0 {
-            LoopInfo_::NotInLoop => panic!("ICE exit_loop called while not in a loop"),
-            LoopInfo_::BreakTypeUnknown => None,
-            LoopInfo_::BreakType(t) => Some(*t),
-        }
+    // Returns the loop's break type, if it has one
+    pub fn exit_loop(&mut self, name: BlockLabel) -> Option<Type> {
+        self.loop_breaks
+            .remove(&name)
+            .expect("ICE exit_loop called while not in the loop")
     }
 }
 
@@ -647,6 +694,10 @@ pub fn make_struct_type(
     ty_args_opt: Option<Vec<Type>>,
 ) -> (Type, Vec<Type>) {
     let tn = sp(loc, TypeName_::ModuleType(*m, *n));
+    let test_only_loc = test_only_attribute_loc(&context.struct_definition(m, n).attributes);
+    check_test_only_use(context, loc, Some(m), test_only_loc, || {
+        format!("Invalid use of test-only struct '{}::{}'", m, n)
+    });
     let sdef = context.struct_definition(m, n);
     match ty_args_opt {
         None => {
@@ -771,12 +822,17 @@ pub fn make_constant_type(
     c: &ConstantName,
 ) -> Type {
     let in_current_module = m == &context.current_module;
-    let (defined_loc, signature) = {
+    let (defined_loc, signature, test_only_loc) = {
         let ConstantInfo {
             defined_loc,
             signature,
+            attributes,
         } = context.constant_info(m, c);
-        (*defined_loc, signature.clone())
+        (
+            *defined_loc,
+            signature.clone(),
+            test_only_attribute_loc(attributes),
+        )
     };
     if !in_current_module {
         let msg = match m {
@@ -791,6 +847,11 @@ pub fn make_constant_type(
             (defined_loc, internal_msg)
         ));
     }
+    if let Some(test_only_loc) = test_only_loc {
+        check_test_only_use(context, loc, m.as_ref(), Some(test_only_loc), || {
+            format!("Invalid use of test-only constant '{}'", c)
+        });
+    }
 
     signature
 }
@@ -847,7 +908,7 @@ pub fn make_function_type(
         .signature
         .parameters
         .iter()
-        .map(|(n, t)| (*n, subst_tparams(tparam_subst, t.clone())))
+        .map(|(_, n, t)| (*n, subst_tparams(tparam_subst, t.clone())))
         .collect();
     let return_ty = subst_tparams(tparam_subst, finfo.signature.return_type.clone());
     let acquires = if in_current_module {
@@ -857,6 +918,7 @@ pub fn make_function_type(
     };
 
     let defined_loc = finfo.defined_loc;
+    let test_only_loc = test_only_attribute_loc(&finfo.attributes);
     match finfo.visibility {
         Visibility::Internal if in_current_module => (),
         Visibility::Internal => {
@@ -905,7 +967,10 @@ pub fn make_function_type(
                 (vis_loc, internal_msg),
             ));
         }
-        Visibility::Friend(_) if in_current_module || context.current_module_is_a_friend_of(m) => {}
+        Visibility::Friend(_) if in_current_module => (),
+        Visibility::Friend(_) if context.current_module_is_a_friend_of(m) => {
+            context.record_current_module_as_used_friend(m);
+        }
         Visibility::Friend(vis_loc) => {
             let internal_msg = format!(
                 "This function can only be called from a 'friend' of module '{}'",
@@ -919,9 +984,72 @@ pub fn make_function_type(
         }
         Visibility::Public(_) => (),
     };
+    check_test_only_use(context, loc, Some(m), test_only_loc, || {
+        format!("Invalid call to test-only function '{}::{}'", m, f)
+    });
     (defined_loc, ty_args, params, acquires, return_ty)
 }
 
+//**************************************************************************************************
+// Test-only members
+//**************************************************************************************************
+
+/// Returns the location of the `#[test_only]` annotation in `attributes`, if any
+pub fn test_only_attribute_loc(attributes: &Attributes) -> Option<Loc> {
+    attributes.iter().find_map(|(loc, attr_, _)| {
+        matches!(
+            attr_,
+            AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::TestOnly))
+        )
+        .then_some(loc)
+    })
+}
+
+/// Returns true if `attributes` mark a member as test code, i.e. `#[test]` or `#[test_only]`
+pub fn is_test_function(attributes: &Attributes) -> bool {
+    attributes.iter().any(|(_, attr_, _)| {
+        matches!(
+            attr_,
+            AttributeName_::Known(KnownAttribute::Testing(
+                TestingAttribute::Test | TestingAttribute::TestOnly
+            ))
+        )
+    })
+}
+
+// `#[test_only]` members are removed outside of test mode, so any use of them from non-test code
+// would fail to compile there. Report it at the use, pointing at what makes the member test-only:
+// either its own annotation (`member_test_only_loc`) or that of its module, if it is not in a script.
+fn check_test_only_use(
+    context: &mut Context,
+    loc: Loc,
+    m: Option<&ModuleIdent>,
+    member_test_only_loc: Option<Loc>,
+    mk_msg: impl FnOnce() -> String,
+) {
+    if context.in_test_code() {
+        return;
+    }
+    let test_only_label = match member_test_only_loc {
+        Some(test_only_loc) => (test_only_loc, "Declared '#[test_only]' here".to_owned()),
+        None => match m.and_then(|m| {
+            test_only_attribute_loc(&context.module_info(m).attributes).map(|loc| (m, loc))
+        }) {
+            Some((m, test_only_loc)) => (
+                test_only_loc,
+                format!("Module '{}' is declared '#[test_only]' here", m),
+            ),
+            None => return,
+        },
+    };
+    let mut diag = diag!(TypeSafety::TestOnlyUse, (loc, mk_msg()), test_only_label);
+    diag.add_note(
+        "Test-only members can only be used from '#[test]' or '#[test_only]' functions, or from \
+         '#[test_only]' modules",
+    );
+    context.env.add_diag(diag);
+}
+
 //**************************************************************************************************
 // Constraints
 //**************************************************************************************************
@@ -1035,13 +1163,21 @@ pub fn ability_not_satisfied_tips<'a>(
         declared_abilities.has_ability_(constraint),
     ) {
         // Type was not given the ability
-        (Some(dloc), false) => diag.add_secondary_label((
-            dloc,
-            format!(
-                "To satisfy the constraint, the '{}' ability would need to be added here",
-                constraint
-            ),
-        )),
+        (Some(dloc), false) => {
+            let (suggestion, fix_opt) =
+                suggested_abilities(ty, dloc, declared_abilities, constraint);
+            diag.add_secondary_label((
+                dloc,
+                format!(
+                    "To satisfy the constraint, the '{}' ability would need to be added here: \
+                     '{}'",
+                    constraint, suggestion
+                ),
+            ));
+            if let Some(fix) = fix_opt {
+                diag.add_fix(fix)
+            }
+        }
         // Type does not have the ability
         (_, false) => (),
         // Type has the ability but a type argument causes it to fail
@@ -1069,6 +1205,55 @@ pub fn ability_not_satisfied_tips<'a>(
     }
 }
 
+// The declaration of the abilities of the type declared at `dloc` once `constraint` is added to
+// them, along with the edit making that change. The edit is only given when the declared
+// abilities are known to be at a place where the new one can be written, i.e. always for type
+// parameters, but only for structs that already declare some abilities.
+fn suggested_abilities(
+    ty: &Type,
+    dloc: Loc,
+    declared_abilities: &AbilitySet,
+    constraint: Ability_,
+) -> (String, Option<Fix>) {
+    let mut abilities = declared_abilities.clone();
+    let _ = abilities.add(sp(dloc, constraint));
+    let abilities = abilities.iter().map(|ability| ability.value);
+    let declared_loc_opt = declared_abilities
+        .iter()
+        .map(|ability| ability.loc)
+        .reduce(|l1, l2| {
+            Loc::new(
+                l1.file_hash(),
+                std::cmp::min(l1.start(), l2.start()),
+                std::cmp::max(l1.end(), l2.end()),
+            )
+        });
+    let fix_msg = format!("Add the '{}' ability", constraint);
+    match &ty.value {
+        Type_::Param(TParam {
+            user_specified_name,
+            ..
+        }) => {
+            let constraints = format_delim(abilities, " + ");
+            let edit = match declared_loc_opt {
+                Some(declared_loc) => (declared_loc, constraints.clone()),
+                None => (
+                    Loc::new(dloc.file_hash(), dloc.end(), dloc.end()),
+                    format!(": {}", constraints),
+                ),
+            };
+            let suggestion = format!("{}: {}", user_specified_name, constraints);
+            (suggestion, Some(Fix::new(fix_msg, [edit])))
+        }
+        _ => {
+            let abilities = format_comma(abilities);
+            let fix_opt = declared_loc_opt
+                .map(|declared_loc| Fix::new(fix_msg, [(declared_loc, abilities.clone())]));
+            (format!("has {}", abilities), fix_opt)
+        }
+    }
+}
+
 fn solve_builtin_type_constraint(
     context: &mut Context,
     builtin_set: &BTreeSet<BuiltinTypeName_>,
@@ -1269,10 +1454,19 @@ pub fn instantiate(context: &mut Context, sp!(loc, t_): Type) -> Type {
             Ref(mut_, Box::new(instantiate(context, inner)))
         }
         Apply(abilities_opt, n, ty_args) => {
+            if let sp!(_, TypeName_::ModuleType(m, s)) = &n {
+                let test_only_loc =
+                    test_only_attribute_loc(&context.struct_definition(m, s).attributes);
+                check_test_only_use(context, loc, Some(m), test_only_loc, || {
+                    format!("Invalid use of test-only struct '{}::{}'", m, s)
+                });
+            }
             instantiate_apply(context, loc, abilities_opt, n, ty_args)
         }
         x @ Param(_) => x,
-        Var(_) => panic!("ICE instantiate type variable"),
+        // only the expansions of macros have type variables before typing, one for each type
+        // parameter of the macro, which is already fresh
+        x @ Var(_) => x,
     };
     sp(loc, it_)
 }
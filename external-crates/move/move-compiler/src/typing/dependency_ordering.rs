@@ -11,7 +11,10 @@ use crate::{
 };
 use move_ir_types::location::*;
 use move_symbol_pool::Symbol;
-use petgraph::{algo::toposort as petgraph_toposort, graphmap::DiGraphMap};
+use petgraph::{
+    algo::{tarjan_scc as petgraph_tarjan_scc, toposort as petgraph_toposort},
+    graphmap::DiGraphMap,
+};
 use std::collections::{BTreeMap, BTreeSet};
 
 //**************************************************************************************************
@@ -36,10 +39,14 @@ pub fn program(
     } = context;
     let graph = dependency_graph(&module_neighbors);
     match petgraph_toposort(&graph, None) {
-        Err(cycle_node) => {
-            let cycle_ident = *cycle_node.node_id();
-            let error = cycle_error(&module_neighbors, cycle_ident);
-            compilation_env.add_diag(error);
+        Err(_) => {
+            // Report a minimal cycle in each strongly connected component, instead of the whole
+            // component, which can span most of a large package
+            for scc in petgraph_tarjan_scc(&graph) {
+                if let Some(cycle) = minimal_cycle(&graph, scc) {
+                    compilation_env.add_diag(cycle_error(&module_neighbors, &cycle));
+                }
+            }
         }
         Ok(ordered_ids) => {
             for (order, mident) in ordered_ids.iter().rev().enumerate() {
@@ -196,14 +203,26 @@ fn dependency_graph(
     graph
 }
 
+/// The shortest cycle through any module in the strongly connected component `scc`, with ties
+/// broken by the order of the modules, or `None` if the component is a single module.
+fn minimal_cycle<'a>(
+    graph: &DiGraphMap<&'a ModuleIdent, ()>,
+    mut scc: Vec<&'a ModuleIdent>,
+) -> Option<Vec<&'a ModuleIdent>> {
+    // modules are never their own neighbors, so a single module cannot form a cycle
+    if scc.len() < 2 {
+        return None;
+    }
+    scc.sort();
+    scc.into_iter()
+        .map(|mident| shortest_cycle(graph, mident))
+        .min_by_key(|cycle| cycle.len())
+}
+
 fn cycle_error(
     deps: &BTreeMap<ModuleIdent, BTreeMap<ModuleIdent, BTreeMap<DepType, Loc>>>,
-    cycle_ident: ModuleIdent,
+    cycle: &[&ModuleIdent],
 ) -> Diagnostic {
-    let graph = dependency_graph(deps);
-    // For printing uses, sort the cycle by location (earliest first)
-    let cycle = shortest_cycle(&graph, &cycle_ident);
-
     let mut cycle_info = cycle
         .windows(2)
         .map(|pair| {
@@ -217,7 +236,7 @@ fn cycle_error(
                 (Some(loc), _) => (
                     *loc,
                     DepType::Use,
-                    format!("'{}' uses '{}'", neighbor, node),
+                    format!("'{}' uses '{}'", node, neighbor),
                     node,
                     neighbor,
                 ),
@@ -252,13 +271,21 @@ fn cycle_error(
         (loc, msg)
     };
 
+    let cycle_msg = format!(
+        "Dependency cycle: {}",
+        cycle
+            .iter()
+            .map(|mident| format!("'{}'", mident))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    );
     Diagnostic::new(
         Declarations::InvalidModule,
         (cycle_loc, use_msg),
         cycle_info
             .into_iter()
             .map(|(loc, _dep_type, msg, _node, _neighbor)| (loc, msg)),
-        std::iter::empty::<String>(),
+        vec![cycle_msg],
     )
 }
 
@@ -329,7 +356,7 @@ fn function(context: &mut Context, fdef: &T::Function) {
 }
 
 fn function_signature(context: &mut Context, sig: &N::FunctionSignature) {
-    types(context, sig.parameters.iter().map(|(_, st)| st));
+    types(context, sig.parameters.iter().map(|(_, _, st)| st));
     type_(context, &sig.return_type)
 }
 
@@ -444,11 +471,11 @@ fn exp(context: &mut Context, e: &T::Exp) {
             exp(context, e2);
             exp(context, e3);
         }
-        E::While(e1, e2) => {
+        E::While(_, e1, e2) => {
             exp(context, e1);
             exp(context, e2);
         }
-        E::Loop { has_break: _, body } => exp(context, body),
+        E::Loop { body, .. } => exp(context, body),
         E::Block(seq) => sequence(context, seq),
         E::Assign(sp!(_, lvs_), ty_opts, e) => {
             lvalues(context, lvs_);
@@ -463,6 +490,7 @@ fn exp(context: &mut Context, e: &T::Exp) {
         }
         E::Return(e) => exp(context, e),
         E::Abort(e) => exp(context, e),
+        E::Break(_, Some(e)) => exp(context, e),
         E::Dereference(e) => exp(context, e),
         E::UnaryExp(_, e) => exp(context, e),
         E::BinopExp(e1, _, _, e2) => {
@@ -500,8 +528,8 @@ fn exp(context: &mut Context, e: &T::Exp) {
         | E::Copy { .. }
         | E::Use(_)
         | E::Constant(..)
-        | E::Break
-        | E::Continue
+        | E::Break(_, None)
+        | E::Continue(_)
         | E::BorrowLocal(..)
         | E::Spec(..)
         | E::UnresolvedError => (),
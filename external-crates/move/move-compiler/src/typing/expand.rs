@@ -25,7 +25,7 @@ pub fn function_body_(context: &mut Context, b_: &mut T::FunctionBody_) {
 }
 
 pub fn function_signature(context: &mut Context, sig: &mut FunctionSignature) {
-    for (_, st) in &mut sig.parameters {
+    for (_, _, st) in &mut sig.parameters {
         type_(context, st);
     }
     type_(context, &mut sig.return_type);
@@ -112,7 +112,7 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
     use T::UnannotatedExp_ as E;
     match &e.exp.value {
         // dont expand the type for return, abort, break, or continue
-        E::Break | E::Continue | E::Return(_) | E::Abort(_) => {
+        E::Break(_, _) | E::Continue(_) | E::Return(_) | E::Abort(_) => {
             let t = e.ty.clone();
             match core::unfold_type(&context.subst, t) {
                 sp!(_, Type_::Anything) => (),
@@ -225,8 +225,8 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
         | E::Move { .. }
         | E::Copy { .. }
         | E::BorrowLocal(_, _)
-        | E::Break
-        | E::Continue
+        | E::Break(_, None)
+        | E::Continue(_)
         | E::UnresolvedError => (),
 
         E::ModuleCall(call) => module_call(context, call),
@@ -244,7 +244,7 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
             exp(context, et);
             exp(context, ef);
         }
-        E::While(eb, eloop) => {
+        E::While(_, eb, eloop) => {
             exp(context, eb);
             exp(context, eloop);
         }
@@ -258,6 +258,7 @@ pub fn exp(context: &mut Context, e: &mut T::Exp) {
 
         E::Return(er)
         | E::Abort(er)
+        | E::Break(_, Some(er))
         | E::Dereference(er)
         | E::UnaryExp(_, er)
         | E::Borrow(_, er, _)
@@ -219,8 +219,8 @@ fn exp(context: &mut Context, e: &T::Exp) {
         | E::Move { .. }
         | E::Copy { .. }
         | E::BorrowLocal(_, _)
-        | E::Break
-        | E::Continue
+        | E::Break(_, None)
+        | E::Continue(_)
         | E::Spec(_, _)
         | E::UnresolvedError => (),
 
@@ -234,7 +234,7 @@ fn exp(context: &mut Context, e: &T::Exp) {
             exp(context, et);
             exp(context, ef);
         }
-        E::While(eb, eloop) => {
+        E::While(_, eb, eloop) => {
             exp(context, eb);
             exp(context, eloop);
         }
@@ -246,6 +246,7 @@ fn exp(context: &mut Context, e: &T::Exp) {
         | E::Vector(_, _, _, er)
         | E::Return(er)
         | E::Abort(er)
+        | E::Break(_, Some(er))
         | E::Dereference(er)
         | E::UnaryExp(_, er)
         | E::Borrow(_, er, _)
@@ -9,19 +9,10 @@ use super::{
 use crate::{
     diag,
     diagnostics::{codes::*, Diagnostic},
-    editions::Flavor,
-    expansion::ast::{
-        AttributeName_, AttributeValue_, Attribute_, Attributes, Fields, Friend, ModuleAccess_,
-        ModuleIdent, ModuleIdent_, Value_, Visibility,
-    },
+    expansion::ast::{Fields, Friend, ModuleIdent, ModuleIdent_, Value_, Visibility},
     naming::ast::{self as N, TParam, TParamID, Type, TypeName_, Type_},
     parser::ast::{Ability_, BinOp_, ConstantName, Field, FunctionName, StructName, UnaryOp_},
-    shared::{
-        known_attributes::{KnownAttribute, TestingAttribute},
-        unique_map::UniqueMap,
-        *,
-    },
-    sui_mode,
+    shared::{unique_map::UniqueMap, *},
     typing::{ast as T, dependency_ordering},
     FullyCompiledProgram,
 };
@@ -81,6 +72,11 @@ fn modules(
         typed_mdef
     });
 
+    // generated before the friends added for 'public(package)' calls, which are all used
+    for (_, mident, mdef) in &typed_modules {
+        gen_unused_warnings(context, mident, mdef);
+    }
+
     for (mident, friends) in all_new_friends {
         let mdef = typed_modules.get_mut(&mident).unwrap();
         // point of interest: if we have any new friends, we know there can't be any
@@ -90,10 +86,6 @@ fn modules(
             .expect("ICE compiler added duplicate friends to public(package) friend list");
     }
 
-    for (_, mident, mdef) in &typed_modules {
-        gen_unused_warnings(context, mident, mdef);
-    }
-
     typed_modules
 }
 
@@ -105,26 +97,49 @@ fn module(
     assert!(context.current_script_constants.is_none());
 
     context.current_module = Some(ident);
+    context.current_module_is_test_only = core::test_only_attribute_loc(&mdef.attributes).is_some();
     let N::ModuleDefinition {
         loc,
         warning_filter,
         package_name,
         attributes,
+        doc: _,
         is_source_module,
         friends,
         mut structs,
+        enums,
         functions: nfunctions,
         constants: nconstants,
+        syntax_methods: _,
+        use_funs,
         spec_dependencies,
     } = mdef;
     context.env.add_warning_filter_scope(warning_filter.clone());
+    context.use_funs = use_funs;
     structs
         .iter_mut()
         .for_each(|(_, _, s)| struct_def(context, s));
-    process_attributes(context, &attributes);
+    for (loc, _, _) in &enums {
+        // TODO type check and compile enums once 'match' is supported
+        let msg = "Invalid enum declaration. 'enum' declarations are not yet supported";
+        context
+            .env
+            .add_diag(diag!(TypeSafety::UnsupportedEnum, (loc, msg)));
+    }
     let constants = nconstants.map(|name, c| constant(context, name, c));
-    let functions = nfunctions.map(|name, f| function(context, name, f, false));
+    // macros are type checked where they are declared, but only their expansions are compiled
+    let functions = nfunctions.filter_map(|name, f| {
+        let is_macro = f.macro_.is_some();
+        let new_friends = is_macro.then(|| context.new_friends.clone());
+        let tfunction = function(context, name, f, false);
+        if let Some(new_friends) = new_friends {
+            // the calls in the body of a macro are made where it is expanded
+            context.new_friends = new_friends;
+        }
+        (!is_macro).then_some(tfunction)
+    });
     assert!(context.constraints.is_empty());
+    context.use_funs = N::UseFuns::new();
     context.env.pop_warning_filter_scope();
     let typed_module = T::ModuleDefinition {
         loc,
@@ -159,6 +174,8 @@ fn scripts(
 fn script(context: &mut Context, nscript: N::Script) -> T::Script {
     assert!(context.current_script_constants.is_none());
     context.current_module = None;
+    context.current_module_is_test_only =
+        core::test_only_attribute_loc(&nscript.attributes).is_some();
     let N::Script {
         warning_filter,
         package_name,
@@ -204,8 +221,10 @@ fn function(
         warning_filter,
         index,
         attributes,
+        doc: _,
         visibility,
         entry,
+        macro_: _,
         mut signature,
         body: n_body,
         acquires,
@@ -214,7 +233,7 @@ fn function(
     assert!(context.constraints.is_empty());
     context.reset_for_module_item();
     context.current_function = Some(name);
-    process_attributes(context, &attributes);
+    context.current_member_is_test = core::is_test_function(&attributes);
     function_signature(context, &signature);
     if is_script {
         let mk_msg = || {
@@ -253,7 +272,7 @@ fn function(
 fn function_signature(context: &mut Context, sig: &N::FunctionSignature) {
     assert!(context.constraints.is_empty());
 
-    for (param, param_ty) in &sig.parameters {
+    for (_, param, param_ty) in &sig.parameters {
         let param_ty = core::instantiate(context, param_ty.clone());
         context.add_single_type_constraint(
             param_ty.loc,
@@ -308,14 +327,14 @@ fn constant(context: &mut Context, _name: ConstantName, nconstant: N::Constant)
         warning_filter,
         index,
         attributes,
+        doc: _,
         loc,
         signature,
         value: nvalue,
     } = nconstant;
+    context.current_member_is_test = core::is_test_function(&attributes);
     context.env.add_warning_filter_scope(warning_filter.clone());
 
-    process_attributes(context, &attributes);
-
     // Don't need to add base type constraint, as it is checked in `check_valid_constant::signature`
     let mut signature = core::instantiate(context, signature);
     check_valid_constant::signature(
@@ -434,12 +453,15 @@ mod check_valid_constant {
             //*****************************************
             // Error cases handled elsewhere
             //*****************************************
-            E::Use(_) | E::Continue | E::Break | E::UnresolvedError => return,
+            E::Use(_) | E::Continue(_) | E::Break(_, _) | E::UnresolvedError => return,
 
             //*****************************************
             // Valid cases
             //*****************************************
             E::Unit { .. } | E::Value(_) | E::Move { .. } | E::Copy { .. } => return,
+            // Constants of other modules are reported as visibility errors, and cycles between
+            // constants are reported when folding them
+            E::Constant(_, _) => return,
             E::Block(seq) => {
                 sequence(context, seq);
                 return;
@@ -486,7 +508,7 @@ mod check_valid_constant {
                 exp(context, ef);
                 "'if' expressions are"
             }
-            E::While(eb, eloop) => {
+            E::While(_, eb, eloop) => {
                 exp(context, eb);
                 exp(context, eloop);
                 "'while' expressions are"
@@ -522,7 +544,6 @@ mod check_valid_constant {
                 }
                 "Structs are"
             }
-            E::Constant(_, _) => "Other constants are",
         };
         context.env.add_diag(diag!(
             TypeSafety::UnsupportedConstant,
@@ -582,6 +603,7 @@ mod check_valid_constant {
 fn struct_def(context: &mut Context, s: &mut N::StructDefinition) {
     assert!(context.constraints.is_empty());
     context.reset_for_module_item();
+    context.current_member_is_test = core::is_test_function(&s.attributes);
     context
         .env
         .add_warning_filter_scope(s.warning_filter.clone());
@@ -1256,13 +1278,6 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
 
         NE::Constant(m, c) => {
             let ty = core::make_constant_type(context, eloc, &m, &c);
-            if let Some(mident) = m {
-                context
-                    .used_module_members
-                    .entry(mident.value)
-                    .or_insert_with(BTreeSet::new)
-                    .insert(c.value());
-            }
             (ty, TE::Constant(m, c))
         }
 
@@ -1294,6 +1309,9 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             let args = exp_vec(context, nargs_);
             module_call(context, eloc, m, f, ty_args_opt, argloc, args)
         }
+        NE::MethodCall(ndotted, method, ty_args_opt, sp!(argloc, nargs_)) => {
+            method_call(context, eloc, ndotted, method, ty_args_opt, argloc, nargs_)
+        }
         NE::Builtin(b, sp!(argloc, nargs_)) => {
             let args = exp_vec(context, nargs_);
             builtin_call(context, eloc, b, argloc, args)
@@ -1324,7 +1342,7 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             );
             (ty, TE::IfElse(eb, et, ef))
         }
-        NE::While(nb, nloop) => {
+        NE::While(name, nb, nloop) => {
             let eb = exp(context, nb);
             let bloc = eb.exp.loc;
             subtype(
@@ -1334,14 +1352,26 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
                 eb.ty.clone(),
                 Type_::bool(bloc),
             );
-            let (_has_break, ty, body) = loop_body(context, eloc, false, nloop);
-            (sp(eloc, ty.value), TE::While(eb, body))
-        }
-        NE::Loop(nloop) => {
-            let (has_break, ty, body) = loop_body(context, eloc, true, nloop);
-            let eloop = TE::Loop { has_break, body };
+            let (_has_break, ty, body) = loop_body(context, eloc, name, false, nloop);
+            (sp(eloc, ty.value), TE::While(name, eb, body))
+        }
+        NE::Loop(name, nloop) => {
+            let (has_break, ty, body) = loop_body(context, eloc, name, true, nloop);
+            let eloop = TE::Loop {
+                name,
+                has_break,
+                body,
+            };
             (sp(eloc, ty.value), eloop)
         }
+        NE::Match(_, _) => {
+            // TODO type check and compile 'match' once exhaustiveness checking is in place
+            let msg = "Invalid 'match'. 'match' expressions are not yet supported";
+            context
+                .env
+                .add_diag(diag!(TypeSafety::UnsupportedMatch, (eloc, msg)));
+            (context.error_type(eloc), TE::UnresolvedError)
+        }
         NE::Block(nseq) => {
             let seq = sequence(context, nseq);
             (sequence_type(&seq).clone(), TE::Block(seq))
@@ -1382,34 +1412,25 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             subtype(context, eloc, || "Invalid abort", ecode.ty.clone(), code_ty);
             (sp(eloc, Type_::Anything), TE::Abort(ecode))
         }
-        NE::Break => {
-            if !context.in_loop() {
-                let msg = "Invalid usage of 'break'. 'break' can only be used inside a loop body";
-                context
-                    .env
-                    .add_diag(diag!(TypeSafety::InvalidLoopControl, (eloc, msg)))
-            }
-            let current_break_ty = sp(eloc, Type_::Unit);
-            let break_ty = match context.get_break_type() {
+        NE::Break(name, nvalue_opt) => {
+            let (current_break_ty, evalue_opt) = match nvalue_opt {
+                None => (sp(eloc, Type_::Unit), None),
+                Some(nvalue) => {
+                    let evalue = exp(context, nvalue);
+                    (evalue.ty.clone(), Some(evalue))
+                }
+            };
+            let break_ty = match context.get_break_type(name) {
                 None => current_break_ty,
                 Some(t) => {
                     let t = t.clone();
                     join(context, eloc, || "Invalid break.", t, current_break_ty)
                 }
             };
-            context.set_break_type(break_ty);
-            (sp(eloc, Type_::Anything), TE::Break)
-        }
-        NE::Continue => {
-            if !context.in_loop() {
-                let msg =
-                    "Invalid usage of 'continue'. 'continue' can only be used inside a loop body";
-                context
-                    .env
-                    .add_diag(diag!(TypeSafety::InvalidLoopControl, (eloc, msg)))
-            }
-            (sp(eloc, Type_::Anything), TE::Continue)
+            context.set_break_type(name, break_ty);
+            (sp(eloc, Type_::Anything), TE::Break(name, evalue_opt))
         }
+        NE::Continue(name) => (sp(eloc, Type_::Anything), TE::Continue(name)),
 
         NE::Dereference(nref) => {
             let eref = exp(context, nref);
@@ -1470,6 +1491,18 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             let items = es.into_iter().map(T::single_item).collect();
             (ty, TE::ExpList(items))
         }
+        NE::PackVariant(_, _, _, _, nfields) => {
+            // the arguments are still checked, but the declaration of the enum is reported as not
+            // supported, so the construction is an error
+            for (_, (_, narg)) in nfields {
+                exp_(context, narg);
+            }
+            let msg = "Invalid construction. 'enum' variants are not yet supported";
+            context
+                .env
+                .add_diag(diag!(TypeSafety::UnsupportedEnum, (eloc, msg)));
+            (context.error_type(eloc), TE::UnresolvedError)
+        }
         NE::Pack(m, n, ty_args_opt, nfields) => {
             let (bt, targs) = core::make_struct_type(context, eloc, &m, &n, ty_args_opt);
             let typed_nfields =
@@ -1561,7 +1594,21 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
             (context.error_type(eloc), TE::UnresolvedError)
         }
 
+        NE::MacroExpansion(ty_args, e) => {
+            for (ty, constraints) in ty_args {
+                let ty = core::instantiate(context, ty);
+                context.add_base_type_constraint(eloc, "Invalid type argument", ty.clone());
+                context.add_ability_set_constraint(eloc, None::<String>, ty, constraints);
+            }
+            let T::Exp {
+                ty,
+                exp: sp!(_, e_),
+            } = *exp(context, e);
+            (ty, e_)
+        }
+
         NE::BinopExp(..) => unreachable!(),
+        NE::MacroCall(..) => panic!("ICE macro calls should have been expanded in naming"),
     };
     T::exp(ty, sp(eloc, e_))
 }
@@ -1569,12 +1616,19 @@ fn exp_inner(context: &mut Context, sp!(eloc, ne_): N::Exp) -> T::Exp {
 fn loop_body(
     context: &mut Context,
     eloc: Loc,
+    name: N::BlockLabel,
     is_loop: bool,
     nloop: Box<N::Exp>,
 ) -> (bool, Type, Box<T::Exp>) {
-    let old_loop_info = context.enter_loop();
+    // a 'while' loop can only be exited with ()
+    let break_type = if is_loop {
+        None
+    } else {
+        Some(sp(eloc, Type_::Unit))
+    };
+    context.enter_loop(name, break_type);
     let eloop = exp(context, nloop);
-    let break_type_opt = context.exit_loop(old_loop_info);
+    let break_type_opt = context.exit_loop(name);
 
     let lloc = eloop.exp.loc;
     subtype(
@@ -1721,6 +1775,7 @@ fn lvalue(
         NL::Var {
             var,
             unused_binding,
+            ..
         } => {
             let var_ty = match case {
                 C::Bind => {
@@ -1798,6 +1853,19 @@ fn lvalue(
                 Some(mut_) => TL::BorrowUnpack(mut_, m, n, targs, tfields),
             }
         }
+        NL::UnpackVariant(_, _, _, _, fields) => {
+            // the inner binders are still declared, but the declaration of the enum is reported as
+            // not supported, so the deconstruction is an error
+            for (_, (_, nl)) in fields {
+                let ety = context.error_type(nl.loc);
+                lvalue(context, case, nl, ety);
+            }
+            let msg = "Invalid deconstruction. 'enum' variants are not yet supported";
+            context
+                .env
+                .add_diag(diag!(TypeSafety::UnsupportedEnum, (loc, msg)));
+            TL::Ignore
+        }
     };
     sp(loc, tl_)
 }
@@ -1975,6 +2043,18 @@ fn exp_dotted(
                 field_ty,
             )
         }
+        NE::Index(_, _) => {
+            // TODO resolve to a vector borrow, or to the index syntax method of the struct
+            let msg = "Invalid index. Index syntax is not yet supported outside of specifications";
+            context
+                .env
+                .add_diag(diag!(TypeSafety::UnsupportedIndex, (dloc, msg)));
+            // a mutable reference can be used in any position, so no further errors are reported
+            let ty = context.error_type(dloc);
+            let ref_ty = sp(dloc, Type_::Ref(true, Box::new(ty.clone())));
+            let e = T::exp(ref_ty, sp(dloc, T::UnannotatedExp_::UnresolvedError));
+            (ExpDotted_::Exp(Box::new(e)), ty)
+        }
     };
     (sp(dloc, edot_), ty)
 }
@@ -2124,14 +2204,133 @@ fn module_call(
         parameter_types: params_ty_list,
         acquires,
     };
-    context
-        .used_module_members
-        .entry(m.value)
-        .or_insert_with(BTreeSet::new)
-        .insert(f.value());
     (ret_ty, T::UnannotatedExp_::ModuleCall(Box::new(call)))
 }
 
+/// Elaborates `e.f(args)` into a call of the function `f` defined in the module of the type of
+/// `e`, or of the function aliased as `f` for that type by a `use fun`, with `e` as its first
+/// argument. The receiver is borrowed (mutably or not) if the first
+/// parameter of `f` is a reference, and copied out of any reference otherwise.
+fn method_call(
+    context: &mut Context,
+    loc: Loc,
+    ndotted: N::ExpDotted,
+    method: Name,
+    ty_args_opt: Option<Vec<Type>>,
+    argloc: Loc,
+    nargs_: Vec<N::Exp>,
+) -> (Type, T::UnannotatedExp_) {
+    use T::UnannotatedExp_ as TE;
+    let (edotted, receiver_ty) = exp_dotted(context, "method call", ndotted);
+    let resolved = resolve_method(context, loc, receiver_ty.clone(), &method);
+    let args = exp_vec(context, nargs_);
+    let Some((m, f)) = resolved else {
+        assert!(context.env.has_errors());
+        return (context.error_type(loc), TE::UnresolvedError);
+    };
+
+    let borrow_receiver = context
+        .function_info(&m, &f)
+        .signature
+        .parameters
+        .first()
+        .and_then(|(_, _, sp!(_, ty_))| match ty_ {
+            Type_::Ref(mut_, _) => Some(*mut_),
+            _ => None,
+        });
+    let receiver = match (borrow_receiver, edotted) {
+        (Some(mut_), edotted) => exp_dotted_to_borrow(context, loc, mut_, edotted),
+        (None, sp!(_, ExpDotted_::TmpBorrow(e, _))) => *e,
+        (None, sp!(dloc, ExpDotted_::Exp(e))) => {
+            context.add_ability_constraint(
+                dloc,
+                Some(format!(
+                    "Invalid implicit copy of the receiver of '{}' without the '{}' ability",
+                    method,
+                    Ability_::COPY,
+                )),
+                receiver_ty.clone(),
+                Ability_::Copy,
+            );
+            T::exp(receiver_ty, sp(dloc, TE::Dereference(e)))
+        }
+        (None, edotted) => exp_dotted_to_owned_value(context, loc, edotted, receiver_ty),
+    };
+
+    let args = std::iter::once(receiver).chain(args).collect();
+    module_call(context, loc, m, f, ty_args_opt, argloc, args)
+}
+
+/// Finds the function aliased as `method` for the struct type `ty` by a `use fun` of the current
+/// module, or else the function `method` in the module defining `ty`.
+fn resolve_method(
+    context: &mut Context,
+    loc: Loc,
+    ty: Type,
+    method: &Name,
+) -> Option<(ModuleIdent, FunctionName)> {
+    use TypeName_::*;
+    use Type_::*;
+    const UNINFERRED_MSG: &str = "Could not infer the type before method call. Try annotating here";
+    let msg = || format!("Unbound method '{}'", method);
+    match core::ready_tvars(&context.subst, ty) {
+        sp!(_, UnresolvedError) => None,
+        sp!(tloc, Anything) => {
+            context.env.add_diag(diag!(
+                TypeSafety::UninferredType,
+                (loc, msg()),
+                (tloc, UNINFERRED_MSG),
+            ));
+            None
+        }
+        sp!(tloc, Var(i)) if !context.subst.is_num_var(i) => {
+            context.env.add_diag(diag!(
+                TypeSafety::UninferredType,
+                (loc, msg()),
+                (tloc, UNINFERRED_MSG),
+            ));
+            None
+        }
+        sp!(tloc, Apply(_, sp!(_, ModuleType(m, n)), _)) => {
+            let use_fun = context
+                .use_funs
+                .get(&(m.value, n.0.value))
+                .and_then(|methods| methods.get(&method.value));
+            if let Some(use_fun) = use_fun {
+                return Some((use_fun.module, use_fun.function));
+            }
+            let f = FunctionName(*method);
+            if context.modules.module(&m).functions.contains_key(&f) {
+                Some((m, f))
+            } else {
+                let smsg = format!(
+                    "Methods of '{}::{}' are functions declared in '{}', which has no function \
+                     '{}'",
+                    &m, &n, &m, method
+                );
+                context.env.add_diag(diag!(
+                    NameResolution::UnboundModuleMember,
+                    (loc, msg()),
+                    (tloc, smsg),
+                ));
+                None
+            }
+        }
+        t => {
+            let smsg = format!(
+                "Expected a struct type but got: {}",
+                core::error_format(&t, &context.subst)
+            );
+            context.env.add_diag(diag!(
+                TypeSafety::ExpectedSpecificType,
+                (loc, msg()),
+                (t.loc, smsg),
+            ));
+            None
+        }
+    }
+}
+
 fn builtin_call(
     context: &mut Context,
     loc: Loc,
@@ -2342,37 +2541,13 @@ fn make_arg_types<S: std::fmt::Display, F: Fn() -> S>(
     given
 }
 
-//**************************************************************************************************
-// Utils
-//**************************************************************************************************
-
-fn process_attributes(context: &mut Context, all_attributes: &Attributes) {
-    for (_, _, attr) in all_attributes {
-        match &attr.value {
-            Attribute_::Name(_) => (),
-            Attribute_::Parameterized(_, attrs) => process_attributes(context, attrs),
-            Attribute_::Assigned(_, val) => {
-                let AttributeValue_::ModuleAccess(mod_access) = &val.value else {
-                    continue;
-                };
-                if let ModuleAccess_::ModuleAccess(mident, name) = mod_access.value {
-                    // conservatively assume that each `ModuleAccess` refers to a constant name
-                    context
-                        .used_module_members
-                        .entry(mident.value)
-                        .or_insert_with(BTreeSet::new)
-                        .insert(name.value);
-                }
-            }
-        }
-    }
-}
-
 //**************************************************************************************************
 // Module-wide warnings
 //**************************************************************************************************
 
-/// Generates warnings for unused (private) functions and unused constants.
+/// Generates warnings for friends that never call a 'public(friend)' function of the module. Unused
+/// constants and functions are reported by naming, which also sees the ones used in the bodies of
+/// macros.
 fn gen_unused_warnings(context: &mut Context, mident: &ModuleIdent_, mdef: &T::ModuleDefinition) {
     if !mdef.is_source_module {
         // generate warnings only for modules compiled in this pass rather than for all modules
@@ -2381,58 +2556,20 @@ fn gen_unused_warnings(context: &mut Context, mident: &ModuleIdent_, mdef: &T::M
         return;
     }
 
-    let is_sui_mode = context.env.package_config(mdef.package_name).flavor == Flavor::Sui;
     context
         .env
         .add_warning_filter_scope(mdef.warning_filter.clone());
-
-    for (loc, name, c) in &mdef.constants {
-        context
-            .env
-            .add_warning_filter_scope(c.warning_filter.clone());
-
-        let members = context.used_module_members.get(mident);
-        if members.is_none() || !members.unwrap().contains(name) {
-            let msg = format!("The constant '{name}' is never used. Consider removing it.");
-            context
-                .env
-                .add_diag(diag!(UnusedItem::Constant, (loc, msg)))
-        }
-
-        context.env.pop_warning_filter_scope();
-    }
-
-    for (loc, name, fun) in &mdef.functions {
-        if fun.attributes.iter().any(|(_, n, _)| {
-            n == &AttributeName_::Known(KnownAttribute::Testing(TestingAttribute::Test))
-        }) {
-            // functions with #[test] attribute are implicitly used
-            continue;
-        }
-        if is_sui_mode && *name == sui_mode::INIT_FUNCTION_NAME {
-            // a Sui-specific filter to avoid signaling that the init function is unused
-            continue;
-        }
-        context
-            .env
-            .add_warning_filter_scope(fun.warning_filter.clone());
-
-        let members = context.used_module_members.get(mident);
-        if fun.entry.is_none()
-            && matches!(fun.visibility, Visibility::Internal)
-            && (members.is_none() || !members.unwrap().contains(name))
-        {
-            // TODO: postponing handling of friend functions until we decide what to do with them
-            // vis-a-vis ideas around package-private
+    for (_, friend, f) in &mdef.friends {
+        if !context.used_friends.contains(&(*mident, *friend)) {
             let msg = format!(
-                "The non-'public', non-'entry' function '{name}' is never called. \
-                           Consider removing it."
+                "Module '{friend}' is declared as a friend, but never calls a '{}' function of \
+                 this module. Consider removing the declaration.",
+                Visibility::FRIEND
             );
             context
                 .env
-                .add_diag(diag!(UnusedItem::Function, (loc, msg)))
+                .add_diag(diag!(UnusedItem::Friend, (f.loc, msg)))
         }
-        context.env.pop_warning_filter_scope();
     }
 
     context.env.pop_warning_filter_scope();
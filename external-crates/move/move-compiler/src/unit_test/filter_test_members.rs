@@ -192,12 +192,24 @@ fn create_test_poison(mloc: Loc) -> P::ModuleMember {
         sp(mloc, args_),
     );
 
+    // Marked as test-only since it calls into the test-only `0x1::UnitTest` module
+    let test_only_attr = sp(
+        mloc,
+        P::Attribute_::Name(sp(
+            mloc,
+            known_attributes::TestingAttribute::TEST_ONLY.into(),
+        )),
+    );
+
+    // #[test_only]
     // fun unit_test_poison() { 0x1::UnitTest::create_signers_for_testing(0); () }
     P::ModuleMember::Function(P::Function {
-        attributes: vec![],
+        doc: None,
+        attributes: vec![sp(mloc, vec![test_only_attr])],
         loc: mloc,
         visibility: P::Visibility::Internal,
         entry: Some(mloc), // it's a bit of a hack to avoid treating this function as unused
+        macro_: None,
         acquires: vec![],
         signature,
         name: P::FunctionName(sp(mloc, "unit_test_poison".into())),
@@ -242,7 +254,10 @@ fn test_attributes(attrs: &P::Attributes) -> Vec<(Loc, known_attributes::Testing
                 KnownAttribute::Testing(test_attr) => Some((attr.loc, test_attr)),
                 KnownAttribute::Verification(_)
                 | KnownAttribute::Native(_)
-                | KnownAttribute::Diagnostic(_) => None,
+                | KnownAttribute::Diagnostic(_)
+                | KnownAttribute::Syntax(_)
+                | KnownAttribute::Deprecation(_)
+                | KnownAttribute::Cfg(_) => None,
             },
         )
         .collect()
@@ -69,7 +69,10 @@ fn verification_attributes(
                 KnownAttribute::Verification(verify_attr) => Some((attr.loc, verify_attr)),
                 KnownAttribute::Testing(_)
                 | KnownAttribute::Native(_)
-                | KnownAttribute::Diagnostic(_) => None,
+                | KnownAttribute::Diagnostic(_)
+                | KnownAttribute::Syntax(_)
+                | KnownAttribute::Deprecation(_)
+                | KnownAttribute::Cfg(_) => None,
             },
         )
         .collect()
@@ -0,0 +1,10 @@
+module 0x42::m {
+    public enum Empty {}
+
+    public enum Duplicates {
+        A,
+        A(u64),
+        B { x: u64, x: u8 },
+        c,
+    }
+}
@@ -0,0 +1,27 @@
+module 0x42::m {
+    use std::string::String;
+
+    public fun unknown_escape(): String {
+        "a\qb"
+    }
+
+    public fun invalid_hex(): String {
+        "\xZZ"
+    }
+
+    public fun invalid_unicode_digit(): String {
+        "\u{12G4}"
+    }
+
+    public fun invalid_unicode_scalar(): String {
+        "\u{D800}"
+    }
+
+    public fun too_many_unicode_digits(): String {
+        "\u{1234567}"
+    }
+
+    public fun missing_unicode_brace(): String {
+        "\u1234"
+    }
+}
@@ -0,0 +1,13 @@
+module 0x42::m {
+    use std::string::String;
+
+    const BYTES: vector<u8> = "\xFF";
+
+    public fun truncated(): String {
+        "\xE2\x9C"
+    }
+
+    public fun bytes(): vector<u8> {
+        BYTES
+    }
+}
@@ -0,0 +1,47 @@
+module 0x42::shapes {
+    public enum Shape<T> has copy, drop {
+        Empty,
+        Circle(T),
+        Rectangle { width: T, height: T },
+    }
+
+    public fun empty(): Shape<u64> {
+        Shape::Empty
+    }
+
+    public fun circle(r: u64): Shape<u64> {
+        Shape::Circle(r)
+    }
+
+    public fun rectangle(width: u64, height: u64): Shape<u64> {
+        Shape::Rectangle<u64> { width, height }
+    }
+
+    public fun width(s: Shape<u64>): u64 {
+        let Shape::Rectangle { width, height: _ } = s;
+        width
+    }
+
+    public fun radius(s: Shape<u64>): u64 {
+        let Shape::Circle(r) = s;
+        r
+    }
+
+    public fun area(s: Shape<u64>): u64 {
+        match (s) {
+            Shape::Empty => 0,
+            Shape::Circle(r) => 3 * r * r,
+            Shape::Rectangle { width, height } => width * height,
+        }
+    }
+}
+
+module 0x42::m {
+    use 0x42::shapes::{Self, Shape};
+
+    // variants resolve through the alias of the enum, but enums are not yet supported in typing
+    fun t(): Shape<u8> {
+        let _ = shapes::Shape::Circle<u8>(0);
+        Shape::Empty
+    }
+}
@@ -0,0 +1,31 @@
+module 0x42::m {
+    public enum E has drop {
+        A,
+        B(u64),
+        C { x: u64 },
+    }
+
+    public struct S has drop { e: E }
+
+    fun unbound_variants() {
+        let _ = E::D;
+        let _ = E::D(0);
+        let _ = E::D { x: 0 };
+        let E::D(_) = E::A;
+    }
+
+    fun mismatched_fields(e: E) {
+        let _ = E::B;
+        let _ = E::A(0);
+        let _ = E::B { x: 0 };
+        let _ = E::C(0);
+        let _ = E::B(0, 1);
+        let E::C(_) = e;
+    }
+
+    fun mismatched_positions(): E::A {
+        let _ = E::A<u64> {};
+        let _ = E { };
+        E::A
+    }
+}
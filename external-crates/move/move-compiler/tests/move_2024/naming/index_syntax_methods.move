@@ -0,0 +1,13 @@
+module 0x42::m {
+    public struct Table<phantom K, V> has drop { values: vector<V> }
+
+    #[syntax(index)]
+    public fun borrow<K, V>(t: &Table<K, V>, i: u64): &V {
+        std::vector::borrow(&t.values, i)
+    }
+
+    #[syntax(index)]
+    public fun borrow_mut<K, V>(t: &mut Table<K, V>, i: u64): &mut V {
+        std::vector::borrow_mut(&mut t.values, i)
+    }
+}
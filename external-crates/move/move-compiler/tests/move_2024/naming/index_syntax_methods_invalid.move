@@ -0,0 +1,61 @@
+module 0x42::m {
+    public struct S has drop { f: u64 }
+    public struct T has drop { f: u64 }
+
+    #[syntax(index)]
+    fun no_parameters(): &u64 {
+        abort 0
+    }
+
+    #[syntax(index)]
+    fun by_value(_s: S): &u64 {
+        abort 0
+    }
+
+    #[syntax(index)]
+    fun not_a_struct(_v: &vector<u64>, _i: u64): &u64 {
+        abort 0
+    }
+
+    #[syntax(index)]
+    fun mismatched_return(s: &mut S): &u64 {
+        &s.f
+    }
+
+    #[syntax(index)]
+    fun first(s: &S): &u64 {
+        &s.f
+    }
+
+    #[syntax(index)]
+    fun second(s: &S): &u64 {
+        &s.f
+    }
+
+    #[syntax(index)]
+    fun other_mut(t: &mut T): &mut u64 {
+        &mut t.f
+    }
+
+    #[syntax(index)]
+    fun another_mut(t: &mut T): &mut u64 {
+        &mut t.f
+    }
+
+    #[syntax]
+    fun no_kind(s: &S): &u64 {
+        &s.f
+    }
+
+    #[syntax(for)]
+    fun unknown_kind(s: &S): &u64 {
+        &s.f
+    }
+}
+
+module 0x42::n {
+    #[syntax(index)]
+    fun foreign_struct(_s: &0x42::m::S): &u64 {
+        abort 0
+    }
+}
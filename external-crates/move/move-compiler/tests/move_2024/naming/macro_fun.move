@@ -0,0 +1,42 @@
+module 0x42::a {
+    public struct Box<T> has copy, drop { value: T }
+
+    public fun new<T>(value: T): Box<T> {
+        Box { value }
+    }
+
+    public fun value<T: copy>(b: &Box<T>): T {
+        b.value
+    }
+
+    // the locals of the body cannot capture or shadow the locals at the call site
+    public macro fun swap_sum(x: u64, y: u64): u64 {
+        let tmp = x;
+        let x = y;
+        tmp + x
+    }
+
+    public macro fun wrap<T>(v: T): Box<T> {
+        new(v)
+    }
+
+    public macro fun wrap_twice<T>(v: T): Box<Box<T>> {
+        wrap!(wrap!<T>(v))
+    }
+}
+
+module 0x42::b {
+    use 0x42::a;
+
+    public fun hygiene(): u64 {
+        let tmp = 1;
+        let x = 2;
+        a::swap_sum!(tmp, x) + tmp + x
+    }
+
+    public fun generic(): u64 {
+        let b = a::wrap!(1u64);
+        let bb = a::wrap_twice!<u64>(2);
+        a::value(&b) + a::value(&a::value(&bb))
+    }
+}
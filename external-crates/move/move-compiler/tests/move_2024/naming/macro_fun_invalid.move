@@ -0,0 +1,45 @@
+module 0x42::a {
+    macro fun internal(x: u64): u64 { x }
+
+    public macro fun ret(x: u64): u64 {
+        return x
+    }
+
+    public macro fun brk() {
+        break
+    }
+
+    public macro fun rec(x: u64): u64 {
+        rec!(x)
+    }
+
+    public fun not_macro(x: u64): u64 { x }
+
+    public fun call_internal(): u64 {
+        internal!(1)
+    }
+}
+
+module 0x42::b {
+    use 0x42::a;
+
+    public fun t0(): u64 {
+        a::internal!(1)
+    }
+
+    public fun t1(): u64 {
+        a::not_macro!(1)
+    }
+
+    public fun t2(): u64 {
+        a::ret(1)
+    }
+
+    public fun t3(): u64 {
+        a::rec!(1)
+    }
+
+    public fun t4(): u64 {
+        a::ret!(1, 2)
+    }
+}
@@ -0,0 +1,20 @@
+module 0x42::m {
+    public struct Pair<T>(T, T) has drop;
+    public struct Point has drop { x: u64, y: u64 }
+
+    // patterns resolve, but 'match' is not yet supported in typing
+    fun pair(p: Pair<u64>): u64 {
+        match (p) {
+            Pair(0, y) => y,
+            Pair(x, _) if (x > 10) => x,
+            Pair<u64>(x, y) => x + y,
+        }
+    }
+
+    fun point(p: Point): u64 {
+        match (p) {
+            Point { x: 0, y } => y,
+            Point { x, y: _ } => x,
+        }
+    }
+}
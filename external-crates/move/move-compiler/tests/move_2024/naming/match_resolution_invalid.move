@@ -0,0 +1,34 @@
+module 0x42::m {
+    public struct Pair<T>(T, T) has drop;
+    public struct Point has drop { x: u64, y: u64 }
+
+    fun unbound(p: Pair<u64>): u64 {
+        match (p) {
+            Missing(x, _) => x,
+            _ => 0,
+        }
+    }
+
+    fun duplicate_binder(p: Pair<u64>): u64 {
+        match (p) {
+            Pair(x, x) => x,
+        }
+    }
+
+    fun positional_mismatch(p: Pair<u64>, q: Point): u64 {
+        match (p) {
+            Pair { x, y: _ } => x,
+            Pair(x) => x,
+        };
+        match (q) {
+            Point(x, _) => x,
+        }
+    }
+
+    fun binders_out_of_scope(p: Point): u64 {
+        match (p) {
+            Point { x, y: _ } => x,
+            Point { x: _, y: _ } => x,
+        }
+    }
+}
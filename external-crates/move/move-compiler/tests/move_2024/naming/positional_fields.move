@@ -0,0 +1,33 @@
+module 0x42::m {
+    public struct Empty() has copy, drop;
+    public struct Pair<T>(T, u64) has copy, drop;
+    public struct Wrapper(Pair<bool>);
+
+    public fun empty(): Empty {
+        Empty()
+    }
+
+    public fun make(x: bool, n: u64): Wrapper {
+        Wrapper(Pair(x, n))
+    }
+
+    public fun make_u64(n: u64): Pair<u64> {
+        Pair<u64>(n, n + 1)
+    }
+
+    public fun unwrap(w: Wrapper): (bool, u64) {
+        let Wrapper(Pair(x, n)) = w;
+        (x, n)
+    }
+
+    public fun unwrap_assign(w: Wrapper): (bool, u64) {
+        let x;
+        let n;
+        Wrapper(Pair(x, n)) = w;
+        (x, n)
+    }
+
+    public fun unpack_empty(e: Empty) {
+        let Empty() = e;
+    }
+}
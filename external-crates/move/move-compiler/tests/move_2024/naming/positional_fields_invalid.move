@@ -0,0 +1,28 @@
+module 0x42::m {
+    public struct Named has drop { x: u64 }
+    public struct Pos(u64, bool) has drop;
+
+    public fun t0(): Named {
+        Named(0)
+    }
+
+    public fun t1(): Pos {
+        Pos { x: 0 }
+    }
+
+    public fun t2(p: Pos) {
+        let Pos { x: _ } = p;
+    }
+
+    public fun t3(n: Named) {
+        let Named(_) = n;
+    }
+
+    public fun t4(): Pos {
+        Pos(0)
+    }
+
+    public fun t5(p: Pos) {
+        let Pos(_, _, _) = p;
+    }
+}
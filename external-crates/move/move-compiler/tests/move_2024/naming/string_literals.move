@@ -0,0 +1,21 @@
+module 0x42::m {
+    use std::string::String;
+
+    const NAME: vector<u8> = "hello";
+
+    public fun greeting(): String {
+        "hello, world!\n"
+    }
+
+    public fun escapes(): String {
+        "caf\u{E9} \xE2\x9C\x93 \"quoted\" \\ \t"
+    }
+
+    public fun empty(): String {
+        ""
+    }
+
+    public fun name(): vector<u8> {
+        NAME
+    }
+}
@@ -0,0 +1,49 @@
+module 0x42::table {
+    public struct Table<phantom K, phantom V> has store, drop {}
+    public struct Key has copy, drop, store {}
+
+    public fun new<K, V>(): Table<K, V> {
+        Table {}
+    }
+
+    public fun key(): Key {
+        Key {}
+    }
+}
+
+module 0x42::m {
+    use 0x42::table::{Self, Table as Map, Key};
+
+    public struct S has drop {
+        balances: Map<Key, u64>,
+    }
+
+    fun make(): Map<Key, u64> {
+        table::new<Key, u64>()
+    }
+
+    fun wrap(balances: Map<Key, u64>): S {
+        S { balances }
+    }
+
+    fun nested(): vector<Map<Key, Map<u8, u64>>> {
+        vector[table::new<Key, Map<u8, u64>>()]
+    }
+
+    // type parameters shadow aliases
+    fun shadowed_by_tparam<Key: drop>(k: Key): Key {
+        k
+    }
+
+    // so do aliases in inner scopes
+    fun shadowed_by_use(): u64 {
+        use 0x42::table::Key as Map;
+        let _k: Map = table::key();
+        0
+    }
+
+    fun unpack(s: S): Map<Key, u64> {
+        let S { balances } = s;
+        balances
+    }
+}
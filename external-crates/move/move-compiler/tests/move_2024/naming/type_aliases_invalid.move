@@ -0,0 +1,14 @@
+module 0x42::table {
+    public struct Table<phantom K, phantom V> has store, drop {}
+}
+
+module 0x42::m {
+    use 0x42::table::Table as Map;
+    use 0x42::table::Table as Unused;
+
+    fun too_few(_m: Map<u64>) {}
+
+    fun too_many(_m: Map<u64, u64, u64>) {}
+
+    fun with_tparam<Map>(_m: Map<u64, u64>) {}
+}
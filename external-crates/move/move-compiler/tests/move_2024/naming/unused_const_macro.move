@@ -0,0 +1,9 @@
+// constants used only in the body of a macro, even one that is never called, are used
+module a::m {
+    const USED_IN_MACRO: u64 = 0;
+    const UNUSED: u64 = 1;
+
+    public macro fun f(): u64 {
+        USED_IN_MACRO
+    }
+}
@@ -0,0 +1,18 @@
+// functions called only in the body of a macro, even one that is never called, or only with the
+// method syntax, are used
+module a::m {
+    public struct S has drop {}
+
+    fun used_in_macro(): u64 { 0 }
+    fun used_as_method(_: &S): u64 { 1 }
+    fun unused(): u64 { 2 }
+    macro fun unused_macro(): u64 { 3 }
+
+    public macro fun f(): u64 {
+        used_in_macro()
+    }
+
+    public fun g(s: &S): u64 {
+        s.used_as_method()
+    }
+}
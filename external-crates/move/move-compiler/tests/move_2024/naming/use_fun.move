@@ -0,0 +1,49 @@
+module 0x42::table {
+    public struct Table<phantom K, phantom V> has drop { size: u64 }
+
+    public fun length<K, V>(self: &Table<K, V>): u64 {
+        self.size
+    }
+}
+
+module 0x42::table_ext {
+    use 0x42::table::Table;
+
+    public fun is_empty<K, V>(t: &Table<K, V>): bool {
+        t.length() == 0
+    }
+
+    public fun into_size<K, V>(t: Table<K, V>): u64 {
+        t.length()
+    }
+}
+
+module 0x42::m {
+    use 0x42::table::Table;
+    use 0x42::table_ext;
+
+    use fun table_ext::is_empty as Table.is_empty;
+    use fun 0x42::table_ext::into_size as 0x42::table::Table.size;
+    // aliases take precedence over the functions of the module declaring the struct
+    use fun twice as Table.length;
+
+    fun twice<K, V>(t: &Table<K, V>): u64 {
+        0x42::table::length(t) * 2
+    }
+
+    public fun calls(t: &Table<u64, bool>, owned: Table<u8, u8>): bool {
+        !t.is_empty() && owned.size() == t.length()
+    }
+}
+
+module 0x42::n {
+    use 0x42::table::Table as Map;
+    use 0x42::table_ext::is_empty;
+
+    // the type can be an alias, and the function a member alias
+    use fun is_empty as Map.empty;
+
+    public fun empty(m: &Map<u64, u64>): bool {
+        m.empty()
+    }
+}
@@ -0,0 +1,23 @@
+module 0x42::table {
+    public struct Table has drop {}
+    public struct Other has drop {}
+
+    public fun length(_self: &Table): u64 { 0 }
+    public fun other(_self: &Other): u64 { 0 }
+    public fun no_parameters(): u64 { 0 }
+}
+
+module 0x42::m {
+    use 0x42::table::{Self, Table};
+
+    use fun table::other as Table.other;
+    use fun table::no_parameters as Table.none;
+    use fun table::missing as Table.missing;
+    use fun unbound as Table.unbound;
+    use fun table::length as Table.size;
+    use fun table::length as Table.size;
+
+    public fun in_block() {
+        use fun table::length as Table.len;
+    }
+}
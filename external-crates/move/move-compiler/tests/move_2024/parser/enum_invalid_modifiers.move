@@ -0,0 +1,9 @@
+module 0x42::m {
+    enum Internal { A }
+
+    public(package) enum Package { A }
+
+    public entry enum Entry { A }
+
+    public native enum Native { A }
+}
@@ -0,0 +1,18 @@
+module 0x42::m {
+    public struct S { f: u64 }
+
+    public fun sum(mut x: u64, s: S): u64 {
+        let mut y = x;
+        let S { mut f } = s;
+        f = f + 1;
+        y = y + f;
+        x = x + y;
+        x
+    }
+
+    // 'mut' is only a modifier when followed by the name of the local
+    public fun mut_as_name(mut: u64): u64 {
+        let mut = mut + 1;
+        mut
+    }
+}
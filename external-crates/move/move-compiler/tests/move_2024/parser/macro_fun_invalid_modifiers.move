@@ -0,0 +1,8 @@
+module 0x42::m {
+    macro native fun f();
+    entry macro fun g() {}
+    public macro struct S {}
+    macro const C: u64 = 0;
+
+    public fun c(): u64 { C }
+}
@@ -0,0 +1,5 @@
+module 0x42::m {
+    public fun f(): vector<u8> {
+        "unterminated
+    }
+}
@@ -0,0 +1,41 @@
+module 0x42::m {
+    public fun find(v: &vector<u64>, x: u64): u64 {
+        let i = 0;
+        let n = std::vector::length(v);
+        loop {
+            if (i >= n) break n;
+            if (*std::vector::borrow(v, i) == x) break i;
+            i = i + 1;
+        }
+    }
+
+    public fun nested(n: u64): u64 {
+        let count = 0;
+        let i = 0;
+        'outer: while (i < n) {
+            i = i + 1;
+            let j = 0;
+            'inner: loop {
+                j = j + 1;
+                if (j > i) break 'inner;
+                if (j == 3) continue 'outer;
+                if (count > 100) break 'outer;
+                count = count + 1;
+            }
+        };
+        count
+    }
+
+    public fun labeled_value(): u64 {
+        'a: loop {
+            loop {
+                break 'a 42
+            }
+        }
+    }
+
+    public fun same_label_in_sequence(): u64 {
+        'a: loop { break 'a 1 };
+        'a: loop { break 'a 2 }
+    }
+}
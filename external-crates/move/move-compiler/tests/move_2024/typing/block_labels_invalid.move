@@ -0,0 +1,31 @@
+module 0x42::m {
+    public fun unbound() {
+        loop { break 'a };
+        while (true) { continue 'b };
+    }
+
+    public fun shadowed() {
+        'a: loop {
+            'a: loop { break 'a }
+        }
+    }
+
+    public fun mismatched_break_types(): u64 {
+        loop {
+            if (true) break 0;
+            break false
+        }
+    }
+
+    public fun while_break_with_value() {
+        'a: while (true) {
+            break 'a 0
+        }
+    }
+
+    public fun inner_break_type(): u64 {
+        'a: loop {
+            loop { break 'a true }
+        }
+    }
+}
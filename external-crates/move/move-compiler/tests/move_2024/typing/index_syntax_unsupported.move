@@ -0,0 +1,23 @@
+module 0x42::m {
+    public struct S has drop { f: u64 }
+    public struct Table has drop { values: vector<S> }
+
+    #[syntax(index)]
+    fun borrow(_t: &Table, _i: u64): &S {
+        abort 0
+    }
+
+    #[syntax(index)]
+    fun borrow_mut(_t: &mut Table, _i: u64): &mut S {
+        abort 0
+    }
+
+    fun index(t: &mut Table, v: vector<u64>) {
+        v[0];
+        &t[0];
+        &mut t[0];
+        t[0] = S { f: 0 };
+        t[0].f;
+        t.values[0].f = 1;
+    }
+}
@@ -0,0 +1,10 @@
+module 0x42::m {
+    // macros are type checked where they are declared, even if they are never called
+    public macro fun bad(x: u64): bool {
+        x + 1
+    }
+
+    public macro fun bad_generic<T>(x: T): T {
+        x + x
+    }
+}
@@ -0,0 +1,20 @@
+module 0x42::m {
+    public struct Box<T> has copy, drop { value: T }
+
+    public macro fun pair<T: copy>(v: T): (T, T) {
+        let x: T = v;
+        (x, x)
+    }
+
+    public macro fun wrap<T>(v: T): Box<T> {
+        let b: Box<T> = Box { value: v };
+        b
+    }
+
+    public fun t(): u64 {
+        // the type parameter is inferred once for all of its uses
+        let (a, b) = pair!(1);
+        let w = wrap!(a);
+        w.value + b
+    }
+}
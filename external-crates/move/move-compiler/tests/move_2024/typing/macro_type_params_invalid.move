@@ -0,0 +1,26 @@
+module 0x42::m {
+    public struct NoCopy has drop {}
+
+    public macro fun pair<T: copy>(v: T): (T, T) {
+        let x: T = v;
+        (x, x)
+    }
+
+    public macro fun same<T>(a: T, b: T): (T, T) {
+        (a, b)
+    }
+
+    // the constraints of the type parameters are checked, whether they are inferred or given
+    public fun inferred() {
+        let (_, _) = pair!(NoCopy {});
+    }
+
+    public fun given() {
+        let (_, _) = pair!<NoCopy>(NoCopy {});
+    }
+
+    // the type parameter is a single type for all of its uses
+    public fun mismatch() {
+        let (_, _) = same!(1u64, false);
+    }
+}
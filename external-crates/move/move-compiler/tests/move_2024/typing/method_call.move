@@ -0,0 +1,47 @@
+module 0x42::m {
+    public struct T has copy, drop { x: u64 }
+    public struct S has copy, drop { f: u64, t: T }
+
+    public fun value(self: &S): u64 {
+        self.f
+    }
+
+    public fun set(self: &mut S, f: u64) {
+        self.f = f
+    }
+
+    public fun destroy(self: S): u64 {
+        let S { f, t: _ } = self;
+        f
+    }
+
+    public fun x(self: &T): u64 {
+        self.x
+    }
+
+    public fun with<U: drop>(self: &S, _u: U): &S {
+        self
+    }
+
+    public fun receivers(s: S, r: &S, m: &mut S): u64 {
+        // locals are borrowed, references are passed as they are
+        s.set(1);
+        m.set(2);
+        let a = s.value() + r.value() + m.value();
+        // fields are borrowed from the receiver
+        let b = s.t.x() + r.t.x() + m.t.x();
+        // calls can be chained, and take type arguments
+        let c = r.with<u8>(0).with(false).value();
+        // values are moved or copied out of references
+        let d = r.destroy() + s.destroy();
+        a + b + c + d
+    }
+}
+
+module 0x42::n {
+    use 0x42::m::S;
+
+    public fun other_module(s: &S): u64 {
+        s.value()
+    }
+}
@@ -0,0 +1,11 @@
+module 0x42::m {
+    public struct S has drop { f: u64 }
+
+    public fun t0(u: u64) {
+        u.value();
+    }
+
+    public fun t1(s: S) {
+        s.value();
+    }
+}
@@ -0,0 +1,4 @@
+module 0x42::M {
+    // only the operation that aborts is reported, not the expressions containing it
+    const NESTED: u64 = ((1 / 0) + 1) * 2;
+}
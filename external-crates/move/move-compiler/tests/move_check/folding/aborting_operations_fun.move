@@ -0,0 +1,6 @@
+module 0x42::M {
+    // only the operation that aborts is reported, not the expressions containing it
+    public fun nested(x: u64): u64 {
+        x + (255u8 + 1 as u64)
+    }
+}
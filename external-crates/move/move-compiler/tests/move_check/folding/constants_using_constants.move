@@ -0,0 +1,15 @@
+module 0x42::M {
+    const BASE: u64 = 10;
+    const SCALED: u64 = BASE * BASE + 1;
+    // constants can use constants declared after them
+    const DIVIDED: u8 = (LATER as u8) / 7;
+    const LATER: u64 = SCALED - BASE;
+    const FLAGS: vector<bool> = vector[BASE > 5, LATER == 91, !ENABLED];
+    const ENABLED: bool = true;
+    const ADDR: address = @0x42;
+    const SAME_ADDR: bool = ADDR == @0x42;
+
+    public fun values(): (u64, u64, u8, vector<bool>, bool) {
+        (SCALED, LATER, DIVIDED, FLAGS, SAME_ADDR)
+    }
+}
@@ -0,0 +1,9 @@
+module 0x42::M {
+    const SELF: u64 = SELF + 1;
+
+    const A: u64 = B * 2;
+    const B: u64 = A + 1;
+
+    // constants using constants of a cycle are not reported again
+    const C: u64 = A;
+}
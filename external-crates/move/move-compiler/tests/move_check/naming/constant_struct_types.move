@@ -0,0 +1,9 @@
+module 0x42::M {
+    struct S has copy, drop { f: u64 }
+
+    // struct types are resolved in constants as anywhere else, even though typing rejects them
+    const C1: Missing = Missing { f: 0 };
+    const C2: vector<Missing> = vector[];
+    const C3: Self::Missing = Self::Missing { f: 0 };
+    const C4: vector<S> = vector[S { f: 0 }];
+}
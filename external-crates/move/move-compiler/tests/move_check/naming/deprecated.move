@@ -0,0 +1,36 @@
+module 0x42::m {
+    #[deprecated]
+    struct S has drop {}
+
+    #[deprecated(note = b"use 'g' instead")]
+    public fun f(): S { S {} }
+
+    public fun g() {}
+
+    #[deprecated(note = b"no longer used")]
+    const C: u64 = 0;
+
+    // uses in the declaring module are not deprecated
+    public fun c(): u64 { C }
+}
+
+#[deprecated(note = b"use '0x42::m' instead")]
+module 0x42::old {
+    public fun h() {}
+}
+
+module 0x42::n {
+    use 0x42::m::{Self, S};
+
+    fun uses(): S {
+        0x42::m::g();
+        0x42::old::h();
+        m::c();
+        m::f()
+    }
+
+    #[allow(deprecated_usage)]
+    fun allowed(): S {
+        m::f()
+    }
+}
@@ -0,0 +1,10 @@
+module 0x42::m {
+    #[deprecated = b"note"]
+    public fun f() {}
+
+    #[deprecated(note = 0)]
+    public fun h() {}
+
+    #[deprecated(note = b"note", since = b"1.0")]
+    public fun i() {}
+}
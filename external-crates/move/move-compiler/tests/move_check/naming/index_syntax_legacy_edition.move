@@ -0,0 +1,13 @@
+module 0x42::m {
+    struct S has drop { f: u64 }
+
+    #[syntax(index)]
+    fun borrow(s: &S, _i: u64): &u64 {
+        &s.f
+    }
+
+    fun index(s: &S, v: vector<u64>) {
+        v[0];
+        s[0];
+    }
+}
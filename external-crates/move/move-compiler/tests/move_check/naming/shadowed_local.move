@@ -0,0 +1,29 @@
+module 0x42::m {
+    fun shadows_parameter(x: u64): u64 {
+        let x = x + 1;
+        x
+    }
+
+    fun shadows_local(): u64 {
+        let x = 0;
+        let x = x + 1;
+        { let x = x + 1; x }
+    }
+
+    fun shadows_underscore(_x: u64): u64 {
+        let _x = 0;
+        let y = 0;
+        let _y = y;
+        y
+    }
+
+    #[allow(shadowed_local)]
+    fun allowed(x: u64): u64 {
+        let x = x + 1;
+        x
+    }
+
+    fun distinct_scopes(cond: bool): u64 {
+        if (cond) { let x = 0; x } else { let x = 1; x }
+    }
+}
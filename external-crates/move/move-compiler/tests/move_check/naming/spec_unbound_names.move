@@ -0,0 +1,37 @@
+module 0x42::m {
+    const C: u64 = 0;
+
+    struct S has drop { f: u64 }
+
+    public fun foo(x: u64): u64 {
+        let y = x + C;
+        spec {
+            assert y == x + C;
+            assert y == z;
+        };
+        y
+    }
+    spec foo {
+        ensures result == x + C;
+        ensures result == spec_plus(x, 0);
+        ensures result == w;
+        aborts_if missing(x);
+    }
+
+    spec S {
+        invariant f > 0;
+        invariant forall i in 0..f: i < g;
+    }
+
+    spec fun spec_plus(a: u64, b: u64): u64 {
+        let c = a + b;
+        c + C + d
+    }
+}
+
+module 0x42::n {
+    spec module {
+        fun spec_foo(x: u64): u64 { 0x42::m::spec_plus(x, 0x42::m::foo(x)) }
+        fun spec_bar(): u64 { 0x42::m::bar() }
+    }
+}
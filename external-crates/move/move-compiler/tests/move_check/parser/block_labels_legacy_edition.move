@@ -0,0 +1,5 @@
+module 0x42::m {
+    fun t() {
+        'a: loop { break 'a }
+    }
+}
@@ -0,0 +1,16 @@
+module 0x42::m {
+    #[cfg(feature = "metrics")]
+    public fun metrics() {}
+
+    #[cfg(feature = b"metrics")]
+    public fun byte_string_feature() {}
+
+    #[cfg]
+    public fun no_feature() {}
+
+    #[cfg(unix)]
+    public fun not_a_feature() {}
+
+    #[cfg(feature = 0)]
+    public fun not_a_string() {}
+}
@@ -0,0 +1,6 @@
+module 0x42::m {
+    // 'enum' is an ordinary identifier outside of the 2024 edition
+    fun enum(enum: u64): u64 { enum }
+
+    public enum E { A, B }
+}
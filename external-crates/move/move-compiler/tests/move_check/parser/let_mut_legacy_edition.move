@@ -0,0 +1,10 @@
+module 0x42::m {
+    struct S { f: u64 }
+
+    public fun sum(mut x: u64, s: S): u64 {
+        let mut y = x;
+        let S { mut f } = s;
+        x = y + f;
+        x
+    }
+}
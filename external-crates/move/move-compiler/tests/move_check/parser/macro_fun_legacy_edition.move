@@ -0,0 +1,7 @@
+module 0x42::m {
+    macro fun double(x: u64): u64 { x * 2 }
+
+    public fun test(): u64 {
+        double!(1)
+    }
+}
@@ -0,0 +1,8 @@
+module 0x42::m {
+    fun match(x: u64): u64 { x }
+
+    // 'match' is an ordinary identifier outside of the 2024 edition
+    fun call(): u64 {
+        match(0)
+    }
+}
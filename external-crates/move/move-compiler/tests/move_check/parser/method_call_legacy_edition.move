@@ -0,0 +1,11 @@
+module 0x42::m {
+    struct S has drop { f: u64 }
+
+    public fun value(self: &S): u64 {
+        self.f
+    }
+
+    public fun test(s: S): u64 {
+        s.value()
+    }
+}
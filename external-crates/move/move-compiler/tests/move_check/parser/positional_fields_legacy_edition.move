@@ -0,0 +1,8 @@
+module 0x42::m {
+    struct S(u64);
+
+    public fun value(s: S): u64 {
+        let S(x) = s;
+        x
+    }
+}
@@ -0,0 +1,7 @@
+module 0x42::m {
+    const NAME: vector<u8> = "hello";
+
+    public fun name(): vector<u8> {
+        NAME
+    }
+}
@@ -0,0 +1,10 @@
+module 0x2::X {
+    friend 0x3::Y;
+    public(friend) fun f_friend() {}
+}
+
+module 0x3::Y {
+    fun f_friend_call() {
+        0x2::X::f_friend()
+    }
+}
@@ -0,0 +1,14 @@
+module 0x42::m {
+    friend 0x42::used;
+    friend 0x42::unused;
+
+    public(friend) fun foo() {}
+}
+
+module 0x42::used {
+    public fun bar() {
+        0x42::m::foo()
+    }
+}
+
+module 0x42::unused {}
@@ -0,0 +1,13 @@
+module 0x42::M {
+    // tests expected to fail abort on purpose, their aborting operations are not reported
+    #[test]
+    #[expected_failure]
+    fun expected_underflow() {
+        0 - 1;
+    }
+
+    #[test]
+    fun unexpected_underflow() {
+        0 - 1;
+    }
+}
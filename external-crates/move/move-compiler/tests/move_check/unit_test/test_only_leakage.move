@@ -0,0 +1,32 @@
+// Check that non-test code cannot use #[test_only] members, even in test mode where they are not
+// filtered out
+address 0x1 {
+#[test_only]
+module TestUtils {
+    public fun helper() {}
+}
+
+module M {
+    #[test_only]
+    struct Fixture has drop {}
+
+    #[test_only]
+    public fun setup() {}
+
+    public fun leak_call() { setup() }
+
+    public fun leak_struct() { Fixture {}; }
+
+    #[test_only]
+    fun ok_test_only() { setup(); Fixture {}; }
+
+    #[test]
+    fun ok_test() { setup(); 0x1::TestUtils::helper() }
+}
+
+module N {
+    use 0x1::TestUtils;
+
+    public fun leak_module() { TestUtils::helper() }
+}
+}
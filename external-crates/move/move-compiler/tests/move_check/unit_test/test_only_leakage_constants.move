@@ -0,0 +1,19 @@
+// Check that non-test code cannot use #[test_only] constants, even in test mode where they are not
+// filtered out
+module 0x1::M {
+    #[test_only]
+    const FIXTURE: u64 = 0;
+
+    const LEAK_CONSTANT: u64 = FIXTURE + 1;
+
+    public fun leak(): u64 { FIXTURE }
+
+    #[test_only]
+    const OK_CONSTANT: u64 = FIXTURE + 1;
+
+    #[test_only]
+    fun ok_test_only(): u64 { FIXTURE }
+
+    #[test]
+    fun ok_test() { assert!(FIXTURE == 0, 0) }
+}
@@ -0,0 +1,40 @@
+// Check that non-test code cannot use #[test_only] types, in signatures and annotations, even in
+// test mode where they are not filtered out
+address 0x1 {
+#[test_only]
+module TestUtils {
+    struct Helper has copy, drop {}
+}
+
+module M {
+    use 0x1::TestUtils::Helper;
+
+    #[test_only]
+    struct Fixture has copy, drop {}
+
+    struct Wrapper<T> has drop { t: T }
+
+    struct LeakField has drop { f: Fixture }
+
+    public fun leak_param(_f: Fixture) {}
+
+    public fun leak_return(): vector<Fixture> { vector[] }
+
+    public fun leak_annotation() {
+        let _v: vector<Fixture> = vector[];
+    }
+
+    public fun leak_type_arg(w: Wrapper<Fixture>): Wrapper<Fixture> { w }
+
+    public fun leak_module_type(_h: &Helper) {}
+
+    #[test_only]
+    struct OkField has drop { f: Fixture }
+
+    #[test_only]
+    fun ok_test_only(f: Fixture): Wrapper<Fixture> {
+        let w: Wrapper<Fixture> = Wrapper { t: f };
+        w
+    }
+}
+}
@@ -12,7 +12,7 @@ use move_compiler::{
     command_line::compiler::move_check_for_errors,
     diagnostics::*,
     editions::{Edition, Flavor},
-    shared::{Flags, NumericalAddress, PackageConfig, PackagePaths},
+    shared::{CrossAddressFriends, Flags, NumericalAddress, PackageConfig, PackagePaths},
     Compiler, PASS_PARSER,
 };
 
@@ -22,6 +22,8 @@ const KEEP_TMP: &str = "KEEP";
 const TEST_EXT: &str = "unit_test";
 const VERIFICATION_EXT: &str = "verification";
 const UNUSED_EXT: &str = "unused";
+const CROSS_ADDRESS_FRIENDS_EXT: &str = "cross_address_friends";
+const SHADOWING_EXT: &str = "shadowing";
 
 const SUI_MODE_DIR: &str = "sui_mode";
 const MOVE_2024_DIR: &str = "move_2024";
@@ -140,14 +142,62 @@ fn testsuite(path: &Path, mut config: PackageConfig) -> datatest_stable::Result<
         )?;
     }
 
+    config
+        .warning_filter
+        .union(&WarningFilters::unused_warnings_filter_for_test());
+
+    // A case with friends out of the current address, that should also be compiled with them
+    // allowed with a warning
+    if path.with_extension(CROSS_ADDRESS_FRIENDS_EXT).exists() {
+        let friends_exp_path = format!(
+            "{}.{}.{}",
+            path.with_extension("").to_string_lossy(),
+            CROSS_ADDRESS_FRIENDS_EXT,
+            EXP_EXT
+        );
+        let friends_out_path = format!(
+            "{}.{}.{}",
+            path.with_extension("").to_string_lossy(),
+            CROSS_ADDRESS_FRIENDS_EXT,
+            OUT_EXT
+        );
+        run_test(
+            path,
+            Path::new(&friends_exp_path),
+            Path::new(&friends_out_path),
+            Flags::empty().set_cross_address_friends(CrossAddressFriends::Warn),
+            config.clone(),
+        )?;
+    }
+
+    // A case with shadowed locals, that should also be compiled with shadowing warnings enabled
+    if path.with_extension(SHADOWING_EXT).exists() {
+        let shadowing_exp_path = format!(
+            "{}.{}.{}",
+            path.with_extension("").to_string_lossy(),
+            SHADOWING_EXT,
+            EXP_EXT
+        );
+        let shadowing_out_path = format!(
+            "{}.{}.{}",
+            path.with_extension("").to_string_lossy(),
+            SHADOWING_EXT,
+            OUT_EXT
+        );
+        run_test(
+            path,
+            Path::new(&shadowing_exp_path),
+            Path::new(&shadowing_out_path),
+            Flags::empty().set_warn_shadowing(true),
+            config.clone(),
+        )?;
+    }
+
     let exp_path = path.with_extension(EXP_EXT);
     let out_path = path.with_extension(OUT_EXT);
 
     let flags = Flags::empty();
 
-    config
-        .warning_filter
-        .union(&WarningFilters::unused_warnings_filter_for_test());
     run_test(path, &exp_path, &out_path, flags, config)?;
     Ok(())
 }
@@ -455,13 +455,13 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
     /// enters them into the environment. Returns a vector for representing them in the target AST.
     pub fn analyze_and_add_params(
         &mut self,
-        params: &[(PA::Var, EA::Type)],
+        params: &[(PA::Mutability, PA::Var, EA::Type)],
         for_move_fun: bool,
     ) -> Vec<(Symbol, Type)> {
         params
             .iter()
             .enumerate()
-            .map(|(idx, (v, ty))| {
+            .map(|(idx, (_, v, ty))| {
                 let ty = self.translate_type(ty);
                 let sym = self.symbol_pool().make(v.0.value.as_str());
                 self.define_local(
@@ -991,10 +991,11 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                     }
                     let bind_loc = self.to_loc(&list.value[0].loc);
                     match &list.value[0].value {
-                        EA::LValue_::Var(maccess, _) => {
+                        EA::LValue_::Var(_, maccess, _) => {
                             let name = match &maccess.value {
                                 EA::ModuleAccess_::Name(n) => n,
-                                EA::ModuleAccess_::ModuleAccess(_, n) => n,
+                                EA::ModuleAccess_::ModuleAccess(_, n)
+                                | EA::ModuleAccess_::Variant(_, n) => n,
                             };
                             // Define the local. Currently we mimic
                             // Rust/ML semantics here, allowing to shadow with each let,
@@ -1066,6 +1067,10 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
     ) -> ExpData {
         let global_var_sym = match &maccess.value {
             EA::ModuleAccess_::ModuleAccess(..) => self.parent.module_access_to_qualified(maccess),
+            EA::ModuleAccess_::Variant(..) => {
+                self.error(loc, "enum variants are not supported in specifications");
+                return self.new_error_exp();
+            }
             EA::ModuleAccess_::Name(name) => {
                 // First try to resolve simple name as local.
                 let sym = self.symbol_pool().make(name.value.as_str());
@@ -1258,6 +1263,8 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
                     self.new_error_exp()
                 }
             }
+            // indexing in specs is expanded to `EA::Exp_::Index` instead
+            EA::ExpDotted_::Index(..) => unreachable!(),
         }
     }
 
@@ -1784,6 +1791,7 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
             let loc = self.to_loc(&bind.loc);
             match &bind.value {
                 EA::LValue_::Var(
+                    _,
                     Spanned {
                         value: EA::ModuleAccess_::Name(n),
                         ..
@@ -1880,6 +1888,7 @@ impl<'env, 'translator, 'module_translator> ExpTranslator<'env, 'translator, 'mo
             }
             match &bind.value {
                 EA::LValue_::Var(
+                    _,
                     Spanned {
                         value: EA::ModuleAccess_::Name(n),
                         ..
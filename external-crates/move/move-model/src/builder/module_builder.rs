@@ -189,18 +189,18 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
         &self,
         access: &EA::ModuleAccess,
     ) -> (Option<ModuleName>, Symbol) {
-        match &access.value {
-            EA::ModuleAccess_::Name(n) => (None, self.symbol_pool().make(n.value.as_str())),
-            EA::ModuleAccess_::ModuleAccess(m, n) => {
-                let loc = self.parent.to_loc(&m.loc);
-                let addr_bytes = self.parent.resolve_address(&loc, &m.value.address);
-                let module_name = ModuleName::from_address_bytes_and_name(
-                    addr_bytes,
-                    self.symbol_pool().make(m.value.module.0.value.as_str()),
-                );
-                (Some(module_name), self.symbol_pool().make(n.value.as_str()))
-            }
-        }
+        let (m, n) = match &access.value {
+            EA::ModuleAccess_::Name(n) => return (None, self.symbol_pool().make(n.value.as_str())),
+            EA::ModuleAccess_::ModuleAccess(m, n) => (m, n),
+            EA::ModuleAccess_::Variant(e, n) => (&e.value.0, n),
+        };
+        let loc = self.parent.to_loc(&m.loc);
+        let addr_bytes = self.parent.resolve_address(&loc, &m.value.address);
+        let module_name = ModuleName::from_address_bytes_and_name(
+            addr_bytes,
+            self.symbol_pool().make(m.value.module.0.value.as_str()),
+        );
+        (Some(module_name), self.symbol_pool().make(n.value.as_str()))
     }
 
     /// Converts a ModuleAccess into a qualified symbol which can be used for lookup of
@@ -304,29 +304,29 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
                             self.symbol_pool().make(""),
                         )
                     }
-                    EA::AttributeValue_::ModuleAccess(macc) => match macc.value {
-                        EA::ModuleAccess_::Name(n) => AttributeValue::Name(
-                            value_node_id,
-                            None,
-                            self.symbol_pool().make(n.value.as_str()),
-                        ),
-                        EA::ModuleAccess_::ModuleAccess(mident, n) => {
+                    EA::AttributeValue_::ModuleAccess(macc) => {
+                        let (mident_opt, n) = match &macc.value {
+                            EA::ModuleAccess_::Name(n) => (None, n),
+                            EA::ModuleAccess_::ModuleAccess(mident, n) => (Some(mident), n),
+                            EA::ModuleAccess_::Variant(e, n) => (Some(&e.value.0), n),
+                        };
+                        let module_name_opt = mident_opt.map(|mident| {
                             let addr_bytes = self.parent.resolve_address(
                                 &self.parent.to_loc(&macc.loc),
                                 &mident.value.address,
                             );
-                            let module_name = ModuleName::from_address_bytes_and_name(
+                            ModuleName::from_address_bytes_and_name(
                                 addr_bytes,
                                 self.symbol_pool()
                                     .make(mident.value.module.0.value.as_str()),
-                            );
-                            AttributeValue::Name(
-                                value_node_id,
-                                Some(module_name),
-                                self.symbol_pool().make(n.value.as_str()),
                             )
-                        }
-                    },
+                        });
+                        AttributeValue::Name(
+                            value_node_id,
+                            module_name_opt,
+                            self.symbol_pool().make(n.value.as_str()),
+                        )
+                    }
                 };
                 Attribute::Assign(node_id, self.symbol_pool().make(n.value.as_str()), v)
             }
@@ -871,6 +871,16 @@ impl<'env, 'translator> ModuleBuilder<'env, 'translator> {
                 }
                 Some(field_map)
             }
+            EA::StructFields::Positional(tys) => {
+                let mut field_map = BTreeMap::new();
+                for (idx, ty) in tys.iter().enumerate() {
+                    let field = PA::Field::positional(ty.loc, idx);
+                    let field_sym = et.symbol_pool().make(field.0.value.as_str());
+                    let field_ty = et.translate_type(ty);
+                    field_map.insert(field_sym, (idx, field_ty));
+                }
+                Some(field_map)
+            }
             EA::StructFields::Native(_) => None,
         };
         self.parent
@@ -30,7 +30,7 @@ use move_binary_format::{
 use move_compiler::{
     self,
     compiled_unit::{self, AnnotatedCompiledScript, AnnotatedCompiledUnit},
-    diagnostics::{Diagnostics, WarningFilters},
+    diagnostics::{codes::Severity, Diagnostics, WarningFilters},
     expansion::ast::{self as E, Address, ModuleDefinition, ModuleIdent, ModuleIdent_},
     parser::ast::{self as P, ModuleName as ParserModuleName},
     shared::{parse_named_address, unique_map::UniqueMap, NumericalAddress, PackagePaths},
@@ -293,8 +293,11 @@ pub fn run_model_builder_with_options_and_compilation_flags<
     // Check for bytecode verifier errors (there should not be any)
     let diags = compiled_unit::verify_units(&units);
     if !diags.is_empty() {
+        let has_errors = diags.max_severity() > Some(Severity::Warning);
         add_move_lang_diagnostics(&mut env, diags);
-        return Ok(env);
+        if has_errors {
+            return Ok(env);
+        }
     }
 
     // Now that it is known that the program has no errors, run the spec checker on verified units
@@ -376,7 +379,9 @@ fn add_move_lang_diagnostics(env: &mut GlobalEnv, diags: Diagnostics) {
         let loc = env.to_loc(&loc);
         Label::new(style, loc.file_id(), loc.span()).with_message(msg)
     };
-    for (severity, msg, primary_label, secondary_labels, notes) in diags.into_codespan_format() {
+    for (severity, msg, primary_label, secondary_labels, notes, _fixes) in
+        diags.into_codespan_format()
+    {
         let diag = Diagnostic::new(severity)
             .with_labels(vec![mk_label(true, primary_label)])
             .with_message(msg.to_string())
@@ -572,13 +577,17 @@ fn run_spec_checker(env: &mut GlobalEnv, units: Vec<AnnotatedCompiledUnit>, mut
                         warning_filter: _warning_filter,
                         package_name,
                         attributes,
+                        doc: None,
                         loc,
                         is_source_module: true,
                         friends: UniqueMap::new(),
                         structs: UniqueMap::new(),
+                        enums: UniqueMap::new(),
                         constants,
                         functions,
                         specs,
+                        type_aliases: UniqueMap::new(),
+                        use_funs: vec![],
                     };
                     let module = script_into_module(script.script);
                     (
@@ -79,6 +79,8 @@ static_symbols!(
     "event",
     "emit",
     "Receiving",
+    "utf8",
+    "loop",
 );
 
 /// The global, unique cache of strings.
@@ -17,7 +17,7 @@ use move_package::{compilation::build_plan::BuildPlan, BuildConfig};
 use move_unit_test::UnitTestingConfig;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     io::Write,
     path::{Path, PathBuf},
@@ -47,12 +47,13 @@ pub struct Test {
     /// List all tests
     #[clap(name = "list", short = 'l', long = "list")]
     pub list: bool,
-    /// Number of threads to use for running tests.
+    /// Number of threads to use for running tests. Tests are run in parallel, each in its own VM.
     #[clap(
         name = "num_threads",
         default_value = "8",
         short = 't',
-        long = "threads"
+        long = "threads",
+        visible_alias = "test-threads"
     )]
     pub num_threads: usize,
     /// Report test statistics at the end of testing. CSV report generated if 'csv' passed
@@ -139,6 +140,31 @@ pub enum UnitTestResult {
 }
 
 pub fn run_move_unit_tests<CW: Write + Send, TW: Write + Send>(
+    pkg_path: &Path,
+    build_config: move_package::BuildConfig,
+    unit_test_config: UnitTestingConfig,
+    natives: Vec<NativeFunctionRecord>,
+    cost_table: Option<CostTable>,
+    compute_coverage: bool,
+    compiler_writer: &mut CW,
+    test_writer: &mut TW,
+) -> Result<UnitTestResult> {
+    let (result, _) = run_move_unit_tests_with_gas_used(
+        pkg_path,
+        build_config,
+        unit_test_config,
+        natives,
+        cost_table,
+        compute_coverage,
+        compiler_writer,
+        test_writer,
+    )?;
+    Ok(result)
+}
+
+/// Same as `run_move_unit_tests`, but also returns the gas used by each test that passed, keyed
+/// by its fully qualified (`<address>::<module_name>::<fn_name>`) name.
+pub fn run_move_unit_tests_with_gas_used<CW: Write + Send, TW: Write + Send>(
     pkg_path: &Path,
     mut build_config: move_package::BuildConfig,
     mut unit_test_config: UnitTestingConfig,
@@ -147,7 +173,7 @@ pub fn run_move_unit_tests<CW: Write + Send, TW: Write + Send>(
     compute_coverage: bool,
     compiler_writer: &mut CW,
     test_writer: &mut TW,
-) -> Result<UnitTestResult> {
+) -> Result<(UnitTestResult, BTreeMap<String, u64>)> {
     let mut test_plan = None;
     build_config.test_mode = true;
     build_config.dev_mode = true;
@@ -241,13 +267,12 @@ pub fn run_move_unit_tests<CW: Write + Send, TW: Write + Send>(
 
     // Run the tests. If any of the tests fail, then we don't produce a coverage report, so cleanup
     // the trace files.
-    if !unit_test_config
-        .run_and_report_unit_tests(test_plan, Some(natives), cost_table, test_writer)
-        .unwrap()
-        .1
-    {
+    let (_, ok, gas_used) = unit_test_config
+        .run_and_report_unit_tests_with_gas_used(test_plan, Some(natives), cost_table, test_writer)
+        .unwrap();
+    if !ok {
         cleanup_trace();
-        return Ok(UnitTestResult::Failure);
+        return Ok((UnitTestResult::Failure, gas_used));
     }
 
     // Compute the coverage map. This will be used by other commands after this.
@@ -255,7 +280,7 @@ pub fn run_move_unit_tests<CW: Write + Send, TW: Write + Send>(
         let coverage_map = CoverageMap::from_trace_file(trace_path);
         output_map_to_file(coverage_map_path, &coverage_map).unwrap();
     }
-    Ok(UnitTestResult::Success)
+    Ok((UnitTestResult::Success, gas_used))
 }
 
 impl From<UnitTestResult> for ExitStatus {
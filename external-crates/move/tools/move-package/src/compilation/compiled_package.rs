@@ -574,7 +574,8 @@ impl CompiledPackage {
             Flags::testing()
         } else {
             Flags::empty()
-        };
+        }
+        .set_cross_address_friends(resolution_graph.build_options.cross_address_friends);
         // invoke the compiler
         let mut paths = deps_package_paths.clone();
         paths.push(sources_package_paths.clone());
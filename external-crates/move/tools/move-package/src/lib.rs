@@ -12,7 +12,10 @@ pub mod source_package;
 
 use anyhow::Result;
 use clap::*;
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    shared::CrossAddressFriends,
+};
 use move_core_types::account_address::AccountAddress;
 use move_model::model::GlobalEnv;
 use resolution::{dependency_graph::DependencyGraphBuilder, resolution_graph::ResolvedGraph};
@@ -89,6 +92,23 @@ pub struct BuildConfig {
     /// warning suppression in dependency packages.
     #[clap(long = "dependencies-are-root", global = true)]
     pub deps_as_root: bool,
+
+    /// How modules of other addresses declared as friends are treated. They are rejected by
+    /// default, as the bytecode verifier does not accept them.
+    #[clap(
+        long = "cross-address-friends",
+        global = true,
+        value_enum,
+        default_value_t
+    )]
+    #[serde(default)]
+    pub cross_address_friends: CrossAddressFriends,
+
+    /// Features of the root package to enable, in addition to those it enables by default. Items
+    /// annotated with `#[cfg(feature = "<name>")]` are only compiled if their feature is enabled.
+    #[clap(long = "features", value_delimiter = ',', global = true)]
+    #[serde(default)]
+    pub features: Vec<String>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd)]
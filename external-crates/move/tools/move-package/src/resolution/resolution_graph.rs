@@ -461,6 +461,23 @@ impl Package {
                 .or(config.default_edition)
                 .unwrap_or_default(),
             warning_filter: WarningFilters::new_for_source(),
+            // the warnings of dependencies are not reported, so neither are their lint levels
+            lint_levels: if is_dependency {
+                BTreeMap::new()
+            } else {
+                self.source_package.lints.clone()
+            },
+            // only the features of the root package can be enabled from the build config
+            features: self
+                .source_package
+                .features
+                .iter()
+                .map(|(feature, enabled_by_default)| {
+                    let enabled = *enabled_by_default
+                        || (!is_dependency && config.features.contains(&feature.to_string()));
+                    (*feature, enabled)
+                })
+                .collect(),
         }
     }
 }
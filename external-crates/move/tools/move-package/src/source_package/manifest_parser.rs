@@ -4,7 +4,10 @@
 
 use crate::{package_hooks, source_package::parsed_manifest as PM};
 use anyhow::{anyhow, bail, format_err, Context, Result};
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    shared::LintLevel,
+};
 use move_core_types::account_address::{AccountAddress, AccountAddressParseError};
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -24,6 +27,9 @@ const ADDRESSES_NAME: &str = "addresses";
 const DEV_ADDRESSES_NAME: &str = "dev-addresses";
 const DEPENDENCY_NAME: &str = "dependencies";
 const DEV_DEPENDENCY_NAME: &str = "dev-dependencies";
+const GAS_THRESHOLDS_NAME: &str = "gas-thresholds";
+const LINTS_NAME: &str = "lints";
+const FEATURES_NAME: &str = "features";
 
 const KNOWN_NAMES: &[&str] = &[
     PACKAGE_NAME,
@@ -32,6 +38,9 @@ const KNOWN_NAMES: &[&str] = &[
     DEV_ADDRESSES_NAME,
     DEPENDENCY_NAME,
     DEV_DEPENDENCY_NAME,
+    GAS_THRESHOLDS_NAME,
+    LINTS_NAME,
+    FEATURES_NAME,
 ];
 
 const REQUIRED_FIELDS: &[&str] = &[PACKAGE_NAME];
@@ -89,6 +98,18 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 .transpose()
                 .context("Error parsing '[dev-dependencies]' section of manifest")?
                 .unwrap_or_default();
+            let lints = table
+                .remove(LINTS_NAME)
+                .map(parse_lints)
+                .transpose()
+                .context("Error parsing '[lints]' section of manifest")?
+                .unwrap_or_default();
+            let features = table
+                .remove(FEATURES_NAME)
+                .map(parse_features)
+                .transpose()
+                .context("Error parsing '[features]' section of manifest")?
+                .unwrap_or_default();
             Ok(PM::SourceManifest {
                 package,
                 addresses,
@@ -96,6 +117,8 @@ pub fn parse_source_manifest(tval: TV) -> Result<PM::SourceManifest> {
                 build,
                 dependencies,
                 dev_dependencies,
+                lints,
+                features,
             })
         }
         x => {
@@ -217,6 +240,50 @@ pub fn parse_dependencies(tval: TV) -> Result<PM::Dependencies> {
     }
 }
 
+pub fn parse_lints(tval: TV) -> Result<PM::LintLevels> {
+    match tval {
+        TV::Table(table) => {
+            let mut lints = BTreeMap::new();
+            for (lint, level) in table.into_iter() {
+                let level = level
+                    .as_str()
+                    .ok_or_else(|| format_err!("The level of lint '{lint}' must be a string"))?;
+                let level = LintLevel::from_str(level)
+                    .map_err(|err| format_err!("Invalid level for lint '{lint}'. {err}"))?;
+                lints.insert(Symbol::from(lint), level);
+            }
+            Ok(lints)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
+pub fn parse_features(tval: TV) -> Result<PM::Features> {
+    match tval {
+        TV::Table(table) => {
+            let mut features = BTreeMap::new();
+            for (feature, enabled) in table.into_iter() {
+                let enabled = enabled.as_bool().ok_or_else(|| {
+                    format_err!(
+                        "Whether feature '{feature}' is enabled by default must be a boolean"
+                    )
+                })?;
+                features.insert(Symbol::from(feature), enabled);
+            }
+            Ok(features)
+        }
+        x => bail!(
+            "Malformed section in manifest {}. Expected a table, but encountered a {}",
+            x,
+            x.type_str()
+        ),
+    }
+}
+
 pub fn parse_build_info(tval: TV) -> Result<PM::BuildInfo> {
     match tval {
         TV::Table(mut table) => {
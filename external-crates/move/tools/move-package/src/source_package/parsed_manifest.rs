@@ -4,7 +4,10 @@
 
 use anyhow::{bail, Result};
 
-use move_compiler::editions::{Edition, Flavor};
+use move_compiler::{
+    editions::{Edition, Flavor},
+    shared::LintLevel,
+};
 use move_core_types::account_address::AccountAddress;
 use move_symbol_pool::symbol::Symbol;
 use std::{
@@ -23,6 +26,9 @@ pub type DevAddressDeclarations = BTreeMap<NamedAddress, AccountAddress>;
 pub type Version = (u64, u64, u64);
 pub type Dependencies = BTreeMap<PackageName, Dependency>;
 pub type Substitution = BTreeMap<NamedAddress, SubstOrRename>;
+pub type LintLevels = BTreeMap<Symbol, LintLevel>;
+/// The features of a package, and whether each is enabled by default
+pub type Features = BTreeMap<Symbol, bool>;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct SourceManifest {
@@ -32,6 +38,8 @@ pub struct SourceManifest {
     pub build: Option<BuildInfo>,
     pub dependencies: Dependencies,
     pub dev_dependencies: Dependencies,
+    pub lints: LintLevels,
+    pub features: Features,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -45,12 +45,13 @@ pub struct UnitTestingConfig {
     #[clap(name = "list", short = 'l', long = "list")]
     pub list: bool,
 
-    /// Number of threads to use for running tests.
+    /// Number of threads to use for running tests. Tests are run in parallel, each in its own VM.
     #[clap(
         name = "num_threads",
         default_value = "8",
         short = 't',
-        long = "threads"
+        long = "threads",
+        visible_alias = "test-threads"
     )]
     pub num_threads: usize,
 
@@ -212,6 +213,24 @@ impl UnitTestingConfig {
         cost_table: Option<CostTable>,
         writer: W,
     ) -> Result<(W, bool)> {
+        let (writer, ok, _) = self.run_and_report_unit_tests_with_gas_used(
+            test_plan,
+            native_function_table,
+            cost_table,
+            writer,
+        )?;
+        Ok((writer, ok))
+    }
+
+    /// Same as `run_and_report_unit_tests`, but also returns the gas used by each test that
+    /// passed, keyed by its fully qualified name.
+    pub fn run_and_report_unit_tests_with_gas_used<W: Write + Send>(
+        &self,
+        test_plan: TestPlan,
+        native_function_table: Option<NativeFunctionTable>,
+        cost_table: Option<CostTable>,
+        writer: W,
+    ) -> Result<(W, bool, BTreeMap<String, u64>)> {
         let shared_writer = Mutex::new(writer);
 
         if self.list {
@@ -225,7 +244,7 @@ impl UnitTestingConfig {
                     )?;
                 }
             }
-            return Ok((shared_writer.into_inner().unwrap(), true));
+            return Ok((shared_writer.into_inner().unwrap(), true, BTreeMap::new()));
         }
 
         writeln!(shared_writer.lock().unwrap(), "Running Move unit tests")?;
@@ -257,9 +276,10 @@ impl UnitTestingConfig {
             test_results.report_goldens(&shared_writer)?;
         }
 
+        let gas_used = test_results.gas_used();
         let ok = test_results.summarize(&shared_writer)?;
 
         let writer = shared_writer.into_inner().unwrap();
-        Ok((writer, ok))
+        Ok((writer, ok, gas_used))
     }
 }
@@ -443,6 +443,27 @@ impl TestResults {
         Ok(())
     }
 
+    /// Returns the gas used by each test that passed, keyed by its fully qualified
+    /// (`<address>::<module_name>::<fn_name>`) name.
+    pub fn gas_used(&self) -> BTreeMap<String, u64> {
+        self.final_statistics
+            .passed
+            .iter()
+            .flat_map(|(module_id, test_results)| {
+                test_results.iter().map(move |test_result| {
+                    (
+                        format!(
+                            "{}::{}",
+                            format_module_id(module_id),
+                            test_result.function_ident
+                        ),
+                        test_result.instructions_executed,
+                    )
+                })
+            })
+            .collect()
+    }
+
     pub fn report_statistics<W: Write>(
         &self,
         writer: &Mutex<W>,
@@ -174,21 +174,55 @@ impl TestRunner {
         })
     }
 
+    /// Run the tests in parallel, on `num_threads` threads. Every test runs in its own VM and
+    /// session over the starting storage state, so tests cannot observe each other and can run in
+    /// any order. Their results are still written in the order of the test plan.
     pub fn run<W: Write + Send>(self, writer: &Mutex<W>) -> Result<TestResults> {
-        rayon::ThreadPoolBuilder::new()
-            .num_threads(self.num_threads)
-            .build()
-            .unwrap()
-            .install(|| {
-                let final_statistics = self
-                    .tests
-                    .module_tests
-                    .par_iter()
-                    .map(|(_, test_plan)| self.testing_config.exec_module_tests(test_plan, writer))
-                    .reduce(TestStatistics::new, |acc, stats| acc.combine(stats));
-
-                Ok(TestResults::new(final_statistics, self.tests))
-            })
+        let final_statistics = {
+            let tests: Vec<_> = self
+                .tests
+                .module_tests
+                .values()
+                .flat_map(|test_plan| {
+                    test_plan
+                        .tests
+                        .iter()
+                        .map(move |(function_name, test_info)| {
+                            (test_plan, function_name, test_info)
+                        })
+                })
+                .collect();
+            let output = OrderedOutput::new(writer);
+
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(self.num_threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    tests
+                        .par_iter()
+                        .enumerate()
+                        .map_init(
+                            // The model is not thread safe, so every thread builds its own
+                            || self.testing_config.build_stackless_model(),
+                            |stackless_model, (index, (test_plan, function_name, test_info))| {
+                                let mut test_output = TestOutput::new(test_plan);
+                                let stats = self.testing_config.exec_test(
+                                    stackless_model.as_ref(),
+                                    test_plan,
+                                    function_name,
+                                    test_info,
+                                    &mut test_output,
+                                );
+                                output.write(index, test_output.buffer);
+                                stats
+                            },
+                        )
+                        .reduce(TestStatistics::new, |acc, stats| acc.combine(stats))
+                })
+        };
+
+        Ok(TestResults::new(final_statistics, self.tests))
     }
 
     pub fn filter(&mut self, test_name_slice: &str) {
@@ -210,44 +244,73 @@ impl TestRunner {
     }
 }
 
+/// Writes the output of the tests in the order of the test plan, whatever the order in which they
+/// finish: the output of a test is held back until all the tests before it have been written.
+struct OrderedOutput<'a, W> {
+    writer: &'a Mutex<W>,
+    /// The index of the next test to write, and the output of the tests after it that finished
+    pending: Mutex<(usize, BTreeMap<usize, String>)>,
+}
+
+impl<'a, W: Write> OrderedOutput<'a, W> {
+    fn new(writer: &'a Mutex<W>) -> Self {
+        Self {
+            writer,
+            pending: Mutex::new((0, BTreeMap::new())),
+        }
+    }
+
+    fn write(&self, index: usize, output: String) {
+        let mut pending = self.pending.lock().unwrap();
+        let (next, outputs) = &mut *pending;
+        outputs.insert(index, output);
+        let mut writer = self.writer.lock().unwrap();
+        while let Some(output) = outputs.remove(next) {
+            write!(writer, "{}", output).unwrap();
+            *next += 1;
+        }
+    }
+}
+
 // TODO: do not expose this to backend implementations
-struct TestOutput<'a, 'b, W> {
+struct TestOutput<'a> {
     test_plan: &'a ModuleTestPlan,
-    writer: &'b Mutex<W>,
+    buffer: String,
 }
 
-impl<'a, 'b, W: Write> TestOutput<'a, 'b, W> {
-    fn pass(&self, fn_name: &str) {
-        writeln!(
-            self.writer.lock().unwrap(),
-            "[ {}    ] {}::{}",
+impl<'a> TestOutput<'a> {
+    fn new(test_plan: &'a ModuleTestPlan) -> Self {
+        Self {
+            test_plan,
+            buffer: String::new(),
+        }
+    }
+
+    fn pass(&mut self, fn_name: &str) {
+        self.buffer += &format!(
+            "[ {}    ] {}::{}\n",
             "PASS".bold().bright_green(),
             format_module_id(&self.test_plan.module_id),
             fn_name
         )
-        .unwrap()
     }
 
-    fn fail(&self, fn_name: &str) {
-        writeln!(
-            self.writer.lock().unwrap(),
-            "[ {}    ] {}::{}",
+    fn fail(&mut self, fn_name: &str) {
+        self.buffer += &format!(
+            "[ {}    ] {}::{}\n",
             "FAIL".bold().bright_red(),
             format_module_id(&self.test_plan.module_id),
             fn_name,
         )
-        .unwrap()
     }
 
-    fn timeout(&self, fn_name: &str) {
-        writeln!(
-            self.writer.lock().unwrap(),
-            "[ {} ] {}::{}",
+    fn timeout(&mut self, fn_name: &str) {
+        self.buffer += &format!(
+            "[ {} ] {}::{}\n",
             "TIMEOUT".bold().bright_yellow(),
             format_module_id(&self.test_plan.module_id),
             fn_name,
         )
-        .unwrap();
     }
 }
 
@@ -360,11 +423,13 @@ impl SharedTestingConfig {
         )
     }
 
-    fn exec_module_tests_move_vm_and_stackless_vm(
-        &self,
-        test_plan: &ModuleTestPlan,
-        output: &TestOutput<impl Write>,
-    ) -> TestStatistics {
+    /// Build the model used to run the tests with the stackless VM, if they are cross checked
+    /// with it.
+    fn build_stackless_model(&self) -> Option<GlobalEnv> {
+        if !self.check_stackless_vm {
+            return None;
+        }
+
         // TODO: Somehow, paths of some temporary Move interface files are being passed in after those files
         // have been removed. This is a dirty hack to work around the problem while we investigate the root
         // cause.
@@ -375,220 +440,204 @@ impl SharedTestingConfig {
             .cloned()
             .collect::<Vec<_>>();
 
-        let stackless_model = if self.check_stackless_vm {
-            let model = run_model_builder_with_options_and_compilation_flags(
-                vec![PackagePaths {
-                    name: None,
-                    paths: filtered_sources,
-                    named_address_map: self.named_address_values.clone(),
-                }],
-                vec![],
-                ModelBuilderOptions::default(),
-                Flags::testing(),
-                Some(WarningFilters::unused_warnings_filter_for_test()),
-            )
-            .unwrap_or_else(|e| panic!("Unable to build stackless bytecode: {}", e));
+        let model = run_model_builder_with_options_and_compilation_flags(
+            vec![PackagePaths {
+                name: None,
+                paths: filtered_sources,
+                named_address_map: self.named_address_values.clone(),
+            }],
+            vec![],
+            ModelBuilderOptions::default(),
+            Flags::testing(),
+            Some(WarningFilters::unused_warnings_filter_for_test()),
+        )
+        .unwrap_or_else(|e| panic!("Unable to build stackless bytecode: {}", e));
 
-            if model.has_errors() {
-                let mut stderr = StandardStream::stderr(ColorChoice::Always);
-                model.report_diag(&mut stderr, Severity::Error);
-                panic!("Move model has errors");
-            }
+        if model.has_errors() {
+            let mut stderr = StandardStream::stderr(ColorChoice::Always);
+            model.report_diag(&mut stderr, Severity::Error);
+            panic!("Move model has errors");
+        }
 
-            Some(model)
-        } else {
-            None
-        };
+        Some(model)
+    }
 
+    /// Run a single test, with the Move VM and, if given a model, the stackless VM.
+    fn exec_test(
+        &self,
+        stackless_model: Option<&GlobalEnv>,
+        test_plan: &ModuleTestPlan,
+        function_name: &str,
+        test_info: &TestCase,
+        output: &mut TestOutput,
+    ) -> TestStatistics {
         let mut stats = TestStatistics::new();
 
-        for (function_name, test_info) in &test_plan.tests {
-            let (cs_result, ext_result, exec_result, test_run_info) =
-                self.execute_via_move_vm(test_plan, function_name, test_info);
+        let (cs_result, ext_result, exec_result, test_run_info) =
+            self.execute_via_move_vm(test_plan, function_name, test_info);
 
-            if self.record_writeset {
-                stats.test_output(
-                    function_name.to_string(),
+        if self.record_writeset {
+            stats.test_output(
+                function_name.to_string(),
+                test_plan,
+                format!("{:?}", cs_result),
+            );
+        }
+
+        if let Some(stackless_model) = stackless_model {
+            let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) =
+                self.execute_via_stackless_vm(stackless_model, test_plan, function_name, test_info);
+            let move_vm_result = adapt_move_vm_result(exec_result.clone());
+            let move_vm_change_set =
+                adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
+            if stackless_vm_result != move_vm_result
+                || stackless_vm_change_set != move_vm_change_set
+            {
+                output.fail(function_name);
+                stats.test_failure(
+                    TestFailure::new(
+                        FailureReason::mismatch(
+                            move_vm_result,
+                            move_vm_change_set,
+                            stackless_vm_result,
+                            stackless_vm_change_set,
+                        ),
+                        test_run_info,
+                        None,
+                        None,
+                    ),
                     test_plan,
-                    format!("{:?}", cs_result),
                 );
+                return stats;
             }
-
-            if self.check_stackless_vm {
-                let (stackless_vm_change_set, stackless_vm_result, _, prop_check_result) = self
-                    .execute_via_stackless_vm(
-                        stackless_model.as_ref().unwrap(),
-                        test_plan,
-                        function_name,
-                        test_info,
-                    );
-                let move_vm_result = adapt_move_vm_result(exec_result.clone());
-                let move_vm_change_set =
-                    adapt_move_vm_change_set(cs_result.clone(), &self.starting_storage_state);
-                if stackless_vm_result != move_vm_result
-                    || stackless_vm_change_set != move_vm_change_set
-                {
-                    output.fail(function_name);
-                    stats.test_failure(
-                        TestFailure::new(
-                            FailureReason::mismatch(
-                                move_vm_result,
-                                move_vm_change_set,
-                                stackless_vm_result,
-                                stackless_vm_change_set,
-                            ),
-                            test_run_info,
-                            None,
-                            None,
-                        ),
-                        test_plan,
-                    );
-                    continue;
-                }
-                if let Some(prop_failure) = prop_check_result {
-                    output.fail(function_name);
-                    stats.test_failure(
-                        TestFailure::new(
-                            FailureReason::property(prop_failure),
-                            test_run_info,
-                            None,
-                            None,
-                        ),
-                        test_plan,
-                    );
-                    continue;
-                }
+            if let Some(prop_failure) = prop_check_result {
+                output.fail(function_name);
+                stats.test_failure(
+                    TestFailure::new(
+                        FailureReason::property(prop_failure),
+                        test_run_info,
+                        None,
+                        None,
+                    ),
+                    test_plan,
+                );
+                return stats;
             }
+        }
 
-            let save_session_state = || {
-                if self.save_storage_state_on_failure {
-                    cs_result.ok().and_then(|changeset| {
-                        ext_result.ok().and_then(|extensions| {
-                            print_resources_and_extensions(
-                                &changeset,
-                                extensions,
-                                &self.starting_storage_state,
-                            )
-                            .ok()
-                        })
+        let save_session_state = || {
+            if self.save_storage_state_on_failure {
+                cs_result.ok().and_then(|changeset| {
+                    ext_result.ok().and_then(|extensions| {
+                        print_resources_and_extensions(
+                            &changeset,
+                            extensions,
+                            &self.starting_storage_state,
+                        )
+                        .ok()
                     })
-                } else {
-                    None
-                }
-            };
-            match exec_result {
-                Err(err) => {
-                    let actual_err =
-                        MoveError(err.major_status(), err.sub_status(), err.location().clone());
-                    assert!(err.major_status() != StatusCode::EXECUTED);
-                    match test_info.expected_failure.as_ref() {
-                        Some(ExpectedFailure::Expected) => {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        Some(ExpectedFailure::ExpectedWithError(expected_err))
-                            if expected_err == &actual_err =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
-                            if actual_err.0 == StatusCode::ABORTED
-                                && actual_err.1.is_some()
-                                && actual_err.1.unwrap() == *code =>
-                        {
-                            output.pass(function_name);
-                            stats.test_success(test_run_info, test_plan);
-                        }
-                        // incorrect cases
-                        Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_error(expected_err.clone(), actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::wrong_abort_deprecated(
-                                        *expected_code,
-                                        actual_err,
-                                    ),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        None if err.major_status() == StatusCode::OUT_OF_GAS => {
-                            // Ran out of ticks, report a test timeout and log a test failure
-                            output.timeout(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::timeout(),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
-                        None => {
-                            output.fail(function_name);
-                            stats.test_failure(
-                                TestFailure::new(
-                                    FailureReason::unexpected_error(actual_err),
-                                    test_run_info,
-                                    Some(err),
-                                    save_session_state(),
-                                ),
-                                test_plan,
-                            )
-                        }
+                })
+            } else {
+                None
+            }
+        };
+        match exec_result {
+            Err(err) => {
+                let actual_err =
+                    MoveError(err.major_status(), err.sub_status(), err.location().clone());
+                assert!(err.major_status() != StatusCode::EXECUTED);
+                match test_info.expected_failure.as_ref() {
+                    Some(ExpectedFailure::Expected) => {
+                        output.pass(function_name);
+                        stats.test_success(test_run_info, test_plan);
                     }
-                }
-                Ok(_) => {
-                    // Expected the test to fail, but it executed
-                    if test_info.expected_failure.is_some() {
+                    Some(ExpectedFailure::ExpectedWithError(expected_err))
+                        if expected_err == &actual_err =>
+                    {
+                        output.pass(function_name);
+                        stats.test_success(test_run_info, test_plan);
+                    }
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(code))
+                        if actual_err.0 == StatusCode::ABORTED
+                            && actual_err.1.is_some()
+                            && actual_err.1.unwrap() == *code =>
+                    {
+                        output.pass(function_name);
+                        stats.test_success(test_run_info, test_plan);
+                    }
+                    // incorrect cases
+                    Some(ExpectedFailure::ExpectedWithError(expected_err)) => {
                         output.fail(function_name);
                         stats.test_failure(
                             TestFailure::new(
-                                FailureReason::no_error(),
+                                FailureReason::wrong_error(expected_err.clone(), actual_err),
                                 test_run_info,
-                                None,
+                                Some(err),
                                 save_session_state(),
                             ),
                             test_plan,
                         )
-                    } else {
-                        // Expected the test to execute fully and it did
-                        output.pass(function_name);
-                        stats.test_success(test_run_info, test_plan);
                     }
+                    Some(ExpectedFailure::ExpectedWithCodeDEPRECATED(expected_code)) => {
+                        output.fail(function_name);
+                        stats.test_failure(
+                            TestFailure::new(
+                                FailureReason::wrong_abort_deprecated(*expected_code, actual_err),
+                                test_run_info,
+                                Some(err),
+                                save_session_state(),
+                            ),
+                            test_plan,
+                        )
+                    }
+                    None if err.major_status() == StatusCode::OUT_OF_GAS => {
+                        // Ran out of ticks, report a test timeout and log a test failure
+                        output.timeout(function_name);
+                        stats.test_failure(
+                            TestFailure::new(
+                                FailureReason::timeout(),
+                                test_run_info,
+                                Some(err),
+                                save_session_state(),
+                            ),
+                            test_plan,
+                        )
+                    }
+                    None => {
+                        output.fail(function_name);
+                        stats.test_failure(
+                            TestFailure::new(
+                                FailureReason::unexpected_error(actual_err),
+                                test_run_info,
+                                Some(err),
+                                save_session_state(),
+                            ),
+                            test_plan,
+                        )
+                    }
+                }
+            }
+            Ok(_) => {
+                // Expected the test to fail, but it executed
+                if test_info.expected_failure.is_some() {
+                    output.fail(function_name);
+                    stats.test_failure(
+                        TestFailure::new(
+                            FailureReason::no_error(),
+                            test_run_info,
+                            None,
+                            save_session_state(),
+                        ),
+                        test_plan,
+                    )
+                } else {
+                    // Expected the test to execute fully and it did
+                    output.pass(function_name);
+                    stats.test_success(test_run_info, test_plan);
                 }
             }
         }
 
         stats
     }
-
-    // TODO: comparison of results via different backends
-
-    fn exec_module_tests(
-        &self,
-        test_plan: &ModuleTestPlan,
-        writer: &Mutex<impl Write>,
-    ) -> TestStatistics {
-        let output = TestOutput { test_plan, writer };
-
-        self.exec_module_tests_move_vm_and_stackless_vm(test_plan, &output)
-    }
 }
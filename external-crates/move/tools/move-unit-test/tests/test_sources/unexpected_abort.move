@@ -39,6 +39,7 @@ module M {
         abort_in_native()
     }
 
+    #[test_only]
     fun abort_in_native() {
         std::string::internal_sub_string_for_testing(&vector[0], 1, 0);
     }
@@ -160,6 +160,12 @@ pub struct Parameters {
     /// The maximum number of concurrent requests for messages accepted from an un-trusted entity
     #[serde(default = "Parameters::default_max_concurrent_requests")]
     pub max_concurrent_requests: usize,
+    /// When set, the primary starts in amnesia recovery mode and does not vote for any header
+    /// until it has synced certificates past the current round of the network. This must be set
+    /// when restarting a validator whose consensus state was lost or restored from a backup, as
+    /// it no longer knows which headers it already voted for.
+    #[serde(default = "Parameters::default_amnesia_recovery")]
+    pub amnesia_recovery: bool,
     /// Properties for the prometheus metrics
     #[serde(default = "PrometheusMetricsParameters::default")]
     pub prometheus_metrics: PrometheusMetricsParameters,
@@ -211,6 +217,10 @@ impl Parameters {
     fn default_max_concurrent_requests() -> usize {
         500_000
     }
+
+    fn default_amnesia_recovery() -> bool {
+        false
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -307,6 +317,7 @@ impl Default for Parameters {
             batch_size: Parameters::default_batch_size(),
             max_batch_delay: Parameters::default_max_batch_delay(),
             max_concurrent_requests: Parameters::default_max_concurrent_requests(),
+            amnesia_recovery: Parameters::default_amnesia_recovery(),
             prometheus_metrics: PrometheusMetricsParameters::default(),
             network_admin_server: NetworkAdminServerParameters::default(),
             anemo: AnemoParameters::default(),
@@ -354,6 +365,7 @@ impl Parameters {
             "Max concurrent requests set to {}",
             self.max_concurrent_requests
         );
+        info!("Amnesia recovery mode set to {}", self.amnesia_recovery);
         info!(
             "Prometheus metrics server will run on {}",
             self.prometheus_metrics.socket_addr
@@ -9,6 +9,7 @@ use thiserror::Error;
 
 pub mod execution_state;
 pub mod metrics;
+pub mod parameters;
 pub mod primary_node;
 pub mod worker_node;
 
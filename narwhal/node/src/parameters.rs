@@ -0,0 +1,157 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use config::Parameters;
+use prometheus::{register_int_gauge_vec_with_registry, Registry};
+use std::time::Duration;
+use sui_protocol_config::ProtocolConfig;
+use tracing::info;
+
+/// Where the active value of a consensus parameter comes from.
+const SOURCE_PROTOCOL_CONFIG: &str = "protocol_config";
+const SOURCE_LOCAL: &str = "local";
+
+/// A consensus parameter that can be overridden through the protocol config, with its active
+/// value and whether that value comes from the protocol config.
+struct ActiveParameter {
+    name: &'static str,
+    value: u64,
+    from_protocol_config: bool,
+}
+
+/// Returns the parameters to run consensus with for the epoch of `protocol_config`: the local
+/// `parameters`, with the values that the protocol config sets taking precedence. Called every
+/// time the primary or the workers start, so changes to the protocol config take effect at the
+/// next epoch boundary.
+pub fn epoch_parameters(parameters: &Parameters, protocol_config: &ProtocolConfig) -> Parameters {
+    let mut parameters = parameters.clone();
+    if let Some(timeout) = protocol_config.consensus_leader_timeout_ms_as_option() {
+        parameters.max_header_delay = Duration::from_millis(timeout);
+        // The minimum delay can not be longer than the maximum one.
+        parameters.min_header_delay = parameters.min_header_delay.min(parameters.max_header_delay);
+    }
+    if let Some(num_of_batches) = protocol_config.consensus_max_block_num_of_batches_as_option() {
+        parameters.max_header_num_of_batches = num_of_batches as usize;
+    }
+    if let Some(delay) = protocol_config.consensus_propagation_delay_ms_as_option() {
+        parameters.max_batch_delay = Duration::from_millis(delay);
+    }
+    parameters
+}
+
+/// Reports the active values of the parameters used by the primary in `registry`.
+pub(crate) fn register_primary_parameters(
+    parameters: &Parameters,
+    protocol_config: &ProtocolConfig,
+    registry: &Registry,
+) {
+    register_parameters(
+        &[
+            ActiveParameter {
+                name: "leader_timeout_ms",
+                value: parameters.max_header_delay.as_millis() as u64,
+                from_protocol_config: protocol_config
+                    .consensus_leader_timeout_ms_as_option()
+                    .is_some(),
+            },
+            ActiveParameter {
+                name: "max_block_num_of_batches",
+                value: parameters.max_header_num_of_batches as u64,
+                from_protocol_config: protocol_config
+                    .consensus_max_block_num_of_batches_as_option()
+                    .is_some(),
+            },
+        ],
+        registry,
+    );
+}
+
+/// Reports the active values of the parameters used by the workers in `registry`.
+pub(crate) fn register_worker_parameters(
+    parameters: &Parameters,
+    protocol_config: &ProtocolConfig,
+    registry: &Registry,
+) {
+    register_parameters(
+        &[ActiveParameter {
+            name: "propagation_delay_ms",
+            value: parameters.max_batch_delay.as_millis() as u64,
+            from_protocol_config: protocol_config
+                .consensus_propagation_delay_ms_as_option()
+                .is_some(),
+        }],
+        registry,
+    );
+}
+
+fn register_parameters(active: &[ActiveParameter], registry: &Registry) {
+    let gauge = register_int_gauge_vec_with_registry!(
+        "consensus_active_parameters",
+        "The values of the consensus parameters in use for the current epoch, and whether they come from the protocol config or the local configuration",
+        &["parameter", "source"],
+        registry
+    )
+    .unwrap();
+
+    for parameter in active {
+        let source = if parameter.from_protocol_config {
+            SOURCE_PROTOCOL_CONFIG
+        } else {
+            SOURCE_LOCAL
+        };
+        info!(
+            "Consensus parameter {} set to {} from {source}",
+            parameter.name, parameter.value
+        );
+        gauge
+            .with_label_values(&[parameter.name, source])
+            .set(parameter.value as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_protocol_config::{Chain, ProtocolVersion};
+
+    #[test]
+    fn test_local_parameters_without_overrides() {
+        let parameters = Parameters::default();
+        let protocol_config = ProtocolConfig::get_for_version(ProtocolVersion::MAX, Chain::Unknown);
+
+        let active = epoch_parameters(&parameters, &protocol_config);
+        assert_eq!(active.max_header_delay, parameters.max_header_delay);
+        assert_eq!(active.min_header_delay, parameters.min_header_delay);
+        assert_eq!(
+            active.max_header_num_of_batches,
+            parameters.max_header_num_of_batches
+        );
+        assert_eq!(active.max_batch_delay, parameters.max_batch_delay);
+    }
+
+    #[test]
+    fn test_protocol_config_overrides() {
+        let parameters = Parameters::default();
+        let mut protocol_config =
+            ProtocolConfig::get_for_version(ProtocolVersion::MAX, Chain::Unknown);
+        protocol_config.set_consensus_leader_timeout_ms_for_testing(200);
+        protocol_config.set_consensus_max_block_num_of_batches_for_testing(50);
+        protocol_config.set_consensus_propagation_delay_ms_for_testing(20);
+
+        let active = epoch_parameters(&parameters, &protocol_config);
+        assert_eq!(active.max_header_delay, Duration::from_millis(200));
+        // Capped by the leader timeout.
+        assert_eq!(active.min_header_delay, Duration::from_millis(200));
+        assert_eq!(active.max_header_num_of_batches, 50);
+        assert_eq!(active.max_batch_delay, Duration::from_millis(20));
+
+        let registry = Registry::new();
+        register_primary_parameters(&active, &protocol_config, &registry);
+        let families = registry.gather();
+        let metrics = families[0].get_metric();
+        assert_eq!(metrics.len(), 2);
+        assert!(metrics.iter().all(|m| m
+            .get_label()
+            .iter()
+            .any(|l| l.get_value() == SOURCE_PROTOCOL_CONFIG)));
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::metrics::new_registry;
+use crate::parameters::{epoch_parameters, register_primary_parameters};
 use crate::{try_join_all, FuturesUnordered, NodeError};
 use anemo::PeerId;
 use config::{AuthorityIdentifier, Committee, Parameters, WorkerCache};
@@ -83,6 +84,10 @@ impl PrimaryNodeInner {
         // create a new registry
         let registry = new_registry();
 
+        // apply the consensus parameters set by the protocol config for this epoch
+        let parameters = epoch_parameters(&self.parameters, &protocol_config);
+        register_primary_parameters(&parameters, &protocol_config, &registry);
+
         // create the channel to send the shutdown signal
         let mut tx_shutdown = PreSubscribedBroadcastSender::new(NUM_SHUTDOWN_RECEIVERS);
 
@@ -95,7 +100,7 @@ impl PrimaryNodeInner {
             client,
             store,
             protocol_config.clone(),
-            self.parameters.clone(),
+            parameters,
             execution_state,
             &registry,
             &mut tx_shutdown,
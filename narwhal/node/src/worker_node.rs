@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::metrics::new_registry;
+use crate::parameters::{epoch_parameters, register_worker_parameters};
 use crate::{try_join_all, FuturesUnordered, NodeError};
 use anemo::PeerId;
 use arc_swap::{ArcSwap, ArcSwapOption};
@@ -81,6 +82,12 @@ impl WorkerNodeInner {
             (initialise_metrics(&registry), Some(registry))
         };
 
+        // apply the consensus parameters set by the protocol config for this epoch
+        let parameters = epoch_parameters(&self.parameters, &self.protocol_config);
+        if let Some(registry) = registry.as_ref() {
+            register_worker_parameters(&parameters, &self.protocol_config, registry);
+        }
+
         let mut tx_shutdown = PreSubscribedBroadcastSender::new(NUM_SHUTDOWN_RECEIVERS);
 
         let authority = committee
@@ -99,7 +106,7 @@ impl WorkerNodeInner {
             committee.clone(),
             worker_cache.clone(),
             self.protocol_config.clone(),
-            self.parameters.clone(),
+            parameters,
             tx_validator.clone(),
             client.clone(),
             store.batch_store.clone(),
@@ -305,6 +312,10 @@ impl WorkerNodes {
 
         let metrics = initialise_metrics(&registry);
 
+        // report the consensus parameters that the workers run with for this epoch
+        let parameters = epoch_parameters(&self.parameters, &protocol_config);
+        register_worker_parameters(&parameters, &protocol_config, &registry);
+
         self.client.store(Some(Arc::new(client.clone())));
 
         // now clear the previous handles - we want to do that proactively
@@ -0,0 +1,117 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::PrimaryMetrics;
+use parking_lot::Mutex;
+use tracing::{debug, info, warn};
+use types::{error::DagError, error::DagResult, Round};
+
+#[cfg(test)]
+#[path = "tests/amnesia_recovery_tests.rs"]
+mod amnesia_recovery_tests;
+
+/// Guards voting on a primary that may have lost part of its consensus state, e.g. because it was
+/// restored from a backup. Such a primary no longer remembers which headers it voted for after the
+/// backup was taken, so voting again for headers in those rounds could sign two different headers
+/// from the same author and round.
+///
+/// While recovering, the primary refuses to vote until it has learnt the current round of the
+/// network from certificates sent by its peers, and then accepted certificates past that round
+/// into its own store. Any header it voted for before losing its state is at most one round ahead
+/// of a certificate that already existed at that time, so once the primary is synced past the
+/// round the network was at when it came back, no vote it casts can equivocate. The same holds
+/// for the headers it proposes, which are suspended until then as well.
+#[derive(Default)]
+pub struct AmnesiaRecovery {
+    state: Mutex<RecoveryState>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum RecoveryState {
+    /// Not recovering: votes are only subject to the usual equivocation checks.
+    #[default]
+    Done,
+    /// Waiting for a certificate from peers newer than any round known locally at startup.
+    AwaitingPeers { restored_round: Round },
+    /// Syncing certificates until `target_round` has been accepted locally.
+    Syncing { target_round: Round },
+}
+
+impl AmnesiaRecovery {
+    /// Start recovering, given the highest round known locally (certified or committed) at
+    /// startup.
+    pub(crate) fn new(restored_round: Round, metrics: &PrimaryMetrics) -> Self {
+        warn!(
+            "Starting in amnesia recovery mode: votes are suspended until certificates past the \
+            current network round are synced (restored state is at round {restored_round})"
+        );
+        metrics.amnesia_recovery_in_progress.set(1);
+
+        Self {
+            state: Mutex::new(RecoveryState::AwaitingPeers { restored_round }),
+        }
+    }
+
+    /// Checks whether this primary may vote for a header at `round`, given the highest rounds of
+    /// certificates it has received from peers and accepted into its store so far.
+    pub(crate) fn check_vote(
+        &self,
+        round: Round,
+        highest_received_round: Round,
+        highest_processed_round: Round,
+        metrics: &PrimaryMetrics,
+    ) -> DagResult<()> {
+        let mut state = self.state.lock();
+
+        if let RecoveryState::AwaitingPeers { restored_round } = *state {
+            if highest_received_round > restored_round {
+                let target_round = highest_received_round + 1;
+                info!(
+                    "Amnesia recovery: network is at round {highest_received_round}, \
+                    votes resume once round {target_round} is synced"
+                );
+                metrics
+                    .amnesia_recovery_target_round
+                    .set(target_round as i64);
+                *state = RecoveryState::Syncing { target_round };
+            }
+        }
+
+        let target_round = match *state {
+            RecoveryState::Done => return Ok(()),
+            RecoveryState::AwaitingPeers { .. } => None,
+            RecoveryState::Syncing { target_round } => {
+                if highest_processed_round >= target_round {
+                    info!(
+                        "Amnesia recovery complete: synced to round {highest_processed_round}, \
+                        resuming votes"
+                    );
+                    metrics.amnesia_recovery_in_progress.set(0);
+                    *state = RecoveryState::Done;
+                    return Ok(());
+                }
+                Some(target_round)
+            }
+        };
+
+        debug!(
+            "Amnesia recovery: refusing to vote for header at round {round}, \
+            synced to round {highest_processed_round} of {target_round:?}"
+        );
+        metrics.votes_dropped_amnesia_recovery.inc();
+        Err(DagError::VotingSuspendedForRecovery {
+            round,
+            target_round,
+        })
+    }
+
+    /// Whether this primary may propose its own header at `round`. Unlike votes, proposals do not
+    /// move recovery forward: they resume once votes do, and only past the synced round.
+    pub(crate) fn may_propose(&self, round: Round) -> bool {
+        match *self.state.lock() {
+            RecoveryState::Done => true,
+            RecoveryState::AwaitingPeers { .. } => false,
+            RecoveryState::Syncing { target_round } => round > target_round,
+        }
+    }
+}
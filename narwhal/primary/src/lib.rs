@@ -9,6 +9,7 @@
 )]
 
 mod aggregators;
+mod amnesia_recovery;
 mod certificate_fetcher;
 mod certifier;
 mod primary;
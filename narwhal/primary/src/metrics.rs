@@ -307,6 +307,12 @@ pub struct PrimaryMetrics {
     pub certificate_fetcher_total_accept_us: IntCounter,
     /// Number of votes that were requested but not sent due to previously having voted differently
     pub votes_dropped_equivocation_protection: IntCounter,
+    /// Number of votes that were requested but not sent while recovering lost consensus state
+    pub votes_dropped_amnesia_recovery: IntCounter,
+    /// 1 while the primary is in amnesia recovery mode and not voting, 0 otherwise.
+    pub amnesia_recovery_in_progress: IntGauge,
+    /// The round the primary must sync before it votes again, once known, while in amnesia recovery mode.
+    pub amnesia_recovery_target_round: IntGauge,
     /// Number of pending batches in proposer
     pub num_of_pending_batches_in_proposer: IntGauge,
     /// A histogram to track the number of batches included
@@ -462,6 +468,24 @@ impl PrimaryMetrics {
                 registry
             )
             .unwrap(),
+            votes_dropped_amnesia_recovery: register_int_counter_with_registry!(
+                "votes_dropped_amnesia_recovery",
+                "Number of votes that were requested but not sent while recovering lost consensus state",
+                registry
+            )
+            .unwrap(),
+            amnesia_recovery_in_progress: register_int_gauge_with_registry!(
+                "amnesia_recovery_in_progress",
+                "1 while the primary is in amnesia recovery mode and not voting, 0 otherwise",
+                registry
+            )
+            .unwrap(),
+            amnesia_recovery_target_round: register_int_gauge_with_registry!(
+                "amnesia_recovery_target_round",
+                "The round the primary must sync before it votes again, while in amnesia recovery mode",
+                registry
+            )
+            .unwrap(),
             num_of_pending_batches_in_proposer: register_int_gauge_with_registry!(
                 "num_of_pending_batches_in_proposer",
                 "Number of batch digests pending in proposer for next header proposal",
@@ -2,6 +2,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
+    amnesia_recovery::AmnesiaRecovery,
     certificate_fetcher::CertificateFetcher,
     certifier::Certifier,
     metrics::{initialise_metrics, PrimaryMetrics},
@@ -182,6 +183,15 @@ impl Primary {
 
         let signature_service = SignatureService::new(signer);
 
+        let amnesia_recovery = if parameters.amnesia_recovery {
+            let restored_round = certificate_store
+                .highest_round_number()
+                .max(rx_consensus_round_updates.borrow().committed_round);
+            Arc::new(AmnesiaRecovery::new(restored_round, &node_metrics))
+        } else {
+            Arc::new(AmnesiaRecovery::default())
+        };
+
         // Spawn the network receiver listening to messages from the other primaries.
         let address = authority.primary_address();
         let address = address
@@ -197,6 +207,7 @@ impl Primary {
             vote_digest_store,
             rx_narwhal_round_updates,
             parent_digests: Default::default(),
+            amnesia_recovery: amnesia_recovery.clone(),
             metrics: node_metrics.clone(),
         })
         // Allow only one inflight RequestVote RPC at a time per peer.
@@ -467,6 +478,7 @@ impl Primary {
             rx_committed_own_headers,
             node_metrics,
             leader_schedule,
+            amnesia_recovery,
         );
 
         let mut handles = vec![
@@ -535,6 +547,8 @@ struct PrimaryReceiverHandler {
     /// TODO: consider limiting maximum number of digests from one authority, allow timeout
     /// and retries from other authorities.
     parent_digests: Arc<Mutex<BTreeMap<(Round, CertificateDigest), AuthorityIdentifier>>>,
+    /// Suspends voting while recovering consensus state that was lost before startup.
+    amnesia_recovery: Arc<AmnesiaRecovery>,
     metrics: Arc<PrimaryMetrics>,
 }
 
@@ -696,6 +710,15 @@ impl PrimaryReceiverHandler {
             }
         }
 
+        // If this primary lost its consensus state before startup, it may have already voted for a
+        // different header from this author and round. Do not vote until synced past that point.
+        self.amnesia_recovery.check_vote(
+            header.round(),
+            self.synchronizer.highest_received_round(),
+            self.synchronizer.highest_processed_round(),
+            &self.metrics,
+        )?;
+
         // Check if we can vote for this header.
         // Send the vote when:
         // 1. when there is no existing vote for this publicKey & epoch/round
@@ -885,6 +908,7 @@ impl PrimaryToPrimary for PrimaryReceiverHandler {
                         | DagError::HeaderHasDuplicateParentAuthorities(_)
                         | DagError::AlreadyVoted(_, _, _)
                         | DagError::AlreadyVotedNewerHeader(_, _, _)
+                        | DagError::VotingSuspendedForRecovery { .. }
                         | DagError::HeaderRequiresQuorum(_)
                         | DagError::TooOld(_, _, _) => {
                             anemo::types::response::StatusCode::BadRequest
@@ -1,7 +1,7 @@
 // Copyright(C) Facebook, Inc. and its affiliates.
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::metrics::PrimaryMetrics;
+use crate::{amnesia_recovery::AmnesiaRecovery, metrics::PrimaryMetrics};
 use config::{AuthorityIdentifier, Committee, Epoch, WorkerId};
 use consensus::consensus::LeaderSchedule;
 use fastcrypto::hash::Hash as _;
@@ -98,6 +98,8 @@ pub struct Proposer {
     /// The consensus leader schedule to be used in order to resolve the leader needed for the
     /// protocol advancement.
     leader_schedule: LeaderSchedule,
+    /// Suspends proposals while the primary recovers from a loss of its consensus state.
+    amnesia_recovery: Arc<AmnesiaRecovery>,
 }
 
 impl Proposer {
@@ -120,6 +122,7 @@ impl Proposer {
         rx_committed_own_headers: Receiver<(Round, Vec<Round>)>,
         metrics: Arc<PrimaryMetrics>,
         leader_schedule: LeaderSchedule,
+        amnesia_recovery: Arc<AmnesiaRecovery>,
     ) -> JoinHandle<()> {
         let genesis = Certificate::genesis(&committee);
         spawn_logged_monitored_task!(
@@ -148,6 +151,7 @@ impl Proposer {
                     rx_committed_own_headers,
                     metrics,
                     leader_schedule,
+                    amnesia_recovery,
                 }
                 .run()
                 .await;
@@ -439,17 +443,21 @@ impl Proposer {
             // (ii) we have enough digests (header_num_of_batches_threshold) and we are on the happy path (we can vote for
             // the leader or the leader has enough votes to enable a commit).
             // We guarantee that no more than max_header_num_of_batches are included.
+            // No header is proposed while amnesia recovery forbids it at the next round, as it could
+            // equivocate with a header proposed before the primary lost its state.
             let enough_parents = !self.last_parents.is_empty();
+            let may_propose = self.amnesia_recovery.may_propose(self.round + 1);
             let enough_digests = self.digests.len() >= self.header_num_of_batches_threshold;
             let max_delay_timed_out = max_delay_timer.is_elapsed();
             let min_delay_timed_out = min_delay_timer.is_elapsed();
             let should_create_header = (max_delay_timed_out
                 || ((enough_digests || min_delay_timed_out) && advance))
-                && enough_parents;
+                && enough_parents
+                && may_propose;
 
             debug!(
-                "Proposer loop starts: round={} enough_parents={} enough_digests={} advance={} max_delay_timed_out={} min_delay_timed_out={} should_create_header={}", 
-                self.round, enough_parents, enough_digests, advance, max_delay_timed_out, min_delay_timed_out, should_create_header
+                "Proposer loop starts: round={} enough_parents={} enough_digests={} advance={} max_delay_timed_out={} min_delay_timed_out={} may_propose={} should_create_header={}", 
+                self.round, enough_parents, enough_digests, advance, max_delay_timed_out, min_delay_timed_out, may_propose, should_create_header
             );
 
             if should_create_header {
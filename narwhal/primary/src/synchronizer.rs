@@ -547,6 +547,16 @@ impl Synchronizer {
         Self { inner }
     }
 
+    /// Highest round of certificate accepted into the certificate store.
+    pub fn highest_processed_round(&self) -> Round {
+        self.inner.highest_processed_round.load(Ordering::Acquire)
+    }
+
+    /// Highest round of verified certificate that has been received.
+    pub fn highest_received_round(&self) -> Round {
+        self.inner.highest_received_round.load(Ordering::Acquire)
+    }
+
     /// Validates the certificate and accepts it into the DAG, if the certificate can be verified
     /// and has all parents in the certificate store. Otherwise an error is returned.
     /// If the certificate has missing parents and cannot be accepted immediately, the error would
@@ -0,0 +1,71 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+use super::*;
+use prometheus::Registry;
+
+#[test]
+fn disabled_recovery_always_votes() {
+    let metrics = PrimaryMetrics::new(&Registry::new());
+    let recovery = AmnesiaRecovery::default();
+
+    assert!(recovery.check_vote(1, 0, 0, &metrics).is_ok());
+    assert_eq!(metrics.votes_dropped_amnesia_recovery.get(), 0);
+    assert_eq!(metrics.amnesia_recovery_in_progress.get(), 0);
+}
+
+#[test]
+fn recovery_waits_for_peers_then_sync() {
+    let metrics = PrimaryMetrics::new(&Registry::new());
+    let recovery = AmnesiaRecovery::new(10, &metrics);
+    assert_eq!(metrics.amnesia_recovery_in_progress.get(), 1);
+
+    // Nothing newer than the restored state has been received yet.
+    let err = recovery.check_vote(11, 10, 10, &metrics).unwrap_err();
+    assert!(matches!(
+        err,
+        DagError::VotingSuspendedForRecovery {
+            round: 11,
+            target_round: None,
+        }
+    ));
+
+    // Peers are at round 20, so votes resume once round 21 is synced.
+    let err = recovery.check_vote(15, 20, 14, &metrics).unwrap_err();
+    assert!(matches!(
+        err,
+        DagError::VotingSuspendedForRecovery {
+            round: 15,
+            target_round: Some(21),
+        }
+    ));
+    assert_eq!(metrics.amnesia_recovery_target_round.get(), 21);
+
+    // Newer certificates received while syncing do not move the target.
+    assert!(recovery.check_vote(21, 25, 20, &metrics).is_err());
+    assert_eq!(metrics.votes_dropped_amnesia_recovery.get(), 3);
+
+    assert!(recovery.check_vote(22, 25, 21, &metrics).is_ok());
+    assert_eq!(metrics.amnesia_recovery_in_progress.get(), 0);
+
+    // Recovery is over for good.
+    assert!(recovery.check_vote(5, 25, 21, &metrics).is_ok());
+    assert_eq!(metrics.votes_dropped_amnesia_recovery.get(), 3);
+}
+
+#[test]
+fn recovery_suspends_proposals_until_synced() {
+    let metrics = PrimaryMetrics::new(&Registry::new());
+    assert!(AmnesiaRecovery::default().may_propose(1));
+
+    let recovery = AmnesiaRecovery::new(10, &metrics);
+    assert!(!recovery.may_propose(11));
+
+    // Peers are at round 20: only headers past round 21 may be proposed, even before the
+    // primary has synced that far.
+    assert!(recovery.check_vote(15, 20, 14, &metrics).is_err());
+    assert!(!recovery.may_propose(21));
+    assert!(recovery.may_propose(22));
+
+    assert!(recovery.check_vote(22, 25, 21, &metrics).is_ok());
+    assert!(recovery.may_propose(5));
+}
@@ -310,6 +310,7 @@ async fn test_request_vote_has_missing_parents() {
         vote_digest_store: VoteDigestStore::new_for_tests(),
         rx_narwhal_round_updates,
         parent_digests: Default::default(),
+        amnesia_recovery: Default::default(),
         metrics: metrics.clone(),
     };
 
@@ -477,6 +478,7 @@ async fn test_request_vote_accept_missing_parents() {
         vote_digest_store: VoteDigestStore::new_for_tests(),
         rx_narwhal_round_updates,
         parent_digests: Default::default(),
+        amnesia_recovery: Default::default(),
         metrics: metrics.clone(),
     };
 
@@ -632,6 +634,7 @@ async fn test_request_vote_missing_batches() {
         vote_digest_store: VoteDigestStore::new_for_tests(),
         rx_narwhal_round_updates,
         parent_digests: Default::default(),
+        amnesia_recovery: Default::default(),
         metrics: metrics.clone(),
     };
 
@@ -778,6 +781,7 @@ async fn test_request_vote_already_voted() {
         vote_digest_store: VoteDigestStore::new_for_tests(),
         rx_narwhal_round_updates,
         parent_digests: Default::default(),
+        amnesia_recovery: Default::default(),
         metrics: metrics.clone(),
     };
 
@@ -965,6 +969,7 @@ async fn test_fetch_certificates_handler() {
         vote_digest_store: VoteDigestStore::new_for_tests(),
         rx_narwhal_round_updates,
         parent_digests: Default::default(),
+        amnesia_recovery: Default::default(),
         metrics: metrics.clone(),
     };
 
@@ -1136,6 +1141,7 @@ async fn test_request_vote_created_at_in_future() {
         vote_digest_store: VoteDigestStore::new_for_tests(),
         rx_narwhal_round_updates,
         parent_digests: Default::default(),
+        amnesia_recovery: Default::default(),
         metrics: metrics.clone(),
     };
 
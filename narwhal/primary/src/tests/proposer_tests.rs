@@ -44,6 +44,7 @@ async fn propose_empty() {
         rx_committed_own_headers,
         metrics,
         LeaderSchedule::new(committee.clone(), LeaderSwapTable::default()),
+        Arc::new(AmnesiaRecovery::default()),
     );
 
     // Ensure the proposer makes a correct empty header.
@@ -93,6 +94,7 @@ async fn propose_payload_and_repropose_after_n_seconds() {
         rx_committed_own_headers,
         metrics,
         LeaderSchedule::new(committee.clone(), LeaderSwapTable::default()),
+        Arc::new(AmnesiaRecovery::default()),
     );
 
     // Send enough digests for the header payload.
@@ -215,6 +217,7 @@ async fn equivocation_protection() {
         rx_committed_own_headers,
         metrics,
         LeaderSchedule::new(committee.clone(), LeaderSwapTable::default()),
+        Arc::new(AmnesiaRecovery::default()),
     );
 
     // Send enough digests for the header payload.
@@ -287,6 +290,7 @@ async fn equivocation_protection() {
         rx_committed_own_headers,
         metrics,
         LeaderSchedule::new(committee.clone(), LeaderSwapTable::default()),
+        Arc::new(AmnesiaRecovery::default()),
     );
 
     // Send enough digests for the header payload.
@@ -325,3 +329,71 @@ async fn equivocation_protection() {
         assert_eq!(header, new_header);
     }
 }
+
+#[tokio::test]
+async fn no_proposal_during_amnesia_recovery() {
+    let fixture = CommitteeFixture::builder().build();
+    let committee = fixture.committee();
+    let primary = fixture.authorities().next().unwrap();
+    let name = primary.id();
+
+    let mut tx_shutdown = PreSubscribedBroadcastSender::new(NUM_SHUTDOWN_RECEIVERS);
+    let (tx_parents, rx_parents) = test_utils::test_channel!(1);
+    let (_tx_committed_own_headers, rx_committed_own_headers) = test_utils::test_channel!(1);
+    let (_tx_our_digests, rx_our_digests) = test_utils::test_channel!(1);
+    let (tx_headers, mut rx_headers) = test_utils::test_channel!(1);
+    let (tx_narwhal_round_updates, _rx_narwhal_round_updates) = watch::channel(0u64);
+
+    let metrics = Arc::new(PrimaryMetrics::new(&Registry::new()));
+    let amnesia_recovery = Arc::new(AmnesiaRecovery::new(0, &metrics));
+
+    // Spawn the proposer.
+    let _proposer_handle = Proposer::spawn(
+        name,
+        committee.clone(),
+        ProposerStore::new_for_tests(),
+        /* header_num_of_batches_threshold */ 32,
+        /* max_header_num_of_batches */ 100,
+        /* max_header_delay */ Duration::from_millis(20),
+        /* min_header_delay */ Duration::from_millis(20),
+        None,
+        tx_shutdown.subscribe(),
+        /* rx_core */ rx_parents,
+        /* rx_workers */ rx_our_digests,
+        /* tx_core */ tx_headers,
+        tx_narwhal_round_updates,
+        rx_committed_own_headers,
+        metrics.clone(),
+        LeaderSchedule::new(committee.clone(), LeaderSwapTable::default()),
+        amnesia_recovery.clone(),
+    );
+
+    // No header is proposed while the round of the network is unknown, even once timed out.
+    let no_header = Duration::from_millis(500);
+    assert!(tokio::time::timeout(no_header, rx_headers.recv())
+        .await
+        .is_err());
+
+    // Peers are at round 1, so headers may only be proposed past round 2.
+    assert!(amnesia_recovery.check_vote(1, 1, 0, &metrics).is_err());
+
+    let parents: Vec<_> = fixture
+        .headers()
+        .iter()
+        .map(|h| fixture.certificate(h))
+        .collect();
+    tx_parents.send((parents, 1, 0)).await.unwrap();
+    assert!(tokio::time::timeout(no_header, rx_headers.recv())
+        .await
+        .is_err());
+
+    // Once parents of round 2 are received, the header of round 3 is proposed.
+    let parents: Vec<_> = fixture
+        .headers_next_round()
+        .iter()
+        .map(|h| fixture.certificate(h))
+        .collect();
+    tx_parents.send((parents, 2, 0)).await.unwrap();
+    let header = rx_headers.recv().await.unwrap();
+    assert_eq!(header.round(), 3);
+}
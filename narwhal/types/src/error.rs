@@ -79,6 +79,12 @@ pub enum DagError {
     #[error("Already voted a newer header for digest {0} round {1} < {2}")]
     AlreadyVotedNewerHeader(HeaderDigest, Round, Round),
 
+    #[error("Not voting for header at round {round} until round {target_round:?} is synced in amnesia recovery")]
+    VotingSuspendedForRecovery {
+        round: Round,
+        target_round: Option<Round>,
+    },
+
     #[error("Could not form a certificate for header {0}")]
     CouldNotFormCertificate(HeaderDigest),
 
@@ -7,7 +7,7 @@ pub use checked::*;
 mod checked {
     use std::{
         borrow::Borrow,
-        collections::{BTreeMap, HashMap},
+        collections::{BTreeMap, BTreeSet, HashMap},
         sync::Arc,
     };
 
@@ -53,8 +53,8 @@ mod checked {
         error::{ExecutionError, ExecutionErrorKind},
         event::Event,
         execution::{
-            ExecutionResultsV2, ExecutionState, InputObjectMetadata, InputValue, ObjectValue,
-            RawValueType, ResultValue, UsageKind,
+            ExecutionResultsV2, ExecutionState, InputObjectMetadata, InputValue, ObjectLineage,
+            ObjectValue, RawValueType, ResultValue, UsageKind,
         },
         metrics::LimitsMetrics,
         move_package::MovePackage,
@@ -342,6 +342,7 @@ mod checked {
             command_kind: CommandKind<'_>,
             arg: Argument,
         ) -> Result<V, CommandArgumentError> {
+            let shared_object_deletion = self.protocol_config.shared_object_deletion();
             let is_borrowed = self.arg_is_borrowed(&arg);
             let (input_metadata_opt, val_opt) = self.borrow_mut(arg, UsageKind::ByValue)?;
             let is_copyable = if let Some(val) = val_opt {
@@ -363,15 +364,25 @@ mod checked {
             {
                 return Err(CommandArgumentError::InvalidGasCoinUsage);
             }
-            // Immutable objects and shared objects cannot be taken by value
-            if matches!(
-                input_metadata_opt,
+            // Immutable objects cannot be taken by value. Shared objects can only be taken by value
+            // by Move calls, if they were not passed as read-only, and they must be deleted or
+            // re-shared by the end of the transaction (checked in `finish`).
+            match input_metadata_opt {
                 Some(InputObjectMetadata::InputObject {
-                    owner: Owner::Immutable | Owner::Shared { .. },
+                    owner: Owner::Immutable,
                     ..
-                })
-            ) {
-                return Err(CommandArgumentError::InvalidObjectByValue);
+                }) => return Err(CommandArgumentError::InvalidObjectByValue),
+                Some(InputObjectMetadata::InputObject {
+                    owner: Owner::Shared { .. },
+                    is_mutable_input,
+                    ..
+                }) if !shared_object_deletion
+                    || !*is_mutable_input
+                    || !matches!(command_kind, CommandKind::MoveCall { .. }) =>
+                {
+                    return Err(CommandArgumentError::InvalidObjectByValue)
+                }
+                _ => (),
             }
             let val = if is_copyable {
                 val_opt.as_ref().unwrap().clone()
@@ -561,6 +572,51 @@ mod checked {
             Ok(())
         }
 
+        /// Check the results of the last command that are not used by any later command, as told
+        /// by `is_used` from their secondary index. Those that cannot be dropped would fail the
+        /// transaction once all commands are executed, so the transaction fails right away instead.
+        pub fn check_unused_results(
+            &self,
+            is_used: impl Fn(u16) -> bool,
+        ) -> Result<(), ExecutionError> {
+            let Some(command_results) = self.results.last() else {
+                return Ok(());
+            };
+            let result_idx = (self.results.len() - 1) as u16;
+            for (j, result_value) in command_results.iter().enumerate() {
+                let secondary_idx = j as u16;
+                if is_used(secondary_idx) {
+                    continue;
+                }
+                let msg = match &result_value.value {
+                    None
+                    | Some(Value::Raw(RawValueType::Any, _))
+                    | Some(Value::Receiving(_, _, _)) => continue,
+                    Some(Value::Raw(RawValueType::Loaded { abilities, .. }, _))
+                        if abilities.has_drop() =>
+                    {
+                        continue
+                    }
+                    Some(Value::Object(_)) => {
+                        "The object is not used by any later command, so it cannot be transferred \
+                        or consumed"
+                    }
+                    Some(Value::Raw(RawValueType::Loaded { .. }, _)) => {
+                        "The value is not used by any later command, and its type does not have \
+                        drop"
+                    }
+                };
+                return Err(ExecutionError::new_with_source(
+                    ExecutionErrorKind::UnusedValueWithoutDrop {
+                        result_idx,
+                        secondary_idx,
+                    },
+                    msg,
+                ));
+            }
+            Ok(())
+        }
+
         /// Determine the object changes and collect all user events
         pub fn finish<Mode: ExecutionMode>(self) -> Result<ExecutionResults, ExecutionError> {
             let Self {
@@ -582,6 +638,7 @@ mod checked {
             let gas_id_opt = gas.object_metadata.as_ref().map(|info| info.id());
             let mut loaded_runtime_objects = BTreeMap::new();
             let mut additional_writes = BTreeMap::new();
+            let mut by_value_shared_objects = BTreeMap::new();
             for input in inputs.into_iter().chain(std::iter::once(gas)) {
                 let InputValue {
                     object_metadata: Some(InputObjectMetadata::InputObject {
@@ -602,13 +659,15 @@ mod checked {
                 );
                 if let Some(Value::Object(object_value)) = value {
                     add_additional_write(&mut additional_writes, owner, object_value)?;
-                } else {
-                    // The object has been taken by value.
-                    if owner.is_shared() {
-                        // TODO: This would be a case of wrapping shared object, once we support
-                        // shared object deletion. We should report error below.
-                        unreachable!("Passing shared object by value should not be allowed yet");
-                    }
+                } else if owner.is_shared() {
+                    // The shared object has been taken by value, it must be deleted or re-shared,
+                    // which is checked once all writes are known.
+                    assert_invariant!(
+                        protocol_config.shared_object_deletion(),
+                        "Shared objects can only be taken by value if shared object deletion is \
+                        enabled"
+                    );
+                    by_value_shared_objects.insert(id, owner);
                 }
             }
             // check for unused values
@@ -672,7 +731,13 @@ mod checked {
                 refund_max_gas_budget(&mut additional_writes, gas_charger, gas_id)?;
             }
 
+            let record_object_lineage = protocol_config.record_object_lineage();
             let object_runtime: ObjectRuntime = native_extensions.remove();
+            let wrapped_in_loaded_objects = if record_object_lineage {
+                object_runtime.wrapped_in_loaded_objects().clone()
+            } else {
+                BTreeMap::new()
+            };
             let RuntimeResults {
                 writes,
                 user_events: remaining_events,
@@ -688,6 +753,7 @@ mod checked {
             loaded_runtime_objects.extend(loaded_child_objects);
 
             let mut written_objects = BTreeMap::new();
+            let mut wrapped_in_written_objects = BTreeMap::new();
             for package in new_packages {
                 let package_obj = Object::new_from_package(package, tx_digest);
                 let id = package_obj.id();
@@ -701,6 +767,16 @@ mod checked {
                     has_public_transfer,
                     bytes,
                 } = additional_write;
+                if record_object_lineage {
+                    record_wrapped_in_written_object(
+                        vm,
+                        &linkage_view,
+                        &mut wrapped_in_written_objects,
+                        id,
+                        &type_,
+                        &bytes,
+                    )?;
+                }
                 // safe given the invariant that the runtime correctly propagates has_public_transfer
                 let move_object = unsafe {
                     create_written_object(
@@ -734,6 +810,16 @@ mod checked {
                 let Some(bytes) = value.simple_serialize(&layout) else {
                     invariant_violation!("Failed to deserialize already serialized Move value");
                 };
+                if record_object_lineage {
+                    record_wrapped_in_written_object(
+                        vm,
+                        &linkage_view,
+                        &mut wrapped_in_written_objects,
+                        id,
+                        &ty,
+                        &bytes,
+                    )?;
+                }
                 // safe because has_public_transfer has been determined by the abilities
                 let move_object = unsafe {
                     create_written_object(
@@ -764,15 +850,39 @@ mod checked {
                 })
                 .collect();
 
+            let modified_objects: BTreeSet<_> = loaded_runtime_objects
+                .into_iter()
+                .filter_map(|(id, loaded)| loaded.is_modified.then_some(id))
+                .collect();
+            let created_object_ids: BTreeSet<_> =
+                created_object_ids.into_iter().map(|(id, _)| id).collect();
+            let deleted_object_ids: BTreeSet<_> =
+                deleted_object_ids.into_iter().map(|(id, _)| id).collect();
+            check_shared_object_rules(
+                &by_value_shared_objects,
+                &mut written_objects,
+                &deleted_object_ids,
+            )?;
+            let object_lineage = if record_object_lineage {
+                object_lineage(
+                    &written_objects,
+                    &modified_objects,
+                    &created_object_ids,
+                    &deleted_object_ids,
+                    &wrapped_in_loaded_objects,
+                    &wrapped_in_written_objects,
+                )
+            } else {
+                BTreeMap::new()
+            };
+
             Ok(ExecutionResults::V2(ExecutionResultsV2 {
                 written_objects,
-                modified_objects: loaded_runtime_objects
-                    .into_iter()
-                    .filter_map(|(id, loaded)| loaded.is_modified.then_some(id))
-                    .collect(),
-                created_object_ids: created_object_ids.into_iter().map(|(id, _)| id).collect(),
-                deleted_object_ids: deleted_object_ids.into_iter().map(|(id, _)| id).collect(),
+                modified_objects,
+                created_object_ids,
+                deleted_object_ids,
                 user_events,
+                object_lineage,
             }))
         }
 
@@ -1135,6 +1245,10 @@ mod checked {
                 // protected by transaction input checker
                 invariant_violation!("ObjectOwner objects cannot be input")
             }
+            Owner::ConsensusV2 { .. } => {
+                // protected by transaction input checker
+                invariant_violation!("Consensus-owned objects cannot be input yet")
+            }
         };
         let owner = obj.owner;
         let version = obj.version();
@@ -1282,6 +1396,88 @@ mod checked {
         Ok(())
     }
 
+    /// Shared objects taken by value must be deleted or re-shared by the end of the transaction:
+    /// they cannot be wrapped, frozen, or transferred. Re-shared objects keep the version they were
+    /// first shared at.
+    fn check_shared_object_rules(
+        by_value_shared_objects: &BTreeMap<ObjectID, Owner>,
+        written_objects: &mut BTreeMap<ObjectID, Object>,
+        deleted_object_ids: &BTreeSet<ObjectID>,
+    ) -> Result<(), ExecutionError> {
+        for (id, input_owner) in by_value_shared_objects {
+            if let Some(obj) = written_objects.get_mut(id) {
+                if !obj.is_shared() {
+                    return Err(ExecutionError::new_with_source(
+                        ExecutionErrorKind::SharedObjectOperationNotAllowed,
+                        format!("Shared object {id} cannot be frozen or transferred"),
+                    ));
+                }
+                obj.owner = *input_owner;
+            } else if !deleted_object_ids.contains(id) {
+                return Err(ExecutionError::new_with_source(
+                    ExecutionErrorKind::SharedObjectOperationNotAllowed,
+                    format!("Shared object {id} cannot be wrapped"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record every object wrapped in the written object `id`, of type `type_` and with contents
+    /// `bytes`, as being wrapped in `id`.
+    fn record_wrapped_in_written_object(
+        vm: &MoveVM,
+        linkage_view: &LinkageView,
+        wrapped_in: &mut BTreeMap<ObjectID, ObjectID>,
+        id: ObjectID,
+        type_: &Type,
+        bytes: &[u8],
+    ) -> Result<(), ExecutionError> {
+        let fully_annotated_layout = vm
+            .get_runtime()
+            .type_to_fully_annotated_layout(type_)
+            .map_err(|e| convert_vm_error(e, vm, linkage_view))?;
+        let contained_uids = match get_all_uids(&fully_annotated_layout, bytes) {
+            Err(e) => invariant_violation!("Unable to retrieve UIDs for object. Got error: {e}"),
+            Ok(uids) => uids,
+        };
+        for contained_uid in contained_uids {
+            if contained_uid != id {
+                wrapped_in.insert(contained_uid, id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Determine the objects wrapped and unwrapped by the transaction. Objects that were modified
+    /// but neither written nor deleted have been wrapped, and their container is the written
+    /// object they are now wrapped in. Objects that were written or deleted without having been
+    /// created or loaded have been unwrapped, and their container is the loaded object they were
+    /// wrapped in at the start of the transaction.
+    fn object_lineage(
+        written_objects: &BTreeMap<ObjectID, Object>,
+        modified_objects: &BTreeSet<ObjectID>,
+        created_object_ids: &BTreeSet<ObjectID>,
+        deleted_object_ids: &BTreeSet<ObjectID>,
+        wrapped_in_loaded_objects: &BTreeMap<ObjectID, ObjectID>,
+        wrapped_in_written_objects: &BTreeMap<ObjectID, ObjectID>,
+    ) -> BTreeMap<ObjectID, ObjectLineage> {
+        let mut lineage = BTreeMap::new();
+        for id in modified_objects {
+            if !written_objects.contains_key(id) && !deleted_object_ids.contains(id) {
+                let container = wrapped_in_written_objects.get(id).copied();
+                lineage.insert(*id, ObjectLineage::Wrapped { container });
+            }
+        }
+        for id in written_objects.keys().chain(deleted_object_ids) {
+            if !created_object_ids.contains(id) && !modified_objects.contains(id) {
+                let container = wrapped_in_loaded_objects.get(id).copied();
+                lineage.insert(*id, ObjectLineage::Unwrapped { container });
+            }
+        }
+        lineage
+    }
+
     /// Generate an MoveObject given an updated/written object
     /// # Safety
     ///
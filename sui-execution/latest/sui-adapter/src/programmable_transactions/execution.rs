@@ -15,9 +15,11 @@ mod checked {
     };
     use move_core_types::{
         account_address::AccountAddress,
+        gas_algebra::InternalGas,
         identifier::IdentStr,
         language_storage::{ModuleId, TypeTag},
         u256::U256,
+        value::{MoveTypeLayout, MoveValue},
     };
     use move_vm_runtime::{
         move_vm::MoveVM,
@@ -40,7 +42,8 @@ mod checked {
         coin::Coin,
         error::{command_argument_error, ExecutionError, ExecutionErrorKind},
         execution::{
-            CommandKind, ExecutionState, ObjectContents, ObjectValue, RawValueType, Value,
+            ChildObjectAccessStats, CommandKind, ExecutionState, ObjectContents, ObjectValue,
+            RawValueType, Value,
         },
         id::{RESOLVED_SUI_ID, UID},
         metrics::LimitsMetrics,
@@ -59,7 +62,7 @@ mod checked {
     };
     use sui_verifier::{
         private_generics::{EVENT_MODULE, PRIVATE_TRANSFER_FUNCTIONS, TRANSFER_MODULE},
-        INIT_FN_NAME,
+        INIT_FN_NAME, TRANSFER_HOOK_FN_NAME,
     };
 
     use crate::adapter::substitute_package_id;
@@ -77,22 +80,35 @@ mod checked {
         let ProgrammableTransaction { inputs, commands } = pt;
         let mut context = ExecutionContext::new(
             protocol_config,
-            metrics,
+            metrics.clone(),
             vm,
             state_view,
             tx_context,
             gas_charger,
             inputs,
         )?;
+        // results that are not used by any later command are checked as soon as they are produced
+        let result_uses =
+            if protocol_config.early_unused_result_check() && !Mode::allow_arbitrary_values() {
+                Some(result_uses(&commands))
+            } else {
+                None
+            };
         // execute commands
         let mut mode_results = Mode::empty_results();
         for (idx, command) in commands.into_iter().enumerate() {
-            if let Err(err) = execute_command::<Mode>(&mut context, &mut mode_results, command) {
+            let mut result = execute_command::<Mode>(&mut context, &mut mode_results, command);
+            if let (Ok(()), Some(uses)) = (&result, &result_uses) {
+                result = context.check_unused_results(|j| uses[idx].is_used(j));
+            }
+            if let Err(err) = result {
                 let object_runtime: &ObjectRuntime = context.object_runtime();
                 // We still need to record the loaded child objects for replay
                 let loaded_runtime_objects = object_runtime.loaded_runtime_objects();
+                let child_object_access = object_runtime.child_object_access_stats();
                 drop(context);
                 state_view.save_loaded_runtime_objects(loaded_runtime_objects);
+                save_child_object_access_stats(state_view, &metrics, child_object_access);
                 return Err(err.with_command_index(idx));
             };
         }
@@ -103,15 +119,79 @@ mod checked {
         // Record the objects loaded at runtime (dynamic fields + received) for
         // storage rebate calculation.
         let loaded_runtime_objects = object_runtime.loaded_runtime_objects();
+        let child_object_access = object_runtime.child_object_access_stats();
 
         // apply changes
         let finished = context.finish::<Mode>();
         // Save loaded objects for debug. We dont want to lose the info
         state_view.save_loaded_runtime_objects(loaded_runtime_objects);
+        save_child_object_access_stats(state_view, &metrics, child_object_access);
         state_view.record_execution_results(finished?);
         Ok(mode_results)
     }
 
+    /// The results of a command that are used by later commands
+    #[derive(Clone, Default)]
+    struct ResultUses {
+        /// Whether the results are used as a whole, with `Argument::Result`
+        whole: bool,
+        /// The secondary indices of the results used with `Argument::NestedResult`
+        nested: BTreeSet<u16>,
+    }
+
+    impl ResultUses {
+        fn is_used(&self, secondary_idx: u16) -> bool {
+            self.whole || self.nested.contains(&secondary_idx)
+        }
+    }
+
+    /// Find which results of each command are used by later commands, before executing them
+    fn result_uses(commands: &[Command]) -> Vec<ResultUses> {
+        let mut uses = vec![ResultUses::default(); commands.len()];
+        for command in commands {
+            let args: Vec<&Argument> = match command {
+                Command::MoveCall(call) => call.arguments.iter().collect(),
+                Command::TransferObjects(objs, addr) => objs.iter().chain([addr]).collect(),
+                Command::SplitCoins(coin, amounts) => [coin].into_iter().chain(amounts).collect(),
+                Command::MergeCoins(target, coins) => [target].into_iter().chain(coins).collect(),
+                Command::MakeMoveVec(_, args) => args.iter().collect(),
+                Command::Publish(_, _) => vec![],
+                Command::Upgrade(_, _, _, ticket) => vec![ticket],
+            };
+            for arg in args {
+                match arg {
+                    Argument::GasCoin | Argument::Input(_) => (),
+                    Argument::Result(i) => {
+                        if let Some(result_uses) = uses.get_mut(*i as usize) {
+                            result_uses.whole = true;
+                        }
+                    }
+                    Argument::NestedResult(i, j) => {
+                        if let Some(result_uses) = uses.get_mut(*i as usize) {
+                            result_uses.nested.insert(*j);
+                        }
+                    }
+                }
+            }
+        }
+        uses
+    }
+
+    /// Report the child objects accessed by the transaction, whether or not it succeeded
+    fn save_child_object_access_stats(
+        state_view: &mut dyn ExecutionState,
+        metrics: &LimitsMetrics,
+        stats: ChildObjectAccessStats,
+    ) {
+        metrics
+            .child_objects_loaded_per_tx
+            .observe(stats.num_loaded as f64);
+        metrics
+            .child_object_bytes_loaded_per_tx
+            .observe(stats.loaded_bytes as f64);
+        state_view.save_child_object_access_stats(stats);
+    }
+
     /// Execute a single command
     fn execute_command<Mode: ExecutionMode>(
         context: &mut ExecutionContext<'_, '_, '_>,
@@ -189,6 +269,12 @@ mod checked {
                     res,
                 )]
             }
+            Command::TransferObjects(objs, addr_arg)
+                if context.protocol_config.transfer_object_vectors() =>
+            {
+                execute_transfer_objects(context, objs, addr_arg)?;
+                vec![]
+            }
             Command::TransferObjects(objs, addr_arg) => {
                 let objs: Vec<ObjectValue> = objs
                     .into_iter()
@@ -199,6 +285,11 @@ mod checked {
                     context.by_value_arg(CommandKind::TransferObjects, objs.len(), addr_arg)?;
                 for obj in objs {
                     obj.ensure_public_transfer_eligible()?;
+                    let obj = if context.protocol_config.transfer_hooks() {
+                        run_transfer_hook(context, obj, addr)?
+                    } else {
+                        obj
+                    };
                     context.transfer_object(obj, addr)?;
                 }
                 vec![]
@@ -322,6 +413,282 @@ mod checked {
         Ok(())
     }
 
+    /// Transfer objects to an address. Each argument is either an object, or a vector of objects
+    /// (e.g. built by `MakeMoveVec`, or returned by a Move call), every element of which is
+    /// transferred.
+    fn execute_transfer_objects(
+        context: &mut ExecutionContext<'_, '_, '_>,
+        obj_args: Vec<Argument>,
+        addr_arg: Argument,
+    ) -> Result<(), ExecutionError> {
+        let num_args = obj_args.len();
+        // (argument index, element index if the argument is a vector, object)
+        let mut objs = vec![];
+        for (idx, arg) in obj_args.into_iter().enumerate() {
+            match context.by_value_arg(CommandKind::TransferObjects, idx, arg)? {
+                Value::Object(obj) => objs.push((idx, None, obj)),
+                value => {
+                    let elems = unpack_object_vector(context, idx, value)?;
+                    objs.extend(
+                        elems
+                            .into_iter()
+                            .enumerate()
+                            .map(|(elem, obj)| (idx, Some(elem), obj)),
+                    );
+                }
+            }
+        }
+
+        let addr: SuiAddress =
+            context.by_value_arg(CommandKind::TransferObjects, num_args, addr_arg)?;
+        for (idx, elem, obj) in objs {
+            match elem {
+                None => obj.ensure_public_transfer_eligible()?,
+                Some(elem) if !obj.has_public_transfer => {
+                    let msg = format!(
+                        "Element {elem} of vector argument {idx} does not have public transfer"
+                    );
+                    return Err(ExecutionError::new_with_source(
+                        ExecutionErrorKind::InvalidTransferObject,
+                        msg,
+                    ));
+                }
+                // Elements of vectors are charged as if transferred with `sui::transfer`
+                Some(_) => {
+                    let cost = context
+                        .protocol_config
+                        .transfer_transfer_internal_cost_base();
+                    context
+                        .gas_charger
+                        .move_gas_status_mut()
+                        .deduct_gas(InternalGas::new(cost))
+                        .map_err(|e| context.convert_vm_error(e.finish(Location::Undefined)))?;
+                }
+            }
+            let obj = if context.protocol_config.transfer_hooks() {
+                run_transfer_hook(context, obj, addr)?
+            } else {
+                obj
+            };
+            context.transfer_object(obj, addr)?;
+        }
+        Ok(())
+    }
+
+    /// Consult the transfer hook for `obj`'s type, if it has one, before it is transferred to
+    /// `recipient`. Types opt in by defining a `transfer_hook` function in the same module:
+    ///
+    /// ```text
+    /// fun transfer_hook(obj: &mut T, recipient: address, ctx: &TxContext): bool
+    /// ```
+    ///
+    /// with the same type parameters as `T`, for generic types. The transfer fails unless the hook
+    /// returns `true`, and any changes the hook makes to the object are kept. The hook is called
+    /// from the package version that introduced the type, so upgrades cannot remove or change it.
+    ///
+    /// Returns the object, as modified by the hook.
+    fn run_transfer_hook(
+        context: &mut ExecutionContext<'_, '_, '_>,
+        mut obj: ObjectValue,
+        recipient: SuiAddress,
+    ) -> Result<ObjectValue, ExecutionError> {
+        let (sidx, type_arguments) = match &obj.type_ {
+            Type::Struct(sidx) => (*sidx, vec![]),
+            Type::StructInstantiation(sidx, targs) => (*sidx, targs.clone()),
+            _ => invariant_violation!("Objects must be structs"),
+        };
+        let Some(s) = context.vm.get_runtime().get_struct_type(sidx) else {
+            invariant_violation!("Loaded struct not found")
+        };
+
+        let original_address = context.set_link_context((*s.defining_id.address()).into())?;
+        let module_id = ModuleId::new(original_address, s.defining_id.name().to_owned());
+        let result = call_transfer_hook(context, &module_id, &mut obj, type_arguments, recipient);
+        context.linkage_view.reset_linkage();
+
+        if !result? {
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::InvalidTransferObject,
+                format!("Transfer to {recipient} rejected by {module_id}::{TRANSFER_HOOK_FN_NAME}"),
+            ));
+        }
+        Ok(obj)
+    }
+
+    /// Call the transfer hook in `module_id` for `obj`, if there is one, updating `obj` with the
+    /// hook's changes. Returns whether the transfer is allowed, which it is for types without a
+    /// hook.
+    fn call_transfer_hook(
+        context: &mut ExecutionContext<'_, '_, '_>,
+        module_id: &ModuleId,
+        obj: &mut ObjectValue,
+        type_arguments: Vec<Type>,
+        recipient: SuiAddress,
+    ) -> Result<bool, ExecutionError> {
+        let no_new_packages = vec![];
+        let data_store = SuiDataStore::new(&context.linkage_view, &no_new_packages);
+        let module = context
+            .vm
+            .get_runtime()
+            .load_module(module_id, &data_store)
+            .map_err(|e| context.convert_vm_error(e))?;
+        let Some((index, fdef)) = module
+            .function_defs
+            .iter()
+            .enumerate()
+            .find(|(_index, fdef)| {
+                module.identifier_at(module.function_handle_at(fdef.function).name)
+                    == TRANSFER_HOOK_FN_NAME
+            })
+        else {
+            return Ok(true);
+        };
+
+        // The module's hook may be for one of its other types.
+        let num_type_params = module
+            .function_handle_at(fdef.function)
+            .type_parameters
+            .len();
+        if num_type_params != type_arguments.len() {
+            return Ok(true);
+        }
+        let signature = context
+            .load_function(module_id, TRANSFER_HOOK_FN_NAME, &type_arguments)
+            .map_err(|e| context.convert_vm_error(e))?;
+        let signature =
+            subst_signature(signature, &type_arguments).map_err(|e| context.convert_vm_error(e))?;
+        let obj_ref = Type::MutableReference(Box::new(obj.type_.clone()));
+        if signature.parameters.first() != Some(&obj_ref) {
+            return Ok(true);
+        }
+
+        // Hooks are checked by the Sui verifier at publish, but modules published before hooks
+        // were enabled may define a `transfer_hook` that is not one, which is skipped.
+        let well_formed = signature.parameters.len() == 3
+            && signature.parameters[1] == Type::Address
+            && is_tx_context(context, &signature.parameters[2])? == TxContextKind::Immutable
+            && signature.return_ == [Type::Bool];
+        if !well_formed {
+            return Ok(true);
+        }
+        assert_invariant!(
+            matches!(obj.contents, ObjectContents::Raw(_)),
+            "Coins do not have transfer hooks"
+        );
+
+        let index = FunctionDefinitionIndex(index as u16);
+        let last_instr: CodeOffset = fdef
+            .code
+            .as_ref()
+            .map(|code| code.code.len() - 1)
+            .unwrap_or(0) as CodeOffset;
+        let mut obj_bytes = vec![];
+        obj.write_bcs_bytes(&mut obj_bytes);
+        let recipient_bytes = bcs::to_bytes(&recipient).unwrap();
+        let SerializedReturnValues {
+            mut mutable_reference_outputs,
+            return_values,
+        } = vm_move_call(
+            context,
+            module_id,
+            TRANSFER_HOOK_FN_NAME,
+            type_arguments,
+            TxContextKind::Immutable,
+            vec![obj_bytes, recipient_bytes],
+        )?;
+        context.take_user_events(module_id, index, last_instr)?;
+
+        let Some((_, obj_bytes, _)) = mutable_reference_outputs.pop() else {
+            invariant_violation!("Missing object in transfer hook reference outputs");
+        };
+        let [(allowed, _)] = &return_values[..] else {
+            invariant_violation!("Transfer hook must return a single value");
+        };
+        let allowed: bool = bcs::from_bytes(allowed).map_err(|e| {
+            ExecutionError::invariant_violation(format!(
+                "Unable to deserialize transfer hook result. {e}"
+            ))
+        })?;
+        obj.contents = ObjectContents::Raw(obj_bytes);
+        Ok(allowed)
+    }
+
+    /// Split a vector of objects into its elements. Errors if `value` is not a vector, or its
+    /// elements are not objects.
+    fn unpack_object_vector(
+        context: &mut ExecutionContext<'_, '_, '_>,
+        idx: usize,
+        value: Value,
+    ) -> Result<Vec<ObjectValue>, ExecutionError> {
+        let Value::Raw(
+            RawValueType::Loaded {
+                ty: Type::Vector(elem_ty),
+                used_in_non_entry_move_call,
+                ..
+            },
+            bytes,
+        ) = value
+        else {
+            return Err(command_argument_error(
+                CommandArgumentError::TypeMismatch,
+                idx,
+            ));
+        };
+
+        let abilities = context
+            .vm
+            .get_runtime()
+            .get_type_abilities(&elem_ty)
+            .map_err(|e| context.convert_vm_error(e))?;
+        if !abilities.has_key() {
+            let msg = format!("Elements of vector argument {idx} are not objects");
+            return Err(ExecutionError::new_with_source(
+                ExecutionErrorKind::command_argument_error(
+                    CommandArgumentError::TypeMismatch,
+                    idx as u16,
+                ),
+                msg,
+            ));
+        }
+
+        let type_tag = context
+            .vm
+            .get_runtime()
+            .get_type_tag(&elem_ty)
+            .map_err(|e| context.convert_vm_error(e))?;
+        let TypeTag::Struct(struct_tag) = type_tag else {
+            invariant_violation!("Struct type make a non struct type tag")
+        };
+        let type_ = MoveObjectType::from(*struct_tag);
+
+        let layout = context
+            .vm
+            .get_runtime()
+            .type_to_type_layout(&elem_ty)
+            .map_err(|e| context.convert_vm_error(e))?;
+        let vec_layout = MoveTypeLayout::Vector(Box::new(layout));
+        let Some(MoveValue::Vector(elems)) =
+            MoveValue::simple_deserialize(&bytes, &vec_layout).ok()
+        else {
+            invariant_violation!("Could not deserialize a vector of objects")
+        };
+
+        elems
+            .into_iter()
+            .map(|elem| {
+                let Some(contents) = elem.simple_serialize() else {
+                    invariant_violation!("Could not serialize an object in a vector")
+                };
+                context.make_object_value(
+                    type_.clone(),
+                    abilities.has_store(),
+                    used_in_non_entry_move_call,
+                    &contents,
+                )
+            })
+            .collect()
+    }
+
     /// Execute a single Move call
     fn execute_move_call<Mode: ExecutionMode>(
         context: &mut ExecutionContext<'_, '_, '_>,
@@ -823,6 +1190,9 @@ mod checked {
             // Run Sui bytecode verifier, which runs some additional checks that assume the Move
             // bytecode verifier has passed.
             sui_verifier::verifier::sui_verify_module_unmetered(module, &BTreeMap::new())?;
+            if context.protocol_config.transfer_hooks() {
+                sui_verifier::transfer_hook_verifier::verify_module(module)?;
+            }
         }
 
         Ok(())
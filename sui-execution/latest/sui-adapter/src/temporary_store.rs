@@ -13,7 +13,9 @@ use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::VersionDigest;
 use sui_types::committee::EpochId;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
-use sui_types::execution::{DynamicallyLoadedObjectMetadata, ExecutionResults};
+use sui_types::execution::{
+    ChildObjectAccessStats, DynamicallyLoadedObjectMetadata, ExecutionResults, ObjectLineage,
+};
 use sui_types::execution_status::ExecutionStatus;
 use sui_types::inner_temporary_store::InnerTemporaryStore;
 use sui_types::storage::{BackingStore, DeleteKindWithOldVersion};
@@ -27,7 +29,7 @@ use sui_types::{
     event::Event,
     fp_bail,
     gas::GasCostSummary,
-    object::Owner,
+    object::{Authenticator, Owner},
     object::{Data, Object},
     storage::{
         BackingPackageStore, ChildObjectResolver, ObjectChange, ParentSync, Storage, WriteKind,
@@ -67,6 +69,13 @@ pub struct TemporaryStore<'backing> {
     /// The set of objects that we may receive during execution. Not guaranteed to receive all, or
     /// any of the objects referenced in this set.
     receiving_objects: Vec<ObjectRef>,
+
+    /// Objects wrapped or unwrapped by the transaction, and the objects they were wrapped in or
+    /// unwrapped from, if the `record_object_lineage` feature is enabled.
+    object_lineage: BTreeMap<ObjectID, ObjectLineage>,
+
+    /// Child objects looked up at runtime, for diagnostics.
+    child_object_access: ChildObjectAccessStats,
 }
 
 impl<'backing> TemporaryStore<'backing> {
@@ -95,6 +104,8 @@ impl<'backing> TemporaryStore<'backing> {
             loaded_runtime_objects: BTreeMap::new(),
             runtime_packages_loaded_from_db: RwLock::new(BTreeMap::new()),
             receiving_objects,
+            object_lineage: BTreeMap::new(),
+            child_object_access: ChildObjectAccessStats::default(),
         }
     }
 
@@ -161,6 +172,8 @@ impl<'backing> TemporaryStore<'backing> {
             loaded_runtime_objects: self.loaded_runtime_objects,
             no_extraneous_module_bytes: self.protocol_config.no_extraneous_module_bytes(),
             runtime_packages_loaded_from_db: self.runtime_packages_loaded_from_db.read().clone(),
+            object_lineage: self.object_lineage,
+            child_object_access: self.child_object_access,
         }
     }
 
@@ -419,6 +432,7 @@ impl<'backing> TemporaryStore<'backing> {
         self.written.clear();
         self.deleted.clear();
         self.events.clear();
+        self.object_lineage.clear();
     }
 
     pub fn log_event(&mut self, event: Event) {
@@ -546,7 +560,11 @@ impl<'backing> TemporaryStore<'backing> {
                 continue;
             }
             match &obj.owner {
-                Owner::AddressOwner(a) => {
+                Owner::AddressOwner(a)
+                | Owner::ConsensusV2 {
+                    authenticator: Authenticator::SingleOwner(a),
+                    ..
+                } => {
                     assert!(sender == a, "Input object not owned by sender");
                     authenticated_objs.insert(*id);
                 }
@@ -588,7 +606,7 @@ impl<'backing> TemporaryStore<'backing> {
                         Owner::ObjectOwner(_) | Owner::AddressOwner(_) => {
                             objs_to_authenticate.push(*id);
                         }
-                        Owner::Shared { .. } => {
+                        Owner::Shared { .. } | Owner::ConsensusV2 { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
                         Owner::Immutable => {
@@ -621,7 +639,7 @@ impl<'backing> TemporaryStore<'backing> {
                         Owner::AddressOwner(_) | Owner::ObjectOwner(_) => {
                             objs_to_authenticate.push(*id);
                         }
-                        Owner::Shared { .. } => {
+                        Owner::Shared { .. } | Owner::ConsensusV2 { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
                         Owner::Immutable => unreachable!("Immutable objects cannot be deleted"),
@@ -1016,6 +1034,7 @@ impl<'backing> Storage for TemporaryStore<'backing> {
         self.written.clear();
         self.deleted.clear();
         self.events.clear();
+        self.object_lineage.clear();
     }
 
     fn read_object(&self, id: &ObjectID) -> Option<&Object> {
@@ -1058,6 +1077,7 @@ impl<'backing> Storage for TemporaryStore<'backing> {
         for event in results.user_events {
             self.events.push(event);
         }
+        self.object_lineage.extend(results.object_lineage);
     }
 
     fn save_loaded_runtime_objects(
@@ -1066,6 +1086,10 @@ impl<'backing> Storage for TemporaryStore<'backing> {
     ) {
         TemporaryStore::save_loaded_runtime_objects(self, loaded_runtime_objects)
     }
+
+    fn save_child_object_access_stats(&mut self, stats: ChildObjectAccessStats) {
+        self.child_object_access = stats;
+    }
 }
 
 impl<'backing> BackingPackageStore for TemporaryStore<'backing> {
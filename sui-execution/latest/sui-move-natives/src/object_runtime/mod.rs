@@ -24,7 +24,7 @@ use sui_types::{
     base_types::{MoveObjectType, ObjectID, SequenceNumber, SuiAddress},
     committee::EpochId,
     error::{ExecutionError, ExecutionErrorKind, VMMemoryLimitExceededSubStatusCode},
-    execution::DynamicallyLoadedObjectMetadata,
+    execution::{ChildObjectAccessStats, DynamicallyLoadedObjectMetadata},
     id::UID,
     metrics::LimitsMetrics,
     object::{MoveObject, Owner},
@@ -182,6 +182,7 @@ impl<'a> ObjectRuntime<'a> {
     ) -> Self {
         let mut input_object_owners = BTreeMap::new();
         let mut root_version = BTreeMap::new();
+        let mut wrapped_in = BTreeMap::new();
         for (id, input_object) in input_objects {
             let InputObject {
                 contained_uids,
@@ -190,6 +191,7 @@ impl<'a> ObjectRuntime<'a> {
             } = input_object;
             input_object_owners.insert(id, owner);
             debug_assert!(contained_uids.contains(&id));
+            record_wrapped_in(&mut wrapped_in, id, &contained_uids);
             for contained_uid in contained_uids {
                 root_version.insert(contained_uid, version);
             }
@@ -198,6 +200,7 @@ impl<'a> ObjectRuntime<'a> {
             child_object_store: ChildObjectStore::new(
                 object_resolver,
                 root_version,
+                wrapped_in,
                 is_metered,
                 LocalProtocolConfig::new(protocol_config),
                 metrics.clone(),
@@ -450,6 +453,29 @@ impl<'a> ObjectRuntime<'a> {
         self.child_object_store.all_active_objects()
     }
 
+    /// For every object wrapped inside an object loaded by this transaction at the beginning of
+    /// the transaction, the ID of the loaded object it was wrapped in.
+    pub fn wrapped_in_loaded_objects(&self) -> &BTreeMap<ObjectID, ObjectID> {
+        self.child_object_store.wrapped_in()
+    }
+
+    /// How many child objects this transaction has looked up in storage so far, and how large the
+    /// ones that existed were.
+    pub fn child_object_access_stats(&self) -> ChildObjectAccessStats {
+        let cached_objects = self.child_object_store.cached_objects();
+        let (num_loaded, loaded_bytes) = cached_objects
+            .values()
+            .flatten()
+            .fold((0, 0), |(num, bytes), obj| {
+                (num + 1, bytes + obj.object_size_for_gas_metering() as u64)
+            });
+        ChildObjectAccessStats {
+            num_accessed: cached_objects.len() as u64,
+            num_loaded,
+            loaded_bytes,
+        }
+    }
+
     pub fn loaded_runtime_objects(&self) -> BTreeMap<ObjectID, DynamicallyLoadedObjectMetadata> {
         self.child_object_store
             .cached_objects()
@@ -628,7 +654,10 @@ fn check_circular_ownership(
     for (id, recipient) in transfers {
         object_owner_map.remove(&id);
         match recipient {
-            Owner::AddressOwner(_) | Owner::Shared { .. } | Owner::Immutable => (),
+            Owner::AddressOwner(_)
+            | Owner::Shared { .. }
+            | Owner::Immutable
+            | Owner::ConsensusV2 { .. } => (),
             Owner::ObjectOwner(new_owner) => {
                 let new_owner: ObjectID = new_owner.into();
                 let mut cur = new_owner;
@@ -651,6 +680,20 @@ fn check_circular_ownership(
     Ok(())
 }
 
+/// Record that every UID in `contained_uids`, other than `container`'s own, is wrapped in
+/// `container`.
+fn record_wrapped_in(
+    wrapped_in: &mut BTreeMap<ObjectID, ObjectID>,
+    container: ObjectID,
+    contained_uids: &BTreeSet<ObjectID>,
+) {
+    for id in contained_uids {
+        if *id != container {
+            wrapped_in.insert(*id, container);
+        }
+    }
+}
+
 // TODO use a custom DeserializerSeed and improve this performance
 /// WARNING! This function assumes that the bcs bytes have already been validated,
 /// and it will give an invariant violation otherwise.
@@ -23,7 +23,7 @@ use sui_types::{
     storage::ChildObjectResolver,
 };
 
-use super::get_all_uids;
+use super::{get_all_uids, record_wrapped_in};
 pub(super) struct ChildObject {
     pub(super) owner: ObjectID,
     pub(super) ty: Type,
@@ -45,6 +45,9 @@ struct Inner<'a> {
     // If it was a child object, it resolves to the root parent's sequence number.
     // Otherwise, it is just the sequence number at the beginning of the transaction.
     root_version: BTreeMap<ObjectID, SequenceNumber>,
+    // For every UID wrapped inside an object loaded in this transaction (input, child or
+    // received), the ID of the loaded object wrapping it at the beginning of the transaction.
+    wrapped_in: BTreeMap<ObjectID, ObjectID>,
     // cached objects from the resolver. An object might be in this map but not in the store
     // if it's existence was queried, but the value was not used.
     cached_objects: BTreeMap<ObjectID, Option<Object>>,
@@ -179,7 +182,10 @@ impl<'a> Inner<'a> {
                             ))
                         }
                     }
-                    Owner::AddressOwner(_) | Owner::Immutable | Owner::Shared { .. } => {
+                    Owner::AddressOwner(_)
+                    | Owner::Immutable
+                    | Owner::Shared { .. }
+                    | Owner::ConsensusV2 { .. } => {
                         return Err(PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(
                             format!("Bad owner for {child}. \
                             Expected an id owner {parent} but found an address, immutable, or shared owner")
@@ -211,8 +217,8 @@ impl<'a> Inner<'a> {
             ) {
                 return Err(PartialVMError::new(StatusCode::MEMORY_LIMIT_EXCEEDED)
                     .with_message(format!(
-                        "Object runtime cached objects limit ({} entries) reached",
-                        lim
+                        "Object runtime cached objects limit ({lim} entries) reached \
+                        while accessing child object {child} of parent {parent}"
                     ))
                     .with_sub_status(
                         VMMemoryLimitExceededSubStatusCode::OBJECT_RUNTIME_CACHE_LIMIT_EXCEEDED
@@ -283,6 +289,7 @@ impl<'a> Inner<'a> {
                 PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
                     .with_message(format!("Failed to find UIDs. ERROR: {e}"))
             })?;
+        record_wrapped_in(&mut self.wrapped_in, child, &contained_uids);
         let parents_root_version = self.root_version.get(&parent).copied();
         if let Some(v) = parents_root_version {
             debug_assert!(contained_uids.contains(&child));
@@ -330,6 +337,7 @@ impl<'a> ChildObjectStore<'a> {
     pub(super) fn new(
         resolver: &'a dyn ChildObjectResolver,
         root_version: BTreeMap<ObjectID, SequenceNumber>,
+        wrapped_in: BTreeMap<ObjectID, ObjectID>,
         is_metered: bool,
         local_config: LocalProtocolConfig,
         metrics: Arc<LimitsMetrics>,
@@ -339,6 +347,7 @@ impl<'a> ChildObjectStore<'a> {
             inner: Inner {
                 resolver,
                 root_version,
+                wrapped_in,
                 cached_objects: BTreeMap::new(),
                 is_metered,
                 local_config,
@@ -380,6 +389,7 @@ impl<'a> ChildObjectStore<'a> {
                                     "Failed to find UIDs for receiving object. ERROR: {e}"
                                 ))
                         })?;
+                    record_wrapped_in(&mut self.inner.wrapped_in, child, &contained_uids);
                     for id in contained_uids {
                         self.inner.root_version.insert(id, child_version);
                     }
@@ -455,8 +465,8 @@ impl<'a> ChildObjectStore<'a> {
                 ) {
                     return Err(PartialVMError::new(StatusCode::MEMORY_LIMIT_EXCEEDED)
                         .with_message(format!(
-                            "Object runtime store limit ({} entries) reached",
-                            lim
+                            "Object runtime store limit ({lim} entries) reached \
+                            while accessing child object {child} of parent {parent}"
                         ))
                         .with_sub_status(
                             VMMemoryLimitExceededSubStatusCode::OBJECT_RUNTIME_STORE_LIMIT_EXCEEDED
@@ -501,8 +511,8 @@ impl<'a> ChildObjectStore<'a> {
         ) {
             return Err(PartialVMError::new(StatusCode::MEMORY_LIMIT_EXCEEDED)
                 .with_message(format!(
-                    "Object runtime store limit ({} entries) reached",
-                    lim
+                    "Object runtime store limit ({lim} entries) reached \
+                    while adding child object {child} to parent {parent}"
                 ))
                 .with_sub_status(
                     VMMemoryLimitExceededSubStatusCode::OBJECT_RUNTIME_STORE_LIMIT_EXCEEDED as u64,
@@ -559,6 +569,10 @@ impl<'a> ChildObjectStore<'a> {
         &self.inner.cached_objects
     }
 
+    pub(super) fn wrapped_in(&self) -> &BTreeMap<ObjectID, ObjectID> {
+        &self.inner.wrapped_in
+    }
+
     // retrieve the `Op` effects for the child objects
     pub(super) fn take_effects(&mut self) -> BTreeMap<ObjectID, ChildObjectEffect> {
         std::mem::take(&mut self.store)
@@ -219,9 +219,9 @@ pub fn share_object(
     let cost = context.gas_used();
     Ok(match transfer_result {
         // New means the ID was created in this transaction
-        // SameOwner means the object was previously shared and was re-shared; since
-        // shared objects cannot be taken by-value in the adapter, this can only
-        // happen via test_scenario
+        // SameOwner means the object was previously shared and was re-shared; this happens
+        // via test_scenario, or when the adapter lets shared objects be taken by-value (with
+        // shared object deletion enabled)
         TransferResult::New | TransferResult::SameOwner => NativeResult::ok(cost, smallvec![]),
         TransferResult::OwnerChanged => NativeResult::err(cost, E_SHARED_NON_NEW_OBJECT),
     })
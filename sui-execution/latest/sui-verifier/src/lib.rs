@@ -10,11 +10,15 @@ pub mod meter;
 pub mod one_time_witness_verifier;
 pub mod private_generics;
 pub mod struct_with_key_verifier;
+pub mod transfer_hook_verifier;
 
 use move_core_types::{ident_str, identifier::IdentStr, vm_status::StatusCode};
 use sui_types::error::{ExecutionError, ExecutionErrorKind};
 
 pub const INIT_FN_NAME: &IdentStr = ident_str!("init");
+/// Function that a module can define to be consulted when objects of one of its types are
+/// transferred by `TransferObjects`.
+pub const TRANSFER_HOOK_FN_NAME: &IdentStr = ident_str!("transfer_hook");
 pub const TEST_SCENARIO_MODULE_NAME: &str = "test_scenario";
 
 fn verification_failure(error: String) -> ExecutionError {
@@ -21,12 +21,14 @@ pub const EVENT_MODULE: &IdentStr = ident_str!("event");
 pub const EVENT_FUNCTION: &IdentStr = ident_str!("emit");
 pub const PUBLIC_TRANSFER_FUNCTIONS: &[&IdentStr] = &[
     ident_str!("public_transfer"),
+    ident_str!("public_transfer_vec"),
     ident_str!("public_freeze_object"),
     ident_str!("public_share_object"),
     ident_str!("receive"),
 ];
 pub const PRIVATE_TRANSFER_FUNCTIONS: &[&IdentStr] = &[
     ident_str!("transfer"),
+    ident_str!("transfer_vec"),
     ident_str!("freeze_object"),
     ident_str!("share_object"),
 ];
@@ -0,0 +1,136 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{
+    access::ModuleAccess,
+    binary_views::BinaryIndexedView,
+    file_format::{Ability, FunctionDefinition, SignatureToken, Visibility},
+    CompiledModule,
+};
+use move_bytecode_utils::format_signature_token;
+use sui_types::{
+    base_types::{TxContext, TxContextKind, TX_CONTEXT_MODULE_NAME, TX_CONTEXT_STRUCT_NAME},
+    error::ExecutionError,
+    SUI_FRAMEWORK_ADDRESS,
+};
+
+use crate::{verification_failure, TRANSFER_HOOK_FN_NAME};
+
+/// Checks that the transfer hook of a module, if it has one, is well formed
+///
+/// - The existence of the function is optional
+/// - The function must have the name specified by `TRANSFER_HOOK_FN_NAME`
+/// - The function must have `Visibility::Private` and cannot be 'entry'
+/// - The function must have exactly three parameters:
+///   - a mutable reference to a type with `key` declared in the module, instantiated with the
+///   type parameters of the function in order, if it is generic
+///   - the recipient `address`
+///   - the transaction context, as &TxContext
+/// - The function must return a single `bool`, whether the transfer is allowed
+pub fn verify_module(module: &CompiledModule) -> Result<(), ExecutionError> {
+    for fdef in &module.function_defs {
+        let handle = module.function_handle_at(fdef.function);
+        if module.identifier_at(handle.name) == TRANSFER_HOOK_FN_NAME {
+            verify_transfer_hook(module, fdef).map_err(verification_failure)?;
+        }
+    }
+    Ok(())
+}
+
+fn verify_transfer_hook(module: &CompiledModule, fdef: &FunctionDefinition) -> Result<(), String> {
+    let view = &BinaryIndexedView::Module(module);
+
+    if fdef.visibility != Visibility::Private {
+        return Err(format!(
+            "{}. '{}' function must be private",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME
+        ));
+    }
+
+    if fdef.is_entry {
+        return Err(format!(
+            "{}. '{}' cannot be 'entry'",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME
+        ));
+    }
+
+    let fhandle = module.function_handle_at(fdef.function);
+    let parameters = &view.signature_at(fhandle.parameters).0;
+    if parameters.len() != 3 {
+        return Err(format!(
+            "Expected exactly three parameters for {}::{}",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME,
+        ));
+    }
+
+    let num_type_params = fhandle.type_parameters.len();
+    if !is_hooked_object(module, &parameters[0], num_type_params) {
+        return Err(format!(
+            "Expected first parameter for {}::{} to be a mutable reference to a type with 'key' \
+            declared in the module, instantiated with the function's type parameters in order, \
+            but found {}",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME,
+            format_signature_token(view, &parameters[0]),
+        ));
+    }
+
+    if parameters[1] != SignatureToken::Address {
+        return Err(format!(
+            "Expected second parameter for {}::{} to be address, but found {}",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME,
+            format_signature_token(view, &parameters[1]),
+        ));
+    }
+
+    if TxContext::kind(view, &parameters[2]) != TxContextKind::Immutable {
+        return Err(format!(
+            "Expected last parameter for {0}::{1} to be &{2}::{3}::{4}, but found {5}",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME,
+            SUI_FRAMEWORK_ADDRESS,
+            TX_CONTEXT_MODULE_NAME,
+            TX_CONTEXT_STRUCT_NAME,
+            format_signature_token(view, &parameters[2]),
+        ));
+    }
+
+    if view.signature_at(fhandle.return_).0 != [SignatureToken::Bool] {
+        return Err(format!(
+            "{}. '{}' function must return a single bool",
+            module.self_id(),
+            TRANSFER_HOOK_FN_NAME
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether `param` is `&mut T<T0, ..., Tn>`, for a type `T` with `key` declared in `module`, and
+/// `T0, ..., Tn` the `num_type_params` type parameters of the function
+fn is_hooked_object(
+    module: &CompiledModule,
+    param: &SignatureToken,
+    num_type_params: usize,
+) -> bool {
+    let SignatureToken::MutableReference(inner) = param else {
+        return false;
+    };
+    let (sidx, type_arguments) = match &**inner {
+        SignatureToken::Struct(sidx) => (*sidx, &[][..]),
+        SignatureToken::StructInstantiation(sidx, targs) => (*sidx, &targs[..]),
+        _ => return false,
+    };
+    let shandle = module.struct_handle_at(sidx);
+    shandle.module == module.self_handle_idx()
+        && shandle.abilities.has_ability(Ability::Key)
+        && type_arguments.len() == num_type_params
+        && type_arguments
+            .iter()
+            .enumerate()
+            .all(|(i, targ)| *targ == SignatureToken::TypeParameter(i as u16))
+}
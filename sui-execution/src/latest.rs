@@ -187,7 +187,19 @@ impl<'m> verifier::Verifier for Verifier<'m> {
         _protocol_config: &ProtocolConfig,
         modules: &[CompiledModule],
     ) -> SuiResult<()> {
-        run_metered_move_bytecode_verifier(modules, &self.config, &mut self.meter, self.metrics)
+        // Verify modules one at a time, to record the meter units each module used.
+        for module in modules {
+            run_metered_move_bytecode_verifier(
+                std::slice::from_ref(module),
+                &self.config,
+                &mut self.meter,
+                self.metrics,
+            )?;
+            self.metrics
+                .verifier_meter_units_per_module
+                .observe(self.meter.get_usage(Scope::Module) as f64);
+        }
+        Ok(())
     }
 
     fn meter_compiled_modules_with_overrides(
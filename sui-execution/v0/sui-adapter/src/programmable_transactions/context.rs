@@ -1185,6 +1185,10 @@ mod checked {
                 // protected by transaction input checker
                 invariant_violation!("ObjectOwner objects cannot be input")
             }
+            Owner::ConsensusV2 { .. } => {
+                // protected by transaction input checker
+                invariant_violation!("Consensus-owned objects cannot be input yet")
+            }
         };
         let owner = obj.owner;
         let version = obj.version();
@@ -12,7 +12,9 @@ use std::collections::{BTreeMap, HashSet};
 use sui_protocol_config::ProtocolConfig;
 use sui_types::committee::EpochId;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
-use sui_types::execution::{DynamicallyLoadedObjectMetadata, ExecutionResults};
+use sui_types::execution::{
+    ChildObjectAccessStats, DynamicallyLoadedObjectMetadata, ExecutionResults,
+};
 use sui_types::execution_status::ExecutionStatus;
 use sui_types::inner_temporary_store::InnerTemporaryStore;
 use sui_types::storage::{BackingStore, DeleteKindWithOldVersion};
@@ -27,7 +29,7 @@ use sui_types::{
     event::Event,
     fp_bail,
     gas::GasCostSummary,
-    object::Owner,
+    object::{Authenticator, Owner},
     object::{Data, Object},
     storage::{
         BackingPackageStore, ChildObjectResolver, ObjectChange, ParentSync, Storage, WriteKind,
@@ -156,6 +158,8 @@ impl<'backing> TemporaryStore<'backing> {
             loaded_runtime_objects: self.loaded_child_objects,
             no_extraneous_module_bytes: self.protocol_config.no_extraneous_module_bytes(),
             runtime_packages_loaded_from_db: self.runtime_packages_loaded_from_db.read().clone(),
+            object_lineage: BTreeMap::new(),
+            child_object_access: ChildObjectAccessStats::default(),
         }
     }
 
@@ -501,7 +505,11 @@ impl<'backing> TemporaryStore<'backing> {
                 continue;
             }
             match &obj.owner {
-                Owner::AddressOwner(a) => {
+                Owner::AddressOwner(a)
+                | Owner::ConsensusV2 {
+                    authenticator: Authenticator::SingleOwner(a),
+                    ..
+                } => {
                     assert!(sender == a, "Input object not owned by sender");
                     authenticated_objs.insert(*id);
                 }
@@ -541,7 +549,9 @@ impl<'backing> TemporaryStore<'backing> {
                         Owner::ObjectOwner(_parent) => {
                             objs_to_authenticate.push(*id);
                         }
-                        Owner::AddressOwner(_) | Owner::Shared { .. } => {
+                        Owner::AddressOwner(_)
+                        | Owner::Shared { .. }
+                        | Owner::ConsensusV2 { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
                         Owner::Immutable => {
@@ -574,7 +584,9 @@ impl<'backing> TemporaryStore<'backing> {
                         Owner::ObjectOwner(_) => {
                             objs_to_authenticate.push(*id);
                         }
-                        Owner::AddressOwner(_) | Owner::Shared { .. } => {
+                        Owner::AddressOwner(_)
+                        | Owner::Shared { .. }
+                        | Owner::ConsensusV2 { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
                         Owner::Immutable => unreachable!("Immutable objects cannot be deleted"),
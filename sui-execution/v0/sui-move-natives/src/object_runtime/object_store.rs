@@ -116,7 +116,10 @@ impl<'a> Inner<'a> {
                             ))
                         }
                     }
-                    Owner::AddressOwner(_) | Owner::Immutable | Owner::Shared { .. } => {
+                    Owner::AddressOwner(_)
+                    | Owner::Immutable
+                    | Owner::Shared { .. }
+                    | Owner::ConsensusV2 { .. } => {
                         return Err(PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(
                             format!("Bad owner for {child}. \
                             Expected an id owner {parent} but found an address, immutable, or shared owner")
@@ -773,6 +773,7 @@ mod checked {
                 created_object_ids: created_object_ids.into_iter().map(|(id, _)| id).collect(),
                 deleted_object_ids: deleted_object_ids.into_iter().map(|(id, _)| id).collect(),
                 user_events,
+                object_lineage: BTreeMap::new(),
             }))
         }
 
@@ -1135,6 +1136,10 @@ mod checked {
                 // protected by transaction input checker
                 invariant_violation!("ObjectOwner objects cannot be input")
             }
+            Owner::ConsensusV2 { .. } => {
+                // protected by transaction input checker
+                invariant_violation!("Consensus-owned objects cannot be input yet")
+            }
         };
         let owner = obj.owner;
         let version = obj.version();
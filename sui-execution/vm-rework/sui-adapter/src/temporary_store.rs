@@ -13,7 +13,9 @@ use sui_protocol_config::ProtocolConfig;
 use sui_types::base_types::VersionDigest;
 use sui_types::committee::EpochId;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
-use sui_types::execution::{DynamicallyLoadedObjectMetadata, ExecutionResults};
+use sui_types::execution::{
+    ChildObjectAccessStats, DynamicallyLoadedObjectMetadata, ExecutionResults,
+};
 use sui_types::execution_status::ExecutionStatus;
 use sui_types::inner_temporary_store::InnerTemporaryStore;
 use sui_types::storage::{BackingStore, DeleteKindWithOldVersion};
@@ -27,7 +29,7 @@ use sui_types::{
     event::Event,
     fp_bail,
     gas::GasCostSummary,
-    object::Owner,
+    object::{Authenticator, Owner},
     object::{Data, Object},
     storage::{
         BackingPackageStore, ChildObjectResolver, ObjectChange, ParentSync, Storage, WriteKind,
@@ -161,6 +163,8 @@ impl<'backing> TemporaryStore<'backing> {
             loaded_runtime_objects: self.loaded_runtime_objects,
             no_extraneous_module_bytes: self.protocol_config.no_extraneous_module_bytes(),
             runtime_packages_loaded_from_db: self.runtime_packages_loaded_from_db.read().clone(),
+            object_lineage: BTreeMap::new(),
+            child_object_access: ChildObjectAccessStats::default(),
         }
     }
 
@@ -546,7 +550,11 @@ impl<'backing> TemporaryStore<'backing> {
                 continue;
             }
             match &obj.owner {
-                Owner::AddressOwner(a) => {
+                Owner::AddressOwner(a)
+                | Owner::ConsensusV2 {
+                    authenticator: Authenticator::SingleOwner(a),
+                    ..
+                } => {
                     assert!(sender == a, "Input object not owned by sender");
                     authenticated_objs.insert(*id);
                 }
@@ -588,7 +596,7 @@ impl<'backing> TemporaryStore<'backing> {
                         Owner::ObjectOwner(_) | Owner::AddressOwner(_) => {
                             objs_to_authenticate.push(*id);
                         }
-                        Owner::Shared { .. } => {
+                        Owner::Shared { .. } | Owner::ConsensusV2 { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
                         Owner::Immutable => {
@@ -621,7 +629,7 @@ impl<'backing> TemporaryStore<'backing> {
                         Owner::AddressOwner(_) | Owner::ObjectOwner(_) => {
                             objs_to_authenticate.push(*id);
                         }
-                        Owner::Shared { .. } => {
+                        Owner::Shared { .. } | Owner::ConsensusV2 { .. } => {
                             unreachable!("Should already be in authenticated_objs")
                         }
                         Owner::Immutable => unreachable!("Immutable objects cannot be deleted"),
@@ -29,7 +29,7 @@ use std::{
 use sui_types::{
     base_types::{ObjectID, SequenceNumber, SuiAddress},
     id::UID,
-    object::Owner,
+    object::{Authenticator, Owner},
 };
 
 const E_COULD_NOT_GENERATE_EFFECTS: u64 = 0;
@@ -129,7 +129,11 @@ pub fn end_transaction(
             written.push(id);
         }
         match owner {
-            Owner::AddressOwner(a) => {
+            Owner::AddressOwner(a)
+            | Owner::ConsensusV2 {
+                authenticator: Authenticator::SingleOwner(a),
+                ..
+            } => {
                 inventories
                     .address_inventories
                     .entry(a)
@@ -593,9 +597,11 @@ fn transaction_effects(
     let mut frozen = vec![];
     for (id, owner) in transferred {
         match owner {
-            Owner::AddressOwner(a) => {
-                transferred_to_account.push((pack_id(id), Value::address(a.into())))
-            }
+            Owner::AddressOwner(a)
+            | Owner::ConsensusV2 {
+                authenticator: Authenticator::SingleOwner(a),
+                ..
+            } => transferred_to_account.push((pack_id(id), Value::address(a.into()))),
             Owner::ObjectOwner(o) => transferred_to_object.push((pack_id(id), pack_id(o))),
             Owner::Shared { .. } => shared.push(id),
             Owner::Immutable => frozen.push(id),